@@ -4,8 +4,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use mimalloc::MiMalloc;
 use modkit::bootstrap::{
-    AppConfig, dump_effective_modules_config_json, dump_effective_modules_config_yaml,
-    host::init_logging_unified, list_module_names, run_migrate, run_server,
+    AppConfig, ConfigReloadOptions, MigrationCommand, dump_effective_modules_config_json,
+    dump_effective_modules_config_yaml, host::init_logging_unified, list_module_names,
+    run_migrate_command, run_server, run_server_with_reload,
 };
 
 use std::path::PathBuf;
@@ -59,7 +60,29 @@ enum Commands {
     /// Validate configuration and exit
     Check,
     /// Run database migrations and exit (for cloud deployments)
-    Migrate,
+    Migrate {
+        /// Print the SQL that would run instead of executing it
+        #[arg(long, global = true)]
+        dry_run: bool,
+
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Show applied and pending migrations per module
+    Status,
+    /// Revert a module's migrations applied after the given one
+    Down {
+        /// Module whose migrations are reverted
+        #[arg(long)]
+        module: String,
+        /// Last migration to keep applied
+        #[arg(long)]
+        to: String,
+    },
 }
 
 #[tokio::main]
@@ -142,9 +165,31 @@ async fn main() -> Result<()> {
 
     // Dispatch subcommands (default: run)
     match cli.command.as_ref().unwrap_or(&Commands::Run) {
-        Commands::Run => run_server(config).await,
+        // With a config file, edits (or SIGHUP) are applied without a restart
+        Commands::Run => match cli.config {
+            Some(path) => {
+                let reload = ConfigReloadOptions {
+                    verbose: cli.verbose,
+                    ..ConfigReloadOptions::new(path)
+                };
+                run_server_with_reload(config, reload).await
+            }
+            None => run_server(config).await,
+        },
         Commands::Check => check_config(&config),
-        Commands::Migrate => run_migrate(config).await,
+        Commands::Migrate { dry_run, action } => {
+            let command = match action {
+                None if *dry_run => MigrationCommand::Plan,
+                None => MigrationCommand::Apply,
+                Some(MigrateAction::Status) => MigrationCommand::Status,
+                Some(MigrateAction::Down { module, to }) => MigrationCommand::Down {
+                    module: module.clone(),
+                    to: to.clone(),
+                    dry_run: *dry_run,
+                },
+            };
+            run_migrate_command(config, command).await
+        }
     }
 }
 
@@ -28,6 +28,12 @@ use static_authn_plugin as _;
 #[cfg(feature = "static-authz")]
 use static_authz_plugin as _;
 
+#[cfg(feature = "db-credentials")]
+use credential_resolver as _;
+
+#[cfg(feature = "db-credentials")]
+use db_cred_plugin as _;
+
 // === Example Features ===
 
 #[cfg(feature = "users-info-example")]
@@ -54,3 +54,41 @@ fn test_migrate_command_runs_migration_phases() {
         "Should print success message to user"
     );
 }
+
+#[test]
+fn test_migrate_down_help_lists_arguments() {
+    let output = Command::new(hyperspot_binary())
+        .args(["migrate", "down", "--help"])
+        .output()
+        .expect("failed to execute hyperspot-server");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--module"), "down should take a module");
+    assert!(
+        stdout.contains("--to"),
+        "down should take a target migration"
+    );
+    assert!(stdout.contains("--dry-run"), "down should support dry runs");
+}
+
+#[test]
+fn test_migrate_status_does_not_apply() {
+    let output = Command::new(hyperspot_binary())
+        .args(["migrate", "status"])
+        .output()
+        .expect("failed to execute hyperspot-server");
+
+    assert!(
+        output.status.success(),
+        "migrate status should exit successfully. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("[OK] Database migrations completed successfully"),
+        "status must not report applied migrations"
+    );
+}
@@ -1,3 +1,4 @@
+use modkit_db::audit::AuditRecord;
 /// REST DTO for user representation with serde/utoipa
 use time::OffsetDateTime;
 use users_info_sdk::{Address, City, NewAddress, NewCity, NewUser, User, UserFull, UserPatch};
@@ -216,6 +217,41 @@ impl PutAddressReq {
     }
 }
 
+// ==================== Audit DTOs ====================
+
+/// REST DTO for one audit log record
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct AuditRecordDto {
+    pub id: i64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<Uuid>,
+    pub entity: String,
+    pub action: String,
+    pub record_id: String,
+    /// Changed columns: `{column: {"before": .., "after": ..}}`.
+    pub changes: serde_json::Value,
+}
+
+impl From<AuditRecord> for AuditRecordDto {
+    fn from(record: AuditRecord) -> Self {
+        Self {
+            id: record.id,
+            occurred_at: record.occurred_at,
+            actor_id: record.actor_id,
+            tenant_id: record.tenant_id,
+            entity: record.entity,
+            action: record.action,
+            record_id: record.record_id,
+            changes: record.changes,
+        }
+    }
+}
+
 /// Transport-level SSE payload.
 #[derive(Debug, Clone)]
 #[modkit_macros::api_dto(request, response)]
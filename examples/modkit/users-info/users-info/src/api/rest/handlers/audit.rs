@@ -0,0 +1,17 @@
+use super::{ApiResult, AuditRecordDto, Json, JsonPage, SecurityContext, info};
+use crate::module::ConcreteAppServices;
+
+pub(super) async fn list_audit_records(
+    ctx: SecurityContext,
+    svc: std::sync::Arc<ConcreteAppServices>,
+    query: modkit::api::odata::ODataQuery,
+) -> ApiResult<JsonPage<AuditRecordDto>> {
+    info!(
+        user_id = %ctx.subject_id(),
+        "Listing audit records with cursor pagination"
+    );
+
+    let page = svc.audit_log.list_records_page(&ctx, &query).await?;
+
+    Ok(Json(page.map_items(AuditRecordDto::from)))
+}
@@ -3,8 +3,8 @@ use tracing::{field::Empty, info};
 use uuid::Uuid;
 
 use crate::api::rest::dto::{
-    AddressDto, CityDto, CreateCityReq, CreateUserReq, PutAddressReq, UpdateCityReq, UpdateUserReq,
-    UserDto, UserEvent, UserFullDto,
+    AddressDto, AuditRecordDto, CityDto, CreateCityReq, CreateUserReq, PutAddressReq,
+    UpdateCityReq, UpdateUserReq, UserDto, UserEvent, UserFullDto,
 };
 
 use modkit::api::odata::OData;
@@ -17,6 +17,7 @@ use modkit::SseBroadcaster;
 use modkit_security::SecurityContext;
 
 mod addresses;
+mod audit;
 mod cities;
 mod events;
 mod users;
@@ -293,3 +294,22 @@ pub(crate) async fn delete_user_address(
 ) -> ApiResult<impl IntoResponse> {
     addresses::delete_user_address(ctx, svc, user_id).await
 }
+
+// ==================== Audit Handlers ====================
+
+/// List audit records of users with cursor-based pagination
+#[tracing::instrument(
+    skip(svc, query, ctx),
+    fields(
+        limit = query.limit,
+        request_id = Empty,
+        user.id = %ctx.subject_id()
+    )
+)]
+pub(crate) async fn list_audit_records(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<std::sync::Arc<ConcreteAppServices>>,
+    OData(query): OData,
+) -> ApiResult<JsonPage<AuditRecordDto>> {
+    audit::list_audit_records(ctx, svc, query).await
+}
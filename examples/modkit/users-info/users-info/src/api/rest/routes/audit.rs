@@ -0,0 +1,38 @@
+use super::{License, dto, handlers};
+use axum::Router;
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::{OperationBuilder, OperationBuilderODataExt};
+use modkit_db::audit::AuditFilterField;
+
+pub(super) fn register_audit_routes(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // GET /users-info/v1/audit-records - List audit records with cursor-based pagination
+    router = OperationBuilder::get("/users-info/v1/audit-records")
+        .operation_id("users_info.list_audit_records")
+        .summary("List audit records with cursor pagination")
+        .description(
+            "Retrieve the changes made to users, newest first unless $orderby says otherwise",
+        )
+        .tag("audit")
+        .authenticated()
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "limit",
+            false,
+            "Maximum number of audit records to return",
+            "integer",
+        )
+        .query_param("cursor", false, "Cursor for pagination")
+        .handler(handlers::list_audit_records)
+        .json_response_with_schema::<modkit_odata::Page<dto::AuditRecordDto>>(
+            openapi,
+            http::StatusCode::OK,
+            "Paginated list of audit records",
+        )
+        .with_odata_filter::<AuditFilterField>()
+        .with_odata_orderby::<AuditFilterField>()
+        .error_400(openapi)
+        .error_500(openapi)
+        .register(router, openapi);
+
+    router
+}
@@ -6,6 +6,7 @@
 //! - `users` - User endpoints (5: list, get, create, update, delete)
 //! - `cities` - City endpoints (5: list, get, create, update, delete)
 //! - `addresses` - Address endpoints (3: get, upsert, delete)
+//! - `audit` - Audit log endpoint (1: list)
 //! - `events` - SSE event stream (1: user events)
 //!
 //! ## `OData` Integration
@@ -31,6 +32,7 @@ use modkit::api::operation_builder::LicenseFeature;
 use std::sync::Arc;
 
 mod addresses;
+mod audit;
 mod cities;
 mod events;
 mod users;
@@ -55,6 +57,7 @@ pub(crate) fn register_routes(
     router = users::register_user_routes(router, openapi);
     router = cities::register_city_routes(router, openapi);
     router = addresses::register_address_routes(router, openapi);
+    router = audit::register_audit_routes(router, openapi);
 
     router = router.layer(axum::Extension(services));
 
@@ -1,3 +1,4 @@
+use modkit::AuditRetentionConfig;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for the `users_info` module
@@ -12,6 +13,9 @@ pub struct UsersInfoConfig {
     pub audit_base_url: String,
     #[serde(default = "default_notifications_base_url")]
     pub notifications_base_url: String,
+    /// How long audit records of user changes are kept.
+    #[serde(default)]
+    pub audit_retention: AuditRetentionConfig,
 }
 
 impl Default for UsersInfoConfig {
@@ -21,6 +25,7 @@ impl Default for UsersInfoConfig {
             max_page_size: default_max_page_size(),
             audit_base_url: default_audit_base_url(),
             notifications_base_url: default_notifications_base_url(),
+            audit_retention: AuditRetentionConfig::default(),
         }
     }
 }
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use modkit_db::audit::{self, AuditRecord};
+use modkit_db::odata::LimitCfg;
+use modkit_macros::domain_model;
+use tracing::{debug, instrument};
+
+use crate::domain::error::DomainError;
+use crate::domain::service::DbProvider;
+use authz_resolver_sdk::PolicyEnforcer;
+
+use super::{actions, resources};
+use modkit_odata::{ODataQuery, Page};
+use modkit_security::SecurityContext;
+
+/// Read access to the audit log of this module's `#[secure(audit)]` entities.
+///
+/// Records are written by the secure write paths themselves; this service only
+/// lists them, scoped by the PDP like any other tenant-owned resource.
+#[domain_model]
+pub struct AuditLogService {
+    db: Arc<DbProvider>,
+    policy_enforcer: PolicyEnforcer,
+    limit_cfg: LimitCfg,
+}
+
+impl AuditLogService {
+    pub fn new(db: Arc<DbProvider>, policy_enforcer: PolicyEnforcer, limit_cfg: LimitCfg) -> Self {
+        Self {
+            db,
+            policy_enforcer,
+            limit_cfg,
+        }
+    }
+
+    #[instrument(skip(self, ctx, query))]
+    pub async fn list_records_page(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+    ) -> Result<Page<AuditRecord>, DomainError> {
+        debug!("Listing audit records with cursor pagination");
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &resources::AUDIT_RECORD, actions::LIST, None)
+            .await?;
+
+        let page = audit::list_records(&conn, &scope, query, self.limit_cfg)
+            .await
+            .map_err(|e| DomainError::database(e.to_string()))?;
+
+        debug!(
+            "Successfully listed {} audit records in page",
+            page.items.len()
+        );
+        Ok(page)
+    }
+}
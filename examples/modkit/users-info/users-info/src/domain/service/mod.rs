@@ -69,6 +69,7 @@ use modkit_db::DBProvider;
 use modkit_db::odata::LimitCfg;
 
 mod addresses;
+mod audit_log;
 mod cities;
 mod users;
 
@@ -103,6 +104,11 @@ mod users;
 ///   restrictions, e.g., "user A may only have addresses in city 1,
 ///   user B — only in city 2". PDP returns `eq(city_id, <allowed_city>)`
 ///   or `in(city_id, [city1, city2])` predicates.
+///
+/// ## `AUDIT_RECORD`
+/// - **Tenant isolation**: `owner_tenant_id` (maps to the record's `tenant_id`) —
+///   audit records of user changes are readable only within the tenant they touched.
+/// - Read-only: records are written by the secure write paths, never through the API.
 pub(crate) mod resources {
     use super::ResourceType;
     use modkit_security::pep_properties;
@@ -132,6 +138,11 @@ pub(crate) mod resources {
             properties::CITY_ID,
         ],
     };
+
+    pub const AUDIT_RECORD: ResourceType = ResourceType {
+        name: "users_info.audit_record",
+        supported_properties: &[pep_properties::OWNER_TENANT_ID],
+    };
 }
 
 pub(crate) mod actions {
@@ -143,6 +154,7 @@ pub(crate) mod actions {
 }
 
 pub(crate) use addresses::AddressesService;
+pub(crate) use audit_log::AuditLogService;
 pub(crate) use cities::CitiesService;
 pub(crate) use users::UsersService;
 
@@ -197,6 +209,7 @@ where
     pub(crate) users: UsersService<UR, CR, AR>,
     pub(crate) cities: Arc<CitiesService<CR>>,
     pub(crate) addresses: Arc<AddressesService<AR, UR>>,
+    pub(crate) audit_log: Arc<AuditLogService>,
 }
 
 #[cfg(test)]
@@ -248,6 +261,11 @@ where
             Arc::clone(&users_repo),
             enforcer.clone(),
         ));
+        let audit_log = Arc::new(AuditLogService::new(
+            Arc::clone(&db),
+            enforcer.clone(),
+            config.limit_cfg(),
+        ));
 
         Self {
             users: UsersService::new(
@@ -262,6 +280,7 @@ where
             ),
             cities,
             addresses,
+            audit_log,
         }
     }
 }
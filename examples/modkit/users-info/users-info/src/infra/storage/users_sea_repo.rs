@@ -117,10 +117,11 @@ impl UsersRepository for OrmUsersRepository {
         scope: &AccessScope,
         id: Uuid,
     ) -> Result<bool, DomainError> {
+        // Users are audited: the key filter goes after `.secure()` so it is recorded.
         let result = UserEntity::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)))
             .secure()
             .scope_with(scope)
+            .filter(sea_orm::Condition::all().add(Expr::col(Column::Id).eq(id)))
             .exec(conn)
             .await
             .map_err(db_err)?;
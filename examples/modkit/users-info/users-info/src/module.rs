@@ -2,11 +2,15 @@ use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
 use modkit::api::OpenApiRegistry;
-use modkit::{DatabaseCapability, Module, ModuleCtx, RestApiCapability, SseBroadcaster};
+use modkit::{
+    AuditRetention, DatabaseCapability, Module, ModuleCtx, RestApiCapability, Runnable,
+    RunnableCapability, SseBroadcaster,
+};
 use modkit_db::DBProvider;
 use modkit_db::DbError;
 use modkit_http::HttpClient;
 use sea_orm_migration::MigrationTrait;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 use url::Url;
 
@@ -38,7 +42,7 @@ pub(crate) type ConcreteAppServices =
 #[modkit::module(
     name = "users-info",
     deps = ["authz-resolver"],
-    capabilities = [db, rest]
+    capabilities = [db, rest, stateful]
 )]
 pub struct UsersInfo {
     // Keep the domain service behind OnceLock for set-once access.
@@ -46,6 +50,9 @@ pub struct UsersInfo {
     service: OnceLock<Arc<ConcreteAppServices>>,
     // SSE broadcaster for user events
     sse: SseBroadcaster<UserEvent>,
+    // Purges expired audit records of user changes while the module runs
+    audit_retention: OnceLock<Arc<AuditRetention>>,
+    retention_cancel: CancellationToken,
 }
 
 impl Default for UsersInfo {
@@ -53,6 +60,8 @@ impl Default for UsersInfo {
         Self {
             service: OnceLock::new(),
             sse: SseBroadcaster::new(1024),
+            audit_retention: OnceLock::new(),
+            retention_cancel: CancellationToken::new(),
         }
     }
 }
@@ -72,6 +81,14 @@ impl Module for UsersInfo {
         // Acquire DB capability (secure wrapper, no DbHandle exposed to modules)
         let db: Arc<DBProvider<DbError>> = Arc::new(ctx.db_required()?);
 
+        let audit_retention = Arc::new(AuditRetention::new(
+            DBProvider::clone(&db),
+            cfg.audit_retention.clone(),
+        ));
+        self.audit_retention
+            .set(audit_retention)
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
         // Create event publisher adapter that bridges domain events to SSE
         let publisher: Arc<dyn EventPublisher<UserDomainEvent>> =
             Arc::new(SseUserEventPublisher::new(self.sse.clone()));
@@ -146,6 +163,23 @@ impl DatabaseCapability for UsersInfo {
     }
 }
 
+#[async_trait]
+impl RunnableCapability for UsersInfo {
+    async fn start(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+        let retention = self
+            .audit_retention
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Audit retention not initialized"))?;
+        tokio::spawn(Arc::clone(retention).run(self.retention_cancel.clone()));
+        Ok(())
+    }
+
+    async fn stop(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+        self.retention_cancel.cancel();
+        Ok(())
+    }
+}
+
 impl RestApiCapability for UsersInfo {
     fn register_rest(
         &self,
@@ -38,6 +38,8 @@ pub use claims_error::ClaimsError;
 pub use config::{AuthConfig, JwksConfig, PluginConfig, build_auth_dispatcher};
 pub use config_error::ConfigError;
 pub use dispatcher::AuthDispatcher;
+#[cfg(feature = "otel")]
+pub use metrics::OtelMetrics;
 pub use metrics::{AuthEvent, AuthMetricLabels, AuthMetrics, LoggingMetrics, NoOpMetrics};
 pub use plugin_traits::{ClaimsPlugin, IntrospectionProvider, KeyProvider};
 pub use standard_claims::StandardClaim;
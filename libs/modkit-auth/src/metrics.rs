@@ -2,6 +2,8 @@
 ///
 /// This module provides a trait-based approach to metrics that can be
 /// implemented with various backends (Prometheus, `StatsD`, etc.)
+/// With the `otel` feature, [`OtelMetrics`] records into the global
+/// OpenTelemetry meter provider installed by the host.
 /// Auth event types for metrics tracking
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthEvent {
@@ -128,6 +130,72 @@ impl AuthMetrics for LoggingMetrics {
     }
 }
 
+/// OpenTelemetry metrics backend
+///
+/// Each [`AuthEvent`] becomes a counter named after [`AuthEvent::metric_name`];
+/// durations go to the `auth.validation.duration` histogram. `kid` is left out
+/// of the attributes since key rotation would grow series without bound.
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone)]
+pub struct OtelMetrics {
+    meter: opentelemetry::metrics::Meter,
+    duration: opentelemetry::metrics::Histogram<f64>,
+}
+
+#[cfg(feature = "otel")]
+impl OtelMetrics {
+    /// Create the backend on the global meter provider.
+    #[must_use]
+    pub fn new() -> Self {
+        let meter = opentelemetry::global::meter("modkit_auth");
+        let duration = meter
+            .f64_histogram("auth.validation.duration")
+            .with_description("Token validation duration")
+            .with_unit("s")
+            .build();
+        Self { meter, duration }
+    }
+
+    fn attributes(labels: &AuthMetricLabels) -> Vec<opentelemetry::KeyValue> {
+        [
+            ("provider", &labels.provider),
+            ("issuer", &labels.issuer),
+            ("error.type", &labels.error_type),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|v| opentelemetry::KeyValue::new(key, v.clone()))
+        })
+        .collect()
+    }
+}
+
+#[cfg(feature = "otel")]
+impl Default for OtelMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "otel")]
+impl AuthMetrics for OtelMetrics {
+    fn record_event(&self, event: AuthEvent, labels: &AuthMetricLabels) {
+        // The SDK caches instruments by name, so this does not re-register on each call
+        self.meter
+            .u64_counter(event.metric_name())
+            .build()
+            .add(1, &Self::attributes(labels));
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn record_duration(&self, duration_ms: u64, labels: &AuthMetricLabels) {
+        self.duration
+            .record(duration_ms as f64 / 1000.0, &Self::attributes(labels));
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -170,6 +238,23 @@ mod tests {
         metrics.record_duration(100, &labels);
     }
 
+    #[test]
+    #[cfg(feature = "otel")]
+    fn test_otel_metrics_omits_unset_labels() {
+        let labels = AuthMetricLabels::default()
+            .with_provider("keycloak")
+            .with_kid("key-123");
+
+        let attrs = OtelMetrics::attributes(&labels);
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(attrs[0].key.as_str(), "provider");
+
+        // Records against the no-op provider without panicking
+        let metrics = OtelMetrics::new();
+        metrics.record_event(AuthEvent::JwtInvalid, &labels);
+        metrics.record_duration(12, &labels);
+    }
+
     #[test]
     fn test_logging_metrics() {
         let metrics = LoggingMetrics;
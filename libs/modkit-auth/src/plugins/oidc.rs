@@ -47,7 +47,7 @@ impl GenericOidcPlugin {
             .map(|arr| {
                 arr.iter()
                     .filter_map(|v| v.as_str())
-                    .map(ToString::to_string)
+                    .map(str::to_owned)
                     .collect()
             })
             .unwrap_or_default();
@@ -122,7 +122,7 @@ impl ClaimsPlugin for GenericOidcPlugin {
         let jwt_id = raw
             .get(StandardClaim::JTI)
             .and_then(|v| v.as_str())
-            .map(ToString::to_string);
+            .map(str::to_owned);
 
         // 8. Extract tenant_id (required, must be UUID)
         let tenant_id = raw
@@ -171,7 +171,7 @@ pub fn parse_timestamp(
 pub fn extract_string(value: &serde_json::Value, field_name: &str) -> Result<String, ClaimsError> {
     value
         .as_str()
-        .map(ToString::to_string)
+        .map(str::to_owned)
         .ok_or_else(|| ClaimsError::MissingClaim(field_name.to_owned()))
 }
 
@@ -182,7 +182,7 @@ pub fn extract_audiences(value: &serde_json::Value) -> Vec<String> {
         serde_json::Value::String(s) => vec![s.clone()],
         serde_json::Value::Array(arr) => arr
             .iter()
-            .filter_map(|v| v.as_str().map(ToString::to_string))
+            .filter_map(|v| v.as_str().map(str::to_owned))
             .collect(),
         _ => vec![],
     }
@@ -37,8 +37,11 @@
 //! - **Resource**: `resource_col = "column_name"` OR `no_resource`
 //! - **Owner**: `owner_col = "column_name"` OR `no_owner`
 //! - **Type**: `type_col = "column_name"` OR `no_type`
-//! - **Unrestricted**: `unrestricted` (forbids all other attributes)
+//! - **Unrestricted**: `unrestricted` (forbids all scope dimension attributes)
 //! - **Custom PEP property**: `pep_prop(property_name = "column_name")` (repeatable)
+//! - **Soft delete** (optional): `soft_delete_col = "deleted_at"`
+//! - **Optimistic concurrency** (optional): `version_col = "version"`
+//! - **Audit trail** (optional): `audit`
 //!
 //! ## Note on `OData` Macros
 //!
@@ -64,9 +67,16 @@ mod scopable;
 /// - `resource_col = "column_name"` OR `no_resource` - Primary resource ID column
 /// - `owner_col = "column_name"` OR `no_owner` - Owner-based filtering column
 /// - `type_col = "column_name"` OR `no_type` - Type-based filtering column
-/// - `unrestricted` - Mark as global entity (forbids all other attributes)
+/// - `unrestricted` - Mark as global entity (forbids all scope dimension attributes)
 /// - `pep_prop(property_name = "column_name")` - Custom PEP property mapping (repeatable)
 ///
+/// Optional row-lifecycle settings (also allowed with `unrestricted`):
+///
+/// - `soft_delete_col = "column_name"` - Nullable timestamp; deletes set it and selects hide
+///   rows where it is set
+/// - `version_col = "column_name"` - Integer checked and incremented by scoped updates
+/// - `audit` - Record secure inserts, updates and deletes in the module's audit log
+///
 /// The macro auto-generates `resolve_property()` from dimension columns and `pep_prop` entries:
 /// - `tenant_col` → `"owner_tenant_id"`
 /// - `resource_col` → `"id"`
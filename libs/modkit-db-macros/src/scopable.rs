@@ -41,6 +41,11 @@ struct SecureConfig {
     // Unrestricted flag
     unrestricted: Option<Span>,
 
+    // Row lifecycle settings (optional, allowed with `unrestricted`)
+    soft_delete_col: Option<(String, Span)>,
+    version_col: Option<(String, Span)>,
+    audit: Option<Span>,
+
     // Custom PEP property mappings: (property_name, column_name, span)
     pep_props: Vec<(String, String, Span)>,
 }
@@ -63,12 +68,16 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
 
     let entity_ident = syn::Ident::new("Entity", input.ident.span());
 
+    let lifecycle_impl = generate_lifecycle_impl(&config, input.ident.span());
+
     // If unrestricted, generate simple implementation with all None
     if config.unrestricted.is_some() {
         return quote! {
             impl ::modkit_db::secure::ScopableEntity for #entity_ident {
                 const IS_UNRESTRICTED: bool = true;
 
+                #lifecycle_impl
+
                 fn tenant_col() -> ::core::option::Option<Self::Column> {
                     ::core::option::Option::None
                 }
@@ -127,10 +136,36 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
             #type_col_impl
 
             #resolve_property_impl
+
+            #lifecycle_impl
         }
     }
 }
 
+/// Generate `soft_delete_col()` / `version_col()` overrides for the columns that were declared.
+///
+/// Undeclared ones keep the trait's `None` default.
+fn generate_lifecycle_impl(config: &SecureConfig, span: Span) -> TokenStream {
+    let soft_delete = config
+        .soft_delete_col
+        .as_ref()
+        .map(|col| generate_col_impl("soft_delete_col", Some(col), span));
+    let version = config
+        .version_col
+        .as_ref()
+        .map(|col| generate_col_impl("version_col", Some(col), span));
+    let audit = config.audit.map(|_| {
+        quote! {
+            const IS_AUDITED: bool = true;
+        }
+    });
+    quote! {
+        #audit
+        #soft_delete
+        #version
+    }
+}
+
 /// Generate a column method implementation
 fn generate_col_impl(
     method_name: &str,
@@ -338,6 +373,14 @@ fn parse_secure_attrs(input: &DeriveInput) -> SecureConfig {
                 return Ok(());
             }
 
+            if meta.path.is_ident("audit") {
+                if config.audit.is_some() {
+                    abort!(span, "duplicate attribute 'audit'");
+                }
+                config.audit = Some(span);
+                return Ok(());
+            }
+
             if meta.path.is_ident("no_tenant") {
                 if config.unrestricted.is_some() {
                     abort!(span, "Cannot use 'no_tenant' with 'unrestricted'");
@@ -506,6 +549,18 @@ fn parse_key_value_attr(config: &mut SecureConfig, meta: syn::meta::ParseNestedM
             }
             config.owner_col = Some((value, span));
         }
+        "soft_delete_col" => {
+            if config.soft_delete_col.is_some() {
+                abort!(span, "duplicate attribute 'soft_delete_col'");
+            }
+            config.soft_delete_col = Some((value, span));
+        }
+        "version_col" => {
+            if config.version_col.is_some() {
+                abort!(span, "duplicate attribute 'version_col'");
+            }
+            config.version_col = Some((value, span));
+        }
         "type_col" => {
             if config.unrestricted.is_some() {
                 abort!(span, "Cannot use 'type_col' with 'unrestricted'");
@@ -526,7 +581,7 @@ fn parse_key_value_attr(config: &mut SecureConfig, meta: syn::meta::ParseNestedM
                 span,
                 "Unknown attribute '{}'. Valid attributes: tenant_col, no_tenant, \
                  resource_col, no_resource, owner_col, no_owner, type_col, no_type, \
-                 unrestricted, pep_prop",
+                 unrestricted, pep_prop, soft_delete_col, version_col, audit",
                 key
             );
         }
@@ -45,6 +45,7 @@ async fn main() -> anyhow::Result<()> {
         backoff_multiplier: 1.5,
         jitter_pct: 0.2,
         max_attempts: Some(5),
+        ..Default::default()
     };
 
     let start = std::time::Instant::now();
@@ -72,26 +73,23 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    // Demo 4: Show lock file behavior for SQLite
-    println!("\n=== Demo 4: Lock File Information ===");
-    {
-        let _guard = db.lock("sysinfo", "host_update").await?;
-        println!("✓ Acquired lock: sysinfo:host_update");
-
-        // For SQLite, the lock is stored as a file in the cache directory
-        if let Some(cache_dir) = dirs::cache_dir() {
-            let lock_dir = cache_dir.join("hyperspot").join("locks");
-            println!("  Lock files are stored in: {}", lock_dir.display());
-            println!("  Each lock creates a file with PID and timestamp for debugging");
-        }
+    // Demo 4: Fencing tokens from database leases
+    println!("\n=== Demo 4: Fencing Tokens ===");
+    for _ in 0..3 {
+        let guard = db.lock("sysinfo", "host_update").await?;
+        println!(
+            "✓ Acquired lock: sysinfo:host_update (fencing token {:?})",
+            guard.fencing_token()
+        );
+        guard.release().await;
     }
-    println!("✓ Lock released, file cleaned up");
+    println!("✓ Each acquisition received a larger token");
 
     println!("\n=== Demo Complete ===");
     println!("Key features demonstrated:");
     println!("• Module namespacing prevents conflicts between different modules");
     println!("• try_lock provides configurable retry/backoff policies");
-    println!("• File-based locks for SQLite with automatic cleanup");
+    println!("• Database leases with heartbeat renewal and fencing tokens");
     println!("• All locks are automatically released on guard drop");
 
     Ok(())
@@ -3,9 +3,18 @@
 //! Cross-database advisory locking with proper namespacing and configurable
 //! retry/backoff.
 //!
+//! ## Backends
+//! - [`LockBackend::Database`] (default): leases stored in the runtime-owned `modkit_leases`
+//!   table. Leases carry a TTL, are renewed by a heartbeat task while the guard is alive, and
+//!   hand out monotonically increasing fencing tokens ([`DbLockGuard::fencing_token`]). A
+//!   holder that crashes stops renewing, and its lease is taken over once it expires.
+//! - [`LockBackend::File`]: marker files in the local cache directory. Only coordinates
+//!   processes on one host; kept for desktop builds.
+//!
 //! ## Security policy
-//! This crate forbids plain SQL outside migration infrastructure. Therefore, locks
-//! are implemented **purely as file-based locks** (no DB-native advisory locks).
+//! This crate forbids plain SQL outside migration infrastructure. Therefore, no DB-native
+//! advisory locks are used: lease statements are built with `sea-query`, and the lease
+//! table is created by the migration runner.
 //!
 //! Notes:
 //! - Prefer calling `guard.release().await` for deterministic unlock;
@@ -19,6 +28,7 @@
     allow(unused_imports, unused_variables, dead_code, unreachable_code)
 )]
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -26,7 +36,22 @@ use xxhash_rust::xxh3::xxh3_64;
 
 use chrono::SecondsFormat;
 
+use sea_orm::DatabaseConnection;
 use tokio::fs::File;
+use tokio::task::JoinHandle;
+
+use crate::leases::{self, LeaseStore};
+
+/// Storage backend used for advisory locks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockBackend {
+    /// Leases in the shared database; safe across processes and hosts.
+    #[default]
+    Database,
+    /// Marker files on the local filesystem; single host only.
+    File,
+}
 
 // --------------------------- Config ------------------------------------------
 
@@ -45,6 +70,10 @@ pub struct LockConfig {
     pub jitter_pct: f32,
     /// Maximum number of retry attempts (`None` = unlimited).
     pub max_attempts: Option<u32>,
+    /// Lease lifetime for the database backend; an unrenewed lease can be taken over after this.
+    pub lease_ttl: Duration,
+    /// Interval between lease renewals (`None` = a third of `lease_ttl`).
+    pub heartbeat_interval: Option<Duration>,
 }
 
 impl Default for LockConfig {
@@ -56,16 +85,32 @@ impl Default for LockConfig {
             backoff_multiplier: 1.5,
             jitter_pct: 0.2,
             max_attempts: None,
+            lease_ttl: Duration::from_secs(30),
+            heartbeat_interval: None,
         }
     }
 }
 
+impl LockConfig {
+    fn heartbeat(&self) -> Duration {
+        self.heartbeat_interval
+            .unwrap_or(self.lease_ttl / 3)
+            .max(Duration::from_millis(10))
+    }
+}
+
 /* --------------------------- Guard ------------------------------------------- */
 
 #[derive(Debug)]
 enum GuardInner {
     /// File-based fallback (keeps descriptor open until release).
     File { path: PathBuf, file: File },
+    /// Database lease renewed by a background heartbeat.
+    Lease {
+        conn: DatabaseConnection,
+        holder: String,
+        heartbeat: JoinHandle<()>,
+    },
 }
 
 /// Database lock guard that can release lock explicitly via `release()`.
@@ -73,6 +118,7 @@ enum GuardInner {
 #[derive(Debug)]
 pub struct DbLockGuard {
     namespaced_key: String,
+    fencing_token: Option<u64>,
     inner: Option<GuardInner>, // Option to allow moving inner out in Drop
 }
 
@@ -82,10 +128,20 @@ impl DbLockGuard {
         &self.namespaced_key
     }
 
+    /// Fencing token of the lease backing this guard.
+    ///
+    /// Tokens for a given key strictly increase with every acquisition, so downstream
+    /// systems can reject writes carrying a token older than one they have already seen.
+    /// `None` for the file backend.
+    #[must_use]
+    pub fn fencing_token(&self) -> Option<u64> {
+        self.fencing_token
+    }
+
     /// Deterministically release the lock (preferred).
     pub async fn release(mut self) {
         if let Some(inner) = self.inner.take() {
-            unlock_inner(inner).await;
+            unlock_inner(&self.namespaced_key, inner).await;
         }
         // drop self
     }
@@ -97,7 +153,8 @@ impl Drop for DbLockGuard {
         if let Some(inner) = self.inner.take()
             && let Ok(handle) = tokio::runtime::Handle::try_current()
         {
-            handle.spawn(async move { unlock_inner(inner).await });
+            let key = std::mem::take(&mut self.namespaced_key);
+            handle.spawn(async move { unlock_inner(&key, inner).await });
         }
         // else: No runtime or no inner; we cannot perform async cleanup here.
         // The lock may remain held until process exit (DB connection)
@@ -105,13 +162,24 @@ impl Drop for DbLockGuard {
     }
 }
 
-async fn unlock_inner(inner: GuardInner) {
+async fn unlock_inner(key: &str, inner: GuardInner) {
     match inner {
         GuardInner::File { path, file } => {
             // Close file first, then try to remove marker. Ignore errors.
             drop(file);
             _ = tokio::fs::remove_file(&path).await;
         }
+        GuardInner::Lease {
+            conn,
+            holder,
+            heartbeat,
+        } => {
+            heartbeat.abort();
+            if let Err(e) = leases::release(&conn, key, &holder).await {
+                // The lease will expire on its own once the TTL passes.
+                tracing::warn!(lock = %key, error = %e, "failed to release lease");
+            }
+        }
     }
 }
 
@@ -120,12 +188,23 @@ async fn unlock_inner(inner: GuardInner) {
 /// Internal lock manager handling different database backends.
 pub(crate) struct LockManager {
     dsn: String,
+    leases: Option<LeaseStore>,
 }
 
 impl LockManager {
+    /// Lock manager using the file backend.
     #[must_use]
     pub fn new(dsn: String) -> Self {
-        Self { dsn }
+        Self { dsn, leases: None }
+    }
+
+    /// Lock manager using database leases.
+    #[must_use]
+    pub fn with_leases(dsn: String, leases: LeaseStore) -> Self {
+        Self {
+            dsn,
+            leases: Some(leases),
+        }
     }
 
     /// Acquire an advisory lock for `{module}:{key}`.
@@ -137,7 +216,15 @@ impl LockManager {
     /// Returns `DbLockError` if the lock cannot be acquired.
     pub async fn lock(&self, module: &str, key: &str) -> Result<DbLockGuard, DbLockError> {
         let namespaced_key = format!("{module}:{key}");
-        self.lock_file(&namespaced_key).await
+        if self.leases.is_none() {
+            return self.lock_file(&namespaced_key).await;
+        }
+        let guard = self
+            .try_acquire_once(&namespaced_key, &LockConfig::default())
+            .await?;
+        guard.ok_or(DbLockError::AlreadyHeld {
+            lock_name: namespaced_key,
+        })
     }
 
     /// Try to acquire an advisory lock with retry/backoff policy.
@@ -174,7 +261,7 @@ impl LockManager {
                 return Ok(None);
             }
 
-            if let Some(guard) = self.try_acquire_once(&namespaced_key).await? {
+            if let Some(guard) = self.try_acquire_once(&namespaced_key, &config).await? {
                 return Ok(Some(guard));
             }
 
@@ -250,6 +337,7 @@ impl LockManager {
 
         Ok(DbLockGuard {
             namespaced_key: namespaced_key.to_owned(),
+            fencing_token: None,
             inner: Some(GuardInner::File { path, file }),
         })
     }
@@ -289,6 +377,7 @@ impl LockManager {
 
                 Ok(Some(DbLockGuard {
                     namespaced_key: namespaced_key.to_owned(),
+                    fencing_token: None,
                     inner: Some(GuardInner::File { path, file }),
                 }))
             }
@@ -300,8 +389,42 @@ impl LockManager {
     async fn try_acquire_once(
         &self,
         namespaced_key: &str,
+        config: &LockConfig,
     ) -> Result<Option<DbLockGuard>, DbLockError> {
-        self.try_lock_file(namespaced_key).await
+        match &self.leases {
+            Some(store) => Self::try_lease(store, namespaced_key, config).await,
+            None => self.try_lock_file(namespaced_key).await,
+        }
+    }
+
+    // ------------------------ Lease helpers ---------------------
+
+    async fn try_lease(
+        store: &LeaseStore,
+        namespaced_key: &str,
+        config: &LockConfig,
+    ) -> Result<Option<DbLockGuard>, DbLockError> {
+        let Some(lease) = store.try_acquire(namespaced_key, config.lease_ttl).await? else {
+            return Ok(None);
+        };
+
+        let heartbeat = leases::spawn_heartbeat(
+            store.conn().clone(),
+            namespaced_key.to_owned(),
+            lease.holder.clone(),
+            config.lease_ttl,
+            config.heartbeat(),
+        );
+
+        Ok(Some(DbLockGuard {
+            namespaced_key: namespaced_key.to_owned(),
+            fencing_token: Some(lease.fencing_token),
+            inner: Some(GuardInner::Lease {
+                conn: store.conn().clone(),
+                holder: lease.holder,
+                heartbeat,
+            }),
+        }))
     }
 
     /// Generate lock file path for `SQLite` (or when using file-based locks).
@@ -333,6 +456,9 @@ pub enum DbLockError {
 
     #[error("Lock not found: {lock_name}")]
     NotFound { lock_name: String },
+
+    #[error("Lease store error: {0}")]
+    Lease(#[from] sea_orm::DbErr),
 }
 
 // --------------------------- Tests -------------------------------------------
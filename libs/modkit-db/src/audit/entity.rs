@@ -0,0 +1,135 @@
+//! `modkit_audit_log`: one row per audited row change.
+//!
+//! The table is created by the migration runner (see `ensure_audit_table`); this entity only
+//! reads it, scoped by `tenant_id` like any other tenant-owned entity.
+
+use modkit_odata::filter::{FieldKind, FilterField};
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+
+use crate::odata::{FieldToColumn, ODataFieldMapping};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "modkit_audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub occurred_at: OffsetDateTime,
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub entity: String,
+    pub action: String,
+    pub record_id: String,
+    /// JSON object `{column: {"before": .., "after": ..}}`.
+    pub changes: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl crate::secure::ScopableEntity for Entity {
+    fn tenant_col() -> Option<Column> {
+        Some(Column::TenantId)
+    }
+    fn resource_col() -> Option<Column> {
+        None
+    }
+    fn owner_col() -> Option<Column> {
+        None
+    }
+    fn type_col() -> Option<Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<Column> {
+        (property == modkit_security::pep_properties::OWNER_TENANT_ID).then_some(Column::TenantId)
+    }
+}
+
+/// Fields of an audit record usable in `$filter` and `$orderby`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditFilterField {
+    Id,
+    OccurredAt,
+    ActorId,
+    TenantId,
+    Entity,
+    Action,
+    RecordId,
+}
+
+impl FilterField for AuditFilterField {
+    const FIELDS: &'static [Self] = &[
+        Self::Id,
+        Self::OccurredAt,
+        Self::ActorId,
+        Self::TenantId,
+        Self::Entity,
+        Self::Action,
+        Self::RecordId,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::OccurredAt => "occurred_at",
+            Self::ActorId => "actor_id",
+            Self::TenantId => "tenant_id",
+            Self::Entity => "entity",
+            Self::Action => "action",
+            Self::RecordId => "record_id",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            Self::Id => FieldKind::I64,
+            Self::OccurredAt => FieldKind::DateTimeUtc,
+            Self::ActorId | Self::TenantId => FieldKind::Uuid,
+            Self::Entity | Self::Action | Self::RecordId => FieldKind::String,
+        }
+    }
+}
+
+pub struct AuditODataMapper;
+
+impl FieldToColumn<AuditFilterField> for AuditODataMapper {
+    type Column = Column;
+
+    fn map_field(field: AuditFilterField) -> Column {
+        match field {
+            AuditFilterField::Id => Column::Id,
+            AuditFilterField::OccurredAt => Column::OccurredAt,
+            AuditFilterField::ActorId => Column::ActorId,
+            AuditFilterField::TenantId => Column::TenantId,
+            AuditFilterField::Entity => Column::Entity,
+            AuditFilterField::Action => Column::Action,
+            AuditFilterField::RecordId => Column::RecordId,
+        }
+    }
+}
+
+impl ODataFieldMapping<AuditFilterField> for AuditODataMapper {
+    type Entity = Entity;
+
+    fn extract_cursor_value(model: &Model, field: AuditFilterField) -> sea_orm::Value {
+        match field {
+            AuditFilterField::Id => sea_orm::Value::BigInt(Some(model.id)),
+            AuditFilterField::OccurredAt => {
+                sea_orm::Value::TimeDateTimeWithTimeZone(Some(Box::new(model.occurred_at)))
+            }
+            AuditFilterField::ActorId => sea_orm::Value::Uuid(model.actor_id.map(Box::new)),
+            AuditFilterField::TenantId => sea_orm::Value::Uuid(model.tenant_id.map(Box::new)),
+            AuditFilterField::Entity => {
+                sea_orm::Value::String(Some(Box::new(model.entity.clone())))
+            }
+            AuditFilterField::Action => {
+                sea_orm::Value::String(Some(Box::new(model.action.clone())))
+            }
+            AuditFilterField::RecordId => {
+                sea_orm::Value::String(Some(Box::new(model.record_id.clone())))
+            }
+        }
+    }
+}
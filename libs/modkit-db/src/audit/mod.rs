@@ -0,0 +1,427 @@
+//! Audit trail for secure writes.
+//!
+//! Entities declared with `#[secure(audit)]` get one row in the runtime-owned
+//! `modkit_audit_log` table for every row that `secure_insert`, `secure_update_with_scope`,
+//! `SecureUpdateMany::exec` or `SecureDeleteMany::exec` changes. A record holds:
+//!
+//! | Column | Value |
+//! |--------|-------|
+//! | `occurred_at` | time of the write (UTC) |
+//! | `actor_id` | subject set by [`with_audit_actor`], if any |
+//! | `tenant_id` | the row's tenant, or the actor's tenant for entities without `tenant_col` |
+//! | `entity` | table name |
+//! | `action` | `insert`, `update` or `delete` |
+//! | `record_id` | primary key; a JSON array for composite keys |
+//! | `changes` | `{column: {"before": .., "after": ..}}` for the columns that changed |
+//!
+//! Records are written through the runner of the change, so passing the `DbTx` of the
+//! business change makes the record commit or roll back together with it. The same
+//! transaction also keeps the before-values of bulk updates and deletes exact; without
+//! one they are read just before the statement runs.
+//!
+//! The log is append-only: no secure write path touches it, and rows leave it only through
+//! [`purge_before`] / [`purge_older_than`], which retention jobs call. [`list_records`]
+//! reads it with `OData` filtering and cursor pagination.
+
+mod entity;
+
+pub use entity::AuditFilterField;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::time::Duration;
+
+use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
+use modkit_security::{AccessScope, SecurityContext};
+use sea_orm::sea_query::{Alias, Expr, Query, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, ExecResult, IdenStatic, Iterable,
+    ModelTrait, PrimaryKeyToColumn, QueryFilter, StatementBuilder,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::migration_runner::AUDIT_TABLE;
+use crate::odata::{LimitCfg, paginate_odata};
+use crate::secure::{DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner, SecureEntityExt};
+
+const COL_OCCURRED_AT: &str = "occurred_at";
+const COL_ACTOR_ID: &str = "actor_id";
+const COL_TENANT_ID: &str = "tenant_id";
+const COL_ENTITY: &str = "entity";
+const COL_ACTION: &str = "action";
+const COL_RECORD_ID: &str = "record_id";
+const COL_CHANGES: &str = "changes";
+
+/// Records per `INSERT`; keeps bulk changes under the backends' bind-parameter limits.
+const INSERT_BATCH: usize = 100;
+
+#[derive(Debug, Clone, Copy)]
+struct AuditActor {
+    subject_id: Uuid,
+    tenant_id: Uuid,
+}
+
+tokio::task_local! {
+    static ACTOR: AuditActor;
+}
+
+/// Attribute the audit records written while `f` runs to the subject of `ctx`.
+///
+/// The API gateway wraps every request in this; background jobs that write audited
+/// entities on behalf of a subject should do the same. Anonymous contexts record no actor.
+pub async fn with_audit_actor<F: Future>(ctx: &SecurityContext, f: F) -> F::Output {
+    let actor = AuditActor {
+        subject_id: ctx.subject_id(),
+        tenant_id: ctx.subject_tenant_id(),
+    };
+    ACTOR.scope(actor, f).await
+}
+
+fn current_actor() -> Option<AuditActor> {
+    ACTOR.try_with(|actor| *actor).ok()
+}
+
+/// Kind of change an audit record describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    /// Value stored in the `action` column.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// An audit record read back from the log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// Position in the log; strictly increasing.
+    pub id: i64,
+    pub occurred_at: OffsetDateTime,
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    /// Table name of the changed entity.
+    pub entity: String,
+    /// One of the [`AuditAction::as_str`] values.
+    pub action: String,
+    /// Primary key of the changed row; a JSON array for composite keys.
+    pub record_id: String,
+    /// `{column: {"before": .., "after": ..}}`; inserts carry only `after`, deletes only `before`.
+    pub changes: serde_json::Value,
+}
+
+impl From<entity::Model> for AuditRecord {
+    fn from(m: entity::Model) -> Self {
+        Self {
+            id: m.id,
+            occurred_at: m.occurred_at,
+            actor_id: m.actor_id,
+            tenant_id: m.tenant_id,
+            entity: m.entity,
+            action: m.action,
+            record_id: m.record_id,
+            changes: serde_json::from_str(&m.changes).unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+/// Read a page of audit records visible in `scope`, newest first unless `query` orders
+/// otherwise. Filterable and sortable fields are listed by [`AuditFilterField`].
+///
+/// # Errors
+/// Returns `ODataError` if the filter, order or cursor is invalid or the query fails.
+pub async fn list_records(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    query: &ODataQuery,
+    limits: LimitCfg,
+) -> Result<Page<AuditRecord>, ODataError> {
+    paginate_odata::<AuditFilterField, entity::AuditODataMapper, _, _, _, _>(
+        entity::Entity::find().secure().scope_with(scope),
+        runner,
+        query,
+        ("id", SortDir::Desc),
+        limits,
+        AuditRecord::from,
+    )
+    .await
+}
+
+/// Delete every audit record older than `cutoff`, across all tenants.
+///
+/// Intended for retention jobs; returns the number of records removed.
+///
+/// # Errors
+/// Returns `DbError::Sea` if the delete fails.
+pub async fn purge_before(runner: &impl DBRunner, cutoff: OffsetDateTime) -> crate::Result<u64> {
+    let stmt = Query::delete()
+        .from_table(Alias::new(AUDIT_TABLE))
+        .and_where(Expr::col(Alias::new(COL_OCCURRED_AT)).lt(cutoff))
+        .to_owned();
+    Ok(execute(runner, &stmt).await?.rows_affected())
+}
+
+/// [`purge_before`] with the cutoff `max_age` before now.
+///
+/// # Errors
+/// Returns `DbError::Sea` if the delete fails.
+pub async fn purge_older_than(runner: &impl DBRunner, max_age: Duration) -> crate::Result<u64> {
+    let cutoff = time::Duration::try_from(max_age)
+        .ok()
+        .and_then(|age| OffsetDateTime::now_utc().checked_sub(age));
+    match cutoff {
+        Some(cutoff) => purge_before(runner, cutoff).await,
+        None => Ok(0),
+    }
+}
+
+/// Append one record per `(before, after)` pair that changed at least one column.
+pub(crate) async fn record<'a, E>(
+    runner: &impl DBRunner,
+    action: AuditAction,
+    rows: impl IntoIterator<Item = (Option<&'a E::Model>, Option<&'a E::Model>)>,
+) -> Result<(), DbErr>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Model: 'a,
+{
+    let actor = current_actor();
+    let actor_id = actor.map(|a| a.subject_id).filter(|id| !id.is_nil());
+    let actor_tenant = actor.map(|a| a.tenant_id).filter(|id| !id.is_nil());
+    let entity = E::default().table_name().to_owned();
+    let occurred_at = OffsetDateTime::now_utc();
+
+    let mut values: Vec<[SimpleExpr; 7]> = Vec::new();
+    for (before, after) in rows {
+        let Some(row) = after.or(before) else {
+            continue;
+        };
+        let changes = changes::<E>(before, after);
+        if changes.is_empty() {
+            continue;
+        }
+        values.push([
+            occurred_at.into(),
+            actor_id.into(),
+            row_tenant::<E>(row).or(actor_tenant).into(),
+            entity.clone().into(),
+            action.as_str().into(),
+            record_id::<E>(row).into(),
+            serde_json::Value::Object(changes).to_string().into(),
+        ]);
+    }
+
+    for batch in values.chunks(INSERT_BATCH) {
+        let mut stmt = Query::insert();
+        stmt.into_table(Alias::new(AUDIT_TABLE)).columns([
+            Alias::new(COL_OCCURRED_AT),
+            Alias::new(COL_ACTOR_ID),
+            Alias::new(COL_TENANT_ID),
+            Alias::new(COL_ENTITY),
+            Alias::new(COL_ACTION),
+            Alias::new(COL_RECORD_ID),
+            Alias::new(COL_CHANGES),
+        ]);
+        for row in batch {
+            stmt.values_panic(row.clone());
+        }
+        execute(runner, &stmt).await?;
+    }
+    Ok(())
+}
+
+/// Read the rows matching `cond` from the primary, ignoring soft-delete state.
+#[allow(clippy::disallowed_methods)]
+pub(crate) async fn fetch<E: EntityTrait>(
+    runner: &impl DBRunner,
+    cond: Condition,
+) -> Result<Vec<E::Model>, DbErr> {
+    let select = E::find().filter(cond);
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => select.all(db).await,
+        SeaOrmRunner::Tx(tx) => select.all(tx).await,
+    }
+}
+
+/// Condition matching exactly `rows`, by primary key.
+pub(crate) fn key_condition<E: EntityTrait>(rows: &[E::Model]) -> Condition {
+    rows.iter().fold(Condition::any(), |any, row| {
+        any.add(E::PrimaryKey::iter().fold(Condition::all(), |all, pk| {
+            let col = pk.into_column();
+            all.add(col.eq(row.get(col)))
+        }))
+    })
+}
+
+/// A row before and after a write; either side is `None` when it does not exist.
+pub(crate) type RowPair<'a, E> = (
+    Option<&'a <E as EntityTrait>::Model>,
+    Option<&'a <E as EntityTrait>::Model>,
+);
+
+/// Pair each row of `before` with its re-read version in `after`.
+///
+/// Rows missing from `after` (removed concurrently) are dropped.
+pub(crate) fn pair_by_key<'a, E>(
+    before: &'a [E::Model],
+    after: &'a [E::Model],
+) -> Vec<RowPair<'a, E>>
+where
+    E: EntityTrait,
+{
+    let mut after: HashMap<String, &E::Model> =
+        after.iter().map(|row| (record_id::<E>(row), row)).collect();
+    before
+        .iter()
+        .filter_map(|row| {
+            after
+                .remove(&record_id::<E>(row))
+                .map(|updated| (Some(row), Some(updated)))
+        })
+        .collect()
+}
+
+fn changes<E: EntityTrait>(
+    before: Option<&E::Model>,
+    after: Option<&E::Model>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut out = serde_json::Map::new();
+    for col in E::Column::iter() {
+        let before = before.map(|m| m.get(col));
+        let after = after.map(|m| m.get(col));
+        if before.is_some() && before == after {
+            continue;
+        }
+        let mut change = serde_json::Map::new();
+        if let Some(v) = &before {
+            change.insert("before".to_owned(), value_to_json(v));
+        }
+        if let Some(v) = &after {
+            change.insert("after".to_owned(), value_to_json(v));
+        }
+        out.insert(col.as_str().to_owned(), serde_json::Value::Object(change));
+    }
+    out
+}
+
+fn record_id<E: EntityTrait>(row: &E::Model) -> String {
+    let mut keys: Vec<serde_json::Value> = E::PrimaryKey::iter()
+        .map(|pk| value_to_json(&row.get(pk.into_column())))
+        .collect();
+    if keys.len() > 1 {
+        return serde_json::Value::Array(keys).to_string();
+    }
+    match keys.pop() {
+        Some(serde_json::Value::String(s)) => s,
+        Some(other) => other.to_string(),
+        None => String::new(),
+    }
+}
+
+fn row_tenant<E: ScopableEntity + EntityTrait>(row: &E::Model) -> Option<Uuid> {
+    match row.get(E::tenant_col()?) {
+        sea_orm::Value::Uuid(Some(u)) => Some(*u),
+        _ => None,
+    }
+}
+
+/// JSON form of a column value: timestamps as RFC 3339, decimals as strings (no precision
+/// loss), binary as lowercase hex.
+fn value_to_json(v: &sea_orm::Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match v {
+        sea_orm::Value::String(Some(s)) => Json::String(s.as_ref().clone()),
+        sea_orm::Value::Bytes(Some(bytes)) => {
+            let mut hex = String::with_capacity(bytes.len() * 2);
+            for byte in bytes.iter() {
+                // Writing into a `String` cannot fail.
+                write!(hex, "{byte:02x}").ok();
+            }
+            Json::String(hex)
+        }
+        sea_orm::Value::Decimal(Some(d)) => Json::String(d.to_string()),
+        sea_orm::Value::TimeDateTimeWithTimeZone(Some(dt)) => dt
+            .format(&time::format_description::well_known::Rfc3339)
+            .map_or(Json::Null, Json::String),
+        sea_orm::Value::ChronoDateTimeUtc(Some(dt)) => Json::String(dt.to_rfc3339()),
+        // The remaining date/time variants render as quoted SQL literals, or a bare `NULL`.
+        other => match sea_orm::sea_query::sea_value_to_json_value(other) {
+            Json::String(s) => match s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(unquoted) => Json::String(unquoted.to_owned()),
+                None if s == "NULL" => Json::Null,
+                None => Json::String(s),
+            },
+            json => json,
+        },
+    }
+}
+
+async fn execute<S: StatementBuilder>(
+    runner: &impl DBRunner,
+    stmt: &S,
+) -> Result<ExecResult, DbErr> {
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => db.execute(db.get_database_backend().build(stmt)).await,
+        SeaOrmRunner::Tx(tx) => tx.execute(tx.get_database_backend().build(stmt)).await,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_render_without_sql_quoting() {
+        let date = time::macros::date!(2024 - 03 - 01);
+        assert_eq!(
+            value_to_json(&sea_orm::Value::from(date)),
+            serde_json::json!("2024-03-01")
+        );
+        assert_eq!(
+            value_to_json(&sea_orm::Value::from("it's".to_owned())),
+            serde_json::json!("it's")
+        );
+        assert_eq!(
+            value_to_json(&sea_orm::Value::Bytes(Some(Box::new(vec![0x00, 0xff])))),
+            serde_json::json!("00ff")
+        );
+        assert_eq!(
+            value_to_json(&sea_orm::Value::Int(None)),
+            serde_json::Value::Null
+        );
+        assert_eq!(
+            value_to_json(&sea_orm::Value::TimeDateTimeWithTimeZone(None)),
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn record_ids_of_composite_keys_are_json_arrays() {
+        use crate::closure::tenant_closure;
+
+        let a = Uuid::from_u128(1);
+        let d = Uuid::from_u128(2);
+        let row = tenant_closure::Model {
+            ancestor_id: a,
+            descendant_id: d,
+            barrier: 0,
+            descendant_status: "active".to_owned(),
+        };
+        assert_eq!(
+            record_id::<tenant_closure::Entity>(&row),
+            serde_json::json!([a, d]).to_string()
+        );
+    }
+}
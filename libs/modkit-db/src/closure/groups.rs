@@ -0,0 +1,146 @@
+//! Maintenance of `resource_group_closure` and `resource_group_membership`.
+
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Set};
+use uuid::Uuid;
+
+use super::{resource_group_closure, resource_group_membership};
+use crate::secure::{
+    AccessScope, DBRunner, ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert,
+};
+
+/// Add `group_id` under `parent_id` (`None` for a root group).
+///
+/// # Errors
+/// - `ScopeError::Invalid` if `parent_id` is not in the closure.
+/// - `ScopeError::Db` if a row cannot be inserted (e.g., the group already exists).
+pub async fn insert_group(
+    runner: &impl DBRunner,
+    group_id: Uuid,
+    parent_id: Option<Uuid>,
+) -> Result<(), ScopeError> {
+    let scope = AccessScope::allow_all();
+    // Rows are derived from the closure read back below, so never read it from a replica
+    runner.pin_primary();
+
+    let mut ancestors = vec![group_id];
+    if let Some(parent_id) = parent_id {
+        let rows = resource_group_closure::Entity::find()
+            .secure()
+            .scope_with(&scope)
+            .filter(
+                Condition::all().add(resource_group_closure::Column::DescendantId.eq(parent_id)),
+            )
+            .all(runner)
+            .await?;
+        if rows.is_empty() {
+            return Err(ScopeError::Invalid(
+                "parent group is not in resource_group_closure",
+            ));
+        }
+        ancestors.extend(rows.into_iter().map(|row| row.ancestor_id));
+    }
+
+    for ancestor_id in ancestors {
+        let am = resource_group_closure::ActiveModel {
+            ancestor_id: Set(ancestor_id),
+            descendant_id: Set(group_id),
+        };
+        secure_insert::<resource_group_closure::Entity>(am, &scope, runner).await?;
+    }
+    Ok(())
+}
+
+/// Remove `group_id`, its subgroups and all their memberships.
+///
+/// Returns the number of groups removed (0 if the group was unknown).
+///
+/// # Errors
+/// Returns `ScopeError::Db` on database failure.
+pub async fn remove_group(runner: &impl DBRunner, group_id: Uuid) -> Result<u64, ScopeError> {
+    let scope = AccessScope::allow_all();
+    runner.pin_primary();
+
+    let subtree: Vec<Uuid> = resource_group_closure::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .filter(Condition::all().add(resource_group_closure::Column::AncestorId.eq(group_id)))
+        .all(runner)
+        .await?
+        .into_iter()
+        .map(|row| row.descendant_id)
+        .collect();
+    if subtree.is_empty() {
+        return Ok(0);
+    }
+    let removed = subtree.len() as u64;
+
+    resource_group_membership::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(
+            Condition::all().add(resource_group_membership::Column::GroupId.is_in(subtree.clone())),
+        )
+        .exec(runner)
+        .await?;
+    resource_group_closure::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(Condition::all().add(resource_group_closure::Column::DescendantId.is_in(subtree)))
+        .exec(runner)
+        .await?;
+    Ok(removed)
+}
+
+/// Make `resource_id` a member of `group_id`. Adding an existing membership is a no-op.
+///
+/// # Errors
+/// Returns `ScopeError::Db` on database failure.
+pub async fn add_group_member(
+    runner: &impl DBRunner,
+    group_id: Uuid,
+    resource_id: Uuid,
+) -> Result<(), ScopeError> {
+    let scope = AccessScope::allow_all();
+    runner.pin_primary();
+
+    let existing = resource_group_membership::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .filter(membership(group_id, resource_id))
+        .one(runner)
+        .await?;
+    if existing.is_none() {
+        let am = resource_group_membership::ActiveModel {
+            resource_id: Set(resource_id),
+            group_id: Set(group_id),
+        };
+        secure_insert::<resource_group_membership::Entity>(am, &scope, runner).await?;
+    }
+    Ok(())
+}
+
+/// Remove `resource_id` from `group_id`.
+///
+/// Returns whether a membership was removed.
+///
+/// # Errors
+/// Returns `ScopeError::Db` on database failure.
+pub async fn remove_group_member(
+    runner: &impl DBRunner,
+    group_id: Uuid,
+    resource_id: Uuid,
+) -> Result<bool, ScopeError> {
+    let result = resource_group_membership::Entity::delete_many()
+        .secure()
+        .scope_with(&AccessScope::allow_all())
+        .filter(membership(group_id, resource_id))
+        .exec(runner)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+fn membership(group_id: Uuid, resource_id: Uuid) -> Condition {
+    Condition::all()
+        .add(resource_group_membership::Column::GroupId.eq(group_id))
+        .add(resource_group_membership::Column::ResourceId.eq(resource_id))
+}
@@ -0,0 +1,115 @@
+//! Schema for the closure tables.
+
+use sea_orm::Schema;
+use sea_orm_migration::prelude::*;
+
+use super::{resource_group_closure, resource_group_membership, tenant_closure};
+
+/// Migrations a module adds to its own list to get the closure tables.
+#[must_use]
+pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+    vec![Box::new(CreateClosureTables)]
+}
+
+/// Creates `tenant_closure`, `resource_group_closure` and `resource_group_membership`.
+///
+/// Tables and indexes are created with `IF NOT EXISTS`, so modules sharing a
+/// database can each list this migration.
+pub struct CreateClosureTables;
+
+impl MigrationName for CreateClosureTables {
+    fn name(&self) -> &'static str {
+        "m20261016_000001_create_closure_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateClosureTables {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(manager.get_database_backend());
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(tenant_closure::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tenant_closure_descendant")
+                    .table(tenant_closure::Entity)
+                    .col(tenant_closure::Column::DescendantId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(resource_group_closure::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_resource_group_closure_descendant")
+                    .table(resource_group_closure::Entity)
+                    .col(resource_group_closure::Column::DescendantId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                schema
+                    .create_table_from_entity(resource_group_membership::Entity)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_resource_group_membership_group")
+                    .table(resource_group_membership::Entity)
+                    .col(resource_group_membership::Column::GroupId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(resource_group_membership::Entity)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(resource_group_closure::Entity)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(tenant_closure::Entity)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,56 @@
+//! Closure tables for hierarchy-aware authorization.
+//!
+//! The hierarchy predicates returned by the PDP (`in_tenant_subtree`, `in_group`,
+//! `in_group_subtree`) compile to [`ScopeFilter`](crate::secure::ScopeFilter)s that
+//! `build_scope_condition` turns into subqueries against three local tables:
+//!
+//! | Table | Rows |
+//! |-------|------|
+//! | [`tenant_closure`] | every (ancestor, descendant) tenant pair, including self-pairs, with the barrier flag and descendant status |
+//! | [`resource_group_closure`] | every (ancestor, descendant) group pair, including self-pairs |
+//! | [`resource_group_membership`] | (resource, group) memberships |
+//!
+//! A module that enforces these predicates adds [`migrations()`] to its own
+//! migration list and keeps the tables in sync with the functions below. Each
+//! maintenance call issues several statements; run it inside a transaction so
+//! a failure cannot leave a partial closure behind.
+//!
+//! The tables are global projections (no tenant column) and are accessed
+//! through the secure ORM with an unrestricted scope.
+
+/// Implements `ScopableEntity` for a closure entity: no scoping columns.
+macro_rules! unrestricted_entity {
+    () => {
+        impl $crate::secure::ScopableEntity for Entity {
+            const IS_UNRESTRICTED: bool = true;
+
+            fn tenant_col() -> Option<Column> {
+                None
+            }
+            fn resource_col() -> Option<Column> {
+                None
+            }
+            fn owner_col() -> Option<Column> {
+                None
+            }
+            fn type_col() -> Option<Column> {
+                None
+            }
+            fn resolve_property(_property: &str) -> Option<Column> {
+                None
+            }
+        }
+    };
+}
+use unrestricted_entity;
+
+mod groups;
+mod migration;
+pub mod resource_group_closure;
+pub mod resource_group_membership;
+pub mod tenant_closure;
+mod tenants;
+
+pub use groups::{add_group_member, insert_group, remove_group, remove_group_member};
+pub use migration::{CreateClosureTables, migrations};
+pub use tenants::{insert_tenant, remove_tenant, set_tenant_status};
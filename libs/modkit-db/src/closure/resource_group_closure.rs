@@ -0,0 +1,19 @@
+//! `resource_group_closure`: transitive closure of the resource group hierarchy.
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "resource_group_closure")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub ancestor_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub descendant_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+super::unrestricted_entity!();
@@ -0,0 +1,20 @@
+//! `resource_group_membership`: which resources belong to which groups.
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "resource_group_membership")]
+pub struct Model {
+    /// Matched against the resource property named in the predicate (usually `id`).
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub resource_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub group_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+super::unrestricted_entity!();
@@ -0,0 +1,28 @@
+//! `tenant_closure`: transitive closure of the tenant hierarchy.
+
+use sea_orm::entity::prelude::*;
+
+/// No self-managed tenant between ancestor and descendant.
+pub const NO_BARRIER: i32 = 0;
+/// A self-managed tenant sits below the ancestor, up to and including the descendant.
+pub const BARRIER: i32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tenant_closure")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub ancestor_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub descendant_id: Uuid,
+    /// [`NO_BARRIER`] or [`BARRIER`]; the ancestor itself never counts.
+    pub barrier: i32,
+    /// Status of the descendant tenant (e.g., `active`, `suspended`).
+    pub descendant_status: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+super::unrestricted_entity!();
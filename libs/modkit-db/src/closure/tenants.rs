@@ -0,0 +1,129 @@
+//! Maintenance of `tenant_closure`.
+
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Set};
+use uuid::Uuid;
+
+use super::tenant_closure::{self, BARRIER, NO_BARRIER};
+use crate::secure::{
+    AccessScope, DBRunner, ScopeError, SecureDeleteExt, SecureEntityExt, SecureUpdateExt,
+    secure_insert,
+};
+
+/// Add `tenant_id` under `parent_id` (`None` for a root tenant).
+///
+/// Inserts the self-row and one row per ancestor of the parent. A
+/// `self_managed` tenant is a barrier for every ancestor above it.
+///
+/// # Errors
+/// - `ScopeError::Invalid` if `parent_id` is not in the closure.
+/// - `ScopeError::Db` if a row cannot be inserted (e.g., the tenant already exists).
+pub async fn insert_tenant(
+    runner: &impl DBRunner,
+    tenant_id: Uuid,
+    parent_id: Option<Uuid>,
+    self_managed: bool,
+    status: &str,
+) -> Result<(), ScopeError> {
+    let scope = AccessScope::allow_all();
+    // Rows are derived from the closure read back below, so never read it from a replica
+    runner.pin_primary();
+
+    let ancestors = match parent_id {
+        Some(parent_id) => {
+            let rows = tenant_closure::Entity::find()
+                .secure()
+                .scope_with(&scope)
+                .filter(Condition::all().add(tenant_closure::Column::DescendantId.eq(parent_id)))
+                .all(runner)
+                .await?;
+            if rows.is_empty() {
+                return Err(ScopeError::Invalid(
+                    "parent tenant is not in tenant_closure",
+                ));
+            }
+            rows
+        }
+        None => Vec::new(),
+    };
+
+    let row = |ancestor_id: Uuid, barrier: i32| tenant_closure::ActiveModel {
+        ancestor_id: Set(ancestor_id),
+        descendant_id: Set(tenant_id),
+        barrier: Set(barrier),
+        descendant_status: Set(status.to_owned()),
+    };
+
+    secure_insert::<tenant_closure::Entity>(row(tenant_id, NO_BARRIER), &scope, runner).await?;
+    for ancestor in ancestors {
+        // The parent's self-row carries no barrier, so the parent sees only ours
+        let barrier = if self_managed {
+            ancestor.barrier | BARRIER
+        } else {
+            ancestor.barrier
+        };
+        secure_insert::<tenant_closure::Entity>(row(ancestor.ancestor_id, barrier), &scope, runner)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Record a new status for `tenant_id` on all of its closure rows.
+///
+/// # Errors
+/// - `ScopeError::Invalid` if the tenant is not in the closure.
+/// - `ScopeError::Db` on database failure.
+pub async fn set_tenant_status(
+    runner: &impl DBRunner,
+    tenant_id: Uuid,
+    status: &str,
+) -> Result<(), ScopeError> {
+    let result = tenant_closure::Entity::update_many()
+        .col_expr(
+            tenant_closure::Column::DescendantStatus,
+            Expr::value(status.to_owned()),
+        )
+        .secure()
+        .scope_with(&AccessScope::allow_all())
+        .filter(Condition::all().add(tenant_closure::Column::DescendantId.eq(tenant_id)))
+        .exec(runner)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ScopeError::Invalid("tenant is not in tenant_closure"));
+    }
+    Ok(())
+}
+
+/// Remove `tenant_id` and its whole subtree from the closure.
+///
+/// Returns the number of tenants removed (0 if the tenant was unknown).
+///
+/// # Errors
+/// Returns `ScopeError::Db` on database failure.
+pub async fn remove_tenant(runner: &impl DBRunner, tenant_id: Uuid) -> Result<u64, ScopeError> {
+    let scope = AccessScope::allow_all();
+    runner.pin_primary();
+
+    // Collected first: MySQL cannot delete from a table it sub-selects from
+    let subtree: Vec<Uuid> = tenant_closure::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .filter(Condition::all().add(tenant_closure::Column::AncestorId.eq(tenant_id)))
+        .all(runner)
+        .await?
+        .into_iter()
+        .map(|row| row.descendant_id)
+        .collect();
+    if subtree.is_empty() {
+        return Ok(0);
+    }
+    let removed = subtree.len() as u64;
+
+    tenant_closure::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(Condition::all().add(tenant_closure::Column::DescendantId.is_in(subtree)))
+        .exec(runner)
+        .await?;
+    Ok(removed)
+}
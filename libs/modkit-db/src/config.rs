@@ -37,6 +37,7 @@
 //! - `SQLite` fields mixed with server connection fields
 //!
 
+use crate::advisory_locks::LockBackend;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -88,6 +89,14 @@ pub struct DbConnConfig {
     #[serde(default)]
     pub pool: Option<PoolCfg>,
 
+    // Advisory lock backend (`database` by default; `file` for single-host desktop builds):
+    #[serde(default)]
+    pub lock_backend: Option<LockBackend>,
+
+    // Read replicas for scoped reads outside transactions (see `ReplicasCfg`):
+    #[serde(default)]
+    pub replicas: Option<ReplicasCfg>,
+
     // Module-level only: reference to a global server by name.
     // If absent, this module config must be fully self-sufficient (dsn or fields).
     pub server: Option<String>,
@@ -104,6 +113,29 @@ pub enum DbEngineCfg {
     Sqlite,
 }
 
+/// Read replicas of a connection.
+///
+/// Scoped reads (`SecureSelect` executed on a `DbConn`) are spread round-robin across the
+/// healthy nodes. Transactions, writes, and reads issued after a write in the same
+/// read-your-writes scope use the primary.
+///
+/// Each node inherits the primary's settings (credentials, `dbname`, params, pool) and
+/// overrides what differs, usually `host` or `dsn`. `SQLite` nodes need their own
+/// `file`/`path`, which are never inherited.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicasCfg {
+    /// Replica connections; `server` and nested `replicas` are not allowed here.
+    #[serde(default)]
+    pub nodes: Vec<DbConnConfig>,
+    /// Replication lag above which a node stops receiving reads. Unset: lag is not checked.
+    #[serde(with = "modkit_utils::humantime_serde::option", default)]
+    pub max_lag: Option<Duration>,
+    /// How often node health and lag are re-checked (default 5s).
+    #[serde(with = "modkit_utils::humantime_serde::option", default)]
+    pub check_interval: Option<Duration>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct PoolCfg {
@@ -0,0 +1,203 @@
+//! Database-backed leases for advisory locks.
+//!
+//! A lease is a row in the runtime-owned `modkit_leases` table (created by the migration
+//! runner). Acquisition, renewal and release are single-row statements built with
+//! `sea-query`, so they behave the same on `SQLite`, `PostgreSQL` and `MySQL`:
+//!
+//! - **acquire**: insert the row if absent; otherwise take it over if `expires_at` has passed,
+//!   bumping `fencing_token`. The caller owns the lease iff the row then names it as `holder`.
+//! - **renew**: push `expires_at` forward while `holder` still matches.
+//! - **release**: expire the row immediately. The row is kept so the next holder receives a
+//!   strictly larger fencing token.
+//!
+//! Expiry uses the application clock (epoch milliseconds), so hosts sharing a database must
+//! keep their clocks reasonably in sync relative to the lease TTL.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::sea_query::{Alias, Expr, OnConflict, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr};
+use tokio::sync::OnceCell;
+use tokio::task::JoinHandle;
+
+use crate::migration_runner::{LEASE_TABLE, ensure_lease_table};
+
+const COL_LOCK_KEY: &str = "lock_key";
+const COL_HOLDER: &str = "holder";
+const COL_FENCING_TOKEN: &str = "fencing_token";
+const COL_EXPIRES_AT: &str = "expires_at";
+
+/// A lease owned by this process.
+#[derive(Debug)]
+pub struct AcquiredLease {
+    pub(crate) holder: String,
+    pub(crate) fencing_token: u64,
+}
+
+/// Lease operations over a single database connection pool.
+#[derive(Debug, Clone)]
+pub struct LeaseStore {
+    conn: DatabaseConnection,
+    table_ready: Arc<OnceCell<()>>,
+}
+
+impl LeaseStore {
+    pub(crate) fn new(conn: DatabaseConnection, table_ready: Arc<OnceCell<()>>) -> Self {
+        Self { conn, table_ready }
+    }
+
+    pub(crate) fn conn(&self) -> &DatabaseConnection {
+        &self.conn
+    }
+
+    /// Make sure the lease table exists even if no module migrations ran on this database.
+    async fn ensure_table(&self) -> Result<(), DbErr> {
+        self.table_ready
+            .get_or_try_init(|| ensure_lease_table(&self.conn))
+            .await?;
+        Ok(())
+    }
+
+    /// Make a single attempt to acquire `key` for `ttl`.
+    ///
+    /// Returns `Ok(None)` if another holder owns an unexpired lease.
+    pub(crate) async fn try_acquire(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<AcquiredLease>, DbErr> {
+        self.ensure_table().await?;
+
+        let backend = self.conn.get_database_backend();
+        let holder = uuid::Uuid::new_v4().to_string();
+        let now = now_millis();
+        let expires_at = now.saturating_add(duration_millis(ttl));
+
+        let insert = Query::insert()
+            .into_table(Alias::new(LEASE_TABLE))
+            .columns([
+                Alias::new(COL_LOCK_KEY),
+                Alias::new(COL_HOLDER),
+                Alias::new(COL_FENCING_TOKEN),
+                Alias::new(COL_EXPIRES_AT),
+            ])
+            .values_panic([
+                key.into(),
+                holder.clone().into(),
+                1_i64.into(),
+                expires_at.into(),
+            ])
+            .on_conflict(
+                OnConflict::column(Alias::new(COL_LOCK_KEY))
+                    .do_nothing_on([Alias::new(COL_LOCK_KEY)])
+                    .to_owned(),
+            )
+            .to_owned();
+        self.conn.execute(backend.build(&insert)).await?;
+
+        // Stale takeover: only matches when the current lease has expired.
+        let takeover = Query::update()
+            .table(Alias::new(LEASE_TABLE))
+            .value(Alias::new(COL_HOLDER), holder.clone())
+            .value(
+                Alias::new(COL_FENCING_TOKEN),
+                Expr::col(Alias::new(COL_FENCING_TOKEN)).add(1),
+            )
+            .value(Alias::new(COL_EXPIRES_AT), expires_at)
+            .and_where(Expr::col(Alias::new(COL_LOCK_KEY)).eq(key))
+            .and_where(Expr::col(Alias::new(COL_EXPIRES_AT)).lte(now))
+            .to_owned();
+        self.conn.execute(backend.build(&takeover)).await?;
+
+        let select = Query::select()
+            .columns([Alias::new(COL_HOLDER), Alias::new(COL_FENCING_TOKEN)])
+            .from(Alias::new(LEASE_TABLE))
+            .and_where(Expr::col(Alias::new(COL_LOCK_KEY)).eq(key))
+            .to_owned();
+        let Some(row) = self.conn.query_one(backend.build(&select)).await? else {
+            return Ok(None);
+        };
+
+        let current: String = row.try_get("", COL_HOLDER)?;
+        if current != holder {
+            return Ok(None);
+        }
+        let token: i64 = row.try_get("", COL_FENCING_TOKEN)?;
+
+        Ok(Some(AcquiredLease {
+            holder,
+            fencing_token: u64::try_from(token).unwrap_or_default(),
+        }))
+    }
+}
+
+/// Extend the lease by `ttl`. Returns `false` if `holder` no longer owns it.
+pub async fn renew(
+    conn: &DatabaseConnection,
+    key: &str,
+    holder: &str,
+    ttl: Duration,
+) -> Result<bool, DbErr> {
+    let backend = conn.get_database_backend();
+    let expires_at = now_millis().saturating_add(duration_millis(ttl));
+
+    let stmt = Query::update()
+        .table(Alias::new(LEASE_TABLE))
+        .value(Alias::new(COL_EXPIRES_AT), expires_at)
+        .and_where(Expr::col(Alias::new(COL_LOCK_KEY)).eq(key))
+        .and_where(Expr::col(Alias::new(COL_HOLDER)).eq(holder))
+        .to_owned();
+    let res = conn.execute(backend.build(&stmt)).await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// Expire the lease if `holder` still owns it.
+pub async fn release(conn: &DatabaseConnection, key: &str, holder: &str) -> Result<(), DbErr> {
+    let backend = conn.get_database_backend();
+
+    let stmt = Query::update()
+        .table(Alias::new(LEASE_TABLE))
+        .value(Alias::new(COL_EXPIRES_AT), 0_i64)
+        .and_where(Expr::col(Alias::new(COL_LOCK_KEY)).eq(key))
+        .and_where(Expr::col(Alias::new(COL_HOLDER)).eq(holder))
+        .to_owned();
+    conn.execute(backend.build(&stmt)).await?;
+    Ok(())
+}
+
+/// Spawn a task that renews the lease every `interval` until aborted or the lease is lost.
+pub fn spawn_heartbeat(
+    conn: DatabaseConnection,
+    key: String,
+    holder: String,
+    ttl: Duration,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately; the lease was just written.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match renew(&conn, &key, &holder, ttl).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    tracing::warn!(lock = %key, "lease lost to another holder; stopping heartbeat");
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!(lock = %key, error = %e, "failed to renew lease");
+                }
+            }
+        }
+    })
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn duration_millis(d: Duration) -> i64 {
+    i64::try_from(d.as_millis()).unwrap_or(i64::MAX)
+}
@@ -67,22 +67,27 @@
 )]
 
 // Re-export key types for public API
-pub use advisory_locks::{DbLockGuard, LockConfig};
+pub use advisory_locks::{DbLockGuard, LockBackend, LockConfig};
 
 // Re-export sea_orm_migration for modules that implement DatabaseCapability
 pub use sea_orm_migration;
 
 // Core modules
 pub mod advisory_locks;
+pub mod audit;
+pub mod closure;
 pub mod config;
 pub mod manager;
 pub mod migration_runner;
 pub mod odata;
 pub mod options;
+pub mod outbox;
 
 pub mod secure;
 
 mod db_provider;
+mod leases;
+mod replicas;
 
 // Internal modules
 mod pool_opts;
@@ -90,12 +95,14 @@ mod pool_opts;
 mod sqlite;
 
 // Re-export important types from new modules
-pub use config::{DbConnConfig, GlobalDatabaseConfig, PoolCfg};
+pub use config::{DbConnConfig, GlobalDatabaseConfig, PoolCfg, ReplicasCfg};
 pub use manager::DbManager;
 pub use options::redact_credentials_in_dsn;
 
 // Re-export secure database types for convenience
-pub use secure::{Db, DbConn, DbTx};
+pub use secure::{Db, DbConn, DbTx, PoolStats, read_your_writes};
+
+pub use audit::with_audit_actor;
 
 // Re-export service-friendly provider
 pub use db_provider::DBProvider;
@@ -122,6 +129,7 @@ pub async fn build_db(cfg: DbConnConfig, global: Option<&GlobalDatabaseConfig>)
     Ok(Db::new(handle))
 }
 
+use std::sync::Arc;
 use std::time::Duration;
 
 // Internal imports
@@ -204,6 +212,13 @@ pub enum DbError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 
+    /// Optimistic concurrency check failed: the row is no longer at version `expected`.
+    ///
+    /// Converted from [`secure::ScopeError::VersionConflict`]; see
+    /// `modkit::api::problem::from_db_error` for the HTTP mapping.
+    #[error("version conflict: row no longer at version {expected}")]
+    VersionConflict { expected: i64 },
+
     /// Attempted to create a non-transactional connection inside an active transaction.
     ///
     /// This error occurs when `Db::conn()` is called from within a transaction closure.
@@ -234,9 +249,15 @@ pub enum DbError {
 
 impl From<crate::secure::ScopeError> for DbError {
     fn from(value: crate::secure::ScopeError) -> Self {
-        // Scope errors are not infra connection errors, but they still originate from the DB
-        // access layer. We keep the wrapper thin and preserve the message for callers.
-        DbError::Other(anyhow::Error::new(value))
+        match value {
+            // Callers report conflicts to clients, so keep them typed.
+            crate::secure::ScopeError::VersionConflict { expected } => {
+                DbError::VersionConflict { expected }
+            }
+            // Scope errors are not infra connection errors, but they still originate from the DB
+            // access layer. We keep the wrapper thin and preserve the message for callers.
+            other => DbError::Other(anyhow::Error::new(other)),
+        }
     }
 }
 
@@ -266,6 +287,8 @@ pub struct ConnectOpts {
     pub test_before_acquire: bool,
     /// For `SQLite` file DSNs, create parent directories if missing.
     pub create_sqlite_dirs: bool,
+    /// Backend used by `lock`/`try_lock`.
+    pub lock_backend: LockBackend,
 }
 impl Default for ConnectOpts {
     fn default() -> Self {
@@ -278,6 +301,7 @@ impl Default for ConnectOpts {
             test_before_acquire: false,
 
             create_sqlite_dirs: true,
+            lock_backend: LockBackend::default(),
         }
     }
 }
@@ -288,6 +312,9 @@ pub(crate) struct DbHandle {
     engine: DbEngine,
     dsn: String,
     sea: DatabaseConnection,
+    lock_backend: LockBackend,
+    lease_table_ready: Arc<tokio::sync::OnceCell<()>>,
+    replicas: Option<Arc<replicas::ReplicaSet>>,
 }
 
 #[cfg(feature = "sqlite")]
@@ -333,6 +360,9 @@ impl DbHandle {
                     engine,
                     dsn: dsn.to_owned(),
                     sea,
+                    lock_backend: opts.lock_backend,
+                    lease_table_ready: Arc::default(),
+                    replicas: None,
                 })
             }
             #[cfg(not(feature = "pg"))]
@@ -346,6 +376,9 @@ impl DbHandle {
                     engine,
                     dsn: dsn.to_owned(),
                     sea,
+                    lock_backend: opts.lock_backend,
+                    lease_table_ready: Arc::default(),
+                    replicas: None,
                 })
             }
             #[cfg(not(feature = "mysql"))]
@@ -425,6 +458,9 @@ impl DbHandle {
                     engine,
                     dsn: clean_dsn,
                     sea,
+                    lock_backend: opts.lock_backend,
+                    lease_table_ready: Arc::default(),
+                    replicas: None,
                 })
             }
             #[cfg(not(feature = "sqlite"))]
@@ -481,12 +517,42 @@ impl DbHandle {
 
     // --- Advisory locks ---
 
+    /// Select the advisory lock backend for this handle.
+    #[must_use]
+    pub(crate) fn with_lock_backend(mut self, backend: LockBackend) -> Self {
+        self.lock_backend = backend;
+        self
+    }
+
+    // --- Read replicas ---
+
+    /// Attach read replicas for scoped reads issued outside transactions.
+    #[must_use]
+    pub(crate) fn with_replicas(mut self, replicas: Arc<replicas::ReplicaSet>) -> Self {
+        self.replicas = Some(replicas);
+        self
+    }
+
+    pub(crate) fn replicas(&self) -> Option<&replicas::ReplicaSet> {
+        self.replicas.as_deref()
+    }
+
+    fn lock_manager(&self) -> advisory_locks::LockManager {
+        match self.lock_backend {
+            LockBackend::Database => advisory_locks::LockManager::with_leases(
+                self.dsn.clone(),
+                leases::LeaseStore::new(self.sea.clone(), Arc::clone(&self.lease_table_ready)),
+            ),
+            LockBackend::File => advisory_locks::LockManager::new(self.dsn.clone()),
+        }
+    }
+
     /// Acquire an advisory lock with the given key and module namespace.
     ///
     /// # Errors
     /// Returns an error if the lock cannot be acquired.
     pub async fn lock(&self, module: &str, key: &str) -> Result<DbLockGuard> {
-        let lock_manager = advisory_locks::LockManager::new(self.dsn.clone());
+        let lock_manager = self.lock_manager();
         let guard = lock_manager.lock(module, key).await?;
         Ok(guard)
     }
@@ -501,7 +567,7 @@ impl DbHandle {
         key: &str,
         config: LockConfig,
     ) -> Result<Option<DbLockGuard>> {
-        let lock_manager = advisory_locks::LockManager::new(self.dsn.clone());
+        let lock_manager = self.lock_manager();
         let res = lock_manager.try_lock(module, key, config).await?;
         Ok(res)
     }
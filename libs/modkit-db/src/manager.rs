@@ -118,6 +118,11 @@ impl DbManager {
         // Finalize SQLite paths if needed
         let module_home_dir = self.home_dir.join(module);
         cfg = self.finalize_sqlite_paths(cfg, &module_home_dir)?;
+        if let Some(replicas) = cfg.replicas.as_mut() {
+            for node in &mut replicas.nodes {
+                *node = self.finalize_sqlite_paths(std::mem::take(node), &module_home_dir)?;
+            }
+        }
 
         // Build the database handle
         let handle = build_db_handle(cfg, self.global.as_ref()).await?;
@@ -134,7 +139,7 @@ impl DbManager {
 
     /// Merge global server configuration into module configuration.
     /// Module fields override server fields. Params maps are merged with module taking precedence.
-    fn merge_server_into_module(
+    pub(crate) fn merge_server_into_module(
         mut module_cfg: DbConnConfig,
         server_cfg: DbConnConfig,
     ) -> DbConnConfig {
@@ -186,6 +191,16 @@ impl DbManager {
             module_cfg.pool = server_cfg.pool;
         }
 
+        // Lock backend: module takes precedence
+        if module_cfg.lock_backend.is_none() {
+            module_cfg.lock_backend = server_cfg.lock_backend;
+        }
+
+        // Replicas: module takes precedence (the whole set, nodes are not merged)
+        if module_cfg.replicas.is_none() {
+            module_cfg.replicas = server_cfg.replicas;
+        }
+
         // Note: file, path, and server fields are module-only and not merged
 
         module_cfg
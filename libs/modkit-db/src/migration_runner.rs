@@ -11,6 +11,10 @@
 //! where `<hash8>` is an 8-character hex hash derived from the module prefix via `xxh3_64`.
 //! This prevents conflicts between modules that might have similarly named migrations.
 //!
+//! A history row records when the migration was applied, a checksum of the statements
+//! it executed and, once it has been rolled back via [`rollback_migrations_for_module`],
+//! when it was reverted. A reverted migration counts as pending again.
+//!
 //! Examples:
 //! - Test prefix "_test" → `modkit_migrations___test__e5f6a7b8`
 //!
@@ -18,15 +22,29 @@
 //!
 //! Modules only provide migration definitions via `MigrationTrait`. The runtime executes
 //! them using its privileged connection. Modules never receive raw database access.
+//!
+//! # Previews and Drift
+//!
+//! Migration steps can be rendered without a database: they run against a recording
+//! proxy connection of the same backend, which yields the exact statements they would
+//! execute. This powers [`plan_migrations_for_module`] (dry runs) and the checksums used
+//! to detect migrations that were edited after being applied. Steps that read from the
+//! database (for example `has_table`) cannot be rendered; they get no checksum.
+//!
+//! # Runtime-Owned Tables
+//!
+//! Besides the per-module history tables, the runner owns `modkit_leases`, which backs
+//! database advisory locks (see [`crate::advisory_locks`]). It is created before any module
+//! migration runs, so modules never declare it themselves.
 
 use sea_orm::{
-    ConnectionTrait, DatabaseBackend, DbErr, ExecResult, FromQueryResult, Statement,
-    TransactionTrait,
+    ConnectionTrait, Database, DatabaseBackend, DbErr, ExecResult, FromQueryResult,
+    ProxyDatabaseTrait, ProxyExecResult, ProxyRow, Statement, TransactionTrait,
 };
-use sea_orm_migration::MigrationTrait;
-use std::collections::HashSet;
+use sea_orm_migration::{MigrationTrait, SchemaManager};
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use xxhash_rust::xxh3::xxh3_64;
 
 /// Errors that can occur during migration execution.
@@ -56,9 +74,37 @@ pub enum MigrationError {
         source: DbErr,
     },
 
+    /// Failed to create the runtime-owned lease table.
+    #[error("failed to create lease table: {source}")]
+    CreateLeaseTable { source: DbErr },
+
+    /// Failed to create the runtime-owned audit log table.
+    #[error("failed to create audit log table: {source}")]
+    CreateAuditTable { source: DbErr },
+
     /// Duplicate migration name found in provided migrations list.
     #[error("duplicate migration name '{name}' for module '{module}'")]
     DuplicateMigrationName { module: String, name: String },
+
+    /// A rollback target that is not one of the module's migrations.
+    #[error("module '{module}' has no migration named '{name}'")]
+    UnknownMigration { module: String, name: String },
+
+    /// A migration could not be rendered without a database.
+    #[error("migration '{migration}' for module '{module}' cannot be previewed: {source}")]
+    PreviewFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
+
+    /// A migration's `down` step failed, or its reversal could not be recorded.
+    #[error("reverting migration '{migration}' failed for module '{module}': {source}")]
+    RevertFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
 }
 
 /// Result of a migration run.
@@ -72,10 +118,69 @@ pub struct MigrationResult {
     pub applied_names: Vec<String>,
 }
 
+/// Where a migration stands for a module (see [`migration_status`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Applied and not reverted.
+    Applied,
+    /// Never applied, or reverted since.
+    Pending,
+    /// Recorded as applied, but the module no longer provides it.
+    Unknown,
+}
+
+/// Status of a single migration, as reported by [`migration_status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// Migration name.
+    pub name: String,
+    /// Whether the migration is applied.
+    pub state: MigrationState,
+    /// When the migration was last applied, as reported by the database.
+    pub applied_at: Option<String>,
+    /// When the migration was last reverted, if ever.
+    pub reverted_at: Option<String>,
+    /// The applied migration now renders different statements than when it was applied.
+    pub drifted: bool,
+}
+
+/// Statements a migration step would execute, produced without touching the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationPlan {
+    /// Migration name.
+    pub name: String,
+    /// SQL statements in execution order, with parameters inlined.
+    pub statements: Vec<String>,
+}
+
 /// Internal model for querying migration history.
+///
+/// Timestamps are read as text so that every backend reports them the same way.
 #[derive(Debug, FromQueryResult)]
-struct MigrationRecord {
+struct HistoryRecord {
     version: String,
+    applied_at: String,
+    checksum: Option<String>,
+    reverted_at: Option<String>,
+}
+
+impl HistoryRecord {
+    fn is_applied(&self) -> bool {
+        self.reverted_at.is_none()
+    }
+}
+
+/// Internal model for listing history table columns.
+#[derive(Debug, FromQueryResult)]
+struct ColumnRecord {
+    column_name: String,
+}
+
+/// Direction of a migration step.
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Up,
+    Down,
 }
 
 /// Sanitize a module name into a safe identifier fragment.
@@ -97,12 +202,16 @@ fn sanitize_module_name(name: &str) -> String {
 /// Build the per-module migration table name.
 ///
 /// Format: `modkit_migrations__<prefix>__<hash8>`
+fn migration_table_name(module_name: &str) -> String {
+    module_table_name("modkit_migrations__", module_name)
+}
+
+/// Build a runtime-owned per-module table name: `<table_prefix><prefix>__<hash8>`.
 ///
 /// - `<prefix>` is a sanitized module name fragment
 /// - `<hash8>` is a stable hash of the ORIGINAL module name
 /// - Name is capped to Postgres 63-byte identifier limit (and kept short for all backends)
-fn migration_table_name(module_name: &str) -> String {
-    const PREFIX: &str = "modkit_migrations__";
+pub(crate) fn module_table_name(table_prefix: &str, module_name: &str) -> String {
     const SEP: &str = "__";
     const HASH_LEN: usize = 8;
     const PG_IDENT_MAX: usize = 63;
@@ -111,8 +220,8 @@ fn migration_table_name(module_name: &str) -> String {
     let hash = xxh3_64(module_name.as_bytes());
     let hash8 = format!("{hash:016x}")[..HASH_LEN].to_owned();
 
-    // Reserve space for: PREFIX + prefix + SEP + hash8
-    let reserved = PREFIX.len() + SEP.len() + HASH_LEN;
+    // Reserve space for: table_prefix + prefix + SEP + hash8
+    let reserved = table_prefix.len() + SEP.len() + HASH_LEN;
     let max_prefix_len = PG_IDENT_MAX.saturating_sub(reserved);
     let prefix_part = if max_prefix_len == 0 {
         String::new()
@@ -122,7 +231,7 @@ fn migration_table_name(module_name: &str) -> String {
         sanitized
     };
 
-    format!("{PREFIX}{prefix_part}{SEP}{hash8}")
+    format!("{table_prefix}{prefix_part}{SEP}{hash8}")
 }
 
 /// Create the migration history table for a module if it doesn't exist.
@@ -167,26 +276,218 @@ async fn ensure_migration_table(
             source: e,
         })?;
 
+    upgrade_migration_table(conn, table_name, module_name).await
+}
+
+/// Quote a table name for the connection's backend.
+fn quote_table(backend: DatabaseBackend, table_name: &str) -> String {
+    match backend {
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => format!(r#""{table_name}""#),
+        DatabaseBackend::MySql => format!("`{table_name}`"),
+    }
+}
+
+/// Add the history columns introduced after the table's original layout.
+///
+/// Both columns are nullable, so tables created by older runtimes keep their rows intact;
+/// those rows simply have no checksum to compare against.
+async fn upgrade_migration_table(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<(), MigrationError> {
+    let backend = conn.get_database_backend();
+    let to_err = |e| MigrationError::CreateTable {
+        module: module_name.to_owned(),
+        source: e,
+    };
+
+    let sql = match backend {
+        DatabaseBackend::Postgres => format!(
+            "SELECT CAST(column_name AS TEXT) AS column_name FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = '{table_name}'"
+        ),
+        DatabaseBackend::MySql => format!(
+            "SELECT column_name AS column_name FROM information_schema.columns \
+             WHERE table_schema = DATABASE() AND table_name = '{table_name}'"
+        ),
+        DatabaseBackend::Sqlite => {
+            format!("SELECT name AS column_name FROM pragma_table_info('{table_name}')")
+        }
+    };
+    let existing: HashSet<String> =
+        ColumnRecord::find_by_statement(Statement::from_string(backend, sql))
+            .all(conn)
+            .await
+            .map_err(to_err)?
+            .into_iter()
+            .map(|c| c.column_name.to_ascii_lowercase())
+            .collect();
+
+    let columns = match backend {
+        DatabaseBackend::Postgres => [("checksum", "VARCHAR(16)"), ("reverted_at", "TIMESTAMPTZ")],
+        DatabaseBackend::MySql => [
+            ("checksum", "VARCHAR(16)"),
+            ("reverted_at", "TIMESTAMP NULL"),
+        ],
+        DatabaseBackend::Sqlite => [("checksum", "TEXT"), ("reverted_at", "TEXT")],
+    };
+    let table = quote_table(backend, table_name);
+    for (column, ty) in columns {
+        if existing.contains(column) {
+            continue;
+        }
+        let sql = format!("ALTER TABLE {table} ADD COLUMN {column} {ty}");
+        conn.execute(Statement::from_string(backend, sql))
+            .await
+            .map_err(to_err)?;
+    }
+
+    Ok(())
+}
+
+/// Name of the runtime-owned table backing database advisory locks.
+pub(crate) const LEASE_TABLE: &str = "modkit_leases";
+
+/// Create the lease table used by database advisory locks if it doesn't exist.
+///
+/// `expires_at` is stored as epoch milliseconds so that lease comparisons behave the
+/// same on every backend.
+pub(crate) async fn ensure_lease_table(conn: &impl ConnectionTrait) -> Result<(), DbErr> {
+    let backend = conn.get_database_backend();
+
+    let sql = match backend {
+        DatabaseBackend::Postgres => format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS "{LEASE_TABLE}" (
+                lock_key VARCHAR(255) PRIMARY KEY,
+                holder VARCHAR(64) NOT NULL,
+                fencing_token BIGINT NOT NULL,
+                expires_at BIGINT NOT NULL
+            )
+            "#
+        ),
+        DatabaseBackend::MySql => format!(
+            r"
+            CREATE TABLE IF NOT EXISTS `{LEASE_TABLE}` (
+                lock_key VARCHAR(255) PRIMARY KEY,
+                holder VARCHAR(64) NOT NULL,
+                fencing_token BIGINT NOT NULL,
+                expires_at BIGINT NOT NULL
+            )
+            "
+        ),
+        DatabaseBackend::Sqlite => format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS "{LEASE_TABLE}" (
+                lock_key TEXT PRIMARY KEY,
+                holder TEXT NOT NULL,
+                fencing_token INTEGER NOT NULL,
+                expires_at INTEGER NOT NULL
+            )
+            "#
+        ),
+    };
+
+    conn.execute(Statement::from_string(backend, sql)).await?;
     Ok(())
 }
 
-/// Query all applied migrations for a module.
-async fn get_applied_migrations(
+/// Name of the runtime-owned table holding audit records (see [`crate::audit`]).
+pub(crate) const AUDIT_TABLE: &str = "modkit_audit_log";
+
+/// Create the audit log table and its `occurred_at` index if they don't exist.
+pub(crate) async fn ensure_audit_table(conn: &impl ConnectionTrait) -> Result<(), DbErr> {
+    let backend = conn.get_database_backend();
+
+    let statements = match backend {
+        DatabaseBackend::Postgres => vec![
+            format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS "{AUDIT_TABLE}" (
+                    id BIGSERIAL PRIMARY KEY,
+                    occurred_at TIMESTAMPTZ NOT NULL,
+                    actor_id UUID NULL,
+                    tenant_id UUID NULL,
+                    entity VARCHAR(255) NOT NULL,
+                    action VARCHAR(16) NOT NULL,
+                    record_id TEXT NOT NULL,
+                    changes TEXT NOT NULL
+                )
+                "#
+            ),
+            format!(
+                r#"CREATE INDEX IF NOT EXISTS "ix_{AUDIT_TABLE}_occurred_at" ON "{AUDIT_TABLE}" (occurred_at)"#
+            ),
+        ],
+        // MySQL has no `CREATE INDEX IF NOT EXISTS`; declare the index inline.
+        DatabaseBackend::MySql => vec![format!(
+            r"
+            CREATE TABLE IF NOT EXISTS `{AUDIT_TABLE}` (
+                id BIGINT AUTO_INCREMENT PRIMARY KEY,
+                occurred_at TIMESTAMP(6) NOT NULL,
+                actor_id BINARY(16) NULL,
+                tenant_id BINARY(16) NULL,
+                entity VARCHAR(255) NOT NULL,
+                action VARCHAR(16) NOT NULL,
+                record_id TEXT NOT NULL,
+                changes LONGTEXT NOT NULL,
+                INDEX ix_{AUDIT_TABLE}_occurred_at (occurred_at)
+            )
+            "
+        )],
+        DatabaseBackend::Sqlite => vec![
+            format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS "{AUDIT_TABLE}" (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    occurred_at TEXT NOT NULL,
+                    actor_id BLOB NULL,
+                    tenant_id BLOB NULL,
+                    entity TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    record_id TEXT NOT NULL,
+                    changes TEXT NOT NULL
+                )
+                "#
+            ),
+            format!(
+                r#"CREATE INDEX IF NOT EXISTS "ix_{AUDIT_TABLE}_occurred_at" ON "{AUDIT_TABLE}" (occurred_at)"#
+            ),
+        ],
+    };
+
+    for sql in statements {
+        conn.execute(Statement::from_string(backend, sql)).await?;
+    }
+    Ok(())
+}
+
+/// Load the migration history of a module, keyed by migration name.
+async fn load_history(
     conn: &impl ConnectionTrait,
     table_name: &str,
     module_name: &str,
-) -> Result<HashSet<String>, MigrationError> {
+) -> Result<HashMap<String, HistoryRecord>, MigrationError> {
     let backend = conn.get_database_backend();
+    let table = quote_table(backend, table_name);
 
     let sql = match backend {
-        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
-            format!(r#"SELECT version FROM "{table_name}""#)
+        DatabaseBackend::Postgres => format!(
+            "SELECT version, CAST(applied_at AS TEXT) AS applied_at, checksum, \
+             CAST(reverted_at AS TEXT) AS reverted_at FROM {table}"
+        ),
+        DatabaseBackend::MySql => format!(
+            "SELECT version, CAST(applied_at AS CHAR) AS applied_at, checksum, \
+             CAST(reverted_at AS CHAR) AS reverted_at FROM {table}"
+        ),
+        DatabaseBackend::Sqlite => {
+            format!("SELECT version, applied_at, checksum, reverted_at FROM {table}")
         }
-        DatabaseBackend::MySql => format!(r"SELECT version FROM `{table_name}`"),
     };
 
-    let records: Vec<MigrationRecord> =
-        MigrationRecord::find_by_statement(Statement::from_string(backend, sql))
+    let records: Vec<HistoryRecord> =
+        HistoryRecord::find_by_statement(Statement::from_string(backend, sql))
             .all(conn)
             .await
             .map_err(|e| MigrationError::QueryHistory {
@@ -194,29 +495,48 @@ async fn get_applied_migrations(
                 source: e,
             })?;
 
-    Ok(records.into_iter().map(|r| r.version).collect())
+    Ok(records
+        .into_iter()
+        .map(|r| (r.version.clone(), r))
+        .collect())
 }
 
 /// Record a migration as applied.
+///
+/// A migration that was reverted earlier already has a row (`reapply`); it is updated in
+/// place so the history keeps one row per migration.
 async fn record_migration(
     conn: &impl ConnectionTrait,
     table_name: &str,
     module_name: &str,
     migration_name: &str,
+    checksum: Option<&str>,
+    reapply: bool,
 ) -> Result<ExecResult, MigrationError> {
     let backend = conn.get_database_backend();
+    let table = quote_table(backend, table_name);
 
-    let sql = match backend {
-        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
-            format!(r#"INSERT INTO "{table_name}" (version) VALUES ($1)"#)
+    let sql = match (backend, reapply) {
+        (DatabaseBackend::Postgres | DatabaseBackend::Sqlite, false) => {
+            format!("INSERT INTO {table} (checksum, version) VALUES ($1, $2)")
+        }
+        (DatabaseBackend::MySql, false) => {
+            format!("INSERT INTO {table} (checksum, version) VALUES (?, ?)")
         }
-        DatabaseBackend::MySql => format!(r"INSERT INTO `{table_name}` (version) VALUES (?)"),
+        (DatabaseBackend::Postgres | DatabaseBackend::Sqlite, true) => format!(
+            "UPDATE {table} SET checksum = $1, applied_at = CURRENT_TIMESTAMP, \
+             reverted_at = NULL WHERE version = $2"
+        ),
+        (DatabaseBackend::MySql, true) => format!(
+            "UPDATE {table} SET checksum = ?, applied_at = CURRENT_TIMESTAMP, \
+             reverted_at = NULL WHERE version = ?"
+        ),
     };
 
     conn.execute(Statement::from_sql_and_values(
         backend,
         &sql,
-        [migration_name.into()],
+        [checksum.map(str::to_owned).into(), migration_name.into()],
     ))
     .await
     .map_err(|e| MigrationError::RecordFailed {
@@ -226,6 +546,122 @@ async fn record_migration(
     })
 }
 
+/// Record a migration as reverted.
+async fn record_reversal(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    migration_name: &str,
+) -> Result<ExecResult, DbErr> {
+    let backend = conn.get_database_backend();
+    let table = quote_table(backend, table_name);
+
+    let sql = match backend {
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+            format!("UPDATE {table} SET reverted_at = CURRENT_TIMESTAMP WHERE version = $1")
+        }
+        DatabaseBackend::MySql => {
+            format!("UPDATE {table} SET reverted_at = CURRENT_TIMESTAMP WHERE version = ?")
+        }
+    };
+
+    conn.execute(Statement::from_sql_and_values(
+        backend,
+        &sql,
+        [migration_name.into()],
+    ))
+    .await
+}
+
+/// Proxy backend that records every executed statement and reports success.
+///
+/// Reads have no canned results and fail, so steps that inspect the schema cannot be
+/// rendered.
+#[derive(Debug, Default)]
+struct RecordingProxy {
+    statements: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl ProxyDatabaseTrait for RecordingProxy {
+    async fn query(&self, statement: Statement) -> Result<Vec<ProxyRow>, DbErr> {
+        Err(DbErr::Custom(format!(
+            "cannot render a step that reads from the database: {statement}"
+        )))
+    }
+
+    async fn execute(&self, statement: Statement) -> Result<ProxyExecResult, DbErr> {
+        self.statements
+            .lock()
+            .map_err(|_| DbErr::Custom("statement recorder poisoned".to_owned()))?
+            .push(statement.to_string());
+        Ok(ProxyExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        })
+    }
+}
+
+/// Render the statements a migration step executes, without a database.
+///
+/// The step runs against a proxy connection that records every statement and reports
+/// success. Reads have no canned results, so steps that inspect the schema fail here.
+async fn render_step(
+    backend: DatabaseBackend,
+    migration: &dyn MigrationTrait,
+    step: Step,
+) -> Result<Vec<String>, DbErr> {
+    let recorder = RecordingProxy::default();
+    let statements = std::sync::Arc::clone(&recorder.statements);
+    let conn = Database::connect_proxy(backend, std::sync::Arc::new(Box::new(recorder))).await?;
+    {
+        let manager = SchemaManager::new(&conn);
+        match step {
+            Step::Up => migration.up(&manager).await?,
+            Step::Down => migration.down(&manager).await?,
+        }
+    }
+
+    let statements = statements
+        .lock()
+        .map_err(|_| DbErr::Custom("statement recorder poisoned".to_owned()))?
+        .clone();
+    Ok(statements)
+}
+
+/// Checksum of the statements a migration's `up` step executes, if it can be rendered.
+async fn migration_checksum(
+    backend: DatabaseBackend,
+    migration: &dyn MigrationTrait,
+) -> Option<String> {
+    let statements = render_step(backend, migration, Step::Up).await.ok()?;
+    Some(format!(
+        "{:016x}",
+        xxh3_64(statements.join(";\n").as_bytes())
+    ))
+}
+
+/// Whether an applied migration was edited since; unknown checksums never count as drift.
+fn is_drifted(recorded: Option<&str>, current: Option<&str>) -> bool {
+    matches!((recorded, current), (Some(recorded), Some(current)) if recorded != current)
+}
+
+/// Reject duplicate migration names (security/correctness: deterministic + idempotent).
+fn ensure_unique_names(
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<(), MigrationError> {
+    let mut seen = HashSet::new();
+    for m in migrations {
+        if !seen.insert(m.name()) {
+            return Err(MigrationError::DuplicateMigrationName {
+                module: module_name.to_owned(),
+                name: m.name().to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Run migrations for a specific module using a `Db`.
 ///
 /// This is the main entry point for the runtime to execute module migrations.
@@ -268,10 +704,12 @@ pub async fn run_migrations_for_module(
 /// Run migrations for a specific module (internal implementation).
 ///
 /// This function:
-/// 1. Creates a per-module migration table if it doesn't exist.
-/// 2. Queries which migrations have already been applied.
-/// 3. Sorts migrations by name for deterministic ordering.
-/// 4. Executes pending migrations and records them.
+/// 1. Creates the runtime-owned lease table if it doesn't exist.
+/// 2. Creates a per-module migration table if it doesn't exist.
+/// 3. Loads the module's migration history.
+/// 4. Sorts migrations by name for deterministic ordering.
+/// 5. Executes pending (never applied or reverted) migrations and records them with
+///    their checksum, warning about applied migrations whose checksum changed.
 ///
 /// # Arguments
 ///
@@ -291,6 +729,13 @@ async fn run_module_migrations<C>(
 where
     C: ConnectionTrait + TransactionTrait,
 {
+    ensure_lease_table(conn)
+        .await
+        .map_err(|source| MigrationError::CreateLeaseTable { source })?;
+    ensure_audit_table(conn)
+        .await
+        .map_err(|source| MigrationError::CreateAuditTable { source })?;
+
     if migrations.is_empty() {
         debug!(module = module_name, "No migrations to run");
         return Ok(MigrationResult {
@@ -300,17 +745,7 @@ where
         });
     }
 
-    // Reject duplicate migration names early (security/correctness: deterministic + idempotent)
-    let mut seen = HashSet::new();
-    for m in &migrations {
-        let n = m.name().to_owned();
-        if !seen.insert(n.clone()) {
-            return Err(MigrationError::DuplicateMigrationName {
-                module: module_name.to_owned(),
-                name: n,
-            });
-        }
-    }
+    ensure_unique_names(module_name, &migrations)?;
 
     // Get the per-module migration table name
     let table_name = migration_table_name(module_name);
@@ -318,8 +753,9 @@ where
     // Ensure the migration table exists
     ensure_migration_table(conn, &table_name, module_name).await?;
 
-    // Get already-applied migrations
-    let applied = get_applied_migrations(conn, &table_name, module_name).await?;
+    // Get the recorded history (applied and reverted migrations)
+    let history = load_history(conn, &table_name, module_name).await?;
+    let backend = conn.get_database_backend();
 
     // Sort migrations by name for deterministic ordering
     let mut sorted_migrations: Vec<_> = migrations.into_iter().collect();
@@ -333,8 +769,17 @@ where
 
     for migration in sorted_migrations {
         let name = migration.name().to_owned();
-
-        if applied.contains(&name) {
+        let checksum = migration_checksum(backend, migration.as_ref()).await;
+        let record = history.get(&name);
+
+        if let Some(record) = record.filter(|r| r.is_applied()) {
+            if is_drifted(record.checksum.as_deref(), checksum.as_deref()) {
+                warn!(
+                    module = module_name,
+                    migration = %name,
+                    "Applied migration was modified after it ran; its changes are not re-applied"
+                );
+            }
             debug!(
                 module = module_name,
                 migration = %name,
@@ -350,49 +795,15 @@ where
             "Applying migration"
         );
 
-        // Best-effort atomicity:
-        // Try to wrap `up()` + history record into an explicit transaction.
-        // Note: Some backends (or specific DDL) may auto-commit; this is still best-effort.
-        let txn = conn
-            .begin()
-            .await
-            .map_err(|e| MigrationError::MigrationFailed {
-                module: module_name.to_owned(),
-                migration: name.clone(),
-                source: e,
-            })?;
-
-        let manager = sea_orm_migration::SchemaManager::new(&txn);
-        let res: Result<(), MigrationError> = (async {
-            migration
-                .up(&manager)
-                .await
-                .map_err(|e| MigrationError::MigrationFailed {
-                    module: module_name.to_owned(),
-                    migration: name.clone(),
-                    source: e,
-                })?;
-
-            record_migration(&txn, &table_name, module_name, &name).await?;
-            Ok(())
-        })
-        .await;
-
-        match res {
-            Ok(()) => {
-                txn.commit()
-                    .await
-                    .map_err(|e| MigrationError::MigrationFailed {
-                        module: module_name.to_owned(),
-                        migration: name.clone(),
-                        source: e,
-                    })?;
-            }
-            Err(err) => {
-                _ = txn.rollback().await;
-                return Err(err);
-            }
-        }
+        apply_migration(
+            conn,
+            &table_name,
+            module_name,
+            migration.as_ref(),
+            checksum.as_deref(),
+            record.is_some(),
+        )
+        .await?;
 
         info!(
             module = module_name,
@@ -414,6 +825,83 @@ where
     Ok(result)
 }
 
+/// Apply one migration and record it.
+///
+/// Best-effort atomicity: `up()` and the history record run in an explicit transaction.
+/// Some backends (or specific DDL) may auto-commit; this is still best-effort.
+async fn apply_migration<C>(
+    conn: &C,
+    table_name: &str,
+    module_name: &str,
+    migration: &dyn MigrationTrait,
+    checksum: Option<&str>,
+    reapply: bool,
+) -> Result<(), MigrationError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let name = migration.name();
+    let failed = |e| MigrationError::MigrationFailed {
+        module: module_name.to_owned(),
+        migration: name.to_owned(),
+        source: e,
+    };
+
+    let txn = conn.begin().await.map_err(failed)?;
+
+    let manager = SchemaManager::new(&txn);
+    let res: Result<(), MigrationError> = (async {
+        migration.up(&manager).await.map_err(failed)?;
+        record_migration(&txn, table_name, module_name, name, checksum, reapply).await?;
+        Ok(())
+    })
+    .await;
+
+    match res {
+        Ok(()) => txn.commit().await.map_err(failed),
+        Err(err) => {
+            _ = txn.rollback().await;
+            Err(err)
+        }
+    }
+}
+
+/// Run one migration's `down` step and mark it reverted, in a single transaction.
+async fn revert_migration<C>(
+    conn: &C,
+    table_name: &str,
+    module_name: &str,
+    migration: &dyn MigrationTrait,
+) -> Result<(), MigrationError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let name = migration.name();
+    let failed = |e| MigrationError::RevertFailed {
+        module: module_name.to_owned(),
+        migration: name.to_owned(),
+        source: e,
+    };
+
+    let txn = conn.begin().await.map_err(failed)?;
+
+    let manager = SchemaManager::new(&txn);
+    let res: Result<(), DbErr> = (async {
+        migration.down(&manager).await?;
+        record_reversal(&txn, table_name, name).await?;
+        Ok(())
+    })
+    .await;
+
+    match res {
+        Ok(()) => txn.commit().await.map_err(failed),
+        Err(err) => {
+            _ = txn.rollback().await;
+            Err(failed(err))
+        }
+    }
+}
+
 /// Run migrations for testing purposes.
 ///
 /// This is a convenience function for unit tests that don't need per-module
@@ -479,22 +967,16 @@ pub async fn get_pending_migrations(
     get_pending_migrations_internal(&conn, module_name, migrations).await
 }
 
-/// Internal implementation for checking pending migrations.
-async fn get_pending_migrations_internal(
+/// Check whether a module's history table exists.
+///
+/// Propagates DB errors rather than treating them as "table missing".
+async fn history_table_exists(
     conn: &impl ConnectionTrait,
+    table_name: &str,
     module_name: &str,
-    migrations: &[Box<dyn MigrationTrait>],
-) -> Result<Vec<String>, MigrationError> {
-    if migrations.is_empty() {
-        return Ok(vec![]);
-    }
-
-    let table_name = migration_table_name(module_name);
-
-    // Check if table exists - if not, all migrations are pending.
-    // Propagate DB errors rather than treating them as "table missing".
+) -> Result<bool, MigrationError> {
     let backend = conn.get_database_backend();
-    let table_exists = match backend {
+    let exists = match backend {
         DatabaseBackend::Postgres => {
             let sql = format!(
                 "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = '{table_name}')"
@@ -539,19 +1021,275 @@ async fn get_pending_migrations_internal(
         }
     };
 
-    if !table_exists {
-        return Ok(migrations.iter().map(|m| m.name().to_owned()).collect());
+    Ok(exists)
+}
+
+/// Load a module's history without creating the table; a missing table means no history.
+///
+/// An existing table is brought up to the current layout first, which only adds nullable
+/// columns.
+async fn read_history(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<HashMap<String, HistoryRecord>, MigrationError> {
+    if !history_table_exists(conn, table_name, module_name).await? {
+        return Ok(HashMap::new());
     }
+    upgrade_migration_table(conn, table_name, module_name).await?;
+    load_history(conn, table_name, module_name).await
+}
 
-    let applied = get_applied_migrations(conn, &table_name, module_name).await?;
+/// Internal implementation for checking pending migrations.
+async fn get_pending_migrations_internal(
+    conn: &impl ConnectionTrait,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<String>, MigrationError> {
+    if migrations.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let table_name = migration_table_name(module_name);
+    let history = read_history(conn, &table_name, module_name).await?;
 
     Ok(migrations
         .iter()
-        .filter(|m| !applied.contains(m.name()))
+        .filter(|m| !history.get(m.name()).is_some_and(HistoryRecord::is_applied))
         .map(|m| m.name().to_owned())
         .collect())
 }
 
+/// Report applied and pending migrations of a module, oldest first.
+///
+/// Applied migrations whose statements changed since they ran are flagged as drifted.
+/// Migrations recorded as applied that the module no longer provides are listed last
+/// as [`MigrationState::Unknown`]. Nothing is created or applied.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if migration names are duplicated or the history cannot
+/// be queried.
+pub async fn migration_status(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let conn = db.sea_internal();
+    migration_status_internal(&conn, module_name, migrations).await
+}
+
+async fn migration_status_internal(
+    conn: &impl ConnectionTrait,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    ensure_unique_names(module_name, migrations)?;
+
+    let table_name = migration_table_name(module_name);
+    let mut history = read_history(conn, &table_name, module_name).await?;
+    let backend = conn.get_database_backend();
+
+    let mut sorted: Vec<&dyn MigrationTrait> = migrations.iter().map(AsRef::as_ref).collect();
+    sorted.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut statuses = Vec::with_capacity(sorted.len());
+    for migration in sorted {
+        let Some(record) = history.remove(migration.name()) else {
+            statuses.push(MigrationStatus {
+                name: migration.name().to_owned(),
+                state: MigrationState::Pending,
+                applied_at: None,
+                reverted_at: None,
+                drifted: false,
+            });
+            continue;
+        };
+
+        let (state, drifted) = if record.is_applied() {
+            let current = migration_checksum(backend, migration).await;
+            let drifted = is_drifted(record.checksum.as_deref(), current.as_deref());
+            (MigrationState::Applied, drifted)
+        } else {
+            (MigrationState::Pending, false)
+        };
+        statuses.push(MigrationStatus {
+            name: record.version,
+            state,
+            applied_at: Some(record.applied_at),
+            reverted_at: record.reverted_at,
+            drifted,
+        });
+    }
+
+    let mut orphans: Vec<HistoryRecord> = history
+        .into_values()
+        .filter(HistoryRecord::is_applied)
+        .collect();
+    orphans.sort_by(|a, b| a.version.cmp(&b.version));
+    statuses.extend(orphans.into_iter().map(|record| MigrationStatus {
+        name: record.version,
+        state: MigrationState::Unknown,
+        applied_at: Some(record.applied_at),
+        reverted_at: None,
+        drifted: false,
+    }));
+
+    Ok(statuses)
+}
+
+/// Render the statements that running a module's pending migrations would execute.
+///
+/// This is the dry-run counterpart of [`run_migrations_for_module`]: the history is read
+/// but nothing is created, applied or recorded.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if the history cannot be queried, or
+/// [`MigrationError::PreviewFailed`] for a migration that reads from the database.
+pub async fn plan_migrations_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationPlan>, MigrationError> {
+    let conn = db.sea_internal();
+    ensure_unique_names(module_name, migrations)?;
+
+    let pending: HashSet<String> = get_pending_migrations_internal(&conn, module_name, migrations)
+        .await?
+        .into_iter()
+        .collect();
+    let mut sorted: Vec<&dyn MigrationTrait> = migrations
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|m| pending.contains(m.name()))
+        .collect();
+    sorted.sort_by(|a, b| a.name().cmp(b.name()));
+
+    plan_steps(conn.get_database_backend(), module_name, sorted, Step::Up).await
+}
+
+/// Revert a module's applied migrations that sort after `target`, newest first.
+///
+/// Each migration's `down` step runs in its own transaction together with marking the
+/// migration reverted in the history table, so an interrupted rollback leaves the history
+/// consistent with what was undone. `target` itself stays applied. Reverted migrations
+/// become pending and are applied again by the next [`run_migrations_for_module`].
+///
+/// Returns the names of the reverted migrations in the order they were reverted.
+///
+/// # Errors
+///
+/// Returns [`MigrationError::UnknownMigration`] if `target` is not one of `migrations`,
+/// or `Err(MigrationError)` if the history cannot be queried or a `down` step fails.
+pub async fn rollback_migrations_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: Vec<Box<dyn MigrationTrait>>,
+    target: &str,
+) -> Result<Vec<String>, MigrationError> {
+    let conn = db.sea_internal();
+    let table_name = migration_table_name(module_name);
+    let to_revert =
+        rollback_candidates(&conn, &table_name, module_name, &migrations, target).await?;
+
+    let mut reverted = Vec::with_capacity(to_revert.len());
+    for migration in to_revert {
+        info!(
+            module = module_name,
+            migration = %migration.name(),
+            "Reverting migration"
+        );
+        revert_migration(&conn, &table_name, module_name, migration).await?;
+        reverted.push(migration.name().to_owned());
+    }
+
+    info!(
+        module = module_name,
+        reverted = reverted.len(),
+        to = target,
+        "Migration rollback complete"
+    );
+
+    Ok(reverted)
+}
+
+/// Render the statements [`rollback_migrations_for_module`] would execute.
+///
+/// # Errors
+///
+/// Same as [`rollback_migrations_for_module`], plus [`MigrationError::PreviewFailed`]
+/// for a `down` step that reads from the database.
+pub async fn plan_rollback_for_module(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+    target: &str,
+) -> Result<Vec<MigrationPlan>, MigrationError> {
+    let conn = db.sea_internal();
+    let table_name = migration_table_name(module_name);
+    let to_revert =
+        rollback_candidates(&conn, &table_name, module_name, migrations, target).await?;
+    plan_steps(
+        conn.get_database_backend(),
+        module_name,
+        to_revert,
+        Step::Down,
+    )
+    .await
+}
+
+/// Applied migrations newer than `target`, newest first.
+async fn rollback_candidates<'m>(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+    migrations: &'m [Box<dyn MigrationTrait>],
+    target: &str,
+) -> Result<Vec<&'m dyn MigrationTrait>, MigrationError> {
+    ensure_unique_names(module_name, migrations)?;
+    if !migrations.iter().any(|m| m.name() == target) {
+        return Err(MigrationError::UnknownMigration {
+            module: module_name.to_owned(),
+            name: target.to_owned(),
+        });
+    }
+
+    let history = read_history(conn, table_name, module_name).await?;
+    let mut newer: Vec<&dyn MigrationTrait> = migrations
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|m| m.name() > target)
+        .filter(|m| history.get(m.name()).is_some_and(HistoryRecord::is_applied))
+        .collect();
+    newer.sort_by(|a, b| b.name().cmp(a.name()));
+    Ok(newer)
+}
+
+/// Render one step of each migration, in the given order.
+async fn plan_steps(
+    backend: DatabaseBackend,
+    module_name: &str,
+    migrations: Vec<&dyn MigrationTrait>,
+    step: Step,
+) -> Result<Vec<MigrationPlan>, MigrationError> {
+    let mut plans = Vec::with_capacity(migrations.len());
+    for migration in migrations {
+        let statements = render_step(backend, migration, step).await.map_err(|e| {
+            MigrationError::PreviewFailed {
+                module: module_name.to_owned(),
+                migration: migration.name().to_owned(),
+                source: e,
+            }
+        })?;
+        plans.push(MigrationPlan {
+            name: migration.name().to_owned(),
+            statements,
+        });
+    }
+    Ok(plans)
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -621,7 +1359,15 @@ mod tests {
             Ok(())
         }
 
-        async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+            let backend = manager.get_database_backend();
+            let table_name = format!("test_{}", self.name.replace('-', "_"));
+            let sql = format!("DROP TABLE IF EXISTS {}", quote_table(backend, &table_name));
+
+            manager
+                .get_connection()
+                .execute(Statement::from_string(backend, sql))
+                .await?;
             Ok(())
         }
     }
@@ -650,6 +1396,54 @@ mod tests {
             assert!(result.applied_names.is_empty());
         }
 
+        #[tokio::test]
+        async fn test_lease_table_created_by_runner() {
+            let db = setup_test_db().await;
+
+            run_migrations_for_module(&db, "test_module", vec![])
+                .await
+                .expect("Migration should succeed");
+
+            let conn = db.sea_internal();
+            let row = conn
+                .query_one(Statement::from_string(
+                    DatabaseBackend::Sqlite,
+                    format!(
+                        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{LEASE_TABLE}'"
+                    ),
+                ))
+                .await
+                .expect("Query should succeed")
+                .expect("Row should exist");
+            assert_eq!(row.try_get_by_index::<i32>(0).unwrap(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_audit_table_created_by_runner() {
+            let db = setup_test_db().await;
+
+            run_migrations_for_module(&db, "test_module", vec![])
+                .await
+                .expect("Migration should succeed");
+            // A second run must tolerate the existing table and index.
+            run_migrations_for_module(&db, "other_module", vec![])
+                .await
+                .expect("Migration should succeed");
+
+            let conn = db.sea_internal();
+            let row = conn
+                .query_one(Statement::from_string(
+                    DatabaseBackend::Sqlite,
+                    format!(
+                        "SELECT COUNT(*) FROM sqlite_master WHERE name IN ('{AUDIT_TABLE}', 'ix_{AUDIT_TABLE}_occurred_at')"
+                    ),
+                ))
+                .await
+                .expect("Query should succeed")
+                .expect("Row should exist");
+            assert_eq!(row.try_get_by_index::<i32>(0).unwrap(), 2);
+        }
+
         #[tokio::test]
         async fn test_run_module_migrations_single() {
             let db = setup_test_db().await;
@@ -875,5 +1669,245 @@ mod tests {
 
             assert_eq!(result.applied, 1);
         }
+
+        fn test_migrations(names: &[&str]) -> Vec<Box<dyn MigrationTrait>> {
+            names
+                .iter()
+                .map(|name| {
+                    Box::new(TestMigration {
+                        name: (*name).to_owned(),
+                    }) as Box<dyn MigrationTrait>
+                })
+                .collect()
+        }
+
+        async fn sqlite_table_exists(db: &Db, table_name: &str) -> bool {
+            let conn = db.sea_internal();
+            let row = conn
+                .query_one(Statement::from_string(
+                    DatabaseBackend::Sqlite,
+                    format!(
+                        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{table_name}'"
+                    ),
+                ))
+                .await
+                .expect("Query should succeed")
+                .expect("Row should exist");
+            row.try_get_by_index::<i32>(0).unwrap() == 1
+        }
+
+        /// Same name as a `TestMigration`, but creates a different table layout.
+        struct EditedMigration;
+
+        impl MigrationName for EditedMigration {
+            fn name(&self) -> &'static str {
+                "m001_first"
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl MigrationTrait for EditedMigration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                manager
+                    .get_connection()
+                    .execute_unprepared(
+                        "CREATE TABLE IF NOT EXISTS \"test_m001_first\" (id INTEGER PRIMARY KEY, note TEXT)",
+                    )
+                    .await?;
+                Ok(())
+            }
+        }
+
+        #[tokio::test]
+        async fn test_migration_status() {
+            let db = setup_test_db().await;
+            let module_name = "test_status";
+            let migrations = test_migrations(&["m002_second", "m001_first"]);
+
+            let status = migration_status(&db, module_name, &migrations)
+                .await
+                .expect("Status without history table should succeed");
+            assert!(
+                status
+                    .iter()
+                    .all(|s| s.state == MigrationState::Pending && s.applied_at.is_none())
+            );
+
+            run_migrations_for_module(&db, module_name, test_migrations(&["m001_first"]))
+                .await
+                .expect("Should succeed");
+
+            let status = migration_status(&db, module_name, &migrations)
+                .await
+                .expect("Should succeed");
+            assert_eq!(status.len(), 2);
+            assert_eq!(status[0].name, "m001_first");
+            assert_eq!(status[0].state, MigrationState::Applied);
+            assert!(status[0].applied_at.is_some());
+            assert!(!status[0].drifted);
+            assert_eq!(status[1].name, "m002_second");
+            assert_eq!(status[1].state, MigrationState::Pending);
+
+            // A migration dropped from the module is still reported.
+            let status = migration_status(&db, module_name, &test_migrations(&["m002_second"]))
+                .await
+                .expect("Should succeed");
+            assert_eq!(status[1].name, "m001_first");
+            assert_eq!(status[1].state, MigrationState::Unknown);
+        }
+
+        #[tokio::test]
+        async fn test_plan_migrations_does_not_apply() {
+            let db = setup_test_db().await;
+            let module_name = "test_plan";
+
+            run_migrations_for_module(&db, module_name, test_migrations(&["m001_first"]))
+                .await
+                .expect("Should succeed");
+
+            let migrations = test_migrations(&["m001_first", "m002_second"]);
+            let plans = plan_migrations_for_module(&db, module_name, &migrations)
+                .await
+                .expect("Plan should succeed");
+
+            assert_eq!(plans.len(), 1);
+            assert_eq!(plans[0].name, "m002_second");
+            assert_eq!(
+                plans[0].statements,
+                vec![r#"CREATE TABLE IF NOT EXISTS "test_m002_second" (id INTEGER PRIMARY KEY)"#]
+            );
+            assert!(!sqlite_table_exists(&db, "test_m002_second").await);
+
+            let pending = get_pending_migrations(&db, module_name, &migrations)
+                .await
+                .expect("Should succeed");
+            assert_eq!(pending, vec!["m002_second"]);
+        }
+
+        #[tokio::test]
+        async fn test_rollback_reverts_newer_migrations() {
+            let db = setup_test_db().await;
+            let module_name = "test_rollback";
+            let names = ["m001_first", "m002_second", "m003_third"];
+
+            run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+
+            let plans =
+                plan_rollback_for_module(&db, module_name, &test_migrations(&names), "m001_first")
+                    .await
+                    .expect("Plan should succeed");
+            assert_eq!(
+                plans.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+                vec!["m003_third", "m002_second"]
+            );
+            assert!(sqlite_table_exists(&db, "test_m003_third").await);
+
+            let reverted = rollback_migrations_for_module(
+                &db,
+                module_name,
+                test_migrations(&names),
+                "m001_first",
+            )
+            .await
+            .expect("Rollback should succeed");
+            assert_eq!(reverted, vec!["m003_third", "m002_second"]);
+            assert!(sqlite_table_exists(&db, "test_m001_first").await);
+            assert!(!sqlite_table_exists(&db, "test_m002_second").await);
+            assert!(!sqlite_table_exists(&db, "test_m003_third").await);
+
+            let status = migration_status(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(status[0].state, MigrationState::Applied);
+            assert_eq!(status[1].state, MigrationState::Pending);
+            assert!(status[1].reverted_at.is_some());
+
+            // Reverted migrations are applied again by a normal run.
+            let result = run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(result.applied_names, vec!["m002_second", "m003_third"]);
+            assert!(sqlite_table_exists(&db, "test_m003_third").await);
+
+            let status = migration_status(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert!(status.iter().all(|s| s.state == MigrationState::Applied));
+            assert!(status.iter().all(|s| s.reverted_at.is_none()));
+        }
+
+        #[tokio::test]
+        async fn test_rollback_unknown_target_rejected() {
+            let db = setup_test_db().await;
+
+            let err = rollback_migrations_for_module(
+                &db,
+                "test_rollback_unknown",
+                test_migrations(&["m001_first"]),
+                "m000_missing",
+            )
+            .await
+            .unwrap_err();
+
+            assert!(
+                matches!(err, MigrationError::UnknownMigration { name, .. } if name == "m000_missing")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_edited_migration_reported_as_drifted() {
+            let db = setup_test_db().await;
+            let module_name = "test_drift";
+
+            run_migrations_for_module(&db, module_name, test_migrations(&["m001_first"]))
+                .await
+                .expect("Should succeed");
+
+            let edited: Vec<Box<dyn MigrationTrait>> = vec![Box::new(EditedMigration)];
+            let status = migration_status(&db, module_name, &edited)
+                .await
+                .expect("Should succeed");
+            assert_eq!(status[0].state, MigrationState::Applied);
+            assert!(status[0].drifted);
+
+            // Drift is reported, never re-applied.
+            let result = run_migrations_for_module(&db, module_name, edited)
+                .await
+                .expect("Should succeed");
+            assert_eq!(result.skipped, 1);
+        }
+
+        #[tokio::test]
+        async fn test_legacy_history_table_upgraded() {
+            let db = setup_test_db().await;
+            let module_name = "test_legacy";
+            let table_name = migration_table_name(module_name);
+
+            let conn = db.sea_internal();
+            for sql in [
+                format!(
+                    r#"CREATE TABLE "{table_name}" (version TEXT PRIMARY KEY, applied_at TEXT NOT NULL DEFAULT (datetime('now')))"#
+                ),
+                format!(r#"INSERT INTO "{table_name}" (version) VALUES ('m001_first')"#),
+            ] {
+                conn.execute(Statement::from_string(DatabaseBackend::Sqlite, sql))
+                    .await
+                    .expect("Legacy setup should succeed");
+            }
+
+            let migrations = test_migrations(&["m001_first", "m002_second"]);
+            let status = migration_status(&db, module_name, &migrations)
+                .await
+                .expect("Should succeed");
+            assert_eq!(status[0].state, MigrationState::Applied);
+            assert!(!status[0].drifted, "rows without a checksum never drift");
+
+            let result = run_migrations_for_module(&db, module_name, migrations)
+                .await
+                .expect("Should succeed");
+            assert_eq!(result.applied_names, vec!["m002_second"]);
+        }
     }
 }
@@ -860,7 +860,7 @@ where
     s = s.limit(fetch);
 
     #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
+    let mut rows = match DBRunnerInternal::as_seaorm_read(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
         SeaOrmRunner::Tx(tx) => s.all(tx).await,
     }
@@ -524,7 +524,7 @@ where
     s = s.limit(fetch);
 
     #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
+    let mut rows = match DBRunnerInternal::as_seaorm_read(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
         SeaOrmRunner::Tx(tx) => s.all(tx).await,
     }
@@ -700,7 +700,7 @@ where
         k: cursor_keys,
         o: primary_dir,
         s: order.to_signed_tokens(),
-        f: filter_hash.map(ToString::to_string),
+        f: filter_hash.map(str::to_owned),
         d: direction.to_owned(),
     })
 }
@@ -1,7 +1,10 @@
 //! Database connection options and configuration types.
 
-use crate::config::{DbConnConfig, DbEngineCfg, GlobalDatabaseConfig, PoolCfg};
-use crate::{DbError, DbHandle, Result};
+use std::sync::Arc;
+
+use crate::config::{DbConnConfig, DbEngineCfg, GlobalDatabaseConfig, PoolCfg, ReplicasCfg};
+use crate::replicas::{DEFAULT_CHECK_INTERVAL, ReplicaSet};
+use crate::{DbEngine, DbError, DbHandle, DbManager, Result};
 
 // Pool configuration moved to config.rs
 
@@ -90,6 +93,9 @@ impl DbConnectOptions {
                     engine: crate::DbEngine::Sqlite,
                     dsn: format!("sqlite://{filename}"),
                     sea,
+                    lock_backend: crate::LockBackend::default(),
+                    lease_table_ready: std::sync::Arc::default(),
+                    replicas: None,
                 };
 
                 Ok(handle)
@@ -111,6 +117,9 @@ impl DbConnectOptions {
                         opts.get_database().unwrap_or("")
                     ),
                     sea,
+                    lock_backend: crate::LockBackend::default(),
+                    lease_table_ready: std::sync::Arc::default(),
+                    replicas: None,
                 };
 
                 Ok(handle)
@@ -127,6 +136,9 @@ impl DbConnectOptions {
                     engine: crate::DbEngine::MySql,
                     dsn: "mysql://<redacted>@...".to_owned(),
                     sea,
+                    lock_backend: crate::LockBackend::default(),
+                    lease_table_ready: std::sync::Arc::default(),
+                    replicas: None,
                 };
 
                 Ok(handle)
@@ -256,6 +268,20 @@ pub(crate) async fn build_db_handle(
     mut cfg: DbConnConfig,
     _global: Option<&GlobalDatabaseConfig>,
 ) -> Result<DbHandle> {
+    let replicas = cfg.replicas.take();
+    let handle = connect_handle(cfg.clone()).await?;
+
+    match replicas {
+        Some(replicas) if !replicas.nodes.is_empty() => {
+            let set = build_replica_set(&cfg, replicas, handle.engine()).await?;
+            Ok(handle.with_replicas(set))
+        }
+        _ => Ok(handle),
+    }
+}
+
+/// Connect a single pool (primary or replica node) from its configuration.
+async fn connect_handle(mut cfg: DbConnConfig) -> Result<DbHandle> {
     // Expand environment variables in DSN and password
     if let Some(dsn) = &cfg.dsn {
         cfg.dsn = Some(expand_env_vars(dsn)?);
@@ -291,11 +317,73 @@ pub(crate) async fn build_db_handle(
     tracing::debug!(dsn = log_dsn, engine = ?engine, "Building database connection");
 
     // Connect to database
-    let handle = connect_options.connect(pool_cfg).await?;
+    let handle = connect_options
+        .connect(pool_cfg)
+        .await?
+        .with_lock_backend(cfg.lock_backend.unwrap_or_default());
 
     Ok(handle)
 }
 
+/// Connect every replica node, probe it once, and start the health monitor.
+///
+/// # Errors
+/// Returns `DbError::InvalidConfig` for nodes that reference a server or nest replicas, and
+/// `DbError::ConfigConflict` if a node's engine differs from the primary's.
+async fn build_replica_set(
+    primary: &DbConnConfig,
+    replicas: ReplicasCfg,
+    engine: DbEngine,
+) -> Result<Arc<ReplicaSet>> {
+    let mut nodes = Vec::with_capacity(replicas.nodes.len());
+    for (index, node) in replicas.nodes.into_iter().enumerate() {
+        if node.server.is_some() || node.replicas.is_some() {
+            return Err(DbError::InvalidConfig(format!(
+                "replica node #{index}: 'server' and 'replicas' are not allowed on replica nodes"
+            )));
+        }
+        let handle = connect_handle(inherit_primary(node, primary)).await?;
+        if handle.engine != engine {
+            return Err(DbError::ConfigConflict(format!(
+                "replica node #{index} uses {:?} but the primary uses {engine:?}",
+                handle.engine
+            )));
+        }
+        tracing::debug!(
+            replica = %redact_credentials_in_dsn(Some(&handle.dsn)),
+            "Connected read replica"
+        );
+        nodes.push((handle.dsn, handle.sea));
+    }
+
+    let set = Arc::new(ReplicaSet::new(nodes, replicas.max_lag));
+    set.probe().await;
+    ReplicaSet::spawn_monitor(
+        &set,
+        replicas.check_interval.unwrap_or(DEFAULT_CHECK_INTERVAL),
+    );
+    Ok(set)
+}
+
+/// Fill a replica node's unset settings from the primary.
+///
+/// A node that carries its own `dsn`, `file` or `path` is addressed on its own: it only
+/// inherits tuning (engine, params, pool). Otherwise the primary's DSN and connection fields
+/// are the base and the node overrides what differs, typically `host`.
+fn inherit_primary(node: DbConnConfig, primary: &DbConnConfig) -> DbConnConfig {
+    let mut base = primary.clone();
+    base.replicas = None;
+    if node.dsn.is_some() || node.file.is_some() || node.path.is_some() {
+        base.dsn = None;
+        base.host = None;
+        base.port = None;
+        base.user = None;
+        base.password = None;
+        base.dbname = None;
+    }
+    DbManager::merge_server_into_module(node, base)
+}
+
 fn determine_engine(cfg: &DbConnConfig) -> Result<DbEngineCfg> {
     // If both engine and DSN are provided, validate they don't conflict.
     // (We do the same check in validate_config_consistency, but keep this here to ensure
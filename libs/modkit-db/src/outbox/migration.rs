@@ -0,0 +1,163 @@
+//! Schema for a module's outbox tables.
+
+use sea_orm_migration::prelude::*;
+
+use super::{
+    COL_ATTEMPTS, COL_CREATED_AT, COL_ERROR, COL_FAILED_AT, COL_ID, COL_LAST_SEQ, COL_PAYLOAD,
+    COL_SEQ, COL_SUBSCRIBER, COL_TOPIC, COL_UPDATED_AT, Outbox,
+};
+
+/// Creates the outbox, subscriber offset and dead-letter tables of one module.
+///
+/// Obtain it from [`Outbox::migration`] and append it to the module's migration list.
+pub struct CreateOutboxTables {
+    outbox: Outbox,
+}
+
+impl CreateOutboxTables {
+    pub(super) fn new(outbox: Outbox) -> Self {
+        Self { outbox }
+    }
+}
+
+impl MigrationName for CreateOutboxTables {
+    fn name(&self) -> &'static str {
+        "m20261016_000002_create_outbox_tables"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateOutboxTables {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let outbox = Alias::new(self.outbox.outbox_table());
+        manager
+            .create_table(
+                Table::create()
+                    .table(outbox.clone())
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new(COL_SEQ))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_TOPIC))
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new(COL_PAYLOAD)).text().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new(COL_CREATED_AT))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(self.outbox.topic_index())
+                    .table(outbox)
+                    .col(Alias::new(COL_TOPIC))
+                    .col(Alias::new(COL_SEQ))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(self.outbox.offsets_table()))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new(COL_SUBSCRIBER))
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_TOPIC))
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_LAST_SEQ))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_UPDATED_AT))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new(COL_SUBSCRIBER))
+                            .col(Alias::new(COL_TOPIC)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(self.outbox.dead_letter_table()))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Alias::new(COL_ID))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new(COL_SEQ)).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new(COL_SUBSCRIBER))
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_TOPIC))
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new(COL_PAYLOAD)).text().not_null())
+                    .col(ColumnDef::new(Alias::new(COL_ERROR)).text().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new(COL_ATTEMPTS))
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(COL_FAILED_AT))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            self.outbox.dead_letter_table(),
+            self.outbox.offsets_table(),
+            self.outbox.outbox_table(),
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
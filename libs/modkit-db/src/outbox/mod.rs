@@ -0,0 +1,481 @@
+//! Transactional outbox storage.
+//!
+//! Each module that publishes domain events owns three tables, named after the module
+//! like the migration history table (`<prefix><module>__<hash8>`):
+//!
+//! | Table | Rows |
+//! |-------|------|
+//! | `modkit_outbox__*` | one row per event, ordered by the auto-increment `seq` |
+//! | `modkit_outbox_offsets__*` | last delivered `seq` per subscriber and topic |
+//! | `modkit_outbox_dlq__*` | events a subscriber gave up on, with the last error |
+//!
+//! [`Outbox::enqueue`] takes any [`DBRunner`], so passing the `DbTx` of the business
+//! change makes the event commit or roll back together with it. Delivery (the relay and
+//! typed subscribers) lives in `modkit::events`; this module only stores and reads rows.
+//!
+//! `seq` is allocated when a row is inserted but becomes visible when its transaction
+//! commits, so a reader can see `seq` 5 while 4 is still being written. Readers that track
+//! an offset use [`Outbox::fetch_ready`], which stops before such a gap until it has been
+//! open for longer than a settle time, after which the missing row is taken as rolled back.
+//!
+//! The module adds [`Outbox::migration`] to its own migration list.
+
+mod migration;
+
+pub use migration::CreateOutboxTables;
+
+use std::time::Duration;
+
+use sea_orm::sea_query::{Alias, Expr, OnConflict, Order, Query, SelectStatement};
+use sea_orm::{ConnectionTrait, ExecResult, QueryResult, StatementBuilder};
+use sea_orm_migration::MigrationTrait;
+
+use crate::migration_runner::module_table_name;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+pub(crate) const COL_SEQ: &str = "seq";
+pub(crate) const COL_TOPIC: &str = "topic";
+pub(crate) const COL_PAYLOAD: &str = "payload";
+pub(crate) const COL_CREATED_AT: &str = "created_at";
+pub(crate) const COL_SUBSCRIBER: &str = "subscriber";
+pub(crate) const COL_LAST_SEQ: &str = "last_seq";
+pub(crate) const COL_UPDATED_AT: &str = "updated_at";
+pub(crate) const COL_ID: &str = "id";
+pub(crate) const COL_ERROR: &str = "error";
+pub(crate) const COL_ATTEMPTS: &str = "attempts";
+pub(crate) const COL_FAILED_AT: &str = "failed_at";
+
+/// An event row read back from the outbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxRecord {
+    /// Position in the module's outbox; strictly increasing.
+    pub seq: i64,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    /// Enqueue time, epoch milliseconds.
+    pub created_at_ms: i64,
+}
+
+/// Events of one topic that can be delivered in order, see [`Outbox::fetch_ready`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadyBatch {
+    pub records: Vec<OutboxRecord>,
+    /// Every row up to this `seq` is settled, whatever its topic. A reader that handled
+    /// `records` can store it as its offset.
+    pub frontier: i64,
+}
+
+/// An event a subscriber failed to process after exhausting its retries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub seq: i64,
+    pub subscriber: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub error: String,
+    pub attempts: i32,
+    /// Time the event was dead-lettered, epoch milliseconds.
+    pub failed_at_ms: i64,
+}
+
+/// Handle to one module's outbox tables.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    events_table: String,
+    offsets_table: String,
+    dead_letter_table: String,
+    topic_index: String,
+}
+
+impl Outbox {
+    /// Outbox of the module `module_name` (the name it runs migrations under).
+    #[must_use]
+    pub fn new(module_name: &str) -> Self {
+        Self {
+            events_table: module_table_name("modkit_outbox__", module_name),
+            offsets_table: module_table_name("modkit_outbox_offsets__", module_name),
+            dead_letter_table: module_table_name("modkit_outbox_dlq__", module_name),
+            topic_index: module_table_name("idx_modkit_outbox__", module_name),
+        }
+    }
+
+    /// Migration creating this outbox's tables.
+    #[must_use]
+    pub fn migration(&self) -> Box<dyn MigrationTrait> {
+        Box::new(CreateOutboxTables::new(self.clone()))
+    }
+
+    /// Name of the events table; unique per module.
+    #[must_use]
+    pub fn outbox_table(&self) -> &str {
+        &self.events_table
+    }
+
+    pub(crate) fn offsets_table(&self) -> &str {
+        &self.offsets_table
+    }
+
+    pub(crate) fn dead_letter_table(&self) -> &str {
+        &self.dead_letter_table
+    }
+
+    pub(crate) fn topic_index(&self) -> &str {
+        &self.topic_index
+    }
+
+    /// Append an event. Pass the transaction of the business change to make both atomic.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the insert fails.
+    pub async fn enqueue(
+        &self,
+        runner: &impl DBRunner,
+        topic: &str,
+        payload: &serde_json::Value,
+    ) -> crate::Result<()> {
+        let stmt = Query::insert()
+            .into_table(Alias::new(&self.events_table))
+            .columns([
+                Alias::new(COL_TOPIC),
+                Alias::new(COL_PAYLOAD),
+                Alias::new(COL_CREATED_AT),
+            ])
+            .values_panic([
+                topic.into(),
+                payload.to_string().into(),
+                now_millis().into(),
+            ])
+            .to_owned();
+        execute(runner, &stmt).await?;
+        Ok(())
+    }
+
+    /// Read up to `limit` events of `topic` with `seq > after`, in `seq` order.
+    ///
+    /// Rows of transactions still in flight are not visible yet and may later appear below
+    /// the returned ones; use [`Outbox::fetch_ready`] to advance an offset.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the query fails or a payload is not valid JSON.
+    pub async fn fetch_after(
+        &self,
+        runner: &impl DBRunner,
+        topic: &str,
+        after: i64,
+        limit: u64,
+    ) -> crate::Result<Vec<OutboxRecord>> {
+        let stmt = self.select_records(topic, after, limit);
+        self.read_records(runner, &stmt).await
+    }
+
+    /// Read up to `limit` events of `topic` with `seq > after` that no row still in flight
+    /// can precede.
+    ///
+    /// A gap in `seq` below a visible row holds the batch back until that row is older than
+    /// `settle`: transactions publishing events are expected to commit within it.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if a query fails or a payload is not valid JSON.
+    pub async fn fetch_ready(
+        &self,
+        runner: &impl DBRunner,
+        topic: &str,
+        after: i64,
+        limit: u64,
+        settle: Duration,
+    ) -> crate::Result<ReadyBatch> {
+        let stmt = Query::select()
+            .columns([Alias::new(COL_SEQ), Alias::new(COL_CREATED_AT)])
+            .from(Alias::new(&self.events_table))
+            .and_where(Expr::col(Alias::new(COL_SEQ)).gt(after))
+            .order_by(Alias::new(COL_SEQ), Order::Asc)
+            .limit(limit)
+            .to_owned();
+        let rows = query_all(runner, &stmt)
+            .await?
+            .iter()
+            .map(|row| -> crate::Result<(i64, i64)> {
+                Ok((row.try_get("", COL_SEQ)?, row.try_get("", COL_CREATED_AT)?))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let horizon = now_millis().saturating_sub(duration_millis(settle));
+        let frontier = settled_frontier(after, &rows, horizon);
+        if frontier == after {
+            return Ok(ReadyBatch {
+                records: Vec::new(),
+                frontier,
+            });
+        }
+        let stmt = self
+            .select_records(topic, after, limit)
+            .and_where(Expr::col(Alias::new(COL_SEQ)).lte(frontier))
+            .to_owned();
+        Ok(ReadyBatch {
+            records: self.read_records(runner, &stmt).await?,
+            frontier,
+        })
+    }
+
+    fn select_records(&self, topic: &str, after: i64, limit: u64) -> SelectStatement {
+        Query::select()
+            .columns([
+                Alias::new(COL_SEQ),
+                Alias::new(COL_TOPIC),
+                Alias::new(COL_PAYLOAD),
+                Alias::new(COL_CREATED_AT),
+            ])
+            .from(Alias::new(&self.events_table))
+            .and_where(Expr::col(Alias::new(COL_TOPIC)).eq(topic))
+            .and_where(Expr::col(Alias::new(COL_SEQ)).gt(after))
+            .order_by(Alias::new(COL_SEQ), Order::Asc)
+            .limit(limit)
+            .to_owned()
+    }
+
+    async fn read_records(
+        &self,
+        runner: &impl DBRunner,
+        stmt: &SelectStatement,
+    ) -> crate::Result<Vec<OutboxRecord>> {
+        query_all(runner, stmt)
+            .await?
+            .iter()
+            .map(|row| -> crate::Result<OutboxRecord> {
+                Ok(OutboxRecord {
+                    seq: row.try_get("", COL_SEQ)?,
+                    topic: row.try_get("", COL_TOPIC)?,
+                    payload: parse_payload(row)?,
+                    created_at_ms: row.try_get("", COL_CREATED_AT)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Last `seq` of `topic` delivered to `subscriber` (0 if it never committed one).
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the query fails.
+    pub async fn offset(
+        &self,
+        runner: &impl DBRunner,
+        subscriber: &str,
+        topic: &str,
+    ) -> crate::Result<i64> {
+        let stmt = Query::select()
+            .column(Alias::new(COL_LAST_SEQ))
+            .from(Alias::new(&self.offsets_table))
+            .and_where(Expr::col(Alias::new(COL_SUBSCRIBER)).eq(subscriber))
+            .and_where(Expr::col(Alias::new(COL_TOPIC)).eq(topic))
+            .to_owned();
+
+        match query_all(runner, &stmt).await?.first() {
+            Some(row) => Ok(row.try_get("", COL_LAST_SEQ)?),
+            None => Ok(0),
+        }
+    }
+
+    /// Record `seq` as the last event of `topic` delivered to `subscriber`.
+    ///
+    /// Also used to rewind a subscriber for replay: the next delivery starts at `seq + 1`.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the upsert fails.
+    pub async fn commit_offset(
+        &self,
+        runner: &impl DBRunner,
+        subscriber: &str,
+        topic: &str,
+        seq: i64,
+    ) -> crate::Result<()> {
+        let stmt = Query::insert()
+            .into_table(Alias::new(&self.offsets_table))
+            .columns([
+                Alias::new(COL_SUBSCRIBER),
+                Alias::new(COL_TOPIC),
+                Alias::new(COL_LAST_SEQ),
+                Alias::new(COL_UPDATED_AT),
+            ])
+            .values_panic([
+                subscriber.into(),
+                topic.into(),
+                seq.into(),
+                now_millis().into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([Alias::new(COL_SUBSCRIBER), Alias::new(COL_TOPIC)])
+                    .update_columns([Alias::new(COL_LAST_SEQ), Alias::new(COL_UPDATED_AT)])
+                    .to_owned(),
+            )
+            .to_owned();
+        execute(runner, &stmt).await?;
+        Ok(())
+    }
+
+    /// Move an event `subscriber` gave up on into the dead-letter table.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the insert fails.
+    pub async fn dead_letter(
+        &self,
+        runner: &impl DBRunner,
+        subscriber: &str,
+        record: &OutboxRecord,
+        error: &str,
+        attempts: i32,
+    ) -> crate::Result<()> {
+        let stmt = Query::insert()
+            .into_table(Alias::new(&self.dead_letter_table))
+            .columns([
+                Alias::new(COL_SEQ),
+                Alias::new(COL_SUBSCRIBER),
+                Alias::new(COL_TOPIC),
+                Alias::new(COL_PAYLOAD),
+                Alias::new(COL_ERROR),
+                Alias::new(COL_ATTEMPTS),
+                Alias::new(COL_FAILED_AT),
+            ])
+            .values_panic([
+                record.seq.into(),
+                subscriber.into(),
+                record.topic.as_str().into(),
+                record.payload.to_string().into(),
+                error.into(),
+                attempts.into(),
+                now_millis().into(),
+            ])
+            .to_owned();
+        execute(runner, &stmt).await?;
+        Ok(())
+    }
+
+    /// Dead letters recorded for `subscriber`, oldest first.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if the query fails or a payload is not valid JSON.
+    pub async fn dead_letters(
+        &self,
+        runner: &impl DBRunner,
+        subscriber: &str,
+    ) -> crate::Result<Vec<DeadLetter>> {
+        let stmt = Query::select()
+            .columns([
+                Alias::new(COL_SEQ),
+                Alias::new(COL_SUBSCRIBER),
+                Alias::new(COL_TOPIC),
+                Alias::new(COL_PAYLOAD),
+                Alias::new(COL_ERROR),
+                Alias::new(COL_ATTEMPTS),
+                Alias::new(COL_FAILED_AT),
+            ])
+            .from(Alias::new(&self.dead_letter_table))
+            .and_where(Expr::col(Alias::new(COL_SUBSCRIBER)).eq(subscriber))
+            .order_by(Alias::new(COL_ID), Order::Asc)
+            .to_owned();
+
+        query_all(runner, &stmt)
+            .await?
+            .iter()
+            .map(|row| -> crate::Result<DeadLetter> {
+                Ok(DeadLetter {
+                    seq: row.try_get("", COL_SEQ)?,
+                    subscriber: row.try_get("", COL_SUBSCRIBER)?,
+                    topic: row.try_get("", COL_TOPIC)?,
+                    payload: parse_payload(row)?,
+                    error: row.try_get("", COL_ERROR)?,
+                    attempts: row.try_get("", COL_ATTEMPTS)?,
+                    failed_at_ms: row.try_get("", COL_FAILED_AT)?,
+                })
+            })
+            .collect()
+    }
+}
+
+async fn execute<S: StatementBuilder>(
+    runner: &impl DBRunner,
+    stmt: &S,
+) -> Result<ExecResult, sea_orm::DbErr> {
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => db.execute(db.get_database_backend().build(stmt)).await,
+        SeaOrmRunner::Tx(tx) => tx.execute(tx.get_database_backend().build(stmt)).await,
+    }
+}
+
+async fn query_all<S: StatementBuilder>(
+    runner: &impl DBRunner,
+    stmt: &S,
+) -> Result<Vec<QueryResult>, sea_orm::DbErr> {
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => db.query_all(db.get_database_backend().build(stmt)).await,
+        SeaOrmRunner::Tx(tx) => tx.query_all(tx.get_database_backend().build(stmt)).await,
+    }
+}
+
+fn parse_payload(row: &QueryResult) -> Result<serde_json::Value, sea_orm::DbErr> {
+    let raw: String = row.try_get("", COL_PAYLOAD)?;
+    serde_json::from_str(&raw).map_err(|e| sea_orm::DbErr::Json(e.to_string()))
+}
+
+/// Highest `seq` such that every row from `after` up to it is visible in `rows` (`seq`,
+/// `created_at`), or lies in a gap below a row created before `horizon_ms`.
+///
+/// Sequence numbers are allocated in insert order, so a missing `seq` below a visible row
+/// belongs to a transaction that has been open at least since that row was inserted.
+fn settled_frontier(after: i64, rows: &[(i64, i64)], horizon_ms: i64) -> i64 {
+    let mut frontier = after;
+    for &(seq, created_at_ms) in rows {
+        if seq > frontier.saturating_add(1) && created_at_ms > horizon_ms {
+            break;
+        }
+        frontier = seq;
+    }
+    frontier
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn duration_millis(d: Duration) -> i64 {
+    i64::try_from(d.as_millis()).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_names_are_per_module_and_bounded() {
+        let a = Outbox::new("users-info");
+        let b = Outbox::new("simple-user-settings");
+
+        assert!(a.outbox_table().starts_with("modkit_outbox__users_info__"));
+        assert!(a.offsets_table().starts_with("modkit_outbox_offsets__"));
+        assert!(a.dead_letter_table().starts_with("modkit_outbox_dlq__"));
+        assert_ne!(a.outbox_table(), b.outbox_table());
+
+        let long = Outbox::new(&"very-long-module-name-".repeat(8));
+        for name in [
+            long.outbox_table(),
+            long.offsets_table(),
+            long.dead_letter_table(),
+            long.topic_index(),
+        ] {
+            assert!(name.len() <= 63, "{name} exceeds 63 bytes");
+        }
+    }
+
+    #[test]
+    fn frontier_stops_at_unsettled_gaps() {
+        // Contiguous rows are ready whatever their age.
+        assert_eq!(settled_frontier(0, &[(1, 100), (2, 100), (3, 100)], 0), 3);
+        // 3 may still commit: 4 and 5 wait for it.
+        assert_eq!(settled_frontier(2, &[(4, 100), (5, 100)], 50), 2);
+        // Once 4 is older than the settle time, 3 is taken as rolled back.
+        assert_eq!(settled_frontier(2, &[(4, 100), (5, 100)], 100), 5);
+        // Only the gap matters, not how old the rows past it are.
+        assert_eq!(settled_frontier(0, &[(1, 10), (3, 100), (4, 10)], 50), 1);
+        assert_eq!(settled_frontier(7, &[], 50), 7);
+    }
+}
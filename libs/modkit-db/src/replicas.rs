@@ -0,0 +1,161 @@
+//! Read replicas behind a primary connection.
+//!
+//! A [`ReplicaSet`] holds one pool per replica node and hands out connections round-robin,
+//! skipping nodes that failed their last health check. A background monitor re-probes every
+//! node on a fixed interval: a node is healthy when it answers a ping and, if `max_lag` is
+//! configured, reports a replication lag at or below it.
+//!
+//! Routing decisions (which reads may go to a replica) live in `secure::db`; this module only
+//! knows which replicas are currently usable.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement};
+
+/// Default interval between health checks.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+struct ReplicaNode {
+    dsn: String,
+    sea: DatabaseConnection,
+    healthy: AtomicBool,
+}
+
+/// Replica pools with their current health.
+pub struct ReplicaSet {
+    nodes: Vec<ReplicaNode>,
+    next: AtomicUsize,
+    max_lag: Option<Duration>,
+}
+
+impl std::fmt::Debug for ReplicaSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut list = f.debug_list();
+        for node in &self.nodes {
+            list.entry(&(&node.dsn, node.healthy.load(Ordering::Relaxed)));
+        }
+        list.finish()
+    }
+}
+
+impl ReplicaSet {
+    /// Nodes start out healthy; call [`ReplicaSet::probe`] before routing reads to them.
+    pub(crate) fn new(nodes: Vec<(String, DatabaseConnection)>, max_lag: Option<Duration>) -> Self {
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|(dsn, sea)| ReplicaNode {
+                    dsn,
+                    sea,
+                    healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            max_lag,
+        }
+    }
+
+    /// Next healthy replica in round-robin order, or `None` if every node is down.
+    pub(crate) fn pick(&self) -> Option<&DatabaseConnection> {
+        let len = self.nodes.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|offset| &self.nodes[(start + offset) % len])
+            .find(|node| node.healthy.load(Ordering::Relaxed))
+            .map(|node| &node.sea)
+    }
+
+    /// Re-check every node and update its health flag.
+    pub(crate) async fn probe(&self) {
+        for node in &self.nodes {
+            let check = check_node(&node.sea, self.max_lag).await;
+            let was_healthy = node.healthy.swap(check.is_ok(), Ordering::Relaxed);
+            match check {
+                Err(reason) if was_healthy => {
+                    tracing::warn!(replica = %node.dsn, %reason, "Replica excluded from reads");
+                }
+                Ok(()) if !was_healthy => {
+                    tracing::info!(replica = %node.dsn, "Replica serving reads again");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Probe the set every `interval` until the last handle referencing it is dropped.
+    pub(crate) fn spawn_monitor(set: &Arc<Self>, interval: Duration) {
+        let set = Arc::downgrade(set);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // The first tick completes immediately; the set was probed when it was built.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(set) = set.upgrade() else {
+                    break;
+                };
+                set.probe().await;
+            }
+        });
+    }
+}
+
+async fn check_node(sea: &DatabaseConnection, max_lag: Option<Duration>) -> Result<(), String> {
+    sea.ping().await.map_err(|e| e.to_string())?;
+    let Some(max_lag) = max_lag else {
+        return Ok(());
+    };
+    match replication_lag(sea).await {
+        Ok(Some(lag)) if lag <= max_lag => Ok(()),
+        Ok(Some(lag)) => Err(format!(
+            "replication lag {}ms exceeds {}ms",
+            lag.as_millis(),
+            max_lag.as_millis()
+        )),
+        Ok(None) => Err("replication is not running".to_owned()),
+        Err(e) => Err(format!("cannot read replication lag: {e}")),
+    }
+}
+
+/// Replication lag reported by the node itself.
+///
+/// A node that is not replicating from anything (e.g. a promoted primary) reports zero.
+/// `None` means the node is configured as a replica but replication is stopped.
+async fn replication_lag(sea: &DatabaseConnection) -> Result<Option<Duration>, DbErr> {
+    let backend = sea.get_database_backend();
+    match backend {
+        DbBackend::Postgres => {
+            // Replay position equal to receive position means the standby is caught up even
+            // if the last replayed transaction is old (an idle primary).
+            let sql = "SELECT CAST(COALESCE(CASE \
+                 WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0 \
+                 ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()) * 1000 \
+                 END, 0) AS DOUBLE PRECISION) AS lag_ms";
+            let row = sea
+                .query_one(Statement::from_string(backend, sql))
+                .await?
+                .ok_or_else(|| DbErr::Custom("replication lag query returned no row".into()))?;
+            let lag_ms: f64 = row.try_get("", "lag_ms")?;
+            Ok(Some(Duration::from_secs_f64(lag_ms.max(0.0) / 1000.0)))
+        }
+        DbBackend::MySql => {
+            let Some(row) = sea
+                .query_one(Statement::from_string(backend, "SHOW REPLICA STATUS"))
+                .await?
+            else {
+                return Ok(Some(Duration::ZERO));
+            };
+            let seconds = match row.try_get::<Option<u64>>("", "Seconds_Behind_Source") {
+                Ok(seconds) => seconds,
+                Err(_) => row
+                    .try_get::<Option<i64>>("", "Seconds_Behind_Source")?
+                    .and_then(|s| u64::try_from(s).ok()),
+            };
+            Ok(seconds.map(Duration::from_secs))
+        }
+        DbBackend::Sqlite => Ok(Some(Duration::ZERO)),
+    }
+}
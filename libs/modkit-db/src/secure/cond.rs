@@ -1,7 +1,12 @@
-use sea_orm::{ColumnTrait, Condition, EntityTrait, sea_query::Expr};
+use sea_orm::sea_query::{Expr, Query, SelectStatement};
+use sea_orm::{ColumnTrait, Condition, EntityTrait};
 
+use crate::closure::{resource_group_closure, resource_group_membership, tenant_closure};
 use crate::secure::{AccessScope, ScopableEntity};
-use modkit_security::access_scope::{ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::access_scope::{
+    GroupScopeFilter, GroupSubtreeScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
+    TenantSubtreeScopeFilter,
+};
 
 /// Convert a [`ScopeValue`] to a `sea_query::SimpleExpr` for SQL binding.
 fn scope_value_to_sea_expr(v: &ScopeValue) -> sea_orm::sea_query::SimpleExpr {
@@ -26,6 +31,87 @@ fn scope_values_to_sea_values(values: &[ScopeValue]) -> Vec<sea_orm::Value> {
         .collect()
 }
 
+/// `SELECT descendant_id FROM tenant_closure WHERE ancestor_id = :root
+/// [AND barrier = 0] [AND descendant_status IN (..)]`
+fn tenant_subtree_query(f: &TenantSubtreeScopeFilter) -> SelectStatement {
+    let mut query = Query::select();
+    query
+        .column((tenant_closure::Entity, tenant_closure::Column::DescendantId))
+        .from(tenant_closure::Entity)
+        .and_where(
+            Expr::col((tenant_closure::Entity, tenant_closure::Column::AncestorId))
+                .eq(f.root_tenant_id()),
+        );
+    if f.respect_barriers() {
+        query.and_where(
+            Expr::col((tenant_closure::Entity, tenant_closure::Column::Barrier))
+                .eq(tenant_closure::NO_BARRIER),
+        );
+    }
+    if let Some(statuses) = f.tenant_status() {
+        query.and_where(
+            Expr::col((
+                tenant_closure::Entity,
+                tenant_closure::Column::DescendantStatus,
+            ))
+            .is_in(statuses.iter().cloned()),
+        );
+    }
+    query
+}
+
+/// `SELECT resource_id FROM resource_group_membership WHERE group_id IN (:group_ids)`
+fn group_members_query(f: &GroupScopeFilter) -> SelectStatement {
+    let mut query = members_query();
+    query.and_where(
+        Expr::col((
+            resource_group_membership::Entity,
+            resource_group_membership::Column::GroupId,
+        ))
+        .is_in(f.group_ids().iter().copied()),
+    );
+    query
+}
+
+/// Members of any group in the subtree of `root_group_id`.
+fn group_subtree_members_query(f: &GroupSubtreeScopeFilter) -> SelectStatement {
+    let mut groups = Query::select();
+    groups
+        .column((
+            resource_group_closure::Entity,
+            resource_group_closure::Column::DescendantId,
+        ))
+        .from(resource_group_closure::Entity)
+        .and_where(
+            Expr::col((
+                resource_group_closure::Entity,
+                resource_group_closure::Column::AncestorId,
+            ))
+            .eq(f.root_group_id()),
+        );
+
+    let mut query = members_query();
+    query.and_where(
+        Expr::col((
+            resource_group_membership::Entity,
+            resource_group_membership::Column::GroupId,
+        ))
+        .in_subquery(groups),
+    );
+    query
+}
+
+fn members_query() -> SelectStatement {
+    let mut query = Query::select();
+    query
+        .column((
+            resource_group_membership::Entity,
+            resource_group_membership::Column::ResourceId,
+        ))
+        .from(resource_group_membership::Entity);
+    query
+}
+
 /// Build a deny-all condition (`WHERE false`).
 fn deny_all() -> Condition {
     Condition::all().add(Expr::value(false))
@@ -40,6 +126,9 @@ fn deny_all() -> Condition {
 /// - Unknown `pep_properties` fail that constraint (fail-closed)
 /// - If all constraints fail resolution, deny-all
 ///
+/// Hierarchy filters become `IN (subquery)` against the closure tables from
+/// [`crate::closure`], which must exist in the same database.
+///
 /// # Policy Rules
 ///
 /// | Scope | Behavior |
@@ -79,6 +168,17 @@ where
     }
 }
 
+/// Condition that excludes soft-deleted rows (`soft_delete_col IS NULL`).
+///
+/// Returns `None` for entities without a soft-delete column.
+pub fn build_live_rows_condition<E>() -> Option<Condition>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    E::soft_delete_col().map(|col| Condition::all().add(Expr::col(col).is_null()))
+}
+
 /// Build SQL for a single constraint (AND of filters).
 ///
 /// Returns `None` if any filter references an unknown property (fail-closed).
@@ -102,6 +202,15 @@ where
                 let sea_values = scope_values_to_sea_values(inf.values());
                 and_cond = and_cond.add(Expr::col(col).is_in(sea_values));
             }
+            ScopeFilter::InTenantSubtree(f) => {
+                and_cond = and_cond.add(Expr::col(col).in_subquery(tenant_subtree_query(f)));
+            }
+            ScopeFilter::InGroup(f) => {
+                and_cond = and_cond.add(Expr::col(col).in_subquery(group_members_query(f)));
+            }
+            ScopeFilter::InGroupSubtree(f) => {
+                and_cond = and_cond.add(Expr::col(col).in_subquery(group_subtree_members_query(f)));
+            }
         }
     }
     Some(and_cond)
@@ -266,4 +375,104 @@ mod tests {
             "Expected a real condition, got deny-all: {cond_str}"
         );
     }
+
+    // --- Hierarchy filters ---
+
+    fn postgres_sql(cond: Condition) -> String {
+        use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+
+        custom_prop_entity::Entity::find()
+            .filter(cond)
+            .build(DbBackend::Postgres)
+            .to_string()
+    }
+
+    #[test]
+    fn test_tenant_subtree_filter_queries_closure() {
+        let root = uuid::Uuid::new_v4();
+        let scope =
+            AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_tenant_subtree(
+                pep_properties::OWNER_TENANT_ID,
+                root,
+            )]));
+
+        let sql = postgres_sql(build_scope_condition::<custom_prop_entity::Entity>(&scope));
+        assert!(
+            sql.contains(
+                r#""tenant_id" IN (SELECT "tenant_closure"."descendant_id" FROM "tenant_closure""#
+            ),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(&format!(r#""ancestor_id" = '{root}'"#)),
+            "{sql}"
+        );
+        assert!(sql.contains(r#""tenant_closure"."barrier" = 0"#), "{sql}");
+        assert!(!sql.contains("descendant_status"), "{sql}");
+    }
+
+    #[test]
+    fn test_tenant_subtree_filter_ignoring_barriers_with_status() {
+        let filter = modkit_security::TenantSubtreeScopeFilter::new(
+            pep_properties::OWNER_TENANT_ID,
+            uuid::Uuid::new_v4(),
+        )
+        .with_respect_barriers(false)
+        .with_tenant_status(vec!["active".to_owned(), "suspended".to_owned()]);
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::InTenantSubtree(
+            filter,
+        )]));
+
+        let sql = postgres_sql(build_scope_condition::<custom_prop_entity::Entity>(&scope));
+        assert!(!sql.contains("barrier"), "{sql}");
+        assert!(
+            sql.contains(r#""tenant_closure"."descendant_status" IN ('active', 'suspended')"#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_group_filters_query_membership() {
+        let group = uuid::Uuid::new_v4();
+        let in_group = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_group(
+            pep_properties::RESOURCE_ID,
+            vec![group],
+        )]));
+        let sql = postgres_sql(build_scope_condition::<custom_prop_entity::Entity>(
+            &in_group,
+        ));
+        assert!(
+            sql.contains(&format!(
+                r#""id" IN (SELECT "resource_group_membership"."resource_id" FROM "resource_group_membership" WHERE "resource_group_membership"."group_id" IN ('{group}'))"#
+            )),
+            "{sql}"
+        );
+
+        let in_subtree =
+            AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_group_subtree(
+                pep_properties::RESOURCE_ID,
+                group,
+            )]));
+        let sql = postgres_sql(build_scope_condition::<custom_prop_entity::Entity>(
+            &in_subtree,
+        ));
+        assert!(
+            sql.contains(r#""resource_group_membership"."group_id" IN (SELECT "resource_group_closure"."descendant_id" FROM "resource_group_closure""#),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(&format!(r#""ancestor_id" = '{group}'"#)),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_hierarchy_filter_on_unknown_property_denies() {
+        let scope = AccessScope::single(ScopeConstraint::new(vec![ScopeFilter::in_group(
+            "folder_id",
+            vec![uuid::Uuid::new_v4()],
+        )]));
+        let cond = build_scope_condition::<custom_prop_entity::Entity>(&scope);
+        assert!(format!("{cond:?}").contains("Value(Bool(Some(false)))"));
+    }
 }
@@ -41,12 +41,14 @@
 //! let user_id = result?;
 //! ```
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{cell::Cell, future::Future, pin::Pin, sync::Arc};
 
 use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
 
 use super::tx_config::TxConfig;
 use super::tx_error::TxError;
+use crate::replicas::ReplicaSet;
 use crate::{DbError, DbHandle};
 
 // Task-local guard to detect transaction bypass attempts.
@@ -58,6 +60,14 @@ tokio::task_local! {
     static IN_TX: Cell<bool>;
 }
 
+// Task-local marker for read-your-writes routing.
+//
+// Inside a `read_your_writes` scope it flips to `true` the first time the task
+// writes or opens a transaction; from then on `DbConn` reads skip the replicas.
+tokio::task_local! {
+    static WROTE: Cell<bool>;
+}
+
 /// Check if we're currently inside a transaction context.
 ///
 /// Returns `true` if a transaction is active in the current task.
@@ -73,9 +83,31 @@ async fn with_tx_guard<F, T>(f: F) -> T
 where
     F: Future<Output = T>,
 {
+    note_primary_use();
     IN_TX.scope(Cell::new(true), f).await
 }
 
+/// Record that the current task touched the primary (no-op outside `read_your_writes`).
+fn note_primary_use() {
+    WROTE.try_with(|wrote| wrote.set(true)).ok();
+}
+
+fn wrote_in_scope() -> bool {
+    WROTE.try_with(Cell::get).unwrap_or(false)
+}
+
+/// Run `f` as one read-your-writes unit, typically a single request.
+///
+/// Reads through [`DbConn`] normally go to a read replica when the database has any.
+/// Within this scope, once anything writes or opens a transaction, every later read in the
+/// same task goes to the primary, even through a different `DbConn`, so callers see their
+/// own changes despite replication lag. Without replicas this only costs a task-local.
+///
+/// Scopes do not cross `tokio::spawn`; spawned tasks start with a fresh state.
+pub async fn read_your_writes<F: Future>(f: F) -> F::Output {
+    WROTE.scope(Cell::new(false), f).await
+}
+
 /// Database handle for secure operations.
 ///
 /// # Security
@@ -175,6 +207,8 @@ impl Db {
         }
         Ok(DbConn {
             conn: self.handle.sea_internal_ref(),
+            replicas: self.handle.replicas(),
+            pinned: AtomicBool::new(false),
         })
     }
 
@@ -469,6 +503,37 @@ impl Db {
         }
     }
 
+    /// Check that the database answers a round-trip on a pooled connection.
+    ///
+    /// Used by the runtime's health checks; it reads and writes nothing.
+    ///
+    /// # Errors
+    /// Returns `DbError::Sea` if no connection can be acquired or the ping fails.
+    pub async fn ping(&self) -> Result<(), DbError> {
+        self.handle.sea_internal_ref().ping().await?;
+        Ok(())
+    }
+
+    /// Snapshot of the connection pool occupancy.
+    ///
+    /// Returns `None` when the backend's driver is not compiled in.
+    #[must_use]
+    pub fn pool_stats(&self) -> Option<PoolStats> {
+        use sea_orm::{ConnectionTrait, DbBackend};
+
+        let conn = self.handle.sea_internal_ref();
+        match conn.get_database_backend() {
+            #[cfg(feature = "pg")]
+            DbBackend::Postgres => Some(PoolStats::of(conn.get_postgres_connection_pool())),
+            #[cfg(feature = "mysql")]
+            DbBackend::MySql => Some(PoolStats::of(conn.get_mysql_connection_pool())),
+            #[cfg(feature = "sqlite")]
+            DbBackend::Sqlite => Some(PoolStats::of(conn.get_sqlite_connection_pool())),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     /// Return database engine identifier for logging/tracing.
     #[must_use]
     pub fn db_engine(&self) -> &'static str {
@@ -482,6 +547,31 @@ impl Db {
     }
 }
 
+/// Connection pool occupancy reported by [`Db::pool_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Open connections, idle or in use.
+    pub size: u32,
+    /// Open connections not currently checked out.
+    pub idle: u32,
+    /// Upper bound configured for the pool.
+    pub max: u32,
+}
+
+impl PoolStats {
+    #[cfg_attr(
+        not(any(feature = "pg", feature = "mysql", feature = "sqlite")),
+        allow(dead_code)
+    )]
+    fn of<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> Self {
+        Self {
+            size: pool.size(),
+            idle: u32::try_from(pool.num_idle()).unwrap_or(u32::MAX),
+            max: pool.options().get_max_connections(),
+        }
+    }
+}
+
 /// Non-transactional database runner.
 ///
 /// This type borrows from a [`Db`] and can be used to execute queries outside
@@ -504,8 +594,41 @@ impl Db {
 ///     .all(&conn)
 ///     .await?;
 /// ```
+///
+/// # Read replicas
+///
+/// When the database is configured with replicas, scoped reads (`all`, `one`, `count`,
+/// `OData` pages) are served round-robin by a healthy replica. After the first write through
+/// a `DbConn`, all of its queries use the primary. See [`read_your_writes`] to extend that
+/// guarantee to every connection used by a request.
 pub struct DbConn<'a> {
     pub(crate) conn: &'a DatabaseConnection,
+    replicas: Option<&'a ReplicaSet>,
+    pinned: AtomicBool,
+}
+
+impl<'a> DbConn<'a> {
+    /// The primary connection; later reads on this `DbConn` stay on it.
+    pub(crate) fn primary(&self) -> &'a DatabaseConnection {
+        self.pin_primary();
+        self.conn
+    }
+
+    /// Send every later query on this `DbConn` (and in the read-your-writes scope) to the primary.
+    pub(crate) fn pin_primary(&self) {
+        self.pinned.store(true, Ordering::Relaxed);
+        note_primary_use();
+    }
+
+    /// Connection for a read: a healthy replica unless a prior write pinned the primary.
+    pub(crate) fn read_target(&self) -> &'a DatabaseConnection {
+        if self.pinned.load(Ordering::Relaxed) || wrote_in_scope() {
+            return self.conn;
+        }
+        self.replicas
+            .and_then(ReplicaSet::pick)
+            .unwrap_or(self.conn)
+    }
 }
 
 impl std::fmt::Debug for DbConn<'_> {
@@ -1,11 +1,12 @@
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, InsertResult, IntoActiveModel, ModelTrait,
-    QueryFilter,
-    sea_query::{IntoIden, OnConflict, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, InsertResult, IntoActiveModel, ModelTrait,
+    QueryFilter, QueryTrait,
+    sea_query::{Expr, IntoIden, OnConflict, SimpleExpr},
 };
 use std::marker::PhantomData;
 
-use crate::secure::cond::build_scope_condition;
+use crate::audit::{self, AuditAction};
+use crate::secure::cond::{build_live_rows_condition, build_scope_condition};
 use crate::secure::error::ScopeError;
 use crate::secure::{
     AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, Scoped, SeaOrmRunner, SecureEntityExt,
@@ -36,6 +37,26 @@ fn sea_value_to_scope_value(v: &sea_orm::Value) -> Option<modkit_security::Scope
     }
 }
 
+/// Read an integer version column value.
+fn version_number(v: &sea_orm::Value) -> Option<i64> {
+    match v {
+        sea_orm::Value::BigInt(Some(n)) => Some(*n),
+        sea_orm::Value::Int(Some(n)) => Some(i64::from(*n)),
+        sea_orm::Value::SmallInt(Some(n)) => Some(i64::from(*n)),
+        _ => None,
+    }
+}
+
+/// The version following `v`, in the same integer representation as `v`.
+fn next_version(v: &sea_orm::Value) -> Option<sea_orm::Value> {
+    match v {
+        sea_orm::Value::BigInt(Some(n)) => n.checked_add(1).map(sea_orm::Value::from),
+        sea_orm::Value::Int(Some(n)) => n.checked_add(1).map(sea_orm::Value::from),
+        sea_orm::Value::SmallInt(Some(n)) => n.checked_add(1).map(sea_orm::Value::from),
+        _ => None,
+    }
+}
+
 /// Validate that the values in an `ActiveModel` satisfy at least one constraint
 /// in the provided `AccessScope`.
 ///
@@ -55,6 +76,10 @@ fn sea_value_to_scope_value(v: &sea_orm::Value) -> Option<modkit_security::Scope
 /// - A filter whose property does **not** resolve (unknown property) causes
 ///   that constraint to fail (fail-closed), consistent with the query-path
 ///   behavior in `build_scope_condition`.
+/// - A hierarchy filter (`InTenantSubtree`, `InGroup`, `InGroupSubtree`) also
+///   fails its constraint: closure-table membership cannot be checked against
+///   in-memory values. Inserts should be authorized without row-level
+///   constraints or with literal `Eq`/`In` filters.
 ///
 /// # Errors
 ///
@@ -78,6 +103,9 @@ where
     'next_constraint: for constraint in scope.constraints() {
         // AND over filters within this constraint.
         for filter in constraint.filters() {
+            if filter.is_hierarchical() {
+                continue 'next_constraint;
+            }
             let Some(col) = <A::Entity as ScopableEntity>::resolve_property(filter.property())
             else {
                 // Unknown property → this constraint fails (fail-closed).
@@ -171,6 +199,9 @@ where
 /// let user = secure_insert::<user::Entity>(am, &ctx, conn).await?;
 /// ```
 ///
+/// For `#[secure(audit)]` entities the inserted values are recorded in the audit log
+/// through the same `runner`.
+///
 /// # Errors
 ///
 /// - Returns `ScopeError::Db` if the database insert fails.
@@ -196,10 +227,14 @@ where
 
     validate_insert_scope(&am, scope)?;
 
-    match DBRunnerInternal::as_seaorm(runner) {
-        SeaOrmRunner::Conn(db) => Ok(am.insert(db).await?),
-        SeaOrmRunner::Tx(tx) => Ok(am.insert(tx).await?),
+    let inserted = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => am.insert(db).await?,
+        SeaOrmRunner::Tx(tx) => am.insert(tx).await?,
+    };
+    if E::IS_AUDITED {
+        audit::record::<E>(runner, AuditAction::Insert, [(None, Some(&inserted))]).await?;
     }
+    Ok(inserted)
 }
 
 /// Secure update helper for updating a single entity by ID inside a scope.
@@ -208,11 +243,21 @@ where
 /// - Verifies the target row exists **within the scope** before updating.
 /// - For tenant-scoped entities, forbids changing `tenant_id` (immutable).
 ///
+/// # Row lifecycle
+/// - Soft-deleted rows are treated as missing.
+/// - For entities with a `version_col`, the update only applies while the stored version
+///   equals the one carried by `am` (or the stored one, if `am` leaves it `NotSet`), and
+///   writes that version plus one. The check is repeated in the `UPDATE` itself, so a
+///   concurrent writer cannot slip in between.
+///
+/// For `#[secure(audit)]` entities the changed columns are recorded in the audit log.
+///
 /// # Errors
 /// - `ScopeError::Denied` if the row is not accessible in the scope.
 /// - `ScopeError::Denied("tenant_id is immutable")` if caller attempts to change `tenant_id`.
+/// - `ScopeError::VersionConflict` if the row was modified since `am`'s version was read.
 pub async fn secure_update_with_scope<E>(
-    am: E::ActiveModel,
+    mut am: E::ActiveModel,
     scope: &AccessScope,
     id: uuid::Uuid,
     runner: &impl DBRunner,
@@ -223,6 +268,8 @@ where
     E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
     E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
 {
+    // The version check below must compare against the primary's row, not a lagging copy
+    runner.pin_primary();
     let existing = E::find()
         .secure()
         .scope_with(scope)
@@ -262,10 +309,81 @@ where
         }
     }
 
-    match DBRunnerInternal::as_seaorm(runner) {
-        SeaOrmRunner::Conn(db) => Ok(am.update(db).await?),
-        SeaOrmRunner::Tx(tx) => Ok(am.update(tx).await?),
+    let version = bump_version::<E>(&mut am, &existing)?;
+
+    let mut update = E::update(am);
+    if let Some(live) = build_live_rows_condition::<E>() {
+        update = update.filter(live);
+    }
+    if let Some((vcol, expected, _)) = &version {
+        update = update.filter(Expr::col(*vcol).eq(expected.clone()));
+    }
+
+    let result = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => update.exec(db).await,
+        SeaOrmRunner::Tx(tx) => update.exec(tx).await,
+    };
+    let updated = match (result, version) {
+        (Err(DbErr::RecordNotUpdated), Some((_, _, expected))) => {
+            return Err(ScopeError::VersionConflict { expected });
+        }
+        (Err(DbErr::RecordNotUpdated), None) => {
+            return Err(ScopeError::Denied(
+                "entity not found or not accessible in current security scope",
+            ));
+        }
+        (result, _) => result?,
+    };
+    if E::IS_AUDITED {
+        audit::record::<E>(
+            runner,
+            AuditAction::Update,
+            [(Some(&existing), Some(&updated))],
+        )
+        .await?;
     }
+    Ok(updated)
+}
+
+/// Version column, the expected stored value and its number.
+type VersionCheck<E> = (<E as EntityTrait>::Column, sea_orm::Value, i64);
+
+/// Check `am`'s version against the stored row and set it to the next version.
+///
+/// Returns `None` for entities without a `version_col`.
+fn bump_version<E>(
+    am: &mut E::ActiveModel,
+    existing: &E::Model,
+) -> Result<Option<VersionCheck<E>>, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E>,
+    E::Model: sea_orm::ModelTrait<Entity = E>,
+{
+    let Some(vcol) = E::version_col() else {
+        return Ok(None);
+    };
+
+    let stored = existing.get(vcol);
+    let expected = match am.get(vcol) {
+        sea_orm::ActiveValue::Set(v) | sea_orm::ActiveValue::Unchanged(v) => v,
+        sea_orm::ActiveValue::NotSet => stored.clone(),
+    };
+    let (Some(expected_n), Some(next)) = (version_number(&expected), next_version(&expected))
+    else {
+        return Err(ScopeError::Invalid(
+            "version column must hold a non-null integer",
+        ));
+    };
+    if version_number(&stored) != Some(expected_n) {
+        return Err(ScopeError::VersionConflict {
+            expected: expected_n,
+        });
+    }
+
+    am.set(vcol, next);
+    Ok(Some((vcol, expected, expected_n)))
 }
 
 /// Helper to validate a tenant ID is in the scope.
@@ -656,6 +774,10 @@ where
 /// This wrapper uses the typestate pattern to ensure that update operations
 /// cannot be executed without first applying access control via `.scope_with()`.
 ///
+/// Updates of `#[secure(audit)]` entities read the matching rows before and after the
+/// statement to record their changes, so they must be filtered through this wrapper
+/// (the conditions are replayed onto those reads) rather than on the raw `UpdateMany`.
+///
 /// # Example
 /// ```ignore
 /// use modkit_db::secure::{AccessScope, SecureUpdateExt};
@@ -673,6 +795,10 @@ pub struct SecureUpdateMany<E: EntityTrait, S> {
     pub(crate) inner: sea_orm::UpdateMany<E>,
     pub(crate) _state: PhantomData<S>,
     pub(crate) tenant_update_attempted: bool,
+    /// Conditions applied through this wrapper, replayed for audit reads.
+    pub(crate) cond: sea_orm::Condition,
+    /// The wrapped `UpdateMany` already carried filters when `.secure()` was called.
+    pub(crate) prefiltered: bool,
 }
 
 // Fluent builder methods (available in all typestates).
@@ -696,6 +822,7 @@ where
     /// Add an additional filter. Scope conditions remain in place once applied.
     #[must_use]
     pub fn filter(mut self, filter: sea_orm::Condition) -> Self {
+        self.cond = self.cond.add(filter.clone());
         self.inner = QueryFilter::filter(self.inner, filter);
         self
     }
@@ -713,10 +840,22 @@ where
     E: EntityTrait,
 {
     fn secure(self) -> SecureUpdateMany<E, Unscoped> {
+        // Compare against an unfiltered update setting the same values.
+        let query = self.as_query();
+        let mut unfiltered = E::update_many().into_query();
+        unfiltered.values(
+            query
+                .get_values()
+                .iter()
+                .map(|(col, expr)| (col.clone(), (**expr).clone())),
+        );
+        let prefiltered = &unfiltered != query;
         SecureUpdateMany {
             inner: self,
             _state: PhantomData,
             tenant_update_attempted: false,
+            cond: sea_orm::Condition::all(),
+            prefiltered,
         }
     }
 }
@@ -739,9 +878,11 @@ where
     pub fn scope_with(self, scope: &AccessScope) -> SecureUpdateMany<E, Scoped> {
         let cond = build_scope_condition::<E>(scope);
         SecureUpdateMany {
-            inner: self.inner.filter(cond),
+            inner: self.inner.filter(cond.clone()),
             _state: PhantomData,
             tenant_update_attempted: self.tenant_update_attempted,
+            cond: self.cond.add(cond),
+            prefiltered: self.prefiltered,
         }
     }
 }
@@ -749,23 +890,59 @@ where
 // Methods available only on Scoped updates
 impl<E> SecureUpdateMany<E, Scoped>
 where
-    E: EntityTrait,
+    E: ScopableEntity + EntityTrait,
 {
     /// Execute the update operation.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - `ScopeError::Denied` if the update sets the tenant column.
+    /// - `ScopeError::Invalid` if an audited update was built from a pre-filtered `UpdateMany`.
+    /// - `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::UpdateResult, ScopeError> {
         if self.tenant_update_attempted {
             return Err(ScopeError::Denied("tenant_id is immutable"));
         }
+        if E::IS_AUDITED {
+            return self.exec_audited(runner).await;
+        }
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
         }
     }
 
+    /// Update exactly the rows read beforehand and record how each one changed.
+    #[allow(clippy::disallowed_methods)]
+    async fn exec_audited(
+        self,
+        runner: &impl DBRunner,
+    ) -> Result<sea_orm::UpdateResult, ScopeError> {
+        if self.prefiltered {
+            return Err(ScopeError::Invalid(
+                "audited entities must be filtered after .secure()",
+            ));
+        }
+        let before = audit::fetch::<E>(runner, self.cond).await?;
+        if before.is_empty() {
+            return Ok(sea_orm::UpdateResult { rows_affected: 0 });
+        }
+        let keys = audit::key_condition::<E>(&before);
+        let update = QueryFilter::filter(self.inner, keys.clone());
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => update.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+        };
+        let after = audit::fetch::<E>(runner, keys).await?;
+        audit::record::<E>(
+            runner,
+            AuditAction::Update,
+            audit::pair_by_key::<E>(&before, &after),
+        )
+        .await?;
+        Ok(result)
+    }
+
     /// Unwrap the inner `SeaORM` `UpdateMany` for advanced use cases.
     ///
     /// # Safety
@@ -782,6 +959,15 @@ where
 /// This wrapper uses the typestate pattern to ensure that delete operations
 /// cannot be executed without first applying access control via `.scope_with()`.
 ///
+/// For entities with a `soft_delete_col`, executing sets that column on the matching
+/// live rows instead of removing them (see [`permanently`](Self::permanently)). Such
+/// deletes must be filtered after `.secure()`; the scope and filter conditions are
+/// replayed onto the `UPDATE`, which is impossible for filters applied to the raw
+/// `DeleteMany`.
+///
+/// Deletes of `#[secure(audit)]` entities are subject to the same rule: the rows are read
+/// first so their values can be recorded in the audit log.
+///
 /// # Example
 /// ```ignore
 /// use modkit_db::secure::{AccessScope, SecureDeleteExt};
@@ -798,6 +984,11 @@ where
 pub struct SecureDeleteMany<E: EntityTrait, S> {
     pub(crate) inner: sea_orm::DeleteMany<E>,
     pub(crate) _state: PhantomData<S>,
+    /// Conditions applied through this wrapper, replayed for soft deletes.
+    pub(crate) cond: sea_orm::Condition,
+    /// The wrapped `DeleteMany` already carried filters when `.secure()` was called.
+    pub(crate) prefiltered: bool,
+    pub(crate) permanent: bool,
 }
 
 /// Extension trait to convert a regular `SeaORM` `DeleteMany` into a `SecureDeleteMany`.
@@ -812,9 +1003,13 @@ where
     E: EntityTrait,
 {
     fn secure(self) -> SecureDeleteMany<E, Unscoped> {
+        let prefiltered = self.as_query() != E::delete_many().as_query();
         SecureDeleteMany {
             inner: self,
             _state: PhantomData,
+            cond: sea_orm::Condition::all(),
+            prefiltered,
+            permanent: false,
         }
     }
 }
@@ -837,8 +1032,11 @@ where
     pub fn scope_with(self, scope: &AccessScope) -> SecureDeleteMany<E, Scoped> {
         let cond = build_scope_condition::<E>(scope);
         SecureDeleteMany {
-            inner: self.inner.filter(cond),
+            inner: self.inner.filter(cond.clone()),
             _state: PhantomData,
+            cond: self.cond.add(cond),
+            prefiltered: self.prefiltered,
+            permanent: self.permanent,
         }
     }
 }
@@ -846,30 +1044,131 @@ where
 // Methods available only on Scoped deletes
 impl<E> SecureDeleteMany<E, Scoped>
 where
-    E: EntityTrait,
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
 {
     /// Add additional filters to the scoped delete.
     /// The scope conditions remain in place.
     #[must_use]
     pub fn filter(mut self, filter: sea_orm::Condition) -> Self {
+        self.cond = self.cond.add(filter.clone());
         self.inner = QueryFilter::filter(self.inner, filter);
         self
     }
 
+    /// Remove the rows even if the entity has a `soft_delete_col`.
+    ///
+    /// Use for purging rows that were soft-deleted earlier; no-op for other entities.
+    #[must_use]
+    pub fn permanently(mut self) -> Self {
+        self.permanent = true;
+        self
+    }
+
     /// Execute the delete operation.
     ///
+    /// For soft-delete entities this is an `UPDATE` of the live matching rows, and
+    /// `rows_affected` counts the rows that became deleted.
+    ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - `ScopeError::Invalid` if a soft or audited delete was built from a pre-filtered
+    ///   `DeleteMany`.
+    /// - `ScopeError::Db` if the database operation fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::DeleteResult, ScopeError> {
+        if E::IS_AUDITED {
+            return self.exec_audited(runner).await;
+        }
+        if !self.permanent
+            && let Some(col) = E::soft_delete_col()
+        {
+            if self.prefiltered {
+                return Err(ScopeError::Invalid(
+                    "soft-delete entities must be filtered after .secure()",
+                ));
+            }
+            let update = E::update_many()
+                .col_expr(col, Expr::value(time::OffsetDateTime::now_utc()))
+                .filter(self.cond)
+                .filter(Expr::col(col).is_null());
+            let result = match DBRunnerInternal::as_seaorm(runner) {
+                SeaOrmRunner::Conn(db) => update.exec(db).await?,
+                SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+            };
+            return Ok(sea_orm::DeleteResult {
+                rows_affected: result.rows_affected,
+            });
+        }
+
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
         }
     }
 
+    /// Delete exactly the rows read beforehand and record their values.
+    ///
+    /// A soft delete is recorded as the change of its `soft_delete_col`.
+    #[allow(clippy::disallowed_methods)]
+    async fn exec_audited(
+        self,
+        runner: &impl DBRunner,
+    ) -> Result<sea_orm::DeleteResult, ScopeError> {
+        if self.prefiltered {
+            return Err(ScopeError::Invalid(
+                "audited entities must be filtered after .secure()",
+            ));
+        }
+        let soft_delete_col = E::soft_delete_col().filter(|_| !self.permanent);
+        let mut cond = self.cond;
+        if let Some(col) = soft_delete_col {
+            cond = cond.add(Expr::col(col).is_null());
+        }
+        let before = audit::fetch::<E>(runner, cond).await?;
+        if before.is_empty() {
+            return Ok(sea_orm::DeleteResult { rows_affected: 0 });
+        }
+        let keys = audit::key_condition::<E>(&before);
+
+        let Some(col) = soft_delete_col else {
+            let delete = QueryFilter::filter(self.inner, keys);
+            let result = match DBRunnerInternal::as_seaorm(runner) {
+                SeaOrmRunner::Conn(db) => delete.exec(db).await?,
+                SeaOrmRunner::Tx(tx) => delete.exec(tx).await?,
+            };
+            audit::record::<E>(
+                runner,
+                AuditAction::Delete,
+                before.iter().map(|row| (Some(row), None)),
+            )
+            .await?;
+            return Ok(result);
+        };
+
+        let update = E::update_many()
+            .col_expr(col, Expr::value(time::OffsetDateTime::now_utc()))
+            .filter(keys.clone())
+            .filter(Expr::col(col).is_null());
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => update.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+        };
+        let after = audit::fetch::<E>(runner, keys).await?;
+        audit::record::<E>(
+            runner,
+            AuditAction::Delete,
+            audit::pair_by_key::<E>(&before, &after),
+        )
+        .await?;
+        Ok(sea_orm::DeleteResult {
+            rows_affected: result.rows_affected,
+        })
+    }
+
     /// Unwrap the inner `SeaORM` `DeleteMany` for advanced use cases.
     ///
+    /// This is always a physical delete, regardless of `soft_delete_col`.
+    ///
     /// # Safety
     /// The caller must ensure they don't remove or bypass the security
     /// conditions that were applied during `.scope_with()`.
@@ -1415,4 +1714,38 @@ mod tests {
             "Unknown property must cause constraint to fail (fail-closed)"
         );
     }
+
+    #[test]
+    fn test_validate_insert_scope_hierarchy_filter_fails_closed() {
+        use modkit_security::access_scope::{ScopeConstraint, ScopeFilter};
+        use modkit_security::pep_properties;
+        use owner_entity::ActiveModel;
+        use sea_orm::Set;
+
+        let tenant_id = Uuid::new_v4();
+        let subtree_only = AccessScope::from_constraints(vec![ScopeConstraint::new(vec![
+            ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, tenant_id),
+        ])]);
+
+        let am = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            user_id: Set(Uuid::new_v4()),
+            city_id: Set(Uuid::new_v4()),
+        };
+        assert!(
+            validate_insert_scope(&am, &subtree_only).is_err(),
+            "Subtree membership cannot be checked in memory (fail-closed)"
+        );
+
+        // A literal alternative path still admits the insert
+        let with_fallback = AccessScope::from_constraints(vec![
+            subtree_only.constraints()[0].clone(),
+            ScopeConstraint::new(vec![ScopeFilter::eq(
+                pep_properties::OWNER_TENANT_ID,
+                tenant_id,
+            )]),
+        ]);
+        assert!(validate_insert_scope(&am, &with_fallback).is_ok());
+    }
 }
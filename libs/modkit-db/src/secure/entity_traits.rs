@@ -11,6 +11,10 @@ use sea_orm::EntityTrait;
 /// **Important**: No implicit defaults are allowed. Every scope dimension must be explicitly
 /// specified as `Some(Column::...)` or `None` to enforce compile-time safety in secure systems.
 ///
+/// Two optional row-lifecycle columns are not scope dimensions and default to `None`:
+/// - `soft_delete_col()`: Nullable timestamp set instead of deleting the row
+/// - `version_col()`: Integer checked and incremented on every scoped update
+///
 /// # Example (Manual Implementation)
 /// ```rust,ignore
 /// impl ScopableEntity for user::Entity {
@@ -107,6 +111,15 @@ pub trait ScopableEntity: EntityTrait {
     /// Default: `false` (entity participates in scoping logic)
     const IS_UNRESTRICTED: bool = false;
 
+    /// Record every secure write to this entity in the audit log.
+    ///
+    /// When true, `secure_insert`, `secure_update_with_scope`, `SecureUpdateMany::exec` and
+    /// `SecureDeleteMany::exec` append one audit record per affected row through the same
+    /// runner as the change (see [`crate::audit`]).
+    ///
+    /// Declared via `#[secure(audit)]`; default: `false`.
+    const IS_AUDITED: bool = false;
+
     /// Returns the column that stores the tenant identifier.
     ///
     /// - Multi-tenant entities: `Some(Column::TenantId)`
@@ -136,6 +149,28 @@ pub trait ScopableEntity: EntityTrait {
     /// Must be explicitly specified via `type_col = "..."` or `no_type`.
     fn type_col() -> Option<Self::Column>;
 
+    /// Returns the nullable timestamp column that marks a row as soft-deleted.
+    ///
+    /// When set, scoped selects hide rows where the column is non-null (unless the query
+    /// opts in with `with_deleted()`), and scoped deletes set it instead of removing rows.
+    ///
+    /// Declared via `soft_delete_col = "..."`; defaults to `None`.
+    #[must_use]
+    fn soft_delete_col() -> Option<Self::Column> {
+        None
+    }
+
+    /// Returns the integer column used for optimistic concurrency control.
+    ///
+    /// When set, `secure_update_with_scope` only writes a row whose stored version equals the
+    /// version carried by the `ActiveModel`, and increments it on success.
+    ///
+    /// Declared via `version_col = "..."`; defaults to `None`.
+    #[must_use]
+    fn version_col() -> Option<Self::Column> {
+        None
+    }
+
     /// Resolve an authorization property name to a database column.
     ///
     /// Maps PEP property names (e.g. `"owner_tenant_id"`) to `SeaORM` columns
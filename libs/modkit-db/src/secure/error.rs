@@ -18,4 +18,12 @@ pub enum ScopeError {
     /// Operation denied - entity not accessible in current security scope.
     #[error("access denied: {0}")]
     Denied(&'static str),
+
+    /// Optimistic concurrency check failed: the row's version is no longer `expected`.
+    ///
+    /// Converts into [`DbError::VersionConflict`](crate::DbError::VersionConflict), which
+    /// HTTP handlers report as `409 Conflict`, or as `412 Precondition Failed` when the
+    /// expected version came from an `If-Match` header.
+    #[error("version conflict: row no longer at version {expected}")]
+    VersionConflict { expected: i64 },
 }
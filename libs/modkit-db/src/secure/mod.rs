@@ -126,8 +126,8 @@ pub use error::ScopeError;
 
 // Security types from modkit-security
 pub use modkit_security::{
-    AccessScope, EqScopeFilter, InScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
-    pep_properties,
+    AccessScope, EqScopeFilter, GroupScopeFilter, GroupSubtreeScopeFilter, InScopeFilter,
+    ScopeConstraint, ScopeFilter, ScopeValue, TenantSubtreeScopeFilter, pep_properties,
 };
 
 // Ergonomic secure connection API (no raw SeaORM types leaked)
@@ -140,7 +140,7 @@ pub use runner::DBRunner;
 pub(crate) use runner::{DBRunnerInternal, SeaOrmRunner};
 
 // Primary database types (new secure API)
-pub use db::{Db, DbConn, DbTx};
+pub use db::{Db, DbConn, DbTx, PoolStats, read_your_writes};
 
 // Transaction error types (no SeaORM types leaked)
 pub use tx_error::{InfraError, TxError};
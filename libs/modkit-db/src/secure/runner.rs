@@ -28,7 +28,16 @@ pub enum SeaOrmRunner<'a> {
 
 /// Internal-only bridge to `SeaORM`'s executor types.
 pub trait DBRunnerInternal: sealed::Sealed + Send + Sync {
+    /// Executor for writes (and anything that must see them).
     fn as_seaorm(&self) -> SeaOrmRunner<'_>;
+
+    /// Executor for a read that tolerates replication lag.
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        self.as_seaorm()
+    }
+
+    /// Keep later reads on this runner on the primary.
+    fn pin_primary(&self) {}
 }
 
 /// Hidden capability marker used by repositories and services.
@@ -45,7 +54,15 @@ pub trait DBRunner: DBRunnerInternal {}
 impl sealed::Sealed for DbConn<'_> {}
 impl DBRunnerInternal for DbConn<'_> {
     fn as_seaorm(&self) -> SeaOrmRunner<'_> {
-        SeaOrmRunner::Conn(self.conn)
+        SeaOrmRunner::Conn(self.primary())
+    }
+
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        SeaOrmRunner::Conn(self.read_target())
+    }
+
+    fn pin_primary(&self) {
+        DbConn::pin_primary(self);
     }
 }
 impl DBRunner for DbConn<'_> {}
@@ -51,7 +51,7 @@ use std::{future::Future, pin::Pin};
 
 use sea_orm::{
     AccessMode, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
-    IsolationLevel, QueryFilter, TransactionTrait, sea_query::Expr,
+    IsolationLevel, TransactionTrait, sea_query::Expr,
 };
 use uuid::Uuid;
 
@@ -142,6 +142,7 @@ impl SecureConn {
     ///
     /// Returns a `SecureSelect<E, Scoped>` that automatically applies
     /// tenant/resource filtering based on the provided security context.
+    /// Soft-deleted rows are hidden; see [`find_with_deleted`](Self::find_with_deleted).
     ///
     /// # Example
     ///
@@ -165,6 +166,18 @@ impl SecureConn {
         E::find().secure().scope_with(scope)
     }
 
+    /// Like [`find`](Self::find), but also returns soft-deleted rows.
+    ///
+    /// Entities without a `soft_delete_col` behave exactly as with `find`.
+    #[allow(clippy::unused_self)] // Same shape as `find`
+    pub fn find_with_deleted<E>(&self, scope: &AccessScope) -> SecureSelect<E, Scoped>
+    where
+        E: ScopableEntity + EntityTrait,
+        E::Column: ColumnTrait + Copy,
+    {
+        E::find().secure().scope_with_deleted(scope)
+    }
+
     /// Create a scoped select query filtered by a specific resource ID.
     ///
     /// This is a convenience method that combines `find()` with `.and_id()`.
@@ -365,6 +378,8 @@ impl SecureConn {
     /// # Errors
     ///
     /// - `ScopeError::Denied` if the entity is not accessible in the current scope
+    /// - `ScopeError::VersionConflict` if the entity has a `version_col` and the row
+    ///   changed since `am` was loaded
     /// - `ScopeError::Db` if the database operation fails
     pub async fn update_with_ctx<E>(
         &self,
@@ -383,7 +398,8 @@ impl SecureConn {
 
     /// Delete a single entity by ID (scoped).
     ///
-    /// This validates the entity exists in scope before deleting.
+    /// This validates the entity exists in scope before deleting. Entities with a
+    /// `soft_delete_col` are marked deleted rather than removed.
     ///
     /// # Example
     ///
@@ -395,7 +411,7 @@ impl SecureConn {
     /// # Returns
     ///
     /// - `Ok(true)` if entity was deleted
-    /// - `Ok(false)` if entity not found in scope
+    /// - `Ok(false)` if entity not found in scope (or already soft-deleted)
     ///
     /// # Errors
     ///
@@ -410,9 +426,9 @@ impl SecureConn {
         })?;
 
         let result = E::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(resource_col).eq(id)))
             .secure()
             .scope_with(scope)
+            .filter(sea_orm::Condition::all().add(Expr::col(resource_col).eq(id)))
             .exec(self)
             .await?;
 
@@ -4,7 +4,7 @@ use sea_orm::{
 };
 use std::sync::Arc;
 
-use crate::secure::cond::build_scope_condition;
+use crate::secure::cond::{build_live_rows_condition, build_scope_condition};
 use crate::secure::error::ScopeError;
 use crate::secure::{AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner};
 
@@ -164,14 +164,10 @@ where
     /// - Resources only → filter by resource IDs
     /// - Both → AND them together
     ///
+    /// Soft-deleted rows are excluded for entities with a `soft_delete_col`;
+    /// use [`scope_with_deleted`](Self::scope_with_deleted) to include them.
     pub fn scope_with(self, scope: &AccessScope) -> SecureSelect<E, Scoped> {
-        let cond = build_scope_condition::<E>(scope);
-        SecureSelect {
-            inner: self.inner.filter(cond),
-            state: Scoped {
-                scope: Arc::new(scope.clone()),
-            },
-        }
+        self.scoped(Arc::new(scope.clone()), false)
     }
 
     /// Apply access control scope using an `Arc<AccessScope>`.
@@ -179,9 +175,24 @@ where
     /// This is useful when you already have the scope in an `Arc` and want to
     /// avoid an extra clone.
     pub fn scope_with_arc(self, scope: Arc<AccessScope>) -> SecureSelect<E, Scoped> {
-        let cond = build_scope_condition::<E>(&scope);
+        self.scoped(scope, false)
+    }
+
+    /// Like [`scope_with`](Self::scope_with), but also returns soft-deleted rows.
+    ///
+    /// Intended for restore, audit and purge flows. Has the same effect as
+    /// `scope_with` for entities without a `soft_delete_col`.
+    pub fn scope_with_deleted(self, scope: &AccessScope) -> SecureSelect<E, Scoped> {
+        self.scoped(Arc::new(scope.clone()), true)
+    }
+
+    fn scoped(self, scope: Arc<AccessScope>, include_deleted: bool) -> SecureSelect<E, Scoped> {
+        let mut inner = self.inner.filter(build_scope_condition::<E>(&scope));
+        if !include_deleted && let Some(live) = build_live_rows_condition::<E>() {
+            inner = inner.filter(live);
+        }
         SecureSelect {
-            inner: self.inner.filter(cond),
+            inner,
             state: Scoped { scope },
         }
     }
@@ -198,7 +209,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(self, runner: &impl DBRunner) -> Result<Vec<E::Model>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -210,7 +221,7 @@ where
     /// Returns `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn one(self, runner: &impl DBRunner) -> Result<Option<E::Model>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
         }
@@ -225,7 +236,7 @@ where
     where
         E::Model: sea_orm::FromQueryResult + Send + Sync,
     {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.count(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.count(tx).await?),
         }
@@ -573,7 +584,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -588,7 +599,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Option<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
         }
@@ -654,7 +665,7 @@ where
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Vec<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
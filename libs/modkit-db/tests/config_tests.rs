@@ -29,6 +29,8 @@ fn test_dbconnconfig_serialization() {
             acquire_timeout: Some(Duration::from_secs(30)),
             ..Default::default()
         }),
+        lock_backend: None,
+        replicas: None,
         server: Some("test_server".to_owned()),
     };
 
@@ -69,6 +69,8 @@ async fn test_dbmanager_server_merge() {
                 acquire_timeout: Some(Duration::from_secs(1)),
                 ..Default::default()
             }),
+            lock_backend: None,
+            replicas: None,
             server: None,
         },
     );
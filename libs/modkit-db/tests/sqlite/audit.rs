@@ -0,0 +1,325 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Audit records written by the secure write paths for `#[secure(audit)]` entities.
+
+use modkit_db::audit::{self, AuditRecord};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::LimitCfg;
+use modkit_db::secure::{
+    Db, DbConn, ScopeError, SecureDeleteExt, SecureUpdateExt, secure_insert,
+    secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db, with_audit_actor};
+use modkit_odata::ODataQuery;
+use modkit_odata::ast::{CompareOperator, Expr as ODataExpr, Value as ODataValue};
+use modkit_security::{AccessScope, SecurityContext};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm_migration::prelude as mig;
+use serde_json::json;
+use uuid::Uuid;
+
+mod doc {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "audited_docs")]
+    #[secure(
+        tenant_col = "tenant_id",
+        resource_col = "id",
+        no_owner,
+        no_type,
+        soft_delete_col = "deleted_at",
+        audit
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub title: String,
+        pub deleted_at: Option<TimeDateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateDocs;
+
+impl mig::MigrationName for CreateDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_audited_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("audited_docs"))
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("title"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("deleted_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("audited_docs"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> Db {
+    let dsn = format!(
+        "sqlite:file:memdb_audit_{}?mode=memory&cache=shared",
+        Uuid::new_v4().simple()
+    );
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateDocs)])
+        .await
+        .expect("migrate");
+    db
+}
+
+async fn insert_doc(conn: &DbConn<'_>, scope: &AccessScope, tenant: Uuid, title: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    secure_insert::<doc::Entity>(
+        doc::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant),
+            title: Set(title.to_owned()),
+            deleted_at: Set(None),
+        },
+        scope,
+        conn,
+    )
+    .await
+    .expect("insert");
+    id
+}
+
+fn by_id(id: Uuid) -> sea_orm::Condition {
+    sea_orm::Condition::all().add(doc::Column::Id.eq(id))
+}
+
+const LIMITS: LimitCfg = LimitCfg {
+    default: 50,
+    max: 100,
+};
+
+async fn records(conn: &DbConn<'_>, scope: &AccessScope, query: &ODataQuery) -> Vec<AuditRecord> {
+    audit::list_records(conn, scope, query, LIMITS)
+        .await
+        .expect("list")
+        .items
+}
+
+#[tokio::test]
+async fn single_row_writes_are_recorded_with_actor() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let ctx = SecurityContext::builder()
+        .subject_id(Uuid::new_v4())
+        .subject_tenant_id(tenant)
+        .build()
+        .unwrap();
+
+    let id = with_audit_actor(&ctx, async {
+        let id = insert_doc(&conn, &scope, tenant, "draft").await;
+        let am = doc::ActiveModel {
+            id: Set(id),
+            title: Set("final".to_owned()),
+            ..Default::default()
+        };
+        secure_update_with_scope::<doc::Entity>(am, &scope, id, &conn)
+            .await
+            .unwrap();
+        id
+    })
+    .await;
+
+    let log = records(&conn, &scope, &ODataQuery::default()).await;
+    assert_eq!(log.len(), 2);
+
+    let (update, insert) = (&log[0], &log[1]);
+    assert_eq!(update.action, "update");
+    assert_eq!(update.entity, "audited_docs");
+    assert_eq!(update.record_id, id.to_string());
+    assert_eq!(update.actor_id, Some(ctx.subject_id()));
+    assert_eq!(update.tenant_id, Some(tenant));
+    assert_eq!(
+        update.changes,
+        json!({"title": {"before": "draft", "after": "final"}}),
+        "only changed columns are recorded"
+    );
+
+    assert_eq!(insert.action, "insert");
+    assert_eq!(insert.changes["title"], json!({"after": "draft"}));
+    assert_eq!(
+        insert.changes["tenant_id"],
+        json!({"after": tenant.to_string()})
+    );
+}
+
+#[tokio::test]
+async fn bulk_writes_record_every_row() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let a = insert_doc(&conn, &scope, tenant, "a").await;
+    let b = insert_doc(&conn, &scope, tenant, "b").await;
+
+    let updated = doc::Entity::update_many()
+        .secure()
+        .scope_with(&scope)
+        .col_expr(doc::Column::Title, Expr::value("renamed"))
+        .exec(&conn)
+        .await
+        .unwrap();
+    assert_eq!(updated.rows_affected, 2);
+
+    doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(a))
+        .exec(&conn)
+        .await
+        .unwrap();
+    doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(b))
+        .permanently()
+        .exec(&conn)
+        .await
+        .unwrap();
+
+    let log = records(&conn, &scope, &ODataQuery::default()).await;
+    let actions: Vec<&str> = log.iter().map(|r| r.action.as_str()).collect();
+    assert_eq!(
+        actions,
+        ["delete", "delete", "update", "update", "insert", "insert"]
+    );
+    assert!(log.iter().all(|r| r.actor_id.is_none()), "no actor was set");
+
+    let (hard, soft) = (&log[0], &log[1]);
+    assert_eq!(hard.record_id, b.to_string());
+    assert_eq!(hard.changes["title"], json!({"before": "renamed"}));
+    assert_eq!(soft.record_id, a.to_string());
+    let mark = soft.changes["deleted_at"].as_object().unwrap();
+    assert_eq!(mark["before"], serde_json::Value::Null);
+    assert!(mark["after"].is_string(), "soft delete records the mark");
+    assert!(soft.changes.get("title").is_none());
+}
+
+#[tokio::test]
+async fn audited_bulk_writes_reject_filters_before_secure() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, "doc").await;
+
+    let err = doc::Entity::update_many()
+        .col_expr(doc::Column::Title, Expr::value("x"))
+        .filter(by_id(id))
+        .secure()
+        .scope_with(&scope)
+        .exec(&conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let err = doc::Entity::delete_many()
+        .filter(by_id(id))
+        .secure()
+        .scope_with(&scope)
+        .permanently()
+        .exec(&conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Invalid(_)));
+}
+
+#[tokio::test]
+async fn records_are_tenant_scoped_filterable_and_purgeable() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let (t1, t2) = (Uuid::new_v4(), Uuid::new_v4());
+    let id = insert_doc(&conn, &AccessScope::for_tenant(t1), t1, "mine").await;
+    insert_doc(&conn, &AccessScope::for_tenant(t2), t2, "theirs").await;
+    doc::Entity::update_many()
+        .secure()
+        .scope_with(&AccessScope::for_tenant(t1))
+        .col_expr(doc::Column::Title, Expr::value("still mine"))
+        .exec(&conn)
+        .await
+        .unwrap();
+
+    let scope = AccessScope::for_tenant(t1);
+    let log = records(&conn, &scope, &ODataQuery::default()).await;
+    assert_eq!(log.len(), 2);
+    assert!(log.iter().all(|r| r.tenant_id == Some(t1)));
+
+    let inserts = ODataQuery::default().with_filter(ODataExpr::Compare(
+        Box::new(ODataExpr::Identifier("action".to_owned())),
+        CompareOperator::Eq,
+        Box::new(ODataExpr::Value(ODataValue::String("insert".to_owned()))),
+    ));
+    let log = records(&conn, &scope, &inserts).await;
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].record_id, id.to_string());
+
+    assert_eq!(
+        audit::purge_older_than(&conn, std::time::Duration::from_hours(1))
+            .await
+            .unwrap(),
+        0
+    );
+    let cutoff = time::OffsetDateTime::now_utc() + time::Duration::seconds(1);
+    assert_eq!(audit::purge_before(&conn, cutoff).await.unwrap(), 3);
+    assert!(
+        records(&conn, &scope, &ODataQuery::default())
+            .await
+            .is_empty()
+    );
+}
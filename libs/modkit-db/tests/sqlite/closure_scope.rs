@@ -0,0 +1,311 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for hierarchy scope filters against the closure tables.
+//!
+//! Schema comes from `modkit_db::closure::migrations()` plus a test entity
+//! table; hierarchies are built with the closure maintenance functions.
+
+use modkit_db::closure;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    Db, DbConn, ScopableEntity, SecureEntityExt, TenantSubtreeScopeFilter, secure_insert,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::{AccessScope, ScopeConstraint, ScopeFilter, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod doc_ent {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "closure_scope_docs")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for doc_ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(doc_ent::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(doc_ent::Column::Id)
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            p if p == pep_properties::RESOURCE_ID => Self::resource_col(),
+            _ => None,
+        }
+    }
+}
+
+struct CreateDocsTable;
+
+impl mig::MigrationName for CreateDocsTable {
+    fn name(&self) -> &'static str {
+        "m001_create_closure_scope_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateDocsTable {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("closure_scope_docs"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("closure_scope_docs"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!(
+        "sqlite:file:memdb_closure_scope_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let db = connect_db(&dsn, opts).await.expect("db connect");
+
+    let mut migrations = closure::migrations();
+    migrations.push(Box::new(CreateDocsTable));
+    run_migrations_for_testing(&db, migrations)
+        .await
+        .expect("migrate");
+    db
+}
+
+async fn insert_doc(conn: &DbConn<'_>, tenant_id: Uuid) -> Uuid {
+    let id = Uuid::new_v4();
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant_id),
+    };
+    secure_insert::<doc_ent::Entity>(am, &AccessScope::allow_all(), conn)
+        .await
+        .expect("insert doc");
+    id
+}
+
+async fn visible(conn: &DbConn<'_>, filter: ScopeFilter) -> Vec<Uuid> {
+    let scope = AccessScope::single(ScopeConstraint::new(vec![filter]));
+    let mut ids: Vec<Uuid> = doc_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(conn)
+        .await
+        .expect("select")
+        .into_iter()
+        .map(|doc| doc.id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+    ids.sort();
+    ids
+}
+
+/// root ─┬─ child
+///       └─ managed (self-managed) ── grandchild
+#[tokio::test]
+async fn tenant_subtree_respects_barriers_and_status() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let (root, child, managed, grandchild) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+
+    closure::insert_tenant(&conn, root, None, false, "active")
+        .await
+        .unwrap();
+    closure::insert_tenant(&conn, child, Some(root), false, "active")
+        .await
+        .unwrap();
+    closure::insert_tenant(&conn, managed, Some(root), true, "active")
+        .await
+        .unwrap();
+    closure::insert_tenant(&conn, grandchild, Some(managed), false, "active")
+        .await
+        .unwrap();
+
+    let root_doc = insert_doc(&conn, root).await;
+    let child_doc = insert_doc(&conn, child).await;
+    let managed_doc = insert_doc(&conn, managed).await;
+    let grandchild_doc = insert_doc(&conn, grandchild).await;
+
+    let subtree = TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root);
+    assert_eq!(
+        visible(&conn, ScopeFilter::InTenantSubtree(subtree.clone())).await,
+        sorted(vec![root_doc, child_doc])
+    );
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::InTenantSubtree(subtree.clone().with_respect_barriers(false))
+        )
+        .await,
+        sorted(vec![root_doc, child_doc, managed_doc, grandchild_doc])
+    );
+
+    // The self-managed tenant sees its own subtree
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, managed)
+        )
+        .await,
+        sorted(vec![managed_doc, grandchild_doc])
+    );
+
+    closure::set_tenant_status(&conn, child, "suspended")
+        .await
+        .unwrap();
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::InTenantSubtree(subtree.with_tenant_status(vec!["active".to_owned()]))
+        )
+        .await,
+        vec![root_doc]
+    );
+
+    assert_eq!(closure::remove_tenant(&conn, managed).await.unwrap(), 2);
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::InTenantSubtree(
+                TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root)
+                    .with_respect_barriers(false)
+            )
+        )
+        .await,
+        sorted(vec![root_doc, child_doc])
+    );
+}
+
+#[tokio::test]
+async fn insert_tenant_rejects_unknown_parent() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+
+    let err = closure::insert_tenant(&conn, Uuid::new_v4(), Some(Uuid::new_v4()), false, "active")
+        .await
+        .expect_err("unknown parent");
+    assert!(err.to_string().contains("parent tenant"), "{err}");
+}
+
+#[tokio::test]
+async fn group_membership_and_subtree() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let (group, subgroup) = (Uuid::new_v4(), Uuid::new_v4());
+
+    closure::insert_group(&conn, group, None).await.unwrap();
+    closure::insert_group(&conn, subgroup, Some(group))
+        .await
+        .unwrap();
+
+    let in_group_doc = insert_doc(&conn, tenant).await;
+    let in_subgroup_doc = insert_doc(&conn, tenant).await;
+    let ungrouped_doc = insert_doc(&conn, tenant).await;
+    closure::add_group_member(&conn, group, in_group_doc)
+        .await
+        .unwrap();
+    closure::add_group_member(&conn, subgroup, in_subgroup_doc)
+        .await
+        .unwrap();
+    // Idempotent
+    closure::add_group_member(&conn, subgroup, in_subgroup_doc)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::in_group(pep_properties::RESOURCE_ID, vec![group])
+        )
+        .await,
+        vec![in_group_doc]
+    );
+    assert_eq!(
+        visible(
+            &conn,
+            ScopeFilter::in_group_subtree(pep_properties::RESOURCE_ID, group)
+        )
+        .await,
+        sorted(vec![in_group_doc, in_subgroup_doc])
+    );
+    assert!(
+        !visible(
+            &conn,
+            ScopeFilter::in_group_subtree(pep_properties::RESOURCE_ID, group)
+        )
+        .await
+        .contains(&ungrouped_doc)
+    );
+
+    assert!(
+        closure::remove_group_member(&conn, group, in_group_doc)
+            .await
+            .unwrap()
+    );
+    assert_eq!(closure::remove_group(&conn, subgroup).await.unwrap(), 1);
+    assert!(
+        visible(
+            &conn,
+            ScopeFilter::in_group_subtree(pep_properties::RESOURCE_ID, group)
+        )
+        .await
+        .is_empty()
+    );
+}
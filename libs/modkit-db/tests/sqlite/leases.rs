@@ -0,0 +1,166 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Database-backed advisory lock leases.
+//!
+//! Covers acquisition conflicts, fencing token monotonicity, stale-lease takeover,
+//! and selecting the file backend instead.
+
+use std::time::Duration;
+
+use modkit_db::{ConnectOpts, Db, DbConnConfig, LockBackend, LockConfig, build_db, connect_db};
+use uuid::Uuid;
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!(
+        "sqlite:file:memdb_leases_{name}_{}?mode=memory&cache=shared",
+        Uuid::new_v4().simple()
+    );
+    connect_db(&dsn, opts).await.expect("db connect")
+}
+
+fn no_retry() -> LockConfig {
+    LockConfig {
+        max_attempts: Some(1),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn lease_conflict_and_release() {
+    let db = setup("conflict").await;
+
+    let guard = db.lock("jobs", "nightly").await.unwrap();
+    assert_eq!(guard.fencing_token(), Some(1));
+
+    assert!(db.lock("jobs", "nightly").await.is_err());
+    assert!(
+        db.try_lock("jobs", "nightly", no_retry())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Same key in another module namespace is independent.
+    let other = db.lock("billing", "nightly").await.unwrap();
+    other.release().await;
+
+    guard.release().await;
+    let again = db.lock("jobs", "nightly").await.unwrap();
+    assert_eq!(again.fencing_token(), Some(2));
+    again.release().await;
+}
+
+#[tokio::test]
+async fn fencing_tokens_increase_monotonically() {
+    let db = setup("fencing").await;
+
+    let mut last = 0;
+    for _ in 0..5 {
+        let guard = db.lock("jobs", "reindex").await.unwrap();
+        let token = guard.fencing_token().unwrap();
+        assert!(token > last, "token {token} must exceed {last}");
+        last = token;
+        guard.release().await;
+    }
+}
+
+#[tokio::test]
+async fn stale_lease_is_taken_over() {
+    let db = setup("takeover").await;
+
+    // A holder that stops renewing (heartbeat far beyond the TTL) behaves like a crashed one.
+    let stale_cfg = LockConfig {
+        lease_ttl: Duration::from_millis(100),
+        heartbeat_interval: Some(Duration::from_hours(1)),
+        ..no_retry()
+    };
+    let stale = db
+        .try_lock("jobs", "compact", stale_cfg)
+        .await
+        .unwrap()
+        .expect("first acquisition");
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let fresh = db
+        .try_lock("jobs", "compact", no_retry())
+        .await
+        .unwrap()
+        .expect("expired lease should be taken over");
+    assert!(fresh.fencing_token() > stale.fencing_token());
+
+    // The former holder releasing late must not free the new holder's lease.
+    stale.release().await;
+    assert!(
+        db.try_lock("jobs", "compact", no_retry())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    fresh.release().await;
+}
+
+#[tokio::test]
+async fn heartbeat_keeps_lease_alive() {
+    let db = setup("heartbeat").await;
+
+    let cfg = LockConfig {
+        lease_ttl: Duration::from_millis(150),
+        heartbeat_interval: Some(Duration::from_millis(30)),
+        ..no_retry()
+    };
+    let guard = db
+        .try_lock("jobs", "sync", cfg)
+        .await
+        .unwrap()
+        .expect("acquired");
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+
+    assert!(
+        db.try_lock("jobs", "sync", no_retry())
+            .await
+            .unwrap()
+            .is_none(),
+        "renewed lease must not be taken over"
+    );
+    guard.release().await;
+}
+
+#[tokio::test]
+async fn file_backend_remains_selectable() {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        lock_backend: LockBackend::File,
+        ..Default::default()
+    };
+    let dsn = format!(
+        "sqlite:file:memdb_leases_file_{}?mode=memory&cache=shared",
+        Uuid::new_v4().simple()
+    );
+    let db = connect_db(&dsn, opts).await.unwrap();
+
+    let guard = db.lock("jobs", "desktop").await.unwrap();
+    assert_eq!(guard.fencing_token(), None);
+    guard.release().await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let cfg = DbConnConfig {
+        dsn: Some(format!(
+            "sqlite://{}",
+            dir.path().join("leases.db").display()
+        )),
+        lock_backend: Some(LockBackend::File),
+        ..Default::default()
+    };
+    let db = build_db(cfg, None).await.unwrap();
+    let guard = db.lock("jobs", "desktop_cfg").await.unwrap();
+    assert_eq!(guard.fencing_token(), None);
+    guard.release().await;
+}
@@ -5,10 +5,16 @@
 
 #![cfg(feature = "sqlite")]
 
+mod audit;
+mod closure_scope;
 mod concurrency_tests;
+mod leases;
 mod manager;
 mod options;
+mod outbox;
 mod pooling_tests;
+mod replicas;
+mod row_lifecycle;
 mod secure_insert_tenant_validation;
 mod secure_update_tenant_safety;
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -0,0 +1,208 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Outbox storage: transactional enqueue, ordered reads, offsets and dead letters.
+
+use std::time::Duration;
+
+use modkit_db::migration_runner::{run_migrations_for_module, run_migrations_for_testing};
+use modkit_db::outbox::Outbox;
+use modkit_db::{ConnectOpts, Db, DbError, connect_db};
+use serde_json::json;
+use uuid::Uuid;
+
+async fn setup(outbox: &Outbox) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let dsn = format!(
+        "sqlite:file:memdb_outbox_{}?mode=memory&cache=shared",
+        Uuid::new_v4().simple()
+    );
+    let db = connect_db(&dsn, opts).await.expect("db connect");
+    run_migrations_for_testing(&db, vec![outbox.migration()])
+        .await
+        .expect("outbox migration");
+    db
+}
+
+#[tokio::test]
+async fn enqueue_follows_transaction_outcome() {
+    let outbox = Outbox::new("orders");
+    let db = setup(&outbox).await;
+
+    let ob = outbox.clone();
+    db.transaction_ref(move |tx| {
+        Box::pin(async move {
+            ob.enqueue(tx, "order.created", &json!({"id": 1})).await?;
+            ob.enqueue(tx, "order.shipped", &json!({"id": 1})).await?;
+            Ok(())
+        })
+    })
+    .await
+    .unwrap();
+
+    let ob = outbox.clone();
+    let res: Result<(), DbError> = db
+        .transaction_ref(move |tx| {
+            Box::pin(async move {
+                ob.enqueue(tx, "order.created", &json!({"id": 2})).await?;
+                Err(DbError::Other(anyhow::anyhow!("business change failed")))
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    let conn = db.conn().unwrap();
+    let created = outbox
+        .fetch_after(&conn, "order.created", 0, 100)
+        .await
+        .unwrap();
+    assert_eq!(created.len(), 1, "rolled back event must not be visible");
+    assert_eq!(created[0].payload, json!({"id": 1}));
+
+    let shipped = outbox
+        .fetch_after(&conn, "order.shipped", 0, 100)
+        .await
+        .unwrap();
+    assert_eq!(shipped.len(), 1);
+    assert!(shipped[0].seq > created[0].seq);
+}
+
+#[tokio::test]
+async fn fetch_after_respects_offset_and_limit() {
+    let outbox = Outbox::new("orders");
+    let db = setup(&outbox).await;
+    let conn = db.conn().unwrap();
+
+    for i in 0..5 {
+        outbox
+            .enqueue(&conn, "order.created", &json!({ "n": i }))
+            .await
+            .unwrap();
+    }
+
+    let first = outbox
+        .fetch_after(&conn, "order.created", 0, 2)
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+
+    let rest = outbox
+        .fetch_after(&conn, "order.created", first[1].seq, 100)
+        .await
+        .unwrap();
+    let ns: Vec<_> = rest.iter().map(|r| r.payload["n"].clone()).collect();
+    assert_eq!(ns, vec![json!(2), json!(3), json!(4)]);
+}
+
+#[tokio::test]
+async fn fetch_ready_reports_frontier_across_topics() {
+    let outbox = Outbox::new("orders");
+    let db = setup(&outbox).await;
+    let conn = db.conn().unwrap();
+
+    for topic in ["order.created", "order.shipped", "order.created"] {
+        outbox.enqueue(&conn, topic, &json!({})).await.unwrap();
+    }
+    let all = outbox
+        .fetch_after(&conn, "order.created", 0, 100)
+        .await
+        .unwrap();
+
+    let batch = outbox
+        .fetch_ready(&conn, "order.shipped", 0, 100, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(batch.records.len(), 1);
+    assert_eq!(batch.frontier, all[1].seq, "rows of other topics count too");
+
+    let batch = outbox
+        .fetch_ready(&conn, "order.created", 0, 2, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(batch.records, all[..1]);
+    assert_eq!(batch.frontier, all[0].seq + 1);
+}
+
+#[tokio::test]
+async fn offsets_are_per_subscriber_and_topic() {
+    let outbox = Outbox::new("orders");
+    let db = setup(&outbox).await;
+    let conn = db.conn().unwrap();
+
+    assert_eq!(outbox.offset(&conn, "billing", "created").await.unwrap(), 0);
+    outbox
+        .commit_offset(&conn, "billing", "created", 7)
+        .await
+        .unwrap();
+    outbox
+        .commit_offset(&conn, "billing", "created", 9)
+        .await
+        .unwrap();
+    assert_eq!(outbox.offset(&conn, "billing", "created").await.unwrap(), 9);
+    assert_eq!(outbox.offset(&conn, "billing", "shipped").await.unwrap(), 0);
+    assert_eq!(outbox.offset(&conn, "search", "created").await.unwrap(), 0);
+
+    outbox
+        .enqueue(&conn, "order.created", &json!({"id": 3}))
+        .await
+        .unwrap();
+    let record = outbox
+        .fetch_after(&conn, "order.created", 0, 1)
+        .await
+        .unwrap()
+        .remove(0);
+    outbox
+        .dead_letter(&conn, "billing", &record, "card declined", 3)
+        .await
+        .unwrap();
+
+    let dead = outbox.dead_letters(&conn, "billing").await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].seq, record.seq);
+    assert_eq!(dead[0].error, "card declined");
+    assert_eq!(dead[0].attempts, 3);
+    assert_eq!(dead[0].payload, json!({"id": 3}));
+    assert!(
+        outbox
+            .dead_letters(&conn, "search")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn modules_get_separate_outboxes() {
+    let orders = Outbox::new("orders");
+    let users = Outbox::new("users");
+    let db = setup(&orders).await;
+    // Both migrations share a name: they are told apart by the module's history table.
+    run_migrations_for_module(&db, "users", vec![users.migration()])
+        .await
+        .unwrap();
+    let conn = db.conn().unwrap();
+
+    orders
+        .enqueue(&conn, "created", &json!({"id": 1}))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        orders
+            .fetch_after(&conn, "created", 0, 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(
+        users
+            .fetch_after(&conn, "created", 0, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
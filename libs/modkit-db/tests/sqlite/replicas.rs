@@ -0,0 +1,234 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Read-replica routing.
+//!
+//! Primary and replicas are separate `SQLite` files seeded with different rows, so the
+//! rows a query returns tell which node served it.
+
+use std::path::{Path, PathBuf};
+
+use modkit_db::config::ReplicasCfg;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{DbConn, SecureEntityExt, secure_insert};
+use modkit_db::{Db, DbConnConfig, DbError, build_db, read_your_writes};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod note {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "replica_notes")]
+    #[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub origin: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateNotes;
+
+impl mig::MigrationName for CreateNotes {
+    fn name(&self) -> &'static str {
+        "m001_create_replica_notes"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateNotes {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("replica_notes"))
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("origin"))
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+fn sqlite_cfg(path: &Path) -> DbConnConfig {
+    DbConnConfig {
+        dsn: Some(format!("sqlite://{}", path.display())),
+        ..Default::default()
+    }
+}
+
+async fn insert(conn: &DbConn<'_>, tenant: Uuid, origin: &str) {
+    let am = note::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        origin: Set(origin.to_owned()),
+    };
+    secure_insert::<note::Entity>(am, &AccessScope::for_tenant(tenant), conn)
+        .await
+        .unwrap();
+}
+
+/// Create a database file holding a single note from `origin`.
+async fn seed(dir: &Path, origin: &str, tenant: Uuid) -> PathBuf {
+    let path = dir.join(format!("{origin}.db"));
+    let db = build_db(sqlite_cfg(&path), None).await.unwrap();
+    run_migrations_for_testing(&db, vec![Box::new(CreateNotes)])
+        .await
+        .unwrap();
+    insert(&db.conn().unwrap(), tenant, origin).await;
+    path
+}
+
+async fn replicated(primary: &Path, replicas: &[PathBuf]) -> Db {
+    let cfg = DbConnConfig {
+        replicas: Some(ReplicasCfg {
+            nodes: replicas.iter().map(|p| sqlite_cfg(p)).collect(),
+            ..Default::default()
+        }),
+        ..sqlite_cfg(primary)
+    };
+    build_db(cfg, None).await.unwrap()
+}
+
+async fn origins(conn: &DbConn<'_>, tenant: Uuid) -> Vec<String> {
+    let mut origins: Vec<String> = note::Entity::find()
+        .secure()
+        .scope_with(&AccessScope::for_tenant(tenant))
+        .all(conn)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|n| n.origin)
+        .collect();
+    origins.sort();
+    origins
+}
+
+#[tokio::test]
+async fn reads_go_to_replica_and_writes_pin_the_primary() {
+    let dir = tempfile::tempdir().unwrap();
+    let tenant = Uuid::new_v4();
+    let primary = seed(dir.path(), "primary", tenant).await;
+    let replica = seed(dir.path(), "replica", tenant).await;
+    let db = replicated(&primary, &[replica]).await;
+
+    let conn = db.conn().unwrap();
+    assert_eq!(origins(&conn, tenant).await, ["replica"]);
+
+    insert(&conn, tenant, "written").await;
+    assert_eq!(origins(&conn, tenant).await, ["primary", "written"]);
+
+    // A fresh connection outside any read-your-writes scope reads the replica again.
+    assert_eq!(origins(&db.conn().unwrap(), tenant).await, ["replica"]);
+}
+
+#[tokio::test]
+async fn transactions_read_the_primary() {
+    let dir = tempfile::tempdir().unwrap();
+    let tenant = Uuid::new_v4();
+    let primary = seed(dir.path(), "primary", tenant).await;
+    let replica = seed(dir.path(), "replica", tenant).await;
+    let db = replicated(&primary, &[replica]).await;
+
+    let seen = db
+        .transaction_ref(move |tx| {
+            Box::pin(async move {
+                let rows = note::Entity::find()
+                    .secure()
+                    .scope_with(&AccessScope::for_tenant(tenant))
+                    .all(tx)
+                    .await?;
+                Ok::<_, DbError>(rows.into_iter().map(|n| n.origin).collect::<Vec<_>>())
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(seen, ["primary"]);
+}
+
+#[tokio::test]
+async fn read_your_writes_scope_covers_every_connection() {
+    let dir = tempfile::tempdir().unwrap();
+    let tenant = Uuid::new_v4();
+    let primary = seed(dir.path(), "primary", tenant).await;
+    let replica = seed(dir.path(), "replica", tenant).await;
+    let db = replicated(&primary, &[replica]).await;
+
+    read_your_writes(async {
+        assert_eq!(origins(&db.conn().unwrap(), tenant).await, ["replica"]);
+
+        insert(&db.conn().unwrap(), tenant, "written").await;
+
+        assert_eq!(
+            origins(&db.conn().unwrap(), tenant).await,
+            ["primary", "written"]
+        );
+    })
+    .await;
+
+    // Each scope starts clean.
+    read_your_writes(async {
+        assert_eq!(origins(&db.conn().unwrap(), tenant).await, ["replica"]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn reads_rotate_across_replicas() {
+    let dir = tempfile::tempdir().unwrap();
+    let tenant = Uuid::new_v4();
+    let primary = seed(dir.path(), "primary", tenant).await;
+    let first = seed(dir.path(), "first", tenant).await;
+    let second = seed(dir.path(), "second", tenant).await;
+    let db = replicated(&primary, &[first, second]).await;
+
+    let conn = db.conn().unwrap();
+    let mut served = Vec::new();
+    for _ in 0..4 {
+        served.extend(origins(&conn, tenant).await);
+    }
+    served.sort();
+    assert_eq!(served, ["first", "first", "second", "second"]);
+}
+
+#[tokio::test]
+async fn replica_nodes_cannot_reference_a_server() {
+    let dir = tempfile::tempdir().unwrap();
+    let cfg = DbConnConfig {
+        replicas: Some(ReplicasCfg {
+            nodes: vec![DbConnConfig {
+                server: Some("sqlite_server".to_owned()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }),
+        ..sqlite_cfg(&dir.path().join("primary.db"))
+    };
+
+    let err = build_db(cfg, None).await.unwrap_err();
+    assert!(matches!(err, DbError::InvalidConfig(_)), "got {err:?}");
+}
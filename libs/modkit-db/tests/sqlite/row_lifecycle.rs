@@ -0,0 +1,299 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Soft delete and optimistic concurrency declared via `#[secure(soft_delete_col, version_col)]`.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    Db, DbConn, ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert,
+    secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::AccessScope;
+use sea_orm::entity::prelude::*;
+use sea_orm::{IntoActiveModel, Set};
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod doc {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "lifecycle_docs")]
+    #[secure(
+        tenant_col = "tenant_id",
+        resource_col = "id",
+        no_owner,
+        no_type,
+        soft_delete_col = "deleted_at",
+        version_col = "version"
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub title: String,
+        pub deleted_at: Option<TimeDateTimeWithTimeZone>,
+        pub version: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateDocs;
+
+impl mig::MigrationName for CreateDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_lifecycle_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("lifecycle_docs"))
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("title"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("deleted_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("version"))
+                            .integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("lifecycle_docs"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> Db {
+    let dsn = format!(
+        "sqlite:file:memdb_row_lifecycle_{}?mode=memory&cache=shared",
+        Uuid::new_v4().simple()
+    );
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateDocs)])
+        .await
+        .expect("migrate");
+    db
+}
+
+async fn insert_doc(conn: &DbConn<'_>, scope: &AccessScope, tenant: Uuid, title: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    secure_insert::<doc::Entity>(
+        doc::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant),
+            title: Set(title.to_owned()),
+            deleted_at: Set(None),
+            version: Set(0),
+        },
+        scope,
+        conn,
+    )
+    .await
+    .expect("insert");
+    id
+}
+
+fn by_id(id: Uuid) -> sea_orm::Condition {
+    sea_orm::Condition::all().add(doc::Column::Id.eq(id))
+}
+
+#[tokio::test]
+async fn delete_marks_rows_and_selects_hide_them() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let gone = insert_doc(&conn, &scope, tenant, "gone").await;
+    insert_doc(&conn, &scope, tenant, "kept").await;
+
+    let deleted = doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(gone))
+        .exec(&conn)
+        .await
+        .unwrap();
+    assert_eq!(deleted.rows_affected, 1);
+
+    let visible = doc::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .unwrap();
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].title, "kept");
+
+    let all = doc::Entity::find()
+        .secure()
+        .scope_with_deleted(&scope)
+        .all(&conn)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+    let row = all.iter().find(|d| d.id == gone).unwrap();
+    assert!(row.deleted_at.is_some(), "row is kept with a deletion mark");
+
+    let again = doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(gone))
+        .exec(&conn)
+        .await
+        .unwrap();
+    assert_eq!(
+        again.rows_affected, 0,
+        "already deleted rows are not touched"
+    );
+}
+
+#[tokio::test]
+async fn soft_delete_requires_wrapper_filters_and_can_purge() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, "doc").await;
+
+    let err = doc::Entity::delete_many()
+        .filter(by_id(id))
+        .secure()
+        .scope_with(&scope)
+        .exec(&conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let purged = doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .permanently()
+        .exec(&conn)
+        .await
+        .unwrap();
+    assert_eq!(purged.rows_affected, 1);
+
+    let count = doc::Entity::find()
+        .secure()
+        .scope_with_deleted(&scope)
+        .count(&conn)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn update_checks_and_bumps_version() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, "v0").await;
+
+    let loaded = doc::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .and_id(id)
+        .unwrap()
+        .one(&conn)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut first = loaded.clone().into_active_model();
+    first.title = Set("first".to_owned());
+    let updated = secure_update_with_scope::<doc::Entity>(first, &scope, id, &conn)
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 1);
+
+    // A second writer still holding version 0 loses.
+    let mut stale = loaded.into_active_model();
+    stale.title = Set("stale".to_owned());
+    let err = secure_update_with_scope::<doc::Entity>(stale, &scope, id, &conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::VersionConflict { expected: 0 }));
+
+    // Leaving the version unset updates whatever is stored and still bumps it.
+    let blind = doc::ActiveModel {
+        id: Set(id),
+        title: Set("blind".to_owned()),
+        ..Default::default()
+    };
+    let updated = secure_update_with_scope::<doc::Entity>(blind, &scope, id, &conn)
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 2);
+    assert_eq!(updated.title, "blind");
+}
+
+#[tokio::test]
+async fn soft_deleted_rows_cannot_be_updated() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, "doc").await;
+
+    doc::Entity::delete_many()
+        .secure()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .exec(&conn)
+        .await
+        .unwrap();
+
+    let am = doc::ActiveModel {
+        id: Set(id),
+        title: Set("revived".to_owned()),
+        ..Default::default()
+    };
+    let err = secure_update_with_scope::<doc::Entity>(am, &scope, id, &conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Denied(_)));
+}
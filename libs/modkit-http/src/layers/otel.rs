@@ -15,6 +15,9 @@ use tower::{Layer, Service};
 ///
 /// Records `http.status_code` on response and sets `error=true` for 4xx/5xx.
 /// Injects W3C trace context headers when OTEL feature is enabled.
+///
+/// With the `otel` feature the request duration is also recorded in the
+/// `http.client.request.duration` histogram of the global meter provider.
 #[derive(Clone, Default)]
 pub struct OtelLayer;
 
@@ -30,14 +33,32 @@ impl<S> Layer<S> for OtelLayer {
     type Service = OtelService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        OtelService { inner }
+        OtelService {
+            inner,
+            #[cfg(feature = "otel")]
+            duration: client_duration_histogram(),
+        }
     }
 }
 
+#[cfg(feature = "otel")]
+fn client_duration_histogram() -> opentelemetry::metrics::Histogram<f64> {
+    opentelemetry::global::meter("modkit_http")
+        .f64_histogram("http.client.request.duration")
+        .with_description("Duration of outbound HTTP requests")
+        .with_unit("s")
+        .with_boundaries(vec![
+            0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+        ])
+        .build()
+}
+
 /// Service that wraps requests with OpenTelemetry tracing spans
 #[derive(Clone)]
 pub struct OtelService<S> {
     inner: S,
+    #[cfg(feature = "otel")]
+    duration: opentelemetry::metrics::Histogram<f64>,
 }
 
 impl<S, ResBody> Service<Request<Full<Bytes>>> for OtelService<S>
@@ -77,6 +98,19 @@ where
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
 
+        #[cfg(feature = "otel")]
+        let (duration, start, mut attrs) = (
+            self.duration.clone(),
+            std::time::Instant::now(),
+            vec![
+                opentelemetry::KeyValue::new("http.request.method", method.to_string()),
+                opentelemetry::KeyValue::new(
+                    "server.address",
+                    uri.host().unwrap_or_default().to_owned(),
+                ),
+            ],
+        );
+
         Box::pin(async move {
             let span = tracing::span!(
                 Level::INFO, "outgoing_http",
@@ -102,6 +136,18 @@ where
                 }
             }
 
+            #[cfg(feature = "otel")]
+            {
+                attrs.push(match &result {
+                    Ok(response) => opentelemetry::KeyValue::new(
+                        "http.response.status_code",
+                        i64::from(response.status().as_u16()),
+                    ),
+                    Err(_) => opentelemetry::KeyValue::new("error.type", "transport"),
+                });
+                duration.record(start.elapsed().as_secs_f64(), &attrs);
+            }
+
             result
         })
     }
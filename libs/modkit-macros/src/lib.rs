@@ -33,6 +33,8 @@ enum Capability {
     System,
     GrpcHub,
     Grpc,
+    Health,
+    ConfigReload,
 }
 
 impl Capability {
@@ -44,6 +46,8 @@ impl Capability {
         "system",
         "grpc_hub",
         "grpc",
+        "health",
+        "config_reload",
     ];
 
     fn suggest_similar(input: &str) -> Vec<&'static str> {
@@ -71,11 +75,13 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, health, config_reload"
                     )
                 } else {
                     format!(
@@ -98,11 +104,13 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, health, config_reload"
                     )
                 } else {
                     format!(
@@ -590,6 +598,24 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                     {}
                 };
             },
+            Capability::Health => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_HealthCheckCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::HealthCheckCapability,
+                    {}
+                };
+            },
+            Capability::ConfigReload => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_ConfigReloadCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::ConfigReloadCapability,
+                    {}
+                };
+            },
         };
         cap_asserts.push(q);
     }
@@ -739,6 +765,14 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 b.register_grpc_service_with_meta(#name_lit,
                     module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::GrpcServiceCapability>);
             },
+            Capability::Health => quote! {
+                b.register_health_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::HealthCheckCapability>);
+            },
+            Capability::ConfigReload => quote! {
+                b.register_config_reload_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::ConfigReloadCapability>);
+            },
         }
     });
 
@@ -110,18 +110,27 @@ pub mod pep_properties {
 /// Variants mirror the predicate types from the PDP response:
 /// - [`ScopeFilter::Eq`] — equality (`property = value`)
 /// - [`ScopeFilter::In`] — set membership (`property IN (values)`)
+/// - [`ScopeFilter::InTenantSubtree`] — the property holds a tenant in the subtree
+///   rooted at a given tenant
+/// - [`ScopeFilter::InGroup`] — the resource is a member of one of the given groups
+/// - [`ScopeFilter::InGroupSubtree`] — the resource is a member of a group in the
+///   subtree rooted at a given group
 ///
-/// ## Future extensions
-///
-/// Additional filter types (`in_tenant_subtree`, `in_group`,
-/// `in_group_subtree`) are planned. See the authorization design document
-/// (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+/// The last three carry no value list: they are resolved against closure tables
+/// (`tenant_closure`, `resource_group_closure`, `resource_group_membership`) when
+/// the filter is turned into SQL. See `docs/arch/authorization/DESIGN.md`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ScopeFilter {
     /// Equality: `property = value`.
     Eq(EqScopeFilter),
     /// Set membership: `property IN (values)`.
     In(InScopeFilter),
+    /// Tenant hierarchy: `property` is a descendant of (or equal to) a root tenant.
+    InTenantSubtree(TenantSubtreeScopeFilter),
+    /// Group membership: `property` identifies a member of one of the groups.
+    InGroup(GroupScopeFilter),
+    /// Group hierarchy: `property` identifies a member of a group under a root group.
+    InGroupSubtree(GroupSubtreeScopeFilter),
 }
 
 /// Equality scope filter: `property = value`.
@@ -204,6 +213,142 @@ impl InScopeFilter {
     }
 }
 
+/// Tenant subtree scope filter: `property` is in the subtree of `root_tenant_id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TenantSubtreeScopeFilter {
+    /// Authorization property holding a tenant ID (usually `owner_tenant_id`).
+    property: String,
+    /// Root of the subtree; the root itself is included.
+    root_tenant_id: Uuid,
+    /// Stop at self-managed tenants below the root.
+    respect_barriers: bool,
+    /// Only descendants with one of these statuses; `None` means any status.
+    tenant_status: Option<Vec<String>>,
+}
+
+/// Group membership scope filter: the resource belongs to one of `group_ids`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupScopeFilter {
+    /// Authorization property holding the resource ID matched against memberships.
+    property: String,
+    /// Groups the resource may belong to.
+    group_ids: Vec<Uuid>,
+}
+
+/// Group subtree scope filter: the resource belongs to a group under `root_group_id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupSubtreeScopeFilter {
+    /// Authorization property holding the resource ID matched against memberships.
+    property: String,
+    /// Root of the group subtree; the root itself is included.
+    root_group_id: Uuid,
+}
+
+impl TenantSubtreeScopeFilter {
+    /// Create a tenant subtree filter that respects barriers and ignores status.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            respect_barriers: true,
+            tenant_status: None,
+        }
+    }
+
+    /// Whether self-managed tenants below the root (and their subtrees) are excluded.
+    #[must_use]
+    pub fn with_respect_barriers(mut self, respect_barriers: bool) -> Self {
+        self.respect_barriers = respect_barriers;
+        self
+    }
+
+    /// Restrict matching tenants to the given statuses.
+    #[must_use]
+    pub fn with_tenant_status(mut self, statuses: Vec<String>) -> Self {
+        self.tenant_status = Some(statuses);
+        self
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The subtree root.
+    #[inline]
+    #[must_use]
+    pub fn root_tenant_id(&self) -> Uuid {
+        self.root_tenant_id
+    }
+
+    /// Whether barriers are respected.
+    #[inline]
+    #[must_use]
+    pub fn respect_barriers(&self) -> bool {
+        self.respect_barriers
+    }
+
+    /// Allowed tenant statuses, if restricted.
+    #[inline]
+    #[must_use]
+    pub fn tenant_status(&self) -> Option<&[String]> {
+        self.tenant_status.as_deref()
+    }
+}
+
+impl GroupScopeFilter {
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The candidate groups.
+    #[inline]
+    #[must_use]
+    pub fn group_ids(&self) -> &[Uuid] {
+        &self.group_ids
+    }
+}
+
+impl GroupSubtreeScopeFilter {
+    /// Create a group subtree filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// The subtree root.
+    #[inline]
+    #[must_use]
+    pub fn root_group_id(&self) -> Uuid {
+        self.root_group_id
+    }
+}
+
 impl ScopeFilter {
     /// Create an equality filter (`property = value`).
     #[must_use]
@@ -226,23 +371,59 @@ impl ScopeFilter {
         ))
     }
 
+    /// Create a tenant subtree filter rooted at `root_tenant_id` (barriers respected).
+    #[must_use]
+    pub fn in_tenant_subtree(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self::InTenantSubtree(TenantSubtreeScopeFilter::new(property, root_tenant_id))
+    }
+
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn in_group(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self::InGroup(GroupScopeFilter::new(property, group_ids))
+    }
+
+    /// Create a group subtree membership filter.
+    #[must_use]
+    pub fn in_group_subtree(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self::InGroupSubtree(GroupSubtreeScopeFilter::new(property, root_group_id))
+    }
+
     /// The authorization property name.
     #[must_use]
     pub fn property(&self) -> &str {
         match self {
             Self::Eq(f) => f.property(),
             Self::In(f) => f.property(),
+            Self::InTenantSubtree(f) => f.property(),
+            Self::InGroup(f) => f.property(),
+            Self::InGroupSubtree(f) => f.property(),
         }
     }
 
+    /// Whether the filter is resolved through a closure table rather than
+    /// against literal values.
+    #[must_use]
+    pub fn is_hierarchical(&self) -> bool {
+        matches!(
+            self,
+            Self::InTenantSubtree(_) | Self::InGroup(_) | Self::InGroupSubtree(_)
+        )
+    }
+
     /// Collect all values as a slice-like view for iteration.
     ///
     /// For `Eq`, returns a single-element slice; for `In`, returns the values slice.
+    /// Hierarchical filters have no literal values and return an empty view: the
+    /// set they match is only known to the database.
     #[must_use]
     pub fn values(&self) -> ScopeFilterValues<'_> {
         match self {
             Self::Eq(f) => ScopeFilterValues::Single(&f.value),
             Self::In(f) => ScopeFilterValues::Multiple(&f.values),
+            Self::InTenantSubtree(_) | Self::InGroup(_) | Self::InGroupSubtree(_) => {
+                ScopeFilterValues::Multiple(&[])
+            }
         }
     }
 
@@ -600,4 +781,54 @@ mod tests {
         assert!(scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T1)));
         assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T2)));
     }
+
+    // --- Hierarchical filters ---
+
+    #[test]
+    fn tenant_subtree_filter_defaults_and_builders() {
+        let f = TenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, uid(T1));
+        assert!(f.respect_barriers());
+        assert!(f.tenant_status().is_none());
+
+        let f = f
+            .with_respect_barriers(false)
+            .with_tenant_status(vec!["active".to_owned()]);
+        assert!(!f.respect_barriers());
+        assert_eq!(f.tenant_status(), Some(&["active".to_owned()][..]));
+        assert_eq!(f.root_tenant_id(), uid(T1));
+    }
+
+    #[test]
+    fn hierarchical_filters_have_no_literal_values() {
+        let scope = AccessScope::from_constraints(vec![
+            ScopeConstraint::new(vec![ScopeFilter::in_tenant_subtree(
+                pep_properties::OWNER_TENANT_ID,
+                uid(T1),
+            )]),
+            ScopeConstraint::new(vec![ScopeFilter::in_group(
+                pep_properties::RESOURCE_ID,
+                vec![uid(T2)],
+            )]),
+            ScopeConstraint::new(vec![ScopeFilter::in_group_subtree(
+                pep_properties::RESOURCE_ID,
+                uid(T2),
+            )]),
+        ]);
+
+        assert!(scope.has_property(pep_properties::OWNER_TENANT_ID));
+        assert!(
+            scope
+                .all_values_for(pep_properties::OWNER_TENANT_ID)
+                .is_empty()
+        );
+        // The root is not a literal value: the subtree is resolved in SQL
+        assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T1)));
+        assert!(
+            scope
+                .constraints()
+                .iter()
+                .all(|c| c.filters()[0].is_hierarchical())
+        );
+        assert!(!ScopeFilter::eq(pep_properties::RESOURCE_ID, uid(T1)).is_hierarchical());
+    }
 }
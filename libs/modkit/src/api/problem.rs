@@ -20,6 +20,14 @@ pub fn conflict(detail: impl Into<String>) -> Problem {
     Problem::new(StatusCode::CONFLICT, "Conflict", detail)
 }
 
+pub fn precondition_failed(detail: impl Into<String>) -> Problem {
+    Problem::new(
+        StatusCode::PRECONDITION_FAILED,
+        "Precondition Failed",
+        detail,
+    )
+}
+
 pub fn internal_error(detail: impl Into<String>) -> Problem {
     Problem::new(
         StatusCode::INTERNAL_SERVER_ERROR,
@@ -28,6 +36,24 @@ pub fn internal_error(detail: impl Into<String>) -> Problem {
     )
 }
 
+/// Map a database error to a Problem.
+///
+/// A version conflict is `409 Conflict`, or `412 Precondition Failed` when `if_match`
+/// says the expected version came from an `If-Match` header. Other errors are a `500`
+/// that does not expose the underlying cause.
+#[cfg(feature = "db")]
+pub fn from_db_error(err: &modkit_db::DbError, if_match: bool) -> Problem {
+    match err {
+        modkit_db::DbError::VersionConflict { expected } if if_match => precondition_failed(
+            format!("If-Match version {expected} does not match the current version"),
+        ),
+        modkit_db::DbError::VersionConflict { expected } => {
+            conflict(format!("Resource was modified after version {expected}"))
+        }
+        _ => internal_error("An internal database error occurred"),
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -91,8 +117,35 @@ mod tests {
         assert_eq!(conflict_resp.status, StatusCode::CONFLICT);
         assert_eq!(conflict_resp.title, "Conflict");
 
+        let precondition_resp = precondition_failed("If-Match does not match current version");
+        assert_eq!(precondition_resp.status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(precondition_resp.title, "Precondition Failed");
+
         let internal_resp = internal_error("Database connection failed");
         assert_eq!(internal_resp.status, StatusCode::INTERNAL_SERVER_ERROR);
         assert_eq!(internal_resp.title, "Internal Server Error");
     }
+
+    #[cfg(feature = "db")]
+    #[test]
+    fn version_conflict_maps_to_conflict_or_precondition_failed() {
+        use http::StatusCode;
+        use modkit_db::{DbError, secure::ScopeError};
+
+        let err = DbError::from(ScopeError::VersionConflict { expected: 3 });
+        assert!(matches!(err, DbError::VersionConflict { expected: 3 }));
+
+        let conflict_resp = from_db_error(&err, false);
+        assert_eq!(conflict_resp.status, StatusCode::CONFLICT);
+        assert!(conflict_resp.detail.contains("version 3"));
+
+        let precondition_resp = from_db_error(&err, true);
+        assert_eq!(precondition_resp.status, StatusCode::PRECONDITION_FAILED);
+        assert!(precondition_resp.detail.contains("If-Match version 3"));
+
+        let other = DbError::from(ScopeError::Denied("not in scope"));
+        let internal_resp = from_db_error(&other, true);
+        assert_eq!(internal_resp.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!internal_resp.detail.contains("not in scope"));
+    }
 }
@@ -0,0 +1,125 @@
+//! Retention for the audit log of `#[secure(audit)]` entities.
+//!
+//! Audit records live in the database of the module that owns the audited entities
+//! (see `modkit_db::audit`), so each such module runs its own [`AuditRetention`]:
+//!
+//! ```rust,ignore
+//! // init
+//! let retention = Arc::new(AuditRetention::new(db.clone(), cfg.audit_retention.clone()));
+//! // start
+//! tokio::spawn(retention.run(cancel));
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use modkit_db::{DBProvider, DbError};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::lifecycle::Runnable;
+
+/// How long audit records are kept and how often expired ones are removed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditRetentionConfig {
+    /// Records older than this are purged.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_age: Duration,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub purge_interval: Duration,
+}
+
+impl Default for AuditRetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_hours(90 * 24),
+            purge_interval: Duration::from_hours(1),
+        }
+    }
+}
+
+/// Periodic purge of expired audit records.
+pub struct AuditRetention {
+    db: DBProvider<DbError>,
+    config: AuditRetentionConfig,
+}
+
+impl AuditRetention {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, config: AuditRetentionConfig) -> Self {
+        Self { db, config }
+    }
+
+    /// Remove the records older than [`AuditRetentionConfig::max_age`] once.
+    ///
+    /// # Errors
+    /// Returns `DbError` if no connection is available or the delete fails.
+    pub async fn purge_once(&self) -> Result<u64, DbError> {
+        let conn = self.db.conn()?;
+        modkit_db::audit::purge_older_than(&conn, self.config.max_age).await
+    }
+}
+
+#[async_trait]
+impl Runnable for AuditRetention {
+    async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        loop {
+            match self.purge_once().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "purged expired audit records"),
+                Err(e) => tracing::warn!(error = %e, "audit retention pass failed"),
+            }
+            tokio::select! {
+                () = cancel.cancelled() => return Ok(()),
+                () = tokio::time::sleep(self.config.purge_interval) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+
+    #[test]
+    fn config_reads_humantime_durations() {
+        let cfg: AuditRetentionConfig =
+            serde_json::from_str(r#"{"max_age": "30d", "purge_interval": "10m"}"#).unwrap();
+        assert_eq!(cfg.max_age, Duration::from_hours(30 * 24));
+        assert_eq!(cfg.purge_interval, Duration::from_mins(10));
+
+        let cfg: AuditRetentionConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(cfg, AuditRetentionConfig::default());
+    }
+
+    #[tokio::test]
+    async fn run_stops_on_cancel() {
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let dsn = format!(
+            "sqlite:file:memdb_audit_{}?mode=memory&cache=shared",
+            uuid::Uuid::now_v7().simple()
+        );
+        let db = connect_db(&dsn, opts).await.unwrap();
+        run_migrations_for_testing(&db, vec![]).await.unwrap();
+        let retention = Arc::new(AuditRetention::new(
+            DBProvider::new(db),
+            AuditRetentionConfig::default(),
+        ));
+
+        assert_eq!(retention.purge_once().await.unwrap(), 0);
+
+        let cancel = CancellationToken::new();
+        let task = tokio::spawn(retention.run(cancel.clone()));
+        cancel.cancel();
+        task.await.unwrap().unwrap();
+    }
+}
@@ -3,18 +3,22 @@
 use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
 use parking_lot::RwLock;
-use std::collections::HashMap;
-use std::path::Path;
-use std::process::Stdio;
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::process::{Child, Command};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use super::log_forwarder::{StreamKind, spawn_stream_forwarder};
-use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use super::{
+    BackendKind, InstanceEvent, InstanceEventKind, InstanceHandle, ModuleRuntimeBackend,
+    OopModuleConfig, RestartConfig,
+};
+use crate::runtime::{MODKIT_INSTANCE_ID_ENV, ModuleManager};
 
 /// Grace period before force-killing processes on shutdown
 const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
@@ -25,6 +29,12 @@ const INSTANCE_STOP_GRACE_PERIOD: Duration = Duration::from_secs(2);
 /// Timeout for waiting on forwarder tasks during shutdown
 const FORWARDER_DRAIN_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// Interval for checking whether a restarted instance serves traffic yet
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Supervision events kept per process slot
+const MAX_HISTORY: usize = 32;
+
 /// Send graceful termination signal to a child process.
 ///
 /// # Returns
@@ -145,9 +155,17 @@ async fn wait_forwarder(handle: Option<JoinHandle<()>>) {
     }
 }
 
-/// Internal representation of a local process instance
-struct LocalInstance {
-    handle: InstanceHandle,
+/// What the backend needs to (re)start the process of a module
+struct ProcessSpec {
+    module: String,
+    binary: PathBuf,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    working_directory: Option<String>,
+}
+
+/// A started child process with its log forwarders
+struct RunningProcess {
     child: Child,
     /// Task handle for stdout log forwarder
     stdout_forwarder: Option<JoinHandle<()>>,
@@ -155,11 +173,311 @@ struct LocalInstance {
     stderr_forwarder: Option<JoinHandle<()>>,
 }
 
-/// Map key type for instances - uses Uuid directly
+impl RunningProcess {
+    /// Let the log forwarders flush what the exited process wrote.
+    async fn drain(self) {
+        wait_forwarder(self.stdout_forwarder).await;
+        wait_forwarder(self.stderr_forwarder).await;
+    }
+}
+
+/// Start the process described by `spec` as instance `instance_id`.
+fn start_process(
+    spec: &ProcessSpec,
+    instance_id: Uuid,
+    cancel: &CancellationToken,
+) -> Result<RunningProcess> {
+    // Build command
+    let mut cmd = Command::new(&spec.binary);
+    cmd.args(&spec.args);
+    cmd.envs(&spec.env);
+
+    // The module registers in the directory under the ID tracked here
+    cmd.env(MODKIT_INSTANCE_ID_ENV, instance_id.to_string());
+
+    // Pipe stdout/stderr for log forwarding
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    // Set working directory if specified
+    if let Some(ref working_dir) = spec.working_directory {
+        let path = Path::new(working_dir);
+        if path.exists() && path.is_dir() {
+            cmd.current_dir(path);
+        } else {
+            tracing::warn!(
+                module = %spec.module,
+                working_dir = %working_dir,
+                "Working directory does not exist or is not a directory, using current dir"
+            );
+        }
+    }
+
+    // Spawn the process
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("failed to spawn process: {}", spec.binary.display()))?;
+
+    // Spawn log forwarder tasks for stdout/stderr with cancellation support
+    let stdout_forwarder = child.stdout.take().map(|stdout| {
+        spawn_stream_forwarder(
+            stdout,
+            spec.module.clone(),
+            instance_id,
+            cancel.clone(),
+            StreamKind::Stdout,
+        )
+    });
+    let stderr_forwarder = child.stderr.take().map(|stderr| {
+        spawn_stream_forwarder(
+            stderr,
+            spec.module.clone(),
+            instance_id,
+            cancel.clone(),
+            StreamKind::Stderr,
+        )
+    });
+
+    Ok(RunningProcess {
+        child,
+        stdout_forwarder,
+        stderr_forwarder,
+    })
+}
+
+/// Internal representation of a supervised process slot
+struct LocalInstance {
+    /// Handle of the current process, replaced by the supervisor on restart
+    handle: Arc<RwLock<InstanceHandle>>,
+    /// Cancelled to stop the process and end supervision
+    stop: CancellationToken,
+    supervisor: JoinHandle<()>,
+}
+
+/// Map key type for instances - the ID of the first instance started in the slot
 type InstanceMap = HashMap<Uuid, LocalInstance>;
 
+/// Watches the process of one slot and restarts it per the module's restart policy.
+struct Supervisor {
+    slot: Uuid,
+    spec: ProcessSpec,
+    restart: RestartConfig,
+    handle: Arc<RwLock<InstanceHandle>>,
+    instances: Arc<RwLock<InstanceMap>>,
+    module_manager: Arc<OnceLock<Arc<ModuleManager>>>,
+    cancel: CancellationToken,
+    stop: CancellationToken,
+    /// Exited instances kept in the directory until their replacement serves
+    retiring: Vec<Uuid>,
+    /// Times of the restarts within the restart window
+    restarts: VecDeque<Instant>,
+}
+
+impl Supervisor {
+    async fn run(mut self, mut process: RunningProcess) {
+        loop {
+            let status = tokio::select! {
+                () = self.stop.cancelled() => {
+                    self.stop_process(process).await;
+                    self.retire_predecessors();
+                    return;
+                }
+                status = process.child.wait() => status,
+                () = tokio::time::sleep(READY_POLL_INTERVAL), if !self.retiring.is_empty() => {
+                    if self.replacement_serving() {
+                        self.retire_predecessors();
+                    }
+                    continue;
+                }
+            };
+            process.drain().await;
+
+            match self.restart_after_exit(status).await {
+                Some(next) => process = next,
+                None => return,
+            }
+        }
+    }
+
+    /// Record the exit and start a replacement if the policy allows it.
+    /// Returns `None` once supervision is over.
+    async fn restart_after_exit(
+        &mut self,
+        status: std::io::Result<ExitStatus>,
+    ) -> Option<RunningProcess> {
+        let (exit_code, mut succeeded) = match &status {
+            Ok(status) => (status.code(), status.success()),
+            Err(_) => (None, false),
+        };
+        let exited_id = self.handle.read().instance_id;
+        tracing::warn!(
+            module = %self.spec.module,
+            instance_id = %exited_id,
+            exit_code = ?exit_code,
+            status = ?status,
+            "OoP module process exited"
+        );
+        self.record(exited_id, InstanceEventKind::Exited, exit_code);
+        if self.module_manager.get().is_some() {
+            self.retiring.push(exited_id);
+        }
+
+        loop {
+            if !self.restart.policy.should_restart(succeeded) {
+                tracing::info!(
+                    module = %self.spec.module,
+                    policy = ?self.restart.policy,
+                    "OoP module is not restarted"
+                );
+                self.end();
+                return None;
+            }
+
+            let now = Instant::now();
+            while self
+                .restarts
+                .front()
+                .is_some_and(|at| now.duration_since(*at) >= self.restart.window)
+            {
+                self.restarts.pop_front();
+            }
+            let recent = u32::try_from(self.restarts.len()).unwrap_or(u32::MAX);
+            if recent >= self.restart.max_restarts {
+                tracing::error!(
+                    module = %self.spec.module,
+                    restarts = recent,
+                    window = ?self.restart.window,
+                    "OoP module keeps exiting, giving up on restarts"
+                );
+                self.record(exited_id, InstanceEventKind::GaveUp, None);
+                self.end();
+                return None;
+            }
+
+            let backoff = self.restart.backoff(recent);
+            self.record(exited_id, InstanceEventKind::Restarting, None);
+            tracing::info!(
+                module = %self.spec.module,
+                backoff_ms = backoff.as_millis(),
+                "Restarting OoP module"
+            );
+            tokio::select! {
+                () = self.stop.cancelled() => {
+                    self.retire_predecessors();
+                    return None;
+                }
+                () = tokio::time::sleep(backoff) => {}
+            }
+            self.restarts.push_back(Instant::now());
+
+            let instance_id = Uuid::now_v7();
+            match start_process(&self.spec, instance_id, &self.cancel) {
+                Ok(process) => {
+                    let pid = process.child.id();
+                    {
+                        let mut handle = self.handle.write();
+                        handle.instance_id = instance_id;
+                        handle.pid = pid;
+                        handle.created_at = Instant::now();
+                    }
+                    self.record(instance_id, InstanceEventKind::Started, None);
+                    tracing::info!(
+                        module = %self.spec.module,
+                        instance_id = %instance_id,
+                        pid = ?pid,
+                        "Restarted OoP module"
+                    );
+                    return Some(process);
+                }
+                Err(e) => {
+                    tracing::error!(
+                        module = %self.spec.module,
+                        error = %e,
+                        "Failed to restart OoP module"
+                    );
+                    // A restart that could not start counts as a failed run
+                    succeeded = false;
+                }
+            }
+        }
+    }
+
+    /// Append to the slot history and publish it for the current instance.
+    fn record(&self, instance_id: Uuid, kind: InstanceEventKind, exit_code: Option<i32>) {
+        let (current, history) = {
+            let mut handle = self.handle.write();
+            if handle.history.len() == MAX_HISTORY {
+                handle.history.remove(0);
+            }
+            handle.history.push(InstanceEvent {
+                instance_id: instance_id.to_string(),
+                kind,
+                at: SystemTime::now(),
+                exit_code,
+            });
+            (handle.instance_id, handle.history.clone())
+        };
+        if let Some(manager) = self.module_manager.get() {
+            manager.set_instance_history(current, history);
+        }
+    }
+
+    fn replacement_serving(&self) -> bool {
+        let current = self.handle.read().instance_id;
+        self.module_manager
+            .get()
+            .is_some_and(|manager| manager.is_serving(&self.spec.module, current))
+    }
+
+    /// Remove exited instances from the directory.
+    fn retire_predecessors(&mut self) {
+        let Some(manager) = self.module_manager.get() else {
+            return;
+        };
+        for instance_id in self.retiring.drain(..) {
+            tracing::debug!(
+                module = %self.spec.module,
+                instance_id = %instance_id,
+                "Retiring exited OoP module instance"
+            );
+            manager.deregister(&self.spec.module, instance_id);
+        }
+    }
+
+    /// Supervision is over without a stop request. The slot is dropped; the
+    /// last instance stays quarantined in the directory, with its history,
+    /// until the heartbeat policy evicts it.
+    fn end(&mut self) {
+        let last = self.handle.read().instance_id;
+        if let Some(manager) = self.module_manager.get() {
+            for instance_id in self.retiring.drain(..).filter(|id| *id != last) {
+                manager.deregister(&self.spec.module, instance_id);
+            }
+            manager.mark_quarantined(&self.spec.module, last);
+        }
+        self.instances.write().remove(&self.slot);
+    }
+
+    async fn stop_process(&self, mut process: RunningProcess) {
+        let handle = self.handle.read().clone();
+        let (grace, context) = if self.cancel.is_cancelled() {
+            (SHUTDOWN_GRACE_PERIOD, "shutdown")
+        } else {
+            (INSTANCE_STOP_GRACE_PERIOD, "stop_instance")
+        };
+        stop_child_with_grace(&mut process.child, &handle, grace, context).await;
+        process.drain().await;
+    }
+}
+
 /// Backend that spawns modules as local child processes and manages their lifecycle.
 ///
+/// Each process is supervised: when it exits, it is restarted according to
+/// the module's [`RestartConfig`], and every start, exit and restart is
+/// recorded in the [`InstanceHandle`] history. Once bound to the
+/// [`ModuleManager`], the backend keeps an exited instance registered until
+/// its replacement serves traffic, and publishes the history to the directory.
+///
 /// When the cancellation token is triggered, the backend will:
 /// 1. Send termination signal to all processes (SIGTERM on Unix, `TerminateProcess` on Windows)
 /// 2. Wait up to 5 seconds for graceful shutdown
@@ -167,6 +485,7 @@ type InstanceMap = HashMap<Uuid, LocalInstance>;
 pub struct LocalProcessBackend {
     instances: Arc<RwLock<InstanceMap>>,
     cancel: CancellationToken,
+    module_manager: Arc<OnceLock<Arc<ModuleManager>>>,
 }
 
 impl LocalProcessBackend {
@@ -178,6 +497,7 @@ impl LocalProcessBackend {
         let backend = Self {
             instances: Arc::new(RwLock::new(HashMap::new())),
             cancel: cancel.clone(),
+            module_manager: Arc::new(OnceLock::new()),
         };
 
         // Spawn background task to handle shutdown
@@ -191,9 +511,17 @@ impl LocalProcessBackend {
         backend
     }
 
+    /// Bind the directory the spawned modules register in. Only the first
+    /// binding takes effect.
+    pub fn set_module_manager(&self, manager: Arc<ModuleManager>) {
+        if self.module_manager.set(manager).is_err() {
+            tracing::debug!("LocalProcessBackend: module manager already bound");
+        }
+    }
+
     /// Gracefully stop all tracked instances with timeout.
     async fn shutdown_all_instances(instances: Arc<RwLock<InstanceMap>>) {
-        let mut all_instances: Vec<LocalInstance> = {
+        let all_instances: Vec<LocalInstance> = {
             let mut guard = instances.write();
             guard.drain().map(|(_, inst)| inst).collect()
         };
@@ -204,21 +532,15 @@ impl LocalProcessBackend {
 
         tracing::info!(count = all_instances.len(), "Stopping OoP module processes");
 
-        // Stop all processes with grace period
-        for inst in &mut all_instances {
-            stop_child_with_grace(
-                &mut inst.child,
-                &inst.handle,
-                SHUTDOWN_GRACE_PERIOD,
-                "shutdown",
-            )
-            .await;
+        // Supervisors stop their processes with the shutdown grace period
+        // and drain the log forwarders
+        for inst in &all_instances {
+            inst.stop.cancel();
         }
-
-        // Wait for forwarders to drain
         for inst in all_instances {
-            wait_forwarder(inst.stdout_forwarder).await;
-            wait_forwarder(inst.stderr_forwarder).await;
+            if let Err(e) = inst.supervisor.await {
+                tracing::warn!(error = %e, "OoP supervisor task failed during shutdown");
+            }
         }
 
         tracing::info!("All OoP module processes stopped");
@@ -245,109 +567,94 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
         // Generate unique instance ID using UUID v7
         let instance_id = Uuid::now_v7();
 
-        // Build command
-        let mut cmd = Command::new(binary);
-        cmd.args(&cfg.args);
-        cmd.envs(&cfg.env);
-
-        // Pipe stdout/stderr for log forwarding
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
-        // Set working directory if specified
-        if let Some(ref working_dir) = cfg.working_directory {
-            let path = Path::new(working_dir);
-            if path.exists() && path.is_dir() {
-                cmd.current_dir(path);
-            } else {
-                tracing::warn!(
-                    module = %cfg.name,
-                    working_dir = %working_dir,
-                    "Working directory does not exist or is not a directory, using current dir"
-                );
-            }
-        }
-
-        // Spawn the process
-        let mut child = cmd
-            .spawn()
-            .with_context(|| format!("failed to spawn process: {}", binary.display()))?;
+        let spec = ProcessSpec {
+            module: cfg.name.clone(),
+            binary: binary.clone(),
+            args: cfg.args.clone(),
+            env: cfg.env.clone(),
+            working_directory: cfg.working_directory.clone(),
+        };
+        let process = start_process(&spec, instance_id, &self.cancel)?;
 
         // Get PID
-        let pid = child.id();
-
-        // Spawn log forwarder tasks for stdout/stderr with cancellation support
-        let module_name = cfg.name.clone();
-        let cancel = self.cancel.clone();
-        let stdout_forwarder = child.stdout.take().map(|stdout| {
-            spawn_stream_forwarder(
-                stdout,
-                module_name.clone(),
-                instance_id,
-                cancel.clone(),
-                StreamKind::Stdout,
-            )
-        });
-        let stderr_forwarder = child.stderr.take().map(|stderr| {
-            spawn_stream_forwarder(
-                stderr,
-                module_name.clone(),
-                instance_id,
-                cancel.clone(),
-                StreamKind::Stderr,
-            )
-        });
+        let pid = process.child.id();
 
         tracing::info!(
             module = %cfg.name,
             instance_id = %instance_id,
             pid = ?pid,
+            restart = ?cfg.restart.policy,
             "Spawned OoP module with log forwarding"
         );
 
         // Create handle
-        let handle = InstanceHandle {
+        let handle = Arc::new(RwLock::new(InstanceHandle {
             module: cfg.name.clone(),
             instance_id,
             backend: BackendKind::LocalProcess,
             pid,
-            created_at: std::time::Instant::now(),
+            created_at: Instant::now(),
+            history: Vec::new(),
+        }));
+        let stop = self.cancel.child_token();
+        let supervisor = Supervisor {
+            slot: instance_id,
+            spec,
+            restart: cfg.restart.clone(),
+            handle: Arc::clone(&handle),
+            instances: Arc::clone(&self.instances),
+            module_manager: Arc::clone(&self.module_manager),
+            cancel: self.cancel.clone(),
+            stop: stop.clone(),
+            retiring: Vec::new(),
+            restarts: VecDeque::new(),
         };
+        supervisor.record(instance_id, InstanceEventKind::Started, None);
+        let snapshot = handle.read().clone();
 
-        // Store in instances map
+        // Store in instances map; the lock is held while the supervisor starts
+        // so a process that exits at once cannot end supervision before its
+        // slot exists
         {
             let mut instances = self.instances.write();
+            let task = tokio::spawn(supervisor.run(process));
             instances.insert(
                 instance_id,
                 LocalInstance {
-                    handle: handle.clone(),
-                    child,
-                    stdout_forwarder,
-                    stderr_forwarder,
+                    handle,
+                    stop,
+                    supervisor: task,
                 },
             );
         }
 
-        Ok(handle)
+        Ok(snapshot)
     }
 
     async fn stop_instance(&self, handle: &InstanceHandle) -> Result<()> {
+        // Handles of restarted processes carry the current instance ID
         let local = {
             let mut instances = self.instances.write();
-            instances.remove(&handle.instance_id)
+            let slot = instances
+                .iter()
+                .find(|(slot, inst)| {
+                    **slot == handle.instance_id
+                        || inst.handle.read().instance_id == handle.instance_id
+                })
+                .map(|(slot, _)| *slot);
+            slot.and_then(|slot| instances.remove(&slot))
         };
 
-        if let Some(mut local) = local {
-            stop_child_with_grace(
-                &mut local.child,
-                &local.handle,
-                INSTANCE_STOP_GRACE_PERIOD,
-                "stop_instance",
-            )
-            .await;
-
-            // we do not await forwarders here, they'll stop on their own via CancellationToken and pipe close;
-            // shutdown_all_instances handles draining for global shutdown
+        if let Some(local) = local {
+            local.stop.cancel();
+            if let Err(e) = local.supervisor.await {
+                tracing::warn!(
+                    module = %handle.module,
+                    instance_id = %handle.instance_id,
+                    error = %e,
+                    "stop_instance: supervisor task failed"
+                );
+            }
         } else {
             tracing::debug!(
                 module = %handle.module,
@@ -364,8 +671,8 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
 
         let result = instances
             .values()
-            .filter(|inst| inst.handle.module == module)
-            .map(|inst| inst.handle.clone())
+            .map(|inst| inst.handle.read().clone())
+            .filter(|handle| handle.module == module)
             .collect();
 
         Ok(result)
@@ -375,8 +682,8 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
-    use std::time::Instant;
+    #[cfg(unix)]
+    use crate::backends::RestartPolicy;
 
     fn test_backend() -> LocalProcessBackend {
         LocalProcessBackend::new(CancellationToken::new())
@@ -521,6 +828,7 @@ mod tests {
             backend: BackendKind::LocalProcess,
             pid: None,
             created_at: Instant::now(),
+            history: Vec::new(),
         };
 
         // Should not error even if instance doesn't exist
@@ -538,6 +846,169 @@ mod tests {
         assert_eq!(instances.len(), 0);
     }
 
+    /// Script that fails on its first run and keeps running afterwards.
+    #[cfg(unix)]
+    fn fail_once_script(dir: &tempfile::TempDir, exit_code: i32) -> String {
+        let marker = dir.path().join("started");
+        format!(
+            "if [ -e '{0}' ]; then sleep 30; else touch '{0}'; sleep 0.2; exit {exit_code}; fi",
+            marker.display()
+        )
+    }
+
+    #[cfg(unix)]
+    fn restarting_shell(script: &str, policy: RestartPolicy) -> OopModuleConfig {
+        let mut cfg = OopModuleConfig::new("supervised", BackendKind::LocalProcess);
+        cfg.binary = Some(PathBuf::from("/bin/sh"));
+        cfg.args = vec!["-c".to_owned(), script.to_owned()];
+        cfg.restart = RestartConfig {
+            policy,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            max_restarts: 2,
+            window: Duration::from_mins(1),
+        };
+        cfg
+    }
+
+    /// Poll the slot of `module` until `done` holds for its handle, or the
+    /// slot disappears (`None`).
+    #[cfg(unix)]
+    async fn wait_for_handle(
+        backend: &LocalProcessBackend,
+        module: &str,
+        done: impl Fn(&InstanceHandle) -> bool,
+    ) -> Option<InstanceHandle> {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let handles = backend.list_instances(module).await.unwrap();
+                match handles.first() {
+                    None => return None,
+                    Some(h) if done(h) => return Some(h.clone()),
+                    Some(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+                }
+            }
+        })
+        .await
+        .expect("supervisor should make progress")
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_process_is_restarted_with_history() {
+        let backend = test_backend();
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = restarting_shell(&fail_once_script(&dir, 3), RestartPolicy::OnFailure);
+        let first = backend.spawn_instance(&cfg).await.unwrap();
+        assert_eq!(first.history.len(), 1);
+        assert_eq!(first.history[0].kind, InstanceEventKind::Started);
+
+        let restarted = wait_for_handle(&backend, "supervised", |h| h.history.len() == 4)
+            .await
+            .expect("slot should survive the first exit");
+        assert_ne!(restarted.instance_id, first.instance_id);
+        let kinds: Vec<_> = restarted.history.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                InstanceEventKind::Started,
+                InstanceEventKind::Exited,
+                InstanceEventKind::Restarting,
+                InstanceEventKind::Started,
+            ]
+        );
+        assert_eq!(restarted.history[1].exit_code, Some(3));
+        assert_eq!(
+            restarted.history[3].instance_id,
+            restarted.instance_id.to_string()
+        );
+
+        // The original handle still addresses the slot
+        backend.stop_instance(&first).await.unwrap();
+        assert!(
+            backend
+                .list_instances("supervised")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_supervision_ends_per_policy_and_restart_budget() {
+        let backend = test_backend();
+
+        // Clean exit under on-failure: not restarted
+        let cfg = restarting_shell("exit 0", RestartPolicy::OnFailure);
+        backend.spawn_instance(&cfg).await.unwrap();
+        assert!(
+            wait_for_handle(&backend, "supervised", |_| false)
+                .await
+                .is_none()
+        );
+
+        // Crash loop: two restarts, then the circuit opens
+        let manager = Arc::new(ModuleManager::new());
+        backend.set_module_manager(Arc::clone(&manager));
+        let cfg = restarting_shell("exit 1", RestartPolicy::Always);
+        backend.spawn_instance(&cfg).await.unwrap();
+        assert!(
+            wait_for_handle(&backend, "supervised", |_| false)
+                .await
+                .is_none()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_previous_instance_stays_registered_until_replacement_serves() {
+        use crate::runtime::ModuleInstance;
+
+        let backend = test_backend();
+        let manager = Arc::new(ModuleManager::new());
+        backend.set_module_manager(Arc::clone(&manager));
+
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = restarting_shell(&fail_once_script(&dir, 1), RestartPolicy::OnFailure);
+        let first = backend.spawn_instance(&cfg).await.unwrap();
+        manager.register_instance(Arc::new(ModuleInstance::new(
+            "supervised",
+            first.instance_id,
+        )));
+        manager.update_heartbeat("supervised", first.instance_id, Instant::now());
+
+        let restarted = wait_for_handle(&backend, "supervised", |h| {
+            h.instance_id != first.instance_id
+        })
+        .await
+        .unwrap();
+        tokio::time::sleep(READY_POLL_INTERVAL * 2).await;
+        assert!(manager.is_serving("supervised", first.instance_id));
+        assert!(
+            manager
+                .instance_history(restarted.instance_id)
+                .iter()
+                .any(|e| e.kind == InstanceEventKind::Exited && e.exit_code == Some(1))
+        );
+
+        // The replacement registers and heartbeats: the previous one is retired
+        manager.register_instance(Arc::new(ModuleInstance::new(
+            "supervised",
+            restarted.instance_id,
+        )));
+        manager.update_heartbeat("supervised", restarted.instance_id, Instant::now());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while manager.is_serving("supervised", first.instance_id) {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("previous instance should be deregistered");
+
+        backend.stop_instance(&restarted).await.unwrap();
+    }
+
     mod send_terminate_signal_tests {
         #[cfg(unix)]
         use {super::send_terminate_signal, std::time::Duration};
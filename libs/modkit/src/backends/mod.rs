@@ -4,11 +4,17 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+pub use cf_system_sdks::directory::{InstanceEvent, InstanceEventKind};
+
+use crate::runtime::ModuleManager;
+
 /// The kind of backend used to spawn and manage module instances
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackendKind {
@@ -18,6 +24,69 @@ pub enum BackendKind {
     Mock,
 }
 
+/// When a supervised process is started again after it exits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Leave the module down
+    #[default]
+    Never,
+    /// Restart after a non-zero exit or a kill by signal
+    OnFailure,
+    /// Restart after any exit not requested by the host
+    Always,
+}
+
+impl RestartPolicy {
+    #[must_use]
+    pub fn should_restart(self, exited_successfully: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnFailure => !exited_successfully,
+            Self::Always => true,
+        }
+    }
+}
+
+/// Restart supervision of an out-of-process module.
+///
+/// Restarts back off exponentially from `initial_backoff` up to `max_backoff`.
+/// Once `max_restarts` restarts happened within `window`, the module is left down.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RestartConfig {
+    pub policy: RestartPolicy,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub initial_backoff: Duration,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_backoff: Duration,
+    pub max_restarts: u32,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub window: Duration,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::Never,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_mins(1),
+            max_restarts: 5,
+            window: Duration::from_mins(5),
+        }
+    }
+}
+
+impl RestartConfig {
+    /// Delay before the restart following `recent_restarts` restarts in the window
+    #[must_use]
+    pub fn backoff(&self, recent_restarts: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(1 << recent_restarts.min(31))
+            .map_or(self.max_backoff, |d| d.min(self.max_backoff))
+    }
+}
+
 /// Configuration for an out-of-process module
 pub struct OopModuleConfig {
     pub name: String,
@@ -27,6 +96,7 @@ pub struct OopModuleConfig {
     pub working_directory: Option<String>,
     pub backend: BackendKind,
     pub version: Option<String>,
+    pub restart: RestartConfig,
 }
 
 impl OopModuleConfig {
@@ -39,6 +109,7 @@ impl OopModuleConfig {
             working_directory: None,
             backend,
             version: None,
+            restart: RestartConfig::default(),
         }
     }
 }
@@ -51,6 +122,8 @@ pub struct InstanceHandle {
     pub backend: BackendKind,
     pub pid: Option<u32>,
     pub created_at: Instant,
+    /// Supervision history of the process slot, oldest first
+    pub history: Vec<InstanceEvent>,
 }
 
 impl std::fmt::Debug for InstanceHandle {
@@ -61,6 +134,7 @@ impl std::fmt::Debug for InstanceHandle {
             .field("backend", &self.backend)
             .field("pid", &self.pid)
             .field("created_at", &self.created_at)
+            .field("history", &self.history)
             .finish()
     }
 }
@@ -80,6 +154,7 @@ pub struct OopSpawnConfig {
     pub args: Vec<String>,
     pub env: HashMap<String, String>,
     pub working_directory: Option<String>,
+    pub restart: RestartConfig,
 }
 
 /// A type-erased backend for spawning `OoP` modules.
@@ -92,6 +167,10 @@ pub trait OopBackend: Send + Sync {
 
     /// Shutdown all spawned instances (called during stop phase).
     async fn shutdown_all(&self);
+
+    /// Give the backend access to the directory, so restarts can keep the
+    /// previous instance registered until its replacement serves traffic.
+    fn bind_module_manager(&self, _manager: Arc<ModuleManager>) {}
 }
 
 pub mod local;
@@ -110,6 +189,7 @@ impl OopBackend for LocalProcessBackend {
         oop_config.args = config.args;
         oop_config.env = config.env;
         oop_config.working_directory = config.working_directory;
+        oop_config.restart = config.restart;
 
         self.spawn_instance(&oop_config).await?;
         Ok(())
@@ -120,6 +200,10 @@ impl OopBackend for LocalProcessBackend {
         // when the token is triggered, it automatically stops all instances.
         // This method is a no-op because the backend's internal shutdown task handles it.
     }
+
+    fn bind_module_manager(&self, manager: Arc<ModuleManager>) {
+        self.set_module_manager(manager);
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +245,7 @@ mod tests {
             backend: BackendKind::LocalProcess,
             pid: Some(12345),
             created_at: Instant::now(),
+            history: Vec::new(),
         };
 
         let debug_str = format!("{handle:?}");
@@ -169,4 +254,39 @@ mod tests {
         assert!(debug_str.contains("LocalProcess"));
         assert!(debug_str.contains("12345"));
     }
+
+    #[test]
+    fn test_restart_policy_decision() {
+        assert!(!RestartPolicy::Never.should_restart(false));
+        assert!(RestartPolicy::OnFailure.should_restart(false));
+        assert!(!RestartPolicy::OnFailure.should_restart(true));
+        assert!(RestartPolicy::Always.should_restart(true));
+    }
+
+    #[test]
+    fn test_restart_backoff_is_exponential_and_capped() {
+        let cfg = RestartConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            ..RestartConfig::default()
+        };
+        assert_eq!(cfg.backoff(0), Duration::from_millis(100));
+        assert_eq!(cfg.backoff(2), Duration::from_millis(400));
+        assert_eq!(cfg.backoff(4), Duration::from_secs(1));
+        assert_eq!(cfg.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_restart_config_deserializes_humantime() {
+        let cfg: RestartConfig = serde_json::from_value(serde_json::json!({
+            "policy": "on-failure",
+            "initial_backoff": "500ms",
+            "window": "10m",
+        }))
+        .unwrap();
+        assert_eq!(cfg.policy, RestartPolicy::OnFailure);
+        assert_eq!(cfg.initial_backoff, Duration::from_millis(500));
+        assert_eq!(cfg.window, Duration::from_mins(10));
+        assert_eq!(cfg.max_restarts, RestartConfig::default().max_restarts);
+    }
 }
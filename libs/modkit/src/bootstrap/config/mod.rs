@@ -13,7 +13,8 @@ use std::path::{Path, PathBuf};
 use tracing::Level;
 
 use crate::ConfigProvider;
-use crate::telemetry::TracingConfig;
+use crate::backends::RestartConfig;
+use crate::telemetry::{MetricsConfig, TracingConfig};
 use url::Url;
 
 // Re-export dump functions
@@ -45,6 +46,9 @@ pub struct ModuleRuntime {
     /// Execution configuration for `OoP` modules.
     #[serde(default)]
     pub execution: Option<ExecutionConfig>,
+    /// Restart policy for `OoP` modules (never restarted by default).
+    #[serde(default)]
+    pub restart: RestartConfig,
 }
 
 /// Execution configuration for out-of-process modules.
@@ -86,6 +90,8 @@ pub struct AppConfig {
     pub logging: Option<LoggingConfig>,
     /// Tracing configuration (optional, disabled if None).
     pub tracing: Option<TracingConfig>,
+    /// Metrics configuration (optional, disabled if None).
+    pub metrics: Option<MetricsConfig>,
     /// Directory containing per-module YAML files (optional).
     #[serde(default)]
     pub modules_dir: Option<String>,
@@ -234,6 +240,7 @@ impl Default for AppConfig {
             }),
             logging: Some(default_logging_config()),
             tracing: None, // Disabled by default
+            metrics: None, // Disabled by default
             modules_dir: None,
             modules: HashMap::new(),
         }
@@ -259,6 +266,7 @@ impl AppConfig {
             database: None,
             logging: None,
             tracing: None,
+            metrics: None,
             modules_dir: None,
             modules: HashMap::new(),
         };
@@ -996,7 +1004,7 @@ impl RenderedDbConfig {
 /// - Database configuration (structured, for field-by-field merge in `OoP`)
 /// - Module config section
 /// - Logging configuration (for key-by-key merge in `OoP`)
-/// - Tracing configuration for OTEL
+/// - Tracing and metrics configuration for OTEL
 ///
 /// The runtime section is excluded as it's only relevant for the master host.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1015,6 +1023,10 @@ pub struct RenderedModuleConfig {
     /// Tracing configuration from master host for OTEL initialization
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tracing: Option<TracingConfig>,
+    /// Metrics configuration from master host; `OoP` modules push to the same
+    /// OTLP collector (the Prometheus endpoint is served only by the api-gateway)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsConfig>,
 }
 
 impl RenderedModuleConfig {
@@ -1082,12 +1094,14 @@ pub fn render_module_config_for_oop(
 
     // Pass tracing config from master host so OoP modules use the same OTEL settings
     let tracing = app.tracing.clone();
+    let metrics = app.metrics.clone();
 
     Ok(RenderedModuleConfig {
         database,
         config,
         logging,
         tracing,
+        metrics,
     })
 }
 
@@ -1897,6 +1911,8 @@ logging:
                 dbname: None,
                 params: None,
                 pool: None,
+                lock_backend: None,
+                replicas: None,
                 file: None,
                 path: None,
                 server: None,
@@ -2691,6 +2707,26 @@ logging:
         assert!(modules.contains_key("module_b"));
         assert!(modules.contains_key("module_c"));
     }
+
+    #[test]
+    fn test_module_runtime_restart_defaults_to_never() {
+        use crate::backends::RestartPolicy;
+
+        let runtime: ModuleRuntime = serde_json::from_value(serde_json::json!({
+            "type": "oop",
+            "execution": { "executable_path": "/bin/module" },
+        }))
+        .unwrap();
+        assert_eq!(runtime.restart.policy, RestartPolicy::Never);
+
+        let runtime: ModuleRuntime = serde_json::from_value(serde_json::json!({
+            "type": "oop",
+            "restart": { "policy": "always", "max_restarts": 3 },
+        }))
+        .unwrap();
+        assert_eq!(runtime.restart.policy, RestartPolicy::Always);
+        assert_eq!(runtime.restart.max_restarts, 3);
+    }
 }
 
 // Note: DB trait implementations and helper functions removed since we now use DbManager
@@ -20,6 +20,19 @@ pub type OtelLayer = ();
 static CONSOLE_GUARD: std::sync::OnceLock<tracing_appender::non_blocking::WorkerGuard> =
     std::sync::OnceLock::new();
 
+// Filter reload handles of the installed subscriber, set only if it became the global one.
+static LOG_RELOAD: std::sync::OnceLock<LogReloadHandles> = std::sync::OnceLock::new();
+
+/// Swaps the `Targets` behind one reloadable per-layer filter.
+type TargetsReloader = Box<dyn Fn(Targets) -> anyhow::Result<()> + Send + Sync>;
+
+struct LogReloadHandles {
+    /// Console fmt layer and the OTEL layer, which follows console levels.
+    console: Vec<TargetsReloader>,
+    file: Vec<TargetsReloader>,
+    has_default_file: bool,
+}
+
 // ================= level helpers =================
 
 /// Returns true if target == `crate_name` or target starts with "`crate_name::`"
@@ -230,6 +243,37 @@ pub fn init_logging_unified(cfg: &LoggingConfig, base_dir: &Path, otel_layer: Op
     );
 }
 
+/// Apply the levels from `cfg` to the running subscriber.
+///
+/// Console, file and OTEL filters are swapped in place. Everything else keeps its
+/// startup value: file paths, rotation and console format need a restart.
+///
+/// # Errors
+/// Returns an error if the subscriber was not installed by [`init_logging_unified`]
+/// with logging sections, or if it is gone.
+pub fn reload_log_levels(cfg: &LoggingConfig) -> anyhow::Result<()> {
+    let Some(handles) = LOG_RELOAD.get() else {
+        anyhow::bail!("no reloadable logging subscriber is installed");
+    };
+
+    let data = extract_config_data(cfg);
+    let console_targets = build_targets(&data, SinkKind::Console);
+    let file_targets = build_targets(
+        &data,
+        SinkKind::File {
+            has_default_file: handles.has_default_file,
+        },
+    );
+
+    for reload in &handles.console {
+        reload(console_targets.clone())?;
+    }
+    for reload in &handles.file {
+        reload(file_targets.clone())?;
+    }
+    Ok(())
+}
+
 // ================= generic targets builder =================
 
 use tracing::level_filters::LevelFilter;
@@ -405,6 +449,20 @@ fn stderr_supports_ansi() -> bool {
 
 // ================= registry & layers =================
 
+/// Wrap `targets` in a reloadable filter and keep its handle in `handles`.
+fn reloadable<S: 'static>(
+    targets: &Targets,
+    handles: &mut Vec<TargetsReloader>,
+) -> tracing_subscriber::reload::Layer<Targets, S> {
+    let (filter, handle) = tracing_subscriber::reload::Layer::new(targets.clone());
+    handles.push(Box::new(move |targets| {
+        handle
+            .reload(targets)
+            .context("Failed to reload log filter")
+    }));
+    filter
+}
+
 fn install_subscriber(
     console_targets: &tracing_subscriber::filter::Targets,
     file_targets: &tracing_subscriber::filter::Targets,
@@ -422,6 +480,10 @@ fn install_subscriber(
     let (nb_stderr, guard) = tracing_appender::non_blocking(std::io::stderr());
     _ = CONSOLE_GUARD.set(guard);
 
+    let mut console_reload = Vec::new();
+    let mut file_reload = Vec::new();
+    let has_default_file = file_router.default.is_some();
+
     // Console fmt layers: text (human-friendly) or JSON (structured).
     // Only one is active at a time; the other is None.
     let (console_text, console_json) = match console_format {
@@ -433,7 +495,7 @@ fn install_subscriber(
                     .with_target(true)
                     .with_level(true)
                     .with_timer(fmt::time::UtcTime::rfc_3339())
-                    .with_filter(console_targets.clone()),
+                    .with_filter(reloadable(console_targets, &mut console_reload)),
             ),
             None,
         ),
@@ -447,7 +509,7 @@ fn install_subscriber(
                     .with_target(true)
                     .with_level(true)
                     .with_timer(fmt::time::UtcTime::rfc_3339())
-                    .with_filter(console_targets.clone()),
+                    .with_filter(reloadable(console_targets, &mut console_reload)),
             ),
         ),
     };
@@ -464,7 +526,7 @@ fn install_subscriber(
                 .with_level(true)
                 .with_timer(fmt::time::UtcTime::rfc_3339())
                 .with_writer(file_router)
-                .with_filter(file_targets.clone()),
+                .with_filter(reloadable(file_targets, &mut file_reload)),
         )
     };
 
@@ -478,7 +540,8 @@ fn install_subscriber(
 
         #[cfg(feature = "otel")]
         let base = {
-            let otel_opt = otel_layer.map(|otel| otel.with_filter(console_targets.clone()));
+            let otel_opt = otel_layer
+                .map(|otel| otel.with_filter(reloadable(console_targets, &mut console_reload)));
             base.with(otel_opt)
         };
         #[cfg(not(feature = "otel"))]
@@ -490,7 +553,14 @@ fn install_subscriber(
             .with(file_layer_opt)
     };
 
-    _ = subscriber.try_init();
+    // Handles of a subscriber that lost the race for the global slot would reload nothing
+    if subscriber.try_init().is_ok() {
+        _ = LOG_RELOAD.set(LogReloadHandles {
+            console: console_reload,
+            file: file_reload,
+            has_default_file,
+        });
+    }
 }
 
 fn init_minimal(
@@ -47,3 +47,39 @@ async fn wait_sigterm() -> Result<ShutdownSignal> {
 async fn wait_sigterm() -> Result<ShutdownSignal> {
     std::future::pending::<Result<ShutdownSignal>>().await
 }
+
+/// Listener for the configuration reload signal (SIGHUP).
+///
+/// Off Unix there is no such signal and [`ReloadSignal::recv`] never completes.
+pub struct ReloadSignal {
+    #[cfg(unix)]
+    inner: signal::unix::Signal,
+}
+
+impl ReloadSignal {
+    /// Install the SIGHUP handler.
+    ///
+    /// # Errors
+    /// Returns an error if the handler cannot be installed.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            #[cfg(unix)]
+            inner: signal::unix::signal(signal::unix::SignalKind::hangup())?,
+        })
+    }
+
+    /// Wait for the next SIGHUP.
+    #[cfg(unix)]
+    pub async fn recv(&mut self) {
+        if self.inner.recv().await.is_none() {
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Wait for the next SIGHUP.
+    #[cfg(not(unix))]
+    #[allow(clippy::unused_self)]
+    pub async fn recv(&mut self) {
+        std::future::pending::<()>().await;
+    }
+}
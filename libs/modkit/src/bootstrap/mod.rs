@@ -30,5 +30,8 @@ pub use config::{
 // Re-export host types for convenience
 pub use oop::{OopRunOptions, run_oop_with_options};
 
+mod reload;
 mod run;
-pub use run::{run_migrate, run_server};
+pub use crate::runtime::MigrationCommand;
+pub use reload::ConfigReloadOptions;
+pub use run::{run_migrate, run_migrate_command, run_server, run_server_with_reload};
@@ -58,8 +58,8 @@ use super::config::{
 };
 use crate::bootstrap::host::init_logging_unified;
 use crate::runtime::{
-    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, RunOptions, ShutdownOptions, run,
-    shutdown,
+    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_INSTANCE_ID_ENV,
+    RunOptions, ShutdownOptions, run, shutdown,
 };
 use cf_system_sdks::directory::{DirectoryClient, DirectoryGrpcClient};
 
@@ -69,7 +69,7 @@ pub struct OopRunOptions {
     /// Logical module name (e.g., "`file-parser`")
     pub module_name: String,
 
-    /// Instance ID (defaults to a random UUID if None)
+    /// Instance ID (defaults to `MODKIT_INSTANCE_ID`, or a random UUID if neither is set)
     pub instance_id: Option<Uuid>,
 
     /// Directory service gRPC endpoint (e.g., "<http://127.0.0.1:50051>")
@@ -98,9 +98,14 @@ impl Default for OopRunOptions {
         let directory_endpoint = std::env::var(MODKIT_DIRECTORY_ENDPOINT_ENV)
             .unwrap_or_else(|_| "http://127.0.0.1:50051".to_owned());
 
+        // Instance ID assigned by the master host, so it can follow restarts
+        let instance_id = std::env::var(MODKIT_INSTANCE_ID_ENV)
+            .ok()
+            .and_then(|id| Uuid::parse_str(&id).ok());
+
         Self {
             module_name: String::new(),
-            instance_id: None,
+            instance_id,
             directory_endpoint,
             config_path,
             verbose: 0,
@@ -488,6 +493,7 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
             has_config = !rc.config.is_null(),
             has_logging = rc.logging.is_some(),
             has_tracing = rc.tracing.is_some(),
+            has_metrics = rc.metrics.is_some(),
             "Received rendered config from master host"
         );
     } else if std::env::var(MODKIT_MODULE_CONFIG_ENV).is_ok() {
@@ -502,6 +508,21 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
         );
     }
 
+    // Metrics follow the master's settings too, but only push over OTLP:
+    // the Prometheus endpoint is served by the master's api-gateway.
+    #[cfg(feature = "otel")]
+    let meter_provider = rendered_config
+        .as_ref()
+        .and_then(|rc| rc.metrics.clone())
+        .filter(|m| m.enabled)
+        .map(|m| {
+            crate::telemetry::init_metrics(&crate::telemetry::MetricsConfig {
+                prometheus: None,
+                ..m
+            })
+        })
+        .transpose()?;
+
     info!(
         module = %opts.module_name,
         instance_id = %instance_id,
@@ -589,6 +610,13 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
         info!("Module runtime completed successfully");
     }
 
+    #[cfg(feature = "otel")]
+    if let Some(provider) = meter_provider
+        && let Err(e) = provider.shutdown()
+    {
+        warn!(error = %e, "Meter provider shutdown failed");
+    }
+
     result
 }
 
@@ -24,6 +24,7 @@ fn minimal_app_config() -> AppConfig {
         database: None,
         logging: None,
         tracing: None,
+        metrics: None,
         modules_dir: None,
         modules: HashMap::new(),
     }
@@ -376,6 +377,8 @@ mod database_merge {
                     max_lifetime: None,
                     test_before_acquire: None,
                 }),
+                lock_backend: None,
+                replicas: None,
             },
         );
         GlobalDatabaseConfig {
@@ -398,6 +401,8 @@ mod database_merge {
             path: None,
             params: None,
             pool: None,
+            lock_backend: None,
+            replicas: None,
         }
     }
 
@@ -577,6 +582,8 @@ mod database_merge {
                 path: None,
                 params: None,
                 pool: None,
+                lock_backend: None,
+                replicas: None,
             },
         );
         local_config.database = Some(GlobalDatabaseConfig {
@@ -656,6 +663,7 @@ mod full_oop_config {
                 .into(),
             ),
             tracing: None,
+            metrics: None,
         };
 
         let result = build_oop_config_and_db(&local_config, "test_module", Some(&rendered));
@@ -695,6 +703,7 @@ mod full_oop_config {
             }),
             logging: None,
             tracing: None,
+            metrics: None,
         };
 
         let result = build_oop_config_and_db(&local_config, "test_module", Some(&rendered));
@@ -744,6 +753,7 @@ mod full_oop_config {
                 .into(),
             ),
             tracing: None,
+            metrics: None,
         };
 
         let result = build_oop_config_and_db(&local_config, "test_module", Some(&rendered));
@@ -793,6 +803,7 @@ mod full_oop_config {
             config: json!({"master_setting": "value"}),
             logging: None,
             tracing: None,
+            metrics: None,
         };
 
         let result = build_oop_config_and_db(&local_config, "test_module", Some(&rendered));
@@ -821,6 +832,7 @@ mod full_oop_config {
             config: json!({"master_setting": "value"}),
             logging: None,
             tracing: None,
+            metrics: None,
         };
 
         let result = build_oop_config_and_db(&local_config, "test_module", Some(&rendered));
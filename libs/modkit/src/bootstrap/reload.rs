@@ -0,0 +1,286 @@
+//! Host-side trigger for configuration reloads.
+//!
+//! The watcher polls the modification time of the configuration file and listens
+//! for SIGHUP. Either one re-runs the layered load (defaults → YAML → env) with the
+//! startup CLI overrides, applies the new log levels and hands the configuration to
+//! the runtime's [`ConfigReloader`]. A configuration that fails to load is rejected
+//! as a whole and the host keeps running on the previous one.
+
+use super::AppConfig;
+use super::host::{ReloadSignal, reload_log_levels};
+use crate::config::ConfigProvider;
+use crate::config_reload::{ConfigReloader, ReloadReport};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+
+/// Where the host re-reads its configuration from.
+#[derive(Debug, Clone)]
+pub struct ConfigReloadOptions {
+    /// Configuration file the host was started with.
+    pub config_path: PathBuf,
+    /// CLI verbosity, re-applied on top of every reloaded configuration.
+    pub verbose: u8,
+    /// How often the file's modification time is checked.
+    pub poll_interval: Duration,
+}
+
+impl ConfigReloadOptions {
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    #[must_use]
+    pub fn new(config_path: PathBuf) -> Self {
+        Self {
+            config_path,
+            verbose: 0,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Spawn the watcher; it stops when `cancel` fires.
+pub(super) fn spawn_config_watcher(
+    opts: ConfigReloadOptions,
+    initial: &AppConfig,
+    reloader: Arc<ConfigReloader>,
+    cancel: CancellationToken,
+) {
+    let watcher = ConfigWatcher {
+        modified: modified_at(&opts.config_path),
+        logging: logging_snapshot(initial),
+        host: host_snapshot(initial),
+        modules: initial.modules.clone(),
+        opts,
+        reloader,
+    };
+    drop(tokio::spawn(async move { watcher.run(&cancel).await }));
+}
+
+struct ConfigWatcher {
+    opts: ConfigReloadOptions,
+    reloader: Arc<ConfigReloader>,
+    modified: Option<SystemTime>,
+    /// Logging levels currently applied.
+    logging: serde_json::Value,
+    /// Startup values of everything only a restart applies.
+    host: serde_json::Value,
+    modules: HashMap<String, serde_json::Value>,
+}
+
+impl ConfigWatcher {
+    async fn run(mut self, cancel: &CancellationToken) {
+        let mut hangup = ReloadSignal::new()
+            .map_err(|e| tracing::warn!(error = %e, "SIGHUP config reload unavailable"))
+            .ok();
+        let mut poll = tokio::time::interval(self.opts.poll_interval);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        tracing::info!(
+            path = %self.opts.config_path.display(),
+            "Watching configuration for changes (file and SIGHUP)"
+        );
+
+        loop {
+            let trigger = tokio::select! {
+                () = cancel.cancelled() => return,
+                () = next_hangup(hangup.as_mut()) => "SIGHUP",
+                _ = poll.tick() => {
+                    let modified = modified_at(&self.opts.config_path);
+                    if modified == self.modified {
+                        continue;
+                    }
+                    self.modified = modified;
+                    "file change"
+                }
+            };
+            self.reload(trigger).await;
+        }
+    }
+
+    async fn reload(&mut self, trigger: &str) {
+        tracing::info!(
+            trigger,
+            path = %self.opts.config_path.display(),
+            "Reloading configuration"
+        );
+
+        let mut config = match AppConfig::load_layered(&self.opts.config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::error!(
+                    "Configuration reload rejected; keeping the running configuration: {e:#}"
+                );
+                return;
+            }
+        };
+        config.apply_cli_overrides(self.opts.verbose);
+
+        self.reload_logging(&config);
+        if host_snapshot(&config) != self.host {
+            tracing::warn!(
+                "Host settings changed (server, database, tracing, metrics); restart to apply"
+            );
+        }
+
+        let config = Arc::new(config);
+        let report = self
+            .reloader
+            .reload(Arc::clone(&config) as Arc<dyn ConfigProvider>)
+            .await;
+        let unattached = self.changed_unattached_modules(&config, &report);
+        log_report(&report, &unattached);
+    }
+
+    fn reload_logging(&mut self, config: &AppConfig) {
+        let snapshot = logging_snapshot(config);
+        if snapshot == self.logging {
+            return;
+        }
+        match reload_log_levels(&config.logging.clone().unwrap_or_default()) {
+            Ok(()) => {
+                self.logging = snapshot;
+                tracing::info!("Applied reloaded log levels");
+            }
+            Err(e) => tracing::warn!(error = %e, "Log levels not reloaded; restart to apply"),
+        }
+    }
+
+    /// Modules the runtime does not host in-process (out-of-process, added or removed)
+    /// whose section differs from startup.
+    fn changed_unattached_modules(
+        &self,
+        config: &AppConfig,
+        report: &ReloadReport,
+    ) -> BTreeSet<String> {
+        let attached: BTreeSet<&str> = report
+            .unchanged
+            .iter()
+            .chain(&report.applied)
+            .chain(&report.restart_required)
+            .copied()
+            .chain(report.failed.iter().map(|(name, _)| *name))
+            .collect();
+
+        self.modules
+            .keys()
+            .chain(config.modules.keys())
+            .filter(|name| !attached.contains(name.as_str()))
+            .filter(|name| self.modules.get(*name) != config.modules.get(*name))
+            .cloned()
+            .collect()
+    }
+}
+
+fn log_report(report: &ReloadReport, unattached: &BTreeSet<String>) {
+    for module in &report.applied {
+        tracing::info!(module, "Module applied reloaded configuration");
+    }
+    for (module, error) in &report.failed {
+        tracing::error!(
+            module,
+            error = %error,
+            "Module rejected reloaded configuration; keeping the previous one"
+        );
+    }
+    let restart: Vec<&str> = report
+        .restart_required
+        .iter()
+        .copied()
+        .chain(unattached.iter().map(String::as_str))
+        .collect();
+    if !restart.is_empty() {
+        tracing::warn!(
+            modules = ?restart,
+            "Configuration changed for modules that cannot reload it; restart to apply"
+        );
+    }
+    if !report.has_changes() && restart.is_empty() {
+        tracing::info!("Configuration reloaded; no module configuration changed");
+    }
+}
+
+async fn next_hangup(signal: Option<&mut ReloadSignal>) {
+    match signal {
+        Some(signal) => signal.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn logging_snapshot(config: &AppConfig) -> serde_json::Value {
+    serde_json::to_value(&config.logging).unwrap_or_default()
+}
+
+fn host_snapshot(config: &AppConfig) -> serde_json::Value {
+    let mut value = serde_json::to_value(config).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        map.remove("logging");
+        map.remove("modules");
+    }
+    value
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn host_snapshot_ignores_reloadable_sections() {
+        let base = AppConfig::default();
+        let mut changed = base.clone();
+        changed
+            .modules
+            .insert("m".to_owned(), json!({"config": {}}));
+        changed.logging = None;
+        assert_eq!(host_snapshot(&base), host_snapshot(&changed));
+
+        changed.server.home_dir = PathBuf::from("/elsewhere");
+        assert_ne!(host_snapshot(&base), host_snapshot(&changed));
+    }
+
+    #[test]
+    fn unattached_modules_are_diffed_against_startup() {
+        let mut initial = AppConfig::default();
+        initial
+            .modules
+            .insert("local".to_owned(), json!({"config": {"a": 1}}));
+        initial
+            .modules
+            .insert("oop".to_owned(), json!({"config": {"a": 1}}));
+        initial.modules.insert("gone".to_owned(), json!({}));
+        let watcher = ConfigWatcher {
+            opts: ConfigReloadOptions::new(PathBuf::from("config.yaml")),
+            reloader: Arc::new(ConfigReloader::new()),
+            modified: None,
+            logging: logging_snapshot(&initial),
+            host: host_snapshot(&initial),
+            modules: initial.modules.clone(),
+        };
+
+        let mut reloaded = initial.clone();
+        reloaded
+            .modules
+            .insert("local".to_owned(), json!({"config": {"a": 2}}));
+        reloaded
+            .modules
+            .insert("oop".to_owned(), json!({"config": {"a": 2}}));
+        reloaded.modules.remove("gone");
+        let report = ReloadReport {
+            applied: vec!["local"],
+            ..ReloadReport::default()
+        };
+
+        let unattached = watcher.changed_unattached_modules(&reloaded, &report);
+        assert_eq!(
+            unattached.into_iter().collect::<Vec<_>>(),
+            vec!["gone".to_owned(), "oop".to_owned()]
+        );
+    }
+}
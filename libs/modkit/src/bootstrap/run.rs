@@ -1,12 +1,16 @@
 use super::config::{get_module_runtime_config, render_module_config_for_oop};
 use super::host::normalize_path;
+use super::reload::{ConfigReloadOptions, spawn_config_watcher};
 use super::{AppConfig, RuntimeKind};
 use crate::backends::LocalProcessBackend;
+use crate::config_reload::ConfigReloader;
 use crate::runtime::{
-    DbOptions, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run, shutdown,
+    ClientRegistration, DbOptions, MigrationCommand, MigrationOutcome, ModuleMigrationReport,
+    OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run, shutdown,
 };
 use figment::Figment;
 use figment::providers::Serialized;
+use modkit_db::migration_runner::MigrationState;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
@@ -43,6 +47,25 @@ fn spawn_signal_handler(cancel: CancellationToken, context: &str) {
 /// - Problems with the database or third-party services
 /// - An issue during runtime or shutdown
 pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
+    serve(config, None).await
+}
+
+/// Like [`run_server`], and reloads the configuration when the file changes or on SIGHUP.
+///
+/// Modules with the `config_reload` capability get their new section live, log
+/// levels are swapped in place; everything else is reported as requiring a restart.
+///
+/// # Errors
+///
+/// Same as [`run_server`].
+pub async fn run_server_with_reload(
+    config: AppConfig,
+    reload: ConfigReloadOptions,
+) -> anyhow::Result<()> {
+    serve(config, Some(reload)).await
+}
+
+async fn serve(config: AppConfig, reload: Option<ConfigReloadOptions>) -> anyhow::Result<()> {
     tracing::info!("Initializing modules...");
 
     // Generate process-level instance ID once at startup.
@@ -67,6 +90,23 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
     // Build OoP spawn configuration
     let oop_options = build_oop_spawn_options(&config, oop_backend)?;
 
+    // Install the meter provider before the runtime so built-in instruments bind to it
+    #[cfg(feature = "otel")]
+    let meter_provider = config
+        .metrics
+        .as_ref()
+        .filter(|m| m.enabled)
+        .map(crate::telemetry::init_metrics)
+        .transpose()?;
+
+    // The runtime picks up this reloader from the hub and attaches modules to it
+    let mut clients = Vec::new();
+    if let Some(reload) = reload {
+        let reloader = Arc::new(ConfigReloader::new());
+        spawn_config_watcher(reload, &config, Arc::clone(&reloader), cancel.clone());
+        clients.push(ClientRegistration::new::<ConfigReloader>(reloader));
+    }
+
     // Run the ModKit runtime with the root cancellation token.
     // Shutdown is driven by the signal handler spawned above, not by ShutdownOptions::Signals.
     // OoP modules are spawned after the start phase (once grpc-hub has bound its port).
@@ -74,7 +114,7 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
         modules_cfg: Arc::new(config),
         db: db_options,
         shutdown: ShutdownOptions::Token(cancel.clone()),
-        clients: vec![],
+        clients,
         instance_id,
         oop: oop_options,
     };
@@ -85,6 +125,14 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
     #[cfg(feature = "otel")]
     crate::telemetry::init::shutdown_tracing();
 
+    // Flush the last metrics interval to the collector
+    #[cfg(feature = "otel")]
+    if let Some(provider) = meter_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!(error = %e, "Meter provider shutdown failed");
+    }
+
     result
 }
 
@@ -107,8 +155,27 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
 /// - Module discovery fails
 /// - Pre-init phase fails
 /// - Migration phase fails
-#[allow(unknown_lints, de1301_no_print_macros)]
 pub async fn run_migrate(config: AppConfig) -> anyhow::Result<()> {
+    run_migrate_command(config, MigrationCommand::Apply).await
+}
+
+/// Run a migration command (apply, dry run, status or down) and exit.
+///
+/// Runs the same phases as [`run_migrate`], then prints a per-module report
+/// for the operator on stdout.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No database configuration is found
+/// - Module discovery fails
+/// - Pre-init phase fails
+/// - The command fails for any module, or `Down` names an unknown module
+#[allow(unknown_lints, de1301_no_print_macros)]
+pub async fn run_migrate_command(
+    config: AppConfig,
+    command: MigrationCommand,
+) -> anyhow::Result<()> {
     tracing::info!("Starting migration mode...");
 
     // Generate process-level instance ID for this migration run
@@ -147,20 +214,90 @@ pub async fn run_migrate(config: AppConfig) -> anyhow::Result<()> {
         None, // No OoP spawning during migration
     );
 
-    // Run only the migration phases (pre-init + DB migration)
-    let result = host.run_migration_phases().await;
+    // Run only the migration phases (pre-init + DB migration command)
+    let result = host.run_migration_command(&command).await;
 
     // Graceful shutdown - flush any remaining traces
     #[cfg(feature = "otel")]
     crate::telemetry::init::shutdown_tracing();
 
-    result?;
+    let reports = result?;
 
-    tracing::info!("All migrations completed successfully");
-    println!("[OK] Database migrations completed successfully");
+    if command == MigrationCommand::Apply {
+        tracing::info!("All migrations completed successfully");
+        println!("[OK] Database migrations completed successfully");
+    } else {
+        print!("{}", render_migration_reports(&reports));
+    }
     Ok(())
 }
 
+/// Render migration reports as plain text, one block per module.
+fn render_migration_reports(reports: &[ModuleMigrationReport]) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    let mut drifted = 0usize;
+    for report in reports {
+        _ = writeln!(out, "{}:", report.module);
+        match &report.outcome {
+            MigrationOutcome::Applied(result) => {
+                for name in &result.applied_names {
+                    _ = writeln!(out, "  applied {name}");
+                }
+            }
+            MigrationOutcome::Planned(plans) if plans.is_empty() => {
+                _ = writeln!(out, "  nothing to do");
+            }
+            MigrationOutcome::Planned(plans) => {
+                for plan in plans {
+                    _ = writeln!(out, "  -- {}", plan.name);
+                    for statement in &plan.statements {
+                        _ = writeln!(out, "  {statement};");
+                    }
+                }
+            }
+            MigrationOutcome::Status(statuses) => {
+                for status in statuses {
+                    let state = match status.state {
+                        MigrationState::Applied => "applied",
+                        MigrationState::Pending => "pending",
+                        MigrationState::Unknown => "unknown",
+                    };
+                    _ = write!(out, "  [{state}] {}", status.name);
+                    if let Some(at) = &status.applied_at {
+                        _ = write!(out, "  applied_at={at}");
+                    }
+                    if let Some(at) = &status.reverted_at {
+                        _ = write!(out, "  reverted_at={at}");
+                    }
+                    if status.drifted {
+                        drifted += 1;
+                        _ = write!(out, "  [DRIFT] modified after it was applied");
+                    }
+                    out.push('\n');
+                }
+            }
+            MigrationOutcome::Reverted(names) if names.is_empty() => {
+                _ = writeln!(out, "  nothing to revert");
+            }
+            MigrationOutcome::Reverted(names) => {
+                for name in names {
+                    _ = writeln!(out, "  reverted {name}");
+                }
+            }
+        }
+    }
+    if drifted > 0 {
+        _ = writeln!(
+            out,
+            "[WARN] {drifted} applied migration(s) changed since they ran; \
+             write a new migration instead of editing applied ones"
+        );
+    }
+    out
+}
+
 fn resolve_db_options(config: &AppConfig) -> anyhow::Result<DbOptions> {
     if config.database.is_none() {
         tracing::warn!("No global database section found; running without databases");
@@ -243,5 +380,6 @@ fn try_build_oop_module_config(
         env,
         working_directory: exec_cfg.working_directory.clone(),
         rendered_config_json: rendered_json,
+        restart: runtime_cfg.restart.clone(),
     }))
 }
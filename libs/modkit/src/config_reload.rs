@@ -0,0 +1,310 @@
+//! Live reload of module configuration.
+//!
+//! The runtime attaches every in-process module to the [`ConfigReloader`] once it has
+//! initialized. When the host hands it a freshly loaded configuration, the reloader
+//! compares each module's section with the one the module is running on:
+//!
+//! - unchanged sections are left alone;
+//! - a changed `config` subsection is delivered to modules with the `config_reload`
+//!   capability ([`ConfigReloadCapability`]);
+//! - any other change (no capability, or `database`/`runtime` edits) is reported as
+//!   requiring a restart, and the module keeps its current configuration.
+//!
+//! The reloader is published in the `ClientHub`. A host that wants to drive reloads
+//! registers its own instance up front (see `RunOptions::clients`).
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::config::ConfigProvider;
+use crate::context::ModuleCtx;
+use crate::contracts::ConfigReloadCapability;
+
+/// Outcome of one reload, by module.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Section identical to the running one.
+    pub unchanged: Vec<&'static str>,
+    /// New `config` accepted by the module.
+    pub applied: Vec<&'static str>,
+    /// Changed, but only a restart can apply it.
+    pub restart_required: Vec<&'static str>,
+    /// The module rejected the new `config`, with the error it returned.
+    pub failed: Vec<(&'static str, String)>,
+}
+
+impl ReloadReport {
+    /// Whether any module saw a change, applied or not.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !(self.applied.is_empty() && self.restart_required.is_empty() && self.failed.is_empty())
+    }
+}
+
+/// How a module section differs from the running one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionDiff {
+    Unchanged,
+    /// Only the typed `config` subsection changed.
+    ConfigOnly,
+    /// Anything else changed (`database`, `runtime`, ...).
+    Structural,
+}
+
+fn diff_section(old: Option<&Value>, new: Option<&Value>) -> SectionDiff {
+    if old == new {
+        return SectionDiff::Unchanged;
+    }
+    if without_config(old) == without_config(new) {
+        SectionDiff::ConfigOnly
+    } else {
+        SectionDiff::Structural
+    }
+}
+
+/// The section minus its `config` key; a missing section reads as an empty object.
+fn without_config(section: Option<&Value>) -> Value {
+    match section {
+        Some(Value::Object(map)) => Value::Object(
+            map.iter()
+                .filter(|(k, _)| k.as_str() != "config")
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        ),
+        Some(other) => other.clone(),
+        None => Value::Object(serde_json::Map::new()),
+    }
+}
+
+struct Attached {
+    name: &'static str,
+    /// Context carrying the configuration the module currently runs on.
+    ctx: ModuleCtx,
+    handler: Option<Arc<dyn ConfigReloadCapability>>,
+}
+
+/// Delivers configuration changes to running modules.
+#[derive(Default)]
+pub struct ConfigReloader {
+    // Async mutex: reloads are serialized and hold the lock across module callbacks
+    modules: Mutex<Vec<Attached>>,
+}
+
+impl ConfigReloader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a module initialized with `ctx`; `handler` is its reload capability, if any.
+    pub(crate) async fn attach(
+        &self,
+        name: &'static str,
+        ctx: ModuleCtx,
+        handler: Option<Arc<dyn ConfigReloadCapability>>,
+    ) {
+        self.modules
+            .lock()
+            .await
+            .push(Attached { name, ctx, handler });
+    }
+
+    /// Offer `provider` to every attached module.
+    ///
+    /// Modules are visited in initialization order. A module that rejects its new
+    /// section, or needs a restart, keeps comparing against its old one, so it shows
+    /// up again on the next reload until the change is applied.
+    pub async fn reload(&self, provider: Arc<dyn ConfigProvider>) -> ReloadReport {
+        let mut report = ReloadReport::default();
+        let mut modules = self.modules.lock().await;
+
+        for module in modules.iter_mut() {
+            let diff = diff_section(
+                module.ctx.config_provider().get_module_config(module.name),
+                provider.get_module_config(module.name),
+            );
+            let handler = match (diff, &module.handler) {
+                (SectionDiff::Unchanged, _) => {
+                    report.unchanged.push(module.name);
+                    continue;
+                }
+                (SectionDiff::ConfigOnly, Some(handler)) => Arc::clone(handler),
+                (SectionDiff::ConfigOnly | SectionDiff::Structural, _) => {
+                    report.restart_required.push(module.name);
+                    continue;
+                }
+            };
+
+            let ctx = module.ctx.with_config_provider(Arc::clone(&provider));
+            match handler.reload_config(&ctx).await {
+                Ok(()) => {
+                    module.ctx = ctx;
+                    report.applied.push(module.name);
+                }
+                Err(e) => report.failed.push((module.name, format!("{e:#}"))),
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::client_hub::ClientHub;
+    use async_trait::async_trait;
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio_util::sync::CancellationToken;
+    use uuid::Uuid;
+
+    struct JsonProvider(Value);
+
+    impl ConfigProvider for JsonProvider {
+        fn get_module_config(&self, module_name: &str) -> Option<&Value> {
+            self.0.get(module_name)
+        }
+    }
+
+    #[derive(Deserialize, Default)]
+    struct LimitConfig {
+        limit: u32,
+    }
+
+    /// Stores the `limit` it is reloaded with; rejects zero.
+    #[derive(Default)]
+    struct Limiter(AtomicU32);
+
+    #[async_trait]
+    impl ConfigReloadCapability for Limiter {
+        async fn reload_config(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+            let cfg: LimitConfig = ctx.config()?;
+            anyhow::ensure!(cfg.limit > 0, "limit must be positive");
+            self.0.store(cfg.limit, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn ctx(name: &'static str, config: Value) -> ModuleCtx {
+        ModuleCtx::new(
+            name,
+            Uuid::new_v4(),
+            Arc::new(JsonProvider(config)),
+            Arc::new(ClientHub::new()),
+            CancellationToken::new(),
+            None,
+        )
+    }
+
+    fn provider(config: Value) -> Arc<dyn ConfigProvider> {
+        Arc::new(JsonProvider(config))
+    }
+
+    #[test]
+    fn diff_separates_config_from_other_keys() {
+        let base = json!({"config": {"limit": 1}, "runtime": {"type": "local"}});
+        assert_eq!(
+            diff_section(Some(&base), Some(&base.clone())),
+            SectionDiff::Unchanged
+        );
+        assert_eq!(
+            diff_section(
+                Some(&base),
+                Some(&json!({"config": {"limit": 2}, "runtime": {"type": "local"}}))
+            ),
+            SectionDiff::ConfigOnly
+        );
+        assert_eq!(
+            diff_section(Some(&base), Some(&json!({"config": {"limit": 1}}))),
+            SectionDiff::Structural
+        );
+        assert_eq!(
+            diff_section(None, Some(&json!({"config": {"limit": 1}}))),
+            SectionDiff::ConfigOnly
+        );
+    }
+
+    #[tokio::test]
+    async fn reload_applies_config_to_opted_in_modules() {
+        let initial = json!({
+            "limiter": {"config": {"limit": 10}},
+            "static": {"config": {"tenants": ["a"]}},
+            "quiet": {"config": {}},
+        });
+        let limiter = Arc::new(Limiter::default());
+        let reloader = ConfigReloader::new();
+        for name in ["limiter", "static", "quiet"] {
+            let handler = (name == "limiter")
+                .then(|| Arc::clone(&limiter) as Arc<dyn ConfigReloadCapability>);
+            reloader
+                .attach(name, ctx(name, initial.clone()), handler)
+                .await;
+        }
+
+        let report = reloader
+            .reload(provider(json!({
+                "limiter": {"config": {"limit": 25}},
+                "static": {"config": {"tenants": ["a", "b"]}},
+                "quiet": {"config": {}},
+            })))
+            .await;
+
+        assert_eq!(report.applied, vec!["limiter"]);
+        assert_eq!(report.restart_required, vec!["static"]);
+        assert_eq!(report.unchanged, vec!["quiet"]);
+        assert!(report.failed.is_empty());
+        assert_eq!(limiter.0.load(Ordering::SeqCst), 25);
+    }
+
+    #[tokio::test]
+    async fn rejected_config_keeps_the_running_one() {
+        let limiter = Arc::new(Limiter::default());
+        let reloader = ConfigReloader::new();
+        reloader
+            .attach(
+                "limiter",
+                ctx("limiter", json!({"limiter": {"config": {"limit": 10}}})),
+                Some(Arc::clone(&limiter) as Arc<dyn ConfigReloadCapability>),
+            )
+            .await;
+
+        let report = reloader
+            .reload(provider(json!({"limiter": {"config": {"limit": 0}}})))
+            .await;
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].1.contains("limit must be positive"));
+        assert_eq!(limiter.0.load(Ordering::SeqCst), 0);
+
+        // Still diffed against the original section
+        let report = reloader
+            .reload(provider(json!({"limiter": {"config": {"limit": 10}}})))
+            .await;
+        assert_eq!(report.unchanged, vec!["limiter"]);
+        assert!(!report.has_changes());
+    }
+
+    #[tokio::test]
+    async fn database_changes_require_restart_even_with_capability() {
+        let reloader = ConfigReloader::new();
+        reloader
+            .attach(
+                "limiter",
+                ctx("limiter", json!({"limiter": {"config": {"limit": 10}}})),
+                Some(Arc::new(Limiter::default()) as Arc<dyn ConfigReloadCapability>),
+            )
+            .await;
+
+        let report = reloader
+            .reload(provider(json!({
+                "limiter": {"config": {"limit": 20}, "database": {"server": "pg"}},
+            })))
+            .await;
+        assert_eq!(report.restart_required, vec!["limiter"]);
+        assert!(report.applied.is_empty());
+    }
+}
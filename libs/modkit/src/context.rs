@@ -121,6 +121,14 @@ impl ModuleCtx {
         }
     }
 
+    /// Same context (token, DB, hub) seen through a different configuration.
+    pub(crate) fn with_config_provider(&self, config_provider: Arc<dyn ConfigProvider>) -> Self {
+        Self {
+            config_provider,
+            ..self.clone()
+        }
+    }
+
     // ---- public read-only API for modules ----
 
     #[inline]
@@ -96,6 +96,49 @@ pub trait ApiGatewayCapability: Send + Sync + 'static {
 pub trait RunnableCapability: Send + Sync {
     async fn start(&self, cancel: CancellationToken) -> anyhow::Result<()>;
     async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()>;
+
+    /// Current lifecycle status, if the implementation tracks one.
+    ///
+    /// The runtime turns it into the module's `lifecycle` health check; `None` opts out.
+    fn status(&self) -> Option<crate::lifecycle::Status> {
+        None
+    }
+}
+
+/// Health check capability: modules report whether they can serve traffic.
+///
+/// The runtime aggregates these reports with its built-in checks (database ping,
+/// lifecycle status, out-of-process instance states), and the REST host serves
+/// readiness on `/health` and liveness on `/healthz`. Every call is bounded by a
+/// timeout; a check that does not answer in time counts as unhealthy.
+#[async_trait]
+pub trait HealthCheckCapability: Send + Sync {
+    /// Whether the module can serve requests right now.
+    async fn readiness(&self) -> crate::health::HealthCheckResult;
+
+    /// Whether the module is still functional at all.
+    ///
+    /// An unhealthy liveness result gets the whole process restarted, so report it
+    /// only for states the module cannot recover from. Default: healthy.
+    async fn liveness(&self) -> crate::health::HealthCheckResult {
+        crate::health::HealthCheckResult::healthy()
+    }
+}
+
+/// Config reload capability: the module applies a changed configuration section live.
+///
+/// When the host picks up a new configuration (file change or `SIGHUP`) and this
+/// module's section differs from the running one, the runtime calls `reload_config`
+/// with a context backed by the new configuration; read it with `ctx.config::<T>()`.
+/// Modules without this capability are reported as requiring a restart instead.
+#[async_trait]
+pub trait ConfigReloadCapability: Send + Sync {
+    /// Apply the configuration visible through `ctx`.
+    ///
+    /// # Errors
+    /// Returning an error rejects the new section; the module keeps running with
+    /// the configuration it had before.
+    async fn reload_config(&self, ctx: &crate::context::ModuleCtx) -> anyhow::Result<()>;
 }
 
 /// Represents a gRPC service registration callback used by the gRPC hub.
@@ -47,6 +47,7 @@ impl DirectoryClient for LocalDirectoryClient {
                     instance_id: inst.instance_id.to_string(),
                     endpoint: ServiceEndpoint::new(ep.uri.clone()),
                     version: inst.version.clone(),
+                    history: self.mgr.instance_history(inst.instance_id),
                 });
             }
         }
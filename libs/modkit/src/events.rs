@@ -0,0 +1,613 @@
+//! Domain events with a transactional outbox and in-process delivery.
+//!
+//! A publishing module owns an [`EventBus<E>`] per event type. [`EventBus::publish`]
+//! writes the event to the module's outbox table using the caller's runner, so passing
+//! the `DbTx` of the business change makes the event durable exactly when the change
+//! commits. The bus then runs as a [`Runnable`] relay that reads the outbox and delivers
+//! each event to every subscriber:
+//!
+//! - **at-least-once**: a subscriber's offset advances only after it handled the event,
+//!   so a crash in between redelivers it; handlers must be idempotent;
+//! - **in order**: events are delivered in `seq` order, and a gap left by a transaction
+//!   still committing holds back later events until it fills or [`RelayConfig::settle_time`]
+//!   passes (see [`Outbox::fetch_ready`]);
+//! - **per-subscriber offsets**: kept per subscriber and topic, so a slow or failing
+//!   subscriber does not hold back others;
+//! - **one relay**: each pass holds a database lease on the outbox topic, so replicas
+//!   sharing the database do not deliver the same events concurrently;
+//! - **replay**: [`EventBus::replay_from`] rewinds one subscriber to a given sequence;
+//! - **dead letters**: an event still failing after [`RelayConfig::max_attempts`] is moved
+//!   to the dead-letter table and the subscriber continues with the next one.
+//!
+//! Buses are shared through the [`ClientHub`](crate::ClientHub) under their concrete type,
+//! so consumers find them by event type alone:
+//!
+//! ```rust,ignore
+//! // Producer (init): its migrations include `Outbox::new(Self::MODULE_NAME).migration()`.
+//! let bus = Arc::new(EventBus::<UserCreated>::new(db, Outbox::new(Self::MODULE_NAME)));
+//! ctx.client_hub().register::<EventBus<UserCreated>>(bus.clone());
+//! // Producer (start): tokio::spawn(bus.run(cancel));
+//!
+//! // Consumer (init, with the producer in `deps`):
+//! ctx.client_hub()
+//!     .get::<EventBus<UserCreated>>()?
+//!     .subscribe(Arc::new(WelcomeMailer::new()));
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use modkit_db::outbox::{DeadLetter, Outbox, OutboxRecord};
+use modkit_db::secure::DBRunner;
+use modkit_db::{Db, DbError, LockConfig};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio_util::sync::CancellationToken;
+
+use crate::lifecycle::Runnable;
+
+/// An event type that can travel through the outbox.
+pub trait DomainEvent: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Topic the event is stored under. Keep it stable: stored rows are matched by it.
+    const TOPIC: &'static str;
+}
+
+/// A delivered event together with its outbox metadata.
+#[derive(Debug, Clone)]
+pub struct EventEnvelope<E> {
+    /// Outbox sequence number; strictly increasing per publishing module.
+    pub seq: i64,
+    /// Publish time, epoch milliseconds.
+    pub published_at_ms: i64,
+    pub event: E,
+}
+
+/// A typed consumer of events of type `E`.
+#[async_trait]
+pub trait EventSubscriber<E: DomainEvent>: Send + Sync {
+    /// Stable subscriber name. Offsets and dead letters are keyed by it.
+    fn name(&self) -> &str;
+
+    /// Handle one event. Returning an error schedules a retry.
+    async fn handle(&self, envelope: &EventEnvelope<E>) -> anyhow::Result<()>;
+}
+
+/// Relay tuning.
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// Delay between polls when the outbox has nothing new.
+    pub poll_interval: Duration,
+    /// Maximum events read per subscriber per poll.
+    pub batch_size: u64,
+    /// Delivery attempts before an event is dead-lettered.
+    pub max_attempts: u32,
+    /// Delay between delivery attempts of the same event.
+    pub retry_backoff: Duration,
+    /// How long a transaction publishing events may stay open. A gap in the outbox older
+    /// than this is taken as a rolled-back event and skipped.
+    pub settle_time: Duration,
+    /// Lifetime of the relay lease; another replica takes over a relay that stopped
+    /// renewing it after this.
+    pub lease_ttl: Duration,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(500),
+            batch_size: 100,
+            max_attempts: 5,
+            retry_backoff: Duration::from_millis(200),
+            settle_time: Duration::from_secs(10),
+            lease_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Lock namespace of relay leases.
+const RELAY_LOCK_MODULE: &str = "modkit_outbox";
+
+#[derive(Default)]
+struct RelayPass {
+    /// Events handled or dead-lettered.
+    consumed: usize,
+    /// Whether any subscriber's offset moved; the outbox may have more right away.
+    advanced: bool,
+}
+
+/// Outbox-backed event bus for events of type `E`.
+pub struct EventBus<E: DomainEvent> {
+    db: Db,
+    outbox: Outbox,
+    config: RelayConfig,
+    subscribers: RwLock<Vec<Arc<dyn EventSubscriber<E>>>>,
+}
+
+impl<E: DomainEvent> EventBus<E> {
+    /// Bus writing to and relaying from `outbox`.
+    #[must_use]
+    pub fn new(db: Db, outbox: Outbox) -> Self {
+        Self {
+            db,
+            outbox,
+            config: RelayConfig::default(),
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_config(mut self, config: RelayConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Write `event` to the outbox through `runner`.
+    ///
+    /// Pass the transaction of the business change; the event is delivered only if it commits.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the event cannot be serialized or the insert fails.
+    pub async fn publish(&self, runner: &impl DBRunner, event: &E) -> Result<(), DbError> {
+        let payload = serde_json::to_value(event).map_err(|e| DbError::Other(e.into()))?;
+        self.outbox.enqueue(runner, E::TOPIC, &payload).await
+    }
+
+    /// Register a subscriber. A subscriber with the same name is replaced.
+    pub fn subscribe(&self, subscriber: Arc<dyn EventSubscriber<E>>) {
+        let mut subs = self.subscribers.write();
+        subs.retain(|s| s.name() != subscriber.name());
+        subs.push(subscriber);
+    }
+
+    /// Redeliver events to `subscriber` starting at sequence `seq`.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the offset cannot be stored.
+    pub async fn replay_from(&self, subscriber: &str, seq: i64) -> Result<(), DbError> {
+        let conn = self.db.conn()?;
+        self.outbox
+            .commit_offset(&conn, subscriber, E::TOPIC, seq.saturating_sub(1).max(0))
+            .await
+    }
+
+    /// Events `subscriber` gave up on, oldest first.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the dead-letter table cannot be read.
+    pub async fn dead_letters(&self, subscriber: &str) -> Result<Vec<DeadLetter>, DbError> {
+        let conn = self.db.conn()?;
+        self.outbox.dead_letters(&conn, subscriber).await
+    }
+
+    /// Deliver one batch of pending events to every subscriber.
+    ///
+    /// Returns how many events were consumed (handled or dead-lettered). Nothing is
+    /// delivered while another relay of this topic holds the lease.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the lease, the outbox or offsets cannot be read or written.
+    pub async fn relay_once(&self) -> Result<usize, DbError> {
+        Ok(self.relay_pass().await?.consumed)
+    }
+
+    async fn relay_pass(&self) -> Result<RelayPass, DbError> {
+        let lease = self
+            .db
+            .try_lock(
+                RELAY_LOCK_MODULE,
+                &format!("{}:{}", self.outbox.outbox_table(), E::TOPIC),
+                LockConfig {
+                    max_attempts: Some(1),
+                    lease_ttl: self.config.lease_ttl,
+                    ..LockConfig::default()
+                },
+            )
+            .await?;
+        let Some(lease) = lease else {
+            return Ok(RelayPass::default());
+        };
+
+        let subscribers = self.subscribers.read().clone();
+        let mut pass = RelayPass::default();
+        let mut result = Ok(());
+        for subscriber in subscribers {
+            match self.relay_to(subscriber.as_ref()).await {
+                Ok((consumed, advanced)) => {
+                    pass.consumed += consumed;
+                    pass.advanced |= advanced;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        lease.release().await;
+        result.map(|()| pass)
+    }
+
+    /// Returns the events consumed and whether the subscriber's offset moved.
+    async fn relay_to(
+        &self,
+        subscriber: &dyn EventSubscriber<E>,
+    ) -> Result<(usize, bool), DbError> {
+        let conn = self.db.conn()?;
+        let name = subscriber.name();
+        let offset = self.outbox.offset(&conn, name, E::TOPIC).await?;
+        let batch = self
+            .outbox
+            .fetch_ready(
+                &conn,
+                E::TOPIC,
+                offset,
+                self.config.batch_size,
+                self.config.settle_time,
+            )
+            .await?;
+
+        for record in &batch.records {
+            if let Err((error, attempts)) = self.deliver(subscriber, record).await {
+                tracing::warn!(
+                    subscriber = name,
+                    topic = E::TOPIC,
+                    seq = record.seq,
+                    attempts,
+                    error = %format!("{error:#}"),
+                    "event dead-lettered"
+                );
+                self.outbox
+                    .dead_letter(
+                        &conn,
+                        name,
+                        record,
+                        &format!("{error:#}"),
+                        i32::try_from(attempts).unwrap_or(i32::MAX),
+                    )
+                    .await?;
+            }
+            self.outbox
+                .commit_offset(&conn, name, E::TOPIC, record.seq)
+                .await?;
+        }
+        // Skip past the rows of other topics read in this batch.
+        if batch.frontier > batch.records.last().map_or(offset, |r| r.seq) {
+            self.outbox
+                .commit_offset(&conn, name, E::TOPIC, batch.frontier)
+                .await?;
+        }
+        Ok((batch.records.len(), batch.frontier > offset))
+    }
+
+    /// Deliver with retries; on failure returns the last error and the attempts made.
+    async fn deliver(
+        &self,
+        subscriber: &dyn EventSubscriber<E>,
+        record: &OutboxRecord,
+    ) -> Result<(), (anyhow::Error, u32)> {
+        let envelope = EventEnvelope {
+            seq: record.seq,
+            published_at_ms: record.created_at_ms,
+            // An undecodable payload will not get better with retries.
+            event: serde_json::from_value::<E>(record.payload.clone())
+                .map_err(|e| (anyhow::Error::new(e).context("decode event payload"), 1))?,
+        };
+
+        let max_attempts = self.config.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match subscriber.handle(&envelope).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= max_attempts => return Err((e, attempt)),
+                Err(e) => {
+                    tracing::debug!(
+                        subscriber = subscriber.name(),
+                        seq = record.seq,
+                        attempt,
+                        error = %e,
+                        "event delivery failed; retrying"
+                    );
+                    tokio::time::sleep(self.config.retry_backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<E: DomainEvent> Runnable for EventBus<E> {
+    async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        loop {
+            let idle = match self.relay_pass().await {
+                Ok(pass) => !pass.advanced,
+                Err(e) => {
+                    tracing::warn!(topic = E::TOPIC, error = %e, "event relay pass failed");
+                    true
+                }
+            };
+            if idle {
+                tokio::select! {
+                    () = cancel.cancelled() => return Ok(()),
+                    () = tokio::time::sleep(self.config.poll_interval) => {}
+                }
+            } else if cancel.is_cancelled() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::ClientHub;
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use serde::Deserialize;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct UserCreated {
+        id: u32,
+    }
+
+    impl DomainEvent for UserCreated {
+        const TOPIC: &'static str = "test.user.created";
+    }
+
+    /// Records delivered ids; fails the first `fail_first` attempts of every event.
+    struct Recorder {
+        name: &'static str,
+        fail_first: u32,
+        calls: AtomicU32,
+        seen: parking_lot::Mutex<Vec<u32>>,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, fail_first: u32) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                fail_first,
+                calls: AtomicU32::new(0),
+                seen: parking_lot::Mutex::new(Vec::new()),
+            })
+        }
+
+        fn seen(&self) -> Vec<u32> {
+            self.seen.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EventSubscriber<UserCreated> for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn handle(&self, envelope: &EventEnvelope<UserCreated>) -> anyhow::Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let failing = match self.fail_first.checked_add(1) {
+                Some(cycle) => call % cycle < self.fail_first,
+                None => true,
+            };
+            if failing {
+                anyhow::bail!("transient failure");
+            }
+            self.seen.lock().push(envelope.event.id);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct UserDeleted {
+        id: u32,
+    }
+
+    impl DomainEvent for UserDeleted {
+        const TOPIC: &'static str = "test.user.deleted";
+    }
+
+    #[async_trait]
+    impl EventSubscriber<UserDeleted> for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn handle(&self, envelope: &EventEnvelope<UserDeleted>) -> anyhow::Result<()> {
+            self.seen.lock().push(envelope.event.id);
+            Ok(())
+        }
+    }
+
+    async fn setup_bus() -> Arc<EventBus<UserCreated>> {
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let dsn = format!(
+            "sqlite:file:memdb_events_{}?mode=memory&cache=shared",
+            uuid::Uuid::now_v7().simple()
+        );
+        let db = connect_db(&dsn, opts).await.unwrap();
+        let outbox = Outbox::new("events-test");
+        run_migrations_for_testing(&db, vec![outbox.migration()])
+            .await
+            .unwrap();
+
+        Arc::new(EventBus::new(db, outbox).with_config(RelayConfig {
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(1),
+            ..RelayConfig::default()
+        }))
+    }
+
+    async fn publish_in_tx(bus: &Arc<EventBus<UserCreated>>, id: u32) {
+        let b = Arc::clone(bus);
+        bus.db
+            .transaction_ref(move |tx| {
+                Box::pin(async move { b.publish(tx, &UserCreated { id }).await })
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn delivers_committed_events_to_each_subscriber() {
+        let bus = setup_bus().await;
+        let a = Recorder::new("a", 0);
+        let b = Recorder::new("b", 0);
+        bus.subscribe(a.clone());
+        bus.subscribe(b.clone());
+
+        publish_in_tx(&bus, 1).await;
+        publish_in_tx(&bus, 2).await;
+
+        assert_eq!(bus.relay_once().await.unwrap(), 4);
+        assert_eq!(a.seen(), vec![1, 2]);
+        assert_eq!(b.seen(), vec![1, 2]);
+
+        // Offsets were committed: nothing is redelivered.
+        assert_eq!(bus.relay_once().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn rolled_back_events_are_not_delivered() {
+        let bus = setup_bus().await;
+        let a = Recorder::new("a", 0);
+        bus.subscribe(a.clone());
+
+        let b = Arc::clone(&bus);
+        let res: Result<(), DbError> = bus
+            .db
+            .transaction_ref(move |tx| {
+                Box::pin(async move {
+                    b.publish(tx, &UserCreated { id: 9 }).await?;
+                    Err(DbError::Other(anyhow::anyhow!("business change failed")))
+                })
+            })
+            .await;
+        assert!(res.is_err());
+
+        assert_eq!(bus.relay_once().await.unwrap(), 0);
+        assert!(a.seen().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_then_dead_letters() {
+        let bus = setup_bus().await;
+        let flaky = Recorder::new("flaky", 2);
+        let broken = Recorder::new("broken", u32::MAX);
+        bus.subscribe(flaky.clone());
+        bus.subscribe(broken.clone());
+
+        publish_in_tx(&bus, 7).await;
+        bus.relay_once().await.unwrap();
+
+        assert_eq!(flaky.seen(), vec![7], "succeeds on the third attempt");
+        assert!(bus.dead_letters("flaky").await.unwrap().is_empty());
+
+        let dead = bus.dead_letters("broken").await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 3);
+        assert!(dead[0].error.contains("transient failure"));
+        assert_eq!(bus.relay_once().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn replay_redelivers_from_offset() {
+        let bus = setup_bus().await;
+        let a = Recorder::new("a", 0);
+        bus.subscribe(a.clone());
+
+        for id in 1..=3 {
+            publish_in_tx(&bus, id).await;
+        }
+        bus.relay_once().await.unwrap();
+
+        let conn = bus.db.conn().unwrap();
+        let second = bus
+            .outbox
+            .fetch_after(&conn, UserCreated::TOPIC, 0, 10)
+            .await
+            .unwrap()[1]
+            .seq;
+        bus.replay_from("a", second).await.unwrap();
+        bus.relay_once().await.unwrap();
+
+        assert_eq!(a.seen(), vec![1, 2, 3, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn offsets_are_kept_per_topic() {
+        let created = setup_bus().await;
+        let deleted = Arc::new(EventBus::<UserDeleted>::new(
+            created.db.clone(),
+            created.outbox.clone(),
+        ));
+        let audit_created = Recorder::new("audit", 0);
+        let audit_deleted = Recorder::new("audit", 0);
+        created.subscribe(audit_created.clone());
+        deleted.subscribe(audit_deleted.clone());
+
+        publish_in_tx(&created, 1).await;
+        let conn = created.db.conn().unwrap();
+        deleted
+            .publish(&conn, &UserDeleted { id: 1 })
+            .await
+            .unwrap();
+        publish_in_tx(&created, 2).await;
+
+        assert_eq!(created.relay_once().await.unwrap(), 2);
+        assert_eq!(deleted.relay_once().await.unwrap(), 1);
+        assert_eq!(audit_created.seen(), vec![1, 2]);
+        assert_eq!(audit_deleted.seen(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn relay_waits_for_the_lease() {
+        let bus = setup_bus().await;
+        let a = Recorder::new("a", 0);
+        bus.subscribe(a.clone());
+        publish_in_tx(&bus, 1).await;
+
+        let key = format!("{}:{}", bus.outbox.outbox_table(), UserCreated::TOPIC);
+        let other_relay = bus.db.lock(RELAY_LOCK_MODULE, &key).await.unwrap();
+        assert_eq!(bus.relay_once().await.unwrap(), 0);
+        assert!(a.seen().is_empty());
+
+        other_relay.release().await;
+        assert_eq!(bus.relay_once().await.unwrap(), 1);
+        assert_eq!(a.seen(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn bus_is_shared_through_client_hub() {
+        let bus = setup_bus().await;
+        let hub = ClientHub::new();
+        hub.register::<EventBus<UserCreated>>(bus.clone());
+
+        let a = Recorder::new("a", 0);
+        hub.get::<EventBus<UserCreated>>()
+            .unwrap()
+            .subscribe(a.clone());
+
+        let cancel = CancellationToken::new();
+        let relay = tokio::spawn(bus.clone().run(cancel.clone()));
+
+        publish_in_tx(&bus, 5).await;
+        for _ in 0..100 {
+            if !a.seen().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        cancel.cancel();
+        relay.await.unwrap().unwrap();
+
+        assert_eq!(a.seen(), vec![5]);
+    }
+}
@@ -0,0 +1,550 @@
+//! Module health checks aggregated into liveness and readiness reports.
+//!
+//! Modules opt in with the `health` capability
+//! ([`HealthCheckCapability`](crate::contracts::HealthCheckCapability)). On top of
+//! those, the runtime registers built-in checks with the [`HealthAggregator`]:
+//!
+//! - `database`: pings the module's database (readiness only)
+//! - `lifecycle`: status of stateful modules that track one (e.g. [`crate::WithLifecycle`])
+//! - `instances`: directory state of out-of-process modules (readiness only)
+//!
+//! The aggregator is published in the `ClientHub`; the REST host answers `/health`
+//! with readiness and `/healthz` with liveness.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use parking_lot::RwLock;
+use serde::Serialize;
+
+use crate::contracts::{HealthCheckCapability, RunnableCapability};
+use crate::lifecycle::Status;
+use crate::runtime::{InstanceState, ModuleManager};
+
+/// Default per-check timeout.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of a check, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    /// Serving with reduced capacity; does not fail the probe.
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Whether a probe reporting this status passes.
+    #[inline]
+    #[must_use]
+    pub fn is_passing(self) -> bool {
+        self != Self::Unhealthy
+    }
+}
+
+/// Which probe a report answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthProbe {
+    /// The process is functional; a failing probe gets it restarted.
+    Liveness,
+    /// The process can take traffic; a failing probe takes it out of rotation.
+    Readiness,
+}
+
+/// Where the host runtime is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum RuntimePhase {
+    /// Phases up to and including the `OoP` spawn are still running.
+    Starting,
+    /// All modules are started.
+    Serving,
+    /// Shutdown was requested.
+    Stopping,
+}
+
+impl RuntimePhase {
+    #[inline]
+    const fn from_u8(x: u8) -> Self {
+        match x {
+            1 => Self::Serving,
+            2 => Self::Stopping,
+            _ => Self::Starting,
+        }
+    }
+}
+
+/// Result of a single check as reported by its implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckResult {
+    pub status: HealthStatus,
+    pub detail: Option<String>,
+}
+
+impl HealthCheckResult {
+    #[must_use]
+    pub fn healthy() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            detail: None,
+        }
+    }
+
+    #[must_use]
+    pub fn degraded(detail: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Degraded,
+            detail: Some(detail.into()),
+        }
+    }
+
+    #[must_use]
+    pub fn unhealthy(detail: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Unhealthy,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+/// One check in a [`HealthReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub name: &'static str,
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Checks of one module; its status is the worst of its checks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleHealth {
+    pub module: String,
+    pub status: HealthStatus,
+    pub checks: Vec<CheckReport>,
+}
+
+/// Aggregated answer to a probe.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub probe: HealthProbe,
+    pub phase: RuntimePhase,
+    pub status: HealthStatus,
+    pub modules: Vec<ModuleHealth>,
+}
+
+struct RegisteredCheck {
+    module: String,
+    name: &'static str,
+    check: Arc<dyn HealthCheckCapability>,
+}
+
+/// Collects health checks of all modules in the process and runs them per probe.
+///
+/// Checks run concurrently, each bounded by the timeout passed to [`Self::check`].
+/// Readiness additionally fails while the runtime is not in [`RuntimePhase::Serving`].
+pub struct HealthAggregator {
+    checks: RwLock<Vec<RegisteredCheck>>,
+    phase: Arc<AtomicU8>,
+}
+
+impl Default for HealthAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthAggregator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            checks: RwLock::new(Vec::new()),
+            phase: Arc::new(AtomicU8::new(RuntimePhase::Starting as u8)),
+        }
+    }
+
+    /// Register a named check for a module.
+    pub fn register(
+        &self,
+        module: impl Into<String>,
+        name: &'static str,
+        check: Arc<dyn HealthCheckCapability>,
+    ) {
+        self.checks.write().push(RegisteredCheck {
+            module: module.into(),
+            name,
+            check,
+        });
+    }
+
+    /// Report the lifecycle status of a stateful module.
+    ///
+    /// Readiness requires the module to be running. Liveness fails only when the
+    /// module stopped on its own while the runtime is serving.
+    pub fn register_lifecycle(
+        &self,
+        module: impl Into<String>,
+        runnable: Arc<dyn RunnableCapability>,
+    ) {
+        let check = LifecycleCheck {
+            runnable,
+            phase: Arc::clone(&self.phase),
+        };
+        self.register(module, "lifecycle", Arc::new(check));
+    }
+
+    /// Ping the module's database on readiness.
+    #[cfg(feature = "db")]
+    pub fn register_database(&self, module: impl Into<String>, db: modkit_db::Db) {
+        self.register(module, "database", Arc::new(DatabaseCheck(db)));
+    }
+
+    /// Require at least one serving instance of an out-of-process module on readiness.
+    pub fn register_instances(&self, module: impl Into<String>, manager: Arc<ModuleManager>) {
+        let module = module.into();
+        let check = InstancesCheck {
+            module: module.clone(),
+            manager,
+        };
+        self.register(module, "instances", Arc::new(check));
+    }
+
+    pub fn set_phase(&self, phase: RuntimePhase) {
+        self.phase.store(phase as u8, Ordering::Release);
+    }
+
+    #[must_use]
+    pub fn phase(&self) -> RuntimePhase {
+        RuntimePhase::from_u8(self.phase.load(Ordering::Acquire))
+    }
+
+    /// Run every registered check for `probe` and aggregate the results per module.
+    pub async fn check(&self, probe: HealthProbe, timeout: Duration) -> HealthReport {
+        // Collected so the registry lock is released before the checks are awaited.
+        #[allow(clippy::needless_collect)]
+        let checks: Vec<(String, &'static str, Arc<dyn HealthCheckCapability>)> = self
+            .checks
+            .read()
+            .iter()
+            .map(|c| (c.module.clone(), c.name, Arc::clone(&c.check)))
+            .collect();
+
+        let results = join_all(checks.into_iter().map(|(module, name, check)| async move {
+            let started = Instant::now();
+            let run = async {
+                match probe {
+                    HealthProbe::Liveness => check.liveness().await,
+                    HealthProbe::Readiness => check.readiness().await,
+                }
+            };
+            let result = tokio::time::timeout(timeout, run)
+                .await
+                .unwrap_or_else(|_| {
+                    HealthCheckResult::unhealthy(format!(
+                        "timed out after {}ms",
+                        timeout.as_millis()
+                    ))
+                });
+            let report = CheckReport {
+                name,
+                status: result.status,
+                detail: result.detail,
+                duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            };
+            (module, report)
+        }))
+        .await;
+
+        let mut modules: Vec<ModuleHealth> = Vec::new();
+        for (module, report) in results {
+            match modules.iter_mut().find(|m| m.module == module) {
+                Some(entry) => {
+                    entry.status = entry.status.max(report.status);
+                    entry.checks.push(report);
+                }
+                None => modules.push(ModuleHealth {
+                    module,
+                    status: report.status,
+                    checks: vec![report],
+                }),
+            }
+        }
+
+        let phase = self.phase();
+        let status = if probe == HealthProbe::Readiness && phase != RuntimePhase::Serving {
+            HealthStatus::Unhealthy
+        } else {
+            modules
+                .iter()
+                .map(|m| m.status)
+                .max()
+                .unwrap_or(HealthStatus::Healthy)
+        };
+
+        HealthReport {
+            probe,
+            phase,
+            status,
+            modules,
+        }
+    }
+}
+
+struct LifecycleCheck {
+    runnable: Arc<dyn RunnableCapability>,
+    phase: Arc<AtomicU8>,
+}
+
+#[async_trait]
+impl HealthCheckCapability for LifecycleCheck {
+    async fn readiness(&self) -> HealthCheckResult {
+        match self.runnable.status() {
+            Some(Status::Running) | None => HealthCheckResult::healthy(),
+            Some(status) => HealthCheckResult::unhealthy(format!("module is {status:?}")),
+        }
+    }
+
+    async fn liveness(&self) -> HealthCheckResult {
+        let serving =
+            RuntimePhase::from_u8(self.phase.load(Ordering::Acquire)) == RuntimePhase::Serving;
+        if serving && self.runnable.status() == Some(Status::Stopped) {
+            return HealthCheckResult::unhealthy("module stopped while the runtime is serving");
+        }
+        HealthCheckResult::healthy()
+    }
+}
+
+#[cfg(feature = "db")]
+struct DatabaseCheck(modkit_db::Db);
+
+#[cfg(feature = "db")]
+#[async_trait]
+impl HealthCheckCapability for DatabaseCheck {
+    async fn readiness(&self) -> HealthCheckResult {
+        match self.0.ping().await {
+            Ok(()) => HealthCheckResult::healthy(),
+            Err(e) => HealthCheckResult::unhealthy(e.to_string()),
+        }
+    }
+}
+
+struct InstancesCheck {
+    module: String,
+    manager: Arc<ModuleManager>,
+}
+
+#[async_trait]
+impl HealthCheckCapability for InstancesCheck {
+    async fn readiness(&self) -> HealthCheckResult {
+        let instances = self.manager.instances_of(&self.module);
+        let serving = instances
+            .iter()
+            .filter(|i| matches!(i.state(), InstanceState::Ready | InstanceState::Healthy))
+            .count();
+        let quarantined = instances
+            .iter()
+            .filter(|i| i.state() == InstanceState::Quarantined)
+            .count();
+
+        if serving == 0 {
+            HealthCheckResult::unhealthy(format!(
+                "no serving instance ({} registered)",
+                instances.len()
+            ))
+        } else if quarantined > 0 {
+            HealthCheckResult::degraded(format!("{serving} serving, {quarantined} quarantined"))
+        } else {
+            HealthCheckResult::healthy()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::runtime::ModuleInstance;
+    use tokio_util::sync::CancellationToken;
+    use uuid::Uuid;
+
+    struct Fixed(HealthCheckResult);
+
+    #[async_trait]
+    impl HealthCheckCapability for Fixed {
+        async fn readiness(&self) -> HealthCheckResult {
+            self.0.clone()
+        }
+    }
+
+    struct Hanging;
+
+    #[async_trait]
+    impl HealthCheckCapability for Hanging {
+        async fn readiness(&self) -> HealthCheckResult {
+            std::future::pending().await
+        }
+    }
+
+    struct FixedStatus(Status);
+
+    #[async_trait]
+    impl RunnableCapability for FixedStatus {
+        async fn start(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+            Ok(())
+        }
+        async fn stop(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn status(&self) -> Option<Status> {
+            Some(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn readiness_reports_worst_status_per_module() {
+        let agg = HealthAggregator::new();
+        agg.set_phase(RuntimePhase::Serving);
+        agg.register("a", "one", Arc::new(Fixed(HealthCheckResult::healthy())));
+        agg.register(
+            "a",
+            "two",
+            Arc::new(Fixed(HealthCheckResult::degraded("slow"))),
+        );
+        agg.register("b", "one", Arc::new(Fixed(HealthCheckResult::healthy())));
+
+        let report = agg
+            .check(HealthProbe::Readiness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.status.is_passing());
+        assert_eq!(report.modules.len(), 2);
+        assert_eq!(report.modules[0].module, "a");
+        assert_eq!(report.modules[0].status, HealthStatus::Degraded);
+        assert_eq!(report.modules[0].checks.len(), 2);
+        assert_eq!(report.modules[1].status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn readiness_fails_outside_serving_phase() {
+        let agg = HealthAggregator::new();
+        let report = agg
+            .check(HealthProbe::Readiness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+        assert_eq!(report.phase, RuntimePhase::Starting);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+
+        let report = agg
+            .check(HealthProbe::Liveness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+        assert_eq!(report.status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn hanging_check_times_out_as_unhealthy() {
+        let agg = HealthAggregator::new();
+        agg.set_phase(RuntimePhase::Serving);
+        agg.register("a", "stuck", Arc::new(Hanging));
+
+        let report = agg
+            .check(HealthProbe::Readiness, Duration::from_millis(20))
+            .await;
+
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        let check = &report.modules[0].checks[0];
+        assert_eq!(check.name, "stuck");
+        assert!(check.detail.as_deref().unwrap().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn stopped_module_fails_liveness_only_while_serving() {
+        let agg = HealthAggregator::new();
+        agg.register_lifecycle("a", Arc::new(FixedStatus(Status::Stopped)));
+
+        let report = agg
+            .check(HealthProbe::Liveness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        agg.set_phase(RuntimePhase::Serving);
+        let report = agg
+            .check(HealthProbe::Liveness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+
+        agg.set_phase(RuntimePhase::Stopping);
+        let report = agg
+            .check(HealthProbe::Liveness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+        assert_eq!(report.status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn starting_module_is_not_ready() {
+        let agg = HealthAggregator::new();
+        agg.set_phase(RuntimePhase::Serving);
+        agg.register_lifecycle("a", Arc::new(FixedStatus(Status::Starting)));
+        agg.register_lifecycle("b", Arc::new(FixedStatus(Status::Running)));
+
+        let report = agg
+            .check(HealthProbe::Readiness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+
+        assert_eq!(report.modules[0].status, HealthStatus::Unhealthy);
+        assert_eq!(report.modules[1].status, HealthStatus::Healthy);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+    }
+
+    async fn readiness(agg: &HealthAggregator) -> HealthStatus {
+        agg.check(HealthProbe::Readiness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await
+            .status
+    }
+
+    #[tokio::test]
+    async fn oop_readiness_follows_instance_states() {
+        let manager = Arc::new(ModuleManager::new());
+        let agg = HealthAggregator::new();
+        agg.set_phase(RuntimePhase::Serving);
+        agg.register_instances("calc", Arc::clone(&manager));
+
+        assert_eq!(readiness(&agg).await, HealthStatus::Unhealthy);
+
+        let first = Uuid::new_v4();
+        manager.register_instance(Arc::new(ModuleInstance::new("calc", first)));
+        assert_eq!(readiness(&agg).await, HealthStatus::Unhealthy);
+
+        manager.update_heartbeat("calc", first, std::time::Instant::now());
+        assert_eq!(readiness(&agg).await, HealthStatus::Healthy);
+
+        let second = Uuid::new_v4();
+        manager.register_instance(Arc::new(ModuleInstance::new("calc", second)));
+        manager.mark_quarantined("calc", second);
+        assert_eq!(readiness(&agg).await, HealthStatus::Degraded);
+    }
+
+    #[cfg(feature = "db")]
+    #[tokio::test]
+    async fn database_check_pings_connection() {
+        let db = modkit_db::connect_db("sqlite::memory:", modkit_db::ConnectOpts::default())
+            .await
+            .unwrap();
+        let agg = HealthAggregator::new();
+        agg.set_phase(RuntimePhase::Serving);
+        agg.register_database("a", db);
+
+        let report = agg
+            .check(HealthProbe::Readiness, DEFAULT_HEALTH_CHECK_TIMEOUT)
+            .await;
+
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.modules[0].checks[0].name, "database");
+    }
+}
@@ -116,7 +116,13 @@ pub use http::sse::SseBroadcaster;
 // Telemetry utilities
 pub mod telemetry;
 
+#[cfg(feature = "db")]
+pub mod audit;
 pub mod backends;
+pub mod config_reload;
+#[cfg(feature = "db")]
+pub mod events;
+pub mod health;
 pub mod lifecycle;
 pub mod plugins;
 pub mod runtime;
@@ -145,10 +151,16 @@ pub mod gts;
 // Security context scoping wrapper (re-exported from modkit-sdk)
 pub use modkit_sdk::{Secured, WithSecurityContext};
 
+#[cfg(feature = "db")]
+pub use audit::{AuditRetention, AuditRetentionConfig};
 pub use backends::{
     BackendKind, InstanceHandle, LocalProcessBackend, ModuleRuntimeBackend, OopBackend,
-    OopModuleConfig, OopSpawnConfig,
+    OopModuleConfig, OopSpawnConfig, RestartConfig, RestartPolicy,
 };
+pub use config_reload::{ConfigReloader, ReloadReport};
+#[cfg(feature = "db")]
+pub use events::{DomainEvent, EventBus, EventEnvelope, EventSubscriber, RelayConfig};
+pub use health::{HealthAggregator, HealthCheckResult, HealthProbe, HealthReport, HealthStatus};
 pub use lifecycle::{Lifecycle, Runnable, Status, StopReason, WithLifecycle};
 pub use plugins::GtsPluginSelector;
 pub use runtime::{
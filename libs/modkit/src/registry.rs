@@ -25,6 +25,8 @@ pub enum Capability {
     System(Arc<dyn contracts::SystemCapability>),
     GrpcHub(Arc<dyn contracts::GrpcHubCapability>),
     GrpcService(Arc<dyn contracts::GrpcServiceCapability>),
+    HealthCheck(Arc<dyn contracts::HealthCheckCapability>),
+    ConfigReload(Arc<dyn contracts::ConfigReloadCapability>),
 }
 
 impl std::fmt::Debug for Capability {
@@ -38,6 +40,10 @@ impl std::fmt::Debug for Capability {
             Capability::System(_) => write!(f, "System(<impl SystemCapability>)"),
             Capability::GrpcHub(_) => write!(f, "GrpcHub(<impl GrpcHubCapability>)"),
             Capability::GrpcService(_) => write!(f, "GrpcService(<impl GrpcServiceCapability>)"),
+            Capability::HealthCheck(_) => write!(f, "HealthCheck(<impl HealthCheckCapability>)"),
+            Capability::ConfigReload(_) => {
+                write!(f, "ConfigReload(<impl ConfigReloadCapability>)")
+            }
         }
     }
 }
@@ -134,6 +140,30 @@ impl CapTag for GrpcServiceCap {
     }
 }
 
+/// Tag for querying `HealthCheckCapability`.
+pub struct HealthCheckCap;
+impl CapTag for HealthCheckCap {
+    type Out = dyn contracts::HealthCheckCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::HealthCheck(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Tag for querying `ConfigReloadCapability`.
+pub struct ConfigReloadCap;
+impl CapTag for ConfigReloadCap {
+    type Out = dyn contracts::ConfigReloadCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::ConfigReload(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 /// A set of capabilities that a module provides.
 #[derive(Clone)]
 pub struct CapabilitySet {
@@ -211,6 +241,8 @@ impl std::fmt::Debug for ModuleEntry {
             .field("is_system", &self.caps.has::<SystemCap>())
             .field("is_grpc_hub", &self.caps.has::<GrpcHubCap>())
             .field("has_grpc_service", &self.caps.has::<GrpcServiceCap>())
+            .field("has_health_check", &self.caps.has::<HealthCheckCap>())
+            .field("has_config_reload", &self.caps.has::<ConfigReloadCap>())
             .finish_non_exhaustive()
     }
 }
@@ -409,6 +441,28 @@ impl RegistryBuilder {
             .push(Capability::GrpcService(m));
     }
 
+    pub fn register_health_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::HealthCheckCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::HealthCheck(m));
+    }
+
+    pub fn register_config_reload_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::ConfigReloadCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::ConfigReload(m));
+    }
+
     /// Detect cycles in the dependency graph using DFS with path tracking.
     /// Returns the cycle path if found, None otherwise.
     fn detect_cycle_with_path(
@@ -6,8 +6,9 @@
 //! High-level phase order:
 //! - `pre_init` (system modules only)
 //! - DB migrations (modules with DB capability)
-//! - `init` (all modules)
+//! - `init` (all modules; each is then attached to the config reloader)
 //! - `post_init` (system modules only; runs after *all* `init` complete)
+//! - health wiring (module checks plus built-in DB, lifecycle and `OoP` checks)
 //! - REST wiring (modules with REST capability; requires a single REST host)
 //! - gRPC registration (modules with gRPC capability; requires a single gRPC hub)
 //! - start/stop (stateful modules)
@@ -22,10 +23,12 @@ use uuid::Uuid;
 use crate::backends::OopSpawnConfig;
 use crate::client_hub::ClientHub;
 use crate::config::ConfigProvider;
+use crate::config_reload::ConfigReloader;
 use crate::context::ModuleContextBuilder;
+use crate::health::{HealthAggregator, RuntimePhase};
 use crate::registry::{
-    ApiGatewayCap, GrpcHubCap, ModuleEntry, ModuleRegistry, RegistryError, RestApiCap, RunnableCap,
-    SystemCap,
+    ApiGatewayCap, ConfigReloadCap, GrpcHubCap, HealthCheckCap, ModuleEntry, ModuleRegistry,
+    RegistryError, RestApiCap, RunnableCap, SystemCap,
 };
 use crate::runtime::{GrpcInstallerStore, ModuleManager, OopSpawnOptions, SystemContext};
 
@@ -51,12 +54,51 @@ pub enum RunMode {
     MigrateOnly,
 }
 
+/// What a migration-only run does for each module with DB capability.
+#[cfg(feature = "db")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationCommand {
+    /// Apply pending migrations.
+    Apply,
+    /// Render the statements pending migrations would execute, without executing them.
+    Plan,
+    /// Report applied and pending migrations, including drifted ones.
+    Status,
+    /// Revert one module's migrations applied after `to`, or only render them.
+    Down {
+        module: String,
+        to: String,
+        dry_run: bool,
+    },
+}
+
+/// Outcome of a [`MigrationCommand`] for one module.
+#[cfg(feature = "db")]
+#[derive(Debug, Clone)]
+pub enum MigrationOutcome {
+    Applied(modkit_db::migration_runner::MigrationResult),
+    Planned(Vec<modkit_db::migration_runner::MigrationPlan>),
+    Status(Vec<modkit_db::migration_runner::MigrationStatus>),
+    Reverted(Vec<String>),
+}
+
+/// Per-module report returned by [`HostRuntime::run_migration_command`].
+#[cfg(feature = "db")]
+#[derive(Debug, Clone)]
+pub struct ModuleMigrationReport {
+    pub module: &'static str,
+    pub outcome: MigrationOutcome,
+}
+
 /// Environment variable name for passing directory endpoint to `OoP` modules.
 pub const MODKIT_DIRECTORY_ENDPOINT_ENV: &str = "MODKIT_DIRECTORY_ENDPOINT";
 
 /// Environment variable name for passing rendered module config to `OoP` modules.
 pub const MODKIT_MODULE_CONFIG_ENV: &str = "MODKIT_MODULE_CONFIG";
 
+/// Environment variable name for passing the instance ID an `OoP` module registers under.
+pub const MODKIT_INSTANCE_ID_ENV: &str = "MODKIT_INSTANCE_ID";
+
 /// `HostRuntime` owns the lifecycle orchestration for `ModKit`.
 ///
 /// It encapsulates all runtime state and drives modules through the full lifecycle (see module docs).
@@ -66,6 +108,8 @@ pub struct HostRuntime {
     instance_id: Uuid,
     module_manager: Arc<ModuleManager>,
     grpc_installers: Arc<GrpcInstallerStore>,
+    health: Arc<HealthAggregator>,
+    config_reload: Arc<ConfigReloader>,
     #[allow(dead_code)]
     client_hub: Arc<ClientHub>,
     cancel: CancellationToken,
@@ -92,6 +136,17 @@ impl HostRuntime {
         let module_manager = Arc::new(ModuleManager::new());
         let grpc_installers = Arc::new(GrpcInstallerStore::new());
 
+        // Published up front so the REST host can resolve it during init
+        let health = Arc::new(HealthAggregator::new());
+        client_hub.register::<HealthAggregator>(Arc::clone(&health));
+
+        // Reuse the host's reloader if it pre-registered one, so it can drive reloads
+        let config_reload = client_hub.get::<ConfigReloader>().unwrap_or_else(|_| {
+            let reloader = Arc::new(ConfigReloader::new());
+            client_hub.register::<ConfigReloader>(Arc::clone(&reloader));
+            reloader
+        });
+
         // Build the context builder that will resolve per-module DbHandles
         let db_manager = match &db_options {
             #[cfg(feature = "db")]
@@ -113,6 +168,8 @@ impl HostRuntime {
             instance_id,
             module_manager,
             grpc_installers,
+            health,
+            config_reload,
             client_hub,
             cancel,
             db_options,
@@ -203,7 +260,7 @@ impl HostRuntime {
         Ok(db.map(|db| (db, dbm)))
     }
 
-    /// Helper: run migrations for a single module using the new migration runner.
+    /// Helper: run a migration command for a single module using the migration runner.
     ///
     /// This collects migrations from the module and executes them via the
     /// runtime's privileged connection. Modules never see the raw connection.
@@ -212,38 +269,74 @@ impl HostRuntime {
         module_name: &'static str,
         db: &modkit_db::Db,
         db_module: Arc<dyn crate::contracts::DatabaseCapability>,
-    ) -> Result<(), RegistryError> {
+        command: &MigrationCommand,
+    ) -> Result<MigrationOutcome, RegistryError> {
+        use modkit_db::migration_runner as runner;
+
+        let to_registry_error = |e: runner::MigrationError| RegistryError::DbMigrate {
+            module: module_name,
+            source: anyhow::Error::new(e),
+        };
+
         // Collect migrations from the module
         let migrations = db_module.migrations();
 
-        if migrations.is_empty() {
-            tracing::debug!(module = module_name, "No migrations to run");
-            return Ok(());
-        }
+        let outcome = match command {
+            MigrationCommand::Apply => {
+                if migrations.is_empty() {
+                    tracing::debug!(module = module_name, "No migrations to run");
+                    return Ok(MigrationOutcome::Applied(runner::MigrationResult {
+                        applied: 0,
+                        skipped: 0,
+                        applied_names: vec![],
+                    }));
+                }
 
-        tracing::debug!(
-            module = module_name,
-            count = migrations.len(),
-            "Running DB migrations"
-        );
+                tracing::debug!(
+                    module = module_name,
+                    count = migrations.len(),
+                    "Running DB migrations"
+                );
 
-        // Execute migrations using the migration runner
-        let result =
-            modkit_db::migration_runner::run_migrations_for_module(db, module_name, migrations)
-                .await
-                .map_err(|e| RegistryError::DbMigrate {
-                    module: module_name,
-                    source: anyhow::Error::new(e),
-                })?;
+                let result = runner::run_migrations_for_module(db, module_name, migrations)
+                    .await
+                    .map_err(to_registry_error)?;
 
-        tracing::info!(
-            module = module_name,
-            applied = result.applied,
-            skipped = result.skipped,
-            "DB migrations completed"
-        );
+                tracing::info!(
+                    module = module_name,
+                    applied = result.applied,
+                    skipped = result.skipped,
+                    "DB migrations completed"
+                );
+                MigrationOutcome::Applied(result)
+            }
+            MigrationCommand::Plan => MigrationOutcome::Planned(
+                runner::plan_migrations_for_module(db, module_name, &migrations)
+                    .await
+                    .map_err(to_registry_error)?,
+            ),
+            MigrationCommand::Status => MigrationOutcome::Status(
+                runner::migration_status(db, module_name, &migrations)
+                    .await
+                    .map_err(to_registry_error)?,
+            ),
+            MigrationCommand::Down {
+                to, dry_run: true, ..
+            } => MigrationOutcome::Planned(
+                runner::plan_rollback_for_module(db, module_name, &migrations, to)
+                    .await
+                    .map_err(to_registry_error)?,
+            ),
+            MigrationCommand::Down {
+                to, dry_run: false, ..
+            } => MigrationOutcome::Reverted(
+                runner::rollback_migrations_for_module(db, module_name, migrations, to)
+                    .await
+                    .map_err(to_registry_error)?,
+            ),
+        };
 
-        Ok(())
+        Ok(outcome)
     }
 
     /// DB MIGRATION phase: run migrations for all modules with DB capability.
@@ -256,8 +349,21 @@ impl HostRuntime {
     /// table, preventing cross-module interference.
     #[cfg(feature = "db")]
     async fn run_db_phase(&self) -> Result<(), RegistryError> {
+        self.run_db_command(&MigrationCommand::Apply)
+            .await
+            .map(drop)
+    }
+
+    /// Run a migration command for every module with DB capability (or the one
+    /// module a `Down` command names), system modules first.
+    #[cfg(feature = "db")]
+    async fn run_db_command(
+        &self,
+        command: &MigrationCommand,
+    ) -> Result<Vec<ModuleMigrationReport>, RegistryError> {
         tracing::info!("Phase: db (before init)");
 
+        let mut reports = Vec::new();
         for entry in self.registry.modules_by_system_priority() {
             // Check for cancellation before processing each module
             if self.cancel.is_cancelled() {
@@ -265,6 +371,12 @@ impl HostRuntime {
                 return Err(RegistryError::Cancelled);
             }
 
+            if let MigrationCommand::Down { module, .. } = command
+                && module != entry.name
+            {
+                continue;
+            }
+
             let ctx = self.module_context(entry.name).await?;
             let db_module = entry.caps.query::<DatabaseCap>();
 
@@ -273,7 +385,11 @@ impl HostRuntime {
                 .await?
             {
                 Some((db, dbm)) => {
-                    Self::migrate_module(entry.name, &db, dbm).await?;
+                    let outcome = Self::migrate_module(entry.name, &db, dbm, command).await?;
+                    reports.push(ModuleMigrationReport {
+                        module: entry.name,
+                        outcome,
+                    });
                 }
                 None if db_module.is_some() => {
                     tracing::debug!(
@@ -285,12 +401,13 @@ impl HostRuntime {
             }
         }
 
-        Ok(())
+        Ok(reports)
     }
 
     /// INIT phase: initialize all modules in topological order.
     ///
-    /// System modules initialize first, followed by user modules.
+    /// System modules initialize first, followed by user modules. Each initialized
+    /// module is attached to the config reloader with the context it was given.
     async fn run_init_phase(&self) -> Result<(), RegistryError> {
         tracing::info!("Phase: init");
 
@@ -311,6 +428,9 @@ impl HostRuntime {
                     module: entry.name,
                     source: e,
                 })?;
+            self.config_reload
+                .attach(entry.name, ctx, entry.caps.query::<ConfigReloadCap>())
+                .await;
         }
 
         Ok(())
@@ -346,6 +466,66 @@ impl HostRuntime {
         Ok(())
     }
 
+    /// HEALTH phase: collect module health checks into the aggregator.
+    ///
+    /// Besides modules with the health capability, this covers the database of every
+    /// DB module, the lifecycle status of stateful modules and the instances of
+    /// configured `OoP` modules. With `otel`, the same lifecycle states and pools are
+    /// exported as `modkit.module.state` and `db.client.connection.*` gauges.
+    #[cfg_attr(not(feature = "db"), allow(clippy::unused_async))]
+    async fn run_health_phase(&self) {
+        tracing::info!("Phase: health");
+
+        #[cfg(feature = "otel")]
+        let mut lifecycle_gauges = Vec::new();
+        #[cfg(all(feature = "otel", feature = "db"))]
+        let mut pool_gauges = Vec::new();
+
+        for entry in self.registry.modules() {
+            if let Some(check) = entry.caps.query::<HealthCheckCap>() {
+                self.health.register(entry.name, "module", check);
+            }
+            if let Some(runnable) = entry.caps.query::<RunnableCap>()
+                && runnable.status().is_some()
+            {
+                #[cfg(feature = "otel")]
+                lifecycle_gauges.push((entry.name, Arc::clone(&runnable)));
+                self.health.register_lifecycle(entry.name, runnable);
+            }
+            #[cfg(feature = "db")]
+            if entry.caps.has_db()
+                && let DbOptions::Manager(mgr) = &self.db_options
+            {
+                match mgr.get(entry.name).await {
+                    Ok(Some(db)) => {
+                        #[cfg(feature = "otel")]
+                        pool_gauges.push((entry.name, db.clone()));
+                        self.health.register_database(entry.name, db);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(module = entry.name, error = %e, "No database health check");
+                    }
+                }
+            }
+        }
+
+        // Runtime gauges share the health phase's view of stateful modules and pools
+        #[cfg(feature = "otel")]
+        crate::telemetry::metrics::observe_lifecycle_states(lifecycle_gauges);
+        #[cfg(all(feature = "otel", feature = "db"))]
+        crate::telemetry::metrics::observe_db_pools(pool_gauges);
+
+        if let Some(oop_opts) = &self.oop_options {
+            for module_cfg in &oop_opts.modules {
+                self.health.register_instances(
+                    module_cfg.module_name.clone(),
+                    Arc::clone(&self.module_manager),
+                );
+            }
+        }
+    }
+
     /// REST phase: compose the router against the REST host.
     ///
     /// This is a synchronous phase that builds the final Router by:
@@ -584,6 +764,11 @@ impl HostRuntime {
         // Wait for grpc_hub to publish its endpoint (it runs async in start phase)
         let directory_endpoint = self.wait_for_grpc_hub_endpoint().await;
 
+        // Let the backend follow restarted instances through the directory
+        oop_opts
+            .backend
+            .bind_module_manager(Arc::clone(&self.module_manager));
+
         for module_cfg in &oop_opts.modules {
             // Build environment with directory endpoint and rendered config
             // Note: User controls --config via execution.args in master config
@@ -605,6 +790,7 @@ impl HostRuntime {
                 args,
                 env,
                 working_directory: module_cfg.working_directory.clone(),
+                restart: module_cfg.restart.clone(),
             };
 
             oop_opts
@@ -691,6 +877,38 @@ impl HostRuntime {
         self.run_phases_internal(RunMode::MigrateOnly).await
     }
 
+    /// Run pre-init, then a migration command for the modules with DB capability.
+    ///
+    /// `MigrationCommand::Apply` is equivalent to [`Self::run_migration_phases`]; the other
+    /// commands only read the migration history, except `Down` which reverts migrations
+    /// of a single module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pre-init or the command fails for any module, or if a `Down`
+    /// command names a module without a database.
+    #[cfg(feature = "db")]
+    pub async fn run_migration_command(
+        self,
+        command: &MigrationCommand,
+    ) -> anyhow::Result<Vec<ModuleMigrationReport>> {
+        tracing::info!(
+            ?command,
+            "Running migration command (pre-init + db phases only)"
+        );
+
+        self.run_pre_init_phase()?;
+        let reports = self.run_db_command(command).await?;
+
+        if let MigrationCommand::Down { module, .. } = command
+            && reports.is_empty()
+        {
+            anyhow::bail!("module '{module}' is not registered or has no database");
+        }
+
+        Ok(reports)
+    }
+
     /// Internal implementation that runs module phases based on the mode.
     ///
     /// This private method contains the actual phase execution logic and is called
@@ -707,12 +925,13 @@ impl HostRuntime {
     /// 2. DB migration (all modules with database capability)
     /// 3. Init (all modules)
     /// 4. Post-init (system modules only)
-    /// 5. REST (modules with REST capability)
-    /// 6. gRPC (modules with gRPC capability)
-    /// 7. Start (runnable modules)
-    /// 8. `OoP` spawn (out-of-process modules)
-    /// 9. Wait for cancellation
-    /// 10. Stop (runnable modules in reverse order)
+    /// 5. Health wiring (all modules)
+    /// 6. REST (modules with REST capability)
+    /// 7. gRPC (modules with gRPC capability)
+    /// 8. Start (runnable modules)
+    /// 9. `OoP` spawn (out-of-process modules)
+    /// 10. Wait for cancellation
+    /// 11. Stop (runnable modules in reverse order)
     async fn run_phases_internal(self, mode: RunMode) -> anyhow::Result<()> {
         // Log execution mode
         match mode {
@@ -749,22 +968,27 @@ impl HostRuntime {
         // 4. Post-init phase (barrier after ALL init; system modules only)
         self.run_post_init_phase().await?;
 
-        // 5. REST phase (synchronous router composition)
+        // 5. Health wiring phase
+        self.run_health_phase().await;
+
+        // 6. REST phase (synchronous router composition)
         let _router = self.run_rest_phase().await?;
 
-        // 6. gRPC registration phase
+        // 7. gRPC registration phase
         self.run_grpc_phase().await?;
 
-        // 7. Start phase
+        // 8. Start phase
         self.run_start_phase().await?;
 
-        // 8. OoP spawn phase (after grpc_hub is running)
+        // 9. OoP spawn phase (after grpc_hub is running)
         self.run_oop_spawn_phase().await?;
+        self.health.set_phase(RuntimePhase::Serving);
 
-        // 9. Wait for cancellation
+        // 10. Wait for cancellation
         self.cancel.cancelled().await;
+        self.health.set_phase(RuntimePhase::Stopping);
 
-        // 10. Stop phase
+        // 11. Stop phase
         self.run_stop_phase().await?;
 
         Ok(())
@@ -1,5 +1,6 @@
 //! Module Manager - tracks and manages all live module instances in the runtime
 
+use cf_system_sdks::directory::InstanceEvent;
 use dashmap::DashMap;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -159,6 +160,8 @@ impl ModuleInstance {
 pub struct ModuleManager {
     inner: DashMap<String, Vec<Arc<ModuleInstance>>>,
     rr_counters: DashMap<String, usize>,
+    /// Supervision history published by the `OoP` backend, keyed by instance
+    history: DashMap<Uuid, Vec<InstanceEvent>>,
     hb_ttl: Duration,
     hb_grace: Duration,
 }
@@ -180,6 +183,7 @@ impl ModuleManager {
         Self {
             inner: DashMap::new(),
             rr_counters: DashMap::new(),
+            history: DashMap::new(),
             hb_ttl: Duration::from_secs(15),
             hb_grace: Duration::from_secs(30),
         }
@@ -265,6 +269,35 @@ impl ModuleManager {
             self.inner.remove(module);
             self.rr_counters.remove(module);
         }
+        self.history.remove(&instance_id);
+    }
+
+    /// Whether an instance is registered and accepting traffic (ready or healthy)
+    #[must_use]
+    pub fn is_serving(&self, module: &str, instance_id: Uuid) -> bool {
+        self.inner.get(module).is_some_and(|vec| {
+            vec.iter().any(|i| {
+                i.instance_id == instance_id
+                    && matches!(i.state(), InstanceState::Healthy | InstanceState::Ready)
+            })
+        })
+    }
+
+    /// Replace the supervision history of an instance.
+    ///
+    /// The history may be set before the instance registers itself; it is
+    /// dropped when the instance is deregistered.
+    pub fn set_instance_history(&self, instance_id: Uuid, history: Vec<InstanceEvent>) {
+        self.history.insert(instance_id, history);
+    }
+
+    /// Supervision history of an instance, oldest first
+    #[must_use]
+    pub fn instance_history(&self, instance_id: Uuid) -> Vec<InstanceEvent> {
+        self.history
+            .get(&instance_id)
+            .map(|h| h.clone())
+            .unwrap_or_default()
     }
 
     /// Get all instances of a specific module
@@ -306,6 +339,7 @@ impl ModuleManager {
 
                 // Evict quarantined instances that exceed grace period
                 if state.state == Quarantined && age >= self.hb_ttl + self.hb_grace {
+                    self.history.remove(&inst.instance_id);
                     return false; // Remove from directory
                 }
 
@@ -731,4 +765,32 @@ mod tests {
         // Endpoints should differ
         assert_ne!(ep1, ep2);
     }
+
+    #[test]
+    fn test_is_serving_and_history() {
+        use cf_system_sdks::directory::InstanceEventKind;
+
+        let dir = ModuleManager::new();
+        let id = Uuid::new_v4();
+        dir.set_instance_history(
+            id,
+            vec![InstanceEvent {
+                instance_id: id.to_string(),
+                kind: InstanceEventKind::Started,
+                at: std::time::SystemTime::now(),
+                exit_code: None,
+            }],
+        );
+        assert!(!dir.is_serving("test_module", id));
+
+        dir.register_instance(Arc::new(ModuleInstance::new("test_module", id)));
+        assert!(!dir.is_serving("test_module", id));
+        dir.update_heartbeat("test_module", id, Instant::now());
+        assert!(dir.is_serving("test_module", id));
+        assert_eq!(dir.instance_history(id).len(), 1);
+
+        dir.deregister("test_module", id);
+        assert!(!dir.is_serving("test_module", id));
+        assert!(dir.instance_history(id).is_empty());
+    }
 }
@@ -14,7 +14,7 @@
 //! - `OoP` modules are spawned after the start phase so that `grpc-hub` is already running
 //!   and the real directory endpoint is known.
 
-use crate::backends::OopBackend;
+use crate::backends::{OopBackend, RestartConfig};
 use crate::client_hub::ClientHub;
 use crate::config::ConfigProvider;
 use crate::registry::ModuleRegistry;
@@ -85,6 +85,8 @@ pub struct OopModuleSpawnConfig {
     pub working_directory: Option<String>,
     /// Rendered module config JSON (for `MODKIT_MODULE_CONFIG` env var)
     pub rendered_config_json: String,
+    /// Whether and how the backend restarts the process when it exits
+    pub restart: RestartConfig,
 }
 
 /// Options for spawning `OoP` modules.
@@ -1,6 +1,7 @@
-//! OpenTelemetry tracing configuration types
+//! OpenTelemetry tracing and metrics configuration types
 //!
-//! These types define the configuration structure for OpenTelemetry distributed tracing.
+//! These types define the configuration structure for OpenTelemetry distributed tracing
+//! and the metrics pipeline.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +19,27 @@ pub struct TracingConfig {
     pub logs_correlation: Option<LogsCorrelation>,
 }
 
+/// Metrics configuration for the OpenTelemetry meter provider
+///
+/// Metrics are pushed over OTLP when `exporter` is set and served for scraping
+/// by the api-gateway when `prometheus` is set; both may be enabled at once.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub service_name: Option<String>,
+    pub exporter: Option<Exporter>,
+    /// OTLP push interval (default 60s)
+    pub export_interval_ms: Option<u64>,
+    pub prometheus: Option<PrometheusOpts>,
+    pub resource: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct PrometheusOpts {
+    /// Scrape path on the api-gateway (default `/metrics`)
+    pub path: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum ExporterKind {
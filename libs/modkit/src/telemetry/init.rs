@@ -22,7 +22,7 @@ use opentelemetry_sdk::{
 };
 
 #[cfg(feature = "otel")]
-use super::config::TracingConfig;
+use super::config::{Exporter, TracingConfig};
 #[cfg(feature = "otel")]
 use crate::telemetry::config::ExporterKind;
 #[cfg(feature = "otel")]
@@ -64,10 +64,10 @@ fn build_sampler(cfg: &TracingConfig) -> Sampler {
 
 /// Extract exporter kind and endpoint from configuration
 #[cfg(feature = "otel")]
-fn extract_exporter_config(
-    cfg: &TracingConfig,
+pub(super) fn extract_exporter_config(
+    exporter: Option<&Exporter>,
 ) -> (ExporterKind, String, Option<std::time::Duration>) {
-    let (kind, endpoint) = cfg.exporter.as_ref().map_or_else(
+    let (kind, endpoint) = exporter.map_or_else(
         || (ExporterKind::OtlpGrpc, "http://127.0.0.1:4317".into()),
         |e| {
             (
@@ -79,9 +79,7 @@ fn extract_exporter_config(
         },
     );
 
-    let timeout = cfg
-        .exporter
-        .as_ref()
+    let timeout = exporter
         .and_then(|e| e.timeout_ms)
         .map(std::time::Duration::from_millis);
 
@@ -102,7 +100,7 @@ fn build_http_exporter(
     if let Some(t) = timeout {
         b = b.with_timeout(t);
     }
-    if let Some(hmap) = build_headers_from_cfg_and_env(cfg) {
+    if let Some(hmap) = build_headers_from_cfg_and_env(cfg.exporter.as_ref()) {
         b = b.with_headers(hmap);
     }
     #[allow(clippy::expect_used)]
@@ -122,7 +120,7 @@ fn build_grpc_exporter(
     if let Some(t) = timeout {
         b = b.with_timeout(t);
     }
-    if let Some(md) = build_metadata_from_cfg_and_env(cfg) {
+    if let Some(md) = build_metadata_from_cfg_and_env(cfg.exporter.as_ref()) {
         b = b.with_metadata(md);
     }
     b.build().context("build OTLP gRPC exporter")
@@ -155,7 +153,7 @@ pub fn init_tracing(
     // Build resource, sampler, and extract exporter config
     let resource = build_resource(cfg);
     let sampler = build_sampler(cfg);
-    let (kind, endpoint, timeout) = extract_exporter_config(cfg);
+    let (kind, endpoint, timeout) = extract_exporter_config(cfg.exporter.as_ref());
 
     tracing::info!(kind = ?kind, %endpoint, "OTLP exporter config");
 
@@ -185,16 +183,14 @@ pub fn init_tracing(
 }
 
 #[cfg(feature = "otel")]
-fn build_headers_from_cfg_and_env(
-    cfg: &TracingConfig,
+pub(super) fn build_headers_from_cfg_and_env(
+    exporter: Option<&Exporter>,
 ) -> Option<std::collections::HashMap<String, String>> {
     use std::collections::HashMap;
     let mut out: HashMap<String, String> = HashMap::new();
 
     // From config file
-    if let Some(exp) = &cfg.exporter
-        && let Some(hdrs) = &exp.headers
-    {
+    if let Some(hdrs) = exporter.and_then(|e| e.headers.as_ref()) {
         for (k, v) in hdrs {
             out.insert(k.clone(), v.clone());
         }
@@ -235,13 +231,11 @@ where
 }
 
 #[cfg(feature = "otel")]
-fn build_metadata_from_cfg_and_env(cfg: &TracingConfig) -> Option<MetadataMap> {
+pub(super) fn build_metadata_from_cfg_and_env(exporter: Option<&Exporter>) -> Option<MetadataMap> {
     let mut md = MetadataMap::new();
 
     // From config file
-    if let Some(exp) = &cfg.exporter
-        && let Some(hdrs) = &exp.headers
-    {
+    if let Some(hdrs) = exporter.and_then(|e| e.headers.as_ref()) {
         let iter = hdrs.iter().map(|(k, v)| (k.as_str(), v.as_str()));
         extend_metadata_from_source(&mut md, iter, "config");
     }
@@ -325,7 +319,7 @@ pub fn otel_connectivity_probe(cfg: &super::config::TracingConfig) -> anyhow::Re
             .with_http()
             .with_protocol(Protocol::HttpBinary)
             .with_endpoint(endpoint);
-        if let Some(h) = build_headers_from_cfg_and_env(cfg) {
+        if let Some(h) = build_headers_from_cfg_and_env(cfg.exporter.as_ref()) {
             b = b.with_headers(h);
         }
         b.build()
@@ -334,7 +328,7 @@ pub fn otel_connectivity_probe(cfg: &super::config::TracingConfig) -> anyhow::Re
         let mut b = opentelemetry_otlp::SpanExporter::builder()
             .with_tonic()
             .with_endpoint(endpoint);
-        if let Some(md) = build_metadata_from_cfg_and_env(cfg) {
+        if let Some(md) = build_metadata_from_cfg_and_env(cfg.exporter.as_ref()) {
             b = b.with_metadata(md);
         }
         b.build()
@@ -532,7 +526,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_headers_from_cfg_and_env(&cfg);
+        let result = build_headers_from_cfg_and_env(cfg.exporter.as_ref());
         // Should be None if no headers configured and no env var
         // (unless OTEL_EXPORTER_OTLP_HEADERS is set, which we can't control in tests)
         assert!(result.is_none() || result.is_some());
@@ -555,7 +549,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_headers_from_cfg_and_env(&cfg);
+        let result = build_headers_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let result_headers = result.unwrap();
         assert_eq!(
@@ -572,7 +566,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         // Should be None if no headers configured and no env var
         assert!(result.is_none() || result.is_some());
     }
@@ -594,7 +588,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         assert!(!metadata.is_empty());
@@ -618,7 +612,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         assert_eq!(metadata.len(), 2);
@@ -642,7 +636,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         // Should only have the valid header
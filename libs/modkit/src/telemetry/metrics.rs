@@ -0,0 +1,576 @@
+//! OpenTelemetry metrics pipeline
+//!
+//! `init_metrics` installs the global `SdkMeterProvider`. Measurements are pushed
+//! to an OTLP collector on a fixed interval when an exporter is configured, and
+//! collected on demand for the api-gateway's Prometheus scrape endpoint when
+//! `prometheus` is set. Instruments must be created through
+//! `opentelemetry::global::meter` *after* initialization to be exported.
+//!
+//! The runtime also registers observable gauges for module lifecycle state and
+//! database pool occupancy during the health phase.
+
+#[cfg(feature = "otel")]
+use std::fmt::{Display, Write as _};
+#[cfg(feature = "otel")]
+use std::sync::{Arc, OnceLock, Weak};
+#[cfg(feature = "otel")]
+use std::time::Duration;
+
+#[cfg(feature = "otel")]
+use anyhow::Context;
+#[cfg(feature = "otel")]
+use opentelemetry::{KeyValue, global};
+#[cfg(feature = "otel")]
+use opentelemetry_otlp::{Protocol, WithExportConfig, WithHttpConfig, WithTonicConfig};
+#[cfg(feature = "otel")]
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    metrics::{
+        InstrumentKind, ManualReader, PeriodicReader, Pipeline, SdkMeterProvider, Temporality,
+        data::{AggregatedMetrics, Metric, MetricData, ResourceMetrics},
+        reader::MetricReader,
+    },
+};
+
+#[cfg(feature = "otel")]
+use super::config::{ExporterKind, MetricsConfig};
+#[cfg(feature = "otel")]
+use super::init::{
+    build_headers_from_cfg_and_env, build_metadata_from_cfg_and_env, extract_exporter_config,
+};
+#[cfg(feature = "otel")]
+use crate::contracts::RunnableCapability;
+#[cfg(feature = "otel")]
+use crate::lifecycle::Status;
+
+/// Scrape path used when `prometheus.path` is not configured
+pub const DEFAULT_PROMETHEUS_PATH: &str = "/metrics";
+
+/// Instrumentation scope for instruments registered by the runtime itself
+pub const RUNTIME_METER: &str = "modkit";
+
+#[cfg(feature = "otel")]
+static PROMETHEUS: OnceLock<PrometheusExporter> = OnceLock::new();
+
+// ===== init_metrics (feature = "otel") ========================================
+
+/// Initialize the global meter provider from configuration.
+///
+/// Keep the returned provider and call `shutdown()` on it during graceful
+/// shutdown so the last interval is flushed to the collector.
+///
+/// # Errors
+/// Returns an error if metrics are disabled or the OTLP exporter fails to build.
+#[cfg(feature = "otel")]
+pub fn init_metrics(cfg: &MetricsConfig) -> anyhow::Result<SdkMeterProvider> {
+    if !cfg.enabled {
+        return Err(anyhow::anyhow!("metrics are disabled"));
+    }
+
+    let (provider, prometheus) = build_meter_provider(cfg)?;
+
+    if let Some(prometheus) = prometheus {
+        tracing::info!(path = %prometheus.path(), "Prometheus scrape endpoint enabled");
+        if PROMETHEUS.set(prometheus).is_err() {
+            tracing::warn!("Prometheus exporter already installed; keeping the first one");
+        }
+    }
+
+    global::set_meter_provider(provider.clone());
+    tracing::info!("OpenTelemetry meter provider installed");
+    Ok(provider)
+}
+
+/// The Prometheus exporter installed by [`init_metrics`], if enabled.
+#[cfg(feature = "otel")]
+#[must_use]
+pub fn prometheus_exporter() -> Option<&'static PrometheusExporter> {
+    PROMETHEUS.get()
+}
+
+#[cfg(feature = "otel")]
+fn build_meter_provider(
+    cfg: &MetricsConfig,
+) -> anyhow::Result<(SdkMeterProvider, Option<PrometheusExporter>)> {
+    let mut builder = SdkMeterProvider::builder().with_resource(build_resource(cfg));
+
+    if cfg.exporter.is_some() {
+        let (kind, endpoint, timeout) = extract_exporter_config(cfg.exporter.as_ref());
+        tracing::info!(kind = ?kind, %endpoint, "OTLP metrics exporter config");
+
+        let exporter = if matches!(kind, ExporterKind::OtlpHttp) {
+            let mut b = opentelemetry_otlp::MetricExporter::builder()
+                .with_http()
+                .with_protocol(Protocol::HttpBinary)
+                .with_endpoint(endpoint);
+            if let Some(t) = timeout {
+                b = b.with_timeout(t);
+            }
+            if let Some(hmap) = build_headers_from_cfg_and_env(cfg.exporter.as_ref()) {
+                b = b.with_headers(hmap);
+            }
+            b.build().context("build OTLP HTTP metrics exporter")?
+        } else {
+            let mut b = opentelemetry_otlp::MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint);
+            if let Some(t) = timeout {
+                b = b.with_timeout(t);
+            }
+            if let Some(md) = build_metadata_from_cfg_and_env(cfg.exporter.as_ref()) {
+                b = b.with_metadata(md);
+            }
+            b.build().context("build OTLP gRPC metrics exporter")?
+        };
+
+        let mut reader = PeriodicReader::builder(exporter);
+        if let Some(ms) = cfg.export_interval_ms {
+            reader = reader.with_interval(Duration::from_millis(ms));
+        }
+        builder = builder.with_reader(reader.build());
+    }
+
+    let prometheus = if let Some(opts) = &cfg.prometheus {
+        let reader = SharedReader(Arc::new(ManualReader::builder().build()));
+        builder = builder.with_reader(reader.clone());
+        Some(PrometheusExporter {
+            reader,
+            path: opts
+                .path
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PROMETHEUS_PATH.to_owned()),
+        })
+    } else {
+        None
+    };
+
+    Ok((builder.build(), prometheus))
+}
+
+#[cfg(feature = "otel")]
+fn build_resource(cfg: &MetricsConfig) -> Resource {
+    let service_name = cfg.service_name.as_deref().unwrap_or("hyperspot");
+    let mut attrs = vec![KeyValue::new("service.name", service_name.to_owned())];
+
+    if let Some(resource_map) = &cfg.resource {
+        for (k, v) in resource_map {
+            attrs.push(KeyValue::new(k.clone(), v.clone()));
+        }
+    }
+
+    Resource::builder_empty().with_attributes(attrs).build()
+}
+
+// ===== Prometheus pull exporter ===============================================
+
+/// Pull reader rendering the current measurements in the Prometheus text format.
+#[cfg(feature = "otel")]
+#[derive(Debug)]
+pub struct PrometheusExporter {
+    reader: SharedReader,
+    path: String,
+}
+
+#[cfg(feature = "otel")]
+impl PrometheusExporter {
+    /// Scrape path the api-gateway serves this exporter on.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Collect all instruments and encode them (text exposition format 0.0.4).
+    ///
+    /// # Errors
+    /// Returns an error if the meter provider has been shut down.
+    pub fn render(&self) -> anyhow::Result<String> {
+        let mut rm = ResourceMetrics::default();
+        self.reader
+            .collect(&mut rm)
+            .map_err(|e| anyhow::anyhow!("collect metrics: {e}"))?;
+        Ok(encode_text(&rm))
+    }
+}
+
+/// `ManualReader` shared between the meter provider and the scrape handler.
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone)]
+struct SharedReader(Arc<ManualReader>);
+
+#[cfg(feature = "otel")]
+impl MetricReader for SharedReader {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.0.register_pipeline(pipeline);
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.0.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, kind: InstrumentKind) -> Temporality {
+        self.0.temporality(kind)
+    }
+}
+
+#[cfg(feature = "otel")]
+fn encode_text(rm: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for scope in rm.scope_metrics() {
+        for metric in scope.metrics() {
+            match metric.data() {
+                AggregatedMetrics::F64(data) => encode_metric(&mut out, metric, data),
+                AggregatedMetrics::U64(data) => encode_metric(&mut out, metric, data),
+                AggregatedMetrics::I64(data) => encode_metric(&mut out, metric, data),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(feature = "otel")]
+fn encode_metric<T: Copy + Display>(out: &mut String, metric: &Metric, data: &MetricData<T>) {
+    let name = prometheus_name(metric.name(), metric.unit());
+    match data {
+        MetricData::Gauge(gauge) => {
+            write_header(out, &name, metric.description(), "gauge");
+            for dp in gauge.data_points() {
+                let labels: Vec<&KeyValue> = dp.attributes().collect();
+                write_sample(out, &name, &labels, None, dp.value());
+            }
+        }
+        MetricData::Sum(sum) => {
+            let (name, kind) = if sum.is_monotonic() {
+                (format!("{name}_total"), "counter")
+            } else {
+                (name, "gauge")
+            };
+            write_header(out, &name, metric.description(), kind);
+            for dp in sum.data_points() {
+                let labels: Vec<&KeyValue> = dp.attributes().collect();
+                write_sample(out, &name, &labels, None, dp.value());
+            }
+        }
+        MetricData::Histogram(hist) => {
+            write_header(out, &name, metric.description(), "histogram");
+            let bucket = format!("{name}_bucket");
+            for dp in hist.data_points() {
+                let labels: Vec<&KeyValue> = dp.attributes().collect();
+                let mut cumulative = 0u64;
+                for (bound, count) in dp.bounds().zip(dp.bucket_counts()) {
+                    cumulative += count;
+                    let le = bound.to_string();
+                    write_sample(out, &bucket, &labels, Some(&le), cumulative);
+                }
+                write_sample(out, &bucket, &labels, Some("+Inf"), dp.count());
+                write_sample(out, &format!("{name}_sum"), &labels, None, dp.sum());
+                write_sample(out, &format!("{name}_count"), &labels, None, dp.count());
+            }
+        }
+        // Only produced by views opting into exponential aggregation; the built-in
+        // instruments never do.
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+#[cfg(feature = "otel")]
+fn write_header(out: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let help = description.replace('\\', "\\\\").replace('\n', "\\n");
+        _ = writeln!(out, "# HELP {name} {help}");
+    }
+    _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+#[cfg(feature = "otel")]
+fn write_sample<V: Display + Copy>(
+    out: &mut String,
+    name: &str,
+    labels: &[&KeyValue],
+    le: Option<&str>,
+    value: V,
+) {
+    out.push_str(name);
+    if !labels.is_empty() || le.is_some() {
+        out.push('{');
+        let pairs = labels
+            .iter()
+            .map(|kv| (sanitize_name(kv.key.as_str()), kv.value.as_str()))
+            .chain(le.map(|le| ("le".to_owned(), le.into())));
+        for (i, (key, value)) in pairs.enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            _ = write!(out, "{key}=\"{value}\"");
+        }
+        out.push('}');
+    }
+    _ = writeln!(out, " {value}");
+}
+
+/// Map an OpenTelemetry instrument name and unit to a Prometheus metric name,
+/// e.g. `http.server.request.duration` + `s` → `http_server_request_duration_seconds`.
+#[cfg(feature = "otel")]
+fn prometheus_name(name: &str, unit: &str) -> String {
+    let mut name = sanitize_name(name);
+    let suffix = match unit {
+        "s" => Some("seconds".to_owned()),
+        "ms" => Some("milliseconds".to_owned()),
+        "By" => Some("bytes".to_owned()),
+        // Dimensionless, or an annotation such as `{request}`
+        u if u.is_empty() || u == "1" || u.starts_with('{') => None,
+        u => Some(sanitize_name(u)),
+    };
+    if let Some(suffix) = suffix
+        && !name.ends_with(&suffix)
+    {
+        name.push('_');
+        name.push_str(&suffix);
+    }
+    name
+}
+
+#[cfg(feature = "otel")]
+fn sanitize_name(raw: &str) -> String {
+    let mut name: String = raw
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+// ===== runtime gauges =========================================================
+
+/// Report each stateful module's lifecycle state as `modkit.module.state`:
+/// one series per `(module, state)` pair, set to 1 for the current state.
+#[cfg(feature = "otel")]
+pub(crate) fn observe_lifecycle_states(modules: Vec<(&'static str, Arc<dyn RunnableCapability>)>) {
+    if modules.is_empty() {
+        return;
+    }
+    global::meter(RUNTIME_METER)
+        .u64_observable_gauge("modkit.module.state")
+        .with_description("Lifecycle state of stateful modules (1 = current state)")
+        .with_callback(move |observer| {
+            for (module, runnable) in &modules {
+                let Some(current) = runnable.status() else {
+                    continue;
+                };
+                for state in [
+                    Status::Stopped,
+                    Status::Starting,
+                    Status::Running,
+                    Status::Stopping,
+                ] {
+                    observer.observe(
+                        u64::from(state == current),
+                        &[
+                            KeyValue::new("module", *module),
+                            KeyValue::new("state", status_label(state)),
+                        ],
+                    );
+                }
+            }
+        })
+        .build();
+}
+
+#[cfg(feature = "otel")]
+fn status_label(status: Status) -> &'static str {
+    match status {
+        Status::Stopped => "stopped",
+        Status::Starting => "starting",
+        Status::Running => "running",
+        Status::Stopping => "stopping",
+    }
+}
+
+/// Report connection pool occupancy per module database, following the
+/// `db.client.connection.*` semantic conventions.
+#[cfg(all(feature = "otel", feature = "db"))]
+pub(crate) fn observe_db_pools(pools: Vec<(&'static str, modkit_db::Db)>) {
+    if pools.is_empty() {
+        return;
+    }
+    let pools = Arc::new(pools);
+    let meter = global::meter(RUNTIME_METER);
+
+    let count_pools = Arc::clone(&pools);
+    meter
+        .u64_observable_gauge("db.client.connection.count")
+        .with_description("Open connections per pool, by state")
+        .with_unit("{connection}")
+        .with_callback(move |observer| {
+            for (module, db) in count_pools.iter() {
+                let Some(stats) = db.pool_stats() else {
+                    continue;
+                };
+                let pool = KeyValue::new("db.client.connection.pool.name", *module);
+                observer.observe(
+                    u64::from(stats.idle),
+                    &[
+                        pool.clone(),
+                        KeyValue::new("db.client.connection.state", "idle"),
+                    ],
+                );
+                observer.observe(
+                    u64::from(stats.size.saturating_sub(stats.idle)),
+                    &[pool, KeyValue::new("db.client.connection.state", "used")],
+                );
+            }
+        })
+        .build();
+
+    meter
+        .u64_observable_gauge("db.client.connection.max")
+        .with_description("Maximum connections allowed per pool")
+        .with_unit("{connection}")
+        .with_callback(move |observer| {
+            for (module, db) in pools.iter() {
+                if let Some(stats) = db.pool_stats() {
+                    observer.observe(
+                        u64::from(stats.max),
+                        &[KeyValue::new("db.client.connection.pool.name", *module)],
+                    );
+                }
+            }
+        })
+        .build();
+}
+
+// ===== init_metrics (feature disabled) ========================================
+
+#[cfg(not(feature = "otel"))]
+pub fn init_metrics(_cfg: &serde_json::Value) -> Option<()> {
+    tracing::info!("Metrics configuration provided but runtime feature is disabled");
+    None
+}
+
+// ===== tests ==================================================================
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[cfg(feature = "otel")]
+mod tests {
+    use super::*;
+    use crate::telemetry::config::{Exporter, PrometheusOpts};
+    use opentelemetry::metrics::MeterProvider as _;
+
+    fn prometheus_config() -> MetricsConfig {
+        MetricsConfig {
+            enabled: true,
+            service_name: Some("test-service".to_owned()),
+            prometheus: Some(PrometheusOpts::default()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_init_metrics_disabled() {
+        let cfg = MetricsConfig::default();
+        assert!(init_metrics(&cfg).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_meter_provider_with_otlp_exporter() {
+        let cfg = MetricsConfig {
+            enabled: true,
+            exporter: Some(Exporter {
+                kind: ExporterKind::OtlpGrpc,
+                endpoint: Some("http://localhost:4317".to_owned()),
+                headers: None,
+                timeout_ms: Some(5000),
+            }),
+            export_interval_ms: Some(1000),
+            ..Default::default()
+        };
+
+        let (provider, prometheus) = build_meter_provider(&cfg).unwrap();
+        assert!(prometheus.is_none());
+        _ = provider.shutdown();
+    }
+
+    #[test]
+    fn test_prometheus_default_path() {
+        let (_provider, prometheus) = build_meter_provider(&prometheus_config()).unwrap();
+        assert_eq!(prometheus.unwrap().path(), DEFAULT_PROMETHEUS_PATH);
+    }
+
+    #[test]
+    fn test_prometheus_renders_counter_and_histogram() {
+        let (provider, prometheus) = build_meter_provider(&prometheus_config()).unwrap();
+        let prometheus = prometheus.unwrap();
+        let meter = provider.meter("test");
+
+        let counter = meter
+            .u64_counter("jobs.processed")
+            .with_description("Processed jobs")
+            .build();
+        counter.add(3, &[KeyValue::new("queue", "default")]);
+
+        let histogram = meter
+            .f64_histogram("http.server.request.duration")
+            .with_unit("s")
+            .with_boundaries(vec![0.1, 1.0])
+            .build();
+        histogram.record(0.05, &[KeyValue::new("http.route", "/users/{id}")]);
+        histogram.record(0.5, &[KeyValue::new("http.route", "/users/{id}")]);
+
+        let text = prometheus.render().unwrap();
+        assert!(text.contains("# HELP jobs_processed_total Processed jobs"));
+        assert!(text.contains("# TYPE jobs_processed_total counter"));
+        assert!(text.contains("jobs_processed_total{queue=\"default\"} 3"));
+        assert!(text.contains("# TYPE http_server_request_duration_seconds histogram"));
+        assert!(text.contains(
+            "http_server_request_duration_seconds_bucket{http_route=\"/users/{id}\",le=\"0.1\"} 1"
+        ));
+        assert!(text.contains(
+            "http_server_request_duration_seconds_bucket{http_route=\"/users/{id}\",le=\"1\"} 2"
+        ));
+        assert!(text.contains(
+            "http_server_request_duration_seconds_bucket{http_route=\"/users/{id}\",le=\"+Inf\"} 2"
+        ));
+        assert!(
+            text.contains(
+                "http_server_request_duration_seconds_count{http_route=\"/users/{id}\"} 2"
+            )
+        );
+    }
+
+    #[test]
+    fn test_prometheus_escapes_label_values() {
+        let (provider, prometheus) = build_meter_provider(&prometheus_config()).unwrap();
+        let gauge = provider.meter("test").i64_gauge("queue.depth").build();
+        gauge.record(-2, &[KeyValue::new("name", "a\"b")]);
+
+        let text = prometheus.unwrap().render().unwrap();
+        assert!(text.contains("queue_depth{name=\"a\\\"b\"} -2"));
+    }
+
+    #[test]
+    fn test_prometheus_name_units() {
+        assert_eq!(
+            prometheus_name("db.query.time", "ms"),
+            "db_query_time_milliseconds"
+        );
+        assert_eq!(prometheus_name("payload.size", "By"), "payload_size_bytes");
+        assert_eq!(prometheus_name("requests", "{request}"), "requests");
+        assert_eq!(prometheus_name("latency_seconds", "s"), "latency_seconds");
+        assert_eq!(prometheus_name("2xx.count", ""), "_2xx_count");
+    }
+}
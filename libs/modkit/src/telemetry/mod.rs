@@ -1,12 +1,17 @@
 //! Telemetry utilities for OpenTelemetry integration
 //!
 //! This module provides utilities for setting up and configuring
-//! OpenTelemetry tracing layers for distributed tracing.
+//! OpenTelemetry tracing layers for distributed tracing and the metrics pipeline.
 
 pub mod config;
 pub mod init;
+pub mod metrics;
 pub mod throttled_log;
 
-pub use config::{Exporter, HttpOpts, LogsCorrelation, Propagation, Sampler, TracingConfig};
+pub use config::{
+    Exporter, HttpOpts, LogsCorrelation, MetricsConfig, PrometheusOpts, Propagation, Sampler,
+    TracingConfig,
+};
 pub use init::{init_tracing, shutdown_tracing};
+pub use metrics::init_metrics;
 pub use throttled_log::ThrottledLog;
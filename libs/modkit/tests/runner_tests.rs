@@ -409,7 +409,7 @@ async fn test_db_options_manager() {
         oop: None,
     };
 
-    let result = timeout(Duration::from_millis(1000), run(opts)).await;
+    let result = timeout(Duration::from_secs(1), run(opts)).await;
     assert!(result.is_ok());
     let run_result = result.unwrap();
     // Should succeed with DbManager approach
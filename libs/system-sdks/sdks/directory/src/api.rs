@@ -2,6 +2,8 @@
 //!
 //! This module defines the core traits and types for the directory service API.
 
+use std::time::SystemTime;
+
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -48,6 +50,56 @@ pub struct ServiceInstanceInfo {
     pub endpoint: ServiceEndpoint,
     /// Optional version string
     pub version: Option<String>,
+    /// Supervision history of the process slot this instance runs in,
+    /// oldest first. Empty for instances the host does not supervise.
+    pub history: Vec<InstanceEvent>,
+}
+
+/// What happened to a supervised module process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceEventKind {
+    /// A process was started for the instance
+    Started,
+    /// The process exited; `exit_code` is `None` when it was killed by a signal
+    Exited,
+    /// A replacement is scheduled after backoff
+    Restarting,
+    /// Restarts were exhausted and the module stays down
+    GaveUp,
+}
+
+impl InstanceEventKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::Exited => "exited",
+            Self::Restarting => "restarting",
+            Self::GaveUp => "gave_up",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "started" => Some(Self::Started),
+            "exited" => Some(Self::Exited),
+            "restarting" => Some(Self::Restarting),
+            "gave_up" => Some(Self::GaveUp),
+            _ => None,
+        }
+    }
+}
+
+/// Entry in the supervision history of a module instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceEvent {
+    /// Instance the event refers to
+    pub instance_id: String,
+    pub kind: InstanceEventKind,
+    pub at: SystemTime,
+    /// Process exit code, for `Exited` events
+    pub exit_code: Option<i32>,
 }
 
 /// Information for registering a new module instance
@@ -124,4 +176,17 @@ mod tests {
         assert_eq!(info.instance_id, "instance1");
         assert_eq!(info.grpc_services.len(), 1);
     }
+
+    #[test]
+    fn test_instance_event_kind_round_trips() {
+        for kind in [
+            InstanceEventKind::Started,
+            InstanceEventKind::Exited,
+            InstanceEventKind::Restarting,
+            InstanceEventKind::GaveUp,
+        ] {
+            assert_eq!(InstanceEventKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(InstanceEventKind::parse("crashed"), None);
+    }
 }
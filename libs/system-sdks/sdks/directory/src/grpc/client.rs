@@ -139,6 +139,11 @@ impl DirectoryClient for DirectoryGrpcClient {
                 } else {
                     Some(proto_inst.version)
                 },
+                history: proto_inst
+                    .history
+                    .into_iter()
+                    .filter_map(crate::InstanceEventProto::into_event)
+                    .collect(),
             })
             .collect();
 
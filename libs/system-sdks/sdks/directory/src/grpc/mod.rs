@@ -18,10 +18,41 @@ pub use directory::{
     ListInstancesRequest, ListInstancesResponse, RegisterInstanceRequest,
     ResolveGrpcServiceRequest, ResolveGrpcServiceResponse,
 };
+// The proto message shares its name with the domain type
+pub use directory::InstanceEvent as InstanceEventProto;
 
 // Re-export the gRPC client implementation
 pub use client::DirectoryGrpcClient;
 
+impl From<&crate::api::InstanceEvent> for InstanceEventProto {
+    fn from(e: &crate::api::InstanceEvent) -> Self {
+        let at_unix_ms =
+            e.at.duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX));
+        Self {
+            instance_id: e.instance_id.clone(),
+            kind: e.kind.as_str().to_owned(),
+            at_unix_ms,
+            exit_code: e.exit_code,
+        }
+    }
+}
+
+impl InstanceEventProto {
+    /// Convert to the domain type; `None` for kinds this version does not know.
+    #[must_use]
+    pub fn into_event(self) -> Option<crate::api::InstanceEvent> {
+        let kind = crate::api::InstanceEventKind::parse(&self.kind)?;
+        let millis = u64::try_from(self.at_unix_ms).unwrap_or(0);
+        Some(crate::api::InstanceEvent {
+            instance_id: self.instance_id,
+            kind,
+            at: std::time::UNIX_EPOCH + std::time::Duration::from_millis(millis),
+            exit_code: self.exit_code,
+        })
+    }
+}
+
 /// Service name constant for `DirectoryService`
 pub const DIRECTORY_SERVICE_NAME: &str =
     <DirectoryServiceServer<()> as tonic::server::NamedService>::NAME;
@@ -9,6 +9,9 @@ mod api;
 #[cfg(feature = "grpc")]
 mod grpc;
 
-pub use api::{DirectoryClient, RegisterInstanceInfo, ServiceEndpoint, ServiceInstanceInfo};
+pub use api::{
+    DirectoryClient, InstanceEvent, InstanceEventKind, RegisterInstanceInfo, ServiceEndpoint,
+    ServiceInstanceInfo,
+};
 #[cfg(feature = "grpc")]
 pub use grpc::*;
@@ -1,4 +1,5 @@
-use modkit::api::problem::Problem;
+use modkit::api::problem::{self, Problem};
+use modkit_db::DbError;
 
 use crate::domain::error::DomainError;
 use crate::errors::ErrorCode;
@@ -16,7 +17,7 @@ pub fn domain_error_to_problem(e: &DomainError, instance: &str) -> Problem {
         }
         DomainError::Forbidden(msg) => build_forbidden_problem(e, msg, instance, trace_id),
         DomainError::Internal(msg) => build_internal_problem(e, msg, instance, trace_id),
-        DomainError::Database(_) => build_database_problem(e, instance, trace_id),
+        DomainError::Database(db) => build_database_problem(e, db, instance, trace_id),
     }
 }
 
@@ -71,7 +72,19 @@ fn build_internal_problem(
     )
 }
 
-fn build_database_problem(e: &DomainError, instance: &str, trace_id: Option<String>) -> Problem {
+fn build_database_problem(
+    e: &DomainError,
+    db: &DbError,
+    instance: &str,
+    trace_id: Option<String>,
+) -> Problem {
+    if matches!(db, DbError::VersionConflict { .. }) {
+        let problem = problem::from_db_error(db, false).with_instance(instance);
+        return match trace_id {
+            Some(id) => problem.with_trace_id(id),
+            None => problem,
+        };
+    }
     tracing::error!(error = ?e, "Database error occurred");
     ErrorCode::settings_simple_user_settings_internal_database_v1().with_context(
         "An internal database error occurred",
@@ -41,6 +41,18 @@ mod tests {
         assert!(problem.detail.contains("internal database error"));
     }
 
+    #[test]
+    fn test_version_conflict_to_problem() {
+        let error = DomainError::Database(modkit_db::DbError::from(
+            modkit_db::secure::ScopeError::VersionConflict { expected: 2 },
+        ));
+        let problem = domain_error_to_problem(&error, "/api/settings");
+
+        assert_eq!(problem.status, StatusCode::CONFLICT);
+        assert_eq!(problem.instance, "/api/settings");
+        assert!(problem.detail.contains("version 2"));
+    }
+
     #[test]
     fn test_from_domain_error_for_problem_not_found() {
         let error = DomainError::NotFound;
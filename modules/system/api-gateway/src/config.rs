@@ -8,6 +8,10 @@ fn default_body_limit_bytes() -> usize {
     16 * 1024 * 1024
 }
 
+fn default_health_check_timeout_ms() -> u64 {
+    2000
+}
+
 /// API gateway configuration - reused from `api_gateway` module
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -50,6 +54,8 @@ pub struct Defaults {
     pub rate_limit: RateLimitDefaults,
     /// Global request body size limit in bytes
     pub body_limit_bytes: usize,
+    /// Per-check timeout for `/health` and `/healthz`, in milliseconds
+    pub health_check_timeout_ms: u64,
 }
 
 impl Default for Defaults {
@@ -57,6 +63,7 @@ impl Default for Defaults {
         Self {
             rate_limit: RateLimitDefaults::default(),
             body_limit_bytes: default_body_limit_bytes(),
+            health_check_timeout_ms: default_health_check_timeout_ms(),
         }
     }
 }
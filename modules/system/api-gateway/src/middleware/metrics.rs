@@ -0,0 +1,119 @@
+//! HTTP server RED metrics
+//!
+//! Records `http.server.request.duration` and `http.server.active_requests` per the
+//! OpenTelemetry HTTP semantic conventions. The `http.route` attribute is the matched
+//! route template (the `OperationSpec` path), never the raw URI, so cardinality stays
+//! bounded by the number of registered operations.
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::Method;
+use axum::{middleware::Next, response::Response};
+use opentelemetry::metrics::{Histogram, UpDownCounter};
+use opentelemetry::{KeyValue, global};
+use std::time::Instant;
+
+/// Bucket boundaries recommended by the semantic conventions for HTTP durations.
+const DURATION_BUCKETS_SECS: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// Instruments shared by every request passing through the gateway.
+#[derive(Clone)]
+pub struct HttpServerMetrics {
+    duration: Histogram<f64>,
+    active: UpDownCounter<i64>,
+}
+
+impl HttpServerMetrics {
+    /// Create the instruments on the global meter provider.
+    ///
+    /// Must run after the meter provider is installed, which the host does before
+    /// the REST phase.
+    #[must_use]
+    pub fn new() -> Self {
+        let meter = global::meter("api_gateway");
+        Self {
+            duration: meter
+                .f64_histogram("http.server.request.duration")
+                .with_description("Duration of HTTP server requests")
+                .with_unit("s")
+                .with_boundaries(DURATION_BUCKETS_SECS.to_vec())
+                .build(),
+            active: meter
+                .i64_up_down_counter("http.server.active_requests")
+                .with_description("Number of in-flight HTTP server requests")
+                .with_unit("{request}")
+                .build(),
+        }
+    }
+}
+
+impl Default for HttpServerMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decrements the in-flight counter even when the handler future is dropped
+/// (client disconnect, timeout).
+struct ActiveGuard<'a> {
+    active: &'a UpDownCounter<i64>,
+    attrs: &'a [KeyValue],
+}
+
+impl Drop for ActiveGuard<'_> {
+    fn drop(&mut self) {
+        self.active.add(-1, self.attrs);
+    }
+}
+
+/// Middleware recording request rate, errors (by status code) and duration.
+pub async fn http_metrics_middleware(
+    metrics: HttpServerMetrics,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut attrs = vec![KeyValue::new(
+        "http.request.method",
+        method_label(req.method()),
+    )];
+    if let Some(route) = req.extensions().get::<MatchedPath>() {
+        attrs.push(KeyValue::new("http.route", route.as_str().to_owned()));
+    }
+
+    let start = Instant::now();
+    metrics.active.add(1, &attrs);
+    let response = {
+        let _guard = ActiveGuard {
+            active: &metrics.active,
+            attrs: &attrs,
+        };
+        next.run(req).await
+    };
+
+    attrs.push(KeyValue::new(
+        "http.response.status_code",
+        i64::from(response.status().as_u16()),
+    ));
+    metrics
+        .duration
+        .record(start.elapsed().as_secs_f64(), &attrs);
+
+    response
+}
+
+/// Known methods verbatim; anything else collapses to `_OTHER` to bound cardinality.
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::PATCH => "PATCH",
+        Method::DELETE => "DELETE",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        Method::CONNECT => "CONNECT",
+        Method::TRACE => "TRACE",
+        _ => "_OTHER",
+    }
+}
@@ -13,6 +13,7 @@ use axum::http::Method;
 use axum::middleware::from_fn_with_state;
 use axum::{Router, extract::DefaultBodyLimit, middleware::from_fn, routing::get};
 use modkit::api::{OpenApiRegistry, OpenApiRegistryImpl};
+use modkit::health::HealthAggregator;
 use modkit::lifecycle::ReadySignal;
 use parking_lot::Mutex;
 use std::net::SocketAddr;
@@ -55,6 +56,8 @@ pub struct ApiGateway {
     pub(crate) final_router: Mutex<Option<axum::Router>>,
     // AuthN Resolver client (resolved during init, None when auth_disabled)
     pub(crate) authn_client: Mutex<Option<Arc<dyn AuthNResolverClient>>>,
+    // Runtime health aggregator (resolved during init, None when running standalone)
+    pub(crate) health: Mutex<Option<Arc<HealthAggregator>>>,
 
     // Duplicate detection (per (method, path) and per handler id)
     pub(crate) registered_routes: DashMap<(Method, String), ()>,
@@ -70,6 +73,7 @@ impl Default for ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            health: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -87,6 +91,7 @@ impl ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            health: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -125,6 +130,9 @@ impl ApiGateway {
         // Always mark built-in health check routes as public
         public_routes.insert((Method::GET, "/health".to_owned()));
         public_routes.insert((Method::GET, "/healthz".to_owned()));
+        if let Some(exporter) = modkit::telemetry::metrics::prometheus_exporter() {
+            public_routes.insert((Method::GET, exporter.path().to_owned()));
+        }
         public_routes.insert((Method::GET, "/docs".to_owned()));
         public_routes.insert((Method::GET, "/openapi.json".to_owned()));
 
@@ -172,7 +180,8 @@ impl ApiGateway {
         //
         // Desired request execution order (outermost -> innermost):
         // SetRequestId -> PropagateRequestId -> Trace -> push_req_id_to_extensions
-        // -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> Router
+        // -> Metrics -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth
+        // -> License -> ReadYourWrites -> AuditActor -> Router
         //
         // Therefore we must add layers in the reverse order (innermost -> outermost) below.
         // Due future refactoring, this order must be maintained.
@@ -187,7 +196,24 @@ impl ApiGateway {
             .map(|e| e.value().clone())
             .collect();
 
-        // 11) License validation
+        // 14) Audit actor: audited DB writes are attributed to the authenticated subject
+        router = router.layer(from_fn(
+            |req: axum::extract::Request, next: axum::middleware::Next| async move {
+                match req.extensions().get::<SecurityContext>().cloned() {
+                    Some(ctx) => modkit_db::with_audit_actor(&ctx, next.run(req)).await,
+                    None => next.run(req).await,
+                }
+            },
+        ));
+
+        // 13) Read-your-writes: once a handler writes, its later reads skip DB read replicas
+        router = router.layer(from_fn(
+            |req: axum::extract::Request, next: axum::middleware::Next| {
+                modkit_db::read_your_writes(next.run(req))
+            },
+        ));
+
+        // 12) License validation
         let license_map = middleware::license_validation::LicenseRequirementMap::from_specs(&specs);
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -196,7 +222,7 @@ impl ApiGateway {
             },
         ));
 
-        // 10) Auth
+        // 11) Auth
         if config.auth_disabled {
             // Build security contexts for compatibility during migration
             let default_security_context = SecurityContext::builder()
@@ -231,10 +257,10 @@ impl ApiGateway {
             ));
         }
 
-        // 9) Error mapping (outer to auth so it can translate auth/handler errors)
+        // 10) Error mapping (outer to auth so it can translate auth/handler errors)
         router = router.layer(from_fn(modkit::api::error_layer::error_mapping_middleware));
 
-        // 8) Per-route rate limiting & in-flight limits
+        // 9) Per-route rate limiting & in-flight limits
         let rate_map = middleware::rate_limit::RateLimiterMap::from_specs(&specs, &config)?;
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -243,7 +269,7 @@ impl ApiGateway {
             },
         ));
 
-        // 7) MIME type validation
+        // 8) MIME type validation
         let mime_map = middleware::mime_validation::build_mime_validation_map(&specs);
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -252,21 +278,30 @@ impl ApiGateway {
             },
         ));
 
-        // 6) CORS (must be outer to auth/limits so OPTIONS preflight short-circuits)
+        // 7) CORS (must be outer to auth/limits so OPTIONS preflight short-circuits)
         if config.cors_enabled {
             router = router.layer(crate::cors::build_cors_layer(&config));
         }
 
-        // 5) Body limit
+        // 6) Body limit
         router = router.layer(RequestBodyLimitLayer::new(config.defaults.body_limit_bytes));
         router = router.layer(DefaultBodyLimit::max(config.defaults.body_limit_bytes));
 
-        // 4) Timeout
+        // 5) Timeout
         router = router.layer(TimeoutLayer::with_status_code(
             axum::http::StatusCode::GATEWAY_TIMEOUT,
             Duration::from_secs(30),
         ));
 
+        // 4) HTTP server metrics (outer to timeout so 504s are recorded as such)
+        let http_metrics = middleware::metrics::HttpServerMetrics::new();
+        router = router.layer(from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let metrics = http_metrics.clone();
+                middleware::metrics::http_metrics_middleware(metrics, req, next)
+            },
+        ));
+
         // 3) Record request_id into span + extensions (requires span to exist first => must be inner to Trace)
         router = router.layer(from_fn(middleware::request_id::push_req_id_to_extensions));
 
@@ -355,9 +390,7 @@ impl ApiGateway {
         tracing::debug!("Building new router (standalone/fallback mode)");
         // In standalone mode (no REST pipeline), register both health endpoints here.
         // In normal operation, rest_prepare() registers these instead.
-        let mut router = Router::new()
-            .route("/health", get(web::health_check))
-            .route("/healthz", get(|| async { "ok" }));
+        let mut router = Self::add_metrics_route(self.add_health_routes(Router::new()));
 
         // Apply all middleware layers including auth, above the router
         let authn_client = self.authn_client.lock().clone();
@@ -369,6 +402,34 @@ impl ApiGateway {
         Ok(router)
     }
 
+    /// Attach the Prometheus scrape endpoint when `metrics.prometheus` is configured.
+    fn add_metrics_route(router: Router) -> Router {
+        match modkit::telemetry::metrics::prometheus_exporter() {
+            Some(exporter) => router.route(
+                exporter.path(),
+                get(move || async move { web::prometheus_metrics(exporter) }),
+            ),
+            None => router,
+        }
+    }
+
+    /// Attach `/health` (readiness) and `/healthz` (liveness) backed by the runtime aggregator.
+    fn add_health_routes(&self, router: Router) -> Router {
+        let health = self.health.lock().clone();
+        let timeout =
+            Duration::from_millis(self.get_cached_config().defaults.health_check_timeout_ms);
+        let liveness = health.clone();
+        router
+            .route(
+                "/health",
+                get(move || web::health_check(health.clone(), timeout)),
+            )
+            .route(
+                "/healthz",
+                get(move || web::liveness_check(liveness.clone(), timeout)),
+            )
+    }
+
     /// Build `OpenAPI` specification from registered routes and components.
     ///
     /// # Errors
@@ -556,6 +617,9 @@ impl modkit::Module for ApiGateway {
             tracing::info!("AuthN Resolver client resolved from ClientHub");
         }
 
+        // Published by the host runtime; absent when the gateway runs standalone
+        *self.health.lock() = ctx.client_hub().get::<HealthAggregator>().ok();
+
         Ok(())
     }
 }
@@ -568,11 +632,10 @@ impl modkit::contracts::ApiGatewayCapability for ApiGateway {
         router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
         // Add health check endpoints:
-        // - /health: detailed JSON response with status and timestamp
-        // - /healthz: simple "ok" liveness probe (Kubernetes-style)
-        let router = router
-            .route("/health", get(web::health_check))
-            .route("/healthz", get(|| async { "ok" }));
+        // - /health: readiness with per-module results (Kubernetes-style)
+        // - /healthz: liveness, "ok" unless a module cannot recover
+        // and the Prometheus scrape endpoint, if enabled
+        let router = Self::add_metrics_route(self.add_health_routes(router));
 
         // You may attach global middlewares here (trace, compression, cors), but do not start server.
         tracing::debug!("REST host prepared base router with health check endpoints");
@@ -1,10 +1,15 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use axum::{
     http::StatusCode,
-    response::{Html, Json},
+    response::{Html, IntoResponse, Json, Response},
     routing::{MethodRouter, get},
 };
 use chrono::{SecondsFormat, Utc};
-use serde_json::{Value, json};
+use modkit::health::{HealthAggregator, HealthProbe, HealthReport};
+use modkit::telemetry::metrics::PrometheusExporter;
+use serde_json::json;
 
 /// Returns a 501 Not Implemented handler for operations without implementations
 #[allow(dead_code)]
@@ -20,11 +25,67 @@ pub fn placeholder_handler_501() -> MethodRouter {
     })
 }
 
-pub async fn health_check() -> Json<Value> {
-    Json(json!({
-        "status": "healthy",
-        "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
-    }))
+/// Readiness probe: per-module health, `503` while any module is unhealthy.
+///
+/// Without an aggregator (standalone gateway) the process itself is all there is to report.
+pub async fn health_check(health: Option<Arc<HealthAggregator>>, timeout: Duration) -> Response {
+    let Some(health) = health else {
+        return Json(json!({
+            "status": "healthy",
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+        }))
+        .into_response();
+    };
+    report_response(&health.check(HealthProbe::Readiness, timeout).await)
+}
+
+/// Liveness probe: plain `ok`, or the failing report with `503`.
+pub async fn liveness_check(health: Option<Arc<HealthAggregator>>, timeout: Duration) -> Response {
+    let Some(health) = health else {
+        return "ok".into_response();
+    };
+    let report = health.check(HealthProbe::Liveness, timeout).await;
+    if report.status.is_passing() {
+        return "ok".into_response();
+    }
+    report_response(&report)
+}
+
+fn report_response(report: &HealthReport) -> Response {
+    let code = if report.status.is_passing() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(json!({
+            "status": report.status,
+            "phase": report.phase,
+            "modules": report.modules,
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+        })),
+    )
+        .into_response()
+}
+
+/// Prometheus scrape endpoint: the current value of every instrument in text format.
+#[must_use]
+pub fn prometheus_metrics(exporter: &PrometheusExporter) -> Response {
+    match exporter.render() {
+        Ok(body) => (
+            [(
+                axum::http::header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )],
+            body,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to collect metrics for scrape");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
 }
 
 #[cfg(not(feature = "embed_elements"))]
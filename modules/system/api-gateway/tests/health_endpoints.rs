@@ -0,0 +1,173 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the `/health` and `/healthz` endpoints
+//!
+//! These tests verify that:
+//! 1. Readiness aggregates module checks and returns 503 when one is unhealthy
+//! 2. Liveness stays "ok" unless a module reports it cannot recover
+//! 3. Readiness fails until the runtime reaches the serving phase
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use modkit::{
+    ClientHub, Module,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, HealthCheckCapability},
+    health::{HealthAggregator, HealthCheckResult, RuntimePhase},
+};
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+struct FixedCheck {
+    readiness: HealthCheckResult,
+    liveness: HealthCheckResult,
+}
+
+#[async_trait]
+impl HealthCheckCapability for FixedCheck {
+    async fn readiness(&self) -> HealthCheckResult {
+        self.readiness.clone()
+    }
+
+    async fn liveness(&self) -> HealthCheckResult {
+        self.liveness.clone()
+    }
+}
+
+async fn gateway_router(health: Arc<HealthAggregator>) -> Router {
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<HealthAggregator>(health);
+
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth_disabled": true,
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare")
+}
+
+async fn get(router: &Router, uri: &str) -> (StatusCode, Vec<u8>) {
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_readiness_reports_unhealthy_module() {
+    let health = Arc::new(HealthAggregator::new());
+    health.set_phase(RuntimePhase::Serving);
+    health.register(
+        "users",
+        "module",
+        Arc::new(FixedCheck {
+            readiness: HealthCheckResult::healthy(),
+            liveness: HealthCheckResult::healthy(),
+        }),
+    );
+    health.register(
+        "billing",
+        "module",
+        Arc::new(FixedCheck {
+            readiness: HealthCheckResult::unhealthy("upstream unreachable"),
+            liveness: HealthCheckResult::healthy(),
+        }),
+    );
+    let router = gateway_router(health).await;
+
+    let (status, body) = get(&router, "/health").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "unhealthy");
+    assert_eq!(json["phase"], "serving");
+    assert_eq!(json["modules"][0]["module"], "users");
+    assert_eq!(json["modules"][0]["status"], "healthy");
+    assert_eq!(json["modules"][1]["status"], "unhealthy");
+    assert_eq!(
+        json["modules"][1]["checks"][0]["detail"],
+        "upstream unreachable"
+    );
+
+    // A module that cannot serve is not a reason to restart the process
+    let (status, body) = get(&router, "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"ok");
+}
+
+#[tokio::test]
+async fn test_liveness_fails_when_module_cannot_recover() {
+    let health = Arc::new(HealthAggregator::new());
+    health.set_phase(RuntimePhase::Serving);
+    health.register(
+        "worker",
+        "module",
+        Arc::new(FixedCheck {
+            readiness: HealthCheckResult::healthy(),
+            liveness: HealthCheckResult::unhealthy("event loop wedged"),
+        }),
+    );
+    let router = gateway_router(health).await;
+
+    let (status, body) = get(&router, "/healthz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "unhealthy");
+    assert_eq!(json["modules"][0]["module"], "worker");
+}
+
+#[tokio::test]
+async fn test_readiness_waits_for_serving_phase() {
+    let health = Arc::new(HealthAggregator::new());
+    let router = gateway_router(Arc::clone(&health)).await;
+
+    let (status, body) = get(&router, "/health").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["phase"], "starting");
+
+    health.set_phase(RuntimePhase::Serving);
+    let (status, _) = get(&router, "/health").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = get(&router, "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,140 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration test for the Prometheus scrape endpoint and HTTP server metrics
+//!
+//! Installs the process-global meter provider, so everything runs in one test:
+//! 1. Requests are recorded under the route template, not the raw path
+//! 2. The scrape endpoint is served on the configured path in text format
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    extract::Path,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use modkit::{
+    Module, ModuleCtx, RestApiCapability,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    contracts::{ApiGatewayCapability, OpenApiRegistry},
+    telemetry::{MetricsConfig, PrometheusOpts},
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+struct ItemsModule;
+
+#[async_trait]
+impl Module for ItemsModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for ItemsModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/items/v1/items/{id}")
+            .operation_id("items:get")
+            .summary("Get item")
+            .path_param("id", "Item ID")
+            .public()
+            .json_response(http::StatusCode::OK, "Item")
+            .handler(get(|Path(id): Path<String>| async move { id }))
+            .register(router, openapi);
+        Ok(router)
+    }
+}
+
+async fn get_text(router: &Router, uri: &str) -> (StatusCode, String) {
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_scrape_reports_requests_per_route() {
+    let _provider = modkit::telemetry::init_metrics(&MetricsConfig {
+        enabled: true,
+        prometheus: Some(PrometheusOpts {
+            path: Some("/internal/metrics".to_owned()),
+        }),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let config = serde_json::json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "127.0.0.1:0",
+                "auth_disabled": true,
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(modkit::ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    let router = ItemsModule
+        .register_rest(&ctx, router, &api_gateway)
+        .expect("Failed to register routes");
+    let router = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize");
+
+    for id in ["a", "b"] {
+        let (status, _) = get_text(&router, &format!("/items/v1/items/{id}")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = get_text(&router, "/internal/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("# TYPE http_server_request_duration_seconds histogram"));
+
+    let count = body
+        .lines()
+        .find(|line| {
+            line.starts_with("http_server_request_duration_seconds_count{")
+                && line.contains("http_route=\"/items/v1/items/{id}\"")
+        })
+        .expect("no series for the item route");
+    assert!(count.contains("http_request_method=\"GET\""));
+    assert!(count.contains("http_response_status_code=\"200\""));
+    assert!(count.ends_with(" 2"), "unexpected count line: {count}");
+    assert!(!body.contains("/items/v1/items/a"));
+}
@@ -5,17 +5,18 @@
 //!
 //! ## Supported predicates
 //!
-//! Only `Eq` and `In` predicates are supported in the first iteration.
+//! - `eq` / `in` compare a property with literal values.
+//! - `in_tenant_subtree`, `in_group` and `in_group_subtree` are resolved through
+//!   closure tables. The PDP may only return them when the PEP declared the
+//!   matching [`Capability`](crate::models::Capability) in its request.
 //!
-//! ## Future extensions
-//!
-//! Additional predicate types (`in_tenant_subtree`, `in_group`,
-//! `in_group_subtree`) are planned. See the authorization design document
-//! (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+//! See `docs/arch/authorization/DESIGN.md` for the predicate taxonomy.
 
+use crate::models::BarrierMode;
 use crate::pep::IntoPropertyValue;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
 /// A constraint on a specific resource property.
 ///
@@ -36,6 +37,12 @@ pub enum Predicate {
     Eq(EqPredicate),
     /// Set membership: `resource_property IN (values)`
     In(InPredicate),
+    /// Tenant hierarchy: `resource_property` is a tenant in the subtree of `root_tenant_id`
+    InTenantSubtree(InTenantSubtreePredicate),
+    /// Group membership: the resource belongs to one of `group_ids`
+    InGroup(InGroupPredicate),
+    /// Group hierarchy: the resource belongs to a group in the subtree of `root_group_id`
+    InGroupSubtree(InGroupSubtreePredicate),
 }
 
 /// Equality predicate: `property = value`.
@@ -84,6 +91,76 @@ impl InPredicate {
     }
 }
 
+/// Tenant subtree predicate: `property` is `root_tenant_id` or one of its descendants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InTenantSubtreePredicate {
+    /// Resource property holding a tenant ID (usually `pep_properties::OWNER_TENANT_ID`).
+    pub property: String,
+    /// Root of the subtree (included).
+    pub root_tenant_id: Uuid,
+    /// Whether self-managed tenants below the root cut the subtree (default: `Respect`).
+    #[serde(default)]
+    pub barrier_mode: BarrierMode,
+    /// Only tenants with one of these statuses match (e.g., `["active"]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_status: Option<Vec<String>>,
+}
+
+impl InTenantSubtreePredicate {
+    /// Create a subtree predicate that respects barriers and ignores tenant status.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            barrier_mode: BarrierMode::default(),
+            tenant_status: None,
+        }
+    }
+}
+
+/// Group membership predicate: the resource identified by `property` is a
+/// member of one of `group_ids`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupPredicate {
+    /// Resource property holding the resource ID (usually `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// Candidate groups.
+    pub group_ids: Vec<Uuid>,
+}
+
+impl InGroupPredicate {
+    /// Create a group membership predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids: group_ids.into_iter().collect(),
+        }
+    }
+}
+
+/// Group subtree predicate: the resource identified by `property` is a member
+/// of `root_group_id` or of one of its descendant groups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupSubtreePredicate {
+    /// Resource property holding the resource ID (usually `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// Root of the group subtree (included).
+    pub root_group_id: Uuid,
+}
+
+impl InGroupSubtreePredicate {
+    /// Create a group subtree predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -132,4 +209,49 @@ mod tests {
         let json_str = serde_json::to_string(&in_pred).unwrap();
         assert!(json_str.contains(r#""op":"in""#));
     }
+
+    #[test]
+    fn hierarchy_predicates_serialize_with_op_tags() {
+        let root = "44444444-4444-4444-4444-444444444444"
+            .parse::<Uuid>()
+            .unwrap();
+
+        let subtree = Predicate::InTenantSubtree(InTenantSubtreePredicate::new(
+            pep_properties::OWNER_TENANT_ID,
+            root,
+        ));
+        let json = serde_json::to_value(&subtree).unwrap();
+        assert_eq!(json["op"], "in_tenant_subtree");
+        assert_eq!(json["barrier_mode"], "respect");
+        assert!(json.get("tenant_status").is_none());
+
+        let group = Predicate::InGroup(InGroupPredicate::new(pep_properties::RESOURCE_ID, [root]));
+        assert_eq!(serde_json::to_value(&group).unwrap()["op"], "in_group");
+
+        let group_subtree = Predicate::InGroupSubtree(InGroupSubtreePredicate::new(
+            pep_properties::RESOURCE_ID,
+            root,
+        ));
+        assert_eq!(
+            serde_json::to_value(&group_subtree).unwrap()["op"],
+            "in_group_subtree"
+        );
+    }
+
+    #[test]
+    fn tenant_subtree_predicate_defaults_barrier_mode() {
+        let predicate: Predicate = serde_json::from_value(json!({
+            "op": "in_tenant_subtree",
+            "property": "owner_tenant_id",
+            "root_tenant_id": "44444444-4444-4444-4444-444444444444",
+            "tenant_status": ["active"],
+        }))
+        .unwrap();
+
+        let Predicate::InTenantSubtree(p) = predicate else {
+            panic!("expected in_tenant_subtree, got {predicate:?}");
+        };
+        assert_eq!(p.barrier_mode, BarrierMode::Respect);
+        assert_eq!(p.tenant_status, Some(vec!["active".to_owned()]));
+    }
 }
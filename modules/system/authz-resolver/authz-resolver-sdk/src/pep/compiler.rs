@@ -13,15 +13,22 @@
 //!
 //! Unknown/unsupported properties fail that constraint (fail-closed).
 //!
+//! Hierarchy predicates (`in_tenant_subtree`, `in_group`, `in_group_subtree`)
+//! compile only when the PEP declared the matching [`Capability`]; otherwise the
+//! constraint fails, since the PDP was not allowed to return them.
+//!
 //! When `require_constraints=false`, empty constraints are treated as
 //! `allow_all()` (legitimate PDP "yes, no row-level filtering"). When
 //! `require_constraints=true`, empty constraints are an error (fail-closed).
 //! If the PDP returns constraints regardless of the flag, they are compiled.
 
-use modkit_security::{AccessScope, ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::{
+    AccessScope, GroupScopeFilter, GroupSubtreeScopeFilter, ScopeConstraint, ScopeFilter,
+    ScopeValue, TenantSubtreeScopeFilter,
+};
 
 use crate::constraints::{Constraint, Predicate};
-use crate::models::EvaluationResponse;
+use crate::models::{BarrierMode, Capability, EvaluationResponse};
 
 /// Error during constraint compilation.
 #[derive(Debug, thiserror::Error)]
@@ -63,6 +70,28 @@ pub fn compile_to_access_scope(
     response: &EvaluationResponse,
     require_constraints: bool,
     supported_properties: &[&str],
+) -> Result<AccessScope, ConstraintCompileError> {
+    compile_to_access_scope_with(response, require_constraints, supported_properties, &[])
+}
+
+/// Like [`compile_to_access_scope`], additionally accepting the hierarchy
+/// predicates enabled by the PEP's declared `capabilities`.
+///
+/// | Predicate           | Required capability                          |
+/// |---------------------|----------------------------------------------|
+/// | `in_tenant_subtree` | `TenantHierarchy`                            |
+/// | `in_group`          | `GroupMembership` (or `GroupHierarchy`)      |
+/// | `in_group_subtree`  | `GroupHierarchy`                             |
+///
+/// # Errors
+///
+/// Same as [`compile_to_access_scope`]; a hierarchy predicate without its
+/// capability fails its constraint like an unsupported property does.
+pub fn compile_to_access_scope_with(
+    response: &EvaluationResponse,
+    require_constraints: bool,
+    supported_properties: &[&str],
+    capabilities: &[Capability],
 ) -> Result<AccessScope, ConstraintCompileError> {
     // Step 1: Handle empty constraints based on require_constraints flag.
     if response.context.constraints.is_empty() {
@@ -77,7 +106,7 @@ pub fn compile_to_access_scope(
     let mut fail_reasons: Vec<String> = Vec::new();
 
     for constraint in &response.context.constraints {
-        match compile_constraint(constraint, supported_properties) {
+        match compile_constraint(constraint, supported_properties, capabilities) {
             Ok(sc) => constraints.push(sc),
             Err(reason) => {
                 tracing::warn!(
@@ -107,14 +136,18 @@ pub fn compile_to_access_scope(
 /// Compile a single PDP constraint into a `ScopeConstraint`.
 ///
 /// Each predicate becomes a `ScopeFilter`. If any predicate's property
-/// is not in `supported_properties`, the entire constraint fails (fail-closed).
+/// is not in `supported_properties`, or it needs a capability the PEP did not
+/// declare, the entire constraint fails (fail-closed).
 fn compile_constraint(
     constraint: &Constraint,
     supported_properties: &[&str],
+    capabilities: &[Capability],
 ) -> Result<ScopeConstraint, String> {
     let mut filters = Vec::new();
 
     for predicate in &constraint.predicates {
+        check_capability(predicate, capabilities)?;
+
         let (property, filter) = match predicate {
             Predicate::Eq(eq) => {
                 let value = json_to_scope_value(&eq.value)?;
@@ -128,6 +161,25 @@ fn compile_constraint(
                     .collect::<Result<_, _>>()?;
                 (p.property.as_str(), ScopeFilter::r#in(&p.property, values))
             }
+            Predicate::InTenantSubtree(p) => {
+                let mut filter = TenantSubtreeScopeFilter::new(&p.property, p.root_tenant_id)
+                    .with_respect_barriers(p.barrier_mode == BarrierMode::Respect);
+                if let Some(statuses) = &p.tenant_status {
+                    filter = filter.with_tenant_status(statuses.clone());
+                }
+                (p.property.as_str(), ScopeFilter::InTenantSubtree(filter))
+            }
+            Predicate::InGroup(p) => (
+                p.property.as_str(),
+                ScopeFilter::InGroup(GroupScopeFilter::new(&p.property, p.group_ids.clone())),
+            ),
+            Predicate::InGroupSubtree(p) => (
+                p.property.as_str(),
+                ScopeFilter::InGroupSubtree(GroupSubtreeScopeFilter::new(
+                    &p.property,
+                    p.root_group_id,
+                )),
+            ),
         };
 
         if !supported_properties.contains(&property) {
@@ -140,6 +192,36 @@ fn compile_constraint(
     Ok(ScopeConstraint::new(filters))
 }
 
+/// Reject hierarchy predicates the PEP has not declared it can enforce.
+fn check_capability(predicate: &Predicate, capabilities: &[Capability]) -> Result<(), String> {
+    let (op, accepted): (&str, &[Capability]) = match predicate {
+        Predicate::Eq(_) | Predicate::In(_) => return Ok(()),
+        Predicate::InTenantSubtree(_) => ("in_tenant_subtree", &[Capability::TenantHierarchy]),
+        // Group hierarchy support includes plain membership
+        Predicate::InGroup(_) => (
+            "in_group",
+            &[Capability::GroupMembership, Capability::GroupHierarchy],
+        ),
+        Predicate::InGroupSubtree(_) => ("in_group_subtree", &[Capability::GroupHierarchy]),
+    };
+    if accepted.iter().any(|c| capabilities.contains(c)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "predicate {op} requires capability {}",
+            capability_name(&accepted[0])
+        ))
+    }
+}
+
+fn capability_name(capability: &Capability) -> &'static str {
+    match capability {
+        Capability::TenantHierarchy => "tenant_hierarchy",
+        Capability::GroupMembership => "group_membership",
+        Capability::GroupHierarchy => "group_hierarchy",
+    }
+}
+
 /// Convert a `serde_json::Value` to a `ScopeValue`.
 ///
 /// UUID strings are detected and stored as `ScopeValue::Uuid`;
@@ -167,7 +249,10 @@ fn json_to_scope_value(v: &serde_json::Value) -> Result<ScopeValue, String> {
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
-    use crate::constraints::{EqPredicate, InPredicate};
+    use crate::constraints::{
+        EqPredicate, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
+        InTenantSubtreePredicate,
+    };
     use crate::models::EvaluationResponseContext;
     use modkit_security::pep_properties;
     use serde_json::json;
@@ -505,4 +590,96 @@ mod tests {
             Err(ConstraintCompileError::AllConstraintsFailed { .. })
         ));
     }
+
+    // === Hierarchy predicates ===
+
+    fn single_predicate_response(predicate: Predicate) -> EvaluationResponse {
+        EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints: vec![Constraint {
+                    predicates: vec![predicate],
+                }],
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn tenant_subtree_requires_tenant_hierarchy_capability() {
+        let mut predicate =
+            InTenantSubtreePredicate::new(pep_properties::OWNER_TENANT_ID, uuid(T1));
+        predicate.barrier_mode = BarrierMode::Ignore;
+        predicate.tenant_status = Some(vec!["active".to_owned()]);
+        let response = single_predicate_response(Predicate::InTenantSubtree(predicate));
+
+        let err = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("in_tenant_subtree requires capability tenant_hierarchy")
+        );
+
+        let scope = compile_to_access_scope_with(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[Capability::TenantHierarchy],
+        )
+        .unwrap();
+        let ScopeFilter::InTenantSubtree(filter) = &scope.constraints()[0].filters()[0] else {
+            panic!("expected tenant subtree filter");
+        };
+        assert_eq!(filter.root_tenant_id(), uuid(T1));
+        assert!(!filter.respect_barriers());
+        assert_eq!(filter.tenant_status(), Some(&["active".to_owned()][..]));
+    }
+
+    #[test]
+    fn group_predicates_follow_group_capabilities() {
+        let in_group = single_predicate_response(Predicate::InGroup(InGroupPredicate::new(
+            pep_properties::RESOURCE_ID,
+            [uuid(T1), uuid(T2)],
+        )));
+        let in_subtree = single_predicate_response(Predicate::InGroupSubtree(
+            InGroupSubtreePredicate::new(pep_properties::RESOURCE_ID, uuid(T1)),
+        ));
+
+        let membership = &[Capability::GroupMembership];
+        let scope =
+            compile_to_access_scope_with(&in_group, true, DEFAULT_PROPS, membership).unwrap();
+        assert!(matches!(
+            &scope.constraints()[0].filters()[0],
+            ScopeFilter::InGroup(f) if f.group_ids() == [uuid(T1), uuid(T2)]
+        ));
+        assert!(
+            compile_to_access_scope_with(&in_subtree, true, DEFAULT_PROPS, membership).is_err()
+        );
+
+        // Hierarchy support implies membership support
+        let hierarchy = &[Capability::GroupHierarchy];
+        assert!(compile_to_access_scope_with(&in_group, true, DEFAULT_PROPS, hierarchy).is_ok());
+        let scope =
+            compile_to_access_scope_with(&in_subtree, true, DEFAULT_PROPS, hierarchy).unwrap();
+        assert!(matches!(
+            &scope.constraints()[0].filters()[0],
+            ScopeFilter::InGroupSubtree(f) if f.root_group_id() == uuid(T1)
+        ));
+    }
+
+    #[test]
+    fn hierarchy_predicate_on_unsupported_property_fails() {
+        let response = single_predicate_response(Predicate::InGroup(InGroupPredicate::new(
+            "folder_id",
+            [uuid(T1)],
+        )));
+
+        let err = compile_to_access_scope_with(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[Capability::GroupMembership],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unsupported property: folder_id"));
+    }
 }
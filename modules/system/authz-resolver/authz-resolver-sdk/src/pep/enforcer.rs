@@ -21,7 +21,7 @@ use crate::models::{
     Action, BarrierMode, Capability, EvaluationRequest, EvaluationRequestContext, Resource,
     Subject, TenantContext, TenantMode,
 };
-use crate::pep::compiler::{ConstraintCompileError, compile_to_access_scope};
+use crate::pep::compiler::{ConstraintCompileError, compile_to_access_scope_with};
 
 /// Error from the PEP enforcement flow.
 #[derive(Debug, thiserror::Error)]
@@ -351,10 +351,11 @@ impl PolicyEnforcer {
             });
         }
 
-        Ok(compile_to_access_scope(
+        Ok(compile_to_access_scope_with(
             &response,
             require,
             resource.supported_properties,
+            &self.capabilities,
         )?)
     }
 }
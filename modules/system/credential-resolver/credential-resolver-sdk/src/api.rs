@@ -0,0 +1,107 @@
+//! Public API trait for the credential resolver.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+
+use crate::error::CredentialResolverError;
+use crate::models::{NewSecret, ResolvedSecret, SecretMetadata, SecretRef, SecretUpdate};
+
+/// Public API trait for the credential resolver gateway.
+///
+/// This trait is registered in `ClientHub` by the module:
+///
+/// ```ignore
+/// let resolver = hub.get::<dyn CredentialResolverClient>()?;
+/// let secret = resolver.resolve(&ctx, &SecretRef::parse("cred://openai-key")?).await?;
+/// ```
+///
+/// Every operation acts on the tenant of the `SecurityContext`
+/// (`subject_tenant_id`). Management operations only ever see the
+/// tenant's own secrets and never return secret values; only
+/// [`resolve`](Self::resolve) does.
+#[async_trait]
+pub trait CredentialResolverClient: Send + Sync {
+    /// Resolve a reference to its secret value.
+    ///
+    /// The tenant's own secret wins; otherwise the closest ancestor that
+    /// owns the reference with [`SharingMode::Shared`](crate::SharingMode::Shared)
+    /// supplies it.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if neither the tenant nor any ancestor sharing it owns
+    ///   the reference; ancestors that own it without sharing it are not
+    ///   revealed
+    /// - `NoPluginAvailable` / `ServiceUnavailable` if no storage plugin is ready
+    async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<ResolvedSecret, CredentialResolverError>;
+
+    /// Metadata of the secret [`resolve`](Self::resolve) would return,
+    /// without reading its value. Its `tenant_id` and `updated_at` tell
+    /// whether a value resolved earlier is still current.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`resolve`](Self::resolve).
+    async fn resolve_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// Store a new secret for the tenant.
+    ///
+    /// # Errors
+    ///
+    /// - `AlreadyExists` if the tenant already owns the reference
+    /// - `Validation` if the value is empty or too large
+    async fn create_secret(
+        &self,
+        ctx: &SecurityContext,
+        secret: NewSecret,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// Replace the value and/or sharing mode of one of the tenant's secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    /// - `Validation` if the new value is empty or too large
+    async fn update_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+        update: SecretUpdate,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// Get the metadata of one of the tenant's secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    async fn get_secret_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// List the metadata of all secrets the tenant owns, ordered by reference.
+    async fn list_secrets(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<Vec<SecretMetadata>, CredentialResolverError>;
+
+    /// Delete one of the tenant's secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    async fn delete_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError>;
+}
@@ -0,0 +1,47 @@
+//! Error types for the credential resolver module.
+
+use thiserror::Error;
+
+/// Errors that can occur when using the credential resolver API.
+///
+/// Messages carry secret references only, never secret values.
+#[derive(Debug, Error)]
+pub enum CredentialResolverError {
+    /// No secret with this reference exists for the tenant or any ancestor
+    /// that shares it.
+    #[error("secret not found: {reference}")]
+    NotFound {
+        /// The reference that was looked up.
+        reference: String,
+    },
+
+    /// The caller may not access the secret.
+    #[error("access to secret '{reference}' denied")]
+    AccessDenied {
+        /// The reference that was looked up.
+        reference: String,
+    },
+
+    /// The tenant already owns a secret with this reference.
+    #[error("secret already exists: {reference}")]
+    AlreadyExists {
+        /// The conflicting reference.
+        reference: String,
+    },
+
+    /// The reference or secret value is malformed.
+    #[error("validation error: {0}")]
+    Validation(String),
+
+    /// No plugin is available to handle the request.
+    #[error("no plugin available")]
+    NoPluginAvailable,
+
+    /// The plugin is not available yet.
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    /// An internal error occurred.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
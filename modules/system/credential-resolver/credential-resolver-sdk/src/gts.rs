@@ -0,0 +1,20 @@
+//! GTS schema definitions for credential resolver plugins.
+
+use gts_macros::struct_to_gts_schema;
+use modkit::gts::BaseModkitPluginV1;
+
+/// GTS type definition for credential resolver plugin instances.
+///
+/// # Instance ID Format
+///
+/// ```text
+/// gts.x.core.modkit.plugin.v1~<vendor>.<package>.credential_resolver.plugin.v1~
+/// ```
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseModkitPluginV1,
+    schema_id = "gts.x.core.modkit.plugin.v1~x.core.credential_resolver.plugin.v1~",
+    description = "Credential Resolver plugin specification",
+    properties = ""
+)]
+pub struct CredentialResolverPluginSpecV1;
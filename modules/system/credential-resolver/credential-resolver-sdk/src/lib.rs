@@ -0,0 +1,45 @@
+//! Credential Resolver SDK
+//!
+//! This crate provides the public API for the `credential-resolver` module:
+//!
+//! - [`CredentialResolverClient`] - Public API trait for consumers
+//! - [`CredentialResolverPluginClient`] - Plugin API trait for storage backends
+//! - [`SecretRef`], [`SecretValue`], [`SharingMode`], [`SecretMetadata`] - Domain models
+//! - [`CredentialResolverError`] - Error types
+//! - [`CredentialResolverPluginSpecV1`] - GTS schema for plugin discovery
+//!
+//! ## Usage
+//!
+//! Consumers obtain the client from `ClientHub`:
+//!
+//! ```ignore
+//! use credential_resolver_sdk::{CredentialResolverClient, NewSecret, SecretRef, SharingMode};
+//!
+//! let resolver = hub.get::<dyn CredentialResolverClient>()?;
+//!
+//! // Store a secret for the caller's tenant, visible to its descendants
+//! let reference = SecretRef::parse("cred://partner/openai-key")?;
+//! resolver
+//!     .create_secret(&ctx, NewSecret::new(reference.clone(), value).with_sharing(SharingMode::Shared))
+//!     .await?;
+//!
+//! // Resolve it for the caller's tenant, walking up the tenant hierarchy
+//! let resolved = resolver.resolve(&ctx, &reference).await?;
+//! let api_key = resolved.value.expose();
+//! ```
+
+pub mod api;
+pub mod error;
+pub mod gts;
+pub mod models;
+pub mod plugin_api;
+
+// Re-export main types at crate root
+pub use api::CredentialResolverClient;
+pub use error::CredentialResolverError;
+pub use gts::CredentialResolverPluginSpecV1;
+pub use models::{
+    NewSecret, ResolvedSecret, SECRET_REF_SCHEME, SecretMetadata, SecretRef, SecretUpdate,
+    SecretValue, SharingMode, StoredSecret, TenantId,
+};
+pub use plugin_api::CredentialResolverPluginClient;
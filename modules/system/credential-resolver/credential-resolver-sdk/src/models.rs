@@ -0,0 +1,251 @@
+//! Domain models for the credential resolver module.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use zeroize::Zeroize;
+
+use crate::error::CredentialResolverError;
+
+/// Unique identifier for a tenant.
+pub type TenantId = Uuid;
+
+/// Scheme prefix of every secret reference.
+pub const SECRET_REF_SCHEME: &str = "cred://";
+
+/// Maximum length of the key part of a secret reference.
+const MAX_KEY_LEN: usize = 255;
+
+/// Reference to a secret, e.g. `cred://partner/openai-key`.
+///
+/// The key after the scheme is one or more `/`-separated segments of ASCII
+/// letters, digits, `.`, `_` and `-`, at most 255 characters in total. The
+/// reference is the same for every tenant: which secret it resolves to
+/// depends on the tenant asking.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SecretRef(String);
+
+impl SecretRef {
+    /// Parse a full reference including the `cred://` scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Validation` if the scheme is missing or the key is malformed.
+    pub fn parse(reference: &str) -> Result<Self, CredentialResolverError> {
+        let key = reference.strip_prefix(SECRET_REF_SCHEME).ok_or_else(|| {
+            CredentialResolverError::Validation(format!(
+                "secret reference must start with '{SECRET_REF_SCHEME}'"
+            ))
+        })?;
+        Self::from_key(key)
+    }
+
+    /// Build a reference from its key, i.e. the part after `cred://`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Validation` if the key is malformed.
+    pub fn from_key(key: &str) -> Result<Self, CredentialResolverError> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(CredentialResolverError::Validation(format!(
+                "secret reference key must be 1 to {MAX_KEY_LEN} characters"
+            )));
+        }
+        let valid_segment = |s: &str| {
+            !s.is_empty()
+                && s.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+        };
+        if !key.split('/').all(valid_segment) {
+            return Err(CredentialResolverError::Validation(format!(
+                "invalid secret reference key '{key}': use '/'-separated segments of letters, digits, '.', '_' and '-'"
+            )));
+        }
+        Ok(Self(format!("{SECRET_REF_SCHEME}{key}")))
+    }
+
+    /// The full reference, including the scheme.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The key part of the reference, without the scheme.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.0[SECRET_REF_SCHEME.len()..]
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for SecretRef {
+    type Error = CredentialResolverError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<SecretRef> for String {
+    fn from(value: SecretRef) -> Self {
+        value.0
+    }
+}
+
+/// Secret material.
+///
+/// `Debug` and `Display` never print the value, and the memory is zeroed
+/// when the value is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretValue(String);
+
+impl SecretValue {
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Access the secret material.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Length of the secret material in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for SecretValue {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretValue([REDACTED])")
+    }
+}
+
+impl fmt::Display for SecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+/// Who besides the owning tenant may resolve a secret.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharingMode {
+    /// Only the owning tenant.
+    #[default]
+    Tenant,
+    /// The owning tenant and all of its descendants.
+    Shared,
+}
+
+impl SharingMode {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tenant => "tenant",
+            Self::Shared => "shared",
+        }
+    }
+}
+
+impl std::str::FromStr for SharingMode {
+    type Err = CredentialResolverError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tenant" => Ok(Self::Tenant),
+            "shared" => Ok(Self::Shared),
+            other => Err(CredentialResolverError::Validation(format!(
+                "unknown sharing mode '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Everything known about a stored secret except its value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretMetadata {
+    pub reference: SecretRef,
+    /// Tenant that owns the secret.
+    pub tenant_id: TenantId,
+    /// Subject that created the secret.
+    pub owner_id: Uuid,
+    pub sharing: SharingMode,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// A secret as returned by a storage plugin.
+#[derive(Debug, Clone)]
+pub struct StoredSecret {
+    pub metadata: SecretMetadata,
+    pub value: SecretValue,
+}
+
+/// Request to store a new secret for the caller's tenant.
+#[derive(Debug, Clone)]
+pub struct NewSecret {
+    pub reference: SecretRef,
+    pub value: SecretValue,
+    pub sharing: SharingMode,
+}
+
+impl NewSecret {
+    /// A tenant-private secret; use [`NewSecret::with_sharing`] to share it.
+    #[must_use]
+    pub fn new(reference: SecretRef, value: SecretValue) -> Self {
+        Self {
+            reference,
+            value,
+            sharing: SharingMode::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_sharing(mut self, sharing: SharingMode) -> Self {
+        self.sharing = sharing;
+        self
+    }
+}
+
+/// Changes to an existing secret. Fields left `None` are kept.
+#[derive(Debug, Clone, Default)]
+pub struct SecretUpdate {
+    pub value: Option<SecretValue>,
+    pub sharing: Option<SharingMode>,
+}
+
+/// Result of resolving a reference for a tenant.
+#[derive(Debug, Clone)]
+pub struct ResolvedSecret {
+    pub value: SecretValue,
+    /// Tenant that owns the secret; an ancestor when `is_inherited`.
+    pub owner_tenant_id: TenantId,
+    pub sharing: SharingMode,
+    /// Whether the secret came from an ancestor of the requesting tenant.
+    pub is_inherited: bool,
+}
@@ -0,0 +1,74 @@
+//! Plugin API trait for credential resolver storage backends.
+//!
+//! Plugins are plain per-tenant key-value stores. Tenant scoping, the
+//! hierarchy walk and sharing checks all live in the gateway, so every
+//! backend enforces them the same way.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::CredentialResolverError;
+use crate::models::{NewSecret, SecretMetadata, SecretRef, SecretUpdate, StoredSecret, TenantId};
+
+/// Plugin API trait for credential resolver implementations.
+///
+/// Each plugin registers this trait with a scoped `ClientHub` entry
+/// using its GTS instance ID as the scope.
+#[async_trait]
+pub trait CredentialResolverPluginClient: Send + Sync {
+    /// Get a tenant's secret including its value, or `None` if the tenant
+    /// does not own the reference.
+    async fn get(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<StoredSecret>, CredentialResolverError>;
+
+    /// Get a tenant's secret without reading its value.
+    async fn get_metadata(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<SecretMetadata>, CredentialResolverError>;
+
+    /// List the metadata of all secrets a tenant owns, ordered by reference.
+    async fn list(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<SecretMetadata>, CredentialResolverError>;
+
+    /// Store a new secret owned by `tenant_id` and created by `owner_id`.
+    ///
+    /// # Errors
+    ///
+    /// - `AlreadyExists` if the tenant already owns the reference
+    async fn create(
+        &self,
+        tenant_id: TenantId,
+        owner_id: Uuid,
+        secret: NewSecret,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// Apply `update` to a tenant's secret.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    async fn update(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+        update: SecretUpdate,
+    ) -> Result<SecretMetadata, CredentialResolverError>;
+
+    /// Delete a tenant's secret.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    async fn delete(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError>;
+}
@@ -0,0 +1,84 @@
+//! REST DTOs for secret management.
+//!
+//! Request DTOs carry secret values and therefore do not derive `Debug`;
+//! response DTOs never carry them.
+
+use credential_resolver_sdk::{SecretMetadata, SharingMode};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Who besides the owning tenant may resolve a secret.
+#[derive(Debug, Clone, Copy, Default)]
+#[modkit_macros::api_dto(request, response)]
+pub enum SharingModeDto {
+    /// Only the owning tenant.
+    #[default]
+    Tenant,
+    /// The owning tenant and all of its descendants.
+    Shared,
+}
+
+impl From<SharingModeDto> for SharingMode {
+    fn from(dto: SharingModeDto) -> Self {
+        match dto {
+            SharingModeDto::Tenant => Self::Tenant,
+            SharingModeDto::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<SharingMode> for SharingModeDto {
+    fn from(mode: SharingMode) -> Self {
+        match mode {
+            SharingMode::Tenant => Self::Tenant,
+            SharingMode::Shared => Self::Shared,
+        }
+    }
+}
+
+/// Request to store a new secret for the caller's tenant.
+#[modkit_macros::api_dto(request)]
+pub struct CreateSecretRequest {
+    /// Secret reference, e.g. `cred://partner/openai-key`.
+    pub reference: String,
+    pub value: String,
+    #[serde(default)]
+    pub sharing: SharingModeDto,
+}
+
+/// Request to change the value and/or sharing mode of a secret.
+#[modkit_macros::api_dto(request)]
+pub struct UpdateSecretRequest {
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub sharing: Option<SharingModeDto>,
+}
+
+/// Secret metadata. The value is never returned.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SecretMetadataDto {
+    pub reference: String,
+    pub tenant_id: Uuid,
+    /// Subject that created the secret.
+    pub owner_id: Uuid,
+    pub sharing: SharingModeDto,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<SecretMetadata> for SecretMetadataDto {
+    fn from(m: SecretMetadata) -> Self {
+        Self {
+            reference: m.reference.into(),
+            tenant_id: m.tenant_id,
+            owner_id: m.owner_id,
+            sharing: m.sharing.into(),
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
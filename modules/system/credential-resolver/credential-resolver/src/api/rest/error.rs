@@ -0,0 +1,111 @@
+use http::StatusCode;
+use modkit::api::problem::Problem;
+
+use crate::domain::error::DomainError;
+
+/// Convert domain errors to HTTP Problem responses.
+///
+/// Details name secret references only; secret values never reach an error.
+pub fn domain_error_to_problem(err: DomainError) -> Problem {
+    match err {
+        DomainError::SecretNotFound { reference } => Problem::new(
+            StatusCode::NOT_FOUND,
+            "Secret Not Found",
+            format!("Secret not found: {reference}"),
+        ),
+
+        DomainError::AccessDenied { reference } => Problem::new(
+            StatusCode::FORBIDDEN,
+            "Access Denied",
+            format!("Access to secret '{reference}' denied"),
+        ),
+
+        DomainError::AlreadyExists { reference } => Problem::new(
+            StatusCode::CONFLICT,
+            "Secret Already Exists",
+            format!("Secret already exists: {reference}"),
+        ),
+
+        DomainError::Validation(message) => {
+            Problem::new(StatusCode::BAD_REQUEST, "Validation Error", message)
+        }
+
+        e @ (DomainError::TypesRegistryUnavailable(_)
+        | DomainError::PluginNotFound { .. }
+        | DomainError::PluginUnavailable { .. }) => {
+            tracing::warn!(error = %e, "credential store unavailable");
+            Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service Unavailable",
+                "Credential store is not available",
+            )
+        }
+
+        e @ (DomainError::InvalidPluginInstance { .. } | DomainError::Internal(_)) => {
+            tracing::error!(error = %e, "credential resolver internal error");
+            Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error",
+                "An internal error occurred",
+            )
+        }
+    }
+}
+
+/// Implement Into<Problem> for `DomainError` so `?` works in handlers
+impl From<DomainError> for Problem {
+    fn from(e: DomainError) -> Self {
+        domain_error_to_problem(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_errors_map_to_client_statuses() {
+        let cases = [
+            (
+                DomainError::SecretNotFound {
+                    reference: "cred://a".to_owned(),
+                },
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                DomainError::AccessDenied {
+                    reference: "cred://a".to_owned(),
+                },
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                DomainError::AlreadyExists {
+                    reference: "cred://a".to_owned(),
+                },
+                StatusCode::CONFLICT,
+            ),
+            (
+                DomainError::Validation("bad".to_owned()),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                DomainError::PluginNotFound {
+                    vendor: "hyperspot".to_owned(),
+                },
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+        ];
+        for (err, status) in cases {
+            assert_eq!(domain_error_to_problem(err).status, status);
+        }
+    }
+
+    #[test]
+    fn internal_errors_hide_details() {
+        let problem = domain_error_to_problem(DomainError::Internal(
+            "database error: connection refused".to_owned(),
+        ));
+        assert_eq!(problem.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!problem.detail.contains("database"));
+    }
+}
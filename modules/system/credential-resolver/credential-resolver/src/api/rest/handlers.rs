@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::extract::{Extension, Path};
+use credential_resolver_sdk::{NewSecret, SecretRef, SecretUpdate, SecretValue};
+use modkit::api::prelude::*;
+use modkit_security::SecurityContext;
+
+use crate::domain::{DomainError, Service};
+
+use super::dto::{CreateSecretRequest, SecretMetadataDto, UpdateSecretRequest};
+
+/// Path segments carry the reference key (the part after `cred://`),
+/// percent-encoded when it contains `/`.
+fn reference_from_path(key: &str) -> Result<SecretRef, DomainError> {
+    SecretRef::from_key(key).map_err(DomainError::from)
+}
+
+pub async fn create_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Json(req): Json<CreateSecretRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let reference = SecretRef::parse(&req.reference).map_err(DomainError::from)?;
+    let secret =
+        NewSecret::new(reference, SecretValue::new(req.value)).with_sharing(req.sharing.into());
+    let metadata = svc.create_secret(&ctx, secret).await?;
+    Ok((StatusCode::CREATED, Json(SecretMetadataDto::from(metadata))))
+}
+
+pub async fn list_secrets(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+) -> ApiResult<JsonBody<Vec<SecretMetadataDto>>> {
+    let secrets = svc.list_secrets(&ctx).await?;
+    Ok(Json(secrets.into_iter().map(Into::into).collect()))
+}
+
+pub async fn get_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(key): Path<String>,
+) -> ApiResult<JsonBody<SecretMetadataDto>> {
+    let reference = reference_from_path(&key)?;
+    let metadata = svc.get_secret_metadata(&ctx, &reference).await?;
+    Ok(Json(metadata.into()))
+}
+
+pub async fn update_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(key): Path<String>,
+    Json(req): Json<UpdateSecretRequest>,
+) -> ApiResult<JsonBody<SecretMetadataDto>> {
+    let reference = reference_from_path(&key)?;
+    if req.value.is_none() && req.sharing.is_none() {
+        return Err(DomainError::Validation(
+            "update must change the value or the sharing mode".to_owned(),
+        )
+        .into());
+    }
+    let update = SecretUpdate {
+        value: req.value.map(SecretValue::new),
+        sharing: req.sharing.map(Into::into),
+    };
+    let metadata = svc.update_secret(&ctx, &reference, update).await?;
+    Ok(Json(metadata.into()))
+}
+
+pub async fn delete_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(key): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    let reference = reference_from_path(&key)?;
+    svc.delete_secret(&ctx, &reference).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
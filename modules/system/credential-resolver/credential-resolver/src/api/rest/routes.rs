@@ -0,0 +1,114 @@
+use crate::api::rest::{dto, handlers};
+use crate::domain::Service;
+use axum::{Extension, Router};
+use modkit::api::{OpenApiRegistry, OperationBuilder, operation_builder::LicenseFeature};
+use std::sync::Arc;
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+#[allow(clippy::needless_pass_by_value)] // Arc is intentionally passed by value for Extension layer
+pub fn register_routes(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    service: Arc<Service>,
+) -> Router {
+    // POST /credential-resolver/v1/secrets - Store a secret for the caller's tenant
+    router = OperationBuilder::post("/credential-resolver/v1/secrets")
+        .operation_id("credential_resolver.create_secret")
+        .summary("Create secret")
+        .description("Encrypt and store a secret owned by the caller's tenant")
+        .tag("Credentials")
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<dto::CreateSecretRequest>(openapi, "Secret reference, value and sharing")
+        .handler(handlers::create_secret)
+        .json_response_with_schema::<dto::SecretMetadataDto>(
+            openapi,
+            http::StatusCode::CREATED,
+            "Secret created",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /credential-resolver/v1/secrets - List the caller's tenant secrets
+    router = OperationBuilder::get("/credential-resolver/v1/secrets")
+        .operation_id("credential_resolver.list_secrets")
+        .summary("List secrets")
+        .description("List metadata of the secrets owned by the caller's tenant")
+        .tag("Credentials")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::list_secrets)
+        .json_response_with_schema::<Vec<dto::SecretMetadataDto>>(
+            openapi,
+            http::StatusCode::OK,
+            "Secret metadata",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /credential-resolver/v1/secrets/{key} - Get secret metadata
+    router = OperationBuilder::get("/credential-resolver/v1/secrets/{key}")
+        .operation_id("credential_resolver.get_secret")
+        .summary("Get secret metadata")
+        .description(
+            "Retrieve metadata of one of the caller's tenant secrets; the value is never returned",
+        )
+        .tag("Credentials")
+        .path_param("key", "Reference key after 'cred://', percent-encoded")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::get_secret)
+        .json_response_with_schema::<dto::SecretMetadataDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Secret metadata",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // PUT /credential-resolver/v1/secrets/{key} - Update value and/or sharing
+    router = OperationBuilder::put("/credential-resolver/v1/secrets/{key}")
+        .operation_id("credential_resolver.update_secret")
+        .summary("Update secret")
+        .description("Replace the value and/or sharing mode of one of the caller's tenant secrets")
+        .tag("Credentials")
+        .path_param("key", "Reference key after 'cred://', percent-encoded")
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<dto::UpdateSecretRequest>(openapi, "New value and/or sharing mode")
+        .handler(handlers::update_secret)
+        .json_response_with_schema::<dto::SecretMetadataDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Secret updated",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /credential-resolver/v1/secrets/{key} - Delete a secret
+    router = OperationBuilder::delete("/credential-resolver/v1/secrets/{key}")
+        .operation_id("credential_resolver.delete_secret")
+        .summary("Delete secret")
+        .description("Delete one of the caller's tenant secrets")
+        .tag("Credentials")
+        .path_param("key", "Reference key after 'cred://', percent-encoded")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::delete_secret)
+        .json_response(http::StatusCode::NO_CONTENT, "Secret deleted")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router = router.layer(Extension(service));
+
+    router
+}
@@ -0,0 +1,26 @@
+//! Configuration for the credential resolver module.
+
+use serde::Deserialize;
+
+/// Module configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CredentialResolverConfig {
+    /// Vendor selector used to pick a plugin implementation.
+    ///
+    /// The module queries types-registry for plugin instances matching
+    /// this vendor and selects the one with lowest priority.
+    pub vendor: String,
+
+    /// Largest secret value accepted by create and update, in bytes.
+    pub max_value_bytes: usize,
+}
+
+impl Default for CredentialResolverConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            max_value_bytes: 64 * 1024,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Domain errors for the credential resolver module.
+
+use credential_resolver_sdk::CredentialResolverError;
+use modkit_macros::domain_model;
+
+/// Internal domain errors.
+#[domain_model]
+#[derive(thiserror::Error, Debug)]
+pub enum DomainError {
+    #[error("types registry is not available: {0}")]
+    TypesRegistryUnavailable(String),
+
+    #[error("no plugin instances found for vendor '{vendor}'")]
+    PluginNotFound { vendor: String },
+
+    #[error("invalid plugin instance content for '{gts_id}': {reason}")]
+    InvalidPluginInstance { gts_id: String, reason: String },
+
+    #[error("plugin not available for '{gts_id}': {reason}")]
+    PluginUnavailable { gts_id: String, reason: String },
+
+    #[error("secret not found: {reference}")]
+    SecretNotFound { reference: String },
+
+    #[error("access to secret '{reference}' denied")]
+    AccessDenied { reference: String },
+
+    #[error("secret already exists: {reference}")]
+    AlreadyExists { reference: String },
+
+    #[error("validation error: {0}")]
+    Validation(String),
+
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl From<types_registry_sdk::TypesRegistryError> for DomainError {
+    fn from(e: types_registry_sdk::TypesRegistryError) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<modkit::client_hub::ClientHubError> for DomainError {
+    fn from(e: modkit::client_hub::ClientHubError) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DomainError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<modkit::plugins::ChoosePluginError> for DomainError {
+    fn from(e: modkit::plugins::ChoosePluginError) -> Self {
+        match e {
+            modkit::plugins::ChoosePluginError::InvalidPluginInstance { gts_id, reason } => {
+                Self::InvalidPluginInstance { gts_id, reason }
+            }
+            modkit::plugins::ChoosePluginError::PluginNotFound { vendor } => {
+                Self::PluginNotFound { vendor }
+            }
+        }
+    }
+}
+
+impl From<CredentialResolverError> for DomainError {
+    fn from(e: CredentialResolverError) -> Self {
+        match e {
+            CredentialResolverError::NotFound { reference } => Self::SecretNotFound { reference },
+            CredentialResolverError::AccessDenied { reference } => Self::AccessDenied { reference },
+            CredentialResolverError::AlreadyExists { reference } => {
+                Self::AlreadyExists { reference }
+            }
+            CredentialResolverError::Validation(msg) => Self::Validation(msg),
+            CredentialResolverError::NoPluginAvailable => Self::PluginNotFound {
+                vendor: "unknown".to_owned(),
+            },
+            CredentialResolverError::ServiceUnavailable(msg) => Self::PluginUnavailable {
+                gts_id: "unknown".to_owned(),
+                reason: msg,
+            },
+            CredentialResolverError::Internal(msg) => Self::Internal(msg),
+        }
+    }
+}
+
+impl From<DomainError> for CredentialResolverError {
+    fn from(e: DomainError) -> Self {
+        match e {
+            DomainError::PluginNotFound { .. } => Self::NoPluginAvailable,
+            DomainError::InvalidPluginInstance { gts_id, reason } => {
+                Self::Internal(format!("invalid plugin instance '{gts_id}': {reason}"))
+            }
+            DomainError::PluginUnavailable { gts_id, reason } => {
+                Self::ServiceUnavailable(format!("plugin not available for '{gts_id}': {reason}"))
+            }
+            DomainError::SecretNotFound { reference } => Self::NotFound { reference },
+            DomainError::AccessDenied { reference } => Self::AccessDenied { reference },
+            DomainError::AlreadyExists { reference } => Self::AlreadyExists { reference },
+            DomainError::Validation(msg) => Self::Validation(msg),
+            DomainError::TypesRegistryUnavailable(reason) | DomainError::Internal(reason) => {
+                Self::Internal(reason)
+            }
+        }
+    }
+}
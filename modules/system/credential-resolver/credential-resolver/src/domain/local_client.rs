@@ -0,0 +1,123 @@
+//! Local (in-process) client for the credential resolver module.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use credential_resolver_sdk::{
+    CredentialResolverClient, CredentialResolverError, NewSecret, ResolvedSecret, SecretMetadata,
+    SecretRef, SecretUpdate,
+};
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+
+use super::{DomainError, Service};
+
+/// Local client wrapping the credential resolver service.
+///
+/// Registered in `ClientHub` by the module during `init()`.
+#[domain_model]
+pub struct CredentialResolverLocalClient {
+    svc: Arc<Service>,
+}
+
+impl CredentialResolverLocalClient {
+    #[must_use]
+    pub fn new(svc: Arc<Service>) -> Self {
+        Self { svc }
+    }
+}
+
+/// Missing and inaccessible secrets are ordinary outcomes for callers;
+/// only infrastructure failures are logged as errors.
+fn log_and_convert(op: &str, e: DomainError) -> CredentialResolverError {
+    match e {
+        DomainError::SecretNotFound { .. }
+        | DomainError::AccessDenied { .. }
+        | DomainError::AlreadyExists { .. }
+        | DomainError::Validation(_) => {
+            tracing::debug!(operation = op, error = %e, "credential-resolver call rejected");
+        }
+        _ => tracing::error!(operation = op, error = ?e, "credential-resolver call failed"),
+    }
+    e.into()
+}
+
+#[async_trait]
+impl CredentialResolverClient for CredentialResolverLocalClient {
+    async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<ResolvedSecret, CredentialResolverError> {
+        self.svc
+            .resolve(ctx, reference)
+            .await
+            .map_err(|e| log_and_convert("resolve", e))
+    }
+
+    async fn resolve_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        self.svc
+            .resolve_metadata(ctx, reference)
+            .await
+            .map_err(|e| log_and_convert("resolve_metadata", e))
+    }
+
+    async fn create_secret(
+        &self,
+        ctx: &SecurityContext,
+        secret: NewSecret,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        self.svc
+            .create_secret(ctx, secret)
+            .await
+            .map_err(|e| log_and_convert("create_secret", e))
+    }
+
+    async fn update_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+        update: SecretUpdate,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        self.svc
+            .update_secret(ctx, reference, update)
+            .await
+            .map_err(|e| log_and_convert("update_secret", e))
+    }
+
+    async fn get_secret_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        self.svc
+            .get_secret_metadata(ctx, reference)
+            .await
+            .map_err(|e| log_and_convert("get_secret_metadata", e))
+    }
+
+    async fn list_secrets(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<Vec<SecretMetadata>, CredentialResolverError> {
+        self.svc
+            .list_secrets(ctx)
+            .await
+            .map_err(|e| log_and_convert("list_secrets", e))
+    }
+
+    async fn delete_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError> {
+        self.svc
+            .delete_secret(ctx, reference)
+            .await
+            .map_err(|e| log_and_convert("delete_secret", e))
+    }
+}
@@ -0,0 +1,9 @@
+//! Domain layer for the credential resolver.
+
+pub mod error;
+pub mod local_client;
+pub mod service;
+
+pub use error::DomainError;
+pub use local_client::CredentialResolverLocalClient;
+pub use service::Service;
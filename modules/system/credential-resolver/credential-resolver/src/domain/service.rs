@@ -0,0 +1,587 @@
+//! Domain service for the credential resolver module.
+//!
+//! Plugin discovery is lazy: resolved on first API call after
+//! types-registry is ready.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use credential_resolver_sdk::{
+    CredentialResolverPluginClient, CredentialResolverPluginSpecV1, NewSecret, ResolvedSecret,
+    SecretMetadata, SecretRef, SecretUpdate, SecretValue, SharingMode, TenantId,
+};
+use modkit::client_hub::{ClientHub, ClientScope};
+use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
+use modkit::telemetry::ThrottledLog;
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use tenant_resolver_sdk::{GetAncestorsOptions, TenantResolverClient, TenantResolverError};
+use tracing::info;
+use types_registry_sdk::{ListQuery, TypesRegistryClient};
+
+use super::error::DomainError;
+
+/// Throttle interval for unavailable plugin warnings.
+const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
+
+/// Credential resolver service.
+///
+/// Discovers the storage plugin via types-registry and owns all policy:
+/// every call is scoped to the tenant of the `SecurityContext`, and
+/// resolution walks up the tenant hierarchy honouring each secret's
+/// [`SharingMode`]. Plugins only store and load per-tenant secrets.
+#[domain_model]
+pub struct Service {
+    hub: Arc<ClientHub>,
+    vendor: String,
+    max_value_bytes: usize,
+    /// Shared selector for plugin instance IDs.
+    selector: GtsPluginSelector,
+    /// Throttle for plugin unavailable warnings.
+    unavailable_log_throttle: ThrottledLog,
+}
+
+impl Service {
+    /// Creates a new service with lazy plugin resolution.
+    #[must_use]
+    pub fn new(hub: Arc<ClientHub>, vendor: String, max_value_bytes: usize) -> Self {
+        Self {
+            hub,
+            vendor,
+            max_value_bytes,
+            selector: GtsPluginSelector::new(),
+            unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+        }
+    }
+
+    /// Lazily resolves and returns the plugin client.
+    async fn get_plugin(&self) -> Result<Arc<dyn CredentialResolverPluginClient>, DomainError> {
+        let instance_id = self.selector.get_or_init(|| self.resolve_plugin()).await?;
+        let scope = ClientScope::gts_id(instance_id.as_ref());
+
+        if let Some(client) = self
+            .hub
+            .try_get_scoped::<dyn CredentialResolverPluginClient>(&scope)
+        {
+            Ok(client)
+        } else {
+            if self.unavailable_log_throttle.should_log() {
+                tracing::warn!(
+                    plugin_gts_id = %instance_id,
+                    vendor = %self.vendor,
+                    "Plugin client not registered yet"
+                );
+            }
+            Err(DomainError::PluginUnavailable {
+                gts_id: instance_id.to_string(),
+                reason: "client not registered yet".into(),
+            })
+        }
+    }
+
+    /// Resolves the plugin instance from types-registry.
+    #[tracing::instrument(skip_all, fields(vendor = %self.vendor))]
+    async fn resolve_plugin(&self) -> Result<String, DomainError> {
+        info!("Resolving credential resolver plugin");
+
+        let registry = self
+            .hub
+            .get::<dyn TypesRegistryClient>()
+            .map_err(|e| DomainError::TypesRegistryUnavailable(e.to_string()))?;
+
+        let plugin_type_id = CredentialResolverPluginSpecV1::gts_schema_id().clone();
+
+        let instances = registry
+            .list(
+                ListQuery::new()
+                    .with_pattern(format!("{plugin_type_id}*"))
+                    .with_is_type(false),
+            )
+            .await?;
+
+        let gts_id = choose_plugin_instance::<CredentialResolverPluginSpecV1>(
+            &self.vendor,
+            instances.iter().map(|e| (e.gts_id.as_str(), &e.content)),
+        )?;
+        info!(plugin_gts_id = %gts_id, "Selected credential resolver plugin instance");
+
+        Ok(gts_id)
+    }
+
+    /// The tenant followed by its ancestors, closest first.
+    ///
+    /// The tenant resolver is looked up on every call: without one every
+    /// tenant is a root and only sees its own secrets.
+    async fn tenant_chain(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: TenantId,
+    ) -> Result<Vec<TenantId>, DomainError> {
+        let mut chain = vec![tenant_id];
+        let Ok(tenants) = self.hub.get::<dyn TenantResolverClient>() else {
+            return Ok(chain);
+        };
+        match tenants
+            .get_ancestors(ctx, tenant_id, &GetAncestorsOptions::default())
+            .await
+        {
+            Ok(resp) => chain.extend(resp.ancestors.into_iter().map(|t| t.id)),
+            Err(TenantResolverError::TenantNotFound { .. }) => {}
+            Err(e) => {
+                return Err(DomainError::Internal(format!(
+                    "failed to resolve ancestors of tenant {tenant_id}: {e}"
+                )));
+            }
+        }
+        Ok(chain)
+    }
+
+    fn validate_value(&self, value: &SecretValue) -> Result<(), DomainError> {
+        if value.is_empty() {
+            return Err(DomainError::Validation(
+                "secret value must not be empty".to_owned(),
+            ));
+        }
+        if value.len() > self.max_value_bytes {
+            return Err(DomainError::Validation(format!(
+                "secret value exceeds {} bytes",
+                self.max_value_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Resolve `reference` for the caller's tenant.
+    ///
+    /// # Errors
+    ///
+    /// - `SecretNotFound` if neither the tenant nor an ancestor sharing it
+    ///   owns the reference
+    /// - Plugin resolution errors
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %reference))]
+    pub async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<ResolvedSecret, DomainError> {
+        let plugin = self.get_plugin().await?;
+        let chain = self.tenant_chain(ctx, ctx.subject_tenant_id()).await?;
+        resolve_in_chain(plugin.as_ref(), &chain, reference).await
+    }
+
+    /// Metadata of the secret [`Self::resolve`] would return, without
+    /// decrypting it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::resolve`].
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %reference))]
+    pub async fn resolve_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, DomainError> {
+        let plugin = self.get_plugin().await?;
+        let chain = self.tenant_chain(ctx, ctx.subject_tenant_id()).await?;
+        let (_, metadata) = find_in_chain(plugin.as_ref(), &chain, reference).await?;
+        Ok(metadata)
+    }
+
+    /// Store a new secret for the caller's tenant.
+    ///
+    /// # Errors
+    ///
+    /// - `AlreadyExists` if the tenant already owns the reference
+    /// - `Validation` if the value is empty or too large
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %secret.reference))]
+    pub async fn create_secret(
+        &self,
+        ctx: &SecurityContext,
+        secret: NewSecret,
+    ) -> Result<SecretMetadata, DomainError> {
+        self.validate_value(&secret.value)?;
+        let plugin = self.get_plugin().await?;
+        plugin
+            .create(ctx.subject_tenant_id(), ctx.subject_id(), secret)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    /// Update one of the caller's tenant secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `SecretNotFound` if the tenant does not own the reference
+    /// - `Validation` if the new value is empty or too large
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %reference))]
+    pub async fn update_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+        update: SecretUpdate,
+    ) -> Result<SecretMetadata, DomainError> {
+        if let Some(value) = &update.value {
+            self.validate_value(value)?;
+        }
+        let plugin = self.get_plugin().await?;
+        plugin
+            .update(ctx.subject_tenant_id(), reference, update)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    /// Get the metadata of one of the caller's tenant secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `SecretNotFound` if the tenant does not own the reference
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %reference))]
+    pub async fn get_secret_metadata(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<SecretMetadata, DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .get_metadata(ctx.subject_tenant_id(), reference)
+            .await?
+            .ok_or_else(|| DomainError::SecretNotFound {
+                reference: reference.to_string(),
+            })
+    }
+
+    /// List the metadata of the caller's tenant secrets.
+    ///
+    /// # Errors
+    ///
+    /// - Plugin resolution errors
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id()))]
+    pub async fn list_secrets(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<Vec<SecretMetadata>, DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .list(ctx.subject_tenant_id())
+            .await
+            .map_err(DomainError::from)
+    }
+
+    /// Delete one of the caller's tenant secrets.
+    ///
+    /// # Errors
+    ///
+    /// - `SecretNotFound` if the tenant does not own the reference
+    #[tracing::instrument(skip_all, fields(tenant.id = %ctx.subject_tenant_id(), reference = %reference))]
+    pub async fn delete_secret(
+        &self,
+        ctx: &SecurityContext,
+        reference: &SecretRef,
+    ) -> Result<(), DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .delete(ctx.subject_tenant_id(), reference)
+            .await
+            .map_err(DomainError::from)
+    }
+}
+
+/// Walk `chain` (requesting tenant first, then its ancestors) and return the
+/// metadata of the first secret the requesting tenant may use.
+///
+/// The tenant's own secret always qualifies and shadows anything above it;
+/// an ancestor's secret only qualifies when it is shared. Ancestor secrets
+/// that are not shared are skipped and reported like missing ones, so the
+/// walk never reveals what an ancestor keeps to itself. Only metadata is
+/// read.
+pub(crate) async fn find_in_chain(
+    plugin: &dyn CredentialResolverPluginClient,
+    chain: &[TenantId],
+    reference: &SecretRef,
+) -> Result<(usize, SecretMetadata), DomainError> {
+    for (depth, &tenant_id) in chain.iter().enumerate() {
+        let Some(metadata) = plugin.get_metadata(tenant_id, reference).await? else {
+            continue;
+        };
+        if depth == 0 || metadata.sharing == SharingMode::Shared {
+            return Ok((depth, metadata));
+        }
+    }
+    Err(DomainError::SecretNotFound {
+        reference: reference.to_string(),
+    })
+}
+
+/// Resolve the secret [`find_in_chain`] selects. Only that secret's value is
+/// read.
+pub(crate) async fn resolve_in_chain(
+    plugin: &dyn CredentialResolverPluginClient,
+    chain: &[TenantId],
+    reference: &SecretRef,
+) -> Result<ResolvedSecret, DomainError> {
+    let (depth, metadata) = find_in_chain(plugin, chain, reference).await?;
+    // The secret may have been deleted since its metadata was read.
+    let secret = plugin
+        .get(metadata.tenant_id, reference)
+        .await?
+        .ok_or_else(|| DomainError::SecretNotFound {
+            reference: reference.to_string(),
+        })?;
+    Ok(ResolvedSecret {
+        value: secret.value,
+        owner_tenant_id: metadata.tenant_id,
+        sharing: secret.metadata.sharing,
+        is_inherited: depth > 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use credential_resolver_sdk::{CredentialResolverError, StoredSecret};
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Plugin keeping secrets in a map, enough to drive the hierarchy walk.
+    #[domain_model]
+    #[derive(Default)]
+    struct MapPlugin {
+        secrets: Mutex<HashMap<(TenantId, String), StoredSecret>>,
+        /// Tenants whose secret values were read.
+        values_read: Mutex<Vec<TenantId>>,
+    }
+
+    impl MapPlugin {
+        fn insert(&self, tenant_id: TenantId, reference: &str, value: &str, sharing: SharingMode) {
+            let now = OffsetDateTime::now_utc();
+            let reference = SecretRef::parse(reference).unwrap();
+            self.secrets.lock().unwrap().insert(
+                (tenant_id, reference.to_string()),
+                StoredSecret {
+                    metadata: SecretMetadata {
+                        reference,
+                        tenant_id,
+                        owner_id: Uuid::nil(),
+                        sharing,
+                        created_at: now,
+                        updated_at: now,
+                    },
+                    value: SecretValue::new(value),
+                },
+            );
+        }
+    }
+
+    #[async_trait]
+    impl CredentialResolverPluginClient for MapPlugin {
+        async fn get(
+            &self,
+            tenant_id: TenantId,
+            reference: &SecretRef,
+        ) -> Result<Option<StoredSecret>, CredentialResolverError> {
+            self.values_read.lock().unwrap().push(tenant_id);
+            Ok(self
+                .secrets
+                .lock()
+                .unwrap()
+                .get(&(tenant_id, reference.to_string()))
+                .cloned())
+        }
+
+        async fn get_metadata(
+            &self,
+            tenant_id: TenantId,
+            reference: &SecretRef,
+        ) -> Result<Option<SecretMetadata>, CredentialResolverError> {
+            Ok(self
+                .secrets
+                .lock()
+                .unwrap()
+                .get(&(tenant_id, reference.to_string()))
+                .map(|s| s.metadata.clone()))
+        }
+
+        async fn list(
+            &self,
+            _tenant_id: TenantId,
+        ) -> Result<Vec<SecretMetadata>, CredentialResolverError> {
+            unimplemented!()
+        }
+
+        async fn create(
+            &self,
+            _tenant_id: TenantId,
+            _owner_id: Uuid,
+            _secret: NewSecret,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            unimplemented!()
+        }
+
+        async fn update(
+            &self,
+            _tenant_id: TenantId,
+            _reference: &SecretRef,
+            _update: SecretUpdate,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            unimplemented!()
+        }
+
+        async fn delete(
+            &self,
+            _tenant_id: TenantId,
+            _reference: &SecretRef,
+        ) -> Result<(), CredentialResolverError> {
+            unimplemented!()
+        }
+    }
+
+    fn key() -> SecretRef {
+        SecretRef::parse("cred://partner/openai-key").unwrap()
+    }
+
+    #[tokio::test]
+    async fn own_secret_resolves_regardless_of_sharing() {
+        let (customer, partner) = (Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            customer,
+            "cred://partner/openai-key",
+            "own",
+            SharingMode::Tenant,
+        );
+
+        let resolved = resolve_in_chain(&plugin, &[customer, partner], &key())
+            .await
+            .unwrap();
+        assert_eq!(resolved.value.expose(), "own");
+        assert_eq!(resolved.owner_tenant_id, customer);
+        assert!(!resolved.is_inherited);
+    }
+
+    #[tokio::test]
+    async fn shared_ancestor_secret_is_inherited() {
+        let (customer, partner, root) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            partner,
+            "cred://partner/openai-key",
+            "partner",
+            SharingMode::Shared,
+        );
+        plugin.insert(
+            root,
+            "cred://partner/openai-key",
+            "root",
+            SharingMode::Shared,
+        );
+
+        let resolved = resolve_in_chain(&plugin, &[customer, partner, root], &key())
+            .await
+            .unwrap();
+        assert_eq!(resolved.value.expose(), "partner");
+        assert_eq!(resolved.owner_tenant_id, partner);
+        assert!(resolved.is_inherited);
+    }
+
+    #[tokio::test]
+    async fn own_secret_shadows_shared_ancestor_secret() {
+        let (customer, partner) = (Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            partner,
+            "cred://partner/openai-key",
+            "partner",
+            SharingMode::Shared,
+        );
+        plugin.insert(
+            customer,
+            "cred://partner/openai-key",
+            "own",
+            SharingMode::Tenant,
+        );
+
+        let resolved = resolve_in_chain(&plugin, &[customer, partner], &key())
+            .await
+            .unwrap();
+        assert_eq!(resolved.value.expose(), "own");
+    }
+
+    #[tokio::test]
+    async fn unshared_ancestor_secret_is_not_found() {
+        let (customer, partner) = (Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            partner,
+            "cred://partner/openai-key",
+            "partner",
+            SharingMode::Tenant,
+        );
+
+        let err = resolve_in_chain(&plugin, &[customer, partner], &key())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::SecretNotFound { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn walk_continues_past_unshared_ancestor() {
+        let (customer, partner, root) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            partner,
+            "cred://partner/openai-key",
+            "partner",
+            SharingMode::Tenant,
+        );
+        plugin.insert(
+            root,
+            "cred://partner/openai-key",
+            "root",
+            SharingMode::Shared,
+        );
+
+        let resolved = resolve_in_chain(&plugin, &[customer, partner, root], &key())
+            .await
+            .unwrap();
+        assert_eq!(resolved.value.expose(), "root");
+        assert_eq!(resolved.owner_tenant_id, root);
+        // Only the secret handed out is read.
+        assert_eq!(*plugin.values_read.lock().unwrap(), vec![root]);
+    }
+
+    #[tokio::test]
+    async fn secrets_of_other_branches_are_not_found() {
+        let (customer, sibling) = (Uuid::new_v4(), Uuid::new_v4());
+        let plugin = MapPlugin::default();
+        plugin.insert(
+            sibling,
+            "cred://partner/openai-key",
+            "sibling",
+            SharingMode::Shared,
+        );
+
+        let err = resolve_in_chain(&plugin, &[customer], &key())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::SecretNotFound { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn value_size_is_bounded() {
+        let svc = Service::new(Arc::new(ClientHub::new()), "hyperspot".to_owned(), 4);
+        assert!(svc.validate_value(&SecretValue::new("abcd")).is_ok());
+        assert!(matches!(
+            svc.validate_value(&SecretValue::new("abcde")),
+            Err(DomainError::Validation(_))
+        ));
+        assert!(matches!(
+            svc.validate_value(&SecretValue::new("")),
+            Err(DomainError::Validation(_))
+        ));
+    }
+}
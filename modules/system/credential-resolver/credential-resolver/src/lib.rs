@@ -0,0 +1,15 @@
+//! Credential Resolver Module
+//!
+//! This module discovers credential store plugins via types-registry and
+//! resolves `cred://` secret references for the calling tenant, walking up
+//! the tenant hierarchy for secrets that ancestors share.
+//!
+//! The module provides the `CredentialResolverClient` trait registered in
+//! `ClientHub` and a REST API for managing a tenant's secrets. Neither the
+//! REST API nor any management call returns secret values.
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod api;
+pub mod config;
+pub mod domain;
+pub mod module;
@@ -0,0 +1,101 @@
+//! Credential resolver module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use axum::Router;
+use credential_resolver_sdk::{CredentialResolverClient, CredentialResolverPluginSpecV1};
+use modkit::Module;
+use modkit::api::OpenApiRegistry;
+use modkit::context::ModuleCtx;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::api::rest::routes;
+use crate::config::CredentialResolverConfig;
+use crate::domain::{CredentialResolverLocalClient, Service};
+
+/// Credential Resolver module.
+///
+/// This module:
+/// 1. Registers the plugin schema in types-registry
+/// 2. Discovers plugin instances via types-registry
+/// 3. Resolves secret references for the calling tenant, enforcing tenant
+///    scoping and sharing, and routes storage calls to the selected plugin
+/// 4. Exposes secret management over REST without ever returning values
+///
+/// Plugin discovery is lazy: happens on first API call after types-registry
+/// is ready. The tenant hierarchy comes from the tenant resolver when one is
+/// registered.
+#[modkit::module(
+    name = "credential-resolver",
+    deps = ["types-registry"],
+    capabilities = [rest]
+)]
+pub(crate) struct CredentialResolver {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for CredentialResolver {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Module for CredentialResolver {
+    #[tracing::instrument(skip_all, fields(vendor))]
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: CredentialResolverConfig = ctx.config()?;
+        tracing::Span::current().record("vendor", cfg.vendor.as_str());
+        info!(vendor = %cfg.vendor, "Initializing {} module", Self::MODULE_NAME);
+
+        // Register plugin schema in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let schema_str = CredentialResolverPluginSpecV1::gts_schema_with_refs_as_string();
+        let schema_json: serde_json::Value = serde_json::from_str(&schema_str)?;
+        let results = registry.register(vec![schema_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+        info!(
+            schema_id = %CredentialResolverPluginSpecV1::gts_schema_id(),
+            "Registered plugin schema in types-registry"
+        );
+
+        // Create service
+        let hub = ctx.client_hub();
+        let svc = Arc::new(Service::new(hub, cfg.vendor, cfg.max_value_bytes));
+        self.service
+            .set(svc.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register local client in ClientHub
+        let api: Arc<dyn CredentialResolverClient> =
+            Arc::new(CredentialResolverLocalClient::new(svc));
+        ctx.client_hub()
+            .register::<dyn CredentialResolverClient>(api);
+
+        info!("{} module initialized successfully", Self::MODULE_NAME);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl modkit::contracts::RestApiCapability for CredentialResolver {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<Router> {
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("{} module not initialized", Self::MODULE_NAME))?
+            .clone();
+
+        Ok(routes::register_routes(router, openapi, service))
+    }
+}
@@ -0,0 +1,50 @@
+//! Configuration for the database credential resolver plugin.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Plugin configuration.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DbCredPluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// ID of the master key that encrypts new and updated secrets.
+    pub active_key_id: String,
+
+    /// Master keys by ID, each a base64-encoded 32-byte AES-256 key.
+    ///
+    /// Keep retired keys here until every secret sealed with them has been
+    /// rewritten; secrets remember the ID of the key that sealed them.
+    pub master_keys: HashMap<String, String>,
+}
+
+impl Default for DbCredPluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 100,
+            active_key_id: "default".to_owned(),
+            master_keys: HashMap::new(),
+        }
+    }
+}
+
+impl fmt::Debug for DbCredPluginConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut key_ids: Vec<&str> = self.master_keys.keys().map(String::as_str).collect();
+        key_ids.sort_unstable();
+        f.debug_struct("DbCredPluginConfig")
+            .field("vendor", &self.vendor)
+            .field("priority", &self.priority)
+            .field("active_key_id", &self.active_key_id)
+            .field("master_keys", &key_ids)
+            .finish()
+    }
+}
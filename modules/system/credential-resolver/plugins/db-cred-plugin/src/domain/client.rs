@@ -0,0 +1,326 @@
+//! Client implementation for the database credential resolver plugin.
+//!
+//! Implements `CredentialResolverPluginClient` using the domain service.
+
+use async_trait::async_trait;
+use credential_resolver_sdk::{
+    CredentialResolverError, CredentialResolverPluginClient, NewSecret, SecretMetadata, SecretRef,
+    SecretUpdate, StoredSecret, TenantId,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::repo::SecretRecord;
+use super::service::Service;
+
+#[async_trait]
+impl CredentialResolverPluginClient for Service {
+    async fn get(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<StoredSecret>, CredentialResolverError> {
+        let Some(record) = self.repo.find(tenant_id, reference).await? else {
+            return Ok(None);
+        };
+        let value = self.keyring.open(tenant_id, reference, &record.sealed)?;
+        Ok(Some(StoredSecret {
+            metadata: record.metadata(),
+            value,
+        }))
+    }
+
+    async fn get_metadata(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<SecretMetadata>, CredentialResolverError> {
+        Ok(self
+            .repo
+            .find(tenant_id, reference)
+            .await?
+            .map(|r| r.metadata()))
+    }
+
+    async fn list(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<SecretMetadata>, CredentialResolverError> {
+        Ok(self
+            .repo
+            .list(tenant_id)
+            .await?
+            .iter()
+            .map(SecretRecord::metadata)
+            .collect())
+    }
+
+    async fn create(
+        &self,
+        tenant_id: TenantId,
+        owner_id: Uuid,
+        secret: NewSecret,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        let sealed = self
+            .keyring
+            .seal(tenant_id, &secret.reference, &secret.value)?;
+        let now = OffsetDateTime::now_utc();
+        let record = SecretRecord {
+            id: Uuid::new_v4(),
+            tenant_id,
+            reference: secret.reference,
+            owner_id,
+            sharing: secret.sharing,
+            sealed,
+            created_at: now,
+            updated_at: now,
+        };
+        let metadata = record.metadata();
+        self.repo.insert(record).await?;
+        Ok(metadata)
+    }
+
+    async fn update(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+        update: SecretUpdate,
+    ) -> Result<SecretMetadata, CredentialResolverError> {
+        let mut record = self.repo.find(tenant_id, reference).await?.ok_or_else(|| {
+            CredentialResolverError::NotFound {
+                reference: reference.to_string(),
+            }
+        })?;
+
+        // A new value always gets a fresh data key under the active master key,
+        // which is also how secrets migrate off a retired master key.
+        if let Some(value) = update.value {
+            record.sealed = self.keyring.seal(tenant_id, reference, &value)?;
+        }
+        if let Some(sharing) = update.sharing {
+            record.sharing = sharing;
+        }
+        record.updated_at = OffsetDateTime::now_utc();
+
+        let metadata = record.metadata();
+        self.repo.update(record).await?;
+        Ok(metadata)
+    }
+
+    async fn delete(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError> {
+        self.repo.delete(tenant_id, reference).await
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use credential_resolver_sdk::{SecretValue, SharingMode};
+    use modkit_macros::domain_model;
+
+    use super::*;
+    use crate::domain::envelope::{KEY_LEN, Keyring};
+    use crate::domain::repo::SecretRepository;
+
+    #[domain_model]
+    #[derive(Default)]
+    struct MemRepo {
+        rows: Mutex<HashMap<(TenantId, SecretRef), SecretRecord>>,
+    }
+
+    #[async_trait]
+    impl SecretRepository for MemRepo {
+        async fn find(
+            &self,
+            tenant_id: TenantId,
+            reference: &SecretRef,
+        ) -> Result<Option<SecretRecord>, CredentialResolverError> {
+            let rows = self.rows.lock().unwrap();
+            Ok(rows.get(&(tenant_id, reference.clone())).cloned())
+        }
+
+        async fn list(
+            &self,
+            tenant_id: TenantId,
+        ) -> Result<Vec<SecretRecord>, CredentialResolverError> {
+            let rows = self.rows.lock().unwrap();
+            let mut out: Vec<_> = rows
+                .values()
+                .filter(|r| r.tenant_id == tenant_id)
+                .cloned()
+                .collect();
+            out.sort_by(|a, b| a.reference.as_str().cmp(b.reference.as_str()));
+            Ok(out)
+        }
+
+        async fn insert(&self, record: SecretRecord) -> Result<(), CredentialResolverError> {
+            let mut rows = self.rows.lock().unwrap();
+            let key = (record.tenant_id, record.reference.clone());
+            if rows.contains_key(&key) {
+                return Err(CredentialResolverError::AlreadyExists {
+                    reference: record.reference.to_string(),
+                });
+            }
+            rows.insert(key, record);
+            Ok(())
+        }
+
+        async fn update(&self, record: SecretRecord) -> Result<(), CredentialResolverError> {
+            let mut rows = self.rows.lock().unwrap();
+            rows.insert((record.tenant_id, record.reference.clone()), record);
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            tenant_id: TenantId,
+            reference: &SecretRef,
+        ) -> Result<(), CredentialResolverError> {
+            let mut rows = self.rows.lock().unwrap();
+            rows.remove(&(tenant_id, reference.clone()))
+                .map(|_| ())
+                .ok_or_else(|| CredentialResolverError::NotFound {
+                    reference: reference.to_string(),
+                })
+        }
+    }
+
+    fn keyring(active: &str) -> Keyring {
+        let keys: HashMap<String, String> = [("k1", 1u8), ("k2", 2u8)]
+            .into_iter()
+            .map(|(id, b)| (id.to_owned(), STANDARD.encode([b; KEY_LEN])))
+            .collect();
+        Keyring::from_config(active, &keys).unwrap()
+    }
+
+    fn service() -> (Service, Arc<MemRepo>) {
+        let repo = Arc::new(MemRepo::default());
+        (Service::new(repo.clone(), keyring("k1")), repo)
+    }
+
+    fn reference(key: &str) -> SecretRef {
+        SecretRef::from_key(key).unwrap()
+    }
+
+    #[tokio::test]
+    async fn create_then_get_returns_value() {
+        let (svc, _) = service();
+        let tenant = Uuid::new_v4();
+        let owner = Uuid::new_v4();
+
+        let meta = svc
+            .create(
+                tenant,
+                owner,
+                NewSecret::new(reference("openai-key"), SecretValue::new("sk-1"))
+                    .with_sharing(SharingMode::Shared),
+            )
+            .await
+            .unwrap();
+        assert_eq!(meta.tenant_id, tenant);
+        assert_eq!(meta.owner_id, owner);
+        assert_eq!(meta.sharing, SharingMode::Shared);
+
+        let stored = svc
+            .get(tenant, &reference("openai-key"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.value.expose(), "sk-1");
+        assert_eq!(stored.metadata, meta);
+
+        assert!(
+            svc.get(Uuid::new_v4(), &reference("openai-key"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_create_conflicts() {
+        let (svc, _) = service();
+        let tenant = Uuid::new_v4();
+        let secret = || NewSecret::new(reference("k"), SecretValue::new("v"));
+
+        svc.create(tenant, Uuid::nil(), secret()).await.unwrap();
+        assert!(matches!(
+            svc.create(tenant, Uuid::nil(), secret()).await,
+            Err(CredentialResolverError::AlreadyExists { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_reseals_value_under_active_key() {
+        let (_, repo) = service();
+        let tenant = Uuid::new_v4();
+        let r = reference("k");
+
+        let old = Service::new(repo.clone(), keyring("k1"));
+        old.create(
+            tenant,
+            Uuid::nil(),
+            NewSecret::new(r.clone(), SecretValue::new("v1")),
+        )
+        .await
+        .unwrap();
+
+        let rotated = Service::new(repo.clone(), keyring("k2"));
+        let meta = rotated
+            .update(
+                tenant,
+                &r,
+                SecretUpdate {
+                    sharing: Some(SharingMode::Shared),
+                    ..SecretUpdate::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(meta.sharing, SharingMode::Shared);
+        let record = repo.find(tenant, &r).await.unwrap().unwrap();
+        assert_eq!(record.sealed.key_id, "k1");
+
+        rotated
+            .update(
+                tenant,
+                &r,
+                SecretUpdate {
+                    value: Some(SecretValue::new("v2")),
+                    ..SecretUpdate::default()
+                },
+            )
+            .await
+            .unwrap();
+        let record = repo.find(tenant, &r).await.unwrap().unwrap();
+        assert_eq!(record.sealed.key_id, "k2");
+        assert_eq!(
+            rotated
+                .get(tenant, &r)
+                .await
+                .unwrap()
+                .unwrap()
+                .value
+                .expose(),
+            "v2"
+        );
+    }
+
+    #[tokio::test]
+    async fn update_of_missing_secret_is_not_found() {
+        let (svc, _) = service();
+        let err = svc
+            .update(Uuid::new_v4(), &reference("k"), SecretUpdate::default())
+            .await;
+        assert!(matches!(err, Err(CredentialResolverError::NotFound { .. })));
+    }
+}
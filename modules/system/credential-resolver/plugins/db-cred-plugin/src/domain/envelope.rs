@@ -0,0 +1,330 @@
+//! Envelope encryption for stored secrets.
+//!
+//! Every secret gets its own random data key. The data key encrypts the
+//! value with AES-256-GCM and is itself encrypted ("wrapped") with one of the
+//! configured master keys. Both ciphertexts are bound to the owning tenant
+//! and reference through the AEAD associated data, so a row copied to another
+//! tenant or reference fails to decrypt instead of leaking its value.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use aws_lc_rs::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use aws_lc_rs::rand::{SecureRandom, SystemRandom};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use credential_resolver_sdk::{CredentialResolverError, SecretRef, SecretValue, TenantId};
+use modkit_macros::domain_model;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Length of master and data keys in bytes (AES-256).
+pub const KEY_LEN: usize = 32;
+
+/// A secret value in its stored form.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealedValue {
+    /// ID of the master key that wrapped the data key.
+    pub key_id: String,
+    /// Data key encrypted with the master key: `nonce || ciphertext || tag`.
+    pub wrapped_key: Vec<u8>,
+    /// Value encrypted with the data key: `nonce || ciphertext || tag`.
+    pub ciphertext: Vec<u8>,
+}
+
+/// Master keys plus the ID of the one that seals new values.
+#[domain_model]
+pub struct Keyring {
+    active_key_id: String,
+    keys: HashMap<String, LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl Keyring {
+    /// Build a keyring from base64-encoded master keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key is not valid base64, is not [`KEY_LEN`]
+    /// bytes long, or if `active_key_id` is not among `master_keys`.
+    pub fn from_config<S: BuildHasher>(
+        active_key_id: &str,
+        master_keys: &HashMap<String, String, S>,
+    ) -> anyhow::Result<Self> {
+        if !master_keys.contains_key(active_key_id) {
+            anyhow::bail!("active master key '{active_key_id}' is not configured");
+        }
+
+        let mut keys = HashMap::with_capacity(master_keys.len());
+        for (id, encoded) in master_keys {
+            let raw = Zeroizing::new(
+                STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|e| anyhow::anyhow!("master key '{id}' is not valid base64: {e}"))?,
+            );
+            if raw.len() != KEY_LEN {
+                anyhow::bail!(
+                    "master key '{id}' must be {KEY_LEN} bytes, got {}",
+                    raw.len()
+                );
+            }
+            keys.insert(id.clone(), aes_key(&raw)?);
+        }
+
+        Ok(Self {
+            active_key_id: active_key_id.to_owned(),
+            keys,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// ID of the master key used by [`seal`](Self::seal).
+    #[must_use]
+    pub fn active_key_id(&self) -> &str {
+        &self.active_key_id
+    }
+
+    /// Encrypt `value` under a fresh data key wrapped with the active master key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the system random generator or cipher fails.
+    pub fn seal(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+        value: &SecretValue,
+    ) -> Result<SealedValue, CredentialResolverError> {
+        let kek = self
+            .keys
+            .get(&self.active_key_id)
+            .ok_or_else(|| unknown_key(&self.active_key_id))?;
+        let aad = associated_data(tenant_id, reference);
+
+        let mut dek = Zeroizing::new([0u8; KEY_LEN]);
+        self.rng
+            .fill(&mut dek[..])
+            .map_err(|_| crypto_err("failed to generate data key"))?;
+        let dek_key = aes_key(&dek[..]).map_err(|_| crypto_err("invalid data key"))?;
+
+        Ok(SealedValue {
+            key_id: self.active_key_id.clone(),
+            wrapped_key: self.encrypt(kek, &aad, &dek[..])?,
+            ciphertext: self.encrypt(&dek_key, &aad, value.expose().as_bytes())?,
+        })
+    }
+
+    /// Decrypt a value previously produced by [`seal`](Self::seal) for the
+    /// same tenant and reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Internal` if the master key is not configured or the stored
+    /// value fails authentication.
+    pub fn open(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+        sealed: &SealedValue,
+    ) -> Result<SecretValue, CredentialResolverError> {
+        let kek = self
+            .keys
+            .get(&sealed.key_id)
+            .ok_or_else(|| unknown_key(&sealed.key_id))?;
+        let aad = associated_data(tenant_id, reference);
+
+        let dek = decrypt(kek, &aad, &sealed.wrapped_key)?;
+        let dek_key = aes_key(&dek).map_err(|_| crypto_err("invalid data key"))?;
+        let mut plain = decrypt(&dek_key, &aad, &sealed.ciphertext)?;
+
+        String::from_utf8(std::mem::take(&mut *plain))
+            .map(SecretValue::new)
+            .map_err(|e| {
+                e.into_bytes().zeroize();
+                crypto_err("secret value is not valid UTF-8")
+            })
+    }
+
+    fn encrypt(
+        &self,
+        key: &LessSafeKey,
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, CredentialResolverError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| crypto_err("failed to generate nonce"))?;
+
+        let mut sealed = plaintext.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut sealed,
+        )
+        .map_err(|_| crypto_err("failed to encrypt"))?;
+
+        let mut out = Vec::with_capacity(NONCE_LEN + sealed.len());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+}
+
+fn decrypt(
+    key: &LessSafeKey,
+    aad: &[u8],
+    data: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, CredentialResolverError> {
+    if data.len() < NONCE_LEN {
+        return Err(crypto_err("stored ciphertext is truncated"));
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| crypto_err("stored nonce is invalid"))?;
+
+    let mut buf = Zeroizing::new(ciphertext.to_vec());
+    let len = key
+        .open_in_place(nonce, Aad::from(aad), &mut buf[..])
+        .map_err(|_| crypto_err("failed to decrypt secret"))?
+        .len();
+    buf.truncate(len);
+    Ok(buf)
+}
+
+fn aes_key(raw: &[u8]) -> anyhow::Result<LessSafeKey> {
+    let key =
+        UnboundKey::new(&AES_256_GCM, raw).map_err(|_| anyhow::anyhow!("invalid AES-256 key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn associated_data(tenant_id: TenantId, reference: &SecretRef) -> Vec<u8> {
+    format!("{tenant_id}\n{}", reference.as_str()).into_bytes()
+}
+
+fn unknown_key(key_id: &str) -> CredentialResolverError {
+    CredentialResolverError::Internal(format!("master key '{key_id}' is not configured"))
+}
+
+fn crypto_err(msg: &str) -> CredentialResolverError {
+    CredentialResolverError::Internal(msg.to_owned())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn encoded(byte: u8) -> String {
+        STANDARD.encode([byte; KEY_LEN])
+    }
+
+    fn keyring(active: &str, ids: &[(&str, u8)]) -> Keyring {
+        let keys: HashMap<String, String> = ids
+            .iter()
+            .map(|(id, byte)| ((*id).to_owned(), encoded(*byte)))
+            .collect();
+        Keyring::from_config(active, &keys).unwrap()
+    }
+
+    fn reference(key: &str) -> SecretRef {
+        SecretRef::from_key(key).unwrap()
+    }
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let ring = keyring("k1", &[("k1", 1)]);
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+
+        let sealed = ring
+            .seal(tenant, &r, &SecretValue::new("sk-live-123"))
+            .unwrap();
+        assert_eq!(sealed.key_id, "k1");
+        assert!(
+            !sealed
+                .ciphertext
+                .windows(b"sk-live-123".len())
+                .any(|w| w == b"sk-live-123")
+        );
+
+        let opened = ring.open(tenant, &r, &sealed).unwrap();
+        assert_eq!(opened.expose(), "sk-live-123");
+    }
+
+    #[test]
+    fn each_seal_uses_a_fresh_data_key() {
+        let ring = keyring("k1", &[("k1", 1)]);
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+        let value = SecretValue::new("same");
+
+        let a = ring.seal(tenant, &r, &value).unwrap();
+        let b = ring.seal(tenant, &r, &value).unwrap();
+        assert_ne!(a.wrapped_key, b.wrapped_key);
+        assert_ne!(a.ciphertext, b.ciphertext);
+    }
+
+    #[test]
+    fn sealed_value_is_bound_to_tenant_and_reference() {
+        let ring = keyring("k1", &[("k1", 1)]);
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+        let sealed = ring.seal(tenant, &r, &SecretValue::new("v")).unwrap();
+
+        assert!(ring.open(Uuid::new_v4(), &r, &sealed).is_err());
+        assert!(ring.open(tenant, &reference("other"), &sealed).is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let ring = keyring("k1", &[("k1", 1)]);
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+        let mut sealed = ring.seal(tenant, &r, &SecretValue::new("v")).unwrap();
+
+        let last = sealed.ciphertext.len() - 1;
+        sealed.ciphertext[last] ^= 0x01;
+        assert!(ring.open(tenant, &r, &sealed).is_err());
+    }
+
+    #[test]
+    fn retired_key_still_opens_after_rotation() {
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+        let old = keyring("k1", &[("k1", 1)]);
+        let sealed = old.seal(tenant, &r, &SecretValue::new("v")).unwrap();
+
+        let rotated = keyring("k2", &[("k1", 1), ("k2", 2)]);
+        assert_eq!(rotated.open(tenant, &r, &sealed).unwrap().expose(), "v");
+        assert_eq!(
+            rotated
+                .seal(tenant, &r, &SecretValue::new("v"))
+                .unwrap()
+                .key_id,
+            "k2"
+        );
+
+        let dropped = keyring("k2", &[("k2", 2)]);
+        assert!(matches!(
+            dropped.open(tenant, &r, &sealed),
+            Err(CredentialResolverError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        let mut keys = HashMap::new();
+        keys.insert("k1".to_owned(), encoded(1));
+        assert!(Keyring::from_config("missing", &keys).is_err());
+
+        keys.insert("short".to_owned(), STANDARD.encode([0u8; 16]));
+        assert!(Keyring::from_config("k1", &keys).is_err());
+
+        keys.remove("short");
+        keys.insert("garbage".to_owned(), "not base64!".to_owned());
+        assert!(Keyring::from_config("k1", &keys).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Domain layer for the database credential resolver plugin.
+
+mod client;
+pub mod envelope;
+pub mod repo;
+pub mod service;
+
+pub use envelope::Keyring;
+pub use repo::{SecretRecord, SecretRepository};
+pub use service::Service;
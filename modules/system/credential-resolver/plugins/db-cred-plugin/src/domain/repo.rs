@@ -0,0 +1,84 @@
+//! Storage abstraction for sealed secrets.
+
+use async_trait::async_trait;
+use credential_resolver_sdk::{
+    CredentialResolverError, SecretMetadata, SecretRef, SharingMode, TenantId,
+};
+use modkit_macros::domain_model;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::envelope::SealedValue;
+
+/// A stored secret: its metadata plus the sealed value.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRecord {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub reference: SecretRef,
+    pub owner_id: Uuid,
+    pub sharing: SharingMode,
+    pub sealed: SealedValue,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl SecretRecord {
+    /// Metadata view of the record, without the sealed value.
+    #[must_use]
+    pub fn metadata(&self) -> SecretMetadata {
+        SecretMetadata {
+            reference: self.reference.clone(),
+            tenant_id: self.tenant_id,
+            owner_id: self.owner_id,
+            sharing: self.sharing,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// Tenant-scoped persistence for [`SecretRecord`]s.
+///
+/// Implementations never see plaintext values; sealing and opening happen in
+/// the service.
+#[async_trait]
+pub trait SecretRepository: Send + Sync {
+    /// Find a tenant's record by reference.
+    async fn find(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<SecretRecord>, CredentialResolverError>;
+
+    /// All records of a tenant, ordered by reference.
+    async fn list(&self, tenant_id: TenantId)
+    -> Result<Vec<SecretRecord>, CredentialResolverError>;
+
+    /// Insert a new record.
+    ///
+    /// # Errors
+    ///
+    /// - `AlreadyExists` if the tenant already owns the reference
+    async fn insert(&self, record: SecretRecord) -> Result<(), CredentialResolverError>;
+
+    /// Overwrite the sharing mode, sealed value and `updated_at` of an
+    /// existing record.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the record no longer exists
+    async fn update(&self, record: SecretRecord) -> Result<(), CredentialResolverError>;
+
+    /// Delete a tenant's record by reference.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not own the reference
+    async fn delete(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError>;
+}
@@ -0,0 +1,26 @@
+//! Domain service for the database credential resolver plugin.
+
+use std::sync::Arc;
+
+use modkit_macros::domain_model;
+
+use super::envelope::Keyring;
+use super::repo::SecretRepository;
+
+/// Database credential resolver service.
+///
+/// Seals values with the [`Keyring`] before they reach the repository and
+/// opens them only when a caller asks for the value itself.
+#[domain_model]
+pub struct Service {
+    pub(super) repo: Arc<dyn SecretRepository>,
+    pub(super) keyring: Keyring,
+}
+
+impl Service {
+    /// Creates a new service over `repo`, sealing with `keyring`.
+    #[must_use]
+    pub fn new(repo: Arc<dyn SecretRepository>, keyring: Keyring) -> Self {
+        Self { repo, keyring }
+    }
+}
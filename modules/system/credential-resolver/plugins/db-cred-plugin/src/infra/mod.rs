@@ -0,0 +1,3 @@
+//! Infrastructure layer for the database credential resolver plugin.
+
+pub mod storage;
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+CREATE TABLE IF NOT EXISTS credential_secret (
+    id UUID PRIMARY KEY NOT NULL,
+    tenant_id UUID NOT NULL,
+    reference VARCHAR(263) NOT NULL,
+    owner_id UUID NOT NULL,
+    sharing VARCHAR(16) NOT NULL,
+    key_id VARCHAR(64) NOT NULL,
+    wrapped_key BYTEA NOT NULL,
+    ciphertext BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT uq_credential_secret_tenant_reference UNIQUE (tenant_id, reference)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS credential_secret (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id VARCHAR(36) NOT NULL,
+    reference VARCHAR(263) NOT NULL,
+    owner_id VARCHAR(36) NOT NULL,
+    sharing VARCHAR(16) NOT NULL,
+    key_id VARCHAR(64) NOT NULL,
+    wrapped_key VARBINARY(128) NOT NULL,
+    ciphertext MEDIUMBLOB NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE KEY uq_credential_secret_tenant_reference (tenant_id, reference)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+CREATE TABLE IF NOT EXISTS credential_secret (
+    id TEXT PRIMARY KEY NOT NULL,
+    tenant_id TEXT NOT NULL,
+    reference TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    sharing TEXT NOT NULL,
+    key_id TEXT NOT NULL,
+    wrapped_key BLOB NOT NULL,
+    ciphertext BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, reference)
+);
+                "
+            }
+        };
+
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS credential_secret;")
+            .await?;
+        Ok(())
+    }
+}
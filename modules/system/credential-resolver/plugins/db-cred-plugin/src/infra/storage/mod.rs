@@ -0,0 +1,7 @@
+//! Database storage for sealed secrets.
+
+pub mod entity;
+pub mod migrations;
+mod sea_repo;
+
+pub use sea_repo::SeaOrmSecretRepo;
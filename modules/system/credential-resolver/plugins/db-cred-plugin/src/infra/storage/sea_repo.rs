@@ -0,0 +1,359 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use credential_resolver_sdk::{CredentialResolverError, SecretRef, SharingMode, TenantId};
+use modkit_db::secure::{
+    ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, QueryFilter, SqlErr};
+
+use super::entity::{ActiveModel, Column, Entity as SecretEntity, Model};
+use crate::domain::envelope::SealedValue;
+use crate::domain::repo::{SecretRecord, SecretRepository};
+
+/// `SeaORM`-backed secret repository.
+///
+/// Reference uniqueness per tenant is enforced by the `(tenant_id, reference)`
+/// unique constraint. Only sealed values are written; the table never holds
+/// plaintext.
+pub struct SeaOrmSecretRepo {
+    db: Arc<DBProvider<DbError>>,
+}
+
+impl SeaOrmSecretRepo {
+    #[must_use]
+    pub fn new(db: Arc<DBProvider<DbError>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SecretRepository for SeaOrmSecretRepo {
+    async fn find(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<Option<SecretRecord>, CredentialResolverError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        SecretEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Column::Reference.eq(reference.as_str())))
+            .one(&conn)
+            .await
+            .map_err(db_err)?
+            .map(record_from_model)
+            .transpose()
+    }
+
+    async fn list(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<SecretRecord>, CredentialResolverError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        SecretEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Reference, Order::Asc)
+            .all(&conn)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(record_from_model)
+            .collect()
+    }
+
+    async fn insert(&self, record: SecretRecord) -> Result<(), CredentialResolverError> {
+        let scope = AccessScope::for_tenant(record.tenant_id);
+        let reference = record.reference.to_string();
+        let conn = self.db.conn().map_err(db_err)?;
+        secure_insert::<SecretEntity>(record_to_active_model(record, true), &scope, &conn)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    CredentialResolverError::AlreadyExists { reference }
+                } else {
+                    db_err(e)
+                }
+            })?;
+        Ok(())
+    }
+
+    async fn update(&self, record: SecretRecord) -> Result<(), CredentialResolverError> {
+        let id = record.id;
+        let scope = AccessScope::for_tenant(record.tenant_id);
+        let reference = record.reference.to_string();
+        let conn = self.db.conn().map_err(db_err)?;
+        secure_update_with_scope::<SecretEntity>(
+            record_to_active_model(record, false),
+            &scope,
+            id,
+            &conn,
+        )
+        .await
+        .map_err(|e| match e {
+            ScopeError::Denied(_) => CredentialResolverError::NotFound { reference },
+            e => db_err(e),
+        })?;
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        tenant_id: TenantId,
+        reference: &SecretRef,
+    ) -> Result<(), CredentialResolverError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = SecretEntity::delete_many()
+            .filter(Condition::all().add(Column::Reference.eq(reference.as_str())))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(db_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(CredentialResolverError::NotFound {
+                reference: reference.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn record_to_active_model(r: SecretRecord, with_created_at: bool) -> ActiveModel {
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    ActiveModel {
+        id: Set(r.id),
+        tenant_id: Set(r.tenant_id),
+        reference: Set(r.reference.into()),
+        owner_id: Set(r.owner_id),
+        sharing: Set(r.sharing.as_str().to_owned()),
+        key_id: Set(r.sealed.key_id),
+        wrapped_key: Set(r.sealed.wrapped_key),
+        ciphertext: Set(r.sealed.ciphertext),
+        created_at: if with_created_at {
+            Set(r.created_at)
+        } else {
+            NotSet
+        },
+        updated_at: Set(r.updated_at),
+    }
+}
+
+fn record_from_model(m: Model) -> Result<SecretRecord, CredentialResolverError> {
+    Ok(SecretRecord {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        reference: SecretRef::parse(&m.reference).map_err(|e| corrupt_row(m.id, "reference", e))?,
+        owner_id: m.owner_id,
+        sharing: SharingMode::from_str(&m.sharing).map_err(|e| corrupt_row(m.id, "sharing", e))?,
+        sealed: SealedValue {
+            key_id: m.key_id,
+            wrapped_key: m.wrapped_key,
+            ciphertext: m.ciphertext,
+        },
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    })
+}
+
+fn corrupt_row(id: uuid::Uuid, column: &str, e: impl Display) -> CredentialResolverError {
+    CredentialResolverError::Internal(format!("stored secret {id} has invalid {column}: {e}"))
+}
+
+fn db_err(e: impl Display) -> CredentialResolverError {
+    CredentialResolverError::Internal(format!("database error: {e}"))
+}
+
+fn is_unique_violation(e: &ScopeError) -> bool {
+    matches!(
+        e,
+        ScopeError::Db(db) if matches!(db.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+    )
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::HashMap;
+
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use credential_resolver_sdk::{
+        CredentialResolverPluginClient, NewSecret, SecretUpdate, SecretValue,
+    };
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::envelope::{KEY_LEN, Keyring};
+    use crate::domain::service::Service;
+    use crate::infra::storage::migrations::Migrator;
+
+    async fn sqlite_test_db() -> Arc<DBProvider<DbError>> {
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let db = connect_db("sqlite::memory:", opts)
+            .await
+            .expect("failed to open in-memory SQLite database");
+        run_migrations_for_testing(&db, Migrator::migrations())
+            .await
+            .expect("failed to run migrations");
+        Arc::new(DBProvider::new(db))
+    }
+
+    fn reference(key: &str) -> SecretRef {
+        SecretRef::from_key(key).unwrap()
+    }
+
+    fn make_record(tenant_id: TenantId, key: &str) -> SecretRecord {
+        let now = OffsetDateTime::now_utc();
+        SecretRecord {
+            id: Uuid::new_v4(),
+            tenant_id,
+            reference: reference(key),
+            owner_id: Uuid::new_v4(),
+            sharing: SharingMode::Tenant,
+            sealed: SealedValue {
+                key_id: "k1".to_owned(),
+                wrapped_key: vec![1, 2, 3],
+                ciphertext: vec![4, 5, 6],
+            },
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn insert_find_and_list_are_tenant_scoped() {
+        let repo = SeaOrmSecretRepo::new(sqlite_test_db().await);
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let rec = make_record(owner, "b");
+        repo.insert(rec.clone()).await.unwrap();
+        repo.insert(make_record(owner, "a")).await.unwrap();
+        repo.insert(make_record(other, "b")).await.unwrap();
+
+        let found = repo.find(owner, &reference("b")).await.unwrap().unwrap();
+        assert_eq!(found.id, rec.id);
+        assert_eq!(found.sealed, rec.sealed);
+        assert!(repo.find(owner, &reference("c")).await.unwrap().is_none());
+
+        let keys: Vec<_> = repo
+            .list(owner)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.reference.key().to_owned())
+            .collect();
+        assert_eq!(keys, ["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn reference_uniqueness_is_enforced_by_database() {
+        let repo = SeaOrmSecretRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+        repo.insert(make_record(tenant, "k")).await.unwrap();
+
+        assert!(matches!(
+            repo.insert(make_record(tenant, "k")).await,
+            Err(CredentialResolverError::AlreadyExists { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_and_delete_do_not_cross_tenants() {
+        let repo = SeaOrmSecretRepo::new(sqlite_test_db().await);
+        let owner = Uuid::new_v4();
+        let mut rec = make_record(owner, "k");
+        repo.insert(rec.clone()).await.unwrap();
+
+        let mut foreign = rec.clone();
+        foreign.tenant_id = Uuid::new_v4();
+        assert!(matches!(
+            repo.update(foreign).await,
+            Err(CredentialResolverError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.delete(Uuid::new_v4(), &reference("k")).await,
+            Err(CredentialResolverError::NotFound { .. })
+        ));
+
+        rec.sharing = SharingMode::Shared;
+        repo.update(rec).await.unwrap();
+        let found = repo.find(owner, &reference("k")).await.unwrap().unwrap();
+        assert_eq!(found.sharing, SharingMode::Shared);
+
+        repo.delete(owner, &reference("k")).await.unwrap();
+        assert!(repo.find(owner, &reference("k")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn stored_rows_never_contain_plaintext() {
+        let db = sqlite_test_db().await;
+        let keys: HashMap<String, String> =
+            HashMap::from([("k1".to_owned(), STANDARD.encode([9u8; KEY_LEN]))]);
+        let svc = Service::new(
+            Arc::new(SeaOrmSecretRepo::new(db.clone())),
+            Keyring::from_config("k1", &keys).unwrap(),
+        );
+        let tenant = Uuid::new_v4();
+        let r = reference("openai-key");
+
+        svc.create(
+            tenant,
+            Uuid::nil(),
+            NewSecret::new(r.clone(), SecretValue::new("sk-plaintext-1")),
+        )
+        .await
+        .unwrap();
+        svc.update(
+            tenant,
+            &r,
+            SecretUpdate {
+                value: Some(SecretValue::new("sk-plaintext-2")),
+                ..SecretUpdate::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let conn = db.conn().unwrap();
+        let rows = SecretEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant))
+            .all(&conn)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        for needle in [b"sk-plaintext-1".as_slice(), b"sk-plaintext-2".as_slice()] {
+            assert!(
+                !rows[0]
+                    .ciphertext
+                    .windows(needle.len())
+                    .any(|w| w == needle)
+            );
+            assert!(
+                !rows[0]
+                    .wrapped_key
+                    .windows(needle.len())
+                    .any(|w| w == needle)
+            );
+        }
+
+        let stored = svc.get(tenant, &r).await.unwrap().unwrap();
+        assert_eq!(stored.value.expose(), "sk-plaintext-2");
+    }
+}
@@ -0,0 +1,35 @@
+//! Database Credential Resolver Plugin
+//!
+//! This plugin stores secrets for the credential resolver in the module
+//! database. Values are envelope-encrypted: each secret has its own AES-256-GCM
+//! data key, wrapped with a master key from configuration, so the database
+//! never holds plaintext.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   db-cred-plugin:
+//!     database:
+//!       server: "sqlite_users"
+//!       file: "credentials.db"
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 100
+//!       active_key_id: "2026-04"
+//!       master_keys:
+//!         "2026-04": "${CREDENTIALS_MASTER_KEY}"
+//! ```
+//!
+//! Master keys are base64-encoded 32-byte keys (`openssl rand -base64 32`).
+//! To rotate, add a new key, point `active_key_id` at it, and keep the old key
+//! until every secret has been rewritten.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use module::DbCredPlugin;
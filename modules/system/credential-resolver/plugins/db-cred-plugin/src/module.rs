@@ -0,0 +1,107 @@
+//! Database credential resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use credential_resolver_sdk::{CredentialResolverPluginClient, CredentialResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::DbCredPluginConfig;
+use crate::domain::{Keyring, Service};
+use crate::infra::storage::SeaOrmSecretRepo;
+
+/// Database credential resolver plugin module.
+///
+/// Stores envelope-encrypted secrets in the module database.
+///
+/// **Plugin registration pattern:**
+/// - Gateway registers the plugin schema (GTS type definition)
+/// - This plugin registers its instance (implementation metadata)
+/// - This plugin registers its scoped client (implementation in `ClientHub`)
+#[modkit::module(
+    name = "db-cred-plugin",
+    deps = ["types-registry"],
+    capabilities = [db]
+)]
+pub struct DbCredPlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for DbCredPlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for DbCredPlugin {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        info!("Providing {} database migrations", Self::MODULE_NAME);
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+#[async_trait]
+impl Module for DbCredPlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        info!("Initializing {} module", Self::MODULE_NAME);
+
+        // Load configuration
+        let cfg: DbCredPluginConfig = ctx.config()?;
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            active_key_id = %cfg.active_key_id,
+            master_key_count = cfg.master_keys.len(),
+            "Loaded plugin configuration"
+        );
+
+        // Refuse to start without a usable master key rather than failing on
+        // the first secret write.
+        let keyring = Keyring::from_config(&cfg.active_key_id, &cfg.master_keys)?;
+
+        // Generate plugin instance ID
+        let instance_id = CredentialResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.db_credential_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<CredentialResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: CredentialResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        // Create service over the module database
+        let db = Arc::new(ctx.db_required()?);
+        let repo = Arc::new(SeaOrmSecretRepo::new(db));
+        let service = Arc::new(Service::new(repo, keyring));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn CredentialResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn CredentialResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id, "{} module initialized successfully", Self::MODULE_NAME);
+        Ok(())
+    }
+}
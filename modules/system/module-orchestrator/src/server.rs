@@ -7,9 +7,9 @@ use tonic::{Request, Response, Status};
 
 use cf_system_sdks::directory::{
     DeregisterInstanceRequest, DirectoryClient, DirectoryService, DirectoryServiceServer,
-    HeartbeatRequest, InstanceInfo, ListInstancesRequest, ListInstancesResponse,
-    RegisterInstanceInfo, RegisterInstanceRequest, ResolveGrpcServiceRequest,
-    ResolveGrpcServiceResponse, ServiceEndpoint,
+    HeartbeatRequest, InstanceEventProto, InstanceInfo, ListInstancesRequest,
+    ListInstancesResponse, RegisterInstanceInfo, RegisterInstanceRequest,
+    ResolveGrpcServiceRequest, ResolveGrpcServiceResponse, ServiceEndpoint,
 };
 
 /// gRPC service implementation of Directory Service
@@ -63,6 +63,7 @@ impl DirectoryService for DirectoryServiceImpl {
                     instance_id: i.instance_id,
                     endpoint_uri: i.endpoint.uri,
                     version: i.version.unwrap_or_default(),
+                    history: i.history.iter().map(InstanceEventProto::from).collect(),
                 })
                 .collect(),
         };
@@ -1,7 +1,9 @@
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
 use futures_core::Stream;
+use http::HeaderMap;
 
 /// Boxed error type for body stream errors.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -107,6 +109,40 @@ impl Body {
     }
 }
 
+/// Trailing headers of a streamed proxy response, such as `grpc-status` and
+/// `grpc-message` on gRPC calls.
+///
+/// Attached to responses as an extension
+/// (`resp.extensions().get::<Trailers>()`). The gateway fills it in when the
+/// body stream reaches its end, so read it after the body is consumed.
+#[derive(Debug, Clone, Default)]
+pub struct Trailers(Arc<Mutex<Option<HeaderMap>>>);
+
+impl Trailers {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the trailers received from the upstream.
+    pub fn set(&self, trailers: HeaderMap) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(trailers);
+    }
+
+    /// Trailers received so far; `None` until the stream has ended or when
+    /// the upstream sent none.
+    #[must_use]
+    pub fn get(&self) -> Option<HeaderMap> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
 impl From<()> for Body {
     fn from((): ()) -> Self {
         Body::Empty
@@ -151,6 +187,17 @@ impl From<BodyStream> for Body {
 mod tests {
     use super::*;
 
+    #[test]
+    fn trailers_shared_between_clones() {
+        let trailers = Trailers::new();
+        assert!(trailers.get().is_none());
+
+        let mut h = HeaderMap::new();
+        h.insert("grpc-status", "0".parse().unwrap());
+        trailers.clone().set(h);
+        assert_eq!(trailers.get().unwrap()["grpc-status"], "0");
+    }
+
     #[test]
     fn body_empty_is_empty() {
         assert!(Body::Empty.is_empty());
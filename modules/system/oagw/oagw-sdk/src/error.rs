@@ -32,6 +32,27 @@ pub enum ServiceGatewayError {
         retry_after_secs: Option<u64>,
     },
 
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    #[error("{detail}")]
+    QueueFull {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
@@ -49,4 +70,28 @@ pub enum ServiceGatewayError {
 
     #[error("{detail}")]
     RequestTimeout { detail: String, instance: String },
+
+    #[error("{detail}")]
+    PluginNotFound { detail: String, instance: String },
+
+    /// The plugin is still referenced by an upstream or route (409 Conflict).
+    #[error("{detail}")]
+    PluginInUse { detail: String, instance: String },
+
+    /// A guard or transform plugin rejected the request with its own status
+    /// and error code.
+    #[error("{detail}")]
+    PluginRejected {
+        status: u16,
+        code: String,
+        detail: String,
+        instance: String,
+    },
+
+    #[error("{detail}")]
+    PluginFailed { detail: String, instance: String },
+
+    /// The CORS guard refused a browser request's origin, method or headers.
+    #[error("{detail}")]
+    CorsRejected { detail: String, instance: String },
 }
@@ -5,13 +5,15 @@ pub mod error;
 pub mod models;
 
 pub use models::{
-    AuthConfig, BurstConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
-    CreateUpstreamRequestBuilder, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod,
-    ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginsConfig, RateLimitAlgorithm,
-    RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules,
-    Route, Scheme, Server, SharingMode, SustainedRate, UpdateRouteRequest,
-    UpdateRouteRequestBuilder, UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream,
-    Window,
+    AuthConfig, BurstConfig, CircuitBreakerConfig, CircuitBreakerScope, CreateRouteRequest,
+    CreateRouteRequestBuilder, CreateUpstreamRequest, CreateUpstreamRequestBuilder, DegradeConfig,
+    Endpoint, FailureConditions, FallbackResponse, GrpcMatch, GrpcTranscodingConfig, HeadersConfig,
+    HealthCheckConfig, HttpMatch, HttpMethod, ListQuery, LoadBalancingConfig,
+    LoadBalancingStrategy, MatchRules, OutlierDetectionConfig, PassthroughMode, PathSuffixMode,
+    PluginsConfig, QueueConfig, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+    RateLimitStrategy, RequestHeaderRules, ResponseCacheConfig, ResponseHeaderRules, Route, Scheme,
+    Server, SharingMode, SustainedRate, UpdateRouteRequest, UpdateRouteRequestBuilder,
+    UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream, UsageExtractionConfig, Window,
 };
 
 pub use api::ServiceGatewayClientV1;
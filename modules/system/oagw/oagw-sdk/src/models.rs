@@ -5,6 +5,7 @@
 //! serialization concerns belong to the REST layer.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use uuid::Uuid;
 
@@ -42,6 +43,8 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    /// Relative share of traffic when the upstream uses weighted load balancing.
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -138,6 +141,10 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
     pub cost: u32,
+    /// Wait-queue settings for `RateLimitStrategy::Queue`.
+    pub queue: Option<QueueConfig>,
+    /// Fallback settings for `RateLimitStrategy::Degrade`.
+    pub degrade: Option<DegradeConfig>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -189,6 +196,176 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+/// Bounded wait queue used when the limit is exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// Maximum number of waiting requests per rate-limit key.
+    pub max_depth: u32,
+    /// Maximum time a request may wait before failing with 503.
+    pub timeout: Duration,
+}
+
+/// Fallback behaviour used when the limit is exceeded.
+///
+/// When neither field is set, requests are forwarded to the original
+/// upstream with a degradation marker header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DegradeConfig {
+    pub fallback_upstream_id: Option<Uuid>,
+    pub fallback_response: Option<FallbackResponse>,
+}
+
+/// Static response served instead of forwarding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Circuit breaker configuration for an upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Failure percentage (1-100) within `window_seconds` that opens the circuit.
+    pub failure_rate_threshold: Option<u8>,
+    /// Calls required within the window before the failure rate is evaluated.
+    pub minimum_requests: u32,
+    pub window_seconds: u32,
+    /// Consecutive successful probes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Seconds an open circuit waits before letting probes through.
+    pub timeout_seconds: u32,
+    /// Concurrent probes allowed while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            failure_rate_threshold: None,
+            minimum_requests: 10,
+            window_seconds: 60,
+            success_threshold: 3,
+            timeout_seconds: 30,
+            half_open_max_requests: 3,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+/// Upstream outcomes counted as failures by the circuit breaker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    Global,
+    #[default]
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+/// How requests are spread across the endpoints of a multi-endpoint upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    /// Passive ejection of failing endpoints. `None` disables it.
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+    /// Active HTTP health probes. `None` disables them.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: LoadBalancingStrategy::default(),
+            outlier_detection: Some(OutlierDetectionConfig::default()),
+            health_check: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    Weighted,
+    LeastInFlight,
+}
+
+/// Passive outlier detection settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    /// Consecutive 5xx responses or connect errors that eject an endpoint.
+    pub consecutive_failures: u32,
+    /// Seconds of the first ejection; each back-to-back ejection adds another multiple.
+    pub base_ejection_seconds: u32,
+    /// Maximum share (0-100) of endpoints that may be ejected at the same time.
+    pub max_ejection_percent: u8,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: 5,
+            base_ejection_seconds: 30,
+            max_ejection_percent: 50,
+        }
+    }
+}
+
+/// Active HTTP health probe settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    pub path: String,
+    pub interval_seconds: u32,
+    pub timeout_seconds: u32,
+    pub healthy_threshold: u32,
+    pub unhealthy_threshold: u32,
+    pub expected_statuses: Vec<u16>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            path: "/health".into(),
+            interval_seconds: 10,
+            timeout_seconds: 2,
+            healthy_threshold: 2,
+            unhealthy_threshold: 3,
+            expected_statuses: vec![200],
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -199,6 +376,8 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     /// Plugin references: GTS identifiers (builtin) or UUIDs (custom).
     pub items: Vec<String>,
+    /// Instance config per plugin reference.
+    pub config: HashMap<String, serde_json::Value>,
 }
 
 // ---------------------------------------------------------------------------
@@ -235,13 +414,43 @@ pub struct HttpMatch {
     pub path_suffix_mode: PathSuffixMode,
 }
 
-/// gRPC-protocol match rules for a route (future use).
+/// gRPC-protocol match rules for a route. Matches `POST /{service}/{method}`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct GrpcMatch {
+    /// Fully qualified service name (e.g. `example.v1.UserService`).
     pub service: String,
     pub method: String,
 }
 
+/// JSON transcoding for a gRPC route: HTTP clients send and receive JSON,
+/// the gateway converts to and from protobuf using the given descriptors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrpcTranscodingConfig {
+    /// Serialized `google.protobuf.FileDescriptorSet` that defines the route's
+    /// service, method and message types (e.g. `protoc --descriptor_set_out`
+    /// with `--include_imports`).
+    pub descriptor_set: Vec<u8>,
+}
+
+/// Opt-in caching of upstream responses to `GET` requests on a route. The
+/// upstream's `Cache-Control` and `ETag` headers are honoured; `ttl` applies
+/// when the response does not say how long it stays fresh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCacheConfig {
+    pub ttl: Duration,
+    /// Request headers whose values select distinct cache entries.
+    pub vary: Vec<String>,
+}
+
+/// Where a route's upstream reports token counts in its responses, as JSON
+/// pointers (RFC 6901) into a JSON body or into the `data` of each
+/// server-sent event. For streams the last reported value wins.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UsageExtractionConfig {
+    pub input_tokens: Option<String>,
+    pub output_tokens: Option<String>,
+}
+
 /// Protocol-scoped matching rules. Exactly one of `http` or `grpc` must be present.
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchRules {
@@ -262,6 +471,9 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    pub response_cache: Option<ResponseCacheConfig>,
+    pub usage_extraction: Option<UsageExtractionConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -281,6 +493,8 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
     pub tags: Vec<String>,
 }
 
@@ -317,6 +531,8 @@ pub struct CreateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -332,6 +548,8 @@ impl CreateUpstreamRequest {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
             tags: vec![],
             enabled: true,
         }
@@ -358,6 +576,12 @@ impl CreateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+    pub fn load_balancing(&self) -> Option<&LoadBalancingConfig> {
+        self.load_balancing.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -374,6 +598,8 @@ pub struct CreateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -399,6 +625,14 @@ impl CreateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+    pub fn load_balancing(mut self, load_balancing: LoadBalancingConfig) -> Self {
+        self.load_balancing = Some(load_balancing);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -416,6 +650,8 @@ impl CreateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            circuit_breaker: self.circuit_breaker,
+            load_balancing: self.load_balancing,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -433,6 +669,8 @@ pub struct UpdateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -464,6 +702,12 @@ impl UpdateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+    pub fn load_balancing(&self) -> Option<&LoadBalancingConfig> {
+        self.load_balancing.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -481,6 +725,8 @@ pub struct UpdateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    load_balancing: Option<LoadBalancingConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -514,6 +760,14 @@ impl UpdateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+    pub fn load_balancing(mut self, load_balancing: LoadBalancingConfig) -> Self {
+        self.load_balancing = Some(load_balancing);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -531,6 +785,8 @@ impl UpdateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            circuit_breaker: self.circuit_breaker,
+            load_balancing: self.load_balancing,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -548,6 +804,9 @@ pub struct CreateRouteRequest {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    grpc_transcoding: Option<GrpcTranscodingConfig>,
+    response_cache: Option<ResponseCacheConfig>,
+    usage_extraction: Option<UsageExtractionConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -561,6 +820,9 @@ impl CreateRouteRequest {
             match_rules,
             plugins: None,
             rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -579,6 +841,15 @@ impl CreateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn grpc_transcoding(&self) -> Option<&GrpcTranscodingConfig> {
+        self.grpc_transcoding.as_ref()
+    }
+    pub fn response_cache(&self) -> Option<&ResponseCacheConfig> {
+        self.response_cache.as_ref()
+    }
+    pub fn usage_extraction(&self) -> Option<&UsageExtractionConfig> {
+        self.usage_extraction.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -595,6 +866,9 @@ pub struct CreateRouteRequestBuilder {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    grpc_transcoding: Option<GrpcTranscodingConfig>,
+    response_cache: Option<ResponseCacheConfig>,
+    usage_extraction: Option<UsageExtractionConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -609,6 +883,18 @@ impl CreateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn grpc_transcoding(mut self, grpc_transcoding: GrpcTranscodingConfig) -> Self {
+        self.grpc_transcoding = Some(grpc_transcoding);
+        self
+    }
+    pub fn response_cache(mut self, response_cache: ResponseCacheConfig) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+    pub fn usage_extraction(mut self, usage_extraction: UsageExtractionConfig) -> Self {
+        self.usage_extraction = Some(usage_extraction);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -627,6 +913,9 @@ impl CreateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            grpc_transcoding: self.grpc_transcoding,
+            response_cache: self.response_cache,
+            usage_extraction: self.usage_extraction,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -641,6 +930,9 @@ pub struct UpdateRouteRequest {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    grpc_transcoding: Option<GrpcTranscodingConfig>,
+    response_cache: Option<ResponseCacheConfig>,
+    usage_extraction: Option<UsageExtractionConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -661,6 +953,15 @@ impl UpdateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn grpc_transcoding(&self) -> Option<&GrpcTranscodingConfig> {
+        self.grpc_transcoding.as_ref()
+    }
+    pub fn response_cache(&self) -> Option<&ResponseCacheConfig> {
+        self.response_cache.as_ref()
+    }
+    pub fn usage_extraction(&self) -> Option<&UsageExtractionConfig> {
+        self.usage_extraction.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -677,6 +978,9 @@ pub struct UpdateRouteRequestBuilder {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    grpc_transcoding: Option<GrpcTranscodingConfig>,
+    response_cache: Option<ResponseCacheConfig>,
+    usage_extraction: Option<UsageExtractionConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -695,6 +999,18 @@ impl UpdateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn grpc_transcoding(mut self, grpc_transcoding: GrpcTranscodingConfig) -> Self {
+        self.grpc_transcoding = Some(grpc_transcoding);
+        self
+    }
+    pub fn response_cache(mut self, response_cache: ResponseCacheConfig) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+    pub fn usage_extraction(mut self, usage_extraction: UsageExtractionConfig) -> Self {
+        self.usage_extraction = Some(usage_extraction);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -712,6 +1028,9 @@ impl UpdateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            grpc_transcoding: self.grpc_transcoding,
+            response_cache: self.response_cache,
+            usage_extraction: self.usage_extraction,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -733,6 +1052,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com");
     }
@@ -743,6 +1063,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "example.com");
     }
@@ -753,6 +1074,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 8443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com:8443");
     }
@@ -768,6 +1090,7 @@ mod tests {
             scheme: Scheme::Wss,
             host: "stream.example.com".into(),
             port: 9090,
+            weight: 1,
         };
         let ep2 = ep.clone();
         assert_eq!(ep, ep2);
@@ -790,6 +1113,9 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
             tags: vec![],
             priority: 0,
             enabled: true,
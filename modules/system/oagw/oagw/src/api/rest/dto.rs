@@ -4,6 +4,7 @@
 // to/from internal domain types via `From` impls for the service layer boundary.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -45,12 +46,19 @@ pub struct Endpoint {
     pub host: String,
     #[serde(default = "default_port")]
     pub port: u16,
+    /// Relative share of traffic for the `weighted` load-balancing strategy.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
 }
 
 fn default_port() -> u16 {
     443
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Server {
     pub endpoints: Vec<Endpoint>,
@@ -134,6 +142,10 @@ pub struct RateLimitConfig {
     pub strategy: RateLimitStrategy,
     #[serde(default = "default_cost")]
     pub cost: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<QueueConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degrade: Option<DegradeConfig>,
 }
 
 fn default_cost() -> u32 {
@@ -190,6 +202,202 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueueConfig {
+    pub max_depth: u32,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    #[schema(value_type = String, example = "2s")]
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+pub struct DegradeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_upstream_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_response: Option<FallbackResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FallbackResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub body: String,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_rate_threshold: Option<u8>,
+    #[serde(default = "default_minimum_requests")]
+    pub minimum_requests: u32,
+    #[serde(default = "default_window_seconds")]
+    pub window_seconds: u32,
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u32,
+    #[serde(default = "default_half_open_max_requests")]
+    pub half_open_max_requests: u32,
+    #[serde(default)]
+    pub failure_conditions: FailureConditions,
+    #[serde(default)]
+    pub scope: CircuitBreakerScope,
+}
+
+fn default_failure_threshold() -> u32 {
+    domain::CircuitBreakerConfig::default().failure_threshold
+}
+
+fn default_minimum_requests() -> u32 {
+    domain::CircuitBreakerConfig::default().minimum_requests
+}
+
+fn default_window_seconds() -> u32 {
+    domain::CircuitBreakerConfig::default().window_seconds
+}
+
+fn default_success_threshold() -> u32 {
+    domain::CircuitBreakerConfig::default().success_threshold
+}
+
+fn default_timeout_seconds() -> u32 {
+    domain::CircuitBreakerConfig::default().timeout_seconds
+}
+
+fn default_half_open_max_requests() -> u32 {
+    domain::CircuitBreakerConfig::default().half_open_max_requests
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FailureConditions {
+    #[serde(default = "default_failure_status_codes")]
+    pub status_codes: Vec<u16>,
+    #[serde(default = "default_true")]
+    pub timeout: bool,
+    #[serde(default = "default_true")]
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        domain::FailureConditions::default().into()
+    }
+}
+
+fn default_failure_status_codes() -> Vec<u16> {
+    domain::FailureConditions::default().status_codes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerScope {
+    Global,
+    #[default]
+    PerEndpoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LoadBalancingConfig {
+    #[serde(default)]
+    pub strategy: LoadBalancingStrategy,
+    /// Omitted means the defaults; `null` disables outlier ejection.
+    #[serde(default = "default_outlier_detection")]
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+fn default_outlier_detection() -> Option<OutlierDetectionConfig> {
+    Some(domain::OutlierDetectionConfig::default().into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    Weighted,
+    LeastInFlight,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OutlierDetectionConfig {
+    #[serde(default = "default_consecutive_failures")]
+    pub consecutive_failures: u32,
+    #[serde(default = "default_base_ejection_seconds")]
+    pub base_ejection_seconds: u32,
+    #[serde(default = "default_max_ejection_percent")]
+    pub max_ejection_percent: u8,
+}
+
+fn default_consecutive_failures() -> u32 {
+    domain::OutlierDetectionConfig::default().consecutive_failures
+}
+
+fn default_base_ejection_seconds() -> u32 {
+    domain::OutlierDetectionConfig::default().base_ejection_seconds
+}
+
+fn default_max_ejection_percent() -> u8 {
+    domain::OutlierDetectionConfig::default().max_ejection_percent
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HealthCheckConfig {
+    pub path: String,
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u32,
+    #[serde(default = "default_probe_timeout_seconds")]
+    pub timeout_seconds: u32,
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: u32,
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u32,
+    #[serde(default = "default_expected_statuses")]
+    pub expected_statuses: Vec<u16>,
+}
+
+fn default_interval_seconds() -> u32 {
+    domain::HealthCheckConfig::default().interval_seconds
+}
+
+fn default_probe_timeout_seconds() -> u32 {
+    domain::HealthCheckConfig::default().timeout_seconds
+}
+
+fn default_healthy_threshold() -> u32 {
+    domain::HealthCheckConfig::default().healthy_threshold
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    domain::HealthCheckConfig::default().unhealthy_threshold
+}
+
+fn default_expected_statuses() -> Vec<u16> {
+    domain::HealthCheckConfig::default().expected_statuses
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -200,6 +408,31 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     #[serde(default)]
     pub items: Vec<String>,
+    /// Instance config per plugin id, merged over the plugin's schema defaults.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub config: HashMap<String, serde_json::Value>,
+}
+
+// ---------------------------------------------------------------------------
+// Custom plugins
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginType {
+    Auth,
+    Guard,
+    Transform,
+}
+
+// Variants mirror the hook names (`on_request`, `on_response`, `on_error`).
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginPhase {
+    OnRequest,
+    OnResponse,
+    OnError,
 }
 
 // ---------------------------------------------------------------------------
@@ -241,6 +474,40 @@ pub struct GrpcMatch {
     pub method: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GrpcTranscodingConfig {
+    /// Base64-encoded `google.protobuf.FileDescriptorSet` covering the
+    /// route's service and its message types.
+    #[serde(with = "crate::infra::serde_base64")]
+    #[schema(value_type = String, format = Byte)]
+    pub descriptor_set: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResponseCacheConfig {
+    /// Freshness lifetime used when the upstream response has no
+    /// `Cache-Control: max-age` or `s-maxage`.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    #[schema(value_type = String, example = "5m")]
+    pub ttl: Duration,
+    /// Request headers whose values select distinct cache entries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vary: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UsageExtractionConfig {
+    /// JSON pointer to the prompt token count, e.g. `/usage/prompt_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "/usage/prompt_tokens")]
+    pub input_tokens: Option<String>,
+    /// JSON pointer to the completion token count, e.g.
+    /// `/usage/completion_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "/usage/completion_tokens")]
+    pub output_tokens: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct MatchRules {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -267,6 +534,10 @@ pub struct CreateUpstreamRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default = "default_true")]
@@ -290,6 +561,10 @@ pub struct UpdateUpstreamRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
@@ -308,6 +583,12 @@ pub struct CreateRouteRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCacheConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_extraction: Option<UsageExtractionConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default)]
@@ -325,6 +606,12 @@ pub struct UpdateRouteRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCacheConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_extraction: Option<UsageExtractionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub priority: Option<i32>,
@@ -332,6 +619,26 @@ pub struct UpdateRouteRequest {
     pub enabled: Option<bool>,
 }
 
+// ---------------------------------------------------------------------------
+// Plugin request DTOs
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreatePluginRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub plugin_type: PluginType,
+    /// Phases to run in; inferred from the hooks `source_code` defines when omitted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<PluginPhase>,
+    /// JSON Schema of the instance config; top-level `default`s seed `ctx.config`.
+    #[serde(default)]
+    pub config_schema: serde_json::Value,
+    /// Starlark source defining `on_request`, `on_response` and/or `on_error`.
+    pub source_code: String,
+}
+
 // ---------------------------------------------------------------------------
 // Response DTOs
 // ---------------------------------------------------------------------------
@@ -352,6 +659,10 @@ pub struct UpstreamResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancingConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
@@ -367,12 +678,97 @@ pub struct RouteResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCacheConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_extraction: Option<UsageExtractionConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
 }
 
+/// Custom plugin metadata; the source is served by `GET /plugins/{id}/source`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PluginResponse {
+    pub id: String,
+    pub tenant_id: Uuid,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub plugin_type: PluginType,
+    pub phases: Vec<PluginPhase>,
+    pub config_schema: serde_json::Value,
+}
+
+/// State of one circuit breaker circuit.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerStatusResponse {
+    /// `host:port` for per-endpoint circuits; absent for a global circuit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub window_requests: u32,
+    pub window_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Dimension usage totals are additionally split by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGroupBy {
+    Subject,
+}
+
+/// Query parameters of `GET /oagw/v1/usage`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsageQueryParams {
+    /// Start of the window (RFC 3339), inclusive.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub from: Option<time::OffsetDateTime>,
+    /// End of the window (RFC 3339), exclusive.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub to: Option<time::OffsetDateTime>,
+    /// Upstream GTS identifier.
+    #[serde(default)]
+    pub upstream_id: Option<String>,
+    /// Route GTS identifier.
+    #[serde(default)]
+    pub route_id: Option<String>,
+    #[serde(default)]
+    pub subject_id: Option<Uuid>,
+    #[serde(default)]
+    pub group_by: Option<UsageGroupBy>,
+}
+
+/// Usage of one route, or of one subject on a route, in the queried window.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UsageTotalsResponse {
+    pub upstream_id: String,
+    pub route_id: String,
+    /// Present when grouping by subject.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_id: Option<Uuid>,
+    pub requests: u64,
+    /// Responses with a 4xx or 5xx status.
+    pub errors: u64,
+    /// Responses served from the response cache.
+    pub cache_hits: u64,
+    /// Requests degraded by a rate limit to a fallback response or upstream.
+    pub degraded: u64,
+    pub request_bytes: u64,
+    pub response_bytes: u64,
+    /// Tokens reported by the upstream on routes with `usage_extraction`.
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Sum of request latencies, in milliseconds.
+    pub total_latency_ms: u64,
+}
+
 // ---------------------------------------------------------------------------
 // From conversions: REST value types → domain value types
 // ---------------------------------------------------------------------------
@@ -405,6 +801,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -527,6 +924,27 @@ impl From<RateLimitStrategy> for domain::RateLimitStrategy {
     }
 }
 
+impl From<QueueConfig> for domain::QueueConfig {
+    fn from(v: QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<DegradeConfig> for domain::DegradeConfig {
+    fn from(v: DegradeConfig) -> Self {
+        Self {
+            fallback_upstream_id: v.fallback_upstream_id,
+            fallback_response: v.fallback_response.map(|r| domain::FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }
+    }
+}
+
 impl From<RateLimitConfig> for domain::RateLimitConfig {
     fn from(v: RateLimitConfig) -> Self {
         Self {
@@ -537,6 +955,87 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+        }
+    }
+}
+
+impl From<FailureConditions> for domain::FailureConditions {
+    fn from(v: FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<CircuitBreakerScope> for domain::CircuitBreakerScope {
+    fn from(v: CircuitBreakerScope) -> Self {
+        match v {
+            CircuitBreakerScope::Global => Self::Global,
+            CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate_threshold: v.failure_rate_threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<LoadBalancingStrategy> for domain::LoadBalancingStrategy {
+    fn from(v: LoadBalancingStrategy) -> Self {
+        match v {
+            LoadBalancingStrategy::RoundRobin => Self::RoundRobin,
+            LoadBalancingStrategy::Weighted => Self::Weighted,
+            LoadBalancingStrategy::LeastInFlight => Self::LeastInFlight,
+        }
+    }
+}
+
+impl From<OutlierDetectionConfig> for domain::OutlierDetectionConfig {
+    fn from(v: OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            base_ejection_seconds: v.base_ejection_seconds,
+            max_ejection_percent: v.max_ejection_percent,
+        }
+    }
+}
+
+impl From<HealthCheckConfig> for domain::HealthCheckConfig {
+    fn from(v: HealthCheckConfig) -> Self {
+        Self {
+            path: v.path,
+            interval_seconds: v.interval_seconds,
+            timeout_seconds: v.timeout_seconds,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+            expected_statuses: v.expected_statuses,
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: v.strategy.into(),
+            outlier_detection: v.outlier_detection.map(Into::into),
+            health_check: v.health_check.map(Into::into),
         }
     }
 }
@@ -546,6 +1045,7 @@ impl From<PluginsConfig> for domain::PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -591,6 +1091,32 @@ impl From<GrpcMatch> for domain::GrpcMatch {
     }
 }
 
+impl From<GrpcTranscodingConfig> for domain::GrpcTranscodingConfig {
+    fn from(v: GrpcTranscodingConfig) -> Self {
+        Self {
+            descriptor_set: v.descriptor_set,
+        }
+    }
+}
+
+impl From<ResponseCacheConfig> for domain::ResponseCacheConfig {
+    fn from(v: ResponseCacheConfig) -> Self {
+        Self {
+            ttl: v.ttl,
+            vary: v.vary,
+        }
+    }
+}
+
+impl From<UsageExtractionConfig> for domain::UsageExtractionConfig {
+    fn from(v: UsageExtractionConfig) -> Self {
+        Self {
+            input_tokens: v.input_tokens,
+            output_tokens: v.output_tokens,
+        }
+    }
+}
+
 impl From<MatchRules> for domain::MatchRules {
     fn from(v: MatchRules) -> Self {
         Self {
@@ -600,10 +1126,50 @@ impl From<MatchRules> for domain::MatchRules {
     }
 }
 
+impl From<PluginType> for domain::PluginType {
+    fn from(v: PluginType) -> Self {
+        match v {
+            PluginType::Auth => Self::Auth,
+            PluginType::Guard => Self::Guard,
+            PluginType::Transform => Self::Transform,
+        }
+    }
+}
+
+impl From<PluginPhase> for domain::PluginPhase {
+    fn from(v: PluginPhase) -> Self {
+        match v {
+            PluginPhase::OnRequest => Self::OnRequest,
+            PluginPhase::OnResponse => Self::OnResponse,
+            PluginPhase::OnError => Self::OnError,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // From conversions: domain value types → REST value types
 // ---------------------------------------------------------------------------
 
+impl From<domain::PluginType> for PluginType {
+    fn from(v: domain::PluginType) -> Self {
+        match v {
+            domain::PluginType::Auth => Self::Auth,
+            domain::PluginType::Guard => Self::Guard,
+            domain::PluginType::Transform => Self::Transform,
+        }
+    }
+}
+
+impl From<domain::PluginPhase> for PluginPhase {
+    fn from(v: domain::PluginPhase) -> Self {
+        match v {
+            domain::PluginPhase::OnRequest => Self::OnRequest,
+            domain::PluginPhase::OnResponse => Self::OnResponse,
+            domain::PluginPhase::OnError => Self::OnError,
+        }
+    }
+}
+
 impl From<domain::SharingMode> for SharingMode {
     fn from(v: domain::SharingMode) -> Self {
         match v {
@@ -632,6 +1198,7 @@ impl From<domain::Endpoint> for Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -754,6 +1321,27 @@ impl From<domain::RateLimitStrategy> for RateLimitStrategy {
     }
 }
 
+impl From<domain::QueueConfig> for QueueConfig {
+    fn from(v: domain::QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<domain::DegradeConfig> for DegradeConfig {
+    fn from(v: domain::DegradeConfig) -> Self {
+        Self {
+            fallback_upstream_id: v.fallback_upstream_id,
+            fallback_response: v.fallback_response.map(|r| FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }
+    }
+}
+
 impl From<domain::RateLimitConfig> for RateLimitConfig {
     fn from(v: domain::RateLimitConfig) -> Self {
         Self {
@@ -764,6 +1352,110 @@ impl From<domain::RateLimitConfig> for RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::FailureConditions> for FailureConditions {
+    fn from(v: domain::FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerScope> for CircuitBreakerScope {
+    fn from(v: domain::CircuitBreakerScope) -> Self {
+        match v {
+            domain::CircuitBreakerScope::Global => Self::Global,
+            domain::CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate_threshold: v.failure_rate_threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<domain::CircuitState> for CircuitState {
+    fn from(v: domain::CircuitState) -> Self {
+        match v {
+            domain::CircuitState::Closed => Self::Closed,
+            domain::CircuitState::Open => Self::Open,
+            domain::CircuitState::HalfOpen => Self::HalfOpen,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerStatus> for CircuitBreakerStatusResponse {
+    fn from(v: domain::CircuitBreakerStatus) -> Self {
+        Self {
+            endpoint: v.endpoint,
+            state: v.state.into(),
+            consecutive_failures: v.consecutive_failures,
+            window_requests: v.window_requests,
+            window_failures: v.window_failures,
+            retry_after_secs: v.retry_after_secs,
+        }
+    }
+}
+
+impl From<domain::LoadBalancingStrategy> for LoadBalancingStrategy {
+    fn from(v: domain::LoadBalancingStrategy) -> Self {
+        match v {
+            domain::LoadBalancingStrategy::RoundRobin => Self::RoundRobin,
+            domain::LoadBalancingStrategy::Weighted => Self::Weighted,
+            domain::LoadBalancingStrategy::LeastInFlight => Self::LeastInFlight,
+        }
+    }
+}
+
+impl From<domain::OutlierDetectionConfig> for OutlierDetectionConfig {
+    fn from(v: domain::OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            base_ejection_seconds: v.base_ejection_seconds,
+            max_ejection_percent: v.max_ejection_percent,
+        }
+    }
+}
+
+impl From<domain::HealthCheckConfig> for HealthCheckConfig {
+    fn from(v: domain::HealthCheckConfig) -> Self {
+        Self {
+            path: v.path,
+            interval_seconds: v.interval_seconds,
+            timeout_seconds: v.timeout_seconds,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+            expected_statuses: v.expected_statuses,
+        }
+    }
+}
+
+impl From<domain::LoadBalancingConfig> for LoadBalancingConfig {
+    fn from(v: domain::LoadBalancingConfig) -> Self {
+        Self {
+            strategy: v.strategy.into(),
+            outlier_detection: v.outlier_detection.map(Into::into),
+            health_check: v.health_check.map(Into::into),
         }
     }
 }
@@ -773,6 +1465,7 @@ impl From<domain::PluginsConfig> for PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -818,6 +1511,32 @@ impl From<domain::GrpcMatch> for GrpcMatch {
     }
 }
 
+impl From<domain::GrpcTranscodingConfig> for GrpcTranscodingConfig {
+    fn from(v: domain::GrpcTranscodingConfig) -> Self {
+        Self {
+            descriptor_set: v.descriptor_set,
+        }
+    }
+}
+
+impl From<domain::ResponseCacheConfig> for ResponseCacheConfig {
+    fn from(v: domain::ResponseCacheConfig) -> Self {
+        Self {
+            ttl: v.ttl,
+            vary: v.vary,
+        }
+    }
+}
+
+impl From<domain::UsageExtractionConfig> for UsageExtractionConfig {
+    fn from(v: domain::UsageExtractionConfig) -> Self {
+        Self {
+            input_tokens: v.input_tokens,
+            output_tokens: v.output_tokens,
+        }
+    }
+}
+
 impl From<domain::MatchRules> for MatchRules {
     fn from(v: domain::MatchRules) -> Self {
         Self {
@@ -841,6 +1560,8 @@ impl From<CreateUpstreamRequest> for domain::CreateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
+            load_balancing: r.load_balancing.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -857,6 +1578,8 @@ impl From<UpdateUpstreamRequest> for domain::UpdateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
+            load_balancing: r.load_balancing.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -870,6 +1593,9 @@ impl From<CreateRouteRequest> for domain::CreateRouteRequest {
             match_rules: r.match_rules.into(),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            grpc_transcoding: r.grpc_transcoding.map(Into::into),
+            response_cache: r.response_cache.map(Into::into),
+            usage_extraction: r.usage_extraction.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -883,6 +1609,9 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
             match_rules: r.match_rules.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            grpc_transcoding: r.grpc_transcoding.map(Into::into),
+            response_cache: r.response_cache.map(Into::into),
+            usage_extraction: r.usage_extraction.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -890,6 +1619,19 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
     }
 }
 
+impl From<CreatePluginRequest> for domain::CreatePluginRequest {
+    fn from(r: CreatePluginRequest) -> Self {
+        Self {
+            name: r.name,
+            description: r.description,
+            plugin_type: r.plugin_type.into(),
+            phases: r.phases.into_iter().map(Into::into).collect(),
+            config_schema: r.config_schema,
+            source_code: r.source_code,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // API DTO marker traits (required by OperationBuilder typed methods)
 // ---------------------------------------------------------------------------
@@ -898,9 +1640,13 @@ impl modkit::api::api_dto::RequestApiDto for CreateUpstreamRequest {}
 impl modkit::api::api_dto::RequestApiDto for UpdateUpstreamRequest {}
 impl modkit::api::api_dto::RequestApiDto for CreateRouteRequest {}
 impl modkit::api::api_dto::RequestApiDto for UpdateRouteRequest {}
+impl modkit::api::api_dto::RequestApiDto for CreatePluginRequest {}
 
 impl modkit::api::api_dto::ResponseApiDto for UpstreamResponse {}
 impl modkit::api::api_dto::ResponseApiDto for RouteResponse {}
+impl modkit::api::api_dto::ResponseApiDto for CircuitBreakerStatusResponse {}
+impl modkit::api::api_dto::ResponseApiDto for PluginResponse {}
+impl modkit::api::api_dto::ResponseApiDto for UsageTotalsResponse {}
 
 // ---------------------------------------------------------------------------
 // Helpers
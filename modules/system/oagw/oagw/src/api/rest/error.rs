@@ -3,83 +3,15 @@ use http::{HeaderValue, StatusCode};
 use modkit::api::problem::Problem;
 
 use crate::domain::error::DomainError;
+use crate::infra::proxy::grpc;
 use oagw_sdk::api::ErrorSource;
 
-// ---------------------------------------------------------------------------
-// GTS error type constants
-// ---------------------------------------------------------------------------
-
-pub(crate) const ERR_VALIDATION: &str = "gts.x.core.errors.err.v1~x.oagw.validation.error.v1";
-pub(crate) const ERR_MISSING_TARGET_HOST: &str =
-    "gts.x.core.errors.err.v1~x.oagw.routing.missing_target_host.v1";
-pub(crate) const ERR_INVALID_TARGET_HOST: &str =
-    "gts.x.core.errors.err.v1~x.oagw.routing.invalid_target_host.v1";
-pub(crate) const ERR_UNKNOWN_TARGET_HOST: &str =
-    "gts.x.core.errors.err.v1~x.oagw.routing.unknown_target_host.v1";
-pub(crate) const ERR_AUTH_FAILED: &str = "gts.x.core.errors.err.v1~x.oagw.auth.failed.v1";
-pub(crate) const ERR_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.resource.not_found.v1";
-pub(crate) const ERR_ROUTE_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.route.not_found.v1";
-pub(crate) const ERR_PAYLOAD_TOO_LARGE: &str =
-    "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1";
-pub(crate) const ERR_RATE_LIMIT_EXCEEDED: &str =
-    "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1";
-pub(crate) const ERR_SECRET_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.secret.not_found.v1";
-pub(crate) const ERR_DOWNSTREAM: &str = "gts.x.core.errors.err.v1~x.oagw.downstream.error.v1";
-pub(crate) const ERR_PROTOCOL: &str = "gts.x.core.errors.err.v1~x.oagw.protocol.error.v1";
-pub(crate) const ERR_UPSTREAM_DISABLED: &str =
-    "gts.x.core.errors.err.v1~x.oagw.routing.upstream_disabled.v1";
-pub(crate) const ERR_CONNECTION_TIMEOUT: &str =
-    "gts.x.core.errors.err.v1~x.oagw.timeout.connection.v1";
-pub(crate) const ERR_REQUEST_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.timeout.request.v1";
-
 // ---------------------------------------------------------------------------
 // DomainError → Problem helpers
 // ---------------------------------------------------------------------------
 
-fn gts_type(err: &DomainError) -> &str {
-    match err {
-        DomainError::Validation { .. } | DomainError::Conflict { .. } => ERR_VALIDATION,
-        DomainError::MissingTargetHost { .. } => ERR_MISSING_TARGET_HOST,
-        DomainError::InvalidTargetHost { .. } => ERR_INVALID_TARGET_HOST,
-        DomainError::UnknownTargetHost { .. } => ERR_UNKNOWN_TARGET_HOST,
-        DomainError::AuthenticationFailed { .. } => ERR_AUTH_FAILED,
-        DomainError::NotFound {
-            entity: "route", ..
-        } => ERR_ROUTE_NOT_FOUND,
-        DomainError::NotFound { .. } => ERR_NOT_FOUND,
-        DomainError::PayloadTooLarge { .. } => ERR_PAYLOAD_TOO_LARGE,
-        DomainError::RateLimitExceeded { .. } => ERR_RATE_LIMIT_EXCEEDED,
-        DomainError::SecretNotFound { .. } => ERR_SECRET_NOT_FOUND,
-        DomainError::DownstreamError { .. } | DomainError::Internal { .. } => ERR_DOWNSTREAM,
-        DomainError::ProtocolError { .. } => ERR_PROTOCOL,
-        DomainError::UpstreamDisabled { .. } => ERR_UPSTREAM_DISABLED,
-        DomainError::ConnectionTimeout { .. } => ERR_CONNECTION_TIMEOUT,
-        DomainError::RequestTimeout { .. } => ERR_REQUEST_TIMEOUT,
-    }
-}
-
 fn http_status_code(err: &DomainError) -> StatusCode {
-    match err {
-        DomainError::Validation { .. }
-        | DomainError::MissingTargetHost { .. }
-        | DomainError::InvalidTargetHost { .. }
-        | DomainError::UnknownTargetHost { .. } => StatusCode::BAD_REQUEST,
-        DomainError::Conflict { .. } => StatusCode::CONFLICT,
-        DomainError::AuthenticationFailed { .. } => StatusCode::UNAUTHORIZED,
-        DomainError::NotFound { .. } => StatusCode::NOT_FOUND,
-        DomainError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
-        DomainError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
-        DomainError::SecretNotFound { .. } | DomainError::Internal { .. } => {
-            StatusCode::INTERNAL_SERVER_ERROR
-        }
-        DomainError::DownstreamError { .. } | DomainError::ProtocolError { .. } => {
-            StatusCode::BAD_GATEWAY
-        }
-        DomainError::UpstreamDisabled { .. } => StatusCode::SERVICE_UNAVAILABLE,
-        DomainError::ConnectionTimeout { .. } | DomainError::RequestTimeout { .. } => {
-            StatusCode::GATEWAY_TIMEOUT
-        }
-    }
+    StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
 }
 
 fn error_title(err: &DomainError) -> &str {
@@ -93,12 +25,22 @@ fn error_title(err: &DomainError) -> &str {
         DomainError::NotFound { .. } => "Not Found",
         DomainError::PayloadTooLarge { .. } => "Payload Too Large",
         DomainError::RateLimitExceeded { .. } => "Rate Limit Exceeded",
+        DomainError::QueueTimeout { .. } => "Queue Timeout",
+        DomainError::QueueFull { .. } => "Queue Full",
+        DomainError::CircuitBreakerOpen { .. } => "Circuit Breaker Open",
         DomainError::SecretNotFound { .. } => "Secret Not Found",
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => "Downstream Error",
         DomainError::ProtocolError { .. } => "Protocol Error",
         DomainError::UpstreamDisabled { .. } => "Upstream Disabled",
         DomainError::ConnectionTimeout { .. } => "Connection Timeout",
         DomainError::RequestTimeout { .. } => "Request Timeout",
+        DomainError::PluginNotFound { .. } => "Plugin Not Found",
+        DomainError::PluginInUse { .. } => "Plugin In Use",
+        DomainError::PluginRejected { .. } => "Request Rejected",
+        DomainError::PluginFailed { .. } => "Plugin Failed",
+        DomainError::CorsOriginNotAllowed { .. } => "CORS Origin Not Allowed",
+        DomainError::CorsMethodNotAllowed { .. } => "CORS Method Not Allowed",
+        DomainError::CorsHeadersNotAllowed { .. } => "CORS Headers Not Allowed",
     }
 }
 
@@ -111,13 +53,23 @@ fn error_instance(err: &DomainError) -> &str {
         | DomainError::AuthenticationFailed { instance, .. }
         | DomainError::PayloadTooLarge { instance, .. }
         | DomainError::RateLimitExceeded { instance, .. }
+        | DomainError::QueueTimeout { instance, .. }
+        | DomainError::QueueFull { instance, .. }
+        | DomainError::CircuitBreakerOpen { instance, .. }
         | DomainError::SecretNotFound { instance, .. }
         | DomainError::DownstreamError { instance, .. }
         | DomainError::ProtocolError { instance, .. }
         | DomainError::ConnectionTimeout { instance, .. }
-        | DomainError::RequestTimeout { instance, .. } => instance,
+        | DomainError::RequestTimeout { instance, .. }
+        | DomainError::PluginNotFound { instance, .. }
+        | DomainError::PluginRejected { instance, .. }
+        | DomainError::PluginFailed { instance, .. }
+        | DomainError::CorsOriginNotAllowed { instance, .. }
+        | DomainError::CorsMethodNotAllowed { instance, .. }
+        | DomainError::CorsHeadersNotAllowed { instance, .. } => instance,
         DomainError::NotFound { .. }
         | DomainError::Conflict { .. }
+        | DomainError::PluginInUse { .. }
         | DomainError::UpstreamDisabled { .. }
         | DomainError::Internal { .. } => "",
     }
@@ -129,15 +81,19 @@ fn error_instance(err: &DomainError) -> &str {
 
 impl From<DomainError> for Problem {
     fn from(err: DomainError) -> Self {
-        let gts = gts_type(&err).to_string();
+        let gts = err.gts_type().to_string();
         let inst = error_instance(&err).to_string();
         let status = http_status_code(&err);
         let t = error_title(&err).to_string();
         let detail = err.to_string();
 
-        Problem::new(status, t, detail)
+        let problem = Problem::new(status, t, detail)
             .with_type(gts)
-            .with_instance(inst)
+            .with_instance(inst);
+        match err {
+            DomainError::PluginRejected { code, .. } => problem.with_code(code),
+            _ => problem,
+        }
     }
 }
 
@@ -155,16 +111,67 @@ pub(crate) fn domain_error_to_problem(err: DomainError, instance: &str) -> Probl
     p
 }
 
-/// Convert a `DomainError` into an axum `Response` with the
-/// `x-oagw-error-source: gateway` header. Used by the proxy handler.
-pub fn error_response(err: DomainError) -> Response {
-    let retry_after = match &err {
+fn retry_after_secs(err: &DomainError) -> Option<u64> {
+    match err {
         DomainError::RateLimitExceeded {
             retry_after_secs: Some(secs),
             ..
+        }
+        | DomainError::QueueTimeout {
+            retry_after_secs: Some(secs),
+            ..
+        }
+        | DomainError::QueueFull {
+            retry_after_secs: Some(secs),
+            ..
+        }
+        | DomainError::CircuitBreakerOpen {
+            retry_after_secs: Some(secs),
+            ..
         } => Some(*secs),
         _ => None,
-    };
+    }
+}
+
+/// gRPC status code for a gateway error returned to a native gRPC client.
+fn grpc_status_code(err: &DomainError) -> u32 {
+    match err {
+        DomainError::Validation { .. }
+        | DomainError::Conflict { .. }
+        | DomainError::PluginInUse { .. }
+        | DomainError::MissingTargetHost { .. }
+        | DomainError::InvalidTargetHost { .. }
+        | DomainError::UnknownTargetHost { .. } => 3, // INVALID_ARGUMENT
+        DomainError::ConnectionTimeout { .. } | DomainError::RequestTimeout { .. } => 4, // DEADLINE_EXCEEDED
+        DomainError::NotFound {
+            entity: "route", ..
+        } => 12,           // UNIMPLEMENTED
+        DomainError::NotFound { .. } => 5, // NOT_FOUND
+        DomainError::PayloadTooLarge { .. }
+        | DomainError::RateLimitExceeded { .. }
+        | DomainError::QueueTimeout { .. }
+        | DomainError::QueueFull { .. } => 8, // RESOURCE_EXHAUSTED
+        DomainError::SecretNotFound { .. }
+        | DomainError::Internal { .. }
+        | DomainError::ProtocolError { .. } => 13, // INTERNAL
+        DomainError::UpstreamDisabled { .. }
+        | DomainError::CircuitBreakerOpen { .. }
+        | DomainError::DownstreamError { .. }
+        | DomainError::PluginNotFound { .. }
+        | DomainError::PluginFailed { .. } => 14, // UNAVAILABLE
+        DomainError::PluginRejected { .. } => 9, // FAILED_PRECONDITION
+        DomainError::AuthenticationFailed { .. } => 16, // UNAUTHENTICATED
+        DomainError::CorsOriginNotAllowed { .. }
+        | DomainError::CorsMethodNotAllowed { .. }
+        | DomainError::CorsHeadersNotAllowed { .. } => 7, // PERMISSION_DENIED
+    }
+}
+
+/// Convert a `DomainError` into an axum `Response` with the
+/// `x-oagw-error-source: gateway` header. Used by the proxy handler.
+pub fn error_response(err: DomainError) -> Response {
+    let retry_after = retry_after_secs(&err);
+    let circuit_open = matches!(err, DomainError::CircuitBreakerOpen { .. });
 
     let problem: Problem = err.into();
     let mut response = problem.into_response();
@@ -180,12 +187,49 @@ pub fn error_response(err: DomainError) -> Response {
         response.headers_mut().insert("retry-after", v);
     }
 
+    if circuit_open {
+        response
+            .headers_mut()
+            .insert("x-circuit-state", HeaderValue::from_static("OPEN"));
+    }
+
+    response
+}
+
+/// Gateway error in the shape a native gRPC client expects: HTTP 200 with
+/// the status in `grpc-status`/`grpc-message` (a trailers-only response).
+pub fn grpc_error_response(err: DomainError) -> Response {
+    let retry_after = retry_after_secs(&err);
+    let code = grpc_status_code(&err);
+    let message = grpc::percent_encode(&err.to_string());
+
+    let mut response = StatusCode::OK.into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static(grpc::GRPC_CONTENT_TYPE),
+    );
+    headers.insert("grpc-status", HeaderValue::from(code));
+    if let Ok(v) = HeaderValue::from_str(&message) {
+        headers.insert("grpc-message", v);
+    }
+    headers.insert(
+        "x-oagw-error-source",
+        HeaderValue::from_static(ErrorSource::Gateway.as_str()),
+    );
+    if let Some(secs) = retry_after {
+        headers.insert("retry-after", HeaderValue::from(secs));
+    }
     response
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::{
+        ERR_PLUGIN_IN_USE, ERR_PLUGIN_REJECTED, ERR_RATE_LIMIT_EXCEEDED, ERR_ROUTE_NOT_FOUND,
+        ERR_VALIDATION,
+    };
 
     #[test]
     fn validation_error_produces_correct_problem() {
@@ -224,6 +268,97 @@ mod tests {
         assert_eq!(p.type_url, ERR_RATE_LIMIT_EXCEEDED);
     }
 
+    #[test]
+    fn queue_timeout_produces_503_with_retry_after() {
+        let err = DomainError::QueueTimeout {
+            detail: "request queued for 2s, no capacity available".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: Some(2),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "2");
+    }
+
+    #[test]
+    fn circuit_breaker_open_produces_503_with_state_header() {
+        let err = DomainError::CircuitBreakerOpen {
+            detail: "circuit breaker is open for api.openai.com:443".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: Some(15),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "15");
+        assert_eq!(resp.headers().get("x-circuit-state").unwrap(), "OPEN");
+        assert_eq!(
+            resp.headers().get("x-oagw-error-source").unwrap(),
+            "gateway"
+        );
+    }
+
+    #[test]
+    fn grpc_error_response_uses_trailers_only_form() {
+        let err = DomainError::RateLimitExceeded {
+            detail: "rate limit exceeded for upstream".into(),
+            instance: "/oagw/v1/proxy/users/example.v1.UserService/GetUser".into(),
+            retry_after_secs: Some(3),
+        };
+        let resp = grpc_error_response(err);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let h = resp.headers();
+        assert_eq!(h.get("content-type").unwrap(), "application/grpc");
+        assert_eq!(h.get("grpc-status").unwrap(), "8");
+        assert!(
+            h.get("grpc-message")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .contains("rate limit")
+        );
+        assert_eq!(h.get("retry-after").unwrap(), "3");
+        assert_eq!(h.get("x-oagw-error-source").unwrap(), "gateway");
+
+        let resp = grpc_error_response(DomainError::NotFound {
+            entity: "route",
+            id: uuid::Uuid::nil(),
+        });
+        assert_eq!(resp.headers().get("grpc-status").unwrap(), "12");
+    }
+
+    #[test]
+    fn plugin_rejection_carries_plugin_status_and_code() {
+        let err = DomainError::PluginRejected {
+            status: 413,
+            code: "BODY_TOO_LARGE".into(),
+            detail: "Body exceeds limit".into(),
+            instance: "/oagw/v1/proxy/api/upload".into(),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let p: Problem = DomainError::PluginRejected {
+            status: 400,
+            code: "MISSING_HEADER".into(),
+            detail: "Required header: X-Customer-Id".into(),
+            instance: String::new(),
+        }
+        .into();
+        assert_eq!(p.type_url, ERR_PLUGIN_REJECTED);
+        assert_eq!(p.code, "MISSING_HEADER");
+    }
+
+    #[test]
+    fn plugin_in_use_produces_409() {
+        let p: Problem = DomainError::PluginInUse {
+            detail: "plugin is referenced by 1 upstream(s) and 0 route(s)".into(),
+        }
+        .into();
+        assert_eq!(p.status, StatusCode::CONFLICT);
+        assert_eq!(p.type_url, ERR_PLUGIN_IN_USE);
+        assert_eq!(p.title, "Plugin In Use");
+    }
+
     #[test]
     fn not_found_produces_404() {
         let err = DomainError::NotFound {
@@ -272,6 +407,21 @@ mod tests {
                 instance: "/test".into(),
                 retry_after_secs: None,
             },
+            DomainError::QueueTimeout {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: Some(1),
+            },
+            DomainError::QueueFull {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: None,
+            },
+            DomainError::CircuitBreakerOpen {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: Some(1),
+            },
             DomainError::SecretNotFound {
                 detail: "test".into(),
                 instance: "/test".into(),
@@ -298,6 +448,35 @@ mod tests {
             DomainError::Internal {
                 message: "test".into(),
             },
+            DomainError::PluginNotFound {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::PluginInUse {
+                detail: "test".into(),
+            },
+            DomainError::PluginRejected {
+                status: 400,
+                code: "TEST".into(),
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::PluginFailed {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::CorsOriginNotAllowed {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::CorsMethodNotAllowed {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
+            DomainError::CorsHeadersNotAllowed {
+                detail: "test".into(),
+                instance: "/test".into(),
+            },
         ];
         for err in errors {
             let p: Problem = err.into();
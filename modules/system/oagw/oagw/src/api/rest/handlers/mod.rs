@@ -1,3 +1,5 @@
+pub mod plugin;
 pub mod proxy;
 pub mod route;
 pub mod upstream;
+pub mod usage;
@@ -0,0 +1,129 @@
+use axum::Json;
+use axum::extract::{Extension, Path, Query};
+use axum::response::IntoResponse;
+use http::StatusCode;
+use modkit::api::problem::Problem;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::api::rest::dto::{CreatePluginRequest, PluginResponse};
+use crate::api::rest::error::domain_error_to_problem;
+use crate::api::rest::extractors::PaginationQuery;
+use crate::domain::error::DomainError;
+use crate::domain::gts_helpers as gts;
+use crate::domain::model::{CustomPlugin, PluginType};
+use crate::module::AppState;
+
+fn to_response(p: CustomPlugin) -> PluginResponse {
+    PluginResponse {
+        id: gts::format_plugin_gts(p.plugin_type, p.id),
+        tenant_id: p.tenant_id,
+        name: p.name,
+        description: p.description,
+        plugin_type: p.plugin_type.into(),
+        phases: p.phases.into_iter().map(Into::into).collect(),
+        config_schema: p.config_schema,
+    }
+}
+
+/// Parse a custom plugin GTS identifier. Builtin plugins have named
+/// identifiers and no management resource, so they are reported as not found.
+#[allow(clippy::result_large_err)]
+fn parse_plugin_id(id: &str, instance: &str) -> Result<(PluginType, Uuid), Problem> {
+    match gts::parse_plugin_gts(id) {
+        Ok((plugin_type, Some(uuid))) => Ok((plugin_type, uuid)),
+        Ok((_, None)) => Err(domain_error_to_problem(
+            DomainError::not_found("plugin", Uuid::nil()),
+            instance,
+        )),
+        Err(e) => Err(domain_error_to_problem(e, instance)),
+    }
+}
+
+/// Fetch the plugin addressed by `id`; the type in the identifier must match.
+async fn load_plugin(
+    state: &AppState,
+    ctx: &SecurityContext,
+    id: &str,
+    instance: &str,
+) -> Result<CustomPlugin, Problem> {
+    let (plugin_type, uuid) = parse_plugin_id(id, instance)?;
+    let plugin = state
+        .cp
+        .get_plugin(ctx, uuid)
+        .await
+        .map_err(|e| domain_error_to_problem(e, instance))?;
+    if plugin.plugin_type != plugin_type {
+        return Err(domain_error_to_problem(
+            DomainError::not_found("plugin", uuid),
+            instance,
+        ));
+    }
+    Ok(plugin)
+}
+
+pub async fn create_plugin(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Json(req): Json<CreatePluginRequest>,
+) -> Result<impl IntoResponse, Problem> {
+    let plugin = state
+        .cp
+        .create_plugin(&ctx, req.into())
+        .await
+        .map_err(|e| domain_error_to_problem(e, "/oagw/v1/plugins"))?;
+    Ok((StatusCode::CREATED, Json(to_response(plugin))))
+}
+
+pub async fn get_plugin(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/plugins/{id}");
+    let plugin = load_plugin(&state, &ctx, &id, &instance).await?;
+    Ok(Json(to_response(plugin)))
+}
+
+pub async fn get_plugin_source(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/plugins/{id}/source");
+    let plugin = load_plugin(&state, &ctx, &id, &instance).await?;
+    Ok((
+        [(http::header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        plugin.source_code,
+    ))
+}
+
+pub async fn list_plugins(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<impl IntoResponse, Problem> {
+    let query = pagination.to_list_query();
+    let plugins = state
+        .cp
+        .list_plugins(&ctx, &query)
+        .await
+        .map_err(|e| domain_error_to_problem(e, "/oagw/v1/plugins"))?;
+    let response: Vec<PluginResponse> = plugins.into_iter().map(to_response).collect();
+    Ok(Json(response))
+}
+
+pub async fn delete_plugin(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/plugins/{id}");
+    let plugin = load_plugin(&state, &ctx, &id, &instance).await?;
+    state
+        .cp
+        .delete_plugin(&ctx, plugin.id)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,12 +1,16 @@
 use crate::domain::error::DomainError;
-use crate::infra::proxy::headers;
+use crate::infra::proxy::{grpc, headers, request_body};
 use axum::body::Body;
 use axum::extract::{Extension, Request};
 use axum::response::Response;
+use futures_util::{StreamExt, future, stream};
+use http_body::Frame;
+use http_body_util::StreamBody;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::Trailers;
 
-use crate::api::rest::error::error_response;
+use crate::api::rest::error::{error_response, grpc_error_response};
 use crate::module::AppState;
 
 /// Proxy handler for `/oagw/v1/proxy/{alias}/{path:.*}`.
@@ -20,6 +24,13 @@ pub async fn proxy_handler(
 ) -> Result<Response, Response> {
     let max_body_size = state.config.max_body_size_bytes;
     let (mut parts, body) = req.into_parts();
+    // Native gRPC clients cannot read Problem bodies; they get gateway errors
+    // as gRPC statuses instead.
+    let error_response: fn(DomainError) -> Response = if grpc::is_grpc_request(&parts.headers) {
+        grpc_error_response
+    } else {
+        error_response
+    };
 
     // Parse alias from the URI to validate it's present.
     let path = parts.uri.path();
@@ -54,25 +65,62 @@ pub async fn proxy_handler(
                 instance: path.to_string(),
             })
         })?;
-        if cl_val > max_body_size {
-            return Err(error_response(DomainError::PayloadTooLarge {
-                detail: format!(
-                    "request body of {cl_val} bytes exceeds maximum of {max_body_size} bytes"
-                ),
-                instance: path.to_string(),
-            }));
-        }
+        request_body::check_declared_length(cl_val, max_body_size, path).map_err(error_response)?;
     }
 
-    // Read body bytes (limited to max_body_size).
-    let body_bytes = axum::body::to_bytes(body, max_body_size)
-        .await
-        .map_err(|_| {
-            error_response(DomainError::PayloadTooLarge {
-                detail: format!("request body exceeds maximum of {max_body_size} bytes"),
+    // Validate Transfer-Encoding: only chunked framing is supported, and it
+    // must not be combined with Content-Length.
+    for te in parts.headers.get_all(http::header::TRANSFER_ENCODING) {
+        let te_str = te.to_str().map_err(|_| {
+            error_response(DomainError::Validation {
+                detail: "invalid Transfer-Encoding header".into(),
                 instance: path.to_string(),
             })
         })?;
+        for coding in te_str.split(',').map(str::trim) {
+            if !coding.eq_ignore_ascii_case("chunked") {
+                return Err(error_response(DomainError::Validation {
+                    detail: format!(
+                        "unsupported transfer encoding '{coding}'; only chunked is supported"
+                    ),
+                    instance: path.to_string(),
+                }));
+            }
+        }
+        if parts.headers.contains_key(http::header::CONTENT_LENGTH) {
+            return Err(error_response(DomainError::Validation {
+                detail: "Content-Length and Transfer-Encoding must not both be set".into(),
+                instance: path.to_string(),
+            }));
+        }
+    }
+
+    // Stream the body to the DP; it fails once more than max_body_size bytes
+    // have arrived. A body of known length is announced with Content-Length
+    // so it keeps fixed-length framing upstream, and one known to be over
+    // the limit is refused before the upstream is contacted.
+    let sdk_body = match http_body::Body::size_hint(&body).exact() {
+        Some(0) => oagw_sdk::Body::Empty,
+        exact => {
+            if let Some(len) = exact {
+                let len = usize::try_from(len).unwrap_or(usize::MAX);
+                request_body::check_declared_length(len, max_body_size, path)
+                    .map_err(error_response)?;
+            }
+            if let Some(len) = exact
+                && !parts.headers.contains_key(http::header::TRANSFER_ENCODING)
+                && !parts.headers.contains_key(http::header::CONTENT_LENGTH)
+            {
+                parts
+                    .headers
+                    .insert(http::header::CONTENT_LENGTH, http::HeaderValue::from(len));
+            }
+            oagw_sdk::Body::Stream(request_body::limited(
+                body.into_data_stream(),
+                max_body_size,
+            ))
+        }
+    };
 
     // Strip the proxy prefix from the URI so the DP receives /{alias}/{path}?query.
     let new_uri_str = if let Some(query) = parts.uri.query() {
@@ -88,7 +136,6 @@ pub async fn proxy_handler(
     })?;
 
     // Build http::Request<Body> for the DP service.
-    let sdk_body = oagw_sdk::Body::from(body_bytes);
     let proxy_req = http::Request::from_parts(parts, sdk_body);
 
     // Execute proxy pipeline.
@@ -107,6 +154,10 @@ pub async fn proxy_handler(
         .copied()
         .unwrap_or(ErrorSource::Upstream);
 
+    // The degradation marker is added by the gateway after the upstream
+    // headers were sanitized, so it survives the x-oagw-* strip below.
+    let degraded = resp_parts.headers.get(headers::DEGRADED_HEADER).cloned();
+
     // Sanitize upstream response headers: strip hop-by-hop and x-oagw-*.
     let mut resp_headers = resp_parts.headers;
     headers::sanitize_response_headers(&mut resp_headers);
@@ -119,11 +170,31 @@ pub async fn proxy_handler(
         builder = builder.header(name, value);
     }
 
+    // A relayed WebSocket handshake needs its upgrade headers back; they were
+    // stripped above as hop-by-hop.
+    if resp_parts.status == http::StatusCode::SWITCHING_PROTOCOLS {
+        builder = builder
+            .header(http::header::CONNECTION, "upgrade")
+            .header(http::header::UPGRADE, "websocket");
+    }
+
     // Add error source header.
     builder = builder.header("x-oagw-error-source", error_source.as_str());
+    if let Some(value) = degraded {
+        builder = builder.header(headers::DEGRADED_HEADER, value);
+    }
 
-    // Stream the response body.
-    let body = Body::from_stream(sdk_body.into_stream());
+    // Stream the response body, followed by the upstream trailers once the
+    // stream has ended.
+    let body = match resp_parts.extensions.get::<Trailers>().cloned() {
+        Some(trailers) => {
+            let data = sdk_body.into_stream().map(|chunk| chunk.map(Frame::data));
+            let tail = stream::once(async move { trailers.get() })
+                .filter_map(|t| future::ready(t.map(|t| Ok(Frame::trailers(t)))));
+            Body::new(StreamBody::new(data.chain(tail)))
+        }
+        None => Body::from_stream(sdk_body.into_stream()),
+    };
 
     builder.body(body).map_err(|e| {
         error_response(DomainError::DownstreamError {
@@ -20,6 +20,9 @@ fn to_response(r: Route) -> RouteResponse {
         match_rules: r.match_rules.into(),
         plugins: r.plugins.map(Into::into),
         rate_limit: r.rate_limit.map(Into::into),
+        grpc_transcoding: r.grpc_transcoding.map(Into::into),
+        response_cache: r.response_cache.map(Into::into),
+        usage_extraction: r.usage_extraction.map(Into::into),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -5,7 +5,9 @@ use http::StatusCode;
 use modkit::api::problem::Problem;
 use modkit_security::SecurityContext;
 
-use crate::api::rest::dto::{CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse};
+use crate::api::rest::dto::{
+    CircuitBreakerStatusResponse, CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse,
+};
 use crate::api::rest::error::domain_error_to_problem;
 use crate::api::rest::extractors::{PaginationQuery, parse_gts_id};
 use crate::domain::gts_helpers as gts;
@@ -24,6 +26,8 @@ fn to_response(u: Upstream) -> UpstreamResponse {
         headers: u.headers.map(Into::into),
         plugins: u.plugins.map(Into::into),
         rate_limit: u.rate_limit.map(Into::into),
+        circuit_breaker: u.circuit_breaker.map(Into::into),
+        load_balancing: u.load_balancing.map(Into::into),
         tags: u.tags,
     }
 }
@@ -101,3 +105,24 @@ pub async fn delete_upstream(
         .map_err(|e| domain_error_to_problem(e, &instance))?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn get_upstream_circuit_breaker(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/circuit-breaker");
+    let uuid = parse_gts_id(&id, &instance)?;
+    let upstream = state
+        .cp
+        .get_upstream(&ctx, uuid)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    let response: Vec<CircuitBreakerStatusResponse> = state
+        .dp
+        .circuit_breaker_status(&upstream)
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    Ok(Json(response))
+}
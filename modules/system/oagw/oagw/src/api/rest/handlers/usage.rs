@@ -0,0 +1,60 @@
+use axum::Json;
+use axum::extract::{Extension, Query};
+use axum::response::IntoResponse;
+use modkit::api::problem::Problem;
+use modkit_security::SecurityContext;
+
+use crate::api::rest::dto::{UsageGroupBy, UsageQueryParams, UsageTotalsResponse};
+use crate::api::rest::extractors::parse_gts_id;
+use crate::domain::gts_helpers as gts;
+use crate::domain::usage::{UsageQuery, UsageTotals};
+use crate::module::AppState;
+
+const INSTANCE: &str = "/oagw/v1/usage";
+
+fn to_response(t: UsageTotals) -> UsageTotalsResponse {
+    UsageTotalsResponse {
+        upstream_id: gts::format_upstream_gts(t.upstream_id),
+        route_id: gts::format_route_gts(t.route_id),
+        subject_id: t.subject_id,
+        requests: t.requests,
+        errors: t.errors,
+        cache_hits: t.cache_hits,
+        degraded: t.degraded,
+        request_bytes: t.request_bytes,
+        response_bytes: t.response_bytes,
+        input_tokens: t.input_tokens,
+        output_tokens: t.output_tokens,
+        total_latency_ms: u64::try_from(t.total_latency.as_millis()).unwrap_or(u64::MAX),
+    }
+}
+
+pub async fn get_usage(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Query(params): Query<UsageQueryParams>,
+) -> Result<impl IntoResponse, Problem> {
+    let upstream_id = match params.upstream_id.as_deref() {
+        Some(id) => Some(parse_gts_id(id, INSTANCE)?),
+        None => None,
+    };
+    let route_id = match params.route_id.as_deref() {
+        Some(id) => Some(parse_gts_id(id, INSTANCE)?),
+        None => None,
+    };
+    let query = UsageQuery {
+        from: params.from,
+        to: params.to,
+        upstream_id,
+        route_id,
+        subject_id: params.subject_id,
+        group_by_subject: params.group_by == Some(UsageGroupBy::Subject),
+    };
+    let response: Vec<UsageTotalsResponse> = state
+        .usage
+        .totals(ctx.subject_tenant_id(), &query)
+        .into_iter()
+        .map(to_response)
+        .collect();
+    Ok(Json(response))
+}
@@ -4,9 +4,11 @@ use modkit::api::operation_builder::LicenseFeature;
 
 use crate::module::AppState;
 
+mod plugin;
 mod proxy;
 mod route;
 mod upstream;
+mod usage;
 
 pub(super) struct License;
 
@@ -26,6 +28,8 @@ pub fn register_routes(
 ) -> Router {
     router = upstream::register(router, openapi);
     router = route::register(router, openapi);
+    router = plugin::register(router, openapi);
+    router = usage::register(router, openapi);
     router = proxy::register(router);
     router.layer(axum::Extension(state))
 }
@@ -36,7 +40,10 @@ pub fn register_routes(
 /// Suitable for integration tests that don't need an `OpenApiRegistry`.
 #[cfg(any(test, feature = "test-utils"))]
 pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Router {
-    use crate::api::rest::handlers::{proxy as proxy_h, route as route_h, upstream as upstream_h};
+    use crate::api::rest::handlers::{
+        plugin as plugin_h, proxy as proxy_h, route as route_h, upstream as upstream_h,
+        usage as usage_h,
+    };
     use axum::routing::{any, get, post};
 
     Router::new()
@@ -49,6 +56,10 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
                 .patch(upstream_h::update_upstream)
                 .delete(upstream_h::delete_upstream),
         )
+        .route(
+            "/oagw/v1/upstreams/{id}/circuit-breaker",
+            get(upstream_h::get_upstream_circuit_breaker),
+        )
         // Route CRUD
         .route("/oagw/v1/routes", post(route_h::create_route))
         .route(
@@ -61,6 +72,21 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
             "/oagw/v1/upstreams/{upstream_id}/routes",
             get(route_h::list_routes),
         )
+        // Custom plugins (immutable: no PATCH/PUT)
+        .route(
+            "/oagw/v1/plugins",
+            post(plugin_h::create_plugin).get(plugin_h::list_plugins),
+        )
+        .route(
+            "/oagw/v1/plugins/{id}",
+            get(plugin_h::get_plugin).delete(plugin_h::delete_plugin),
+        )
+        .route(
+            "/oagw/v1/plugins/{id}/source",
+            get(plugin_h::get_plugin_source),
+        )
+        // Usage
+        .route("/oagw/v1/usage", get(usage_h::get_usage))
         // Proxy
         .route("/oagw/v1/proxy/{*path}", any(proxy_h::proxy_handler))
         .layer(axum::Extension(ctx))
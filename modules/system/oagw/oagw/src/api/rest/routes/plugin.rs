@@ -0,0 +1,101 @@
+use axum::Router;
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::OperationBuilder;
+
+use super::super::dto;
+use super::super::handlers;
+use super::License;
+
+pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // POST /oagw/v1/plugins — Create custom plugin
+    router = OperationBuilder::post("/oagw/v1/plugins")
+        .operation_id("oagw.create_plugin")
+        .summary("Create custom plugin")
+        .description(
+            "Compile and store a Starlark guard or transform plugin. Plugins are immutable",
+        )
+        .tag("plugins")
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<dto::CreatePluginRequest>(openapi, "Plugin definition")
+        .handler(handlers::plugin::create_plugin)
+        .json_response_with_schema::<dto::PluginResponse>(
+            openapi,
+            http::StatusCode::CREATED,
+            "Created plugin",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /oagw/v1/plugins — List custom plugins
+    router = OperationBuilder::get("/oagw/v1/plugins")
+        .operation_id("oagw.list_plugins")
+        .summary("List custom plugins")
+        .description("Retrieve the custom plugins of the current tenant")
+        .tag("plugins")
+        .query_param_typed(
+            "limit",
+            false,
+            "Maximum number of results (default 50, max 100)",
+            "integer",
+        )
+        .query_param_typed("offset", false, "Number of results to skip", "integer")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::plugin::list_plugins)
+        .json_response_with_schema::<Vec<dto::PluginResponse>>(
+            openapi,
+            http::StatusCode::OK,
+            "List of plugins",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /oagw/v1/plugins/{id} — Get custom plugin
+    router = OperationBuilder::get("/oagw/v1/plugins/{id}")
+        .operation_id("oagw.get_plugin")
+        .summary("Get custom plugin by ID")
+        .description("Retrieve a custom plugin's metadata by its GTS identifier")
+        .tag("plugins")
+        .path_param("id", "Plugin GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::plugin::get_plugin)
+        .json_response_with_schema::<dto::PluginResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Plugin found",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /oagw/v1/plugins/{id}/source — Get custom plugin source
+    router = OperationBuilder::get("/oagw/v1/plugins/{id}/source")
+        .operation_id("oagw.get_plugin_source")
+        .summary("Get custom plugin source")
+        .description("Retrieve the Starlark source of a custom plugin")
+        .tag("plugins")
+        .path_param("id", "Plugin GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::plugin::get_plugin_source)
+        .text_response(http::StatusCode::OK, "Plugin source", "text/plain")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /oagw/v1/plugins/{id} — Delete custom plugin
+    router = OperationBuilder::delete("/oagw/v1/plugins/{id}")
+        .operation_id("oagw.delete_plugin")
+        .summary("Delete custom plugin")
+        .description("Delete a custom plugin that no upstream or route references")
+        .tag("plugins")
+        .path_param("id", "Plugin GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::plugin::delete_plugin)
+        .json_response(http::StatusCode::NO_CONTENT, "Plugin deleted")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router
+}
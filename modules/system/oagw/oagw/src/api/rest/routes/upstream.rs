@@ -86,6 +86,24 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // GET /oagw/v1/upstreams/{id}/circuit-breaker — Circuit breaker state
+    router = OperationBuilder::get("/oagw/v1/upstreams/{id}/circuit-breaker")
+        .operation_id("oagw.get_upstream_circuit_breaker")
+        .summary("Get upstream circuit breaker state")
+        .description("Retrieve the current circuit breaker state for each circuit of an upstream")
+        .tag("upstreams")
+        .path_param("id", "Upstream GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::get_upstream_circuit_breaker)
+        .json_response_with_schema::<Vec<dto::CircuitBreakerStatusResponse>>(
+            openapi,
+            http::StatusCode::OK,
+            "Circuit breaker state",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     // DELETE /oagw/v1/upstreams/{id} — Delete upstream
     router = OperationBuilder::delete("/oagw/v1/upstreams/{id}")
         .operation_id("oagw.delete_upstream")
@@ -0,0 +1,51 @@
+use axum::Router;
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::OperationBuilder;
+
+use super::super::dto;
+use super::super::handlers;
+use super::License;
+
+pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+    // GET /oagw/v1/usage — Usage of proxied traffic
+    router = OperationBuilder::get("/oagw/v1/usage")
+        .operation_id("oagw.get_usage")
+        .summary("Get usage of proxied traffic")
+        .description(
+            "Aggregate the current tenant's proxied requests, bytes and upstream-reported tokens per route",
+        )
+        .tag("usage")
+        .query_param_typed(
+            "from",
+            false,
+            "Start of the window (RFC 3339), inclusive",
+            "string",
+        )
+        .query_param_typed(
+            "to",
+            false,
+            "End of the window (RFC 3339), exclusive",
+            "string",
+        )
+        .query_param_typed("upstream_id", false, "Upstream GTS identifier", "string")
+        .query_param_typed("route_id", false, "Route GTS identifier", "string")
+        .query_param_typed("subject_id", false, "Calling subject", "string")
+        .query_param_typed(
+            "group_by",
+            false,
+            "`subject` to report each subject separately",
+            "string",
+        )
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::usage::get_usage)
+        .json_response_with_schema::<Vec<dto::UsageTotalsResponse>>(
+            openapi,
+            http::StatusCode::OK,
+            "Usage per route",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router
+}
@@ -11,8 +11,26 @@ pub struct OagwConfig {
     pub proxy_timeout_secs: u64,
     #[serde(default = "default_max_body_size_bytes")]
     pub max_body_size_bytes: usize,
+    /// Seconds a proxied WebSocket connection may go without frames in
+    /// either direction before the gateway closes it.
+    #[serde(default = "default_websocket_idle_timeout_secs")]
+    pub websocket_idle_timeout_secs: u64,
+    /// Bytes of upstream responses kept for routes with `response_cache`.
+    #[serde(default = "default_response_cache_capacity_bytes")]
+    pub response_cache_capacity_bytes: usize,
+    /// Responses larger than this are never cached.
+    #[serde(default = "default_response_cache_max_entry_bytes")]
+    pub response_cache_max_entry_bytes: usize,
+    /// Seconds the in-process usage aggregate keeps per-minute totals.
+    #[serde(default = "default_usage_retention_secs")]
+    pub usage_retention_secs: u64,
+    /// Milliseconds a custom plugin hook may run before the call fails.
+    #[serde(default = "default_plugin_timeout_ms")]
+    pub plugin_timeout_ms: u64,
     /// Optional credentials to pre-load into the in-memory credential resolver.
     /// Keys are secret references (e.g., `cred://openai-key`), values are secrets.
+    /// Used for references the credential-resolver module does not know, or
+    /// for all references when that module is not deployed.
     /// Intended for development and testing only.
     #[serde(default)]
     pub credentials: HashMap<String, String>,
@@ -23,6 +41,11 @@ impl Default for OagwConfig {
         Self {
             proxy_timeout_secs: default_proxy_timeout_secs(),
             max_body_size_bytes: default_max_body_size_bytes(),
+            websocket_idle_timeout_secs: default_websocket_idle_timeout_secs(),
+            response_cache_capacity_bytes: default_response_cache_capacity_bytes(),
+            response_cache_max_entry_bytes: default_response_cache_max_entry_bytes(),
+            usage_retention_secs: default_usage_retention_secs(),
+            plugin_timeout_ms: default_plugin_timeout_ms(),
             credentials: HashMap::new(),
         }
     }
@@ -36,6 +59,26 @@ fn default_max_body_size_bytes() -> usize {
     10 * 1024 * 1024 // 10 MB
 }
 
+fn default_websocket_idle_timeout_secs() -> u64 {
+    300
+}
+
+fn default_response_cache_capacity_bytes() -> usize {
+    crate::infra::proxy::response_cache::DEFAULT_CAPACITY_BYTES
+}
+
+fn default_response_cache_max_entry_bytes() -> usize {
+    crate::infra::proxy::response_cache::DEFAULT_MAX_ENTRY_BYTES
+}
+
+fn default_usage_retention_secs() -> u64 {
+    crate::infra::usage::DEFAULT_RETENTION.as_secs()
+}
+
+fn default_plugin_timeout_ms() -> u64 {
+    100
+}
+
 /// Read-only runtime configuration exposed to handlers via `AppState`.
 ///
 /// Derived from [`OagwConfig`] at init time, excluding sensitive fields
@@ -58,6 +101,20 @@ impl fmt::Debug for OagwConfig {
         f.debug_struct("OagwConfig")
             .field("proxy_timeout_secs", &self.proxy_timeout_secs)
             .field("max_body_size_bytes", &self.max_body_size_bytes)
+            .field(
+                "websocket_idle_timeout_secs",
+                &self.websocket_idle_timeout_secs,
+            )
+            .field(
+                "response_cache_capacity_bytes",
+                &self.response_cache_capacity_bytes,
+            )
+            .field(
+                "response_cache_max_entry_bytes",
+                &self.response_cache_max_entry_bytes,
+            )
+            .field("usage_retention_secs", &self.usage_retention_secs)
+            .field("plugin_timeout_ms", &self.plugin_timeout_ms)
             .field(
                 "credentials",
                 &self
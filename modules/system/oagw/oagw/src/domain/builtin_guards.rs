@@ -0,0 +1,369 @@
+//! Configuration of the builtin CORS and timeout guard plugins.
+//!
+//! Builtin guards are attached like any other plugin, by listing their GTS id
+//! in `plugins.items`; their settings are the `plugins.config` entry under the
+//! same id. The gateway enforces them itself instead of running a script.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::gts_helpers::{CORS_GUARD_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID};
+use super::model::PluginsConfig;
+
+/// `allowed_origins` entry matching any origin.
+pub(crate) const WILDCARD_ORIGIN: &str = "*";
+
+/// Longest preflight cache duration a CORS config may ask browsers for.
+const MAX_PREFLIGHT_AGE_SECS: u32 = 86_400;
+
+/// Methods a CORS config may allow.
+const CORS_METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Settings of the builtin CORS guard.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CorsConfig {
+    /// Origins matched exactly (scheme, host and port), or `*` for any.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    #[serde(default = "default_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    /// Request headers a preflight may announce, matched case-insensitively.
+    #[serde(default = "default_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    /// Seconds browsers may cache a preflight answer.
+    #[serde(default = "default_max_age")]
+    pub max_age: u32,
+    #[serde(default)]
+    pub allow_credentials: bool,
+}
+
+fn default_allowed_methods() -> Vec<String> {
+    vec!["GET".into(), "POST".into()]
+}
+
+fn default_allowed_headers() -> Vec<String> {
+    vec!["Content-Type".into(), "Authorization".into()]
+}
+
+fn default_max_age() -> u32 {
+    MAX_PREFLIGHT_AGE_SECS
+}
+
+impl CorsConfig {
+    /// Whether `allowed_origins` lets any origin in.
+    pub(crate) fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|o| o == WILDCARD_ORIGIN)
+    }
+
+    pub(crate) fn allows_origin(&self, origin: &str) -> bool {
+        self.allows_any_origin() || self.allowed_origins.iter().any(|o| o == origin)
+    }
+
+    pub(crate) fn allows_method(&self, method: &str) -> bool {
+        self.allowed_methods.iter().any(|m| m == method)
+    }
+
+    /// First entry of a comma-separated `Access-Control-Request-Headers`
+    /// value that is not in `allowed_headers`.
+    pub(crate) fn disallowed_header<'a>(&self, requested: &'a str) -> Option<&'a str> {
+        requested
+            .split(',')
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .find(|h| {
+                !self
+                    .allowed_headers
+                    .iter()
+                    .any(|a| a.eq_ignore_ascii_case(h))
+            })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.allow_credentials && self.allows_any_origin() {
+            return Err(
+                "allow_credentials cannot be used with the wildcard origin '*'; list the allowed origins explicitly"
+                    .into(),
+            );
+        }
+        if let Some(origin) = self
+            .allowed_origins
+            .iter()
+            .find(|o| *o != WILDCARD_ORIGIN && !is_serialized_origin(o))
+        {
+            return Err(format!(
+                "allowed_origins: '{origin}' is not an origin (scheme://host[:port])"
+            ));
+        }
+        if let Some(method) = self
+            .allowed_methods
+            .iter()
+            .find(|m| !CORS_METHODS.contains(&m.as_str()))
+        {
+            return Err(format!(
+                "allowed_methods: '{method}' is not one of {}",
+                CORS_METHODS.join(", ")
+            ));
+        }
+        if let Some(header) = self
+            .allowed_headers
+            .iter()
+            .chain(&self.expose_headers)
+            .find(|h| !is_token(h))
+        {
+            return Err(format!("'{header}' is not a valid header name"));
+        }
+        if self.max_age > MAX_PREFLIGHT_AGE_SECS {
+            return Err(format!("max_age must not exceed {MAX_PREFLIGHT_AGE_SECS}"));
+        }
+        Ok(())
+    }
+}
+
+/// `scheme://host[:port]`, without path, query or trailing slash.
+fn is_serialized_origin(origin: &str) -> bool {
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return false;
+    };
+    !scheme.is_empty()
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !authority.is_empty()
+        && !authority.contains(['/', '?', '#', '@', ' '])
+}
+
+/// RFC 9110 token, the syntax of header names.
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Settings of the builtin timeout guard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TimeoutConfig {
+    /// Bound on the whole exchange, from arrival of the request until the
+    /// response body has been relayed.
+    #[serde(default, with = "modkit_utils::humantime_serde::option")]
+    pub request_timeout: Option<Duration>,
+    /// Bound on waiting for the upstream's response headers. Replaces the
+    /// gateway-wide request timeout for the route.
+    #[serde(default, with = "modkit_utils::humantime_serde::option")]
+    pub first_byte_timeout: Option<Duration>,
+}
+
+impl TimeoutConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.request_timeout.is_none() && self.first_byte_timeout.is_none() {
+            return Err("set request_timeout, first_byte_timeout or both".into());
+        }
+        for (name, value) in [
+            ("request_timeout", self.request_timeout),
+            ("first_byte_timeout", self.first_byte_timeout),
+        ] {
+            if value.is_some_and(|d| d.is_zero()) {
+                return Err(format!("{name} must be greater than zero"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builtin guards in effect for a proxy call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BuiltinGuards {
+    pub cors: Option<CorsConfig>,
+    pub timeout: Option<TimeoutConfig>,
+}
+
+impl BuiltinGuards {
+    /// Builtin guards attached to the given plugin layers, upstream first.
+    /// A guard attached to a later layer replaces the earlier binding.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable reason when a bound config cannot be parsed.
+    pub(crate) fn resolve<'a>(
+        layers: impl IntoIterator<Item = Option<&'a PluginsConfig>>,
+    ) -> Result<Self, String> {
+        let mut guards = Self::default();
+        for plugins in layers.into_iter().flatten() {
+            if let Some(cors) = parse(plugins, CORS_GUARD_PLUGIN_ID)? {
+                guards.cors = Some(cors);
+            }
+            if let Some(timeout) = parse(plugins, TIMEOUT_GUARD_PLUGIN_ID)? {
+                guards.timeout = Some(timeout);
+            }
+        }
+        Ok(guards)
+    }
+}
+
+/// Reject builtin guard configs in `plugins` that cannot be enforced.
+///
+/// # Errors
+///
+/// Returns a human-readable reason naming the offending plugin.
+pub(crate) fn validate(plugins: &PluginsConfig) -> Result<(), String> {
+    let invalid = |id: &str, e: String| format!("invalid config for '{id}': {e}");
+    if let Some(cors) = parse::<CorsConfig>(plugins, CORS_GUARD_PLUGIN_ID)? {
+        cors.validate()
+            .map_err(|e| invalid(CORS_GUARD_PLUGIN_ID, e))?;
+    }
+    if let Some(timeout) = parse::<TimeoutConfig>(plugins, TIMEOUT_GUARD_PLUGIN_ID)? {
+        timeout
+            .validate()
+            .map_err(|e| invalid(TIMEOUT_GUARD_PLUGIN_ID, e))?;
+    }
+    Ok(())
+}
+
+/// Config bound to builtin guard `id`, if `plugins` attaches it. A guard
+/// attached without a binding uses its defaults.
+fn parse<T: serde::de::DeserializeOwned>(
+    plugins: &PluginsConfig,
+    id: &str,
+) -> Result<Option<T>, String> {
+    if !plugins.items.iter().any(|item| item == id) {
+        return Ok(None);
+    }
+    let binding = plugins
+        .config
+        .get(id)
+        .cloned()
+        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+    serde_json::from_value(binding)
+        .map(Some)
+        .map_err(|e| format!("invalid config for '{id}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn plugins(id: &str, config: Option<serde_json::Value>) -> PluginsConfig {
+        PluginsConfig {
+            sharing: Default::default(),
+            items: vec![id.to_string()],
+            config: config
+                .map(|c| HashMap::from([(id.to_string(), c)]))
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn cors_defaults_apply_without_binding() {
+        let guards = BuiltinGuards::resolve([Some(&plugins(CORS_GUARD_PLUGIN_ID, None))]).unwrap();
+        let cors = guards.cors.unwrap();
+        assert!(cors.allowed_origins.is_empty());
+        assert_eq!(cors.allowed_methods, ["GET", "POST"]);
+        assert_eq!(cors.allowed_headers, ["Content-Type", "Authorization"]);
+        assert_eq!(cors.max_age, 86_400);
+        assert!(!cors.allow_credentials);
+        assert!(guards.timeout.is_none());
+    }
+
+    #[test]
+    fn route_binding_replaces_upstream_binding() {
+        let upstream = plugins(
+            TIMEOUT_GUARD_PLUGIN_ID,
+            Some(json!({"request_timeout": "5s"})),
+        );
+        let route = plugins(
+            TIMEOUT_GUARD_PLUGIN_ID,
+            Some(json!({"first_byte_timeout": "100ms"})),
+        );
+        let guards = BuiltinGuards::resolve([Some(&upstream), Some(&route)]).unwrap();
+        assert_eq!(
+            guards.timeout,
+            Some(TimeoutConfig {
+                request_timeout: None,
+                first_byte_timeout: Some(Duration::from_millis(100)),
+            })
+        );
+    }
+
+    #[test]
+    fn credentials_with_wildcard_origin_are_rejected() {
+        let err = validate(&plugins(
+            CORS_GUARD_PLUGIN_ID,
+            Some(json!({"allowed_origins": ["*"], "allow_credentials": true})),
+        ))
+        .unwrap_err();
+        assert!(err.contains("allow_credentials cannot be used with the wildcard origin"));
+
+        validate(&plugins(
+            CORS_GUARD_PLUGIN_ID,
+            Some(
+                json!({"allowed_origins": ["https://app.example.com"], "allow_credentials": true}),
+            ),
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn malformed_cors_configs_are_rejected() {
+        for config in [
+            json!({"allowed_origins": ["https://app.example.com/"]}),
+            json!({"allowed_origins": ["app.example.com"]}),
+            json!({"allowed_methods": ["FETCH"]}),
+            json!({"allowed_headers": ["Content Type"]}),
+            json!({"max_age": 86_401}),
+            json!({"allowed_origin": ["*"]}),
+        ] {
+            assert!(
+                validate(&plugins(CORS_GUARD_PLUGIN_ID, Some(config.clone()))).is_err(),
+                "{config} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn timeout_config_needs_a_positive_timeout() {
+        for config in [
+            json!({}),
+            json!({"request_timeout": "0s"}),
+            json!({"first_byte_timeout": "soon"}),
+        ] {
+            assert!(
+                validate(&plugins(TIMEOUT_GUARD_PLUGIN_ID, Some(config.clone()))).is_err(),
+                "{config} was accepted"
+            );
+        }
+        validate(&plugins(
+            TIMEOUT_GUARD_PLUGIN_ID,
+            Some(json!({"request_timeout": "30s", "first_byte_timeout": "2s"})),
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn requested_headers_are_matched_case_insensitively() {
+        let cors: CorsConfig = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(cors.disallowed_header("content-type, AUTHORIZATION"), None);
+        assert_eq!(
+            cors.disallowed_header("content-type, x-debug"),
+            Some("x-debug")
+        );
+    }
+
+    #[test]
+    fn origins_match_exactly() {
+        let cors: CorsConfig =
+            serde_json::from_value(json!({"allowed_origins": ["https://app.example.com"]}))
+                .unwrap();
+        assert!(cors.allows_origin("https://app.example.com"));
+        assert!(!cors.allows_origin("http://app.example.com"));
+        assert!(!cors.allows_origin("https://app.example.com:8443"));
+        assert!(!cors.allows_origin("https://evil.com"));
+    }
+}
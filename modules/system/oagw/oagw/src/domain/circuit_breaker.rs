@@ -0,0 +1,654 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    CircuitBreakerConfig, CircuitBreakerScope, CircuitBreakerStatus, CircuitState, Upstream,
+};
+use crate::domain::services::ConfigChangeListener;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Number of buckets the failure-rate window is split into.
+const WINDOW_BUCKETS: u64 = 10;
+
+/// In-memory circuit breakers keyed by upstream (global scope) or by
+/// upstream endpoint (per-endpoint scope). An upstream's circuits are reset
+/// when it is updated or deleted.
+#[domain_model]
+pub struct CircuitBreakerRegistry {
+    circuits: DashMap<String, Arc<Mutex<Circuit>>>,
+}
+
+impl CircuitBreakerRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            circuits: DashMap::new(),
+        }
+    }
+
+    /// Circuit key for a call to `host:port` on `upstream_id` under `scope`.
+    #[must_use]
+    pub fn key(upstream_id: Uuid, scope: CircuitBreakerScope, host: &str, port: u16) -> String {
+        match scope {
+            CircuitBreakerScope::Global => upstream_id.to_string(),
+            CircuitBreakerScope::PerEndpoint => format!("{upstream_id}/{host}:{port}"),
+        }
+    }
+
+    /// Admit a call through the circuit at `key`.
+    ///
+    /// The returned permit must be resolved with [`CircuitPermit::success`] or
+    /// [`CircuitPermit::failure`]; dropping it records nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::CircuitBreakerOpen` while the circuit is open or
+    /// all half-open probe slots are taken.
+    pub fn try_acquire(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        target: &str,
+        instance: &str,
+    ) -> Result<CircuitPermit, DomainError> {
+        self.try_acquire_at(key, config, target, instance, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        target: &str,
+        instance: &str,
+        now: Instant,
+    ) -> Result<CircuitPermit, DomainError> {
+        let circuit = self.circuit(key, config, now);
+        let mut state = lock(&circuit);
+        match state.admit(now) {
+            Ok(probe) => {
+                let generation = state.generation;
+                drop(state);
+                Ok(CircuitPermit {
+                    circuit,
+                    generation,
+                    probe,
+                    resolved: false,
+                })
+            }
+            Err(retry_after) => Err(DomainError::CircuitBreakerOpen {
+                detail: format!("circuit breaker is open for {target}"),
+                instance: instance.to_string(),
+                retry_after_secs: Some(retry_after.as_secs_f64().ceil().max(1.0) as u64),
+            }),
+        }
+    }
+
+    /// Drop every circuit of `upstream_id`.
+    pub fn evict(&self, upstream_id: Uuid) {
+        let global = upstream_id.to_string();
+        let per_endpoint = format!("{global}/");
+        self.circuits
+            .retain(|key, _| *key != global && !key.starts_with(&per_endpoint));
+    }
+
+    /// Current state of every circuit that `upstream` can have. Circuits that
+    /// have not seen any traffic are reported as closed.
+    #[must_use]
+    pub fn status(&self, upstream: &Upstream) -> Vec<CircuitBreakerStatus> {
+        let Some(config) = upstream.circuit_breaker.as_ref().filter(|c| c.enabled) else {
+            return Vec::new();
+        };
+        let now = Instant::now();
+        let targets: Vec<(String, Option<String>)> = match config.scope {
+            CircuitBreakerScope::Global => vec![(upstream.id.to_string(), None)],
+            CircuitBreakerScope::PerEndpoint => upstream
+                .server
+                .endpoints
+                .iter()
+                .map(|e| {
+                    (
+                        Self::key(upstream.id, config.scope, &e.host, e.port),
+                        Some(format!("{}:{}", e.host, e.port)),
+                    )
+                })
+                .collect(),
+        };
+
+        targets
+            .into_iter()
+            .map(|(key, endpoint)| {
+                let circuit = self.circuits.get(&key).map(|c| Arc::clone(c.value()));
+                match circuit {
+                    Some(c) => lock(&c).snapshot(endpoint, now),
+                    None => CircuitBreakerStatus {
+                        endpoint,
+                        state: CircuitState::Closed,
+                        consecutive_failures: 0,
+                        window_requests: 0,
+                        window_failures: 0,
+                        retry_after_secs: None,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn circuit(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        now: Instant,
+    ) -> Arc<Mutex<Circuit>> {
+        let entry = self
+            .circuits
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(Circuit::new(config, now))));
+        let circuit = Arc::clone(entry.value());
+        drop(entry);
+
+        let mut state = lock(&circuit);
+        if state.config != *config {
+            *state = Circuit::new(config, now);
+        }
+        drop(state);
+        circuit
+    }
+}
+
+impl Default for CircuitBreakerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigChangeListener for CircuitBreakerRegistry {
+    fn upstream_changed(&self, id: Uuid, _alias: &str) {
+        self.evict(id);
+    }
+
+    fn route_changed(&self, _route_id: Uuid) {}
+}
+
+fn lock(circuit: &Mutex<Circuit>) -> MutexGuard<'_, Circuit> {
+    circuit
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Admission granted by [`CircuitBreakerRegistry::try_acquire`].
+#[domain_model]
+pub struct CircuitPermit {
+    circuit: Arc<Mutex<Circuit>>,
+    /// Outcomes from before the last state transition are ignored.
+    generation: u64,
+    probe: bool,
+    resolved: bool,
+}
+
+impl CircuitPermit {
+    pub fn success(self) {
+        self.record_at(false, Instant::now());
+    }
+
+    pub fn failure(self) {
+        self.record_at(true, Instant::now());
+    }
+
+    fn record_at(mut self, failed: bool, now: Instant) {
+        self.resolved = true;
+        lock(&self.circuit).record(self.generation, self.probe, failed, now);
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if !self.resolved && self.probe {
+            lock(&self.circuit).release_probe(self.generation);
+        }
+    }
+}
+
+#[domain_model]
+struct Circuit {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    opened_at: Instant,
+    generation: u64,
+    consecutive_failures: u32,
+    window: FailureWindow,
+    probes_in_flight: u32,
+    probe_successes: u32,
+}
+
+impl Circuit {
+    fn new(config: &CircuitBreakerConfig, now: Instant) -> Self {
+        Self {
+            config: config.clone(),
+            state: CircuitState::Closed,
+            opened_at: now,
+            generation: 0,
+            consecutive_failures: 0,
+            window: FailureWindow::new(config.window_seconds, now),
+            probes_in_flight: 0,
+            probe_successes: 0,
+        }
+    }
+
+    fn open_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.config.timeout_seconds))
+    }
+
+    /// Returns whether the admitted call is a half-open probe, or how long
+    /// until the circuit lets calls through again.
+    fn admit(&mut self, now: Instant) -> Result<bool, Duration> {
+        if self.state == CircuitState::Open {
+            let elapsed = now.duration_since(self.opened_at);
+            let timeout = self.open_timeout();
+            if elapsed < timeout {
+                return Err(timeout - elapsed);
+            }
+            self.transition(CircuitState::HalfOpen, now);
+        }
+
+        match self.state {
+            CircuitState::Closed => Ok(false),
+            CircuitState::HalfOpen
+                if self.probes_in_flight < self.config.half_open_max_requests =>
+            {
+                self.probes_in_flight += 1;
+                Ok(true)
+            }
+            CircuitState::HalfOpen | CircuitState::Open => Err(Duration::from_secs(1)),
+        }
+    }
+
+    fn record(&mut self, generation: u64, probe: bool, failed: bool, now: Instant) {
+        if generation != self.generation {
+            return;
+        }
+
+        match self.state {
+            CircuitState::Closed => {
+                self.window.record(failed, now);
+                if !failed {
+                    self.consecutive_failures = 0;
+                    return;
+                }
+                self.consecutive_failures += 1;
+                if self.consecutive_failures >= self.config.failure_threshold
+                    || self.failure_rate_exceeded(now)
+                {
+                    self.transition(CircuitState::Open, now);
+                }
+            }
+            CircuitState::HalfOpen if probe => {
+                self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+                if failed {
+                    self.transition(CircuitState::Open, now);
+                    return;
+                }
+                self.probe_successes += 1;
+                if self.probe_successes >= self.config.success_threshold {
+                    self.transition(CircuitState::Closed, now);
+                }
+            }
+            CircuitState::HalfOpen | CircuitState::Open => {}
+        }
+    }
+
+    fn release_probe(&mut self, generation: u64) {
+        if generation == self.generation && self.state == CircuitState::HalfOpen {
+            self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+        }
+    }
+
+    fn failure_rate_exceeded(&self, now: Instant) -> bool {
+        let Some(threshold) = self.config.failure_rate_threshold else {
+            return false;
+        };
+        let (total, failures) = self.window.counts(now);
+        total >= self.config.minimum_requests
+            && u64::from(failures) * 100 >= u64::from(threshold) * u64::from(total)
+    }
+
+    fn transition(&mut self, to: CircuitState, now: Instant) {
+        tracing::info!(from = ?self.state, to = ?to, "circuit breaker state change");
+        self.state = to;
+        self.generation += 1;
+        self.consecutive_failures = 0;
+        self.probes_in_flight = 0;
+        self.probe_successes = 0;
+        self.window = FailureWindow::new(self.config.window_seconds, now);
+        if to == CircuitState::Open {
+            self.opened_at = now;
+        }
+    }
+
+    fn snapshot(&self, endpoint: Option<String>, now: Instant) -> CircuitBreakerStatus {
+        let (window_requests, window_failures) = self.window.counts(now);
+        let (state, retry_after_secs) = match self.state {
+            CircuitState::Open => {
+                let remaining = self
+                    .open_timeout()
+                    .saturating_sub(now.duration_since(self.opened_at));
+                if remaining.is_zero() {
+                    (CircuitState::HalfOpen, None)
+                } else {
+                    (
+                        CircuitState::Open,
+                        Some(remaining.as_secs_f64().ceil() as u64),
+                    )
+                }
+            }
+            s => (s, None),
+        };
+        CircuitBreakerStatus {
+            endpoint,
+            state,
+            consecutive_failures: self.consecutive_failures,
+            window_requests,
+            window_failures,
+            retry_after_secs,
+        }
+    }
+}
+
+/// Rolling call/failure counts over `window_seconds`, kept in
+/// [`WINDOW_BUCKETS`] fixed-width buckets.
+#[domain_model]
+struct FailureWindow {
+    origin: Instant,
+    bucket_width: Duration,
+    /// `(bucket index, calls, failures)`, slot `i % WINDOW_BUCKETS`.
+    buckets: Vec<(u64, u32, u32)>,
+}
+
+impl FailureWindow {
+    fn new(window_seconds: u32, now: Instant) -> Self {
+        let width_ms = (u64::from(window_seconds) * 1000 / WINDOW_BUCKETS).max(1);
+        Self {
+            origin: now,
+            bucket_width: Duration::from_millis(width_ms),
+            buckets: vec![(u64::MAX, 0, 0); WINDOW_BUCKETS as usize],
+        }
+    }
+
+    fn index(&self, now: Instant) -> u64 {
+        (now.duration_since(self.origin).as_millis() / self.bucket_width.as_millis()) as u64
+    }
+
+    fn record(&mut self, failed: bool, now: Instant) {
+        let index = self.index(now);
+        let slot = &mut self.buckets[(index % WINDOW_BUCKETS) as usize];
+        if slot.0 != index {
+            *slot = (index, 0, 0);
+        }
+        slot.1 += 1;
+        if failed {
+            slot.2 += 1;
+        }
+    }
+
+    fn counts(&self, now: Instant) -> (u32, u32) {
+        let current = self.index(now);
+        self.buckets
+            .iter()
+            .filter(|(i, _, _)| *i != u64::MAX && current.saturating_sub(*i) < WINDOW_BUCKETS)
+            .fold((0, 0), |(calls, failures), (_, c, f)| {
+                (calls + c, failures + f)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 3,
+            success_threshold: 2,
+            timeout_seconds: 10,
+            half_open_max_requests: 1,
+            ..CircuitBreakerConfig::default()
+        }
+    }
+
+    fn fail_n(
+        registry: &CircuitBreakerRegistry,
+        config: &CircuitBreakerConfig,
+        n: u32,
+        now: Instant,
+    ) {
+        for _ in 0..n {
+            registry
+                .try_acquire_at("k", config, "api:443", "/test", now)
+                .unwrap()
+                .record_at(true, now);
+        }
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        fail_n(&registry, &config, 3, now);
+
+        let err = registry
+            .try_acquire_at("k", &config, "api:443", "/test", now)
+            .err()
+            .unwrap();
+        match err {
+            DomainError::CircuitBreakerOpen {
+                retry_after_secs, ..
+            } => assert_eq!(retry_after_secs, Some(10)),
+            other => panic!("expected CircuitBreakerOpen, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn success_resets_consecutive_failures() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        fail_n(&registry, &config, 2, now);
+        registry
+            .try_acquire_at("k", &config, "api:443", "/test", now)
+            .unwrap()
+            .record_at(false, now);
+        fail_n(&registry, &config, 2, now);
+
+        assert!(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", now)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn opens_on_failure_rate_once_minimum_requests_seen() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 100,
+            failure_rate_threshold: Some(50),
+            minimum_requests: 4,
+            ..config()
+        };
+        let now = Instant::now();
+        for failed in [false, true, false] {
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", now)
+                .unwrap()
+                .record_at(failed, now);
+        }
+        // 1/3 failed, below minimum_requests: still closed.
+        registry
+            .try_acquire_at("k", &config, "api:443", "/test", now)
+            .unwrap()
+            .record_at(true, now);
+        // 2/4 failed = 50%: open.
+        assert!(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", now)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn half_open_limits_probes_and_closes_on_success() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        fail_n(&registry, &config, 3, now);
+
+        let later = now + Duration::from_secs(10);
+        let probe = registry
+            .try_acquire_at("k", &config, "api:443", "/test", later)
+            .unwrap();
+        // Only one probe slot.
+        assert!(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", later)
+                .is_err()
+        );
+        probe.record_at(false, later);
+
+        let second = registry
+            .try_acquire_at("k", &config, "api:443", "/test", later)
+            .unwrap();
+        second.record_at(false, later);
+
+        let status = lock(&registry.circuits.get("k").unwrap()).snapshot(None, later);
+        assert_eq!(status.state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_failure_reopens() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        fail_n(&registry, &config, 3, now);
+
+        let later = now + Duration::from_secs(11);
+        registry
+            .try_acquire_at("k", &config, "api:443", "/test", later)
+            .unwrap()
+            .record_at(true, later);
+
+        assert!(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", later)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn dropped_probe_frees_its_slot() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        fail_n(&registry, &config, 3, now);
+
+        let later = now + Duration::from_secs(10);
+        drop(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", later)
+                .unwrap(),
+        );
+        assert!(
+            registry
+                .try_acquire_at("k", &config, "api:443", "/test", later)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn stale_outcomes_are_ignored_after_transition() {
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        let slow = registry
+            .try_acquire_at("k", &config, "api:443", "/test", now)
+            .unwrap();
+        fail_n(&registry, &config, 3, now);
+
+        let later = now + Duration::from_secs(10);
+        let probe = registry
+            .try_acquire_at("k", &config, "api:443", "/test", later)
+            .unwrap();
+        // A failure from before the circuit opened must not re-open it.
+        slow.record_at(true, later);
+        probe.record_at(false, later);
+
+        let status = lock(&registry.circuits.get("k").unwrap()).snapshot(None, later);
+        assert_eq!(status.state, CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn per_endpoint_scope_isolates_endpoints() {
+        let id = Uuid::new_v4();
+        let a = CircuitBreakerRegistry::key(id, CircuitBreakerScope::PerEndpoint, "a", 443);
+        let b = CircuitBreakerRegistry::key(id, CircuitBreakerScope::PerEndpoint, "b", 443);
+        assert_ne!(a, b);
+        assert_eq!(
+            CircuitBreakerRegistry::key(id, CircuitBreakerScope::Global, "a", 443),
+            CircuitBreakerRegistry::key(id, CircuitBreakerScope::Global, "b", 443),
+        );
+
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        for _ in 0..3 {
+            registry
+                .try_acquire_at(&a, &config, "a:443", "/test", now)
+                .unwrap()
+                .record_at(true, now);
+        }
+        assert!(
+            registry
+                .try_acquire_at(&a, &config, "a:443", "/test", now)
+                .is_err()
+        );
+        assert!(
+            registry
+                .try_acquire_at(&b, &config, "b:443", "/test", now)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn upstream_change_resets_its_circuits() {
+        let (changed, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let keys = [
+            CircuitBreakerRegistry::key(changed, CircuitBreakerScope::Global, "a", 443),
+            CircuitBreakerRegistry::key(changed, CircuitBreakerScope::PerEndpoint, "a", 443),
+            CircuitBreakerRegistry::key(other, CircuitBreakerScope::Global, "a", 443),
+        ];
+        let registry = CircuitBreakerRegistry::new();
+        let config = config();
+        let now = Instant::now();
+        for key in &keys {
+            for _ in 0..3 {
+                registry
+                    .try_acquire_at(key, &config, "a:443", "/test", now)
+                    .unwrap()
+                    .record_at(true, now);
+            }
+        }
+
+        registry.upstream_changed(changed, "api");
+        let open: Vec<bool> = keys
+            .iter()
+            .map(|key| {
+                registry
+                    .try_acquire_at(key, &config, "a:443", "/test", now)
+                    .is_err()
+            })
+            .collect();
+        assert_eq!(open, vec![false, false, true]);
+    }
+}
@@ -1,4 +1,5 @@
 use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
 
 /// The resolved secret material.
 #[domain_model]
@@ -34,6 +35,19 @@ impl std::fmt::Display for SecretValue {
     }
 }
 
+/// Opaque version of a secret, read without its value. Two reads return the
+/// same version only while the value they resolve to is unchanged.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SecretVersion(String);
+
+impl SecretVersion {
+    #[must_use]
+    pub(crate) fn new(version: impl Into<String>) -> Self {
+        Self(version.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,17 +74,36 @@ mod tests {
 pub(crate) enum CredentialError {
     #[error("credential not found: {0}")]
     NotFound(String),
+    /// The tenant may not use the reference.
+    #[error("access to credential denied: {0}")]
+    AccessDenied(String),
     #[error("credential error: {0}")]
-    #[allow(dead_code)]
     Internal(String),
 }
 
 /// Trait for resolving secret references to their actual values.
 #[async_trait::async_trait]
 pub(crate) trait CredentialResolver: Send + Sync {
-    /// Resolve a secret reference (e.g. `cred://openai-key`) to its value.
+    /// Resolve a secret reference (e.g. `cred://openai-key`) to its value
+    /// on behalf of the calling tenant.
+    ///
+    /// # Errors
+    /// Returns `CredentialError::NotFound` if the reference does not exist,
+    /// `CredentialError::AccessDenied` if the tenant may not use it.
+    async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretValue, CredentialError>;
+
+    /// Version of the secret [`Self::resolve`] would return, without reading
+    /// its value.
     ///
     /// # Errors
-    /// Returns `CredentialError::NotFound` if the reference does not exist.
-    async fn resolve(&self, secret_ref: &str) -> Result<SecretValue, CredentialError>;
+    /// Same as [`Self::resolve`].
+    async fn version(
+        &self,
+        ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretVersion, CredentialError>;
 }
@@ -44,6 +44,27 @@ pub enum DomainError {
         retry_after_secs: Option<u64>,
     },
 
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    #[error("{detail}")]
+    QueueFull {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
@@ -58,6 +79,143 @@ pub enum DomainError {
 
     #[error("{detail}")]
     RequestTimeout { detail: String, instance: String },
+
+    #[error("{detail}")]
+    PluginNotFound { detail: String, instance: String },
+
+    #[error("{detail}")]
+    PluginInUse { detail: String },
+
+    /// A guard or transform plugin stopped the request with `ctx.reject`.
+    #[error("{detail}")]
+    PluginRejected {
+        status: u16,
+        code: String,
+        detail: String,
+        instance: String,
+    },
+
+    /// A custom plugin raised an error or exceeded its sandbox limits.
+    #[error("{detail}")]
+    PluginFailed { detail: String, instance: String },
+
+    /// The builtin CORS guard refused the request's origin.
+    #[error("{detail}")]
+    CorsOriginNotAllowed { detail: String, instance: String },
+
+    /// A CORS preflight announced a method the CORS guard does not allow.
+    #[error("{detail}")]
+    CorsMethodNotAllowed { detail: String, instance: String },
+
+    /// A CORS preflight announced headers the CORS guard does not allow.
+    #[error("{detail}")]
+    CorsHeadersNotAllowed { detail: String, instance: String },
+}
+
+// ---------------------------------------------------------------------------
+// GTS error type constants
+// ---------------------------------------------------------------------------
+
+pub(crate) const ERR_VALIDATION: &str = "gts.x.core.errors.err.v1~x.oagw.validation.error.v1";
+pub(crate) const ERR_MISSING_TARGET_HOST: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.missing_target_host.v1";
+pub(crate) const ERR_INVALID_TARGET_HOST: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.invalid_target_host.v1";
+pub(crate) const ERR_UNKNOWN_TARGET_HOST: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.unknown_target_host.v1";
+pub(crate) const ERR_AUTH_FAILED: &str = "gts.x.core.errors.err.v1~x.oagw.auth.failed.v1";
+pub(crate) const ERR_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.resource.not_found.v1";
+pub(crate) const ERR_ROUTE_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.route.not_found.v1";
+pub(crate) const ERR_PAYLOAD_TOO_LARGE: &str =
+    "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1";
+pub(crate) const ERR_RATE_LIMIT_EXCEEDED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1";
+pub(crate) const ERR_QUEUE_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.queue.timeout.v1";
+pub(crate) const ERR_QUEUE_FULL: &str = "gts.x.core.errors.err.v1~x.oagw.queue.full.v1";
+pub(crate) const ERR_CIRCUIT_BREAKER_OPEN: &str =
+    "gts.x.core.errors.err.v1~x.oagw.circuit_breaker.open.v1";
+pub(crate) const ERR_SECRET_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.secret.not_found.v1";
+pub(crate) const ERR_DOWNSTREAM: &str = "gts.x.core.errors.err.v1~x.oagw.downstream.error.v1";
+pub(crate) const ERR_PROTOCOL: &str = "gts.x.core.errors.err.v1~x.oagw.protocol.error.v1";
+pub(crate) const ERR_UPSTREAM_DISABLED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.routing.upstream_disabled.v1";
+pub(crate) const ERR_CONNECTION_TIMEOUT: &str =
+    "gts.x.core.errors.err.v1~x.oagw.timeout.connection.v1";
+pub(crate) const ERR_REQUEST_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.timeout.request.v1";
+pub(crate) const ERR_PLUGIN_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.plugin.not_found.v1";
+pub(crate) const ERR_PLUGIN_IN_USE: &str = "gts.x.core.errors.err.v1~x.oagw.plugin.in_use.v1";
+pub(crate) const ERR_PLUGIN_REJECTED: &str = "gts.x.core.errors.err.v1~x.oagw.plugin.rejected.v1";
+pub(crate) const ERR_PLUGIN_FAILED: &str = "gts.x.core.errors.err.v1~x.oagw.plugin.failed.v1";
+pub(crate) const ERR_CORS_ORIGIN_NOT_ALLOWED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.cors.origin_not_allowed.v1";
+pub(crate) const ERR_CORS_METHOD_NOT_ALLOWED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.cors.method_not_allowed.v1";
+pub(crate) const ERR_CORS_HEADERS_NOT_ALLOWED: &str =
+    "gts.x.core.errors.err.v1~x.oagw.cors.headers_not_allowed.v1";
+
+impl DomainError {
+    /// GTS type the error is reported with.
+    #[must_use]
+    pub(crate) fn gts_type(&self) -> &'static str {
+        match self {
+            Self::Validation { .. } | Self::Conflict { .. } => ERR_VALIDATION,
+            Self::MissingTargetHost { .. } => ERR_MISSING_TARGET_HOST,
+            Self::InvalidTargetHost { .. } => ERR_INVALID_TARGET_HOST,
+            Self::UnknownTargetHost { .. } => ERR_UNKNOWN_TARGET_HOST,
+            Self::AuthenticationFailed { .. } => ERR_AUTH_FAILED,
+            Self::NotFound {
+                entity: "route", ..
+            } => ERR_ROUTE_NOT_FOUND,
+            Self::NotFound { .. } => ERR_NOT_FOUND,
+            Self::PayloadTooLarge { .. } => ERR_PAYLOAD_TOO_LARGE,
+            Self::RateLimitExceeded { .. } => ERR_RATE_LIMIT_EXCEEDED,
+            Self::QueueTimeout { .. } => ERR_QUEUE_TIMEOUT,
+            Self::QueueFull { .. } => ERR_QUEUE_FULL,
+            Self::CircuitBreakerOpen { .. } => ERR_CIRCUIT_BREAKER_OPEN,
+            Self::SecretNotFound { .. } => ERR_SECRET_NOT_FOUND,
+            Self::DownstreamError { .. } | Self::Internal { .. } => ERR_DOWNSTREAM,
+            Self::ProtocolError { .. } => ERR_PROTOCOL,
+            Self::UpstreamDisabled { .. } => ERR_UPSTREAM_DISABLED,
+            Self::ConnectionTimeout { .. } => ERR_CONNECTION_TIMEOUT,
+            Self::RequestTimeout { .. } => ERR_REQUEST_TIMEOUT,
+            Self::PluginNotFound { .. } => ERR_PLUGIN_NOT_FOUND,
+            Self::PluginInUse { .. } => ERR_PLUGIN_IN_USE,
+            Self::PluginRejected { .. } => ERR_PLUGIN_REJECTED,
+            Self::PluginFailed { .. } => ERR_PLUGIN_FAILED,
+            Self::CorsOriginNotAllowed { .. } => ERR_CORS_ORIGIN_NOT_ALLOWED,
+            Self::CorsMethodNotAllowed { .. } => ERR_CORS_METHOD_NOT_ALLOWED,
+            Self::CorsHeadersNotAllowed { .. } => ERR_CORS_HEADERS_NOT_ALLOWED,
+        }
+    }
+
+    /// HTTP status code the error is reported with.
+    #[must_use]
+    pub(crate) fn status_code(&self) -> u16 {
+        match self {
+            Self::Validation { .. }
+            | Self::MissingTargetHost { .. }
+            | Self::InvalidTargetHost { .. }
+            | Self::UnknownTargetHost { .. } => 400,
+            Self::Conflict { .. } | Self::PluginInUse { .. } => 409,
+            Self::AuthenticationFailed { .. } => 401,
+            Self::CorsOriginNotAllowed { .. }
+            | Self::CorsMethodNotAllowed { .. }
+            | Self::CorsHeadersNotAllowed { .. } => 403,
+            Self::NotFound { .. } => 404,
+            Self::PayloadTooLarge { .. } => 413,
+            Self::RateLimitExceeded { .. } => 429,
+            Self::SecretNotFound { .. } | Self::Internal { .. } => 500,
+            Self::DownstreamError { .. } | Self::ProtocolError { .. } => 502,
+            Self::UpstreamDisabled { .. }
+            | Self::QueueTimeout { .. }
+            | Self::QueueFull { .. }
+            | Self::CircuitBreakerOpen { .. }
+            | Self::PluginNotFound { .. }
+            | Self::PluginFailed { .. } => 503,
+            Self::ConnectionTimeout { .. } | Self::RequestTimeout { .. } => 504,
+            Self::PluginRejected { status, .. } => *status,
+        }
+    }
 }
 
 impl DomainError {
@@ -105,6 +263,9 @@ impl From<RepositoryError> for DomainError {
         match e {
             RepositoryError::NotFound { entity, id } => Self::NotFound { entity, id },
             RepositoryError::Conflict(detail) => Self::Conflict { detail },
+            e @ RepositoryError::PluginInUse { .. } => Self::PluginInUse {
+                detail: e.to_string(),
+            },
             RepositoryError::Internal(message) => Self::Internal { message },
         }
     }
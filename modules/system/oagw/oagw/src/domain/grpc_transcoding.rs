@@ -0,0 +1,235 @@
+//! JSON ⇄ protobuf conversion for gRPC routes with `grpc_transcoding`.
+//!
+//! The route's descriptor set is decoded once per route configuration and
+//! cached; requests on the route reuse the resolved method descriptor.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MethodDescriptor};
+use uuid::Uuid;
+
+use super::model::{GrpcMatch, GrpcTranscodingConfig, Route};
+
+/// Codec for one transcoded gRPC method.
+#[derive(Debug)]
+pub(crate) struct Transcoder {
+    method: MethodDescriptor,
+    /// Configuration this transcoder was built from, to detect route updates.
+    source: (GrpcTranscodingConfig, GrpcMatch),
+}
+
+impl Transcoder {
+    /// Resolve `grpc.service`/`grpc.method` in the descriptor set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable reason when the descriptor set cannot be
+    /// decoded, does not define the method, or the method streams requests.
+    pub(crate) fn new(config: &GrpcTranscodingConfig, grpc: &GrpcMatch) -> Result<Self, String> {
+        let pool = DescriptorPool::decode(config.descriptor_set.as_slice())
+            .map_err(|e| format!("invalid descriptor_set: {e}"))?;
+        let service = pool.get_service_by_name(&grpc.service).ok_or_else(|| {
+            format!(
+                "service '{}' is not defined in descriptor_set",
+                grpc.service
+            )
+        })?;
+        let method = service
+            .methods()
+            .find(|m| m.name() == grpc.method)
+            .ok_or_else(|| {
+                format!(
+                    "method '{}' is not defined on service '{}'",
+                    grpc.method, grpc.service
+                )
+            })?;
+        if method.is_client_streaming() {
+            return Err(format!(
+                "client-streaming method '{}' cannot be transcoded",
+                grpc.path()
+            ));
+        }
+        Ok(Self {
+            method,
+            source: (config.clone(), grpc.clone()),
+        })
+    }
+
+    /// Whether the upstream answers with a stream of messages.
+    pub(crate) fn is_server_streaming(&self) -> bool {
+        self.method.is_server_streaming()
+    }
+
+    /// Encode a JSON request body as a protobuf message. An empty body is the
+    /// default (all fields unset) message.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error when the body is not valid JSON for the
+    /// method's input type.
+    pub(crate) fn encode_request(&self, json: &[u8]) -> Result<Vec<u8>, String> {
+        let input = self.method.input();
+        if json.iter().all(u8::is_ascii_whitespace) {
+            return Ok(DynamicMessage::new(input).encode_to_vec());
+        }
+        let mut de = serde_json::Deserializer::from_slice(json);
+        let message = DynamicMessage::deserialize(input, &mut de).map_err(|e| e.to_string())?;
+        de.end().map_err(|e| e.to_string())?;
+        Ok(message.encode_to_vec())
+    }
+
+    /// Decode one protobuf response message into its JSON form.
+    ///
+    /// # Errors
+    ///
+    /// Returns the decode error when the bytes are not a valid message of the
+    /// method's output type.
+    pub(crate) fn decode_response(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        let decoded =
+            DynamicMessage::decode(self.method.output(), message).map_err(|e| e.to_string())?;
+        serde_json::to_vec(&decoded).map_err(|e| e.to_string())
+    }
+}
+
+/// Transcoders keyed by route ID.
+#[derive(Default)]
+pub(crate) struct TranscoderCache {
+    entries: DashMap<Uuid, Arc<Transcoder>>,
+}
+
+impl TranscoderCache {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transcoder for `route`, or `None` when the route has no transcoding
+    /// configured. Rebuilt when the route's descriptors or match change.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the route's descriptors cannot be used.
+    pub(crate) fn get(&self, route: &Route) -> Result<Option<Arc<Transcoder>>, String> {
+        let (Some(config), Some(grpc)) = (&route.grpc_transcoding, &route.match_rules.grpc) else {
+            return Ok(None);
+        };
+        if let Some(existing) = self.entries.get(&route.id)
+            && existing.source.0 == *config
+            && existing.source.1 == *grpc
+        {
+            return Ok(Some(Arc::clone(&existing)));
+        }
+        let transcoder = Arc::new(Transcoder::new(config, grpc)?);
+        self.entries.insert(route.id, Arc::clone(&transcoder));
+        Ok(Some(transcoder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::grpc::{User, user_service_descriptor_set};
+
+    fn grpc(method: &str) -> GrpcMatch {
+        GrpcMatch {
+            service: "example.v1.UserService".into(),
+            method: method.into(),
+        }
+    }
+
+    fn config() -> GrpcTranscodingConfig {
+        GrpcTranscodingConfig {
+            descriptor_set: user_service_descriptor_set(),
+        }
+    }
+
+    #[test]
+    fn resolves_unary_and_streaming_methods() {
+        assert!(
+            !Transcoder::new(&config(), &grpc("GetUser"))
+                .unwrap()
+                .is_server_streaming()
+        );
+        assert!(
+            Transcoder::new(&config(), &grpc("ListUsers"))
+                .unwrap()
+                .is_server_streaming()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_service_method_and_garbage() {
+        let err = Transcoder::new(&config(), &grpc("DeleteUser")).unwrap_err();
+        assert!(err.contains("DeleteUser"), "{err}");
+
+        let other = GrpcMatch {
+            service: "example.v1.Missing".into(),
+            method: "GetUser".into(),
+        };
+        assert!(Transcoder::new(&config(), &other).is_err());
+
+        let garbage = GrpcTranscodingConfig {
+            descriptor_set: vec![0xff, 0xff, 0xff],
+        };
+        assert!(Transcoder::new(&garbage, &grpc("GetUser")).is_err());
+    }
+
+    #[test]
+    fn json_request_encodes_to_protobuf() {
+        let t = Transcoder::new(&config(), &grpc("ListUsers")).unwrap();
+        // proto field name and JSON name are both accepted.
+        let snake = t.encode_request(br#"{"page_size": 10}"#).unwrap();
+        let camel = t.encode_request(br#"{"pageSize": 10}"#).unwrap();
+        assert_eq!(snake, vec![0x08, 10]);
+        assert_eq!(snake, camel);
+
+        assert!(t.encode_request(b"").unwrap().is_empty());
+        assert!(t.encode_request(br#"{"page_size": "ten"}"#).is_err());
+        assert!(t.encode_request(br#"{"page_size": 1} trailing"#).is_err());
+    }
+
+    #[test]
+    fn protobuf_response_decodes_to_json() {
+        let t = Transcoder::new(&config(), &grpc("GetUser")).unwrap();
+        let user = User {
+            id: "u1".into(),
+            name: "Ada".into(),
+        };
+        let json = t.decode_response(&user.encode_to_vec()).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value, serde_json::json!({"id": "u1", "name": "Ada"}));
+    }
+
+    #[test]
+    fn cache_rebuilds_when_route_changes() {
+        let cache = TranscoderCache::new();
+        let mut route = Route {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            upstream_id: Uuid::new_v4(),
+            match_rules: crate::domain::model::MatchRules {
+                http: None,
+                grpc: Some(grpc("GetUser")),
+            },
+            plugins: None,
+            rate_limit: None,
+            grpc_transcoding: Some(config()),
+            response_cache: None,
+            usage_extraction: None,
+            tags: vec![],
+            priority: 0,
+            enabled: true,
+        };
+
+        let first = cache.get(&route).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.get(&route).unwrap().unwrap()));
+
+        route.match_rules.grpc = Some(grpc("ListUsers"));
+        let second = cache.get(&route).unwrap().unwrap();
+        assert!(second.is_server_streaming());
+
+        route.grpc_transcoding = None;
+        assert!(cache.get(&route).unwrap().is_none());
+    }
+}
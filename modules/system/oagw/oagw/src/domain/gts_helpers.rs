@@ -4,6 +4,7 @@
 //! resource GTS identifiers of the form `gts.x.core.oagw.<type>.v1~<uuid>`.
 
 use crate::domain::error::DomainError;
+use crate::domain::model::{PluginType, PluginsConfig};
 use uuid::Uuid;
 
 // -- Schema GTS identifiers --
@@ -53,6 +54,55 @@ pub fn format_route_gts(id: Uuid) -> String {
     format!("{ROUTE_SCHEMA}{}", id.simple())
 }
 
+/// Schema of plugins of the given type.
+#[must_use]
+pub fn plugin_schema(plugin_type: PluginType) -> &'static str {
+    match plugin_type {
+        PluginType::Auth => AUTH_PLUGIN_SCHEMA,
+        PluginType::Guard => GUARD_PLUGIN_SCHEMA,
+        PluginType::Transform => TRANSFORM_PLUGIN_SCHEMA,
+    }
+}
+
+/// Format a custom plugin as an anonymous GTS identifier.
+#[must_use]
+pub fn format_plugin_gts(plugin_type: PluginType, id: Uuid) -> String {
+    format!("{}{}", plugin_schema(plugin_type), id.simple())
+}
+
+/// Parse a plugin reference into its type and, for custom plugins, the UUID.
+/// Builtin plugins have named instances and yield `None`.
+pub fn parse_plugin_gts(s: &str) -> Result<(PluginType, Option<Uuid>), DomainError> {
+    let (plugin_type, instance) = [PluginType::Auth, PluginType::Guard, PluginType::Transform]
+        .into_iter()
+        .find_map(|t| s.strip_prefix(plugin_schema(t)).map(|i| (t, i)))
+        .ok_or_else(|| DomainError::Validation {
+            detail: format!("'{s}' is not a plugin identifier"),
+            instance: s.to_string(),
+        })?;
+    if instance.is_empty() {
+        return Err(DomainError::Validation {
+            detail: format!("plugin identifier '{s}' has no instance"),
+            instance: s.to_string(),
+        });
+    }
+    Ok((plugin_type, Uuid::parse_str(instance).ok()))
+}
+
+/// UUIDs of the custom plugins listed in `plugins.items`, without duplicates.
+#[must_use]
+pub fn custom_plugin_ids(plugins: Option<&PluginsConfig>) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> = plugins
+        .map(|p| p.items.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| parse_plugin_gts(item).ok()?.1)
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
 /// Parse a resource GTS identifier, extracting the schema and UUID instance.
 ///
 /// Validates the schema portion using the `gts` crate and parses the instance
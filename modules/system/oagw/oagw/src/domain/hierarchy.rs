@@ -0,0 +1,360 @@
+//! Upstream configuration shared down the tenant tree.
+//!
+//! A tenant resolves an alias to the closest upstream bound to it along its
+//! ancestor chain (shadowing). Auth, rate limits, plugins and tags of the
+//! ancestors' bindings are then merged into it according to their
+//! [`SharingMode`] (see "Hierarchical Configuration" in DESIGN.md).
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{AuthConfig, PluginsConfig, RateLimitConfig, SharingMode, Upstream};
+use crate::domain::rate_limit::window_to_secs;
+
+/// Ancestry of tenants, as far as configuration sharing is concerned.
+#[async_trait::async_trait]
+pub(crate) trait TenantHierarchy: Send + Sync {
+    /// Ancestors of `tenant_id`, ordered from the direct parent to the root.
+    /// A root tenant, or one the hierarchy does not know, has none.
+    async fn ancestors(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError>;
+}
+
+/// Upstream configuration in effect for one tenant.
+#[derive(Debug, Clone)]
+pub(crate) struct EffectiveUpstream {
+    /// The closest binding, with the fields its ancestors share merged in.
+    pub upstream: Upstream,
+    /// `(tenant, upstream)` of each binding of the alias, closest first.
+    /// Routes are looked up along them in this order.
+    pub bindings: Vec<(Uuid, Uuid)>,
+    /// Binding that defines `upstream.rate_limit`. Requests are counted
+    /// against it, so a limit shared by an ancestor is one budget for all
+    /// of its descendants.
+    pub rate_limit_owner: Uuid,
+    /// Tenant owning each custom plugin in `upstream.plugins`, by
+    /// reference: plugins are resolved in the scope of the tenant whose
+    /// binding lists them.
+    pub plugin_owners: HashMap<String, Uuid>,
+}
+
+/// Effective upstream for the tenant owning `bindings[0]`.
+///
+/// `bindings` are the upstreams bound to one alias along the tenant chain,
+/// closest first. The closest binding supplies the endpoints and everything
+/// else that is not shareable; ancestors contribute the fields they share.
+///
+/// # Panics
+///
+/// Panics if `bindings` is empty.
+pub(crate) fn merge_bindings(mut bindings: Vec<Upstream>) -> EffectiveUpstream {
+    let chain = bindings.iter().map(|u| (u.tenant_id, u.id)).collect();
+    let own = bindings.remove(0);
+    // Root first, the caller's own binding last.
+    let layers: Vec<(&Upstream, bool)> = bindings
+        .iter()
+        .rev()
+        .map(|u| (u, false))
+        .chain(std::iter::once((&own, true)))
+        .collect();
+
+    let auth = merge_auth(layers.iter().map(|(u, own)| (u.auth.as_ref(), *own)));
+    let rate_limit = merge_rate_limit(
+        layers
+            .iter()
+            .map(|(u, own)| (u.rate_limit.as_ref(), u.id, *own)),
+    );
+    let (plugins, plugin_owners) = merge_plugins(
+        layers
+            .iter()
+            .map(|(u, own)| (u.plugins.as_ref(), u.tenant_id, *own)),
+    );
+    let tags: BTreeSet<String> = layers.iter().flat_map(|(u, _)| u.tags.clone()).collect();
+
+    EffectiveUpstream {
+        bindings: chain,
+        rate_limit_owner: rate_limit.map_or(own.id, |(_, owner)| owner),
+        plugin_owners,
+        upstream: Upstream {
+            auth,
+            rate_limit: rate_limit.map(|(rl, _)| rl.clone()),
+            plugins,
+            tags: tags.into_iter().collect(),
+            ..own
+        },
+    }
+}
+
+/// A layer's field is visible to the caller when it is the caller's own or
+/// shared by an ancestor.
+fn visible<T>(field: Option<&T>, own: bool, sharing: impl Fn(&T) -> SharingMode) -> Option<&T> {
+    field.filter(|f| own || sharing(f) != SharingMode::Private)
+}
+
+/// Closer layers override inherited auth; enforced auth cannot be overridden.
+fn merge_auth<'a>(
+    layers: impl Iterator<Item = (Option<&'a AuthConfig>, bool)>,
+) -> Option<AuthConfig> {
+    let mut effective: Option<&AuthConfig> = None;
+    for (auth, own) in layers {
+        let Some(auth) = visible(auth, own, |a| a.sharing) else {
+            continue;
+        };
+        if effective.is_some_and(|e| e.sharing == SharingMode::Enforce) {
+            continue;
+        }
+        effective = Some(auth);
+    }
+    effective.cloned()
+}
+
+/// The strictest visible limit applies, whether it was inherited, enforced
+/// or set by the caller. Returned with the upstream that defines it.
+fn merge_rate_limit<'a>(
+    layers: impl Iterator<Item = (Option<&'a RateLimitConfig>, Uuid, bool)>,
+) -> Option<(&'a RateLimitConfig, Uuid)> {
+    let per_second =
+        |rl: &RateLimitConfig| f64::from(rl.sustained.rate) / window_to_secs(&rl.sustained.window);
+    layers
+        .filter_map(|(rl, upstream_id, own)| {
+            visible(rl, own, |r| r.sharing).map(|rl| (rl, upstream_id))
+        })
+        .min_by(|(a, _), (b, _)| per_second(a).total_cmp(&per_second(b)))
+}
+
+/// Visible chains are concatenated from the root down. A plugin listed by
+/// several layers runs once, at its first position, and belongs to the
+/// tenant of that layer; instance config set by an enforced layer cannot be
+/// overridden below it.
+fn merge_plugins<'a>(
+    layers: impl Iterator<Item = (Option<&'a PluginsConfig>, Uuid, bool)>,
+) -> (Option<PluginsConfig>, HashMap<String, Uuid>) {
+    let mut merged: Option<PluginsConfig> = None;
+    let mut owners: HashMap<String, Uuid> = HashMap::new();
+    let mut enforced: HashSet<String> = HashSet::new();
+    for (plugins, tenant_id, own) in layers {
+        let Some(plugins) = visible(plugins, own, |p| p.sharing) else {
+            continue;
+        };
+        let effective = merged.get_or_insert_with(|| PluginsConfig {
+            sharing: plugins.sharing,
+            items: Vec::new(),
+            config: HashMap::new(),
+        });
+        effective.sharing = plugins.sharing;
+        for item in &plugins.items {
+            if !effective.items.contains(item) {
+                effective.items.push(item.clone());
+                owners.insert(item.clone(), tenant_id);
+            }
+        }
+        for (id, config) in &plugins.config {
+            if !enforced.contains(id) {
+                effective.config.insert(id.clone(), config.clone());
+            }
+        }
+        if plugins.sharing == SharingMode::Enforce {
+            enforced.extend(plugins.config.keys().cloned());
+        }
+    }
+    (merged, owners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{
+        Endpoint, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, Scheme, Server,
+        SustainedRate, Window,
+    };
+
+    fn upstream(host: &str) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            alias: "api.openai.com".into(),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: host.into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
+            tags: vec![],
+        }
+    }
+
+    fn auth(secret_ref: &str, sharing: SharingMode) -> Option<AuthConfig> {
+        Some(AuthConfig {
+            plugin_type: "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.apikey.v1".into(),
+            sharing,
+            config: Some(HashMap::from([("secret_ref".into(), secret_ref.into())])),
+        })
+    }
+
+    fn rate_limit(rate: u32, window: Window, sharing: SharingMode) -> Option<RateLimitConfig> {
+        Some(RateLimitConfig {
+            sharing,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            sustained: SustainedRate { rate, window },
+            burst: None,
+            scope: RateLimitScope::Tenant,
+            strategy: RateLimitStrategy::Reject,
+            cost: 1,
+            queue: None,
+            degrade: None,
+        })
+    }
+
+    fn plugins(items: &[&str], sharing: SharingMode) -> Option<PluginsConfig> {
+        Some(PluginsConfig {
+            sharing,
+            items: items.iter().map(|s| (*s).to_string()).collect(),
+            config: HashMap::new(),
+        })
+    }
+
+    fn merge(bindings: Vec<Upstream>) -> Upstream {
+        merge_bindings(bindings).upstream
+    }
+
+    fn secret_ref(u: &Upstream) -> Option<&str> {
+        u.auth
+            .as_ref()
+            .and_then(|a| a.config.as_ref())
+            .and_then(|c| c.get("secret_ref"))
+            .map(String::as_str)
+    }
+
+    #[test]
+    fn closest_binding_supplies_endpoints() {
+        let child = upstream("staging.example.com");
+        let parent = upstream("prod.example.com");
+        let child_id = child.id;
+
+        let merged = merge(vec![child, parent]);
+        assert_eq!(merged.id, child_id);
+        assert_eq!(merged.server.endpoints[0].host, "staging.example.com");
+    }
+
+    #[test]
+    fn auth_follows_sharing_modes() {
+        let mut parent = upstream("p");
+        let mut child = upstream("c");
+
+        // inherit: used when the child has none, overridden otherwise.
+        parent.auth = auth("cred://partner", SharingMode::Inherit);
+        let merged = merge(vec![child.clone(), parent.clone()]);
+        assert_eq!(secret_ref(&merged), Some("cred://partner"));
+        child.auth = auth("cred://customer", SharingMode::Private);
+        let merged = merge(vec![child.clone(), parent.clone()]);
+        assert_eq!(secret_ref(&merged), Some("cred://customer"));
+
+        // enforce: the child cannot override.
+        parent.auth = auth("cred://partner", SharingMode::Enforce);
+        let merged = merge(vec![child.clone(), parent.clone()]);
+        assert_eq!(secret_ref(&merged), Some("cred://partner"));
+
+        // private: the child must bring its own.
+        parent.auth = auth("cred://partner", SharingMode::Private);
+        child.auth = None;
+        let merged = merge(vec![child, parent]);
+        assert!(merged.auth.is_none());
+    }
+
+    #[test]
+    fn strictest_visible_rate_limit_applies() {
+        let mut root = upstream("r");
+        let mut parent = upstream("p");
+        let mut child = upstream("c");
+        root.rate_limit = rate_limit(10, Window::Second, SharingMode::Private);
+        parent.rate_limit = rate_limit(1000, Window::Minute, SharingMode::Enforce);
+        child.rate_limit = rate_limit(100, Window::Minute, SharingMode::Private);
+
+        let merged = merge_bindings(vec![child.clone(), parent.clone(), root.clone()]);
+        assert_eq!(merged.upstream.rate_limit.unwrap().sustained.rate, 100);
+        assert_eq!(merged.rate_limit_owner, child.id);
+
+        // A laxer child limit does not loosen the enforced one, which is
+        // counted against the parent's binding.
+        child.rate_limit = rate_limit(100, Window::Second, SharingMode::Private);
+        let merged = merge_bindings(vec![child.clone(), parent.clone(), root.clone()]);
+        assert_eq!(merged.upstream.rate_limit.unwrap().sustained.rate, 1000);
+        assert_eq!(merged.rate_limit_owner, parent.id);
+
+        // Private ancestor limits are not inherited.
+        child.rate_limit = None;
+        parent.rate_limit = None;
+        let merged = merge(vec![child, parent, root]);
+        assert!(merged.rate_limit.is_none());
+    }
+
+    #[test]
+    fn plugin_chains_are_layered_from_the_root() {
+        let logging = "gts.x.core.oagw.transform_plugin.v1~x.core.oagw.logging.v1";
+        let metrics = "gts.x.core.oagw.transform_plugin.v1~x.core.oagw.metrics.v1";
+        let mut parent = upstream("p");
+        let mut child = upstream("c");
+        parent.plugins = plugins(&[logging], SharingMode::Inherit);
+        child.plugins = plugins(&[metrics, logging], SharingMode::Private);
+
+        let merged = merge_bindings(vec![child.clone(), parent.clone()]);
+        assert_eq!(
+            merged.upstream.plugins.unwrap().items,
+            vec![logging, metrics]
+        );
+        assert_eq!(merged.plugin_owners[logging], parent.tenant_id);
+        assert_eq!(merged.plugin_owners[metrics], child.tenant_id);
+
+        parent.plugins = plugins(&[logging], SharingMode::Private);
+        let merged = merge(vec![child, parent]);
+        assert_eq!(merged.plugins.unwrap().items, vec![metrics, logging]);
+    }
+
+    #[test]
+    fn enforced_plugin_config_cannot_be_overridden() {
+        let guard = "gts.x.core.oagw.guard_plugin.v1~0190e4a2b1c37f0e8a5b2c3d4e5f6a7b";
+        let mut parent = upstream("p");
+        let mut child = upstream("c");
+        let mut parent_plugins = plugins(&[guard], SharingMode::Enforce).unwrap();
+        parent_plugins
+            .config
+            .insert(guard.into(), serde_json::json!({"header": "x-partner"}));
+        parent.plugins = Some(parent_plugins);
+        let mut child_plugins = plugins(&[], SharingMode::Private).unwrap();
+        child_plugins
+            .config
+            .insert(guard.into(), serde_json::json!({"header": "x-customer"}));
+        child.plugins = Some(child_plugins);
+
+        let merged = merge(vec![child, parent]).plugins.unwrap();
+        assert_eq!(merged.items, vec![guard]);
+        assert_eq!(merged.config[guard]["header"], "x-partner");
+    }
+
+    #[test]
+    fn tags_are_additive() {
+        let mut parent = upstream("p");
+        let mut child = upstream("c");
+        parent.tags = vec!["llm".into(), "openai".into()];
+        child.tags = vec!["openai".into(), "customer".into()];
+
+        let merged = merge(vec![child, parent]);
+        assert_eq!(merged.tags, vec!["customer", "llm", "openai"]);
+    }
+}
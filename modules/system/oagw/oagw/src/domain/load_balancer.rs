@@ -0,0 +1,684 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    Endpoint, HealthCheckConfig, LoadBalancingConfig, LoadBalancingStrategy, Upstream,
+};
+use crate::domain::services::ConfigChangeListener;
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+/// Upper bound on a single outlier ejection, however often the endpoint failed.
+const MAX_EJECTION: Duration = Duration::from_secs(300);
+
+/// Per-upstream endpoint pools. A pool is rebuilt (and its health state
+/// discarded) whenever the upstream's endpoints or balancing config change,
+/// and dropped when the upstream is updated or deleted. Replaced pools are
+/// retired, which stops their prober.
+#[domain_model]
+pub struct LoadBalancer {
+    pools: DashMap<Uuid, Arc<EndpointPool>>,
+}
+
+impl LoadBalancer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pools: DashMap::new(),
+        }
+    }
+
+    /// The pool serving `upstream`, created on first use.
+    #[must_use]
+    pub fn pool(&self, upstream: &Upstream) -> Arc<EndpointPool> {
+        let config = upstream.load_balancing.clone().unwrap_or_default();
+        let mut entry = self
+            .pools
+            .entry(upstream.id)
+            .or_insert_with(|| Arc::new(EndpointPool::new(&upstream.server.endpoints, &config)));
+        if entry.endpoints != upstream.server.endpoints || entry.config != config {
+            entry.retire();
+            *entry = Arc::new(EndpointPool::new(&upstream.server.endpoints, &config));
+        }
+        Arc::clone(entry.value())
+    }
+
+    /// Drop the pool of `upstream_id`, if any.
+    pub fn evict(&self, upstream_id: Uuid) {
+        if let Some((_, pool)) = self.pools.remove(&upstream_id) {
+            pool.retire();
+        }
+    }
+}
+
+impl ConfigChangeListener for LoadBalancer {
+    fn upstream_changed(&self, id: Uuid, _alias: &str) {
+        self.evict(id);
+    }
+
+    fn route_changed(&self, _route_id: Uuid) {}
+}
+
+impl Default for LoadBalancer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolve the `X-OAGW-Target-Host` header against the upstream's endpoints.
+///
+/// Returns the index of the pinned endpoint, or `None` when the request should
+/// be load balanced.
+///
+/// # Errors
+///
+/// - `InvalidTargetHost` if the header is not a bare hostname or IP address.
+/// - `UnknownTargetHost` if it names no configured endpoint.
+/// - `MissingTargetHost` if it is absent and the alias is the common suffix of
+///   several endpoint hosts, which makes the caller's intent ambiguous.
+pub fn resolve_target_host(
+    upstream: &Upstream,
+    target_host: Option<&str>,
+    instance: &str,
+) -> Result<Option<usize>, DomainError> {
+    let endpoints = &upstream.server.endpoints;
+    let Some(host) = target_host else {
+        if endpoints.len() > 1 && is_common_suffix_alias(&upstream.alias, endpoints) {
+            return Err(DomainError::MissingTargetHost {
+                instance: instance.to_string(),
+            });
+        }
+        return Ok(None);
+    };
+
+    if !is_valid_target_host(host) {
+        return Err(DomainError::InvalidTargetHost {
+            instance: instance.to_string(),
+        });
+    }
+
+    match endpoints
+        .iter()
+        .position(|e| e.host.eq_ignore_ascii_case(host))
+    {
+        Some(index) => Ok(Some(index)),
+        None => {
+            let valid: Vec<&str> = endpoints.iter().map(|e| e.host.as_str()).collect();
+            Err(DomainError::UnknownTargetHost {
+                detail: format!(
+                    "X-OAGW-Target-Host '{host}' does not match any configured endpoint. Valid hosts: [{}]",
+                    valid.join(", ")
+                ),
+                instance: instance.to_string(),
+            })
+        }
+    }
+}
+
+fn is_common_suffix_alias(alias: &str, endpoints: &[Endpoint]) -> bool {
+    let suffix = format!(".{}", alias.split(':').next().unwrap_or(alias));
+    endpoints
+        .iter()
+        .all(|e| e.host.to_ascii_lowercase().ends_with(&suffix))
+}
+
+/// Hostname or IPv4 address only: no port, path, query or other punctuation.
+fn is_valid_target_host(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && !host.starts_with(['.', '-'])
+        && !host.ends_with(['.', '-'])
+        && host
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'-')
+}
+
+/// Endpoints of one upstream together with their balancing and health state.
+#[domain_model]
+pub struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    config: LoadBalancingConfig,
+    state: Mutex<PoolState>,
+    probes_started: AtomicBool,
+    /// Set once the pool no longer serves its upstream.
+    retired: watch::Sender<bool>,
+}
+
+#[domain_model]
+struct PoolState {
+    /// Next round-robin position (also breaks least-in-flight ties).
+    cursor: usize,
+    endpoints: Vec<EndpointState>,
+}
+
+#[domain_model]
+#[derive(Default)]
+struct EndpointState {
+    in_flight: u32,
+    /// Smooth weighted round-robin accumulator.
+    current_weight: i64,
+    consecutive_failures: u32,
+    ejected_until: Option<Instant>,
+    /// Back-to-back ejections; scales the next ejection length.
+    ejections: u32,
+    /// Set once active probes declared the endpoint unhealthy.
+    probe_unhealthy: bool,
+    probe_successes: u32,
+    probe_failures: u32,
+}
+
+impl EndpointPool {
+    fn new(endpoints: &[Endpoint], config: &LoadBalancingConfig) -> Self {
+        Self {
+            endpoints: endpoints.to_vec(),
+            config: config.clone(),
+            state: Mutex::new(PoolState {
+                cursor: 0,
+                endpoints: endpoints.iter().map(|_| EndpointState::default()).collect(),
+            }),
+            probes_started: AtomicBool::new(false),
+            retired: watch::channel(false).0,
+        }
+    }
+
+    /// Mark the pool as no longer in use. Leases already handed out keep
+    /// working; only the prober stops.
+    pub fn retire(&self) {
+        self.retired.send_replace(true);
+    }
+
+    /// Receiver that changes, or closes, once the pool is retired or dropped.
+    #[must_use]
+    pub fn retirement(&self) -> watch::Receiver<bool> {
+        self.retired.subscribe()
+    }
+
+    #[must_use]
+    pub fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints
+    }
+
+    #[must_use]
+    pub fn health_check(&self) -> Option<&HealthCheckConfig> {
+        self.config.health_check.as_ref()
+    }
+
+    /// Returns `true` exactly once per pool if active probes are configured,
+    /// so that only one prober runs per pool.
+    pub fn claim_probes(&self) -> bool {
+        self.config.health_check.is_some() && !self.probes_started.swap(true, Ordering::AcqRel)
+    }
+
+    /// Pick an endpoint with the configured strategy, skipping endpoints that
+    /// are ejected or failing health checks. If none are available, all
+    /// endpoints are considered so the upstream never becomes unreachable
+    /// purely through local bookkeeping.
+    ///
+    /// Returns `None` if the upstream has no endpoints.
+    #[must_use]
+    pub fn select(self: &Arc<Self>) -> Option<EndpointLease> {
+        self.select_at(Instant::now())
+    }
+
+    fn select_at(self: &Arc<Self>, now: Instant) -> Option<EndpointLease> {
+        let mut state = lock(&self.state);
+        let n = state.endpoints.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut available: Vec<usize> = (0..n)
+            .filter(|&i| state.endpoints[i].is_available(now))
+            .collect();
+        if available.is_empty() {
+            available = (0..n).collect();
+        }
+
+        let index = match self.config.strategy {
+            LoadBalancingStrategy::RoundRobin => {
+                let cursor = state.cursor;
+                (0..n)
+                    .map(|k| (cursor + k) % n)
+                    .find(|i| available.contains(i))
+                    .unwrap_or(available[0])
+            }
+            LoadBalancingStrategy::Weighted => {
+                let total: i64 = available
+                    .iter()
+                    .map(|&i| i64::from(self.endpoints[i].weight))
+                    .sum();
+                for &i in &available {
+                    state.endpoints[i].current_weight += i64::from(self.endpoints[i].weight);
+                }
+                let best = available
+                    .iter()
+                    .copied()
+                    .reduce(|best, i| {
+                        if state.endpoints[i].current_weight > state.endpoints[best].current_weight
+                        {
+                            i
+                        } else {
+                            best
+                        }
+                    })
+                    .unwrap_or(available[0]);
+                state.endpoints[best].current_weight -= total;
+                best
+            }
+            LoadBalancingStrategy::LeastInFlight => {
+                let cursor = state.cursor;
+                available
+                    .iter()
+                    .copied()
+                    .min_by_key(|&i| (state.endpoints[i].in_flight, (i + n - cursor) % n))
+                    .unwrap_or(available[0])
+            }
+        };
+
+        state.cursor = (index + 1) % n;
+        state.endpoints[index].in_flight += 1;
+        drop(state);
+
+        Some(EndpointLease {
+            pool: Arc::clone(self),
+            index,
+        })
+    }
+
+    /// Feed one active probe result for endpoint `index`.
+    pub fn record_probe(&self, index: usize, healthy: bool) {
+        let Some(hc) = self.config.health_check.as_ref() else {
+            return;
+        };
+        let mut state = lock(&self.state);
+        let Some(ep) = state.endpoints.get_mut(index) else {
+            return;
+        };
+        if healthy {
+            ep.probe_failures = 0;
+            ep.probe_successes += 1;
+            if ep.probe_unhealthy && ep.probe_successes >= hc.healthy_threshold {
+                ep.probe_unhealthy = false;
+                tracing::info!(endpoint = %self.endpoints[index].host, "endpoint passed health checks");
+            }
+        } else {
+            ep.probe_successes = 0;
+            ep.probe_failures += 1;
+            if !ep.probe_unhealthy && ep.probe_failures >= hc.unhealthy_threshold {
+                ep.probe_unhealthy = true;
+                tracing::warn!(endpoint = %self.endpoints[index].host, "endpoint failed health checks");
+            }
+        }
+    }
+
+    fn record(&self, index: usize, failed: bool, now: Instant) {
+        let mut state = lock(&self.state);
+        let n = state.endpoints.len();
+        let ejected = state
+            .endpoints
+            .iter()
+            .filter(|e| e.ejected_until.is_some_and(|t| t > now))
+            .count();
+        let ep = &mut state.endpoints[index];
+        if !failed {
+            ep.consecutive_failures = 0;
+            ep.ejections = 0;
+            return;
+        }
+        let Some(od) = self.config.outlier_detection.as_ref() else {
+            return;
+        };
+        ep.consecutive_failures += 1;
+        if ep.consecutive_failures < od.consecutive_failures
+            || ep.ejected_until.is_some_and(|t| t > now)
+            || ejected * 100 >= n * usize::from(od.max_ejection_percent)
+        {
+            return;
+        }
+        ep.ejections += 1;
+        ep.consecutive_failures = 0;
+        let duration = (Duration::from_secs(u64::from(od.base_ejection_seconds)) * ep.ejections)
+            .min(MAX_EJECTION);
+        ep.ejected_until = Some(now + duration);
+        tracing::warn!(
+            endpoint = %self.endpoints[index].host,
+            seconds = duration.as_secs(),
+            "ejecting endpoint after consecutive failures"
+        );
+    }
+
+    fn release(&self, index: usize) {
+        let mut state = lock(&self.state);
+        let ep = &mut state.endpoints[index];
+        ep.in_flight = ep.in_flight.saturating_sub(1);
+    }
+}
+
+impl EndpointState {
+    fn is_available(&self, now: Instant) -> bool {
+        !self.probe_unhealthy && self.ejected_until.is_none_or(|t| t <= now)
+    }
+}
+
+fn lock(state: &Mutex<PoolState>) -> MutexGuard<'_, PoolState> {
+    state
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// An endpoint handed out by [`EndpointPool::select`]. Counts as in flight
+/// until dropped.
+#[domain_model]
+pub struct EndpointLease {
+    pool: Arc<EndpointPool>,
+    index: usize,
+}
+
+impl EndpointLease {
+    #[must_use]
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.pool.endpoints[self.index]
+    }
+
+    pub fn success(&self) {
+        self.pool.record(self.index, false, Instant::now());
+    }
+
+    /// Count a 5xx response or connect error towards outlier ejection.
+    pub fn failure(&self) {
+        self.pool.record(self.index, true, Instant::now());
+    }
+}
+
+impl Drop for EndpointLease {
+    fn drop(&mut self) {
+        self.pool.release(self.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{OutlierDetectionConfig, Scheme, Server};
+
+    fn endpoint(host: &str, weight: u32) -> Endpoint {
+        Endpoint {
+            scheme: Scheme::Https,
+            host: host.into(),
+            port: 443,
+            weight,
+        }
+    }
+
+    fn upstream(alias: &str, endpoints: Vec<Endpoint>, config: LoadBalancingConfig) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            alias: alias.into(),
+            server: Server { endpoints },
+            protocol: "http".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: Some(config),
+            tags: vec![],
+        }
+    }
+
+    fn strategy(strategy: LoadBalancingStrategy) -> LoadBalancingConfig {
+        LoadBalancingConfig {
+            strategy,
+            ..LoadBalancingConfig::default()
+        }
+    }
+
+    fn pick(pool: &Arc<EndpointPool>, now: Instant) -> String {
+        pool.select_at(now).unwrap().endpoint().host.clone()
+    }
+
+    #[test]
+    fn round_robin_cycles_through_endpoints() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            "svc",
+            vec![endpoint("a", 1), endpoint("b", 1), endpoint("c", 1)],
+            strategy(LoadBalancingStrategy::RoundRobin),
+        );
+        let pool = lb.pool(&u);
+        let now = Instant::now();
+        let picks: Vec<String> = (0..6).map(|_| pick(&pool, now)).collect();
+        assert_eq!(picks, ["a", "b", "c", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn weighted_follows_weights_smoothly() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            "svc",
+            vec![endpoint("a", 3), endpoint("b", 1)],
+            strategy(LoadBalancingStrategy::Weighted),
+        );
+        let pool = lb.pool(&u);
+        let now = Instant::now();
+        let picks: Vec<String> = (0..8).map(|_| pick(&pool, now)).collect();
+        assert_eq!(picks.iter().filter(|h| *h == "a").count(), 6);
+        assert_eq!(picks[..4], ["a", "a", "b", "a"]);
+    }
+
+    #[test]
+    fn least_in_flight_prefers_idle_endpoint() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            "svc",
+            vec![endpoint("a", 1), endpoint("b", 1)],
+            strategy(LoadBalancingStrategy::LeastInFlight),
+        );
+        let pool = lb.pool(&u);
+        let now = Instant::now();
+        let held = pool.select_at(now).unwrap();
+        assert_eq!(held.endpoint().host, "a");
+        assert_eq!(pick(&pool, now), "b");
+        assert_eq!(pick(&pool, now), "b");
+        drop(held);
+        assert_eq!(pick(&pool, now), "a");
+    }
+
+    #[test]
+    fn consecutive_failures_eject_endpoint_until_timeout() {
+        let lb = LoadBalancer::new();
+        let config = LoadBalancingConfig {
+            outlier_detection: Some(OutlierDetectionConfig {
+                consecutive_failures: 2,
+                base_ejection_seconds: 10,
+                max_ejection_percent: 50,
+            }),
+            ..LoadBalancingConfig::default()
+        };
+        let u = upstream("svc", vec![endpoint("a", 1), endpoint("b", 1)], config);
+        let pool = lb.pool(&u);
+        let now = Instant::now();
+        pool.record(0, true, now);
+        pool.record(0, true, now);
+
+        assert!((0..4).all(|_| pick(&pool, now) == "b"));
+        // Only half the pool may be ejected at once.
+        pool.record(1, true, now);
+        pool.record(1, true, now);
+        assert_eq!(pick(&pool, now), "b");
+
+        let later = now + Duration::from_secs(11);
+        let picks: Vec<String> = (0..2).map(|_| pick(&pool, later)).collect();
+        assert!(picks.contains(&"a".to_string()));
+    }
+
+    #[test]
+    fn success_resets_failure_streak() {
+        let lb = LoadBalancer::new();
+        let u = upstream(
+            "svc",
+            vec![endpoint("a", 1), endpoint("b", 1)],
+            LoadBalancingConfig::default(),
+        );
+        let pool = lb.pool(&u);
+        let now = Instant::now();
+        for _ in 0..4 {
+            pool.record(0, true, now);
+        }
+        pool.record(0, false, now);
+        for _ in 0..4 {
+            pool.record(0, true, now);
+        }
+        let picks: Vec<String> = (0..2).map(|_| pick(&pool, now)).collect();
+        assert!(picks.contains(&"a".to_string()));
+    }
+
+    #[test]
+    fn failed_probes_take_endpoint_out_of_rotation() {
+        let lb = LoadBalancer::new();
+        let config = LoadBalancingConfig {
+            health_check: Some(HealthCheckConfig {
+                healthy_threshold: 1,
+                unhealthy_threshold: 2,
+                ..HealthCheckConfig::default()
+            }),
+            ..LoadBalancingConfig::default()
+        };
+        let u = upstream("svc", vec![endpoint("a", 1), endpoint("b", 1)], config);
+        let pool = lb.pool(&u);
+        assert!(pool.claim_probes());
+        assert!(!pool.claim_probes());
+
+        let now = Instant::now();
+        pool.record_probe(0, false);
+        pool.record_probe(0, false);
+        assert!((0..3).all(|_| pick(&pool, now) == "b"));
+
+        pool.record_probe(0, true);
+        let picks: Vec<String> = (0..2).map(|_| pick(&pool, now)).collect();
+        assert!(picks.contains(&"a".to_string()));
+    }
+
+    #[test]
+    fn all_unavailable_falls_back_to_every_endpoint() {
+        let lb = LoadBalancer::new();
+        let config = LoadBalancingConfig {
+            health_check: Some(HealthCheckConfig {
+                unhealthy_threshold: 1,
+                ..HealthCheckConfig::default()
+            }),
+            ..LoadBalancingConfig::default()
+        };
+        let u = upstream("svc", vec![endpoint("a", 1)], config);
+        let pool = lb.pool(&u);
+        pool.record_probe(0, false);
+        assert_eq!(pick(&pool, Instant::now()), "a");
+    }
+
+    #[test]
+    fn pool_is_rebuilt_when_endpoints_change() {
+        let lb = LoadBalancer::new();
+        let mut u = upstream(
+            "svc",
+            vec![endpoint("a", 1)],
+            LoadBalancingConfig::default(),
+        );
+        let first = lb.pool(&u);
+        assert!(Arc::ptr_eq(&first, &lb.pool(&u)));
+        u.server.endpoints.push(endpoint("b", 1));
+        let second = lb.pool(&u);
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(second.endpoints().len(), 2);
+    }
+
+    #[test]
+    fn target_host_pins_endpoint_case_insensitively() {
+        let u = upstream(
+            "vendor.com",
+            vec![endpoint("us.vendor.com", 1), endpoint("eu.vendor.com", 1)],
+            LoadBalancingConfig::default(),
+        );
+        assert_eq!(
+            resolve_target_host(&u, Some("EU.Vendor.com"), "/p").unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn common_suffix_alias_requires_target_host() {
+        let u = upstream(
+            "vendor.com",
+            vec![endpoint("us.vendor.com", 1), endpoint("eu.vendor.com", 1)],
+            LoadBalancingConfig::default(),
+        );
+        let err = resolve_target_host(&u, None, "/p").unwrap_err();
+        assert!(matches!(err, DomainError::MissingTargetHost { .. }));
+
+        let explicit = upstream(
+            "my-service",
+            vec![endpoint("a.example.com", 1), endpoint("b.example.com", 1)],
+            LoadBalancingConfig::default(),
+        );
+        assert_eq!(resolve_target_host(&explicit, None, "/p").unwrap(), None);
+    }
+
+    #[test]
+    fn target_host_rejects_bad_format_and_unknown_hosts() {
+        let u = upstream(
+            "vendor.com",
+            vec![endpoint("us.vendor.com", 1), endpoint("eu.vendor.com", 1)],
+            LoadBalancingConfig::default(),
+        );
+        for bad in [
+            "us.vendor.com:443",
+            "us.vendor.com/path",
+            "us.vendor.com?x=1",
+            "",
+        ] {
+            let err = resolve_target_host(&u, Some(bad), "/p").unwrap_err();
+            assert!(
+                matches!(err, DomainError::InvalidTargetHost { .. }),
+                "{bad}"
+            );
+        }
+        let err = resolve_target_host(&u, Some("10.0.1.1"), "/p").unwrap_err();
+        match err {
+            DomainError::UnknownTargetHost { detail, .. } => {
+                assert!(
+                    detail.contains("[us.vendor.com, eu.vendor.com]"),
+                    "{detail}"
+                );
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replaced_and_evicted_pools_are_retired() {
+        let lb = LoadBalancer::new();
+        let mut u = upstream(
+            "svc",
+            vec![endpoint("a", 1)],
+            LoadBalancingConfig::default(),
+        );
+        let first = lb.pool(&u);
+        let first_retirement = first.retirement();
+        assert!(Arc::ptr_eq(&first, &lb.pool(&u)));
+        assert!(!*first_retirement.borrow());
+
+        u.server.endpoints.push(endpoint("b", 1));
+        let second = lb.pool(&u);
+        assert!(*first_retirement.borrow());
+        let second_retirement = second.retirement();
+
+        lb.upstream_changed(u.id, &u.alias);
+        assert!(*second_retirement.borrow());
+        assert!(!Arc::ptr_eq(&second, &lb.pool(&u)));
+    }
+}
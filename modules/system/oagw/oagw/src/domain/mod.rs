@@ -1,6 +1,11 @@
+pub(crate) mod builtin_guards;
+pub(crate) mod circuit_breaker;
 pub(crate) mod credential;
 pub(crate) mod error;
+pub(crate) mod grpc_transcoding;
 pub(crate) mod gts_helpers;
+pub(crate) mod hierarchy;
+pub(crate) mod load_balancer;
 pub(crate) mod model;
 pub(crate) mod plugin;
 pub(crate) mod rate_limit;
@@ -8,6 +13,7 @@ pub(crate) mod repo;
 pub(crate) mod services;
 pub(crate) mod type_catalog;
 pub(crate) mod type_provisioning;
+pub(crate) mod usage;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub(crate) mod test_support;
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use modkit_macros::domain_model;
 use uuid::Uuid;
@@ -37,6 +38,8 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    /// Relative share of traffic under [`LoadBalancingStrategy::Weighted`].
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -120,6 +123,10 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
     pub cost: u32,
+    /// Wait-queue settings; only consulted when `strategy` is `Queue`.
+    pub queue: Option<QueueConfig>,
+    /// Fallback settings; only consulted when `strategy` is `Degrade`.
+    pub degrade: Option<DegradeConfig>,
 }
 
 #[domain_model]
@@ -173,6 +180,220 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+/// Bounded wait queue for the `Queue` strategy.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// Maximum number of requests waiting per rate-limit key.
+    pub max_depth: u32,
+    /// Maximum time a request may wait for capacity.
+    pub timeout: Duration,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 500,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Fallback behaviour for the `Degrade` strategy.
+///
+/// With neither field set the request is still forwarded to the original
+/// upstream, carrying a degradation marker header.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DegradeConfig {
+    /// Upstream to forward over-limit requests to instead.
+    pub fallback_upstream_id: Option<Uuid>,
+    /// Static response returned instead of forwarding.
+    pub fallback_response: Option<FallbackResponse>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Failure percentage (1-100) within `window_seconds` that opens the circuit.
+    pub failure_rate_threshold: Option<u8>,
+    /// Calls required within the window before the failure rate is evaluated.
+    pub minimum_requests: u32,
+    pub window_seconds: u32,
+    /// Consecutive successful probes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Cool-down before an open circuit lets probes through.
+    pub timeout_seconds: u32,
+    /// Concurrent probes allowed while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            failure_rate_threshold: None,
+            minimum_requests: 10,
+            window_seconds: 60,
+            success_threshold: 3,
+            timeout_seconds: 30,
+            half_open_max_requests: 3,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One circuit for the whole upstream.
+    Global,
+    /// One circuit per upstream endpoint.
+    #[default]
+    PerEndpoint,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Point-in-time view of one circuit, as reported by the management API.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerStatus {
+    /// `host:port` for per-endpoint circuits, `None` for a global circuit.
+    pub endpoint: Option<String>,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub window_requests: u32,
+    pub window_failures: u32,
+    /// Seconds until an open circuit starts probing.
+    pub retry_after_secs: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// LoadBalancingConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadBalancingConfig {
+    pub strategy: LoadBalancingStrategy,
+    /// Passive ejection of endpoints that keep failing. `None` disables it.
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+    /// Active HTTP probes. `None` disables them.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: LoadBalancingStrategy::default(),
+            outlier_detection: Some(OutlierDetectionConfig::default()),
+            health_check: None,
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    /// Smooth weighted round-robin over [`Endpoint::weight`].
+    Weighted,
+    /// Endpoint with the fewest requests currently in flight.
+    LeastInFlight,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    /// Consecutive 5xx responses or connect errors that eject an endpoint.
+    pub consecutive_failures: u32,
+    /// First ejection length; repeated ejections of the same endpoint last longer.
+    pub base_ejection_seconds: u32,
+    /// Upper bound (0-100) on the share of endpoints ejected at once.
+    pub max_ejection_percent: u8,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: 5,
+            base_ejection_seconds: 30,
+            max_ejection_percent: 50,
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// Request path probed on every endpoint, e.g. `/health`.
+    pub path: String,
+    pub interval_seconds: u32,
+    pub timeout_seconds: u32,
+    /// Consecutive successful probes that bring an endpoint back.
+    pub healthy_threshold: u32,
+    /// Consecutive failed probes that take an endpoint out of rotation.
+    pub unhealthy_threshold: u32,
+    pub expected_statuses: Vec<u16>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            path: "/health".into(),
+            interval_seconds: 10,
+            timeout_seconds: 2,
+            healthy_threshold: 2,
+            unhealthy_threshold: 3,
+            expected_statuses: vec![200],
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -182,6 +403,8 @@ pub enum RateLimitStrategy {
 pub struct PluginsConfig {
     pub sharing: SharingMode,
     pub items: Vec<String>,
+    /// Instance config per plugin id, exposed to Starlark plugins as `ctx.config`.
+    pub config: HashMap<String, serde_json::Value>,
 }
 
 // ---------------------------------------------------------------------------
@@ -222,6 +445,45 @@ pub struct GrpcMatch {
     pub method: String,
 }
 
+impl GrpcMatch {
+    /// HTTP/2 request path of the matched call: `/{service}/{method}`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.service, self.method)
+    }
+}
+
+/// Descriptors used to transcode JSON requests on a gRPC route.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrpcTranscodingConfig {
+    /// Serialized `google.protobuf.FileDescriptorSet`.
+    pub descriptor_set: Vec<u8>,
+}
+
+/// Opt-in caching of upstream responses to `GET` requests on a route.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCacheConfig {
+    /// Freshness lifetime of a response that carries no `max-age` or
+    /// `s-maxage` directive.
+    pub ttl: Duration,
+    /// Request headers (lowercase) whose values select distinct entries,
+    /// e.g. `accept` or `accept-language`.
+    pub vary: Vec<String>,
+}
+
+/// JSON pointers to the token counts an upstream reports in its responses,
+/// recorded with the route's usage. They apply to JSON bodies and to the
+/// `data` of each server-sent event; in a stream the last reported value
+/// wins, as providers report running totals.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UsageExtractionConfig {
+    pub input_tokens: Option<String>,
+    pub output_tokens: Option<String>,
+}
+
 #[domain_model]
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchRules {
@@ -242,6 +504,9 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    pub response_cache: Option<ResponseCacheConfig>,
+    pub usage_extraction: Option<UsageExtractionConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -260,9 +525,72 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
     pub tags: Vec<String>,
 }
 
+// ---------------------------------------------------------------------------
+// Custom plugins
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginType {
+    Auth,
+    Guard,
+    Transform,
+}
+
+impl PluginType {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::Guard => "guard",
+            Self::Transform => "transform",
+        }
+    }
+}
+
+// Variants mirror the hook names (`on_request`, `on_response`, `on_error`).
+#[allow(clippy::enum_variant_names)]
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginPhase {
+    OnRequest,
+    OnResponse,
+    OnError,
+}
+
+impl PluginPhase {
+    pub const ALL: [Self; 3] = [Self::OnRequest, Self::OnResponse, Self::OnError];
+
+    /// Name of the Starlark function implementing the phase.
+    #[must_use]
+    pub fn hook_name(self) -> &'static str {
+        match self {
+            Self::OnRequest => "on_request",
+            Self::OnResponse => "on_response",
+            Self::OnError => "on_error",
+        }
+    }
+}
+
+/// Tenant-defined Starlark guard or transform plugin. Immutable once created.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomPlugin {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub plugin_type: PluginType,
+    pub phases: Vec<PluginPhase>,
+    pub config_schema: serde_json::Value,
+    pub source_code: String,
+}
+
 // ---------------------------------------------------------------------------
 // Pagination
 // ---------------------------------------------------------------------------
@@ -294,6 +622,8 @@ pub struct CreateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -308,6 +638,8 @@ pub struct UpdateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub load_balancing: Option<LoadBalancingConfig>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
 }
@@ -319,6 +651,9 @@ pub struct CreateRouteRequest {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    pub response_cache: Option<ResponseCacheConfig>,
+    pub usage_extraction: Option<UsageExtractionConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -330,7 +665,23 @@ pub struct UpdateRouteRequest {
     pub match_rules: Option<MatchRules>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub grpc_transcoding: Option<GrpcTranscodingConfig>,
+    pub response_cache: Option<ResponseCacheConfig>,
+    pub usage_extraction: Option<UsageExtractionConfig>,
     pub tags: Option<Vec<String>>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
 }
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatePluginRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub plugin_type: PluginType,
+    /// Phases the plugin runs in; inferred from the hooks the source defines
+    /// when empty.
+    pub phases: Vec<PluginPhase>,
+    pub config_schema: serde_json::Value,
+    pub source_code: String,
+}
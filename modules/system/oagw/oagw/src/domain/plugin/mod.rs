@@ -1,6 +1,12 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
+use bytes::Bytes;
 use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::domain::model::{CustomPlugin, PluginPhase};
 
 // ---------------------------------------------------------------------------
 // Plugin errors
@@ -12,13 +18,16 @@ pub enum PluginError {
     #[error("secret not found: {0}")]
     SecretNotFound(String),
     #[error("authentication failed: {0}")]
-    #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     AuthFailed(String),
     #[error("request rejected: {0}")]
     #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     Rejected(String),
     #[error("plugin error: {0}")]
     Internal(String),
+    /// Custom plugin source failed to compile, raised an error or exceeded
+    /// its sandbox limits.
+    #[error("script error: {0}")]
+    Script(String),
 }
 
 // ---------------------------------------------------------------------------
@@ -26,7 +35,14 @@ pub enum PluginError {
 // ---------------------------------------------------------------------------
 
 #[domain_model]
+#[derive(Clone)]
 pub struct AuthContext {
+    /// Upstream the request is sent to.
+    pub upstream_id: Uuid,
+    /// Tenant making the request.
+    pub tenant_id: Uuid,
+    /// Caller the request is made for; secrets are resolved on its behalf.
+    pub security_context: SecurityContext,
     pub headers: HashMap<String, String>,
     pub config: HashMap<String, String>,
 }
@@ -34,4 +50,175 @@ pub struct AuthContext {
 #[async_trait::async_trait]
 pub trait AuthPlugin: Send + Sync {
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError>;
+
+    /// Called when the upstream answered `401 Unauthorized` to credentials
+    /// this plugin injected. Returns `true` if the plugin discarded cached
+    /// credentials and the request should be re-authenticated and sent once
+    /// more.
+    async fn on_unauthorized(&self, _ctx: &AuthContext) -> bool {
+        false
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Custom (scripted) plugins
+// ---------------------------------------------------------------------------
+
+/// Header edit made by a plugin, replayed onto the outbound request or the
+/// client response once the chain has run.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderEdit {
+    Set(String, String),
+    Add(String, String),
+    Remove(String),
+}
+
+/// Header view exposed to plugins. Names are compared case-insensitively and
+/// stored lowercase.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginHeaders {
+    entries: Vec<(String, String)>,
+    edits: Vec<HeaderEdit>,
+}
+
+impl PluginHeaders {
+    #[must_use]
+    pub fn new(entries: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(k, v)| (k.to_ascii_lowercase(), v))
+                .collect(),
+            edits: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        let name = name.to_ascii_lowercase();
+        self.entries.retain(|(k, _)| *k != name);
+        self.entries.push((name.clone(), value.to_string()));
+        self.edits.push(HeaderEdit::Set(name, value.to_string()));
+    }
+
+    pub fn add(&mut self, name: &str, value: &str) {
+        let name = name.to_ascii_lowercase();
+        self.entries.push((name.clone(), value.to_string()));
+        self.edits.push(HeaderEdit::Add(name, value.to_string()));
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        let name = name.to_ascii_lowercase();
+        self.entries.retain(|(k, _)| *k != name);
+        self.edits.push(HeaderEdit::Remove(name));
+    }
+
+    /// Distinct header names, in first-seen order.
+    #[must_use]
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = Vec::new();
+        for (k, _) in &self.entries {
+            if !keys.contains(&k.as_str()) {
+                keys.push(k);
+            }
+        }
+        keys
+    }
+
+    /// Edits made since the view was created, in order.
+    #[must_use]
+    pub fn edits(&self) -> &[HeaderEdit] {
+        &self.edits
+    }
+}
+
+/// Request as seen by `on_request` hooks.
+///
+/// `headers` starts as the client's headers; credentials injected by the
+/// auth plugin are not visible to scripts.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct PluginRequest {
+    pub method: String,
+    /// Outbound path (route path plus suffix).
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: PluginHeaders,
+    pub body: Bytes,
+}
+
+/// Upstream response as seen by `on_response` hooks.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct PluginResponse {
+    pub status: u16,
+    pub headers: PluginHeaders,
+    pub body: Bytes,
+}
+
+/// Failed request as seen by `on_error` hooks.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct PluginFailure {
+    pub status: u16,
+    pub code: String,
+    pub message: String,
+    /// The failure came from the upstream rather than the gateway.
+    pub upstream: bool,
+}
+
+/// Everything a hook can read or change during one proxy call.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct PluginExchange {
+    pub tenant_id: Uuid,
+    pub route_id: Uuid,
+    /// When the gateway received the request, for `ctx.time.elapsed_ms()`.
+    pub started: Instant,
+    pub request: PluginRequest,
+    pub response: Option<PluginResponse>,
+    pub error: Option<PluginFailure>,
+}
+
+/// How a hook ended.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginOutcome {
+    /// Continue with the next plugin, then the upstream call.
+    Next,
+    /// Stop the chain and answer with a gateway error.
+    Reject {
+        status: u16,
+        code: String,
+        message: String,
+    },
+    /// Stop the chain and answer with this response.
+    Respond { status: u16, body: String },
+}
+
+/// Sandboxed interpreter for custom plugin source.
+#[async_trait::async_trait]
+pub trait PluginRuntime: Send + Sync {
+    /// Check that `source` loads, and return the phases it defines hooks for.
+    async fn compile(&self, source: &str) -> Result<Vec<PluginPhase>, PluginError>;
+
+    /// Run the `phase` hook of `plugin` against `exchange`. `config` is the
+    /// plugin instance config, exposed to the hook as `ctx.config`.
+    async fn run(
+        &self,
+        plugin: &CustomPlugin,
+        phase: PluginPhase,
+        config: &serde_json::Value,
+        exchange: &mut PluginExchange,
+    ) -> Result<PluginOutcome, PluginError>;
 }
@@ -1,14 +1,70 @@
-use std::collections::HashSet;
-use std::time::Instant;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::domain::error::DomainError;
-use crate::domain::model::{RateLimitConfig, Window};
+use crate::domain::model::{RateLimitAlgorithm, RateLimitConfig, RateLimitStrategy, Window};
+use crate::domain::services::ConfigChangeListener;
 use dashmap::DashMap;
 use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Lower bound on how long a queued request sleeps before re-checking capacity.
+const MIN_QUEUE_POLL: Duration = Duration::from_millis(10);
+
+/// Outcome of [`RateLimiter::acquire`] for requests that may proceed.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// Within the limit (possibly after waiting in the queue).
+    Allowed,
+    /// Over the limit under the `Degrade` strategy; the caller applies the
+    /// configured fallback.
+    Degraded,
+}
 
 #[domain_model]
 pub struct RateLimiter {
-    buckets: DashMap<String, TokenBucket>,
+    buckets: DashMap<String, Meter>,
+    queues: DashMap<String, Arc<WaitQueue>>,
+}
+
+/// Per-key limiter state for the configured algorithm.
+#[domain_model]
+enum Meter {
+    TokenBucket(TokenBucket),
+    SlidingWindow(SlidingWindow),
+}
+
+impl Meter {
+    fn new(config: &RateLimitConfig) -> Self {
+        match config.algorithm {
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket(TokenBucket::new(config)),
+            RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow(SlidingWindow::new(config)),
+        }
+    }
+
+    fn algorithm(&self) -> RateLimitAlgorithm {
+        match self {
+            Self::TokenBucket(_) => RateLimitAlgorithm::TokenBucket,
+            Self::SlidingWindow(_) => RateLimitAlgorithm::SlidingWindow,
+        }
+    }
+
+    fn try_consume(&mut self, cost: f64) -> bool {
+        match self {
+            Self::TokenBucket(b) => b.try_consume(cost),
+            Self::SlidingWindow(w) => w.try_consume(cost),
+        }
+    }
+
+    /// Time until `cost` could be consumed.
+    fn wait_time(&self, cost: f64) -> Duration {
+        match self {
+            Self::TokenBucket(b) => b.wait_time(cost),
+            Self::SlidingWindow(w) => w.wait_time(cost),
+        }
+    }
 }
 
 #[domain_model]
@@ -52,19 +108,112 @@ impl TokenBucket {
         }
     }
 
-    fn retry_after_secs(&self, cost: f64) -> u64 {
+    fn wait_time(&self, cost: f64) -> Duration {
         if self.refill_rate <= 0.0 {
-            return 60;
+            return Duration::from_secs(60);
         }
         let needed = cost - self.tokens;
         if needed <= 0.0 {
-            return 0;
+            return Duration::ZERO;
         }
-        (needed / self.refill_rate).ceil() as u64
+        Duration::from_secs_f64(needed / self.refill_rate)
     }
 }
 
-fn window_to_secs(window: &Window) -> f64 {
+/// Sliding-window counter.
+///
+/// Keeps the consumed cost of the current and previous fixed windows and
+/// weights the previous one by how much of it still overlaps the sliding
+/// window. Unlike a fixed window this prevents a 2x burst straddling a window
+/// boundary, while using constant memory per key (unlike a request log).
+#[domain_model]
+struct SlidingWindow {
+    limit: f64,
+    window_secs: f64,
+    origin: Instant,
+    current_index: u64,
+    current: f64,
+    previous: f64,
+}
+
+impl SlidingWindow {
+    fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            limit: config.sustained.rate as f64,
+            window_secs: window_to_secs(&config.sustained.window),
+            origin: Instant::now(),
+            current_index: 0,
+            current: 0.0,
+            previous: 0.0,
+        }
+    }
+
+    /// Returns the index of the fixed window containing `now` and how far
+    /// into it we are, as a fraction in `[0, 1)`.
+    fn position(&self, now: Instant) -> (u64, f64) {
+        let elapsed = now.duration_since(self.origin).as_secs_f64() / self.window_secs;
+        (elapsed.floor() as u64, elapsed.fract())
+    }
+
+    fn rotate(&mut self, index: u64) {
+        if index == self.current_index {
+            return;
+        }
+        self.previous = if index == self.current_index + 1 {
+            self.current
+        } else {
+            0.0
+        };
+        self.current = 0.0;
+        self.current_index = index;
+    }
+
+    fn try_consume(&mut self, cost: f64) -> bool {
+        let (index, fraction) = self.position(Instant::now());
+        self.rotate(index);
+        let estimate = self.previous * (1.0 - fraction) + self.current;
+        if estimate + cost <= self.limit {
+            self.current += cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn wait_time(&self, cost: f64) -> Duration {
+        if cost > self.limit {
+            return Duration::from_secs_f64(self.window_secs);
+        }
+        let (index, fraction) = self.position(Instant::now());
+        let (previous, current) = match index.saturating_sub(self.current_index) {
+            0 => (self.previous, self.current),
+            1 => (self.current, 0.0),
+            _ => (0.0, 0.0),
+        };
+
+        // Fraction of a window the previous count must decay by before
+        // `cost` fits next to `current`.
+        let needed = |previous: f64, current: f64| -> Option<f64> {
+            let headroom = self.limit - current - cost;
+            if headroom < 0.0 {
+                None
+            } else if previous <= headroom {
+                Some(0.0)
+            } else {
+                Some(1.0 - headroom / previous)
+            }
+        };
+
+        let windows = match needed(previous, current) {
+            Some(target) => (target - fraction).max(0.0),
+            // Not enough room until the current window becomes the previous one.
+            None => (1.0 - fraction) + needed(current, 0.0).unwrap_or(1.0),
+        };
+        Duration::from_secs_f64(windows * self.window_secs)
+    }
+}
+
+pub(crate) fn window_to_secs(window: &Window) -> f64 {
     match window {
         Window::Second => 1.0,
         Window::Minute => 60.0,
@@ -73,21 +222,86 @@ fn window_to_secs(window: &Window) -> f64 {
     }
 }
 
+/// Requests waiting for capacity on a single key under the `Queue` strategy.
+#[domain_model]
+#[derive(Default)]
+struct WaitQueue {
+    depth: AtomicU32,
+    /// Waiters take turns through this lock; tokio's mutex is fair, so
+    /// capacity is handed out in arrival order.
+    turn: tokio::sync::Mutex<()>,
+}
+
+impl WaitQueue {
+    fn reserve(&self, max_depth: u32) -> Option<QueueSlot<'_>> {
+        self.depth
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |d| {
+                (d < max_depth).then_some(d + 1)
+            })
+            .ok()
+            .map(|_| QueueSlot(&self.depth))
+    }
+}
+
+/// Occupies one place in a [`WaitQueue`] until dropped.
+struct QueueSlot<'a>(&'a AtomicU32);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn retry_after_secs(wait: Duration) -> u64 {
+    wait.as_secs_f64().ceil() as u64
+}
+
 impl RateLimiter {
     #[must_use]
     pub fn new() -> Self {
         Self {
             buckets: DashMap::new(),
+            queues: DashMap::new(),
         }
     }
 
-    /// Remove all entries whose keys are not in `active_keys`.
-    ///
-    // TODO: Wire into management service delete operations (upstream/route)
-    // so that stale rate-limit buckets are purged when entities are removed.
-    #[allow(dead_code)]
-    pub fn purge_keys(&self, active_keys: &HashSet<String>) {
-        self.buckets.retain(|k, _| active_keys.contains(k));
+    /// Key under which the limit of upstream `id` is counted.
+    #[must_use]
+    pub fn upstream_key(id: Uuid) -> String {
+        format!("upstream:{id}")
+    }
+
+    /// Key under which the limit of route `id` is counted.
+    #[must_use]
+    pub fn route_key(id: Uuid) -> String {
+        format!("route:{id}")
+    }
+
+    /// Remove the entries of `keys`. Requests already queued on one keep
+    /// waiting on the removed queue; new requests start from a full meter.
+    pub fn purge_keys<'a>(&self, keys: impl IntoIterator<Item = &'a str>) {
+        for key in keys {
+            self.buckets.remove(key);
+            self.queues.remove(key);
+        }
+    }
+
+    /// Consume `config.cost` for `key`, or return how long until it fits.
+    fn check(&self, key: &str, config: &RateLimitConfig) -> Result<(), Duration> {
+        let cost = config.cost as f64;
+        let mut meter = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| Meter::new(config));
+        if meter.algorithm() != config.algorithm {
+            *meter = Meter::new(config);
+        }
+
+        if meter.try_consume(cost) {
+            Ok(())
+        } else {
+            Err(meter.wait_time(cost))
+        }
     }
 
     /// Try to consume tokens for the given key.
@@ -100,30 +314,117 @@ impl RateLimiter {
         config: &RateLimitConfig,
         instance_uri: &str,
     ) -> Result<(), DomainError> {
-        let cost = config.cost as f64;
-        let mut bucket = self
-            .buckets
-            .entry(key.to_string())
-            .or_insert_with(|| TokenBucket::new(config));
-
-        if bucket.try_consume(cost) {
-            Ok(())
-        } else {
-            let retry_after = bucket.retry_after_secs(cost);
-            Err(DomainError::RateLimitExceeded {
+        self.check(key, config)
+            .map_err(|wait| DomainError::RateLimitExceeded {
                 detail: format!("rate limit exceeded for key: {key}"),
                 instance: instance_uri.to_string(),
-                retry_after_secs: Some(retry_after),
+                retry_after_secs: Some(retry_after_secs(wait)),
             })
+    }
+
+    /// Apply the limit for `key` according to `config.strategy`.
+    ///
+    /// - `Reject` fails immediately when the limit is exceeded.
+    /// - `Queue` waits for capacity in a bounded per-key queue.
+    /// - `Degrade` lets the request through as [`RateLimitDecision::Degraded`].
+    ///
+    /// # Errors
+    /// Returns `DomainError::RateLimitExceeded` for `Reject`, and
+    /// `DomainError::QueueFull` / `DomainError::QueueTimeout` for `Queue`.
+    pub async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<RateLimitDecision, DomainError> {
+        match config.strategy {
+            RateLimitStrategy::Reject => self
+                .try_consume(key, config, instance_uri)
+                .map(|()| RateLimitDecision::Allowed),
+            RateLimitStrategy::Degrade => Ok(match self.check(key, config) {
+                Ok(()) => RateLimitDecision::Allowed,
+                Err(_) => RateLimitDecision::Degraded,
+            }),
+            RateLimitStrategy::Queue => self
+                .enqueue(key, config, instance_uri)
+                .await
+                .map(|()| RateLimitDecision::Allowed),
         }
     }
+
+    async fn enqueue(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<(), DomainError> {
+        let queue = Arc::clone(&self.queues.entry(key.to_string()).or_default());
+
+        // Only bypass the queue when nobody is already waiting, so that
+        // newcomers cannot overtake queued requests.
+        let first_wait = if queue.depth.load(Ordering::Acquire) == 0 {
+            match self.check(key, config) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            }
+        } else {
+            MIN_QUEUE_POLL
+        };
+
+        let settings = config.queue.clone().unwrap_or_default();
+        let Some(_slot) = queue.reserve(settings.max_depth) else {
+            return Err(DomainError::QueueFull {
+                detail: format!(
+                    "request queue full ({max}/{max}) for key: {key}",
+                    max = settings.max_depth
+                ),
+                instance: instance_uri.to_string(),
+                retry_after_secs: Some(retry_after_secs(first_wait).max(1)),
+            });
+        };
+
+        let wait_for_capacity = async {
+            let _turn = queue.turn.lock().await;
+            loop {
+                match self.check(key, config) {
+                    Ok(()) => return,
+                    Err(wait) => tokio::time::sleep(wait.max(MIN_QUEUE_POLL)).await,
+                }
+            }
+        };
+
+        tokio::time::timeout(settings.timeout, wait_for_capacity)
+            .await
+            .map_err(|_| {
+                let wait = self
+                    .buckets
+                    .get(key)
+                    .map_or(Duration::ZERO, |m| m.wait_time(config.cost as f64));
+                DomainError::QueueTimeout {
+                    detail: format!(
+                        "request queued for {:?}, no capacity available",
+                        settings.timeout
+                    ),
+                    instance: instance_uri.to_string(),
+                    retry_after_secs: Some(retry_after_secs(wait).max(1)),
+                }
+            })
+    }
+}
+
+impl ConfigChangeListener for RateLimiter {
+    fn upstream_changed(&self, id: Uuid, _alias: &str) {
+        self.purge_keys([Self::upstream_key(id).as_str()]);
+    }
+
+    fn route_changed(&self, route_id: Uuid) {
+        self.purge_keys([Self::route_key(route_id).as_str()]);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{
-        BurstConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, SustainedRate,
-    };
+    use crate::domain::model::{BurstConfig, QueueConfig, RateLimitScope, SustainedRate};
 
     use super::*;
 
@@ -136,6 +437,20 @@ mod tests {
             scope: RateLimitScope::Tenant,
             strategy: RateLimitStrategy::Reject,
             cost: 1,
+            queue: None,
+            degrade: None,
+        }
+    }
+
+    fn make_queue_config(rate: u32, window: Window, timeout: Duration) -> RateLimitConfig {
+        RateLimitConfig {
+            strategy: RateLimitStrategy::Queue,
+            burst: Some(BurstConfig { capacity: 1 }),
+            queue: Some(QueueConfig {
+                max_depth: 1,
+                timeout,
+            }),
+            ..make_config(rate, window, None)
         }
     }
 
@@ -195,6 +510,119 @@ mod tests {
         assert!(limiter.try_consume("key-b", &config, "/test").is_err());
     }
 
+    #[test]
+    fn sliding_window_denies_when_exhausted() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(3, Window::Minute, None)
+        };
+        for _ in 0..3 {
+            assert!(limiter.try_consume("test", &config, "/test").is_ok());
+        }
+        match limiter.try_consume("test", &config, "/test") {
+            Err(DomainError::RateLimitExceeded {
+                retry_after_secs, ..
+            }) => {
+                // The full window only frees up once the next one is a third
+                // over, when the weighted count drops to 2: 80s from now.
+                let retry_after = retry_after_secs.unwrap();
+                assert!(retry_after > 60 && retry_after <= 80, "{retry_after}");
+            }
+            other => panic!("expected RateLimitExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sliding_window_prevents_boundary_burst() {
+        let config = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(10, Window::Second, None)
+        };
+        let mut window = SlidingWindow::new(&config);
+
+        // Exhaust the limit near the end of the first window.
+        window.origin = Instant::now() - Duration::from_millis(900);
+        for _ in 0..10 {
+            assert!(window.try_consume(1.0));
+        }
+
+        // Jump just past the boundary: a fixed window would allow 10 more.
+        window.origin -= Duration::from_millis(200);
+        let accepted = (0..10).filter(|_| window.try_consume(1.0)).count();
+        assert!(accepted >= 1);
+        assert!(accepted < 10, "boundary burst allowed {accepted} requests");
+    }
+
+    #[test]
+    fn algorithm_change_resets_state() {
+        let limiter = RateLimiter::new();
+        let bucket = make_config(1, Window::Minute, None);
+        assert!(limiter.try_consume("test", &bucket, "/test").is_ok());
+        assert!(limiter.try_consume("test", &bucket, "/test").is_err());
+
+        let sliding = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..bucket
+        };
+        assert!(limiter.try_consume("test", &sliding, "/test").is_ok());
+    }
+
+    #[tokio::test]
+    async fn degrade_lets_excess_through_as_degraded() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            strategy: RateLimitStrategy::Degrade,
+            ..make_config(1, Window::Minute, None)
+        };
+        let first = limiter.acquire("test", &config, "/test").await.unwrap();
+        assert_eq!(first, RateLimitDecision::Allowed);
+        let second = limiter.acquire("test", &config, "/test").await.unwrap();
+        assert_eq!(second, RateLimitDecision::Degraded);
+    }
+
+    #[tokio::test]
+    async fn queue_waits_for_capacity() {
+        let limiter = RateLimiter::new();
+        // One token every 100ms.
+        let config = make_queue_config(10, Window::Second, Duration::from_secs(2));
+        limiter.acquire("test", &config, "/test").await.unwrap();
+
+        let started = Instant::now();
+        let decision = limiter.acquire("test", &config, "/test").await.unwrap();
+        assert_eq!(decision, RateLimitDecision::Allowed);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn queue_times_out_with_retry_after() {
+        let limiter = RateLimiter::new();
+        let config = make_queue_config(1, Window::Minute, Duration::from_millis(50));
+        limiter.acquire("test", &config, "/test").await.unwrap();
+
+        match limiter.acquire("test", &config, "/test").await {
+            Err(DomainError::QueueTimeout {
+                retry_after_secs, ..
+            }) => assert!(retry_after_secs.unwrap() >= 1),
+            other => panic!("expected QueueTimeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn queue_rejects_when_full() {
+        let limiter = RateLimiter::new();
+        let config = make_queue_config(1, Window::Minute, Duration::from_millis(50));
+        limiter.acquire("test", &config, "/test").await.unwrap();
+
+        // The first waiter takes the only slot; the second finds the queue full.
+        let (waiter, overflow) = tokio::join!(
+            limiter.acquire("test", &config, "/test"),
+            limiter.acquire("test", &config, "/test"),
+        );
+        assert!(matches!(waiter, Err(DomainError::QueueTimeout { .. })));
+        assert!(matches!(overflow, Err(DomainError::QueueFull { .. })));
+    }
+
     #[test]
     fn purge_removes_stale_entries() {
         let limiter = RateLimiter::new();
@@ -203,8 +631,7 @@ mod tests {
         limiter.try_consume("b", &config, "/test").unwrap();
         limiter.try_consume("c", &config, "/test").unwrap();
 
-        let active: HashSet<String> = ["a", "c"].iter().map(|s| (*s).into()).collect();
-        limiter.purge_keys(&active);
+        limiter.purge_keys(["b"]);
 
         // a and c survive, b is gone.
         assert!(limiter.buckets.contains_key("a"));
@@ -213,14 +640,27 @@ mod tests {
     }
 
     #[test]
-    fn purge_with_empty_set_removes_all() {
+    fn config_changes_reset_their_limits() {
         let limiter = RateLimiter::new();
-        let config = make_config(10, Window::Second, None);
-        limiter.try_consume("x", &config, "/test").unwrap();
-        limiter.try_consume("y", &config, "/test").unwrap();
+        let config = make_config(1, Window::Minute, None);
+        let (upstream, route) = (Uuid::new_v4(), Uuid::new_v4());
+        let upstream_key = RateLimiter::upstream_key(upstream);
+        let route_key = RateLimiter::route_key(route);
+        limiter
+            .try_consume(&upstream_key, &config, "/test")
+            .unwrap();
+        limiter.try_consume(&route_key, &config, "/test").unwrap();
+        assert!(
+            limiter
+                .try_consume(&upstream_key, &config, "/test")
+                .is_err()
+        );
 
-        limiter.purge_keys(&HashSet::new());
+        limiter.upstream_changed(upstream, "api.example.com");
+        assert!(limiter.try_consume(&upstream_key, &config, "/test").is_ok());
+        assert!(limiter.try_consume(&route_key, &config, "/test").is_err());
 
-        assert!(limiter.buckets.is_empty());
+        limiter.route_changed(route);
+        assert!(limiter.try_consume(&route_key, &config, "/test").is_ok());
     }
 }
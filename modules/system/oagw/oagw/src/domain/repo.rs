@@ -1,4 +1,4 @@
-use crate::domain::model::{ListQuery, Route, Upstream};
+use crate::domain::model::{CustomPlugin, ListQuery, Route, Upstream};
 use modkit_macros::domain_model;
 use uuid::Uuid;
 
@@ -14,8 +14,18 @@ pub enum RepositoryError {
     NotFound { entity: &'static str, id: Uuid },
     #[error("conflict: {0}")]
     Conflict(String),
+    /// A plugin cannot be deleted while upstreams or routes reference it.
+    #[error(
+        "plugin {id} is referenced by {} upstream(s) and {} route(s)",
+        upstreams.len(),
+        routes.len()
+    )]
+    PluginInUse {
+        id: Uuid,
+        upstreams: Vec<Uuid>,
+        routes: Vec<Uuid>,
+    },
     #[error("internal: {0}")]
-    #[allow(dead_code)]
     Internal(String),
 }
 
@@ -90,3 +100,26 @@ pub trait RouteRepository: Send + Sync {
         upstream_id: Uuid,
     ) -> Result<u64, RepositoryError>;
 }
+
+/// Repository trait for custom plugin persistence. Plugins are immutable, so
+/// there is no update.
+#[async_trait::async_trait]
+pub trait PluginRepository: Send + Sync {
+    /// Insert a new plugin. Returns Conflict if the name is taken for the tenant.
+    async fn create(&self, plugin: CustomPlugin) -> Result<CustomPlugin, RepositoryError>;
+
+    /// Get a plugin by id, scoped to a tenant.
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<CustomPlugin, RepositoryError>;
+
+    /// List plugins for a tenant with pagination.
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<CustomPlugin>, RepositoryError>;
+
+    /// Delete a plugin. Returns NotFound if it does not exist and
+    /// PluginInUse if an upstream or route of the tenant references it; the
+    /// check and the delete are atomic.
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError>;
+}
@@ -244,6 +244,33 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
             instance,
             retry_after_secs,
         },
+        DomainError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::QueueFull {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::QueueFull {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        },
         DomainError::SecretNotFound { detail, instance } => {
             ServiceGatewayError::SecretNotFound { detail, instance }
         }
@@ -259,6 +286,32 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
         DomainError::RequestTimeout { detail, instance } => {
             ServiceGatewayError::RequestTimeout { detail, instance }
         }
+        DomainError::PluginNotFound { detail, instance } => {
+            ServiceGatewayError::PluginNotFound { detail, instance }
+        }
+        DomainError::PluginInUse { detail } => ServiceGatewayError::PluginInUse {
+            detail,
+            instance: String::new(),
+        },
+        DomainError::PluginRejected {
+            status,
+            code,
+            detail,
+            instance,
+        } => ServiceGatewayError::PluginRejected {
+            status,
+            code,
+            detail,
+            instance,
+        },
+        DomainError::PluginFailed { detail, instance } => {
+            ServiceGatewayError::PluginFailed { detail, instance }
+        }
+        DomainError::CorsOriginNotAllowed { detail, instance }
+        | DomainError::CorsMethodNotAllowed { detail, instance }
+        | DomainError::CorsHeadersNotAllowed { detail, instance } => {
+            ServiceGatewayError::CorsRejected { detail, instance }
+        }
     }
 }
 
@@ -277,6 +330,14 @@ fn sdk_create_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
+        load_balancing: req
+            .load_balancing()
+            .cloned()
+            .map(load_balancing_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
     }
@@ -293,6 +354,14 @@ fn sdk_update_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
+        load_balancing: req
+            .load_balancing()
+            .cloned()
+            .map(load_balancing_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         enabled: req.enabled(),
     }
@@ -304,6 +373,15 @@ fn sdk_create_route_to_domain(req: oagw_sdk::CreateRouteRequest) -> model::Creat
         match_rules: match_rules_to_domain(req.match_rules().clone()),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        grpc_transcoding: req
+            .grpc_transcoding()
+            .cloned()
+            .map(grpc_transcoding_to_domain),
+        response_cache: req.response_cache().cloned().map(response_cache_to_domain),
+        usage_extraction: req
+            .usage_extraction()
+            .cloned()
+            .map(usage_extraction_to_domain),
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -315,6 +393,15 @@ fn sdk_update_route_to_domain(req: oagw_sdk::UpdateRouteRequest) -> model::Updat
         match_rules: req.match_rules().cloned().map(match_rules_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        grpc_transcoding: req
+            .grpc_transcoding()
+            .cloned()
+            .map(grpc_transcoding_to_domain),
+        response_cache: req.response_cache().cloned().map(response_cache_to_domain),
+        usage_extraction: req
+            .usage_extraction()
+            .cloned()
+            .map(usage_extraction_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -348,6 +435,7 @@ fn endpoint_to_domain(v: oagw_sdk::Endpoint) -> model::Endpoint {
         scheme: scheme_to_domain(v.scheme),
         host: v.host,
         port: v.port,
+        weight: v.weight,
     }
 }
 
@@ -434,6 +522,66 @@ fn rate_limit_config_to_domain(v: oagw_sdk::RateLimitConfig) -> model::RateLimit
             oagw_sdk::RateLimitStrategy::Degrade => model::RateLimitStrategy::Degrade,
         },
         cost: v.cost,
+        queue: v.queue.map(|q| model::QueueConfig {
+            max_depth: q.max_depth,
+            timeout: q.timeout,
+        }),
+        degrade: v.degrade.map(|d| model::DegradeConfig {
+            fallback_upstream_id: d.fallback_upstream_id,
+            fallback_response: d.fallback_response.map(|r| model::FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }),
+    }
+}
+
+fn circuit_breaker_config_to_domain(
+    v: oagw_sdk::CircuitBreakerConfig,
+) -> model::CircuitBreakerConfig {
+    model::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate_threshold: v.failure_rate_threshold,
+        minimum_requests: v.minimum_requests,
+        window_seconds: v.window_seconds,
+        success_threshold: v.success_threshold,
+        timeout_seconds: v.timeout_seconds,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: model::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            oagw_sdk::CircuitBreakerScope::Global => model::CircuitBreakerScope::Global,
+            oagw_sdk::CircuitBreakerScope::PerEndpoint => model::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn load_balancing_config_to_domain(v: oagw_sdk::LoadBalancingConfig) -> model::LoadBalancingConfig {
+    model::LoadBalancingConfig {
+        strategy: match v.strategy {
+            oagw_sdk::LoadBalancingStrategy::RoundRobin => model::LoadBalancingStrategy::RoundRobin,
+            oagw_sdk::LoadBalancingStrategy::Weighted => model::LoadBalancingStrategy::Weighted,
+            oagw_sdk::LoadBalancingStrategy::LeastInFlight => {
+                model::LoadBalancingStrategy::LeastInFlight
+            }
+        },
+        outlier_detection: v.outlier_detection.map(|o| model::OutlierDetectionConfig {
+            consecutive_failures: o.consecutive_failures,
+            base_ejection_seconds: o.base_ejection_seconds,
+            max_ejection_percent: o.max_ejection_percent,
+        }),
+        health_check: v.health_check.map(|h| model::HealthCheckConfig {
+            path: h.path,
+            interval_seconds: h.interval_seconds,
+            timeout_seconds: h.timeout_seconds,
+            healthy_threshold: h.healthy_threshold,
+            unhealthy_threshold: h.unhealthy_threshold,
+            expected_statuses: h.expected_statuses,
+        }),
     }
 }
 
@@ -441,6 +589,7 @@ fn plugins_config_to_domain(v: oagw_sdk::PluginsConfig) -> model::PluginsConfig
     model::PluginsConfig {
         sharing: sharing_mode_to_domain(v.sharing),
         items: v.items,
+        config: v.config,
     }
 }
 
@@ -466,6 +615,26 @@ fn http_match_to_domain(v: oagw_sdk::HttpMatch) -> model::HttpMatch {
     }
 }
 
+fn grpc_transcoding_to_domain(v: oagw_sdk::GrpcTranscodingConfig) -> model::GrpcTranscodingConfig {
+    model::GrpcTranscodingConfig {
+        descriptor_set: v.descriptor_set,
+    }
+}
+
+fn response_cache_to_domain(v: oagw_sdk::ResponseCacheConfig) -> model::ResponseCacheConfig {
+    model::ResponseCacheConfig {
+        ttl: v.ttl,
+        vary: v.vary,
+    }
+}
+
+fn usage_extraction_to_domain(v: oagw_sdk::UsageExtractionConfig) -> model::UsageExtractionConfig {
+    model::UsageExtractionConfig {
+        input_tokens: v.input_tokens,
+        output_tokens: v.output_tokens,
+    }
+}
+
 fn grpc_match_to_domain(v: oagw_sdk::GrpcMatch) -> model::GrpcMatch {
     model::GrpcMatch {
         service: v.service,
@@ -516,6 +685,7 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
                     scheme: scheme_to_sdk(e.scheme),
                     host: e.host,
                     port: e.port,
+                    weight: e.weight,
                 })
                 .collect(),
         },
@@ -547,8 +717,11 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
         plugins: u.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: u.rate_limit.map(rate_limit_config_to_sdk),
+        circuit_breaker: u.circuit_breaker.map(circuit_breaker_config_to_sdk),
+        load_balancing: u.load_balancing.map(load_balancing_config_to_sdk),
         tags: u.tags,
     }
 }
@@ -586,8 +759,20 @@ fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
         plugins: r.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: r.rate_limit.map(rate_limit_config_to_sdk),
+        grpc_transcoding: r.grpc_transcoding.map(|t| oagw_sdk::GrpcTranscodingConfig {
+            descriptor_set: t.descriptor_set,
+        }),
+        response_cache: r.response_cache.map(|c| oagw_sdk::ResponseCacheConfig {
+            ttl: c.ttl,
+            vary: c.vary,
+        }),
+        usage_extraction: r.usage_extraction.map(|u| oagw_sdk::UsageExtractionConfig {
+            input_tokens: u.input_tokens,
+            output_tokens: u.output_tokens,
+        }),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -626,6 +811,66 @@ fn rate_limit_config_to_sdk(v: model::RateLimitConfig) -> oagw_sdk::RateLimitCon
             model::RateLimitStrategy::Degrade => oagw_sdk::RateLimitStrategy::Degrade,
         },
         cost: v.cost,
+        queue: v.queue.map(|q| oagw_sdk::QueueConfig {
+            max_depth: q.max_depth,
+            timeout: q.timeout,
+        }),
+        degrade: v.degrade.map(|d| oagw_sdk::DegradeConfig {
+            fallback_upstream_id: d.fallback_upstream_id,
+            fallback_response: d.fallback_response.map(|r| oagw_sdk::FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }),
+    }
+}
+
+fn circuit_breaker_config_to_sdk(v: model::CircuitBreakerConfig) -> oagw_sdk::CircuitBreakerConfig {
+    oagw_sdk::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate_threshold: v.failure_rate_threshold,
+        minimum_requests: v.minimum_requests,
+        window_seconds: v.window_seconds,
+        success_threshold: v.success_threshold,
+        timeout_seconds: v.timeout_seconds,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: oagw_sdk::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            model::CircuitBreakerScope::Global => oagw_sdk::CircuitBreakerScope::Global,
+            model::CircuitBreakerScope::PerEndpoint => oagw_sdk::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn load_balancing_config_to_sdk(v: model::LoadBalancingConfig) -> oagw_sdk::LoadBalancingConfig {
+    oagw_sdk::LoadBalancingConfig {
+        strategy: match v.strategy {
+            model::LoadBalancingStrategy::RoundRobin => oagw_sdk::LoadBalancingStrategy::RoundRobin,
+            model::LoadBalancingStrategy::Weighted => oagw_sdk::LoadBalancingStrategy::Weighted,
+            model::LoadBalancingStrategy::LeastInFlight => {
+                oagw_sdk::LoadBalancingStrategy::LeastInFlight
+            }
+        },
+        outlier_detection: v
+            .outlier_detection
+            .map(|o| oagw_sdk::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_seconds: o.base_ejection_seconds,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+        health_check: v.health_check.map(|h| oagw_sdk::HealthCheckConfig {
+            path: h.path,
+            interval_seconds: h.interval_seconds,
+            timeout_seconds: h.timeout_seconds,
+            healthy_threshold: h.healthy_threshold,
+            unhealthy_threshold: h.unhealthy_threshold,
+            expected_statuses: h.expected_statuses,
+        }),
     }
 }
 
@@ -677,6 +922,7 @@ mod tests {
                     scheme: model::Scheme::Https,
                     host: "example.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "http".into(),
@@ -689,6 +935,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
             tags: vec![],
         };
 
@@ -755,6 +1003,34 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn domain_err_plugin_rejected_keeps_status_and_code() {
+        let err = DomainError::PluginRejected {
+            status: 403,
+            code: "DENIED".into(),
+            detail: "not allowed".into(),
+            instance: "/guarded".into(),
+        };
+        match domain_err_to_sdk(err) {
+            ServiceGatewayError::PluginRejected { status, code, .. } => {
+                assert_eq!(status, 403);
+                assert_eq!(code, "DENIED");
+            }
+            _ => panic!("expected PluginRejected"),
+        }
+    }
+
+    #[test]
+    fn domain_err_plugin_in_use_maps_to_conflict() {
+        let err = DomainError::PluginInUse {
+            detail: "referenced by route".into(),
+        };
+        assert!(matches!(
+            domain_err_to_sdk(err),
+            ServiceGatewayError::PluginInUse { .. }
+        ));
+    }
+
     #[test]
     fn sharing_mode_round_trip() {
         for (sdk_val, expected_domain) in [
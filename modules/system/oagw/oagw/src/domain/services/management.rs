@@ -1,12 +1,20 @@
 use std::sync::Arc;
 
-use super::ControlPlaneService;
+use super::{ConfigChangeListener, ControlPlaneService};
+use crate::domain::builtin_guards;
 use crate::domain::error::DomainError;
+use crate::domain::grpc_transcoding::Transcoder;
+use crate::domain::gts_helpers::{
+    format_plugin_gts, format_route_gts, format_upstream_gts, parse_plugin_gts,
+};
+use crate::domain::hierarchy::{EffectiveUpstream, TenantHierarchy, merge_bindings};
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    AuthConfig, CircuitBreakerConfig, CreatePluginRequest, CreateRouteRequest,
+    CreateUpstreamRequest, CustomPlugin, ListQuery, LoadBalancingConfig, PluginPhase, PluginType,
+    PluginsConfig, Route, Server, UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
-use crate::domain::repo::{RouteRepository, UpstreamRepository};
+use crate::domain::plugin::PluginRuntime;
+use crate::domain::repo::{PluginRepository, RepositoryError, RouteRepository, UpstreamRepository};
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use uuid::Uuid;
@@ -16,6 +24,15 @@ use uuid::Uuid;
 pub(crate) struct ControlPlaneServiceImpl {
     upstreams: Arc<dyn UpstreamRepository>,
     routes: Arc<dyn RouteRepository>,
+    plugins: Arc<dyn PluginRepository>,
+    /// Compiles plugin source on create; nothing is executed here.
+    runtime: Arc<dyn PluginRuntime>,
+    /// Ancestry used to resolve upstreams shared by ancestor tenants. Without
+    /// it only the caller's own upstreams resolve.
+    tenants: Option<Arc<dyn TenantHierarchy>>,
+    /// Notified after upstream and route writes, so that state derived from
+    /// the old configuration can be dropped.
+    listeners: Vec<Arc<dyn ConfigChangeListener>>,
 }
 
 impl ControlPlaneServiceImpl {
@@ -23,8 +40,109 @@ impl ControlPlaneServiceImpl {
     pub(crate) fn new(
         upstreams: Arc<dyn UpstreamRepository>,
         routes: Arc<dyn RouteRepository>,
+        plugins: Arc<dyn PluginRepository>,
+        runtime: Arc<dyn PluginRuntime>,
     ) -> Self {
-        Self { upstreams, routes }
+        Self {
+            upstreams,
+            routes,
+            plugins,
+            runtime,
+            tenants: None,
+            listeners: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_tenant_hierarchy(mut self, tenants: Arc<dyn TenantHierarchy>) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_config_listener(mut self, listener: Arc<dyn ConfigChangeListener>) -> Self {
+        self.listeners.push(listener);
+        self
+    }
+
+    fn notify_upstream_changed(&self, id: Uuid, alias: &str) {
+        for listener in &self.listeners {
+            listener.upstream_changed(id, alias);
+        }
+    }
+
+    fn notify_route_changed(&self, route_id: Uuid) {
+        for listener in &self.listeners {
+            listener.route_changed(route_id);
+        }
+    }
+
+    /// Ids of every route of `upstream_id`.
+    async fn route_ids_of(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let mut ids = Vec::new();
+        let mut query = ListQuery::default();
+        loop {
+            let page = self
+                .routes
+                .list_by_upstream(tenant_id, upstream_id, &query)
+                .await?;
+            ids.extend(page.iter().map(|r| r.id));
+            if page.len() < query.top as usize {
+                return Ok(ids);
+            }
+            query.skip += query.top;
+        }
+    }
+
+    /// The caller's tenant followed by its ancestors, closest first.
+    async fn tenant_chain(&self, ctx: &SecurityContext) -> Result<Vec<Uuid>, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let mut chain = vec![tenant_id];
+        if let Some(tenants) = &self.tenants {
+            chain.extend(tenants.ancestors(ctx, tenant_id).await?);
+        }
+        Ok(chain)
+    }
+
+    /// Upstreams bound to `alias` by each tenant in `chain`, in chain order.
+    async fn alias_bindings(
+        &self,
+        chain: &[Uuid],
+        alias: &str,
+    ) -> Result<Vec<Upstream>, DomainError> {
+        let mut bindings = Vec::new();
+        for &tenant_id in chain {
+            match self.upstreams.get_by_alias(tenant_id, alias).await {
+                Ok(upstream) => bindings.push(upstream),
+                Err(RepositoryError::NotFound { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// First route matching `method` and `path` along `bindings`, given as
+    /// `(tenant, upstream)` closest first.
+    async fn find_route(
+        &self,
+        bindings: &[(Uuid, Uuid)],
+        method: &str,
+        path: &str,
+    ) -> Result<Route, DomainError> {
+        for &(tenant_id, upstream_id) in bindings {
+            if let Ok(route) = self
+                .routes
+                .find_matching(tenant_id, upstream_id, method, path)
+                .await
+            {
+                return Ok(route);
+            }
+        }
+        Err(DomainError::not_found("route", Uuid::nil()))
     }
 }
 
@@ -52,6 +170,218 @@ fn validate_alias(alias: &str) -> Result<(), DomainError> {
     Ok(())
 }
 
+/// Validate circuit breaker thresholds: counts and durations must be positive
+/// and the failure rate a percentage.
+fn validate_circuit_breaker(cb: &CircuitBreakerConfig) -> Result<(), DomainError> {
+    for (name, value) in [
+        ("failure_threshold", cb.failure_threshold),
+        ("success_threshold", cb.success_threshold),
+        ("timeout_seconds", cb.timeout_seconds),
+        ("half_open_max_requests", cb.half_open_max_requests),
+        ("window_seconds", cb.window_seconds),
+    ] {
+        if value == 0 {
+            return Err(DomainError::validation(format!(
+                "circuit_breaker.{name} must be at least 1"
+            )));
+        }
+    }
+    if let Some(rate) = cb.failure_rate_threshold
+        && !(1..=100).contains(&rate)
+    {
+        return Err(DomainError::validation(
+            "circuit_breaker.failure_rate_threshold must be between 1 and 100",
+        ));
+    }
+    Ok(())
+}
+
+/// Every endpoint needs a positive weight, even when the strategy ignores it,
+/// so that switching to `weighted` later never divides traffic by zero.
+fn validate_server(server: &Server) -> Result<(), DomainError> {
+    if server.endpoints.iter().any(|e| e.weight == 0) {
+        return Err(DomainError::validation(
+            "server.endpoints[].weight must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+/// Validate outlier detection and health check settings.
+fn validate_load_balancing(lb: &LoadBalancingConfig) -> Result<(), DomainError> {
+    if let Some(ref od) = lb.outlier_detection {
+        if od.consecutive_failures == 0 || od.base_ejection_seconds == 0 {
+            return Err(DomainError::validation(
+                "load_balancing.outlier_detection thresholds must be at least 1",
+            ));
+        }
+        if od.max_ejection_percent > 100 {
+            return Err(DomainError::validation(
+                "load_balancing.outlier_detection.max_ejection_percent must be at most 100",
+            ));
+        }
+    }
+    if let Some(ref hc) = lb.health_check {
+        for (name, value) in [
+            ("interval_seconds", hc.interval_seconds),
+            ("timeout_seconds", hc.timeout_seconds),
+            ("healthy_threshold", hc.healthy_threshold),
+            ("unhealthy_threshold", hc.unhealthy_threshold),
+        ] {
+            if value == 0 {
+                return Err(DomainError::validation(format!(
+                    "load_balancing.health_check.{name} must be at least 1"
+                )));
+            }
+        }
+        if !hc.path.starts_with('/') {
+            return Err(DomainError::validation(
+                "load_balancing.health_check.path must start with '/'",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Transcoding needs a gRPC match and a descriptor set that defines the
+/// matched method.
+fn validate_grpc_transcoding(route: &Route) -> Result<(), DomainError> {
+    let Some(ref config) = route.grpc_transcoding else {
+        return Ok(());
+    };
+    let Some(ref grpc) = route.match_rules.grpc else {
+        return Err(DomainError::validation(
+            "grpc_transcoding requires match.grpc",
+        ));
+    };
+    Transcoder::new(config, grpc)
+        .map(|_| ())
+        .map_err(|e| DomainError::validation(format!("grpc_transcoding: {e}")))
+}
+
+/// Response caching applies to HTTP routes and needs a positive TTL. `vary`
+/// entries must be header names; they are stored lowercased.
+fn validate_response_cache(route: &mut Route) -> Result<(), DomainError> {
+    let Some(ref mut config) = route.response_cache else {
+        return Ok(());
+    };
+    if route.match_rules.http.is_none() {
+        return Err(DomainError::validation(
+            "response_cache requires match.http",
+        ));
+    }
+    if config.ttl.is_zero() {
+        return Err(DomainError::validation(
+            "response_cache.ttl must be greater than zero",
+        ));
+    }
+    for name in &mut config.vary {
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        {
+            return Err(DomainError::validation(format!(
+                "response_cache.vary: '{name}' is not a valid header name"
+            )));
+        }
+        name.make_ascii_lowercase();
+    }
+    Ok(())
+}
+
+/// Token counts are located with JSON pointers, which are empty or start
+/// with `/`. A config that locates nothing is rejected rather than ignored.
+fn validate_usage_extraction(route: &Route) -> Result<(), DomainError> {
+    let Some(ref config) = route.usage_extraction else {
+        return Ok(());
+    };
+    if config.input_tokens.is_none() && config.output_tokens.is_none() {
+        return Err(DomainError::validation(
+            "usage_extraction needs input_tokens or output_tokens",
+        ));
+    }
+    for (field, pointer) in [
+        ("input_tokens", &config.input_tokens),
+        ("output_tokens", &config.output_tokens),
+    ] {
+        if let Some(pointer) = pointer
+            && !pointer.is_empty()
+            && !pointer.starts_with('/')
+        {
+            return Err(DomainError::validation(format!(
+                "usage_extraction.{field}: '{pointer}' is not a JSON pointer"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject plugin references of the wrong kind: `auth` takes an auth plugin
+/// and `plugins.items` guards and transforms. An `auth` value outside the
+/// plugin schemas is left for the data plane to resolve. Builtin guards must
+/// also come with a config they can enforce.
+fn validate_plugin_refs(
+    auth: Option<&AuthConfig>,
+    plugins: Option<&PluginsConfig>,
+) -> Result<(), DomainError> {
+    if let Some(auth) = auth
+        && let Ok((plugin_type, _)) = parse_plugin_gts(&auth.plugin_type)
+        && plugin_type != PluginType::Auth
+    {
+        return Err(DomainError::validation(format!(
+            "plugin type mismatch: '{}' is a {} plugin, expected auth",
+            auth.plugin_type,
+            plugin_type.as_str()
+        )));
+    }
+    for item in plugins.map(|p| p.items.as_slice()).unwrap_or_default() {
+        let (plugin_type, _) = parse_plugin_gts(item)?;
+        if plugin_type == PluginType::Auth {
+            return Err(DomainError::validation(format!(
+                "plugin type mismatch: '{item}' is an auth plugin, expected guard or transform"
+            )));
+        }
+    }
+    if let Some(plugins) = plugins {
+        builtin_guards::validate(plugins).map_err(DomainError::validation)?;
+    }
+    Ok(())
+}
+
+/// Resolve the phases a new plugin runs in from the hooks its source
+/// defines. Guards only see requests, so they may only hook `on_request`.
+fn plugin_phases(
+    req: &CreatePluginRequest,
+    defined: &[PluginPhase],
+) -> Result<Vec<PluginPhase>, DomainError> {
+    let phases: Vec<PluginPhase> = if req.phases.is_empty() {
+        defined.to_vec()
+    } else {
+        PluginPhase::ALL
+            .into_iter()
+            .filter(|p| req.phases.contains(p))
+            .collect()
+    };
+    if let Some(missing) = phases.iter().find(|p| !defined.contains(p)) {
+        return Err(DomainError::validation(format!(
+            "phases: source_code does not define {}",
+            missing.hook_name()
+        )));
+    }
+    if phases.is_empty() {
+        return Err(DomainError::validation(
+            "source_code must define on_request, on_response or on_error",
+        ));
+    }
+    if req.plugin_type == PluginType::Guard && phases != [PluginPhase::OnRequest] {
+        return Err(DomainError::validation(
+            "guard plugins may only define on_request",
+        ));
+    }
+    Ok(phases)
+}
+
 /// Generate an alias from the upstream's server endpoints.
 /// Single endpoint: host (standard port omitted) or host:port.
 fn generate_alias(upstream: &Upstream) -> String {
@@ -75,6 +405,15 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         let tenant_id = ctx.subject_tenant_id();
         let id = Uuid::new_v4();
 
+        validate_server(&req.server)?;
+        if let Some(ref cb) = req.circuit_breaker {
+            validate_circuit_breaker(cb)?;
+        }
+        if let Some(ref lb) = req.load_balancing {
+            validate_load_balancing(lb)?;
+        }
+        validate_plugin_refs(req.auth.as_ref(), req.plugins.as_ref())?;
+
         let upstream = Upstream {
             id,
             tenant_id,
@@ -86,6 +425,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             headers: req.headers.clone(),
             plugins: req.plugins.clone(),
             rate_limit: req.rate_limit.clone(),
+            circuit_breaker: req.circuit_breaker.clone(),
+            load_balancing: req.load_balancing.clone(),
             tags: req.tags.clone(),
         };
 
@@ -136,9 +477,11 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .get_by_id(tenant_id, id)
             .await
             .map_err(|_| DomainError::not_found("upstream", id))?;
+        let previous_alias = existing.alias.clone();
 
         // Apply partial update.
         if let Some(server) = req.server {
+            validate_server(&server)?;
             existing.server = server;
         }
         if let Some(protocol) = req.protocol {
@@ -160,27 +503,51 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(rate_limit) = req.rate_limit {
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(circuit_breaker) = req.circuit_breaker {
+            validate_circuit_breaker(&circuit_breaker)?;
+            existing.circuit_breaker = Some(circuit_breaker);
+        }
+        if let Some(load_balancing) = req.load_balancing {
+            validate_load_balancing(&load_balancing)?;
+            existing.load_balancing = Some(load_balancing);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
         if let Some(enabled) = req.enabled {
             existing.enabled = enabled;
         }
-
-        self.upstreams
-            .update(existing)
-            .await
-            .map_err(DomainError::from)
+        validate_plugin_refs(existing.auth.as_ref(), existing.plugins.as_ref())?;
+
+        let updated = self.upstreams.update(existing).await?;
+        // Descendant tenants may inherit from this binding, so everything
+        // served under the alias is affected, not just this upstream.
+        self.notify_upstream_changed(id, &previous_alias);
+        if updated.alias != previous_alias {
+            self.notify_upstream_changed(id, &updated.alias);
+        }
+        Ok(updated)
     }
 
     async fn delete_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
+        let upstream = self
+            .upstreams
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", id))?;
+        let route_ids = self.route_ids_of(tenant_id, id).await?;
         // Cascade delete routes.
         let _ = self.routes.delete_by_upstream(tenant_id, id).await;
         self.upstreams
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("upstream", id))
+            .map_err(|_| DomainError::not_found("upstream", id))?;
+        self.notify_upstream_changed(id, &upstream.alias);
+        for route_id in route_ids {
+            self.notify_route_changed(route_id);
+        }
+        Ok(())
     }
 
     // -- Route CRUD --
@@ -202,17 +569,24 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
                 ))
             })?;
 
-        let route = Route {
+        let mut route = Route {
             id: Uuid::new_v4(),
             tenant_id,
             upstream_id: req.upstream_id,
             match_rules: req.match_rules,
             plugins: req.plugins,
             rate_limit: req.rate_limit,
+            grpc_transcoding: req.grpc_transcoding,
+            response_cache: req.response_cache,
+            usage_extraction: req.usage_extraction,
             tags: req.tags,
             priority: req.priority,
             enabled: req.enabled,
         };
+        validate_grpc_transcoding(&route)?;
+        validate_response_cache(&mut route)?;
+        validate_usage_extraction(&route)?;
+        validate_plugin_refs(None, route.plugins.as_ref())?;
 
         self.routes.create(route).await.map_err(DomainError::from)
     }
@@ -260,6 +634,15 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(rate_limit) = req.rate_limit {
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(grpc_transcoding) = req.grpc_transcoding {
+            existing.grpc_transcoding = Some(grpc_transcoding);
+        }
+        if let Some(response_cache) = req.response_cache {
+            existing.response_cache = Some(response_cache);
+        }
+        if let Some(usage_extraction) = req.usage_extraction {
+            existing.usage_extraction = Some(usage_extraction);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
@@ -269,19 +652,127 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(enabled) = req.enabled {
             existing.enabled = enabled;
         }
+        validate_grpc_transcoding(&existing)?;
+        validate_response_cache(&mut existing)?;
+        validate_usage_extraction(&existing)?;
+        validate_plugin_refs(None, existing.plugins.as_ref())?;
+
+        let updated = self.routes.update(existing).await?;
+        self.notify_route_changed(id);
+        Ok(updated)
+    }
 
+    async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
         self.routes
-            .update(existing)
+            .delete(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("route", id))?;
+        self.notify_route_changed(id);
+        Ok(())
+    }
+
+    // -- Custom plugins --
+
+    async fn create_plugin(
+        &self,
+        ctx: &SecurityContext,
+        req: CreatePluginRequest,
+    ) -> Result<CustomPlugin, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+
+        if req.name.trim().is_empty() {
+            return Err(DomainError::validation("name must not be empty"));
+        }
+        if req.plugin_type == PluginType::Auth {
+            return Err(DomainError::validation(
+                "custom auth plugins are not supported; use a builtin auth plugin",
+            ));
+        }
+        let defined = self
+            .runtime
+            .compile(&req.source_code)
+            .await
+            .map_err(|e| DomainError::validation(format!("source_code: {e}")))?;
+        let phases = plugin_phases(&req, &defined)?;
+        let config_schema = match req.config_schema {
+            serde_json::Value::Null => serde_json::json!({}),
+            schema @ serde_json::Value::Object(_) => schema,
+            _ => {
+                return Err(DomainError::validation(
+                    "config_schema must be a JSON object",
+                ));
+            }
+        };
+
+        let plugin = CustomPlugin {
+            id: Uuid::new_v4(),
+            tenant_id,
+            name: req.name,
+            description: req.description,
+            plugin_type: req.plugin_type,
+            phases,
+            config_schema,
+            source_code: req.source_code,
+        };
+        self.plugins.create(plugin).await.map_err(DomainError::from)
+    }
+
+    async fn get_plugin(
+        &self,
+        ctx: &SecurityContext,
+        id: Uuid,
+    ) -> Result<CustomPlugin, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        self.plugins
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("plugin", id))
+    }
+
+    async fn list_plugins(
+        &self,
+        ctx: &SecurityContext,
+        query: &ListQuery,
+    ) -> Result<Vec<CustomPlugin>, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        self.plugins
+            .list(tenant_id, query)
             .await
             .map_err(DomainError::from)
     }
 
-    async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
+    async fn delete_plugin(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
-        self.routes
+        let plugin = self
+            .plugins
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("plugin", id))?;
+
+        self.plugins
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("route", id))
+            .map_err(|e| match e {
+                RepositoryError::PluginInUse {
+                    upstreams, routes, ..
+                } => DomainError::PluginInUse {
+                    detail: format!(
+                        "plugin '{}' is referenced by {} upstream(s) and {} route(s): {}",
+                        format_plugin_gts(plugin.plugin_type, id),
+                        upstreams.len(),
+                        routes.len(),
+                        upstreams
+                            .into_iter()
+                            .map(format_upstream_gts)
+                            .chain(routes.into_iter().map(format_route_gts))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                },
+                RepositoryError::NotFound { .. } => DomainError::not_found("plugin", id),
+                e => e.into(),
+            })
     }
 
     // -- Resolution --
@@ -291,18 +782,27 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         ctx: &SecurityContext,
         alias: &str,
     ) -> Result<Upstream, DomainError> {
-        let tenant_id = ctx.subject_tenant_id();
-        let upstream = self
-            .upstreams
-            .get_by_alias(tenant_id, alias)
-            .await
-            .map_err(|_| DomainError::not_found("upstream", Uuid::nil()))?;
+        Ok(self.resolve_effective_upstream(ctx, alias).await?.upstream)
+    }
 
-        if !upstream.enabled {
+    async fn resolve_effective_upstream(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+    ) -> Result<EffectiveUpstream, DomainError> {
+        let chain = self.tenant_chain(ctx).await?;
+        let bindings = self.alias_bindings(&chain, alias).await?;
+
+        // The closest binding shadows the ancestors' for routing; whether
+        // the alias is usable is its decision alone.
+        let Some(selected) = bindings.first() else {
+            return Err(DomainError::not_found("upstream", Uuid::nil()));
+        };
+        if !selected.enabled {
             return Err(DomainError::upstream_disabled(alias));
         }
 
-        Ok(upstream)
+        Ok(merge_bindings(bindings))
     }
 
     async fn resolve_route(
@@ -312,11 +812,49 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         method: &str,
         path: &str,
     ) -> Result<Route, DomainError> {
-        let tenant_id = ctx.subject_tenant_id();
-        self.routes
-            .find_matching(tenant_id, upstream_id, method, path)
+        let chain = self.tenant_chain(ctx).await?;
+
+        // Routes are owned by the binding the upstream was resolved to; when
+        // none of them match, those of ancestor bindings of the same alias
+        // are inherited.
+        let mut owner = None;
+        for (idx, &tenant_id) in chain.iter().enumerate() {
+            if let Ok(upstream) = self.upstreams.get_by_id(tenant_id, upstream_id).await {
+                owner = Some((idx, upstream));
+                break;
+            }
+        }
+        let Some((idx, upstream)) = owner else {
+            return Err(DomainError::not_found("route", Uuid::nil()));
+        };
+
+        let bindings: Vec<(Uuid, Uuid)> = self
+            .alias_bindings(&chain[idx..], &upstream.alias)
+            .await?
+            .iter()
+            .map(|b| (b.tenant_id, b.id))
+            .collect();
+        self.find_route(&bindings, method, path).await
+    }
+
+    async fn match_route(
+        &self,
+        upstream: &EffectiveUpstream,
+        method: &str,
+        path: &str,
+    ) -> Result<Route, DomainError> {
+        self.find_route(&upstream.bindings, method, path).await
+    }
+
+    async fn get_owned_plugin(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<CustomPlugin, DomainError> {
+        self.plugins
+            .get_by_id(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("route", Uuid::nil()))
+            .map_err(|_| DomainError::not_found("plugin", id))
     }
 }
 
@@ -325,19 +863,58 @@ mod tests {
     use std::sync::Arc;
 
     use crate::domain::model::{
-        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server,
+        Endpoint, GrpcMatch, GrpcTranscodingConfig, HealthCheckConfig, HttpMatch, HttpMethod,
+        MatchRules, OutlierDetectionConfig, PathSuffixMode, RateLimitAlgorithm, RateLimitConfig,
+        RateLimitScope, RateLimitStrategy, ResponseCacheConfig, Scheme, Server, SharingMode,
+        SustainedRate, UsageExtractionConfig, Window,
     };
 
     use super::*;
-    use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+    use crate::infra::plugin::StarlarkRuntime;
+    use crate::infra::storage::{
+        InMemoryPluginRefs, InMemoryPluginRepo, InMemoryRouteRepo, InMemoryUpstreamRepo,
+    };
+    use crate::infra::tenant_hierarchy::InMemoryTenantHierarchy;
 
     fn make_service() -> ControlPlaneServiceImpl {
+        let plugin_refs = Arc::new(InMemoryPluginRefs::new());
         ControlPlaneServiceImpl::new(
-            Arc::new(InMemoryUpstreamRepo::new()),
-            Arc::new(InMemoryRouteRepo::new()),
+            Arc::new(InMemoryUpstreamRepo::new().with_plugin_refs(plugin_refs.clone())),
+            Arc::new(InMemoryRouteRepo::new().with_plugin_refs(plugin_refs.clone())),
+            Arc::new(InMemoryPluginRepo::new().with_plugin_refs(plugin_refs)),
+            Arc::new(StarlarkRuntime::new()),
         )
     }
 
+    /// Service over a root → partner → customer tenant tree.
+    fn make_tree_service() -> (ControlPlaneServiceImpl, Uuid, Uuid, Uuid) {
+        let (root, partner, customer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let tree = InMemoryTenantHierarchy::new();
+        tree.set_parent(partner, root);
+        tree.set_parent(customer, partner);
+        let svc = make_service().with_tenant_hierarchy(Arc::new(tree));
+        (svc, root, partner, customer)
+    }
+
+    fn make_create_plugin(plugin_type: PluginType, source_code: &str) -> CreatePluginRequest {
+        CreatePluginRequest {
+            name: "require-header".into(),
+            description: None,
+            plugin_type,
+            phases: vec![],
+            config_schema: serde_json::Value::Null,
+            source_code: source_code.into(),
+        }
+    }
+
+    fn plugins_config(items: &[&str]) -> PluginsConfig {
+        PluginsConfig {
+            sharing: SharingMode::Private,
+            items: items.iter().map(|s| (*s).to_string()).collect(),
+            config: Default::default(),
+        }
+    }
+
     fn test_ctx(tenant_id: Uuid) -> SecurityContext {
         SecurityContext::builder()
             .subject_tenant_id(tenant_id)
@@ -353,6 +930,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -361,11 +939,44 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
             tags: vec![],
             enabled: true,
         }
     }
 
+    fn apikey_auth(secret_ref: &str, sharing: SharingMode) -> AuthConfig {
+        AuthConfig {
+            plugin_type: crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID.into(),
+            sharing,
+            config: Some(
+                [
+                    ("header".to_string(), "authorization".to_string()),
+                    ("secret_ref".to_string(), secret_ref.to_string()),
+                ]
+                .into(),
+            ),
+        }
+    }
+
+    fn per_minute(rate: u32, sharing: SharingMode) -> RateLimitConfig {
+        RateLimitConfig {
+            sharing,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            sustained: SustainedRate {
+                rate,
+                window: Window::Minute,
+            },
+            burst: None,
+            scope: RateLimitScope::Tenant,
+            strategy: RateLimitStrategy::Reject,
+            cost: 1,
+            queue: None,
+            degrade: None,
+        }
+    }
+
     fn make_create_route(upstream_id: Uuid) -> CreateRouteRequest {
         CreateRouteRequest {
             upstream_id,
@@ -380,6 +991,9 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -450,6 +1064,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 8443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -458,6 +1073,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
             tags: vec![],
             enabled: true,
         };
@@ -505,49 +1122,180 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn duplicate_alias_conflict() {
+    async fn circuit_breaker_rejects_invalid_thresholds() {
         let svc = make_service();
-        let tenant = Uuid::new_v4();
-        let ctx = test_ctx(tenant);
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let mut req = make_create_upstream(Some("cb"));
+        req.circuit_breaker = Some(CircuitBreakerConfig {
+            failure_threshold: 0,
+            ..Default::default()
+        });
+        let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
 
-        svc.create_upstream(&ctx, make_create_upstream(Some("openai")))
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("cb")))
             .await
             .unwrap();
-
         let err = svc
-            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .update_upstream(
+                &ctx,
+                u.id,
+                UpdateUpstreamRequest {
+                    circuit_breaker: Some(CircuitBreakerConfig {
+                        failure_rate_threshold: Some(150),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
             .await
             .unwrap_err();
-        assert!(matches!(err, DomainError::Conflict { .. }));
+        assert!(matches!(err, DomainError::Validation { .. }));
     }
 
     #[tokio::test]
-    async fn route_create_with_wrong_tenant_upstream() {
+    async fn load_balancing_rejects_invalid_settings() {
         let svc = make_service();
-        let t1 = Uuid::new_v4();
-        let t2 = Uuid::new_v4();
-        let ctx1 = test_ctx(t1);
-        let ctx2 = test_ctx(t2);
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let mut req = make_create_upstream(Some("lb"));
+        req.server.endpoints[0].weight = 0;
+        let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+
+        let mut req = make_create_upstream(Some("lb"));
+        req.load_balancing = Some(LoadBalancingConfig {
+            health_check: Some(HealthCheckConfig {
+                path: "health".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
 
         let u = svc
-            .create_upstream(&ctx1, make_create_upstream(Some("openai")))
+            .create_upstream(&ctx, make_create_upstream(Some("lb")))
             .await
             .unwrap();
-
-        // Try to create route in different tenant referencing t1's upstream.
         let err = svc
-            .create_route(&ctx2, make_create_route(u.id))
+            .update_upstream(
+                &ctx,
+                u.id,
+                UpdateUpstreamRequest {
+                    load_balancing: Some(LoadBalancingConfig {
+                        outlier_detection: Some(OutlierDetectionConfig {
+                            max_ejection_percent: 120,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
             .await
             .unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
     }
 
     #[tokio::test]
-    async fn alias_resolution_enabled() {
+    async fn grpc_transcoding_requires_resolvable_method() {
         let svc = make_service();
-        let tenant = Uuid::new_v4();
-        let ctx = test_ctx(tenant);
-
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("grpc")))
+            .await
+            .unwrap();
+        let transcoding = GrpcTranscodingConfig {
+            descriptor_set: crate::test_support::grpc::user_service_descriptor_set(),
+        };
+
+        // HTTP match only.
+        let mut req = make_create_route(u.id);
+        req.grpc_transcoding = Some(transcoding.clone());
+        let err = svc.create_route(&ctx, req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+
+        let mut req = make_create_route(u.id);
+        req.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "example.v1.UserService".into(),
+                method: "GetUser".into(),
+            }),
+        };
+        req.grpc_transcoding = Some(transcoding);
+        let route = svc.create_route(&ctx, req).await.unwrap();
+
+        // Pointing the match at an undefined method is rejected on update.
+        let err = svc
+            .update_route(
+                &ctx,
+                route.id,
+                UpdateRouteRequest {
+                    match_rules: Some(MatchRules {
+                        http: None,
+                        grpc: Some(GrpcMatch {
+                            service: "example.v1.UserService".into(),
+                            method: "DeleteUser".into(),
+                        }),
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::Validation { detail, .. } if detail.contains("DeleteUser"))
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_alias_conflict() {
+        let svc = make_service();
+        let tenant = Uuid::new_v4();
+        let ctx = test_ctx(tenant);
+
+        svc.create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+
+        let err = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict { .. }));
+    }
+
+    #[tokio::test]
+    async fn route_create_with_wrong_tenant_upstream() {
+        let svc = make_service();
+        let t1 = Uuid::new_v4();
+        let t2 = Uuid::new_v4();
+        let ctx1 = test_ctx(t1);
+        let ctx2 = test_ctx(t2);
+
+        let u = svc
+            .create_upstream(&ctx1, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+
+        // Try to create route in different tenant referencing t1's upstream.
+        let err = svc
+            .create_route(&ctx2, make_create_route(u.id))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn alias_resolution_enabled() {
+        let svc = make_service();
+        let tenant = Uuid::new_v4();
+        let ctx = test_ctx(tenant);
+
         let u = svc
             .create_upstream(&ctx, make_create_upstream(Some("openai")))
             .await
@@ -654,4 +1402,534 @@ mod tests {
         // Route should be gone.
         assert!(svc.get_route(&ctx, r.id).await.is_err());
     }
+
+    /// Records the notifications it receives.
+    #[derive(Default)]
+    struct RecordingListener {
+        events: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl ConfigChangeListener for RecordingListener {
+        fn upstream_changed(&self, id: Uuid, alias: &str) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("upstream:{id}:{alias}"));
+        }
+
+        fn route_changed(&self, route_id: Uuid) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("route:{route_id}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_notify_config_listeners() {
+        let listener = Arc::new(RecordingListener::default());
+        let svc = make_service().with_config_listener(listener.clone());
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+        assert!(listener.events.lock().unwrap().is_empty());
+
+        svc.update_route(
+            &ctx,
+            r.id,
+            UpdateRouteRequest {
+                priority: Some(5),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.update_upstream(
+            &ctx,
+            u.id,
+            UpdateUpstreamRequest {
+                alias: Some("openai-v2".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        svc.delete_upstream(&ctx, u.id).await.unwrap();
+
+        assert_eq!(
+            *listener.events.lock().unwrap(),
+            vec![
+                format!("route:{}", r.id),
+                format!("upstream:{}:openai", u.id),
+                format!("upstream:{}:openai-v2", u.id),
+                format!("upstream:{}:openai-v2", u.id),
+                format!("route:{}", r.id),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn response_cache_config_is_validated() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let with_cache = |ttl: u64, vary: &[&str]| CreateRouteRequest {
+            response_cache: Some(ResponseCacheConfig {
+                ttl: std::time::Duration::from_secs(ttl),
+                vary: vary.iter().map(|v| (*v).to_string()).collect(),
+            }),
+            ..make_create_route(u.id)
+        };
+
+        let r = svc
+            .create_route(&ctx, with_cache(60, &["Accept-Language"]))
+            .await
+            .unwrap();
+        assert_eq!(r.response_cache.unwrap().vary, vec!["accept-language"]);
+
+        for req in [with_cache(0, &[]), with_cache(60, &["bad header"])] {
+            let err = svc.create_route(&ctx, req).await.unwrap_err();
+            assert!(
+                matches!(err, DomainError::Validation { .. }),
+                "expected Validation, got {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn usage_extraction_pointers_are_validated() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let with_usage = |input: Option<&str>, output: Option<&str>| CreateRouteRequest {
+            usage_extraction: Some(UsageExtractionConfig {
+                input_tokens: input.map(str::to_owned),
+                output_tokens: output.map(str::to_owned),
+            }),
+            ..make_create_route(u.id)
+        };
+
+        let r = svc
+            .create_route(&ctx, with_usage(Some("/usage/prompt_tokens"), None))
+            .await
+            .unwrap();
+        assert_eq!(
+            r.usage_extraction.unwrap().input_tokens.as_deref(),
+            Some("/usage/prompt_tokens")
+        );
+
+        for req in [
+            with_usage(None, None),
+            with_usage(None, Some("usage.tokens")),
+        ] {
+            let err = svc.create_route(&ctx, req).await.unwrap_err();
+            assert!(
+                matches!(err, DomainError::Validation { .. }),
+                "expected Validation, got {err:?}"
+            );
+        }
+    }
+
+    const GUARD_SOURCE: &str = "def on_request(ctx):\n    return ctx.next()\n";
+
+    #[tokio::test]
+    async fn plugin_create_infers_phases_and_get_round_trips() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let p = svc
+            .create_plugin(&ctx, make_create_plugin(PluginType::Guard, GUARD_SOURCE))
+            .await
+            .unwrap();
+        assert_eq!(p.phases, vec![PluginPhase::OnRequest]);
+        assert_eq!(p.config_schema, serde_json::json!({}));
+        assert_eq!(svc.get_plugin(&ctx, p.id).await.unwrap(), p);
+        assert_eq!(
+            svc.list_plugins(&ctx, &ListQuery::default())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let other = test_ctx(Uuid::new_v4());
+        assert!(matches!(
+            svc.get_plugin(&other, p.id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn plugin_create_rejects_invalid_definitions() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let cases = [
+            make_create_plugin(PluginType::Auth, GUARD_SOURCE),
+            make_create_plugin(
+                PluginType::Guard,
+                "def on_request(ctx):\n    return ctx.next(\n",
+            ),
+            make_create_plugin(PluginType::Transform, "x = 1\n"),
+            make_create_plugin(
+                PluginType::Guard,
+                "def on_response(ctx):\n    return ctx.next()\n",
+            ),
+            CreatePluginRequest {
+                phases: vec![PluginPhase::OnError],
+                ..make_create_plugin(PluginType::Transform, GUARD_SOURCE)
+            },
+            CreatePluginRequest {
+                config_schema: serde_json::json!([]),
+                ..make_create_plugin(PluginType::Guard, GUARD_SOURCE)
+            },
+        ];
+        for req in cases {
+            let err = svc.create_plugin(&ctx, req).await.unwrap_err();
+            assert!(matches!(err, DomainError::Validation { .. }), "{err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn plugin_delete_is_refused_while_referenced() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let p = svc
+            .create_plugin(&ctx, make_create_plugin(PluginType::Guard, GUARD_SOURCE))
+            .await
+            .unwrap();
+        let gts = format_plugin_gts(PluginType::Guard, p.id);
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(
+                &ctx,
+                CreateRouteRequest {
+                    plugins: Some(plugins_config(&[&gts])),
+                    ..make_create_route(u.id)
+                },
+            )
+            .await
+            .unwrap();
+
+        let err = svc.delete_plugin(&ctx, p.id).await.unwrap_err();
+        match err {
+            DomainError::PluginInUse { detail } => {
+                assert!(detail.contains("0 upstream(s) and 1 route(s)"), "{detail}");
+                assert!(detail.contains(&format_route_gts(r.id)), "{detail}");
+            }
+            other => panic!("expected PluginInUse, got {other:?}"),
+        }
+
+        svc.delete_route(&ctx, r.id).await.unwrap();
+        svc.delete_plugin(&ctx, p.id).await.unwrap();
+        assert!(matches!(
+            svc.delete_plugin(&ctx, p.id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn plugin_references_must_match_their_slot() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let err = svc
+            .create_upstream(
+                &ctx,
+                CreateUpstreamRequest {
+                    auth: Some(AuthConfig {
+                        plugin_type: crate::domain::gts_helpers::CORS_GUARD_PLUGIN_ID.into(),
+                        sharing: SharingMode::Private,
+                        config: None,
+                    }),
+                    ..make_create_upstream(Some("openai"))
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("is a guard plugin, expected auth"),
+            "{err}"
+        );
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let err = svc
+            .update_upstream(
+                &ctx,
+                u.id,
+                UpdateUpstreamRequest {
+                    plugins: Some(plugins_config(&[
+                        crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID,
+                    ])),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    // -- Tenant hierarchy --
+
+    #[tokio::test]
+    async fn ancestor_upstream_and_routes_are_inherited() {
+        let (svc, _, partner, customer) = make_tree_service();
+        let u = svc
+            .create_upstream(
+                &test_ctx(partner),
+                CreateUpstreamRequest {
+                    auth: Some(apikey_auth(
+                        "cred://partner-openai-key",
+                        SharingMode::Inherit,
+                    )),
+                    ..make_create_upstream(Some("openai"))
+                },
+            )
+            .await
+            .unwrap();
+        let r = svc
+            .create_route(&test_ctx(partner), make_create_route(u.id))
+            .await
+            .unwrap();
+
+        let ctx = test_ctx(customer);
+        let resolved = svc.resolve_upstream(&ctx, "openai").await.unwrap();
+        assert_eq!(resolved.id, u.id);
+        assert_eq!(resolved.auth, u.auth);
+        let matched = svc
+            .resolve_route(&ctx, resolved.id, "POST", "/v1/chat/completions")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, r.id);
+
+        // Ancestors do not see their descendants' upstreams.
+        let own = svc
+            .create_upstream(&ctx, make_create_upstream(Some("customer-only")))
+            .await
+            .unwrap();
+        assert!(matches!(
+            svc.resolve_upstream(&test_ctx(partner), "customer-only")
+                .await,
+            Err(DomainError::NotFound { .. })
+        ));
+        assert!(matches!(
+            svc.resolve_route(&test_ctx(partner), own.id, "POST", "/v1/chat/completions")
+                .await,
+            Err(DomainError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn descendant_binding_shadows_ancestor_alias() {
+        let (svc, _, partner, customer) = make_tree_service();
+        let parent = svc
+            .create_upstream(&test_ctx(partner), make_create_upstream(Some("openai")))
+            .await
+            .unwrap();
+        let parent_route = svc
+            .create_route(&test_ctx(partner), make_create_route(parent.id))
+            .await
+            .unwrap();
+
+        let mut shadow = make_create_upstream(Some("openai"));
+        shadow.server.endpoints[0].host = "staging.openai.example.com".into();
+        let child = svc
+            .create_upstream(&test_ctx(customer), shadow)
+            .await
+            .unwrap();
+
+        let ctx = test_ctx(customer);
+        let resolved = svc.resolve_upstream(&ctx, "openai").await.unwrap();
+        assert_eq!(resolved.id, child.id);
+        assert_eq!(
+            resolved.server.endpoints[0].host,
+            "staging.openai.example.com"
+        );
+        let resolved = svc
+            .resolve_upstream(&test_ctx(partner), "openai")
+            .await
+            .unwrap();
+        assert_eq!(resolved.id, parent.id);
+
+        // Without routes of its own, the shadowing binding uses the ancestor's.
+        let matched = svc
+            .resolve_route(&ctx, child.id, "POST", "/v1/chat/completions")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, parent_route.id);
+        let own_route = svc
+            .create_route(&ctx, make_create_route(child.id))
+            .await
+            .unwrap();
+        let matched = svc
+            .resolve_route(&ctx, child.id, "POST", "/v1/chat/completions")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, own_route.id);
+
+        // Only the closest binding decides whether the alias is usable.
+        svc.update_upstream(
+            &test_ctx(partner),
+            parent.id,
+            UpdateUpstreamRequest {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(svc.resolve_upstream(&ctx, "openai").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn enforced_rate_limit_survives_shadowing() {
+        let (svc, root, partner, customer) = make_tree_service();
+        svc.create_upstream(
+            &test_ctx(root),
+            CreateUpstreamRequest {
+                rate_limit: Some(per_minute(10, SharingMode::Private)),
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+        svc.create_upstream(
+            &test_ctx(partner),
+            CreateUpstreamRequest {
+                rate_limit: Some(per_minute(100, SharingMode::Enforce)),
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+        svc.create_upstream(
+            &test_ctx(customer),
+            CreateUpstreamRequest {
+                rate_limit: Some(per_minute(1000, SharingMode::Private)),
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+
+        let resolved = svc
+            .resolve_upstream(&test_ctx(customer), "openai")
+            .await
+            .unwrap();
+        let rate_limit = resolved.rate_limit.unwrap();
+        assert_eq!(rate_limit.sustained.rate, 100);
+        assert_eq!(rate_limit.sharing, SharingMode::Enforce);
+    }
+
+    #[tokio::test]
+    async fn ancestor_auth_applies_per_sharing_mode() {
+        let (svc, _, partner, customer) = make_tree_service();
+        let secret_ref = |u: &Upstream| {
+            u.auth
+                .as_ref()
+                .and_then(|a| a.config.as_ref())
+                .and_then(|c| c.get("secret_ref").cloned())
+        };
+        let parent = svc
+            .create_upstream(
+                &test_ctx(partner),
+                CreateUpstreamRequest {
+                    auth: Some(apikey_auth("cred://partner-key", SharingMode::Enforce)),
+                    ..make_create_upstream(Some("openai"))
+                },
+            )
+            .await
+            .unwrap();
+        svc.create_upstream(
+            &test_ctx(customer),
+            CreateUpstreamRequest {
+                auth: Some(apikey_auth("cred://customer-key", SharingMode::Private)),
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+
+        let ctx = test_ctx(customer);
+        let resolved = svc.resolve_upstream(&ctx, "openai").await.unwrap();
+        assert_eq!(secret_ref(&resolved).as_deref(), Some("cred://partner-key"));
+
+        svc.update_upstream(
+            &test_ctx(partner),
+            parent.id,
+            UpdateUpstreamRequest {
+                auth: Some(apikey_auth("cred://partner-key", SharingMode::Private)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let resolved = svc.resolve_upstream(&ctx, "openai").await.unwrap();
+        assert_eq!(
+            secret_ref(&resolved).as_deref(),
+            Some("cred://customer-key")
+        );
+    }
+
+    #[tokio::test]
+    async fn plugin_chains_are_layered_down_the_tree() {
+        use crate::domain::gts_helpers::{
+            LOGGING_TRANSFORM_PLUGIN_ID, METRICS_TRANSFORM_PLUGIN_ID,
+        };
+
+        let (svc, _, partner, customer) = make_tree_service();
+        svc.create_upstream(
+            &test_ctx(partner),
+            CreateUpstreamRequest {
+                plugins: Some(PluginsConfig {
+                    sharing: SharingMode::Inherit,
+                    ..plugins_config(&[LOGGING_TRANSFORM_PLUGIN_ID])
+                }),
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+        svc.create_upstream(
+            &test_ctx(customer),
+            CreateUpstreamRequest {
+                plugins: Some(plugins_config(&[METRICS_TRANSFORM_PLUGIN_ID])),
+                tags: vec!["customer".into()],
+                ..make_create_upstream(Some("openai"))
+            },
+        )
+        .await
+        .unwrap();
+
+        let resolved = svc
+            .resolve_upstream(&test_ctx(customer), "openai")
+            .await
+            .unwrap();
+        assert_eq!(
+            resolved.plugins.unwrap().items,
+            vec![LOGGING_TRANSFORM_PLUGIN_ID, METRICS_TRANSFORM_PLUGIN_ID]
+        );
+        assert_eq!(resolved.tags, vec!["customer"]);
+    }
 }
@@ -9,9 +9,10 @@ use oagw_sdk::Body;
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
+use crate::domain::hierarchy::EffectiveUpstream;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    CircuitBreakerStatus, CreatePluginRequest, CreateRouteRequest, CreateUpstreamRequest,
+    CustomPlugin, ListQuery, Route, UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
 
 /// Internal Control Plane service trait — configuration management and resolution.
@@ -68,6 +69,29 @@ pub(crate) trait ControlPlaneService: Send + Sync {
 
     async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError>;
 
+    // -- Custom plugins (immutable: no update) --
+
+    async fn create_plugin(
+        &self,
+        ctx: &SecurityContext,
+        req: CreatePluginRequest,
+    ) -> Result<CustomPlugin, DomainError>;
+
+    async fn get_plugin(
+        &self,
+        ctx: &SecurityContext,
+        id: Uuid,
+    ) -> Result<CustomPlugin, DomainError>;
+
+    async fn list_plugins(
+        &self,
+        ctx: &SecurityContext,
+        query: &ListQuery,
+    ) -> Result<Vec<CustomPlugin>, DomainError>;
+
+    /// Fails with `PluginInUse` while any upstream or route references the plugin.
+    async fn delete_plugin(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError>;
+
     // -- Resolution --
 
     async fn resolve_upstream(
@@ -76,6 +100,14 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         alias: &str,
     ) -> Result<Upstream, DomainError>;
 
+    /// Upstream bound to `alias` for the caller, with what the data plane
+    /// needs to serve it. The tenant chain is walked once.
+    async fn resolve_effective_upstream(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+    ) -> Result<EffectiveUpstream, DomainError>;
+
     async fn resolve_route(
         &self,
         ctx: &SecurityContext,
@@ -83,6 +115,22 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         method: &str,
         path: &str,
     ) -> Result<Route, DomainError>;
+
+    /// Route matching `method` and `path` along the bindings of `upstream`.
+    async fn match_route(
+        &self,
+        upstream: &EffectiveUpstream,
+        method: &str,
+        path: &str,
+    ) -> Result<Route, DomainError>;
+
+    /// Custom plugin `id` of `tenant_id`, for references made by that
+    /// tenant's configuration.
+    async fn get_owned_plugin(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<CustomPlugin, DomainError>;
 }
 
 /// Internal Data Plane service trait — proxy orchestration and plugin execution.
@@ -93,4 +141,19 @@ pub(crate) trait DataPlaneService: Send + Sync {
         ctx: SecurityContext,
         req: http::Request<Body>,
     ) -> Result<http::Response<Body>, DomainError>;
+
+    /// Circuit breaker state for each circuit of `upstream`.
+    fn circuit_breaker_status(&self, upstream: &Upstream) -> Vec<CircuitBreakerStatus>;
+}
+
+/// Observer of Control Plane writes. Implemented by Data Plane state derived
+/// from configuration (e.g. cached upstream responses, endpoint pools) that
+/// must not outlive it.
+pub(crate) trait ConfigChangeListener: Send + Sync {
+    /// The upstream `id`, bound to `alias`, was updated or deleted. State
+    /// shared by every tenant serving `alias` is keyed by the alias.
+    fn upstream_changed(&self, id: Uuid, alias: &str);
+
+    /// The route `route_id` was updated or deleted.
+    fn route_changed(&self, route_id: Uuid);
 }
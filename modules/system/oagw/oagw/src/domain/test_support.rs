@@ -3,25 +3,61 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::domain::circuit_breaker::CircuitBreakerRegistry;
 use crate::domain::credential::CredentialResolver;
+use crate::domain::load_balancer::LoadBalancer;
+use crate::domain::rate_limit::RateLimiter;
 use modkit::client_hub::ClientHub;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use modkit_http::HttpClientConfig;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use sea_orm_migration::MigratorTrait;
 
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, ServiceGatewayClientV1Facade,
 };
+use crate::infra::plugin::{OAuth2TokenCache, StarlarkRuntime};
 use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryCredentialResolver, InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::proxy::response_cache::ResponseCache;
+use crate::infra::storage::migrations::Migrator;
+use crate::infra::storage::{
+    InMemoryCredentialResolver, SeaOrmPluginRepo, SeaOrmRouteRepo, SeaOrmUpstreamRepo,
+};
+use crate::infra::tenant_hierarchy::InMemoryTenantHierarchy;
+use crate::infra::usage::InMemoryUsageAggregator;
 
 /// Re-export for tests that need to set credentials after creation.
 pub use crate::infra::storage::credential_repo::InMemoryCredentialResolver as TestCredentialResolver;
 
 /// Re-export plugin ID constants for test configurations.
-pub use crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID;
+pub use crate::domain::gts_helpers::{
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID,
+};
+
+/// Open a fresh in-memory SQLite database with the OAGW schema applied.
+///
+/// The pool is pinned to a single connection so every query sees the same
+/// in-memory database.
+pub(crate) async fn sqlite_test_db() -> Arc<DBProvider<DbError>> {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("failed to open in-memory SQLite database");
+    run_migrations_for_testing(&db, Migrator::migrations())
+        .await
+        .expect("failed to run OAGW migrations");
+    Arc::new(DBProvider::new(db))
+}
 
 /// Builder for a fully-wired Control Plane test environment.
 pub struct TestCpBuilder {
     credentials: Vec<(String, String)>,
+    tenants: Option<Arc<InMemoryTenantHierarchy>>,
 }
 
 impl TestCpBuilder {
@@ -29,9 +65,18 @@ impl TestCpBuilder {
     pub fn new() -> Self {
         Self {
             credentials: Vec::new(),
+            tenants: None,
         }
     }
 
+    /// Resolve tenant ancestry from `tenants` instead of treating every
+    /// tenant as a root.
+    #[must_use]
+    pub(crate) fn with_tenant_hierarchy(mut self, tenants: Arc<InMemoryTenantHierarchy>) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
     /// Pre-load credentials into the credential resolver.
     #[must_use]
     pub fn with_credentials(mut self, creds: Vec<(String, String)>) -> Self {
@@ -39,19 +84,44 @@ impl TestCpBuilder {
         self
     }
 
-    /// Create repos, service, and credential resolver, register them in the
-    /// provided `ClientHub`, and return the CP service trait object.
-    pub(crate) fn build_and_register(self, hub: &ClientHub) -> Arc<dyn ControlPlaneService> {
-        let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
-        let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+    /// Create SQLite-backed repos, service, and credential resolver, register
+    /// them in the provided `ClientHub`, and return the CP service trait object.
+    pub(crate) async fn build_and_register(self, hub: &ClientHub) -> Arc<dyn ControlPlaneService> {
+        let db = sqlite_test_db().await;
+        let upstream_repo = Arc::new(SeaOrmUpstreamRepo::new(db.clone()));
+        let route_repo = Arc::new(SeaOrmRouteRepo::new(db.clone()));
+        let plugin_repo = Arc::new(SeaOrmPluginRepo::new(db));
+        let response_cache = Arc::new(ResponseCache::default());
+        let circuit_breakers = Arc::new(CircuitBreakerRegistry::new());
+        let load_balancer = Arc::new(LoadBalancer::new());
+        let rate_limiter = Arc::new(RateLimiter::new());
+        let oauth2_tokens = Arc::new(OAuth2TokenCache::new());
+        let mut svc = ControlPlaneServiceImpl::new(
+            upstream_repo,
+            route_repo,
+            plugin_repo,
+            Arc::new(StarlarkRuntime::new()),
+        )
+        .with_config_listener(response_cache.clone())
+        .with_config_listener(circuit_breakers.clone())
+        .with_config_listener(load_balancer.clone())
+        .with_config_listener(rate_limiter.clone())
+        .with_config_listener(oauth2_tokens.clone());
+        if let Some(tenants) = self.tenants {
+            svc = svc.with_tenant_hierarchy(tenants);
+        }
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(svc);
 
         let cred_resolver: Arc<dyn CredentialResolver> = Arc::new(
             InMemoryCredentialResolver::with_credentials(self.credentials),
         );
 
         hub.register::<dyn CredentialResolver>(cred_resolver);
+        hub.register::<ResponseCache>(response_cache);
+        hub.register::<CircuitBreakerRegistry>(circuit_breakers);
+        hub.register::<LoadBalancer>(load_balancer);
+        hub.register::<RateLimiter>(rate_limiter);
+        hub.register::<OAuth2TokenCache>(oauth2_tokens);
 
         cp
     }
@@ -66,9 +136,13 @@ impl Default for TestCpBuilder {
 /// Builder for a fully-wired Data Plane test environment.
 ///
 /// Requires that a `CredentialResolver` is already registered in the
-/// `ClientHub` (e.g., via `TestCpBuilder`).
+/// `ClientHub` (e.g., via `TestCpBuilder`). A `ResponseCache`,
+/// `CircuitBreakerRegistry`, `LoadBalancer`, `RateLimiter` and `OAuth2TokenCache`
+/// registered there are shared with the control plane that resets them. The usage
+/// aggregate the data plane records into is registered in the hub.
 pub struct TestDpBuilder {
     request_timeout: Option<Duration>,
+    websocket_idle_timeout: Option<Duration>,
 }
 
 impl TestDpBuilder {
@@ -76,6 +150,7 @@ impl TestDpBuilder {
     pub fn new() -> Self {
         Self {
             request_timeout: None,
+            websocket_idle_timeout: None,
         }
     }
 
@@ -86,6 +161,13 @@ impl TestDpBuilder {
         self
     }
 
+    /// Override the WebSocket idle timeout (useful for idle-close tests).
+    #[must_use]
+    pub fn with_websocket_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.websocket_idle_timeout = Some(timeout);
+        self
+    }
+
     /// Fetch CredentialResolver from the hub, create a DP service with
     /// the given CP, and return the trait object.
     pub(crate) fn build_and_register(
@@ -97,11 +179,34 @@ impl TestDpBuilder {
             .get::<dyn CredentialResolver>()
             .expect("CredentialResolver must be registered before building DP");
 
+        // Token endpoints in tests are plain-HTTP mock routes.
         let mut svc = DataPlaneServiceImpl::new(cp, cred_resolver)
-            .expect("failed to build DataPlaneServiceImpl in test");
+            .expect("failed to build DataPlaneServiceImpl in test")
+            .with_token_http_config(HttpClientConfig::for_testing());
+        if let Ok(cache) = hub.get::<ResponseCache>() {
+            svc = svc.with_response_cache(cache);
+        }
+        if let Ok(circuit_breakers) = hub.get::<CircuitBreakerRegistry>() {
+            svc = svc.with_circuit_breakers(circuit_breakers);
+        }
+        if let Ok(load_balancer) = hub.get::<LoadBalancer>() {
+            svc = svc.with_load_balancer(load_balancer);
+        }
+        if let Ok(rate_limiter) = hub.get::<RateLimiter>() {
+            svc = svc.with_rate_limiter(rate_limiter);
+        }
+        if let Ok(tokens) = hub.get::<OAuth2TokenCache>() {
+            svc = svc.with_oauth2_tokens(tokens);
+        }
+        let usage = Arc::new(InMemoryUsageAggregator::default());
+        hub.register::<InMemoryUsageAggregator>(usage.clone());
+        svc = svc.with_usage_sink(usage);
         if let Some(timeout) = self.request_timeout {
             svc = svc.with_request_timeout(timeout);
         }
+        if let Some(timeout) = self.websocket_idle_timeout {
+            svc = svc.with_websocket_idle_timeout(timeout);
+        }
 
         Arc::new(svc)
     }
@@ -125,13 +230,16 @@ pub struct TestAppState {
 /// Use `result.state` when constructing an axum test router and
 /// `result.facade` when you need to create data programmatically
 /// (e.g. `facade.create_upstream(…)`).
-pub fn build_test_app_state(
+pub async fn build_test_app_state(
     hub: &ClientHub,
     cp_builder: TestCpBuilder,
     dp_builder: TestDpBuilder,
 ) -> TestAppState {
-    let cp = cp_builder.build_and_register(hub);
+    let cp = cp_builder.build_and_register(hub).await;
     let dp = dp_builder.build_and_register(hub, cp.clone());
+    let usage = hub
+        .get::<InMemoryUsageAggregator>()
+        .expect("usage aggregate is registered with the data plane");
     let facade: Arc<dyn ServiceGatewayClientV1> =
         Arc::new(ServiceGatewayClientV1Facade::new(cp.clone(), dp.clone()));
     hub.register::<dyn ServiceGatewayClientV1>(facade.clone());
@@ -139,6 +247,7 @@ pub fn build_test_app_state(
         state: crate::module::AppState {
             cp,
             dp,
+            usage,
             config: crate::config::RuntimeConfig {
                 max_body_size_bytes: 100 * 1024 * 1024, // 100 MB default for tests
             },
@@ -149,12 +258,12 @@ pub fn build_test_app_state(
 
 /// Build a fully wired `ServiceGatewayClientV1` facade for integration tests.
 /// Returns the facade registered in `client_hub`.
-pub fn build_test_gateway(
+pub async fn build_test_gateway(
     hub: &ClientHub,
     cp_builder: TestCpBuilder,
     dp_builder: TestDpBuilder,
 ) -> Arc<dyn ServiceGatewayClientV1> {
-    let cp = cp_builder.build_and_register(hub);
+    let cp = cp_builder.build_and_register(hub).await;
     let dp = dp_builder.build_and_register(hub, cp.clone());
     let oagw: Arc<dyn ServiceGatewayClientV1> = Arc::new(ServiceGatewayClientV1Facade::new(cp, dp));
     hub.register::<dyn ServiceGatewayClientV1>(oagw.clone());
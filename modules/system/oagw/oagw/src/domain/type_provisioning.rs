@@ -1,7 +1,7 @@
 //! Trait for reading upstreams and routes from the Types Registry.
 //!
 //! During `post_init()`, OAGW reads GTS instances registered by other modules
-//! and materializes them into the in-memory upstream/route repositories.
+//! and materializes them into the upstream/route repositories.
 
 use async_trait::async_trait;
 use modkit_macros::domain_model;
@@ -30,7 +30,7 @@ pub struct ProvisionedRoute {
 ///
 /// Other modules register upstream/route instances during `init()`.
 /// OAGW calls these methods during `post_init()` to discover and
-/// materialize them into the in-memory repositories.
+/// materialize them into the repositories.
 #[async_trait]
 pub trait TypeProvisioningService: Send + Sync {
     /// List all upstream instances registered in the types-registry.
@@ -0,0 +1,123 @@
+//! Usage metering of proxied traffic.
+//!
+//! The data plane emits one [`UsageRecord`] per exchange on a resolved route
+//! to every configured [`UsageSink`]. Sinks that keep what they receive can
+//! answer [`UsageReport`] queries, which back chargeback and budgets.
+
+use std::time::Duration;
+
+use modkit_macros::domain_model;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// One exchange on a route, recorded once the response has been relayed to
+/// the client (or the client went away).
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageRecord {
+    pub tenant_id: Uuid,
+    pub subject_id: Uuid,
+    /// The upstream that served the request; the fallback upstream when the
+    /// request was degraded to one.
+    pub upstream_id: Uuid,
+    pub route_id: Uuid,
+    pub status: u16,
+    /// From the arrival of the request until the last response byte.
+    pub latency: Duration,
+    /// Body bytes received from the client.
+    pub request_bytes: u64,
+    /// Body bytes relayed to the client.
+    pub response_bytes: u64,
+    /// Token counts reported by the upstream, per the route's
+    /// `usage_extraction`.
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    /// Answered from the response cache without calling the upstream.
+    pub cache_hit: bool,
+    /// Degraded by a rate limit: answered with the configured fallback
+    /// response, or served by the fallback upstream.
+    pub degraded: bool,
+    pub completed_at: OffsetDateTime,
+}
+
+/// Destination of usage records.
+///
+/// `record` runs on the data path when a response completes, so it must not
+/// block; sinks that ship records elsewhere should queue them.
+pub trait UsageSink: Send + Sync {
+    fn record(&self, record: &UsageRecord);
+}
+
+/// Filter of a usage query. Bounds are inclusive of `from` and exclusive of
+/// `to`; the aggregate resolves them to whole minutes.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageQuery {
+    pub from: Option<OffsetDateTime>,
+    pub to: Option<OffsetDateTime>,
+    pub upstream_id: Option<Uuid>,
+    pub route_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    /// Report each subject separately instead of one total per route.
+    pub group_by_subject: bool,
+}
+
+/// Usage of one route, or of one subject on a route, over a query window.
+#[domain_model]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub upstream_id: Uuid,
+    pub route_id: Uuid,
+    /// Set when the query groups by subject.
+    pub subject_id: Option<Uuid>,
+    pub requests: u64,
+    /// Responses with a 4xx or 5xx status.
+    pub errors: u64,
+    pub cache_hits: u64,
+    /// Requests degraded by a rate limit.
+    pub degraded: u64,
+    pub request_bytes: u64,
+    pub response_bytes: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_latency: Duration,
+}
+
+impl UsageTotals {
+    pub(crate) fn add(&mut self, record: &UsageRecord) {
+        self.requests += 1;
+        if record.status >= 400 {
+            self.errors += 1;
+        }
+        if record.cache_hit {
+            self.cache_hits += 1;
+        }
+        if record.degraded {
+            self.degraded += 1;
+        }
+        self.request_bytes += record.request_bytes;
+        self.response_bytes += record.response_bytes;
+        self.input_tokens += record.input_tokens.unwrap_or(0);
+        self.output_tokens += record.output_tokens.unwrap_or(0);
+        self.total_latency += record.latency;
+    }
+
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.cache_hits += other.cache_hits;
+        self.degraded += other.degraded;
+        self.request_bytes += other.request_bytes;
+        self.response_bytes += other.response_bytes;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.total_latency += other.total_latency;
+    }
+}
+
+/// Read side of a sink that aggregates usage.
+pub trait UsageReport: Send + Sync {
+    /// Usage of `tenant_id` matching `query`, ordered by upstream, route and
+    /// subject.
+    fn totals(&self, tenant_id: Uuid, query: &UsageQuery) -> Vec<UsageTotals>;
+}
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use credential_resolver_sdk::{CredentialResolverClient, CredentialResolverError, SecretRef};
+use modkit::client_hub::ClientHub;
+use modkit_security::SecurityContext;
+
+use crate::domain::credential::{CredentialError, CredentialResolver, SecretValue, SecretVersion};
+use crate::infra::storage::InMemoryCredentialResolver;
+
+/// Secret resolution backed by the credential-resolver module.
+///
+/// The client is looked up in the hub on every call. References the module
+/// does not know fall back to the credentials seeded from OAGW configuration,
+/// as does every reference when no credential resolver (or storage plugin)
+/// is deployed, so development setups keep working without a secret store.
+/// A reference the module denies is never served from the seed.
+pub struct CredentialStoreResolver {
+    hub: Arc<ClientHub>,
+    seeded: InMemoryCredentialResolver,
+}
+
+impl CredentialStoreResolver {
+    #[must_use]
+    pub fn new(hub: Arc<ClientHub>, seeded: InMemoryCredentialResolver) -> Self {
+        Self { hub, seeded }
+    }
+}
+
+/// Whether a reference the module failed to resolve is looked up in the
+/// seeded credentials instead.
+fn falls_back(e: &CredentialResolverError) -> bool {
+    matches!(
+        e,
+        CredentialResolverError::NotFound { .. } | CredentialResolverError::NoPluginAvailable
+    )
+}
+
+fn to_credential_error(secret_ref: &str, e: &CredentialResolverError) -> CredentialError {
+    match e {
+        CredentialResolverError::AccessDenied { .. } => {
+            CredentialError::AccessDenied(secret_ref.to_string())
+        }
+        e => CredentialError::Internal(format!("failed to resolve {secret_ref}: {e}")),
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialResolver for CredentialStoreResolver {
+    async fn resolve(
+        &self,
+        ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretValue, CredentialError> {
+        let Ok(client) = self.hub.get::<dyn CredentialResolverClient>() else {
+            return self.seeded.resolve(ctx, secret_ref).await;
+        };
+        let Ok(reference) = SecretRef::parse(secret_ref) else {
+            return self.seeded.resolve(ctx, secret_ref).await;
+        };
+        match client.resolve(ctx, &reference).await {
+            Ok(secret) => Ok(SecretValue::new(secret.value.expose().to_owned())),
+            Err(e) if falls_back(&e) => self.seeded.resolve(ctx, secret_ref).await,
+            Err(e) => Err(to_credential_error(secret_ref, &e)),
+        }
+    }
+
+    async fn version(
+        &self,
+        ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretVersion, CredentialError> {
+        let Ok(client) = self.hub.get::<dyn CredentialResolverClient>() else {
+            return self.seeded.version(ctx, secret_ref).await;
+        };
+        let Ok(reference) = SecretRef::parse(secret_ref) else {
+            return self.seeded.version(ctx, secret_ref).await;
+        };
+        match client.resolve_metadata(ctx, &reference).await {
+            Ok(metadata) => Ok(SecretVersion::new(format!(
+                "{}@{}",
+                metadata.tenant_id,
+                metadata.updated_at.unix_timestamp_nanos()
+            ))),
+            Err(e) if falls_back(&e) => self.seeded.version(ctx, secret_ref).await,
+            Err(e) => Err(to_credential_error(secret_ref, &e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use credential_resolver_sdk::{
+        NewSecret, ResolvedSecret, SecretMetadata, SecretUpdate, SharingMode,
+    };
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Credential resolver client that knows a single reference.
+    struct OneSecret {
+        reference: &'static str,
+        outcome: fn(&str) -> Result<ResolvedSecret, CredentialResolverError>,
+    }
+
+    fn resolved(value: &str) -> Result<ResolvedSecret, CredentialResolverError> {
+        Ok(ResolvedSecret {
+            value: credential_resolver_sdk::SecretValue::new(value),
+            owner_tenant_id: Uuid::nil(),
+            sharing: SharingMode::Tenant,
+            is_inherited: false,
+        })
+    }
+
+    fn denied(reference: &str) -> Result<ResolvedSecret, CredentialResolverError> {
+        Err(CredentialResolverError::AccessDenied {
+            reference: reference.to_owned(),
+        })
+    }
+
+    fn not_found(reference: &SecretRef) -> CredentialResolverError {
+        CredentialResolverError::NotFound {
+            reference: reference.to_string(),
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CredentialResolverClient for OneSecret {
+        async fn resolve(
+            &self,
+            _ctx: &SecurityContext,
+            reference: &SecretRef,
+        ) -> Result<ResolvedSecret, CredentialResolverError> {
+            if reference.as_str() == self.reference {
+                (self.outcome)(reference.as_str())
+            } else {
+                Err(not_found(reference))
+            }
+        }
+
+        async fn resolve_metadata(
+            &self,
+            ctx: &SecurityContext,
+            reference: &SecretRef,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            let secret = self.resolve(ctx, reference).await?;
+            Ok(SecretMetadata {
+                reference: reference.clone(),
+                tenant_id: secret.owner_tenant_id,
+                owner_id: Uuid::nil(),
+                sharing: secret.sharing,
+                created_at: OffsetDateTime::UNIX_EPOCH,
+                updated_at: OffsetDateTime::UNIX_EPOCH,
+            })
+        }
+
+        async fn create_secret(
+            &self,
+            _ctx: &SecurityContext,
+            secret: NewSecret,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            Err(not_found(&secret.reference))
+        }
+
+        async fn update_secret(
+            &self,
+            _ctx: &SecurityContext,
+            reference: &SecretRef,
+            _update: SecretUpdate,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            Err(not_found(reference))
+        }
+
+        async fn get_secret_metadata(
+            &self,
+            _ctx: &SecurityContext,
+            reference: &SecretRef,
+        ) -> Result<SecretMetadata, CredentialResolverError> {
+            Err(not_found(reference))
+        }
+
+        async fn list_secrets(
+            &self,
+            _ctx: &SecurityContext,
+        ) -> Result<Vec<SecretMetadata>, CredentialResolverError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete_secret(
+            &self,
+            _ctx: &SecurityContext,
+            reference: &SecretRef,
+        ) -> Result<(), CredentialResolverError> {
+            Err(not_found(reference))
+        }
+    }
+
+    fn seeded() -> InMemoryCredentialResolver {
+        let seeded = InMemoryCredentialResolver::new();
+        seeded.set("cred://dev-key".into(), "from-config".into());
+        seeded
+    }
+
+    fn store_with(client: OneSecret) -> CredentialStoreResolver {
+        let hub = Arc::new(ClientHub::new());
+        hub.register::<dyn CredentialResolverClient>(Arc::new(client));
+        CredentialStoreResolver::new(hub, seeded())
+    }
+
+    #[tokio::test]
+    async fn without_client_resolves_seeded_credentials() {
+        let store = CredentialStoreResolver::new(Arc::new(ClientHub::new()), seeded());
+        let ctx = SecurityContext::anonymous();
+
+        let secret = store.resolve(&ctx, "cred://dev-key").await.unwrap();
+        assert_eq!(secret.as_str(), "from-config");
+        assert!(matches!(
+            store.resolve(&ctx, "cred://missing").await,
+            Err(CredentialError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn store_value_wins_and_unknown_references_fall_back() {
+        let store = store_with(OneSecret {
+            reference: "cred://dev-key",
+            outcome: |_| resolved("from-store"),
+        });
+        let ctx = SecurityContext::anonymous();
+
+        let secret = store.resolve(&ctx, "cred://dev-key").await.unwrap();
+        assert_eq!(secret.as_str(), "from-store");
+        assert!(matches!(
+            store.resolve(&ctx, "cred://missing").await,
+            Err(CredentialError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn denied_reference_is_not_served_from_seed() {
+        let store = store_with(OneSecret {
+            reference: "cred://dev-key",
+            outcome: denied,
+        });
+
+        assert!(matches!(
+            store
+                .resolve(&SecurityContext::anonymous(), "cred://dev-key")
+                .await,
+            Err(CredentialError::AccessDenied(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn versions_follow_the_source_of_the_value() {
+        let store = store_with(OneSecret {
+            reference: "cred://store-key",
+            outcome: |_| resolved("from-store"),
+        });
+        let ctx = SecurityContext::anonymous();
+
+        let stored = store.version(&ctx, "cred://store-key").await.unwrap();
+        let seeded = store.version(&ctx, "cred://dev-key").await.unwrap();
+        assert_ne!(stored, seeded);
+        assert_eq!(store.version(&ctx, "cred://dev-key").await.unwrap(), seeded);
+        assert!(matches!(
+            store.version(&ctx, "cred://missing").await,
+            Err(CredentialError::NotFound(_))
+        ));
+    }
+}
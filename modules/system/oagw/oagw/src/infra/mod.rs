@@ -1,4 +1,8 @@
+pub(crate) mod credential_store;
 pub(crate) mod plugin;
 pub(crate) mod proxy;
+pub(crate) mod serde_base64;
 pub(crate) mod storage;
+pub(crate) mod tenant_hierarchy;
 pub(crate) mod type_provisioning;
+pub(crate) mod usage;
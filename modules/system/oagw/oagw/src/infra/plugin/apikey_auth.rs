@@ -4,6 +4,8 @@ use crate::domain::credential::CredentialResolver;
 use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
 use serde::Deserialize;
 
+use super::{parse_config, resolve_secret};
+
 /// Configuration for the API key auth plugin.
 #[derive(Debug, Deserialize)]
 struct ApiKeyConfig {
@@ -33,17 +35,13 @@ impl ApiKeyAuthPlugin {
 #[async_trait::async_trait]
 impl AuthPlugin for ApiKeyAuthPlugin {
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
-        let config: ApiKeyConfig = serde_json::from_value(
-            serde_json::to_value(&ctx.config)
-                .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?,
+        let config: ApiKeyConfig = parse_config("apikey", &ctx.config)?;
+        let secret = resolve_secret(
+            self.credential_resolver.as_ref(),
+            &ctx.security_context,
+            &config.secret_ref,
         )
-        .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?;
-
-        let secret = self
-            .credential_resolver
-            .resolve(&config.secret_ref)
-            .await
-            .map_err(|_| PluginError::SecretNotFound(config.secret_ref.clone()))?;
+        .await?;
 
         let value = format!("{}{}", config.prefix, secret.as_str());
         ctx.headers.insert(config.header.to_lowercase(), value);
@@ -57,8 +55,11 @@ mod tests {
     use std::collections::HashMap;
     use std::sync::Arc;
 
+    use crate::domain::credential::{CredentialError, SecretValue, SecretVersion};
     use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
     use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
 
     use super::*;
 
@@ -79,6 +80,9 @@ mod tests {
         let plugin = ApiKeyAuthPlugin::new(creds);
 
         let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
             headers: HashMap::new(),
             config: make_config("authorization", "Bearer ", "cred://openai-key"),
         };
@@ -99,6 +103,9 @@ mod tests {
         let plugin = ApiKeyAuthPlugin::new(creds);
 
         let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
             headers: HashMap::new(),
             config: make_config("x-api-key", "", "cred://custom-key"),
         };
@@ -113,6 +120,9 @@ mod tests {
         let plugin = ApiKeyAuthPlugin::new(creds);
 
         let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
             headers: HashMap::new(),
             config: make_config("authorization", "Bearer ", "cred://missing"),
         };
@@ -120,4 +130,40 @@ mod tests {
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
         assert!(matches!(err, PluginError::SecretNotFound(_)));
     }
+
+    #[tokio::test]
+    async fn secret_access_denied_fails_authentication() {
+        struct DenyAll;
+
+        #[async_trait::async_trait]
+        impl CredentialResolver for DenyAll {
+            async fn resolve(
+                &self,
+                _ctx: &SecurityContext,
+                secret_ref: &str,
+            ) -> Result<SecretValue, CredentialError> {
+                Err(CredentialError::AccessDenied(secret_ref.to_string()))
+            }
+
+            async fn version(
+                &self,
+                _ctx: &SecurityContext,
+                secret_ref: &str,
+            ) -> Result<SecretVersion, CredentialError> {
+                Err(CredentialError::AccessDenied(secret_ref.to_string()))
+            }
+        }
+
+        let plugin = ApiKeyAuthPlugin::new(Arc::new(DenyAll));
+        let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::new(),
+            config: make_config("authorization", "Bearer ", "cred://partner-only/secret"),
+        };
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)));
+    }
 }
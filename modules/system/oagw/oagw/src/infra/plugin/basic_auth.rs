@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+
+use crate::domain::credential::CredentialResolver;
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+use super::{parse_config, resolve_secret};
+
+/// Configuration for the Basic auth plugin.
+#[derive(Debug, Deserialize)]
+struct BasicAuthConfig {
+    /// Secret reference holding the username (e.g. "cred://legacy/basic/username").
+    username_ref: String,
+    /// Secret reference holding the password.
+    password_ref: String,
+}
+
+/// Auth plugin that sends `Authorization: Basic base64(username:password)`.
+pub struct BasicAuthPlugin {
+    credential_resolver: Arc<dyn CredentialResolver>,
+}
+
+impl BasicAuthPlugin {
+    #[must_use]
+    pub fn new(credential_resolver: Arc<dyn CredentialResolver>) -> Self {
+        Self {
+            credential_resolver,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthPlugin for BasicAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config: BasicAuthConfig = parse_config("basic", &ctx.config)?;
+        let resolver = self.credential_resolver.as_ref();
+        let username =
+            resolve_secret(resolver, &ctx.security_context, &config.username_ref).await?;
+        let password =
+            resolve_secret(resolver, &ctx.security_context, &config.password_ref).await?;
+
+        let encoded = STANDARD.encode(format!("{}:{}", username.as_str(), password.as_str()));
+        ctx.headers
+            .insert("authorization".into(), format!("Basic {encoded}"));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn make_ctx() -> AuthContext {
+        AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::from([("authorization".into(), "Bearer tenant-token".into())]),
+            config: HashMap::from([
+                ("username_ref".into(), "cred://legacy/user".into()),
+                ("password_ref".into(), "cred://legacy/pass".into()),
+            ]),
+        }
+    }
+
+    #[tokio::test]
+    async fn injects_basic_credentials() {
+        let creds = Arc::new(InMemoryCredentialResolver::with_credentials(vec![
+            ("cred://legacy/user".into(), "Aladdin".into()),
+            ("cred://legacy/pass".into(), "open sesame".into()),
+        ]));
+        let plugin = BasicAuthPlugin::new(creds);
+
+        let mut ctx = make_ctx();
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_password_returns_error() {
+        let creds = Arc::new(InMemoryCredentialResolver::with_credentials(vec![(
+            "cred://legacy/user".into(),
+            "Aladdin".into(),
+        )]));
+        let plugin = BasicAuthPlugin::new(creds);
+
+        let mut ctx = make_ctx();
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(ref s) if s == "cred://legacy/pass"));
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::domain::credential::CredentialResolver;
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+use super::{parse_config, resolve_secret};
+
+/// Configuration for the bearer token auth plugin.
+#[derive(Debug, Deserialize)]
+struct BearerAuthConfig {
+    /// Secret reference holding a static token (e.g. "cred://api/static-bearer-token").
+    secret_ref: String,
+}
+
+/// Auth plugin that sends a stored service token as `Authorization: Bearer`.
+pub struct BearerAuthPlugin {
+    credential_resolver: Arc<dyn CredentialResolver>,
+}
+
+impl BearerAuthPlugin {
+    #[must_use]
+    pub fn new(credential_resolver: Arc<dyn CredentialResolver>) -> Self {
+        Self {
+            credential_resolver,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthPlugin for BearerAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config: BearerAuthConfig = parse_config("bearer", &ctx.config)?;
+        let token = resolve_secret(
+            self.credential_resolver.as_ref(),
+            &ctx.security_context,
+            &config.secret_ref,
+        )
+        .await?;
+
+        ctx.headers
+            .insert("authorization".into(), format!("Bearer {}", token.as_str()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn replaces_inbound_authorization() {
+        let creds = Arc::new(InMemoryCredentialResolver::with_credentials(vec![(
+            "cred://api/token".into(),
+            "svc-token".into(),
+        )]));
+        let plugin = BearerAuthPlugin::new(creds);
+
+        let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::from([("authorization".into(), "Bearer tenant-token".into())]),
+            config: HashMap::from([("secret_ref".into(), "cred://api/token".into())]),
+        };
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Bearer svc-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_config_is_internal_error() {
+        let plugin = BearerAuthPlugin::new(Arc::new(InMemoryCredentialResolver::new()));
+
+        let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::new(),
+            config: HashMap::new(),
+        };
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+}
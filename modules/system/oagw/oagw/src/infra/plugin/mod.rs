@@ -1,5 +1,70 @@
 pub(crate) mod apikey_auth;
+pub(crate) mod basic_auth;
+pub(crate) mod bearer_auth;
 pub(crate) mod noop_auth;
+pub(crate) mod oauth2_client_cred;
 pub(crate) mod registry;
+pub(crate) mod starlark_runtime;
 
+pub(crate) use oauth2_client_cred::OAuth2TokenCache;
 pub(crate) use registry::AuthPluginRegistry;
+pub(crate) use starlark_runtime::{SandboxLimits, StarlarkRuntime};
+
+use std::collections::HashMap;
+
+use modkit_security::SecurityContext;
+use serde::de::DeserializeOwned;
+
+use crate::domain::credential::{CredentialError, CredentialResolver, SecretValue, SecretVersion};
+use crate::domain::plugin::PluginError;
+
+/// Deserialize a plugin's string-valued `config` map into its typed config.
+fn parse_config<T: DeserializeOwned>(
+    plugin: &str,
+    config: &HashMap<String, String>,
+) -> Result<T, PluginError> {
+    let value = serde_json::to_value(config)
+        .map_err(|e| PluginError::Internal(format!("invalid {plugin} auth config: {e}")))?;
+    serde_json::from_value(value)
+        .map_err(|e| PluginError::Internal(format!("invalid {plugin} auth config: {e}")))
+}
+
+/// Resolve `secret_ref` for the caller of `ctx`.
+///
+/// A denied reference fails authentication; a missing one is reported as
+/// [`PluginError::SecretNotFound`] so it surfaces as a gateway misconfiguration.
+async fn resolve_secret(
+    resolver: &dyn CredentialResolver,
+    ctx: &SecurityContext,
+    secret_ref: &str,
+) -> Result<SecretValue, PluginError> {
+    resolver
+        .resolve(ctx, secret_ref)
+        .await
+        .map_err(|e| secret_error(secret_ref, e))
+}
+
+/// Version of the secret [`resolve_secret`] would return, with the same
+/// errors.
+async fn secret_version(
+    resolver: &dyn CredentialResolver,
+    ctx: &SecurityContext,
+    secret_ref: &str,
+) -> Result<SecretVersion, PluginError> {
+    resolver
+        .version(ctx, secret_ref)
+        .await
+        .map_err(|e| secret_error(secret_ref, e))
+}
+
+fn secret_error(secret_ref: &str, e: CredentialError) -> PluginError {
+    match e {
+        CredentialError::NotFound(_) => PluginError::SecretNotFound(secret_ref.to_string()),
+        CredentialError::AccessDenied(_) => {
+            PluginError::AuthFailed(format!("access to secret '{secret_ref}' denied"))
+        }
+        CredentialError::Internal(msg) => {
+            PluginError::Internal(format!("failed to resolve secret '{secret_ref}': {msg}"))
+        }
+    }
+}
@@ -14,6 +14,9 @@ impl AuthPlugin for NoopAuthPlugin {
 mod tests {
     use std::collections::HashMap;
 
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
     use super::*;
 
     #[tokio::test]
@@ -23,6 +26,9 @@ mod tests {
         headers.insert("x-existing".to_string(), "value".to_string());
 
         let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
             headers: headers.clone(),
             config: HashMap::new(),
         };
@@ -0,0 +1,494 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use modkit_auth::oauth2::{ClientAuthMethod, OAuthClientConfig, SecretString, Token};
+use modkit_http::HttpClientConfig;
+use modkit_security::SecurityContext;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+use url::Url;
+use uuid::Uuid;
+
+use crate::domain::credential::{CredentialResolver, SecretVersion};
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+use crate::domain::services::ConfigChangeListener;
+
+use super::{parse_config, resolve_secret, secret_version};
+
+/// How long a token nobody used is kept.
+const TOKEN_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Configuration for the `OAuth2` client credentials auth plugins.
+#[derive(Debug, Deserialize)]
+struct OAuth2Config {
+    /// Token endpoint (e.g. `https://auth.example.com/oauth/token`).
+    token_url: String,
+    /// Secret reference holding the client identifier.
+    client_id_ref: String,
+    /// Secret reference holding the client secret.
+    client_secret_ref: String,
+    /// Space-separated scopes to request.
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// Everything a cached token was obtained with. A token is reused only while
+/// the upstream's auth config and the versions of the client credentials
+/// match; the credentials themselves are resolved only to fetch a token.
+#[derive(PartialEq)]
+struct TokenSource {
+    client_auth: ClientAuthMethod,
+    token_url: Url,
+    client_id_ref: String,
+    client_secret_ref: String,
+    scopes: Vec<String>,
+    /// Versions of the client id and client secret.
+    versions: [SecretVersion; 2],
+}
+
+struct TokenSlot {
+    source: TokenSource,
+    /// Created by the first request that needs it; `Token` refreshes itself
+    /// in the background afterwards.
+    token: OnceCell<Token>,
+    last_used: Mutex<Instant>,
+}
+
+impl TokenSlot {
+    fn last_used(&self) -> Instant {
+        *self
+            .last_used
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn touch(&self, now: Instant) {
+        *self
+            .last_used
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = now;
+    }
+}
+
+/// `OAuth2` tokens of the client credentials plugins, per upstream and
+/// tenant.
+///
+/// Tokens of an updated or deleted upstream are dropped, and so are tokens
+/// unused for longer than the idle TTL, which also stops their background
+/// refresh.
+pub struct OAuth2TokenCache {
+    slots: DashMap<(Uuid, Uuid), Arc<TokenSlot>>,
+    idle_ttl: Duration,
+    last_sweep: Mutex<Instant>,
+}
+
+impl OAuth2TokenCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_idle_ttl(TOKEN_IDLE_TTL)
+    }
+
+    #[must_use]
+    pub fn with_idle_ttl(idle_ttl: Duration) -> Self {
+        Self {
+            slots: DashMap::new(),
+            idle_ttl,
+            last_sweep: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Cached slot for `key`, replaced when the token source changed.
+    fn slot(&self, key: (Uuid, Uuid), source: TokenSource) -> Arc<TokenSlot> {
+        let now = Instant::now();
+        self.sweep(now);
+        let fresh = |source| {
+            Arc::new(TokenSlot {
+                source,
+                token: OnceCell::new(),
+                last_used: Mutex::new(now),
+            })
+        };
+        let slot = match self.slots.entry(key) {
+            Entry::Occupied(entry) if entry.get().source == source => Arc::clone(entry.get()),
+            Entry::Occupied(mut entry) => {
+                let slot = fresh(source);
+                entry.insert(Arc::clone(&slot));
+                slot
+            }
+            Entry::Vacant(entry) => Arc::clone(&entry.insert(fresh(source))),
+        };
+        slot.touch(now);
+        slot
+    }
+
+    /// The token cached for `key`, if one was obtained.
+    fn token(&self, key: (Uuid, Uuid)) -> Option<Token> {
+        self.slots
+            .get(&key)
+            .and_then(|slot| slot.token.get().cloned())
+    }
+
+    /// Drop idle tokens, at most once per idle TTL.
+    fn sweep(&self, now: Instant) {
+        {
+            let mut last_sweep = self
+                .last_sweep
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if now.duration_since(*last_sweep) < self.idle_ttl {
+                return;
+            }
+            *last_sweep = now;
+        }
+        self.slots
+            .retain(|_, slot| now.duration_since(slot.last_used()) < self.idle_ttl);
+    }
+}
+
+impl ConfigChangeListener for OAuth2TokenCache {
+    fn upstream_changed(&self, id: Uuid, _alias: &str) {
+        self.slots.retain(|(upstream_id, _), _| *upstream_id != id);
+    }
+
+    fn route_changed(&self, _route_id: Uuid) {}
+}
+
+impl Default for OAuth2TokenCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Auth plugin that obtains an access token with the `OAuth2` client
+/// credentials grant and sends it as `Authorization: Bearer`.
+///
+/// Tokens are kept in an [`OAuth2TokenCache`]. When the upstream rejects a
+/// token with `401`, [`AuthPlugin::on_unauthorized`] forces a fresh token.
+pub struct OAuth2ClientCredAuthPlugin {
+    credential_resolver: Arc<dyn CredentialResolver>,
+    /// How the client authenticates to the token endpoint.
+    client_auth: ClientAuthMethod,
+    http_config: HttpClientConfig,
+    tokens: Arc<OAuth2TokenCache>,
+}
+
+impl OAuth2ClientCredAuthPlugin {
+    #[must_use]
+    pub fn new(
+        credential_resolver: Arc<dyn CredentialResolver>,
+        client_auth: ClientAuthMethod,
+        http_config: HttpClientConfig,
+        tokens: Arc<OAuth2TokenCache>,
+    ) -> Self {
+        Self {
+            credential_resolver,
+            client_auth,
+            http_config,
+            tokens,
+        }
+    }
+
+    /// Token endpoint client config, with the client credentials resolved.
+    async fn client_config(
+        &self,
+        ctx: &SecurityContext,
+        source: &TokenSource,
+    ) -> Result<OAuthClientConfig, PluginError> {
+        let resolver = self.credential_resolver.as_ref();
+        let client_id = resolve_secret(resolver, ctx, &source.client_id_ref).await?;
+        let client_secret = resolve_secret(resolver, ctx, &source.client_secret_ref).await?;
+        Ok(OAuthClientConfig {
+            token_endpoint: Some(source.token_url.clone()),
+            client_id: client_id.as_str().to_string(),
+            client_secret: SecretString::new(client_secret.as_str()),
+            scopes: source.scopes.clone(),
+            auth_method: source.client_auth,
+            http_config: Some(self.http_config.clone()),
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthPlugin for OAuth2ClientCredAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config: OAuth2Config = parse_config("oauth2", &ctx.config)?;
+        let token_url = Url::parse(&config.token_url)
+            .map_err(|e| PluginError::Internal(format!("invalid oauth2 auth config: {e}")))?;
+        let resolver = self.credential_resolver.as_ref();
+        let security_context = &ctx.security_context;
+        let source = TokenSource {
+            client_auth: self.client_auth,
+            token_url,
+            versions: [
+                secret_version(resolver, security_context, &config.client_id_ref).await?,
+                secret_version(resolver, security_context, &config.client_secret_ref).await?,
+            ],
+            client_id_ref: config.client_id_ref,
+            client_secret_ref: config.client_secret_ref,
+            scopes: config
+                .scope
+                .as_deref()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        };
+
+        let slot = self.tokens.slot((ctx.upstream_id, ctx.tenant_id), source);
+        let token = slot
+            .token
+            .get_or_try_init(|| async {
+                let config = self.client_config(security_context, &slot.source).await?;
+                Token::new(config).await.map_err(|e| {
+                    PluginError::AuthFailed(format!("OAuth2 token request failed: {e}"))
+                })
+            })
+            .await?;
+        let access_token = token
+            .get()
+            .map_err(|e| PluginError::AuthFailed(e.to_string()))?;
+
+        ctx.headers.insert(
+            "authorization".into(),
+            format!("Bearer {}", access_token.expose()),
+        );
+
+        Ok(())
+    }
+
+    async fn on_unauthorized(&self, ctx: &AuthContext) -> bool {
+        match self.tokens.token((ctx.upstream_id, ctx.tenant_id)) {
+            Some(token) => {
+                token.invalidate().await;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use modkit_security::SecurityContext;
+
+    use crate::infra::storage::credential_repo::InMemoryCredentialResolver;
+    use crate::test_support::MockGuard;
+
+    use super::*;
+
+    fn make_creds() -> Arc<InMemoryCredentialResolver> {
+        Arc::new(InMemoryCredentialResolver::with_credentials(vec![
+            ("cred://vendor/client_id".into(), "gateway".into()),
+            ("cred://vendor/client_secret".into(), "s3cret".into()),
+        ]))
+    }
+
+    fn make_plugin(client_auth: ClientAuthMethod) -> OAuth2ClientCredAuthPlugin {
+        OAuth2ClientCredAuthPlugin::new(
+            make_creds(),
+            client_auth,
+            HttpClientConfig::for_testing(),
+            Arc::new(OAuth2TokenCache::new()),
+        )
+    }
+
+    fn make_ctx(guard: &MockGuard, tenant_id: Uuid) -> AuthContext {
+        AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id,
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::new(),
+            config: HashMap::from([
+                ("token_url".into(), guard.url("/oauth/token")),
+                ("client_id_ref".into(), "cred://vendor/client_id".into()),
+                (
+                    "client_secret_ref".into(),
+                    "cred://vendor/client_secret".into(),
+                ),
+                ("scope".into(), "read write".into()),
+            ]),
+        }
+    }
+
+    async fn token_requests(guard: &MockGuard) -> Vec<crate::test_support::RecordedRequest> {
+        guard
+            .recorded_requests()
+            .await
+            .into_iter()
+            .filter(|r| r.uri.ends_with("/oauth/token"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn caches_token_per_upstream_and_tenant() {
+        let guard = MockGuard::new();
+        let plugin = make_plugin(ClientAuthMethod::Form);
+        let tenant = Uuid::new_v4();
+
+        let mut ctx = make_ctx(&guard, tenant);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers["authorization"], "Bearer tok-1");
+
+        let mut ctx = make_ctx(&guard, tenant);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers["authorization"], "Bearer tok-1");
+
+        let mut other_tenant = make_ctx(&guard, Uuid::new_v4());
+        plugin.authenticate(&mut other_tenant).await.unwrap();
+        assert_eq!(other_tenant.headers["authorization"], "Bearer tok-2");
+
+        assert_eq!(token_requests(&guard).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn form_client_auth_sends_credentials_in_body() {
+        let guard = MockGuard::new();
+        let plugin = make_plugin(ClientAuthMethod::Form);
+        plugin
+            .authenticate(&mut make_ctx(&guard, Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        let requests = token_requests(&guard).await;
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("grant_type=client_credentials"), "{body}");
+        assert!(body.contains("client_id=gateway"), "{body}");
+        assert!(body.contains("scope=read"), "{body}");
+        assert!(
+            !requests[0]
+                .headers
+                .iter()
+                .any(|(k, _)| k == "authorization")
+        );
+    }
+
+    #[tokio::test]
+    async fn basic_client_auth_sends_credentials_in_header() {
+        let guard = MockGuard::new();
+        let plugin = make_plugin(ClientAuthMethod::Basic);
+        plugin
+            .authenticate(&mut make_ctx(&guard, Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        let requests = token_requests(&guard).await;
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(!body.contains("client_secret"), "{body}");
+        let authorization = requests[0]
+            .headers
+            .iter()
+            .find(|(k, _)| k == "authorization")
+            .map(|(_, v)| v.as_str());
+        // base64("gateway:s3cret")
+        assert_eq!(authorization, Some("Basic Z2F0ZXdheTpzM2NyZXQ="));
+    }
+
+    #[tokio::test]
+    async fn unauthorized_fetches_a_new_token() {
+        let guard = MockGuard::new();
+        let plugin = make_plugin(ClientAuthMethod::Form);
+        let tenant = Uuid::new_v4();
+
+        let mut ctx = make_ctx(&guard, tenant);
+        assert!(!plugin.on_unauthorized(&ctx).await);
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert!(plugin.on_unauthorized(&ctx).await);
+
+        let mut ctx = make_ctx(&guard, tenant);
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers["authorization"], "Bearer tok-2");
+    }
+
+    #[tokio::test]
+    async fn unreachable_token_endpoint_fails_authentication() {
+        let plugin = make_plugin(ClientAuthMethod::Form);
+        let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
+            tenant_id: Uuid::nil(),
+            security_context: SecurityContext::anonymous(),
+            headers: HashMap::new(),
+            config: HashMap::from([
+                ("token_url".into(), "http://127.0.0.1:1/oauth/token".into()),
+                ("client_id_ref".into(), "cred://vendor/client_id".into()),
+                (
+                    "client_secret_ref".into(),
+                    "cred://vendor/client_secret".into(),
+                ),
+            ]),
+        };
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::AuthFailed(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rotated_secret_fetches_a_new_token() {
+        let guard = MockGuard::new();
+        let creds = make_creds();
+        let plugin = OAuth2ClientCredAuthPlugin::new(
+            Arc::clone(&creds) as Arc<dyn CredentialResolver>,
+            ClientAuthMethod::Form,
+            HttpClientConfig::for_testing(),
+            Arc::new(OAuth2TokenCache::new()),
+        );
+        let tenant = Uuid::new_v4();
+
+        plugin
+            .authenticate(&mut make_ctx(&guard, tenant))
+            .await
+            .unwrap();
+        creds.set("cred://vendor/client_secret".into(), "s3cret".into());
+        let mut ctx = make_ctx(&guard, tenant);
+        plugin.authenticate(&mut ctx).await.unwrap();
+
+        assert_eq!(ctx.headers["authorization"], "Bearer tok-2");
+    }
+
+    #[tokio::test]
+    async fn upstream_change_drops_its_tokens() {
+        let guard = MockGuard::new();
+        let tokens = Arc::new(OAuth2TokenCache::new());
+        let plugin = OAuth2ClientCredAuthPlugin::new(
+            make_creds(),
+            ClientAuthMethod::Form,
+            HttpClientConfig::for_testing(),
+            Arc::clone(&tokens),
+        );
+        let mut ctx = make_ctx(&guard, Uuid::new_v4());
+        plugin.authenticate(&mut ctx).await.unwrap();
+
+        tokens.upstream_changed(Uuid::new_v4(), "other");
+        assert!(tokens.token((ctx.upstream_id, ctx.tenant_id)).is_some());
+        tokens.upstream_changed(ctx.upstream_id, "vendor");
+        assert!(tokens.token((ctx.upstream_id, ctx.tenant_id)).is_none());
+    }
+
+    #[tokio::test]
+    async fn idle_tokens_are_dropped() {
+        let guard = MockGuard::new();
+        let tokens = Arc::new(OAuth2TokenCache::with_idle_ttl(Duration::from_millis(50)));
+        let plugin = OAuth2ClientCredAuthPlugin::new(
+            make_creds(),
+            ClientAuthMethod::Form,
+            HttpClientConfig::for_testing(),
+            Arc::clone(&tokens),
+        );
+        let idle = make_ctx(&guard, Uuid::new_v4());
+        plugin.authenticate(&mut idle.clone()).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        plugin
+            .authenticate(&mut make_ctx(&guard, Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        assert!(tokens.token((idle.upstream_id, idle.tenant_id)).is_none());
+        assert_eq!(tokens.slots.len(), 1);
+    }
+}
@@ -1,12 +1,21 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use modkit_auth::oauth2::ClientAuthMethod;
+use modkit_http::HttpClientConfig;
+
 use crate::domain::credential::CredentialResolver;
 use crate::domain::plugin::{AuthPlugin, PluginError};
 
 use super::apikey_auth::ApiKeyAuthPlugin;
+use super::basic_auth::BasicAuthPlugin;
+use super::bearer_auth::BearerAuthPlugin;
 use super::noop_auth::NoopAuthPlugin;
-use crate::domain::gts_helpers::{APIKEY_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID};
+use super::oauth2_client_cred::{OAuth2ClientCredAuthPlugin, OAuth2TokenCache};
+use crate::domain::gts_helpers::{
+    APIKEY_AUTH_PLUGIN_ID, BASIC_AUTH_PLUGIN_ID, BEARER_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID,
+    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+};
 
 /// Registry that resolves auth plugin GTS identifiers to plugin implementations.
 pub struct AuthPluginRegistry {
@@ -14,13 +23,47 @@ pub struct AuthPluginRegistry {
 }
 
 impl AuthPluginRegistry {
-    /// Create a registry with the built-in plugins (apikey, noop).
+    /// Create a registry with the built-in plugins (apikey, basic, bearer,
+    /// `OAuth2` client credentials, noop).
+    ///
+    /// `token_http_config` configures the client the `OAuth2` plugins use to
+    /// call token endpoints, and `tokens` holds the tokens they obtain.
     #[must_use]
-    pub fn with_builtins(credential_resolver: Arc<dyn CredentialResolver>) -> Self {
+    pub fn with_builtins(
+        credential_resolver: Arc<dyn CredentialResolver>,
+        token_http_config: HttpClientConfig,
+        tokens: Arc<OAuth2TokenCache>,
+    ) -> Self {
         let mut plugins: HashMap<String, Arc<dyn AuthPlugin>> = HashMap::new();
         plugins.insert(
             APIKEY_AUTH_PLUGIN_ID.to_string(),
-            Arc::new(ApiKeyAuthPlugin::new(credential_resolver)),
+            Arc::new(ApiKeyAuthPlugin::new(Arc::clone(&credential_resolver))),
+        );
+        plugins.insert(
+            BASIC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(BasicAuthPlugin::new(Arc::clone(&credential_resolver))),
+        );
+        plugins.insert(
+            BEARER_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(BearerAuthPlugin::new(Arc::clone(&credential_resolver))),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::new(
+                Arc::clone(&credential_resolver),
+                ClientAuthMethod::Form,
+                token_http_config.clone(),
+                Arc::clone(&tokens),
+            )),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::new(
+                credential_resolver,
+                ClientAuthMethod::Basic,
+                token_http_config,
+                tokens,
+            )),
         );
         plugins.insert(NOOP_AUTH_PLUGIN_ID.to_string(), Arc::new(NoopAuthPlugin));
         Self { plugins }
@@ -48,7 +91,11 @@ mod tests {
 
     fn make_registry() -> AuthPluginRegistry {
         let creds = Arc::new(InMemoryCredentialResolver::new());
-        AuthPluginRegistry::with_builtins(creds)
+        AuthPluginRegistry::with_builtins(
+            creds,
+            HttpClientConfig::for_testing(),
+            Arc::new(OAuth2TokenCache::new()),
+        )
     }
 
     #[test]
@@ -63,6 +110,19 @@ mod tests {
         assert!(registry.resolve(NOOP_AUTH_PLUGIN_ID).is_ok());
     }
 
+    #[test]
+    fn resolves_basic_bearer_and_oauth2_plugins() {
+        let registry = make_registry();
+        for id in [
+            BASIC_AUTH_PLUGIN_ID,
+            BEARER_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+        ] {
+            assert!(registry.resolve(id).is_ok(), "{id}");
+        }
+    }
+
     #[test]
     fn unknown_plugin_returns_error() {
         let registry = make_registry();
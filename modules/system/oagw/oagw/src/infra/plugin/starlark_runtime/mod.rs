@@ -0,0 +1,513 @@
+//! Sandboxed Starlark interpreter for custom guard and transform plugins.
+//!
+//! Scripts get the standard Starlark dialect plus the `json` module and
+//! nothing else: `load` is disabled and no builtin reaches the file system,
+//! the network or the clock (time is only available as
+//! `ctx.time.elapsed_ms()`). Every call evaluates the source in a fresh
+//! module, so no state survives between requests.
+//!
+//! Steps, heap size and wall-clock time are bounded by [`SandboxLimits`],
+//! both between statements and inside expressions (see [`sandbox`]).
+//! Scripts run on the blocking thread pool, and the caller stops waiting
+//! once the time limit has passed even if a single builtin call is still
+//! running. Builtins whose output can outgrow their inputs without going
+//! through `+` or `*` (`str.join`, `str.replace`, `repr` of shared
+//! structures) are only checked at the next statement.
+
+mod sandbox;
+mod values;
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use starlark::environment::Globals;
+use starlark::environment::{GlobalsBuilder, LibraryExtension, Module};
+use starlark::eval::{BeforeStmtFuncDyn, Evaluator};
+use starlark::syntax::AstModule;
+use uuid::Uuid;
+
+use crate::domain::model::{CustomPlugin, PluginPhase};
+use crate::domain::plugin::{PluginError, PluginExchange, PluginOutcome, PluginRuntime};
+
+use sandbox::{BudgetGuard, BudgetHook};
+use values::{Ctx, State};
+
+/// Parsed plugins kept in memory. Plugins are immutable, so an entry never
+/// goes stale; it is only evicted to bound the cache.
+const MAX_CACHED_PROGRAMS: usize = 1024;
+
+/// Resource limits for a single hook call (including evaluating the
+/// module's top level).
+#[derive(Debug, Clone, Copy)]
+pub struct SandboxLimits {
+    /// Maximum number of statements executed plus elements iterated.
+    pub max_steps: u64,
+    /// Maximum size of the script's heap, in bytes.
+    pub max_heap_bytes: usize,
+    /// Maximum wall-clock time.
+    pub max_duration: Duration,
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            max_steps: 100_000,
+            max_heap_bytes: 16 * 1024 * 1024,
+            max_duration: Duration::from_millis(100),
+        }
+    }
+}
+
+/// [`PluginRuntime`] backed by `starlark-rust`.
+pub struct StarlarkRuntime {
+    sandbox: Arc<Sandbox>,
+}
+
+struct Sandbox {
+    globals: Globals,
+    limits: SandboxLimits,
+    /// Parsed source by plugin id.
+    programs: DashMap<Uuid, Arc<AstModule>>,
+}
+
+impl StarlarkRuntime {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_limits(SandboxLimits::default())
+    }
+
+    #[must_use]
+    pub fn with_limits(limits: SandboxLimits) -> Self {
+        Self {
+            sandbox: Arc::new(Sandbox {
+                globals: GlobalsBuilder::extended_by(&[LibraryExtension::Json])
+                    .with(sandbox::sandbox_globals)
+                    .build(),
+                limits,
+                programs: DashMap::new(),
+            }),
+        }
+    }
+
+    /// Parsed source of `plugin`, from the cache when possible.
+    fn program(&self, plugin: &CustomPlugin) -> Result<Arc<AstModule>, PluginError> {
+        if let Some(program) = self.sandbox.programs.get(&plugin.id) {
+            return Ok(Arc::clone(&program));
+        }
+        let program = Arc::new(sandbox::parse(&plugin.source_code)?);
+        if self.sandbox.programs.len() >= MAX_CACHED_PROGRAMS {
+            let victim = self.sandbox.programs.iter().next().map(|e| *e.key());
+            if let Some(victim) = victim {
+                self.sandbox.programs.remove(&victim);
+            }
+        }
+        self.sandbox
+            .programs
+            .insert(plugin.id, Arc::clone(&program));
+        Ok(program)
+    }
+
+    /// Run `f` on the blocking thread pool, giving up after the time limit.
+    async fn spawn<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&Sandbox) -> Result<T, PluginError> + Send + 'static,
+    ) -> Result<T, PluginError> {
+        let sandbox = Arc::clone(&self.sandbox);
+        let task = tokio::task::spawn_blocking(move || f(&sandbox));
+        match tokio::time::timeout(self.sandbox.limits.max_duration, task).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(PluginError::Internal("plugin interpreter panicked".into())),
+            Err(_) => Err(PluginError::Script("time limit exceeded".into())),
+        }
+    }
+}
+
+impl Sandbox {
+    /// Evaluate `program` and, when `call` is given, run that hook.
+    fn execute(
+        &self,
+        program: &AstModule,
+        call: Option<(PluginPhase, &Arc<Mutex<State>>)>,
+    ) -> Result<Execution, PluginError> {
+        let _budget = BudgetGuard::install(&self.limits);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let module = Module::new();
+            let mut eval = Evaluator::new(&module);
+            eval.before_stmt_for_dap((Box::new(BudgetHook) as Box<dyn BeforeStmtFuncDyn>).into());
+            eval.eval_module(program.clone(), &self.globals)
+                .map_err(|e| PluginError::Script(e.to_string()))?;
+
+            let phases = PluginPhase::ALL
+                .into_iter()
+                .filter(|p| module.get(p.hook_name()).is_some())
+                .collect();
+            let outcome = match call {
+                Some((phase, state)) => Some(match module.get(phase.hook_name()) {
+                    Some(hook) => {
+                        let ctx = module.heap().alloc(Ctx::new(Arc::clone(state)));
+                        let ret = eval
+                            .eval_function(hook, &[ctx], &[])
+                            .map_err(|e| PluginError::Script(e.to_string()))?;
+                        values::outcome(state, ret)?
+                    }
+                    None => PluginOutcome::Next,
+                }),
+                None => None,
+            };
+            Ok(Execution { phases, outcome })
+        }));
+
+        result.unwrap_or_else(|_| Err(PluginError::Internal("plugin interpreter panicked".into())))
+    }
+}
+
+impl Default for StarlarkRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginRuntime for StarlarkRuntime {
+    async fn compile(&self, source: &str) -> Result<Vec<PluginPhase>, PluginError> {
+        let program = sandbox::parse(source)?;
+        let execution = self.spawn(move |s| s.execute(&program, None)).await?;
+        Ok(execution.phases)
+    }
+
+    async fn run(
+        &self,
+        plugin: &CustomPlugin,
+        phase: PluginPhase,
+        config: &serde_json::Value,
+        exchange: &mut PluginExchange,
+    ) -> Result<PluginOutcome, PluginError> {
+        let program = self.program(plugin)?;
+        let state = Arc::new(Mutex::new(State::new(exchange.clone(), config.clone())));
+        let shared = Arc::clone(&state);
+        let outcome = self
+            .spawn(move |s| s.execute(&program, Some((phase, &shared))))
+            .await?
+            .outcome
+            .unwrap_or(PluginOutcome::Next);
+        *exchange = values::lock(&state).exchange.clone();
+        Ok(outcome)
+    }
+}
+
+struct Execution {
+    phases: Vec<PluginPhase>,
+    outcome: Option<PluginOutcome>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use bytes::Bytes;
+    use uuid::Uuid;
+
+    use crate::domain::model::PluginType;
+    use crate::domain::plugin::{HeaderEdit, PluginHeaders, PluginRequest, PluginResponse};
+
+    use super::*;
+
+    fn exchange() -> PluginExchange {
+        PluginExchange {
+            tenant_id: Uuid::nil(),
+            route_id: Uuid::nil(),
+            started: Instant::now(),
+            request: PluginRequest {
+                method: "POST".into(),
+                path: "/v1/users".into(),
+                query: vec![("internal_debug".into(), "1".into())],
+                headers: PluginHeaders::new([("X-Internal".into(), "1".into())]),
+                body: Bytes::from_static(br#"{"a":1}"#),
+            },
+            response: None,
+            error: None,
+        }
+    }
+
+    fn plugin(source: &str) -> CustomPlugin {
+        CustomPlugin {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::nil(),
+            name: "test".into(),
+            description: None,
+            plugin_type: PluginType::Guard,
+            phases: PluginPhase::ALL.to_vec(),
+            config_schema: serde_json::json!({}),
+            source_code: source.into(),
+        }
+    }
+
+    async fn run(
+        source: &str,
+        phase: PluginPhase,
+        config: serde_json::Value,
+    ) -> (PluginExchange, Result<PluginOutcome, PluginError>) {
+        let mut ex = exchange();
+        let result = StarlarkRuntime::new()
+            .run(&plugin(source), phase, &config, &mut ex)
+            .await;
+        (ex, result)
+    }
+
+    #[tokio::test]
+    async fn compile_reports_defined_hooks() {
+        let rt = StarlarkRuntime::new();
+        let phases = rt
+            .compile("def on_request(ctx):\n    return ctx.next()\n\ndef on_error(ctx):\n    return ctx.next()\n")
+            .await
+            .unwrap();
+        assert_eq!(phases, vec![PluginPhase::OnRequest, PluginPhase::OnError]);
+
+        assert!(matches!(
+            rt.compile("def on_request(ctx)\n").await,
+            Err(PluginError::Script(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn guard_rejects_with_plugin_status_and_code() {
+        let source = r#"
+def on_request(ctx):
+    for h in ctx.config.get("required_headers", []):
+        if not ctx.request.headers.get(h):
+            return ctx.reject(400, "MISSING_HEADER", "Required header: " + h)
+    return ctx.next()
+"#;
+        let (_, outcome) = run(
+            source,
+            PluginPhase::OnRequest,
+            serde_json::json!({"required_headers": ["X-Customer-Id"]}),
+        )
+        .await;
+        assert_eq!(
+            outcome.unwrap(),
+            PluginOutcome::Reject {
+                status: 400,
+                code: "MISSING_HEADER".into(),
+                message: "Required header: X-Customer-Id".into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn transform_mutates_path_query_and_headers() {
+        let source = r#"
+def on_request(ctx):
+    ctx.request.set_path(ctx.config["path_prefix"] + ctx.request.path)
+    ctx.request.add_query("api_version", "2024-01")
+    q = ctx.request.query
+    if "internal_debug" in q:
+        q.pop("internal_debug")
+        ctx.request.set_query(q)
+    ctx.request.headers.set("X-Feature-Flag", "A")
+    ctx.request.headers.remove("X-Internal")
+    return ctx.next()
+"#;
+        let (ex, outcome) = run(
+            source,
+            PluginPhase::OnRequest,
+            serde_json::json!({"path_prefix": "/v2"}),
+        )
+        .await;
+        assert_eq!(outcome.unwrap(), PluginOutcome::Next);
+        assert_eq!(ex.request.path, "/v2/v1/users");
+        assert_eq!(
+            ex.request.query,
+            vec![("api_version".to_string(), "2024-01".to_string())]
+        );
+        assert_eq!(
+            ex.request.headers.edits(),
+            [
+                HeaderEdit::Set("x-feature-flag".into(), "A".into()),
+                HeaderEdit::Remove("x-internal".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn response_json_redaction() {
+        let source = r#"
+def on_response(ctx):
+    data = ctx.response.json()
+    for field in ctx.config.get("fields", []):
+        if field in data:
+            data[field] = "[REDACTED]"
+    ctx.response.set_json(data)
+    return ctx.next()
+"#;
+        let mut ex = exchange();
+        ex.response = Some(PluginResponse {
+            status: 200,
+            headers: PluginHeaders::default(),
+            body: Bytes::from_static(br#"{"name":"Ann","email":"ann@example.com"}"#),
+        });
+        let config = serde_json::json!({"fields": ["email", "ssn"]});
+        StarlarkRuntime::new()
+            .run(&plugin(source), PluginPhase::OnResponse, &config, &mut ex)
+            .await
+            .unwrap();
+
+        let body: serde_json::Value = serde_json::from_slice(&ex.response.unwrap().body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"name": "Ann", "email": "[REDACTED]"})
+        );
+    }
+
+    #[tokio::test]
+    async fn respond_short_circuits_with_body() {
+        let (_, outcome) = run(
+            "def on_request(ctx):\n    return ctx.respond(200, {\"ok\": True})\n",
+            PluginPhase::OnRequest,
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(
+            outcome.unwrap(),
+            PluginOutcome::Respond {
+                status: 200,
+                body: r#"{"ok":true}"#.into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_hook_continues() {
+        let (_, outcome) = run(
+            "def on_response(ctx):\n    return ctx.next()\n",
+            PluginPhase::OnRequest,
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(outcome.unwrap(), PluginOutcome::Next);
+    }
+
+    // negative-11.8: sandbox restrictions.
+
+    #[tokio::test]
+    async fn endless_loop_hits_step_limit() {
+        let (_, outcome) = run(
+            "def on_request(ctx):\n    for i in range(1000000000):\n        pass\n",
+            PluginPhase::OnRequest,
+            serde_json::json!({}),
+        )
+        .await;
+        let err = outcome.unwrap_err();
+        assert!(err.to_string().contains("step limit"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn large_allocation_hits_memory_limit() {
+        let source =
+            "def on_request(ctx):\n    s = \"x\"\n    for i in range(64):\n        s = s + s\n";
+        let mut ex = exchange();
+        let err = StarlarkRuntime::with_limits(SandboxLimits {
+            max_steps: 1_000,
+            max_heap_bytes: 1024 * 1024,
+            ..SandboxLimits::default()
+        })
+        .run(
+            &plugin(source),
+            PluginPhase::OnRequest,
+            &serde_json::json!({}),
+            &mut ex,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("memory limit"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn single_expressions_are_bounded() {
+        // Generous time limit, so that only the step and memory limits apply.
+        let rt = StarlarkRuntime::with_limits(SandboxLimits {
+            max_duration: Duration::from_secs(30),
+            ..SandboxLimits::default()
+        });
+        for (body, limit) in [
+            ("\"x\" * 2000000000", "memory limit"),
+            ("[0] * 2000000000", "memory limit"),
+            ("list(range(1000000000))", "step limit"),
+            ("[0 for i in range(1000000000)]", "step limit"),
+            (
+                "[0 for i in range(1000) for j in range(1000)]",
+                "step limit",
+            ),
+            (
+                "[0 for i in \"ab\".elems() for j in [1] * 1000000]",
+                "step limit",
+            ),
+        ] {
+            let source = format!("def on_request(ctx):\n    return {body}\n");
+            let err = rt
+                .run(
+                    &plugin(&source),
+                    PluginPhase::OnRequest,
+                    &serde_json::json!({}),
+                    &mut exchange(),
+                )
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(limit), "{body}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn augmented_multiplication_is_refused() {
+        let err = StarlarkRuntime::new()
+            .compile("def on_request(ctx):\n    s = \"x\"\n    s *= 1000000000\n")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PluginError::Script(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn slow_script_hits_time_limit() {
+        let source = "def on_request(ctx):\n    for i in range(90000):\n        s = str(i) * 10\n";
+        let mut ex = exchange();
+        let err = StarlarkRuntime::with_limits(SandboxLimits {
+            max_duration: Duration::ZERO,
+            ..SandboxLimits::default()
+        })
+        .run(
+            &plugin(source),
+            PluginPhase::OnRequest,
+            &serde_json::json!({}),
+            &mut ex,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("time limit"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn load_and_io_builtins_are_unavailable() {
+        let rt = StarlarkRuntime::new();
+        for source in [
+            "load(\"http.star\", \"get\")\n",
+            "def on_request(ctx):\n    return open(\"/etc/passwd\")\n",
+            "def on_request(ctx):\n    return http.get(\"https://example.com\")\n",
+        ] {
+            let err = rt.compile(source).await.unwrap_err();
+            assert!(matches!(err, PluginError::Script(_)), "{source}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn script_errors_are_reported() {
+        let (_, outcome) = run(
+            "def on_request(ctx):\n    return 1 // 0\n",
+            PluginPhase::OnRequest,
+            serde_json::json!({}),
+        )
+        .await;
+        assert!(matches!(outcome, Err(PluginError::Script(_))));
+    }
+}
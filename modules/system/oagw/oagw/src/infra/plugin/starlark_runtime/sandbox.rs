@@ -0,0 +1,279 @@
+//! Budget enforcement for plugin scripts.
+//!
+//! The statement hook only runs between statements, so work done inside a
+//! single expression is metered separately: `+` and `*` are rewritten into
+//! calls to [`sandbox_globals`] functions that check the size of the result
+//! before building it, `range()` and the iterables of comprehensions are
+//! charged one step per element, and every check also enforces the
+//! deadline.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::num::NonZeroI32;
+use std::time::Instant;
+
+use starlark::codemap::{CodeMap, FileSpanRef};
+use starlark::environment::GlobalsBuilder;
+use starlark::eval::{BeforeStmtFuncDyn, Evaluator};
+use starlark::starlark_module;
+use starlark::syntax::{AstModule, Dialect};
+use starlark::values::range::Range;
+use starlark::values::{Heap, Value};
+use starlark_syntax::lexer::{Lexer, Token};
+
+use crate::domain::plugin::PluginError;
+
+use super::SandboxLimits;
+
+const ADD: &str = "_sandbox_add";
+const MUL: &str = "_sandbox_mul";
+/// Opens the wrapper of a comprehension iterable.
+const ITER_CALL: &str = " _sandbox_iter(";
+
+#[derive(Clone, Copy)]
+struct Budget {
+    steps_left: u64,
+    max_heap_bytes: usize,
+    deadline: Instant,
+}
+
+thread_local! {
+    /// Budget of the script running on this thread. Builtins are plain
+    /// functions, so the budget cannot live in the evaluator.
+    static BUDGET: Cell<Option<Budget>> = const { Cell::new(None) };
+}
+
+/// Installs a fresh budget for the current thread and removes it on drop.
+pub(super) struct BudgetGuard(());
+
+impl BudgetGuard {
+    pub(super) fn install(limits: &SandboxLimits) -> Self {
+        BUDGET.with(|b| {
+            b.set(Some(Budget {
+                steps_left: limits.max_steps,
+                max_heap_bytes: limits.max_heap_bytes,
+                deadline: Instant::now() + limits.max_duration,
+            }));
+        });
+        Self(())
+    }
+}
+
+impl Drop for BudgetGuard {
+    fn drop(&mut self) {
+        BUDGET.with(|b| b.set(None));
+    }
+}
+
+/// Charge `steps` and check that `extra_bytes` more would still fit on
+/// `heap`, and that the deadline has not passed.
+fn charge(heap: &Heap, steps: u64, extra_bytes: usize) -> starlark::Result<()> {
+    let exceeded = BUDGET.with(|b| {
+        let mut budget = b.get()?;
+        if budget.steps_left < steps {
+            return Some("step limit exceeded");
+        }
+        budget.steps_left -= steps;
+        b.set(Some(budget));
+        if heap.allocated_bytes().saturating_add(extra_bytes) > budget.max_heap_bytes {
+            return Some("memory limit exceeded");
+        }
+        (Instant::now() >= budget.deadline).then_some("time limit exceeded")
+    });
+    match exceeded {
+        Some(reason) => Err(starlark::Error::new_other(anyhow::anyhow!(reason))),
+        None => Ok(()),
+    }
+}
+
+/// Statement hook that enforces the thread's [`Budget`].
+pub(super) struct BudgetHook;
+
+impl<'a, 'e: 'a> BeforeStmtFuncDyn<'a, 'e> for BudgetHook {
+    fn call<'v>(
+        &mut self,
+        _span: FileSpanRef,
+        eval: &mut Evaluator<'v, 'a, 'e>,
+    ) -> starlark::Result<()> {
+        charge(eval.heap(), 1, 0)
+    }
+}
+
+/// Bytes needed to hold the contents of `value`, if it is a string or a
+/// sequence of references.
+fn content_bytes(value: Value<'_>) -> usize {
+    if let Some(s) = value.unpack_str() {
+        return s.len();
+    }
+    match value.get_type() {
+        "list" | "tuple" | "dict" => value
+            .length()
+            .map_or(0, |n| usize::try_from(n).unwrap_or(0) * size_of::<Value>()),
+        _ => 0,
+    }
+}
+
+#[starlark_module]
+pub(super) fn sandbox_globals(builder: &mut GlobalsBuilder) {
+    /// `range()` charged one step per element, since consumers such as
+    /// `list()` iterate it without running statements.
+    fn range<'v>(
+        #[starlark(require = pos)] a1: i32,
+        #[starlark(require = pos)] a2: Option<i32>,
+        #[starlark(require = pos, default = 1)] step: i32,
+        heap: &'v Heap,
+    ) -> starlark::Result<Value<'v>> {
+        let (start, stop) = match a2 {
+            None => (0, a1),
+            Some(stop) => (a1, stop),
+        };
+        let step = NonZeroI32::new(step).ok_or_else(|| {
+            starlark::Error::new_other(anyhow::anyhow!(
+                "Third argument of range (step) cannot be zero"
+            ))
+        })?;
+        let range = heap.alloc(Range::new(start, stop, step));
+        charge(heap, u64::try_from(range.length()?).unwrap_or(0), 0)?;
+        Ok(range)
+    }
+
+    /// `a + b`.
+    fn _sandbox_add<'v>(
+        #[starlark(require = pos)] a: Value<'v>,
+        #[starlark(require = pos)] b: Value<'v>,
+        heap: &'v Heap,
+    ) -> starlark::Result<Value<'v>> {
+        charge(heap, 0, content_bytes(a).saturating_add(content_bytes(b)))?;
+        a.add(b, heap)
+    }
+
+    /// `a * b`. Repeating a string or sequence is checked against the heap
+    /// limit before the result is built.
+    fn _sandbox_mul<'v>(
+        #[starlark(require = pos)] a: Value<'v>,
+        #[starlark(require = pos)] b: Value<'v>,
+        heap: &'v Heap,
+    ) -> starlark::Result<Value<'v>> {
+        let times = |v: Value<'v>| {
+            v.unpack_i32()
+                .map_or(0, |n| usize::try_from(n).unwrap_or(0))
+        };
+        let extra = content_bytes(a)
+            .saturating_mul(times(b))
+            .max(content_bytes(b).saturating_mul(times(a)));
+        charge(heap, 0, extra)?;
+        a.mul(b, heap)
+    }
+
+    /// Iterable of a comprehension `for` clause, charged one step per
+    /// element each time the clause starts.
+    fn _sandbox_iter<'v>(
+        #[starlark(require = pos)] iterable: Value<'v>,
+        heap: &'v Heap,
+    ) -> starlark::Result<Value<'v>> {
+        let len = iterable
+            .length()
+            .map_or(0, |n| u64::try_from(n).unwrap_or(0));
+        charge(heap, len, 0)?;
+        Ok(iterable)
+    }
+}
+
+pub(super) fn dialect() -> Dialect {
+    let mut dialect = Dialect::Standard;
+    dialect.enable_load = false;
+    dialect
+}
+
+/// Parse `source` with its expressions routed through [`sandbox_globals`].
+pub(super) fn parse(source: &str) -> Result<AstModule, PluginError> {
+    let source = wrap_comprehensions(source)?;
+    let mut ast = AstModule::parse("plugin.star", source, &dialect())
+        .map_err(|e| PluginError::Script(e.to_string()))?;
+    ast.replace_binary_operators(&HashMap::from([
+        ("+".to_owned(), ADD.to_owned()),
+        ("*".to_owned(), MUL.to_owned()),
+    ]));
+    Ok(ast)
+}
+
+/// Wrap the iterable of every comprehension `for` clause in a call to
+/// `_sandbox_iter`. Augmented `*=` is refused, as it cannot be rewritten
+/// into a checked call.
+fn wrap_comprehensions(source: &str) -> Result<String, PluginError> {
+    #[derive(Default)]
+    struct Bracket {
+        /// Seen `for`, waiting for its `in`.
+        in_for: bool,
+        /// Inside a wrapped iterable.
+        wrapping: bool,
+    }
+
+    let mut brackets: Vec<Bracket> = Vec::new();
+    let mut inserts: Vec<(usize, &'static str)> = Vec::new();
+    let codemap = CodeMap::new("plugin.star".to_owned(), source.to_owned());
+    for lexeme in Lexer::new(source, &dialect(), codemap) {
+        // Lexing errors are reported by the parser.
+        let Ok((start, token, end)) = lexeme else {
+            return Ok(source.to_owned());
+        };
+        match token {
+            Token::StarEqual => {
+                return Err(PluginError::Script(
+                    "'*=' is not supported; write 'x = x * y'".into(),
+                ));
+            }
+            Token::OpeningRound | Token::OpeningSquare | Token::OpeningCurly => {
+                brackets.push(Bracket::default());
+            }
+            Token::ClosingRound | Token::ClosingSquare | Token::ClosingCurly
+                if brackets.pop().is_some_and(|b| b.wrapping) =>
+            {
+                inserts.push((start, ")"));
+            }
+            Token::For | Token::If => {
+                if let Some(b) = brackets.last_mut() {
+                    if b.wrapping {
+                        inserts.push((start, ")"));
+                        b.wrapping = false;
+                    }
+                    b.in_for = matches!(token, Token::For);
+                }
+            }
+            Token::In => {
+                if let Some(b) = brackets.last_mut().filter(|b| b.in_for) {
+                    b.in_for = false;
+                    b.wrapping = true;
+                    inserts.push((end, ITER_CALL));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = String::with_capacity(source.len() + inserts.len() * ITER_CALL.len());
+    let mut copied = 0;
+    for (at, text) in inserts {
+        out.push_str(&source[copied..at]);
+        out.push_str(text);
+        copied = at;
+    }
+    out.push_str(&source[copied..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comprehension_iterables_are_wrapped() {
+        assert_eq!(
+            wrap_comprehensions(
+                "x = [a for a in l if a in s for b in f(a)]\nfor i in l:\n    pass\n"
+            )
+            .unwrap(),
+            "x = [a for a in _sandbox_iter( l )if a in s for b in _sandbox_iter( f(a))]\nfor i in l:\n    pass\n"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Starlark values backing the `ctx` object handed to plugin hooks.
+//!
+//! All views share one [`State`]; request and response objects read it on
+//! every attribute access, so mutations made through one handle are visible
+//! through every other.
+
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use allocative::Allocative;
+use anyhow::Context as _;
+use bytes::Bytes;
+use http::{HeaderName, HeaderValue};
+use starlark::environment::{Methods, MethodsBuilder, MethodsStatic};
+use starlark::values::dict::{AllocDict, DictRef};
+use starlark::values::list::AllocList;
+use starlark::values::none::NoneType;
+use starlark::values::structs::AllocStruct;
+use starlark::values::{
+    Heap, NoSerialize, ProvidesStaticType, StarlarkValue, Value, ValueLike, starlark_value,
+};
+use starlark::{starlark_module, starlark_simple_value};
+
+use crate::domain::gts_helpers::format_route_gts;
+use crate::domain::plugin::{PluginError, PluginExchange, PluginHeaders, PluginOutcome};
+
+/// Longest log message a plugin may emit; longer messages are truncated.
+const MAX_LOG_CHARS: usize = 1024;
+
+#[derive(Debug)]
+pub(super) struct State {
+    pub(super) exchange: PluginExchange,
+    config: serde_json::Value,
+    /// Set by `ctx.next()`/`reject()`/`respond()`, used when the hook
+    /// returns `None`.
+    outcome: Option<PluginOutcome>,
+}
+
+impl State {
+    pub(super) fn new(exchange: PluginExchange, config: serde_json::Value) -> Self {
+        Self {
+            exchange,
+            config,
+            outcome: None,
+        }
+    }
+}
+
+type SharedState = Arc<Mutex<State>>;
+
+pub(super) fn lock(state: &SharedState) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Interpret a hook's return value.
+pub(super) fn outcome(state: &SharedState, ret: Value<'_>) -> Result<PluginOutcome, PluginError> {
+    if let Some(outcome) = ret.downcast_ref::<Outcome>() {
+        return Ok(outcome.0.clone());
+    }
+    if ret.is_none() {
+        return Ok(lock(state).outcome.take().unwrap_or(PluginOutcome::Next));
+    }
+    Err(PluginError::Script(format!(
+        "hook returned a value of type '{}'; expected ctx.next(), ctx.reject() or ctx.respond()",
+        ret.get_type()
+    )))
+}
+
+fn json_to_value<'v>(heap: &'v Heap, json: &serde_json::Value) -> Value<'v> {
+    match json {
+        serde_json::Value::Null => Value::new_none(),
+        serde_json::Value::Bool(b) => Value::new_bool(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => heap.alloc(i),
+            None => heap.alloc(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => heap.alloc(s.as_str()),
+        serde_json::Value::Array(items) => {
+            heap.alloc(AllocList(items.iter().map(|v| json_to_value(heap, v))))
+        }
+        serde_json::Value::Object(fields) => heap.alloc(AllocDict(
+            fields
+                .iter()
+                .map(|(k, v)| (k.as_str(), json_to_value(heap, v))),
+        )),
+    }
+}
+
+fn parse_json<'v>(heap: &'v Heap, body: &Bytes) -> anyhow::Result<Value<'v>> {
+    let json: serde_json::Value = serde_json::from_slice(body).context("body is not valid JSON")?;
+    Ok(json_to_value(heap, &json))
+}
+
+fn encode_json(value: Value<'_>) -> anyhow::Result<Bytes> {
+    let json = value.to_json().map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(Bytes::from(json))
+}
+
+fn http_status(status: i32, allowed: std::ops::RangeInclusive<u16>) -> anyhow::Result<u16> {
+    u16::try_from(status)
+        .ok()
+        .filter(|s| allowed.contains(s))
+        .with_context(|| format!("status {status} is out of range {allowed:?}"))
+}
+
+fn truncate(message: &str) -> &str {
+    match message.char_indices().nth(MAX_LOG_CHARS) {
+        Some((end, _)) => &message[..end],
+        None => message,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ctx
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+pub(super) struct Ctx {
+    #[allocative(skip)]
+    state: SharedState,
+}
+
+impl Ctx {
+    pub(super) fn new(state: SharedState) -> Self {
+        Self { state }
+    }
+}
+
+impl fmt::Display for Ctx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<plugin context>")
+    }
+}
+
+starlark_simple_value!(Ctx);
+
+#[starlark_value(type = "plugin_context")]
+impl<'v> StarlarkValue<'v> for Ctx {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(ctx_methods)
+    }
+
+    fn get_attr(&self, attribute: &str, heap: &'v Heap) -> Option<Value<'v>> {
+        let state = lock(&self.state);
+        let value = match attribute {
+            "request" => heap.alloc(Request {
+                state: Arc::clone(&self.state),
+            }),
+            "response" if state.exchange.response.is_some() => heap.alloc(Response {
+                state: Arc::clone(&self.state),
+            }),
+            "response" => Value::new_none(),
+            "error" => match state.exchange.error {
+                Some(ref e) => heap.alloc(AllocStruct([
+                    ("status", heap.alloc(i32::from(e.status))),
+                    ("code", heap.alloc(e.code.as_str())),
+                    ("message", heap.alloc(e.message.as_str())),
+                    ("upstream", Value::new_bool(e.upstream)),
+                ])),
+                None => Value::new_none(),
+            },
+            "config" => json_to_value(heap, &state.config),
+            "route" => heap.alloc(AllocStruct([(
+                "id",
+                heap.alloc(format_route_gts(state.exchange.route_id)),
+            )])),
+            "log" => heap.alloc(Log {
+                route_id: state.exchange.route_id.to_string(),
+            }),
+            "time" => heap.alloc(Time {
+                started: state.exchange.started,
+            }),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn finish(this: Value<'_>, outcome: PluginOutcome) -> anyhow::Result<Outcome> {
+    let ctx = this
+        .downcast_ref::<Ctx>()
+        .context("expected a plugin context")?;
+    lock(&ctx.state).outcome = Some(outcome.clone());
+    Ok(Outcome(outcome))
+}
+
+#[starlark_module]
+fn ctx_methods(builder: &mut MethodsBuilder) {
+    /// Continue with the next plugin.
+    fn next<'v>(this: Value<'v>) -> anyhow::Result<Outcome> {
+        finish(this, PluginOutcome::Next)
+    }
+
+    /// Stop the chain and answer with a gateway error.
+    fn reject<'v>(
+        this: Value<'v>,
+        status: i32,
+        code: &str,
+        message: &str,
+    ) -> anyhow::Result<Outcome> {
+        let outcome = PluginOutcome::Reject {
+            status: http_status(status, 400..=599)?,
+            code: code.to_string(),
+            message: message.to_string(),
+        };
+        finish(this, outcome)
+    }
+
+    /// Stop the chain and answer with `body` (a string, or a value encoded
+    /// as JSON).
+    fn respond<'v>(this: Value<'v>, status: i32, body: Value<'v>) -> anyhow::Result<Outcome> {
+        let body = match body.unpack_str() {
+            Some(s) => s.to_string(),
+            None => body.to_json().map_err(|e| anyhow::anyhow!("{e}"))?,
+        };
+        let outcome = PluginOutcome::Respond {
+            status: http_status(status, 100..=599)?,
+            body,
+        };
+        finish(this, outcome)
+    }
+}
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+pub(super) struct Outcome(#[allocative(skip)] PluginOutcome);
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<plugin outcome>")
+    }
+}
+
+starlark_simple_value!(Outcome);
+
+#[starlark_value(type = "plugin_outcome")]
+impl<'v> StarlarkValue<'v> for Outcome {}
+
+// ---------------------------------------------------------------------------
+// ctx.request
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+struct Request {
+    #[allocative(skip)]
+    state: SharedState,
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<request>")
+    }
+}
+
+starlark_simple_value!(Request);
+
+#[starlark_value(type = "plugin_request")]
+impl<'v> StarlarkValue<'v> for Request {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(request_methods)
+    }
+
+    fn get_attr(&self, attribute: &str, heap: &'v Heap) -> Option<Value<'v>> {
+        let state = lock(&self.state);
+        let request = &state.exchange.request;
+        let value = match attribute {
+            "method" => heap.alloc(request.method.as_str()),
+            "path" => heap.alloc(request.path.as_str()),
+            "query" => heap.alloc(AllocDict(
+                request
+                    .query
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), heap.alloc(v.as_str()))),
+            )),
+            "headers" => heap.alloc(Headers {
+                state: Arc::clone(&self.state),
+                side: Side::Request,
+            }),
+            "body" => heap.alloc(String::from_utf8_lossy(&request.body).as_ref()),
+            "tenant_id" => heap.alloc(state.exchange.tenant_id.to_string()),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn request_state(this: Value<'_>) -> anyhow::Result<&SharedState> {
+    Ok(&this
+        .downcast_ref::<Request>()
+        .context("expected a request")?
+        .state)
+}
+
+#[starlark_module]
+fn request_methods(builder: &mut MethodsBuilder) {
+    /// Replace the outbound path.
+    fn set_path<'v>(this: Value<'v>, path: &str) -> anyhow::Result<NoneType> {
+        anyhow::ensure!(path.starts_with('/'), "path must start with '/'");
+        lock(request_state(this)?).exchange.request.path = path.to_string();
+        Ok(NoneType)
+    }
+
+    /// Replace all query parameters.
+    fn set_query<'v>(this: Value<'v>, query: Value<'v>) -> anyhow::Result<NoneType> {
+        let dict = DictRef::from_value(query).context("query must be a dict")?;
+        let params = dict.iter().map(|(k, v)| (k.to_str(), v.to_str())).collect();
+        lock(request_state(this)?).exchange.request.query = params;
+        Ok(NoneType)
+    }
+
+    /// Append a query parameter.
+    fn add_query<'v>(this: Value<'v>, key: &str, value: Value<'v>) -> anyhow::Result<NoneType> {
+        lock(request_state(this)?)
+            .exchange
+            .request
+            .query
+            .push((key.to_string(), value.to_str()));
+        Ok(NoneType)
+    }
+
+    /// Parse the request body as JSON.
+    fn json<'v>(this: Value<'v>, heap: &'v Heap) -> anyhow::Result<Value<'v>> {
+        let body = lock(request_state(this)?).exchange.request.body.clone();
+        parse_json(heap, &body)
+    }
+
+    /// Replace the request body with `value` encoded as JSON.
+    fn set_json<'v>(this: Value<'v>, value: Value<'v>) -> anyhow::Result<NoneType> {
+        let body = encode_json(value)?;
+        let mut state = lock(request_state(this)?);
+        state.exchange.request.body = body;
+        state
+            .exchange
+            .request
+            .headers
+            .set("content-type", "application/json");
+        Ok(NoneType)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ctx.response
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+struct Response {
+    #[allocative(skip)]
+    state: SharedState,
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<response>")
+    }
+}
+
+starlark_simple_value!(Response);
+
+#[starlark_value(type = "plugin_response")]
+impl<'v> StarlarkValue<'v> for Response {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(response_methods)
+    }
+
+    fn get_attr(&self, attribute: &str, heap: &'v Heap) -> Option<Value<'v>> {
+        let state = lock(&self.state);
+        let response = state.exchange.response.as_ref()?;
+        let value = match attribute {
+            "status" => heap.alloc(i32::from(response.status)),
+            "headers" => heap.alloc(Headers {
+                state: Arc::clone(&self.state),
+                side: Side::Response,
+            }),
+            "body" => heap.alloc(String::from_utf8_lossy(&response.body).as_ref()),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Run `f` on the response of the exchange behind `this`.
+fn with_response<T>(
+    this: Value<'_>,
+    f: impl FnOnce(&mut crate::domain::plugin::PluginResponse) -> T,
+) -> anyhow::Result<T> {
+    let response = this
+        .downcast_ref::<Response>()
+        .context("expected a response")?;
+    let mut state = lock(&response.state);
+    let response = state
+        .exchange
+        .response
+        .as_mut()
+        .context("no response in this phase")?;
+    Ok(f(response))
+}
+
+#[starlark_module]
+fn response_methods(builder: &mut MethodsBuilder) {
+    /// Parse the response body as JSON.
+    fn json<'v>(this: Value<'v>, heap: &'v Heap) -> anyhow::Result<Value<'v>> {
+        let body = with_response(this, |r| r.body.clone())?;
+        parse_json(heap, &body)
+    }
+
+    /// Replace the response body with `value` encoded as JSON.
+    fn set_json<'v>(this: Value<'v>, value: Value<'v>) -> anyhow::Result<NoneType> {
+        let body = encode_json(value)?;
+        with_response(this, |r| {
+            r.body = body;
+            r.headers.set("content-type", "application/json");
+        })?;
+        Ok(NoneType)
+    }
+
+    /// Override the status code returned to the client.
+    fn set_status<'v>(this: Value<'v>, status: i32) -> anyhow::Result<NoneType> {
+        let status = http_status(status, 100..=599)?;
+        with_response(this, |r| r.status = status)?;
+        Ok(NoneType)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Headers
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Request,
+    Response,
+}
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+struct Headers {
+    #[allocative(skip)]
+    state: SharedState,
+    #[allocative(skip)]
+    side: Side,
+}
+
+impl fmt::Display for Headers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<headers>")
+    }
+}
+
+starlark_simple_value!(Headers);
+
+#[starlark_value(type = "plugin_headers")]
+impl<'v> StarlarkValue<'v> for Headers {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(headers_methods)
+    }
+}
+
+/// Run `f` on the header view behind `this`.
+fn with_headers<T>(this: Value<'_>, f: impl FnOnce(&mut PluginHeaders) -> T) -> anyhow::Result<T> {
+    let headers = this.downcast_ref::<Headers>().context("expected headers")?;
+    let mut state = lock(&headers.state);
+    let view = match headers.side {
+        Side::Request => &mut state.exchange.request.headers,
+        Side::Response => {
+            &mut state
+                .exchange
+                .response
+                .as_mut()
+                .context("no response in this phase")?
+                .headers
+        }
+    };
+    Ok(f(view))
+}
+
+fn check_header(name: &str, value: &str) -> anyhow::Result<()> {
+    HeaderName::from_bytes(name.as_bytes())
+        .with_context(|| format!("invalid header name '{name}'"))?;
+    HeaderValue::from_str(value).with_context(|| format!("invalid value for header '{name}'"))?;
+    Ok(())
+}
+
+#[starlark_module]
+fn headers_methods(builder: &mut MethodsBuilder) {
+    /// Value of header `name`, or `None`.
+    fn get<'v>(this: Value<'v>, name: &str, heap: &'v Heap) -> anyhow::Result<Value<'v>> {
+        with_headers(this, |h| {
+            h.get(name).map_or_else(Value::new_none, |v| heap.alloc(v))
+        })
+    }
+
+    /// Set header `name`, replacing existing values.
+    fn set<'v>(this: Value<'v>, name: &str, value: &str) -> anyhow::Result<NoneType> {
+        check_header(name, value)?;
+        with_headers(this, |h| h.set(name, value))?;
+        Ok(NoneType)
+    }
+
+    /// Append a value to header `name`.
+    fn add<'v>(this: Value<'v>, name: &str, value: &str) -> anyhow::Result<NoneType> {
+        check_header(name, value)?;
+        with_headers(this, |h| h.add(name, value))?;
+        Ok(NoneType)
+    }
+
+    /// Remove header `name`.
+    fn remove<'v>(this: Value<'v>, name: &str) -> anyhow::Result<NoneType> {
+        with_headers(this, |h| h.remove(name))?;
+        Ok(NoneType)
+    }
+
+    /// Header names.
+    fn keys<'v>(this: Value<'v>, heap: &'v Heap) -> anyhow::Result<Value<'v>> {
+        with_headers(this, |h| heap.alloc(AllocList(h.keys())))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ctx.log / ctx.time
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+struct Log {
+    route_id: String,
+}
+
+impl fmt::Display for Log {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<log>")
+    }
+}
+
+starlark_simple_value!(Log);
+
+#[starlark_value(type = "plugin_log")]
+impl<'v> StarlarkValue<'v> for Log {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(log_methods)
+    }
+}
+
+/// Route id and optional `data` (as JSON) to attach to a log line.
+fn log_fields<'v>(this: Value<'v>, data: Option<Value<'v>>) -> anyhow::Result<(String, String)> {
+    let log = this.downcast_ref::<Log>().context("expected a logger")?;
+    let data = match data {
+        Some(d) => d.to_json().map_err(|e| anyhow::anyhow!("{e}"))?,
+        None => String::new(),
+    };
+    Ok((log.route_id.clone(), truncate(&data).to_string()))
+}
+
+#[starlark_module]
+fn log_methods(builder: &mut MethodsBuilder) {
+    fn info<'v>(this: Value<'v>, msg: &str, data: Option<Value<'v>>) -> anyhow::Result<NoneType> {
+        let (route_id, data) = log_fields(this, data)?;
+        tracing::info!(target: "oagw::plugin", route_id = %route_id, data = %data, "{}", truncate(msg));
+        Ok(NoneType)
+    }
+
+    fn warn<'v>(this: Value<'v>, msg: &str, data: Option<Value<'v>>) -> anyhow::Result<NoneType> {
+        let (route_id, data) = log_fields(this, data)?;
+        tracing::warn!(target: "oagw::plugin", route_id = %route_id, data = %data, "{}", truncate(msg));
+        Ok(NoneType)
+    }
+
+    fn error<'v>(this: Value<'v>, msg: &str, data: Option<Value<'v>>) -> anyhow::Result<NoneType> {
+        let (route_id, data) = log_fields(this, data)?;
+        tracing::error!(target: "oagw::plugin", route_id = %route_id, data = %data, "{}", truncate(msg));
+        Ok(NoneType)
+    }
+}
+
+#[derive(Debug, ProvidesStaticType, NoSerialize, Allocative)]
+struct Time {
+    #[allocative(skip)]
+    started: Instant,
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<time>")
+    }
+}
+
+starlark_simple_value!(Time);
+
+#[starlark_value(type = "plugin_time")]
+impl<'v> StarlarkValue<'v> for Time {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(time_methods)
+    }
+}
+
+#[starlark_module]
+fn time_methods(builder: &mut MethodsBuilder) {
+    /// Milliseconds since the gateway received the request.
+    fn elapsed_ms<'v>(this: Value<'v>) -> anyhow::Result<i64> {
+        let time = this.downcast_ref::<Time>().context("expected a clock")?;
+        Ok(i64::try_from(time.started.elapsed().as_millis()).unwrap_or(i64::MAX))
+    }
+}
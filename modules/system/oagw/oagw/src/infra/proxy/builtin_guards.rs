@@ -0,0 +1,362 @@
+//! Enforcement of the builtin CORS and timeout guards.
+//!
+//! CORS preflights are answered by the gateway without reaching the upstream;
+//! cross-origin requests from allowed origins get the CORS response headers.
+//! The timeout guard's request deadline also covers the response body, which
+//! is cut off with an error once the deadline passes.
+
+use futures_util::StreamExt;
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use oagw_sdk::Body;
+use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::{BodyStream, BoxError};
+
+use crate::domain::builtin_guards::CorsConfig;
+use crate::domain::error::DomainError;
+
+/// The method a CORS preflight asks about, if `method` and `headers` make
+/// up one: `OPTIONS` with `Origin` and `Access-Control-Request-Method`.
+pub(super) fn preflight_method<'a>(method: &Method, headers: &'a HeaderMap) -> Option<&'a str> {
+    if method != Method::OPTIONS || !headers.contains_key(ORIGIN) {
+        return None;
+    }
+    headers
+        .get(ACCESS_CONTROL_REQUEST_METHOD)
+        .and_then(|v| v.to_str().ok())
+}
+
+/// Answer a CORS preflight: `204 No Content` listing what `cors` allows, or
+/// an error when the origin, method or headers it announces are not allowed.
+pub(super) fn preflight_response(
+    cors: &CorsConfig,
+    req_headers: &HeaderMap,
+    instance_uri: &str,
+) -> Result<http::Response<Body>, DomainError> {
+    let origin = allowed_origin(cors, req_headers, instance_uri)?.ok_or_else(|| {
+        DomainError::CorsOriginNotAllowed {
+            detail: "CORS preflight without Origin".into(),
+            instance: instance_uri.to_string(),
+        }
+    })?;
+    let method = preflight_method(&Method::OPTIONS, req_headers).unwrap_or_default();
+    if !cors.allows_method(method) {
+        return Err(DomainError::CorsMethodNotAllowed {
+            detail: format!("method '{method}' is not in the allowed methods list"),
+            instance: instance_uri.to_string(),
+        });
+    }
+    let requested = match req_headers.get(ACCESS_CONTROL_REQUEST_HEADERS) {
+        Some(v) => v.to_str().map_err(|_| DomainError::CorsHeadersNotAllowed {
+            detail: "Access-Control-Request-Headers is not a valid header list".into(),
+            instance: instance_uri.to_string(),
+        })?,
+        None => "",
+    };
+    if let Some(header) = cors.disallowed_header(requested) {
+        return Err(DomainError::CorsHeadersNotAllowed {
+            detail: format!("header '{header}' is not in the allowed headers list"),
+            instance: instance_uri.to_string(),
+        });
+    }
+
+    let mut resp = http::Response::new(Body::Empty);
+    *resp.status_mut() = StatusCode::NO_CONTENT;
+    let headers = resp.headers_mut();
+    headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    headers.insert(
+        ACCESS_CONTROL_ALLOW_METHODS,
+        list_value(&cors.allowed_methods, instance_uri)?,
+    );
+    if !cors.allowed_headers.is_empty() {
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_HEADERS,
+            list_value(&cors.allowed_headers, instance_uri)?,
+        );
+    }
+    headers.insert(ACCESS_CONTROL_MAX_AGE, HeaderValue::from(cors.max_age));
+    if cors.allow_credentials {
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_CREDENTIALS,
+            HeaderValue::from_static("true"),
+        );
+    }
+    headers.insert(VARY, HeaderValue::from_static("Origin"));
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+/// CORS headers added to the response of an allowed cross-origin request.
+#[derive(Debug, Clone)]
+pub(super) struct CorsResponseHeaders {
+    origin: HeaderValue,
+    expose: Option<HeaderValue>,
+    credentials: bool,
+}
+
+impl CorsResponseHeaders {
+    /// Check the `Origin` of a request against `cors`. Requests without one
+    /// are not cross-origin browser requests and pass unchanged.
+    pub(super) fn for_request(
+        cors: &CorsConfig,
+        req_headers: &HeaderMap,
+        instance_uri: &str,
+    ) -> Result<Option<Self>, DomainError> {
+        let Some(origin) = allowed_origin(cors, req_headers, instance_uri)? else {
+            return Ok(None);
+        };
+        let expose = if cors.expose_headers.is_empty() {
+            None
+        } else {
+            Some(list_value(&cors.expose_headers, instance_uri)?)
+        };
+        Ok(Some(Self {
+            origin,
+            expose,
+            credentials: cors.allow_credentials,
+        }))
+    }
+
+    /// Set the CORS headers on `headers`, replacing any the upstream sent.
+    pub(super) fn apply(&self, headers: &mut HeaderMap) {
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, self.origin.clone());
+        match self.expose {
+            Some(ref expose) => headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, expose.clone()),
+            None => headers.remove(ACCESS_CONTROL_EXPOSE_HEADERS),
+        };
+        if self.credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        } else {
+            headers.remove(ACCESS_CONTROL_ALLOW_CREDENTIALS);
+        }
+        let varies_by_origin = headers
+            .get_all(VARY)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(',').map(str::trim))
+            .any(|v| v == "*" || v.eq_ignore_ascii_case("origin"));
+        if !varies_by_origin {
+            headers.append(VARY, HeaderValue::from_static("Origin"));
+        }
+    }
+}
+
+/// The request's `Origin`, if it has one that `cors` allows.
+fn allowed_origin(
+    cors: &CorsConfig,
+    req_headers: &HeaderMap,
+    instance_uri: &str,
+) -> Result<Option<HeaderValue>, DomainError> {
+    let Some(origin) = req_headers.get(ORIGIN) else {
+        return Ok(None);
+    };
+    // Configs stored before this was validated may still combine the two; a
+    // credentialed wildcard would let any site act on the user's behalf.
+    if cors.allow_credentials && cors.allows_any_origin() {
+        return Err(DomainError::CorsOriginNotAllowed {
+            detail: "CORS guard allows credentials with the wildcard origin '*'; cross-origin requests are refused".into(),
+            instance: instance_uri.to_string(),
+        });
+    }
+    match origin.to_str() {
+        Ok(o) if cors.allows_origin(o) => Ok(Some(origin.clone())),
+        Ok(o) => Err(DomainError::CorsOriginNotAllowed {
+            detail: format!("origin '{o}' is not in the allowed origins list"),
+            instance: instance_uri.to_string(),
+        }),
+        Err(_) => Err(DomainError::CorsOriginNotAllowed {
+            detail: "Origin header is not a valid origin".into(),
+            instance: instance_uri.to_string(),
+        }),
+    }
+}
+
+fn list_value(items: &[String], instance_uri: &str) -> Result<HeaderValue, DomainError> {
+    HeaderValue::from_str(&items.join(", ")).map_err(|e| DomainError::PluginFailed {
+        detail: format!("CORS guard config is unusable: {e}"),
+        instance: instance_uri.to_string(),
+    })
+}
+
+/// Wrap a response body so that it fails once `deadline` has passed before
+/// the upstream finished sending it.
+pub(super) fn with_deadline(body: BodyStream, deadline: tokio::time::Instant) -> BodyStream {
+    Box::pin(futures_util::stream::unfold(
+        Some(body),
+        move |body| async move {
+            let mut body = body?;
+            match tokio::time::timeout_at(deadline, body.next()).await {
+                Ok(Some(chunk)) => Some((chunk, Some(body))),
+                Ok(None) => None,
+                Err(_) => Some((
+                    Err(BoxError::from(
+                        "request timeout elapsed while relaying the response",
+                    )),
+                    None,
+                )),
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use serde_json::json;
+
+    use super::*;
+
+    fn cors(config: serde_json::Value) -> CorsConfig {
+        serde_json::from_value(config).unwrap()
+    }
+
+    fn request(pairs: &[(&str, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(k, v)| {
+                (
+                    http::HeaderName::from_bytes(k.as_bytes()).unwrap(),
+                    HeaderValue::from_str(v).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn preflight_requires_origin_and_requested_method() {
+        let full = request(&[
+            ("origin", "https://app.example.com"),
+            ("access-control-request-method", "POST"),
+        ]);
+        assert_eq!(preflight_method(&Method::OPTIONS, &full), Some("POST"));
+        assert_eq!(preflight_method(&Method::GET, &full), None);
+        let no_origin = request(&[("access-control-request-method", "POST")]);
+        assert_eq!(preflight_method(&Method::OPTIONS, &no_origin), None);
+    }
+
+    #[test]
+    fn preflight_lists_what_is_allowed() {
+        let config = cors(json!({
+            "allowed_origins": ["https://app.example.com"],
+            "max_age": 3600,
+            "allow_credentials": true
+        }));
+        let resp = preflight_response(
+            &config,
+            &request(&[
+                ("origin", "https://app.example.com"),
+                ("access-control-request-method", "POST"),
+                (
+                    "access-control-request-headers",
+                    "content-type, authorization",
+                ),
+            ]),
+            "/test",
+        )
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let h = resp.headers();
+        assert_eq!(h[ACCESS_CONTROL_ALLOW_ORIGIN], "https://app.example.com");
+        assert_eq!(h[ACCESS_CONTROL_ALLOW_METHODS], "GET, POST");
+        assert_eq!(
+            h[ACCESS_CONTROL_ALLOW_HEADERS],
+            "Content-Type, Authorization"
+        );
+        assert_eq!(h[ACCESS_CONTROL_MAX_AGE], "3600");
+        assert_eq!(h[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(h[VARY], "Origin");
+    }
+
+    #[test]
+    fn preflight_rejects_what_is_not_allowed() {
+        let config = cors(json!({"allowed_origins": ["https://app.example.com"]}));
+        let preflight = |origin: &str, method: &str, headers: &str| {
+            preflight_response(
+                &config,
+                &request(&[
+                    ("origin", origin),
+                    ("access-control-request-method", method),
+                    ("access-control-request-headers", headers),
+                ]),
+                "/test",
+            )
+        };
+        assert!(matches!(
+            preflight("https://evil.com", "GET", "content-type"),
+            Err(DomainError::CorsOriginNotAllowed { .. })
+        ));
+        assert!(matches!(
+            preflight("https://app.example.com", "DELETE", "content-type"),
+            Err(DomainError::CorsMethodNotAllowed { .. })
+        ));
+        assert!(matches!(
+            preflight("https://app.example.com", "GET", "x-debug"),
+            Err(DomainError::CorsHeadersNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn credentialed_wildcard_is_refused_at_request_time() {
+        let config = cors(json!({"allowed_origins": ["*"], "allow_credentials": true}));
+        let result = CorsResponseHeaders::for_request(
+            &config,
+            &request(&[("origin", "https://app.example.com")]),
+            "/test",
+        );
+        assert!(matches!(
+            result,
+            Err(DomainError::CorsOriginNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn response_headers_echo_the_origin() {
+        let config = cors(json!({
+            "allowed_origins": ["*"],
+            "expose_headers": ["X-Request-ID"]
+        }));
+        let headers = CorsResponseHeaders::for_request(
+            &config,
+            &request(&[("origin", "https://app.example.com")]),
+            "/test",
+        )
+        .unwrap()
+        .unwrap();
+        let mut resp = request(&[
+            ("access-control-allow-origin", "*"),
+            ("vary", "Accept-Encoding"),
+        ]);
+        headers.apply(&mut resp);
+        assert_eq!(resp[ACCESS_CONTROL_ALLOW_ORIGIN], "https://app.example.com");
+        assert_eq!(resp[ACCESS_CONTROL_EXPOSE_HEADERS], "X-Request-ID");
+        assert!(!resp.contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+        let vary: Vec<_> = resp.get_all(VARY).iter().collect();
+        assert_eq!(vary, ["Accept-Encoding", "Origin"]);
+
+        assert!(
+            CorsResponseHeaders::for_request(&config, &HeaderMap::new(), "/test")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn deadline_cuts_off_a_slow_body() {
+        let chunks = futures_util::stream::iter([Ok::<_, BoxError>(Bytes::from_static(b"a"))])
+            .chain(futures_util::stream::pending());
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(20);
+        let mut body = with_deadline(Box::pin(chunks), deadline);
+        assert_eq!(body.next().await.unwrap().unwrap(), "a");
+        assert!(body.next().await.unwrap().is_err());
+        assert!(body.next().await.is_none());
+    }
+}
@@ -0,0 +1,529 @@
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::{Buf, Bytes, BytesMut};
+use http::{HeaderMap, HeaderValue, StatusCode, header};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::{Body, BodyStream, BoxError, Trailers};
+
+use crate::domain::error::DomainError;
+use crate::domain::grpc_transcoding::Transcoder;
+
+use super::{builtin_guards, headers};
+
+pub(crate) const GRPC_CONTENT_TYPE: &str = "application/grpc";
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Length-prefixed message header: compressed flag + big-endian length.
+const FRAME_HEADER_LEN: usize = 5;
+
+/// Largest message transcoded, gRPC's default receive limit.
+const MAX_MESSAGE_LEN: usize = 4 * 1024 * 1024;
+
+/// gRPC protocol headers forwarded on native calls regardless of the
+/// upstream's passthrough settings.
+const FORWARDED_PROTOCOL_HEADERS: &[&str] =
+    &["grpc-timeout", "grpc-encoding", "grpc-accept-encoding"];
+
+/// Compression negotiation headers, meaningless for a gateway-built body.
+const ENCODING_HEADERS: &[&str] = &["grpc-encoding", "grpc-accept-encoding"];
+
+/// Upstream response body as received from the HTTP client.
+type UpstreamBody = http::Response<reqwest::Body>;
+
+/// Whether the inbound request is a native gRPC call.
+pub(crate) fn is_grpc_request(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with(GRPC_CONTENT_TYPE))
+}
+
+/// Set the headers every gRPC upstream call needs. Transcoded calls carry an
+/// uncompressed body built by the gateway, so the client's encoding headers
+/// and body length do not apply to them.
+pub(crate) fn apply_request_headers(
+    inbound: &HeaderMap,
+    outbound: &mut HeaderMap,
+    transcoded: bool,
+) {
+    if transcoded {
+        outbound.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(GRPC_CONTENT_TYPE),
+        );
+        outbound.remove(header::CONTENT_LENGTH);
+        for name in ENCODING_HEADERS {
+            outbound.remove(*name);
+        }
+    } else {
+        for name in FORWARDED_PROTOCOL_HEADERS {
+            if let Some(value) = inbound.get(*name) {
+                outbound.insert(*name, value.clone());
+            }
+        }
+    }
+    outbound.insert(header::TE, HeaderValue::from_static("trailers"));
+}
+
+/// Parse a `grpc-timeout` header value such as `250m` or `5S`.
+pub(crate) fn parse_timeout(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("grpc-timeout")?.to_str().ok()?;
+    if value.len() < 2 || value.len() > 9 {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    let amount: u64 = amount.parse().ok()?;
+    Some(match unit {
+        "H" => Duration::from_secs(amount.saturating_mul(3600)),
+        "M" => Duration::from_secs(amount.saturating_mul(60)),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    })
+}
+
+/// Wrap one protobuf message in the gRPC length-prefixed framing.
+pub(crate) fn encode_frame(message: &[u8]) -> Result<Vec<u8>, String> {
+    let len = u32::try_from(message.len()).map_err(|_| "message too large".to_string())?;
+    let mut framed = Vec::with_capacity(FRAME_HEADER_LEN + message.len());
+    framed.push(0);
+    framed.extend_from_slice(&len.to_be_bytes());
+    framed.extend_from_slice(message);
+    Ok(framed)
+}
+
+/// Splits a gRPC body into messages as data frames arrive.
+#[derive(Default)]
+pub(crate) struct FrameDecoder {
+    buf: BytesMut,
+}
+
+impl FrameDecoder {
+    pub(crate) fn push(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Next complete message, or `None` until more data arrives.
+    pub(crate) fn next_message(&mut self) -> Result<Option<Bytes>, String> {
+        if self.buf.len() < FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+        if self.buf[0] != 0 {
+            return Err("compressed gRPC messages cannot be transcoded".into());
+        }
+        let len = u32::from_be_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]);
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= MAX_MESSAGE_LEN)
+            .ok_or_else(|| {
+                format!("gRPC message of {len} bytes exceeds {MAX_MESSAGE_LEN} bytes")
+            })?;
+        if self.buf.len() < FRAME_HEADER_LEN + len {
+            return Ok(None);
+        }
+        self.buf.advance(FRAME_HEADER_LEN);
+        Ok(Some(self.buf.split_to(len).freeze()))
+    }
+
+    /// Whether a partial message is left over.
+    pub(crate) fn has_remainder(&self) -> bool {
+        !self.buf.is_empty()
+    }
+}
+
+/// `grpc-status` and `grpc-message` of a finished call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GrpcStatus {
+    pub(crate) code: u32,
+    pub(crate) message: String,
+}
+
+impl GrpcStatus {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let code = headers.get("grpc-status")?.to_str().ok()?.parse().ok()?;
+        let message = headers
+            .get("grpc-message")
+            .map(|v| percent_decode(v.as_bytes()))
+            .unwrap_or_default();
+        Some(Self { code, message })
+    }
+
+    fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "code": self.code, "message": self.message })
+    }
+}
+
+/// HTTP status an HTTP/JSON client sees for a gRPC status code.
+///
+/// `RESOURCE_EXHAUSTED` is reported as 429 so clients apply the same backoff
+/// as for gateway rate limits.
+pub(crate) fn http_status(code: u32) -> StatusCode {
+    match code {
+        0 => StatusCode::OK,
+        1 => StatusCode::from_u16(499).unwrap_or(StatusCode::BAD_REQUEST),
+        3 | 9 | 11 => StatusCode::BAD_REQUEST,
+        4 => StatusCode::GATEWAY_TIMEOUT,
+        5 => StatusCode::NOT_FOUND,
+        6 | 10 => StatusCode::CONFLICT,
+        7 => StatusCode::FORBIDDEN,
+        8 => StatusCode::TOO_MANY_REQUESTS,
+        12 => StatusCode::NOT_IMPLEMENTED,
+        14 => StatusCode::SERVICE_UNAVAILABLE,
+        16 => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Percent-encode a `grpc-message` value (printable ASCII except `%` is
+/// sent as is).
+pub(crate) fn percent_encode(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    for b in message.bytes() {
+        if (0x20..0x7f).contains(&b) && b != b'%' {
+            out.push(char::from(b));
+        } else {
+            let _ = write!(out, "%{b:02X}");
+        }
+    }
+    out
+}
+
+fn percent_decode(value: &[u8]) -> String {
+    let mut out = Vec::with_capacity(value.len());
+    let mut i = 0;
+    while i < value.len() {
+        if value[i] == b'%'
+            && let Some(hex) = value.get(i + 1..i + 3)
+            && let Ok(hex) = std::str::from_utf8(hex)
+            && let Ok(b) = u8::from_str_radix(hex, 16)
+        {
+            out.push(b);
+            i += 3;
+        } else {
+            out.push(value[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Stream a native gRPC response body to the client, recording the
+/// upstream trailers in `trailers` when they arrive. `guard` is held until
+/// the stream ends.
+pub(crate) fn passthrough_body<G: Send + 'static>(
+    response: UpstreamBody,
+    trailers: Trailers,
+    guard: G,
+) -> BodyStream {
+    let body = response.into_body();
+    Box::pin(futures_util::stream::unfold(
+        (body, trailers, guard),
+        |(mut body, trailers, guard)| async move {
+            loop {
+                let frame = match body.frame().await? {
+                    Ok(frame) => frame,
+                    Err(e) => return Some((Err(Box::new(e) as BoxError), (body, trailers, guard))),
+                };
+                match frame.into_data() {
+                    Ok(data) => return Some((Ok(data), (body, trailers, guard))),
+                    Err(frame) => {
+                        if let Ok(mut t) = frame.into_trailers() {
+                            headers::sanitize_response_headers(&mut t);
+                            trailers.set(t);
+                        }
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Convert the upstream gRPC response of a transcoded call into JSON: one
+/// object for unary methods, one line per message for server streaming.
+/// Messages are bounded by [`MAX_MESSAGE_LEN`], and the response must be
+/// complete by `deadline`.
+pub(crate) async fn transcode_response<G: Send + 'static>(
+    response: UpstreamBody,
+    transcoder: Arc<Transcoder>,
+    guard: G,
+    deadline: Option<tokio::time::Instant>,
+    instance: &str,
+) -> Result<http::Response<Body>, DomainError> {
+    // Trailers-only responses carry the status in the headers.
+    if let Some(status) = GrpcStatus::from_headers(response.headers())
+        && !status.is_ok()
+    {
+        return Ok(status_response(&status));
+    }
+
+    if transcoder.is_server_streaming() {
+        let stream = ndjson_stream(response, transcoder, guard);
+        let stream = match deadline {
+            Some(deadline) => builtin_guards::with_deadline(stream, deadline),
+            None => stream,
+        };
+        let mut resp = http::Response::new(Body::Stream(stream));
+        resp.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(NDJSON_CONTENT_TYPE),
+        );
+        resp.extensions_mut().insert(ErrorSource::Upstream);
+        return Ok(resp);
+    }
+
+    let protocol_error = |detail: String| DomainError::ProtocolError {
+        detail,
+        instance: instance.to_string(),
+    };
+    let collect = Limited::new(response.into_body(), FRAME_HEADER_LEN + MAX_MESSAGE_LEN).collect();
+    let collected = match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, collect)
+            .await
+            .map_err(|_| DomainError::RequestTimeout {
+                detail: "gRPC response exceeded the route's request timeout".into(),
+                instance: instance.to_string(),
+            })?,
+        None => collect.await,
+    }
+    .map_err(|e| {
+        if e.is::<LengthLimitError>() {
+            protocol_error(format!(
+                "gRPC response exceeds {} bytes",
+                FRAME_HEADER_LEN + MAX_MESSAGE_LEN
+            ))
+        } else {
+            DomainError::DownstreamError {
+                detail: format!("failed to read gRPC response: {e}"),
+                instance: instance.to_string(),
+            }
+        }
+    })?;
+    drop(guard);
+    if let Some(status) = collected.trailers().and_then(GrpcStatus::from_headers)
+        && !status.is_ok()
+    {
+        return Ok(status_response(&status));
+    }
+
+    let mut decoder = FrameDecoder::default();
+    decoder.push(&collected.to_bytes());
+    let message = decoder
+        .next_message()
+        .map_err(protocol_error)?
+        .ok_or_else(|| protocol_error("gRPC response carried no message".into()))?;
+    if decoder.has_remainder() {
+        return Err(protocol_error(
+            "unary gRPC response carried more than one message".into(),
+        ));
+    }
+    let json = transcoder
+        .decode_response(&message)
+        .map_err(|e| protocol_error(format!("failed to decode gRPC response: {e}")))?;
+
+    let mut resp = http::Response::new(Body::from(json));
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    resp.extensions_mut().insert(ErrorSource::Upstream);
+    Ok(resp)
+}
+
+/// JSON error body for a failed transcoded call.
+fn status_response(status: &GrpcStatus) -> http::Response<Body> {
+    let mut resp = http::Response::new(Body::from(status.to_json().to_string()));
+    *resp.status_mut() = http_status(status.code);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    resp.extensions_mut().insert(ErrorSource::Upstream);
+    resp
+}
+
+struct NdjsonState<G> {
+    body: reqwest::Body,
+    decoder: FrameDecoder,
+    transcoder: Arc<Transcoder>,
+    _guard: G,
+}
+
+/// One JSON line per upstream message. A non-OK trailing status becomes a
+/// final `{"error": {...}}` line, since the response status is already sent.
+fn ndjson_stream<G: Send + 'static>(
+    response: UpstreamBody,
+    transcoder: Arc<Transcoder>,
+    guard: G,
+) -> BodyStream {
+    let state = NdjsonState {
+        body: response.into_body(),
+        decoder: FrameDecoder::default(),
+        transcoder,
+        _guard: guard,
+    };
+    Box::pin(futures_util::stream::unfold(
+        Some(state),
+        |state| async move {
+            let mut state = state?;
+            loop {
+                let frame = match state.body.frame().await {
+                    None => return None,
+                    Some(Ok(frame)) => frame,
+                    Some(Err(e)) => return Some((Err(Box::new(e) as BoxError), None)),
+                };
+                let frame = match frame.into_data() {
+                    Ok(data) => data,
+                    Err(frame) => {
+                        let status = frame
+                            .into_trailers()
+                            .ok()
+                            .and_then(|t| GrpcStatus::from_headers(&t));
+                        return match status {
+                            Some(status) if !status.is_ok() => {
+                                let mut line =
+                                    serde_json::json!({ "error": status.to_json() }).to_string();
+                                line.push('\n');
+                                Some((Ok(Bytes::from(line)), None))
+                            }
+                            _ => None,
+                        };
+                    }
+                };
+                state.decoder.push(&frame);
+                let mut lines = Vec::new();
+                loop {
+                    let message = match state.decoder.next_message() {
+                        Ok(Some(message)) => message,
+                        Ok(None) => break,
+                        Err(e) => return Some((Err(e.into()), None)),
+                    };
+                    match state.transcoder.decode_response(&message) {
+                        Ok(json) => {
+                            lines.extend_from_slice(&json);
+                            lines.push(b'\n');
+                        }
+                        Err(e) => return Some((Err(e.into()), None)),
+                    }
+                }
+                if !lines.is_empty() {
+                    return Some((Ok(Bytes::from(lines)), Some(state)));
+                }
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_grpc_content_types() {
+        let mut h = HeaderMap::new();
+        h.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/grpc+proto"),
+        );
+        assert!(is_grpc_request(&h));
+        h.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        assert!(!is_grpc_request(&h));
+    }
+
+    #[test]
+    fn parses_grpc_timeout_units() {
+        let timeout = |v: &'static str| {
+            let mut h = HeaderMap::new();
+            h.insert("grpc-timeout", HeaderValue::from_static(v));
+            parse_timeout(&h)
+        };
+        assert_eq!(timeout("250m"), Some(Duration::from_millis(250)));
+        assert_eq!(timeout("2S"), Some(Duration::from_secs(2)));
+        assert_eq!(timeout("1H"), Some(Duration::from_secs(3600)));
+        assert_eq!(timeout("5x"), None);
+        assert_eq!(timeout("123456789S"), None);
+    }
+
+    #[test]
+    fn transcoded_calls_drop_client_encoding() {
+        let mut inbound = HeaderMap::new();
+        inbound.insert("grpc-timeout", HeaderValue::from_static("1S"));
+        inbound.insert("grpc-encoding", HeaderValue::from_static("gzip"));
+
+        let mut native = HeaderMap::new();
+        apply_request_headers(&inbound, &mut native, false);
+        assert_eq!(native["grpc-encoding"], "gzip");
+        assert_eq!(native[header::TE], "trailers");
+
+        let mut transcoded = inbound.clone();
+        transcoded.insert(header::CONTENT_LENGTH, HeaderValue::from_static("12"));
+        apply_request_headers(&inbound, &mut transcoded, true);
+        assert_eq!(transcoded[header::CONTENT_TYPE], GRPC_CONTENT_TYPE);
+        assert_eq!(transcoded["grpc-timeout"], "1S");
+        assert!(transcoded.get("grpc-encoding").is_none());
+        assert!(transcoded.get(header::CONTENT_LENGTH).is_none());
+    }
+
+    #[test]
+    fn decoder_handles_split_and_batched_frames() {
+        let mut data = encode_frame(b"one").unwrap();
+        data.extend(encode_frame(b"").unwrap());
+        data.extend(encode_frame(b"three").unwrap());
+
+        let mut decoder = FrameDecoder::default();
+        decoder.push(&data[..4]);
+        assert_eq!(decoder.next_message().unwrap(), None);
+        decoder.push(&data[4..]);
+        assert_eq!(decoder.next_message().unwrap().unwrap(), "one");
+        assert_eq!(decoder.next_message().unwrap().unwrap(), "");
+        assert_eq!(decoder.next_message().unwrap().unwrap(), "three");
+        assert_eq!(decoder.next_message().unwrap(), None);
+        assert!(!decoder.has_remainder());
+
+        decoder.push(&[1, 0, 0, 0, 0]);
+        assert!(decoder.next_message().is_err());
+    }
+
+    #[test]
+    fn decoder_rejects_oversized_messages() {
+        let len = u32::try_from(MAX_MESSAGE_LEN + 1).unwrap();
+        let mut decoder = FrameDecoder::default();
+        decoder.push(&[0]);
+        decoder.push(&len.to_be_bytes());
+        let err = decoder.next_message().unwrap_err();
+        assert!(err.contains("exceeds"), "{err}");
+    }
+
+    #[test]
+    fn status_message_round_trips_percent_encoding() {
+        let encoded = percent_encode("no such user: 100%\n");
+        assert_eq!(encoded, "no such user: 100%25%0A");
+
+        let mut h = HeaderMap::new();
+        h.insert("grpc-status", HeaderValue::from_static("5"));
+        h.insert("grpc-message", HeaderValue::from_str(&encoded).unwrap());
+        let status = GrpcStatus::from_headers(&h).unwrap();
+        assert_eq!(status.code, 5);
+        assert_eq!(status.message, "no such user: 100%\n");
+    }
+
+    #[test]
+    fn maps_status_codes_to_http() {
+        assert_eq!(http_status(5), StatusCode::NOT_FOUND);
+        assert_eq!(http_status(8), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(http_status(14), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(http_status(16), StatusCode::UNAUTHORIZED);
+        assert_eq!(http_status(2), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
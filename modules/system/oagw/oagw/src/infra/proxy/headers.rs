@@ -21,6 +21,18 @@ const STRIPPED_HEADERS: &[&str] = &[
     "set-cookie",
 ];
 
+/// Set by the gateway on requests and responses that the `degrade`
+/// rate-limit strategy let through over the limit.
+pub const DEGRADED_HEADER: &str = "x-oagw-degraded";
+
+/// Pins a request to one endpoint of a multi-endpoint upstream, bypassing
+/// load balancing. Consumed by the gateway, never forwarded.
+pub const TARGET_HOST_HEADER: &str = "x-oagw-target-host";
+
+/// Set by the gateway on responses of routes with `response_cache`: `hit`,
+/// `miss` or `revalidated`.
+pub const CACHE_STATUS_HEADER: &str = "x-oagw-cache";
+
 /// Apply passthrough filter: decide which inbound headers to forward.
 /// Content-Type is always forwarded when present (needed for POST/PUT bodies).
 pub fn apply_passthrough(
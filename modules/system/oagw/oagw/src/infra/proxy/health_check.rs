@@ -0,0 +1,69 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use tokio::sync::watch;
+
+use crate::domain::load_balancer::EndpointPool;
+
+use super::request_builder;
+
+/// Start the active prober for `pool` unless one is already running or the
+/// upstream has no health check configured.
+///
+/// The prober only holds a weak reference and stops as soon as the pool is
+/// retired, i.e. replaced or evicted after its upstream changed.
+pub(crate) fn ensure_prober(client: &reqwest::Client, pool: &Arc<EndpointPool>) {
+    if !pool.claim_probes() {
+        return;
+    }
+    let client = client.clone();
+    let retirement = pool.retirement();
+    let pool = Arc::downgrade(pool);
+    tokio::spawn(run_prober(client, pool, retirement));
+}
+
+async fn run_prober(
+    client: reqwest::Client,
+    pool: Weak<EndpointPool>,
+    mut retirement: watch::Receiver<bool>,
+) {
+    loop {
+        if *retirement.borrow() {
+            return;
+        }
+        let Some(current) = pool.upgrade() else {
+            return;
+        };
+        let Some(hc) = current.health_check().cloned() else {
+            return;
+        };
+
+        let probes = current.endpoints().iter().map(|endpoint| {
+            let client = client.clone();
+            let expected = hc.expected_statuses.clone();
+            let url = request_builder::build_upstream_url(endpoint, &hc.path, "", &[]);
+            let timeout = Duration::from_secs(u64::from(hc.timeout_seconds));
+            async move {
+                match tokio::time::timeout(timeout, client.get(&url).send()).await {
+                    Ok(Ok(resp)) => expected.contains(&resp.status().as_u16()),
+                    _ => false,
+                }
+            }
+        });
+        let results = join_all(probes).await;
+        for (index, healthy) in results.into_iter().enumerate() {
+            current.record_probe(index, healthy);
+        }
+        drop(current);
+
+        // Wake early when the pool is retired or dropped.
+        let interval = Duration::from_secs(u64::from(hc.interval_seconds));
+        if tokio::time::timeout(interval, retirement.changed())
+            .await
+            .is_ok()
+        {
+            return;
+        }
+    }
+}
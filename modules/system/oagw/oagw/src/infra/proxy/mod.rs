@@ -1,5 +1,13 @@
+mod builtin_guards;
+pub(crate) mod grpc;
 pub(crate) mod headers;
+pub(crate) mod health_check;
+mod plugin_chain;
+pub(crate) mod request_body;
 pub(crate) mod request_builder;
+pub(crate) mod response_cache;
 pub(crate) mod service;
+pub(crate) mod usage_meter;
+pub(crate) mod websocket;
 
 pub(crate) use service::DataPlaneServiceImpl;
@@ -0,0 +1,325 @@
+use http::{HeaderMap, HeaderName, HeaderValue};
+use oagw_sdk::Body;
+use oagw_sdk::api::ErrorSource;
+
+use crate::domain::error::DomainError;
+use crate::domain::gts_helpers::{
+    CORS_GUARD_PLUGIN_ID, LOGGING_TRANSFORM_PLUGIN_ID, METRICS_TRANSFORM_PLUGIN_ID,
+    REQUEST_ID_TRANSFORM_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID, parse_plugin_gts,
+};
+use crate::domain::hierarchy::EffectiveUpstream;
+use crate::domain::model::{CustomPlugin, PluginPhase, PluginType, Route};
+use crate::domain::plugin::{
+    HeaderEdit, PluginExchange, PluginFailure, PluginOutcome, PluginRuntime,
+};
+use crate::domain::services::ControlPlaneService;
+
+use super::headers;
+
+/// Builtin guards and transforms. They are implemented by the gateway itself
+/// and never take part in the scripted chain.
+const BUILTIN_PLUGIN_IDS: [&str; 5] = [
+    CORS_GUARD_PLUGIN_ID,
+    TIMEOUT_GUARD_PLUGIN_ID,
+    LOGGING_TRANSFORM_PLUGIN_ID,
+    METRICS_TRANSFORM_PLUGIN_ID,
+    REQUEST_ID_TRANSFORM_PLUGIN_ID,
+];
+
+struct ChainedPlugin {
+    /// Identifier as written in `plugins.items`.
+    reference: String,
+    plugin: CustomPlugin,
+    config: serde_json::Value,
+}
+
+/// Custom plugins of one proxy call, in execution order, together with the
+/// exchange their hooks read and modify.
+pub(super) struct PluginChain {
+    plugins: Vec<ChainedPlugin>,
+    exchange: PluginExchange,
+}
+
+impl PluginChain {
+    /// Resolve the custom plugins referenced by `upstream`, then by `route`.
+    /// Within each layer guards run before transforms, otherwise in the order
+    /// listed. Returns `None` when no custom plugin is referenced.
+    pub(super) async fn resolve(
+        cp: &dyn ControlPlaneService,
+        upstream: &EffectiveUpstream,
+        route: &Route,
+        exchange: PluginExchange,
+        instance_uri: &str,
+    ) -> Result<Option<Self>, DomainError> {
+        let mut plugins = Vec::new();
+        let layers = [
+            upstream.upstream.plugins.as_ref().map(|p| (p, None)),
+            route.plugins.as_ref().map(|p| (p, Some(route.tenant_id))),
+        ];
+        for (layer, layer_owner) in layers.into_iter().flatten() {
+            let mut resolved = Vec::with_capacity(layer.items.len());
+            for reference in &layer.items {
+                let not_found = || DomainError::PluginNotFound {
+                    detail: format!("plugin '{reference}' is not available"),
+                    instance: instance_uri.to_string(),
+                };
+                let (plugin_type, id) = parse_plugin_gts(reference).map_err(|_| not_found())?;
+                let Some(id) = id else {
+                    if BUILTIN_PLUGIN_IDS.contains(&reference.as_str()) {
+                        continue;
+                    }
+                    return Err(not_found());
+                };
+                // Plugins belong to the tenant whose configuration lists
+                // them, which for an inherited upstream chain is an ancestor.
+                let owner = layer_owner
+                    .or_else(|| upstream.plugin_owners.get(reference).copied())
+                    .unwrap_or(upstream.upstream.tenant_id);
+                let plugin = match cp.get_owned_plugin(owner, id).await {
+                    Ok(plugin) if plugin.plugin_type == plugin_type => plugin,
+                    Ok(_) | Err(DomainError::NotFound { .. }) => return Err(not_found()),
+                    Err(e) => return Err(e),
+                };
+                let config = instance_config(&plugin.config_schema, layer.config.get(reference));
+                resolved.push(ChainedPlugin {
+                    reference: reference.clone(),
+                    plugin,
+                    config,
+                });
+            }
+            resolved.sort_by_key(|p| p.plugin.plugin_type != PluginType::Guard);
+            plugins.extend(resolved);
+        }
+
+        Ok((!plugins.is_empty()).then_some(Self { plugins, exchange }))
+    }
+
+    /// Whether any plugin in the chain hooks `phase`.
+    pub(super) fn has(&self, phase: PluginPhase) -> bool {
+        self.plugins
+            .iter()
+            .any(|p| p.plugin.phases.contains(&phase))
+    }
+
+    pub(super) fn exchange(&self) -> &PluginExchange {
+        &self.exchange
+    }
+
+    pub(super) fn exchange_mut(&mut self) -> &mut PluginExchange {
+        &mut self.exchange
+    }
+
+    /// Run the `phase` hooks in chain order, stopping at the first plugin
+    /// that rejects or responds. Returns the response to send instead of
+    /// continuing, if any.
+    pub(super) async fn run(
+        &mut self,
+        runtime: &dyn PluginRuntime,
+        phase: PluginPhase,
+        instance_uri: &str,
+    ) -> Result<Option<http::Response<Body>>, DomainError> {
+        for p in &self.plugins {
+            if !p.plugin.phases.contains(&phase) {
+                continue;
+            }
+            let outcome = runtime
+                .run(&p.plugin, phase, &p.config, &mut self.exchange)
+                .await
+                .map_err(|e| DomainError::PluginFailed {
+                    detail: format!(
+                        "plugin '{}' failed in {}: {e}",
+                        p.reference,
+                        phase.hook_name()
+                    ),
+                    instance: instance_uri.to_string(),
+                })?;
+            match outcome {
+                PluginOutcome::Next => {}
+                PluginOutcome::Reject {
+                    status,
+                    code,
+                    message,
+                } => {
+                    return Err(DomainError::PluginRejected {
+                        status,
+                        code,
+                        detail: message,
+                        instance: instance_uri.to_string(),
+                    });
+                }
+                PluginOutcome::Respond { status, body } => {
+                    return plugin_response(status, body, instance_uri).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Give `on_error` hooks a chance to answer a failed call. The original
+    /// error is returned when every hook continues.
+    pub(super) async fn recover(
+        mut self,
+        runtime: &dyn PluginRuntime,
+        err: DomainError,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        self.exchange.error = Some(PluginFailure {
+            status: err.status_code(),
+            code: err.gts_type().to_string(),
+            message: err.to_string(),
+            upstream: matches!(
+                err,
+                DomainError::DownstreamError { .. }
+                    | DomainError::ConnectionTimeout { .. }
+                    | DomainError::RequestTimeout { .. }
+            ),
+        });
+        match self
+            .run(runtime, PluginPhase::OnError, instance_uri)
+            .await?
+        {
+            Some(resp) => Ok(resp),
+            None => Err(err),
+        }
+    }
+}
+
+/// Whether `on_error` hooks see `err`. Failures of the chain itself are
+/// reported as-is.
+pub(super) fn recoverable(err: &DomainError) -> bool {
+    !matches!(
+        err,
+        DomainError::PluginRejected { .. }
+            | DomainError::PluginFailed { .. }
+            | DomainError::PluginNotFound { .. }
+    )
+}
+
+/// Plugin instance config: the `default` of each top-level property of the
+/// plugin's config schema, overridden by the values bound in `plugins.config`.
+fn instance_config(
+    schema: &serde_json::Value,
+    binding: Option<&serde_json::Value>,
+) -> serde_json::Value {
+    let mut config = serde_json::Map::new();
+    if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+        for (name, property) in properties {
+            if let Some(default) = property.get("default") {
+                config.insert(name.clone(), default.clone());
+            }
+        }
+    }
+    if let Some(serde_json::Value::Object(bound)) = binding {
+        config.extend(bound.clone());
+    }
+    serde_json::Value::Object(config)
+}
+
+/// Replay plugin header edits onto `headers`. Hop-by-hop and internal
+/// headers are stripped again afterwards, so plugins cannot reintroduce them.
+/// Edits that are not valid HTTP headers are dropped.
+pub(super) fn apply_header_edits(target: &mut HeaderMap, edits: &[HeaderEdit]) {
+    for edit in edits {
+        match edit {
+            HeaderEdit::Set(name, value) | HeaderEdit::Add(name, value) => {
+                let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(name.as_bytes()),
+                    HeaderValue::from_str(value),
+                ) else {
+                    continue;
+                };
+                if matches!(edit, HeaderEdit::Set(..)) {
+                    target.insert(name, value);
+                } else {
+                    target.append(name, value);
+                }
+            }
+            HeaderEdit::Remove(name) => {
+                target.remove(name.as_str());
+            }
+        }
+    }
+    headers::strip_hop_by_hop(target);
+    headers::strip_internal_headers(target);
+}
+
+/// Header pairs of `map` with valid UTF-8 values, for a plugin header view.
+pub(super) fn header_pairs(map: &HeaderMap) -> Vec<(String, String)> {
+    map.iter()
+        .filter_map(|(k, v)| {
+            v.to_str()
+                .ok()
+                .map(|s| (k.as_str().to_string(), s.to_string()))
+        })
+        .collect()
+}
+
+/// Response produced by `ctx.respond`. JSON bodies are labelled as such,
+/// anything else is sent as plain text.
+fn plugin_response(
+    status: u16,
+    body: String,
+    instance_uri: &str,
+) -> Result<http::Response<Body>, DomainError> {
+    let content_type = if serde_json::from_str::<serde::de::IgnoredAny>(&body).is_ok() {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    let mut resp = http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .map_err(|e| DomainError::PluginFailed {
+            detail: format!("plugin response is invalid: {e}"),
+            instance: instance_uri.to_string(),
+        })?;
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_config_overrides_schema_defaults() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "header": {"type": "string", "default": "x-customer-id"},
+                "max_bytes": {"type": "integer", "default": 1024},
+                "prefix": {"type": "string"}
+            }
+        });
+        let binding = serde_json::json!({"max_bytes": 10});
+        assert_eq!(
+            instance_config(&schema, Some(&binding)),
+            serde_json::json!({"header": "x-customer-id", "max_bytes": 10})
+        );
+        assert_eq!(
+            instance_config(&serde_json::json!({}), None),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn header_edits_cannot_reintroduce_hop_by_hop_headers() {
+        let mut target = HeaderMap::new();
+        target.insert("x-old", HeaderValue::from_static("1"));
+        apply_header_edits(
+            &mut target,
+            &[
+                HeaderEdit::Set("x-new".into(), "a".into()),
+                HeaderEdit::Add("x-new".into(), "b".into()),
+                HeaderEdit::Remove("x-old".into()),
+                HeaderEdit::Set("connection".into(), "close".into()),
+                HeaderEdit::Set("x-bad".into(), "line\nbreak".into()),
+            ],
+        );
+        assert_eq!(target.get_all("x-new").iter().count(), 2);
+        assert!(!target.contains_key("x-old"));
+        assert!(!target.contains_key("connection"));
+        assert!(!target.contains_key("x-bad"));
+    }
+}
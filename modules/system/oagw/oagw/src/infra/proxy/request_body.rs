@@ -0,0 +1,202 @@
+//! Streaming of proxied request bodies.
+//!
+//! Request bodies are forwarded to the upstream as they arrive. The size limit
+//! is enforced per chunk, so an oversized upload fails once the limit is
+//! crossed instead of after it has been read into memory.
+
+use bytes::Bytes;
+use futures_util::{StreamExt, future};
+use http::HeaderMap;
+use oagw_sdk::body::{Body, BodyStream, BoxError};
+
+use crate::domain::error::DomainError;
+
+/// Bodies up to this size are buffered when the request may have to be sent
+/// twice (credential refresh after a 401).
+pub(super) const REPLAYABLE_LIMIT: usize = 64 * 1024;
+
+/// Failure of a client request body while it is being streamed.
+#[derive(Debug, thiserror::Error)]
+pub enum RequestBodyError {
+    #[error("request body exceeds maximum of {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("failed to read request body: {0}")]
+    Read(BoxError),
+}
+
+impl RequestBodyError {
+    pub(super) fn to_domain(&self, instance_uri: &str) -> DomainError {
+        match self {
+            Self::TooLarge { .. } => DomainError::PayloadTooLarge {
+                detail: self.to_string(),
+                instance: instance_uri.to_string(),
+            },
+            Self::Read(_) => DomainError::Validation {
+                detail: self.to_string(),
+                instance: instance_uri.to_string(),
+            },
+        }
+    }
+}
+
+/// Reject a body whose announced length already exceeds `limit`, before the
+/// upstream is contacted.
+///
+/// # Errors
+///
+/// Returns `PayloadTooLarge` if `len` is over `limit`.
+pub fn check_declared_length(
+    len: usize,
+    limit: usize,
+    instance_uri: &str,
+) -> Result<(), DomainError> {
+    if len > limit {
+        return Err(DomainError::PayloadTooLarge {
+            detail: format!("request body of {len} bytes exceeds maximum of {limit} bytes"),
+            instance: instance_uri.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Wrap a client body stream so that it fails with
+/// [`RequestBodyError::TooLarge`] as soon as more than `limit` bytes have
+/// been received. Read errors are reported as [`RequestBodyError::Read`].
+pub fn limited<S, E>(stream: S, limit: usize) -> BodyStream
+where
+    S: futures_util::Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    let mut received = 0usize;
+    Box::pin(stream.scan(false, move |failed, chunk| {
+        if *failed {
+            return future::ready(None);
+        }
+        let result = match chunk {
+            Ok(chunk) => {
+                received = received.saturating_add(chunk.len());
+                if received > limit {
+                    Err(RequestBodyError::TooLarge { limit })
+                } else {
+                    Ok(chunk)
+                }
+            }
+            Err(e) => Err(RequestBodyError::Read(e.into())),
+        };
+        *failed = result.is_err();
+        future::ready(Some(result.map_err(BoxError::from)))
+    }))
+}
+
+/// Read the whole body into memory, for the pipeline stages that need it
+/// (custom plugins, gRPC transcoding).
+pub(super) async fn buffer(body: Body, instance_uri: &str) -> Result<Bytes, DomainError> {
+    body.into_bytes()
+        .await
+        .map_err(|e| match e.downcast::<RequestBodyError>() {
+            Ok(e) => e.to_domain(instance_uri),
+            Err(e) => RequestBodyError::Read(e).to_domain(instance_uri),
+        })
+}
+
+/// Body length announced by the client's `Content-Length`, if valid.
+pub(super) fn declared_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(http::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Outbound body. Buffered bodies can be sent again (see
+/// [`replayable`]); streams are forwarded chunk by chunk and sent once.
+pub(super) fn to_reqwest(body: Body) -> reqwest::Body {
+    match body {
+        Body::Empty => reqwest::Body::from(Bytes::new()),
+        Body::Bytes(b) => reqwest::Body::from(b),
+        Body::Stream(s) => reqwest::Body::wrap_stream(s),
+    }
+}
+
+/// Copy of a buffered body for resending the request; `None` for streams.
+pub(super) fn replayable(body: &Body) -> Option<Bytes> {
+    match body {
+        Body::Empty => Some(Bytes::new()),
+        Body::Bytes(b) => Some(b.clone()),
+        Body::Stream(_) => None,
+    }
+}
+
+/// The request body failure behind a failed upstream send, if the send
+/// failed because of the client's body rather than the upstream.
+pub(super) fn client_fault(err: &reqwest::Error) -> Option<&RequestBodyError> {
+    let mut source = std::error::Error::source(err);
+    while let Some(e) = source {
+        if let Some(body_err) = e.downcast_ref::<RequestBodyError>() {
+            return Some(body_err);
+        }
+        source = e.source();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn chunks(sizes: &[usize]) -> Vec<Result<Bytes, BoxError>> {
+        sizes
+            .iter()
+            .map(|&n| Ok(Bytes::from(vec![b'x'; n])))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn limited_passes_bodies_within_limit() {
+        let body = Body::Stream(limited(stream::iter(chunks(&[4, 4, 2])), 10));
+        let bytes = buffer(body, "/test").await.unwrap();
+        assert_eq!(bytes.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn limited_fails_once_limit_is_crossed() {
+        let mut s = limited(stream::iter(chunks(&[4, 4, 4, 4])), 10);
+        assert!(s.next().await.unwrap().is_ok());
+        assert!(s.next().await.unwrap().is_ok());
+        let err = s.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<RequestBodyError>(),
+            Some(RequestBodyError::TooLarge { limit: 10 })
+        ));
+        assert!(s.next().await.is_none(), "stream ends after the error");
+    }
+
+    #[test]
+    fn declared_length_over_limit_is_rejected() {
+        assert!(check_declared_length(10, 10, "/test").is_ok());
+        assert!(matches!(
+            check_declared_length(11, 10, "/test"),
+            Err(DomainError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn buffer_maps_failures_to_domain_errors() {
+        let too_large = Body::Stream(limited(stream::iter(chunks(&[8, 8])), 10));
+        assert!(matches!(
+            buffer(too_large, "/test").await,
+            Err(DomainError::PayloadTooLarge { .. })
+        ));
+
+        let broken: Vec<Result<Bytes, BoxError>> = vec![Err("connection reset".into())];
+        let broken = Body::Stream(limited(stream::iter(broken), 10));
+        match buffer(broken, "/test").await {
+            Err(DomainError::Validation { detail, .. }) => {
+                assert!(detail.contains("connection reset"), "{detail}");
+            }
+            other => panic!("expected Validation, got {other:?}"),
+        }
+    }
+}
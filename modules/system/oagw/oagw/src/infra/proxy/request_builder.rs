@@ -1,28 +1,19 @@
-use crate::domain::error::DomainError;
 use crate::domain::model::{Endpoint, Scheme};
 
 /// Build the full upstream URL from endpoint, route path, path suffix, and query params.
 ///
-/// # Errors
-///
-/// Returns `DomainError::Validation` if the endpoint uses an unsupported scheme (e.g. gRPC).
+/// `grpc` endpoints are gRPC over TLS and map to `https`; plaintext (h2c)
+/// gRPC upstreams use the `http` scheme.
 pub fn build_upstream_url(
     endpoint: &Endpoint,
     route_path: &str,
     path_suffix: &str,
     query_params: &[(String, String)],
-) -> Result<String, DomainError> {
+) -> String {
     let scheme = match endpoint.scheme {
         Scheme::Http => "http",
-        Scheme::Https => "https",
+        Scheme::Https | Scheme::Wt | Scheme::Grpc => "https",
         Scheme::Wss => "wss",
-        Scheme::Wt => "https",
-        Scheme::Grpc => {
-            return Err(DomainError::Validation {
-                detail: "gRPC scheme is not supported for HTTP proxy".into(),
-                instance: String::new(),
-            });
-        }
     };
 
     let host_port = if is_default_port(scheme, endpoint.port) {
@@ -31,16 +22,7 @@ pub fn build_upstream_url(
         format!("{}:{}", endpoint.host, endpoint.port)
     };
 
-    // Combine route path + path suffix, avoiding double slashes.
-    let path = if path_suffix.is_empty() {
-        route_path.to_string()
-    } else if route_path.ends_with('/') && path_suffix.starts_with('/') {
-        format!("{}{}", route_path, &path_suffix[1..])
-    } else if !route_path.ends_with('/') && !path_suffix.starts_with('/') {
-        format!("{route_path}/{path_suffix}")
-    } else {
-        format!("{route_path}{path_suffix}")
-    };
+    let path = join_path(route_path, path_suffix);
 
     let mut url = format!("{scheme}://{host_port}{path}");
 
@@ -52,7 +34,20 @@ pub fn build_upstream_url(
         url.push_str(&qs);
     }
 
-    Ok(url)
+    url
+}
+
+/// Combine route path + path suffix, avoiding double slashes.
+pub fn join_path(route_path: &str, path_suffix: &str) -> String {
+    if path_suffix.is_empty() {
+        route_path.to_string()
+    } else if route_path.ends_with('/') && path_suffix.starts_with('/') {
+        format!("{}{}", route_path, &path_suffix[1..])
+    } else if !route_path.ends_with('/') && !path_suffix.starts_with('/') {
+        format!("{route_path}/{path_suffix}")
+    } else {
+        format!("{route_path}{path_suffix}")
+    }
 }
 
 fn is_default_port(scheme: &str, port: u16) -> bool {
@@ -68,6 +63,7 @@ mod tests {
             scheme: Scheme::Https,
             host: host.into(),
             port,
+            weight: 1,
         }
     }
 
@@ -78,8 +74,7 @@ mod tests {
             "/v1/chat",
             "/completions",
             &[],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://api.openai.com/v1/chat/completions");
     }
 
@@ -90,28 +85,25 @@ mod tests {
             "/v1/chat",
             "/models/gpt-4",
             &[("version".into(), "2".into())],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://api.openai.com/v1/chat/models/gpt-4?version=2");
     }
 
     #[test]
     fn nonstandard_port() {
-        let url = build_upstream_url(&endpoint("localhost", 8080), "/api", "", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("localhost", 8080), "/api", "", &[]);
         assert_eq!(url, "https://localhost:8080/api");
     }
 
     #[test]
     fn empty_suffix() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/models", "", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("api.openai.com", 443), "/v1/models", "", &[]);
         assert_eq!(url, "https://api.openai.com/v1/models");
     }
 
     #[test]
     fn avoids_double_slash() {
-        let url =
-            build_upstream_url(&endpoint("api.openai.com", 443), "/v1/", "/chat", &[]).unwrap();
+        let url = build_upstream_url(&endpoint("api.openai.com", 443), "/v1/", "/chat", &[]);
         assert_eq!(url, "https://api.openai.com/v1/chat");
     }
 
@@ -122,8 +114,7 @@ mod tests {
             "/api",
             "/data",
             &[("key".into(), "val".into()), ("foo".into(), "bar".into())],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://example.com/api/data?key=val&foo=bar");
     }
 
@@ -133,8 +124,9 @@ mod tests {
             scheme: Scheme::Http,
             host: "127.0.0.1".into(),
             port: 3000,
+            weight: 1,
         };
-        let url = build_upstream_url(&ep, "/v1/test", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/v1/test", "", &[]);
         assert_eq!(url, "http://127.0.0.1:3000/v1/test");
     }
 
@@ -144,8 +136,9 @@ mod tests {
             scheme: Scheme::Http,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
-        let url = build_upstream_url(&ep, "/api", "", &[]).unwrap();
+        let url = build_upstream_url(&ep, "/api", "", &[]);
         assert_eq!(url, "http://example.com/api");
     }
 
@@ -156,19 +149,22 @@ mod tests {
             "/v1/search",
             "",
             &[("q".into(), "a&b".into())],
-        )
-        .unwrap();
+        );
         assert_eq!(url, "https://api.openai.com/v1/search?q=a%26b");
     }
 
     #[test]
-    fn grpc_scheme_returns_error() {
+    fn grpc_scheme_maps_to_https() {
         let ep = Endpoint {
             scheme: Scheme::Grpc,
             host: "grpc.example.com".into(),
             port: 443,
+            weight: 1,
         };
-        let err = build_upstream_url(&ep, "/service", "", &[]).unwrap_err();
-        assert!(matches!(err, DomainError::Validation { .. }));
+        let url = build_upstream_url(&ep, "/example.v1.UserService/GetUser", "", &[]);
+        assert_eq!(
+            url,
+            "https://grpc.example.com/example.v1.UserService/GetUser"
+        );
     }
 }
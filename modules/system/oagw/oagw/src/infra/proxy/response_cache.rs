@@ -0,0 +1,847 @@
+//! Data-plane cache of upstream responses.
+//!
+//! Routes opt in with `response_cache`. Successful responses to `GET`
+//! requests are kept in memory, keyed by tenant, alias, outbound path and
+//! query, and the request headers the route varies on. The upstream's
+//! `Cache-Control` decides whether and for how long a response is reused;
+//! stale entries that carry an `ETag` are revalidated with `If-None-Match`.
+//! The cache is bounded in bytes and evicts the least recently used entries.
+
+use std::collections::{BTreeMap, HashMap};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt};
+use http::{HeaderMap, HeaderValue, StatusCode, header};
+use oagw_sdk::body::{Body, BodyStream, BoxError};
+use uuid::Uuid;
+
+use crate::domain::model::ResponseCacheConfig;
+use crate::domain::services::ConfigChangeListener;
+
+/// Default bound on the bytes held by the cache.
+pub const DEFAULT_CAPACITY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Default size above which a response is not cached.
+pub const DEFAULT_MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+/// Value of [`super::headers::CACHE_STATUS_HEADER`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheStatus {
+    /// Served from the cache without contacting the upstream.
+    Hit,
+    /// Fetched from the upstream.
+    Miss,
+    /// Served from the cache after the upstream confirmed it with a 304.
+    Revalidated,
+}
+
+impl CacheStatus {
+    pub(crate) fn header_value(self) -> HeaderValue {
+        HeaderValue::from_static(match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+            Self::Revalidated => "revalidated",
+        })
+    }
+}
+
+/// Identity of a cacheable request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    tenant_id: Uuid,
+    alias: String,
+    path: String,
+    /// Sorted, so parameter order does not split entries.
+    query: Vec<(String, String)>,
+    /// Values of the route's `vary` headers, in config order.
+    vary: Vec<Vec<HeaderValue>>,
+}
+
+impl CacheKey {
+    pub(crate) fn new(
+        tenant_id: Uuid,
+        alias: &str,
+        path: &str,
+        query: &[(String, String)],
+        config: &ResponseCacheConfig,
+        req_headers: &HeaderMap,
+    ) -> Self {
+        let mut query = query.to_vec();
+        query.sort();
+        let vary = config
+            .vary
+            .iter()
+            .map(|name| req_headers.get_all(name.as_str()).iter().cloned().collect())
+            .collect();
+        Self {
+            tenant_id,
+            alias: alias.to_string(),
+            path: path.to_string(),
+            query,
+            vary,
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.alias.len()
+            + self.path.len()
+            + self
+                .query
+                .iter()
+                .map(|(k, v)| k.len() + v.len())
+                .sum::<usize>()
+            + self
+                .vary
+                .iter()
+                .flatten()
+                .map(HeaderValue::len)
+                .sum::<usize>()
+    }
+}
+
+/// A stored upstream response.
+#[derive(Debug)]
+pub(crate) struct CachedResponse {
+    route_id: Uuid,
+    upstream_id: Uuid,
+    headers: HeaderMap,
+    body: Bytes,
+    etag: Option<HeaderValue>,
+    stored: Instant,
+    fresh_for: Duration,
+}
+
+impl CachedResponse {
+    fn is_fresh(&self) -> bool {
+        self.stored.elapsed() < self.fresh_for
+    }
+
+    /// Validator to send upstream when the entry has gone stale.
+    pub(crate) fn etag(&self) -> Option<&HeaderValue> {
+        self.etag.as_ref()
+    }
+
+    pub(crate) fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Stored headers with the entry's current `Age`.
+    pub(crate) fn headers_with_age(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        headers.insert(
+            header::AGE,
+            HeaderValue::from(self.stored.elapsed().as_secs()),
+        );
+        headers
+    }
+
+    /// Response for the client: `304 Not Modified` when its `If-None-Match`
+    /// names the stored `ETag`, the stored `200 OK` otherwise.
+    pub(crate) fn to_response(&self, req_headers: &HeaderMap) -> http::Response<Body> {
+        let mut headers = self.headers_with_age();
+        let not_modified = self.etag.as_ref().is_some_and(|etag| {
+            req_headers
+                .get_all(header::IF_NONE_MATCH)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(','))
+                .any(|candidate| etag_matches(candidate.trim(), etag))
+        });
+        let mut resp = if not_modified {
+            headers.remove(header::CONTENT_LENGTH);
+            let mut resp = http::Response::new(Body::Empty);
+            *resp.status_mut() = StatusCode::NOT_MODIFIED;
+            resp
+        } else {
+            http::Response::new(Body::Bytes(self.body.clone()))
+        };
+        *resp.headers_mut() = headers;
+        resp
+    }
+}
+
+/// Weak comparison, as `If-None-Match` requires.
+fn etag_matches(candidate: &str, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    candidate == "*" || candidate.trim_start_matches("W/") == etag.trim_start_matches("W/")
+}
+
+/// Outcome of a cache lookup.
+#[derive(Debug)]
+pub(crate) enum Lookup {
+    Fresh(Arc<CachedResponse>),
+    /// Expired but revalidatable: the request is sent upstream with
+    /// `If-None-Match`, and a 304 renews the entry.
+    Stale(Arc<CachedResponse>),
+    Miss,
+}
+
+/// A cacheable response whose body is still being received.
+#[derive(Debug)]
+pub(crate) struct PendingEntry {
+    key: CacheKey,
+    response: CachedResponse,
+}
+
+#[derive(Debug)]
+struct Slot {
+    response: Arc<CachedResponse>,
+    size: usize,
+    tick: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    slots: HashMap<CacheKey, Slot>,
+    /// Last use of each entry, oldest first.
+    recency: BTreeMap<u64, CacheKey>,
+    size: usize,
+    tick: u64,
+}
+
+impl Entries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(slot) = self.slots.remove(key) {
+            self.recency.remove(&slot.tick);
+            self.size -= slot.size;
+        }
+    }
+
+    fn retain(&mut self, keep: impl Fn(&CacheKey, &CachedResponse) -> bool) {
+        let doomed: Vec<CacheKey> = self
+            .slots
+            .iter()
+            .filter(|(key, slot)| !keep(key, &slot.response))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &doomed {
+            self.remove(key);
+        }
+    }
+}
+
+/// Byte-bounded LRU cache of upstream responses, shared by all routes.
+#[derive(Debug)]
+pub struct ResponseCache {
+    entries: Mutex<Entries>,
+    capacity_bytes: usize,
+    max_entry_bytes: usize,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY_BYTES, DEFAULT_MAX_ENTRY_BYTES)
+    }
+}
+
+impl ResponseCache {
+    #[must_use]
+    pub fn new(capacity_bytes: usize, max_entry_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            capacity_bytes,
+            max_entry_bytes: max_entry_bytes.min(capacity_bytes),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Find the entry for `key`. An entry stored for another route or
+    /// upstream (the request now resolves differently) is not used.
+    pub(crate) fn lookup(&self, key: &CacheKey, route_id: Uuid, upstream_id: Uuid) -> Lookup {
+        let mut entries = self.entries();
+        let tick = entries.next_tick();
+        let Some(slot) = entries.slots.get_mut(key) else {
+            return Lookup::Miss;
+        };
+        let response = Arc::clone(&slot.response);
+        if response.route_id != route_id
+            || response.upstream_id != upstream_id
+            || (!response.is_fresh() && response.etag.is_none())
+        {
+            entries.remove(key);
+            return Lookup::Miss;
+        }
+        let previous = std::mem::replace(&mut slot.tick, tick);
+        entries.recency.remove(&previous);
+        entries.recency.insert(tick, key.clone());
+        if response.is_fresh() {
+            Lookup::Fresh(response)
+        } else {
+            Lookup::Stale(response)
+        }
+    }
+
+    /// Decide whether a `200 OK` upstream response may be stored. The body
+    /// is handed over later with [`Self::complete`] or [`Self::record`].
+    pub(crate) fn admit(
+        &self,
+        key: CacheKey,
+        route_id: Uuid,
+        upstream_id: Uuid,
+        config: &ResponseCacheConfig,
+        status: StatusCode,
+        resp_headers: &HeaderMap,
+    ) -> Option<PendingEntry> {
+        if status != StatusCode::OK {
+            return None;
+        }
+        let declared = resp_headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok());
+        if declared.is_some_and(|len| len > self.max_entry_bytes) {
+            return None;
+        }
+        let etag = resp_headers.get(header::ETAG).cloned();
+        let fresh_for = freshness(resp_headers, config)?;
+        if fresh_for.is_zero() && etag.is_none() {
+            return None;
+        }
+        Some(PendingEntry {
+            key,
+            response: CachedResponse {
+                route_id,
+                upstream_id,
+                headers: resp_headers.clone(),
+                body: Bytes::new(),
+                etag,
+                stored: Instant::now(),
+                fresh_for,
+            },
+        })
+    }
+
+    /// Store an admitted response with its complete body.
+    pub(crate) fn complete(&self, pending: PendingEntry, body: Bytes) {
+        let PendingEntry { key, mut response } = pending;
+        response.body = body;
+        self.insert(key, response);
+    }
+
+    /// Stream `body` to the client and store the response once the stream
+    /// has ended. Streams that fail or outgrow the entry limit are not stored.
+    pub(crate) fn record(self: &Arc<Self>, pending: PendingEntry, body: BodyStream) -> BodyStream {
+        Box::pin(Recording {
+            inner: body,
+            cache: Arc::clone(self),
+            pending: Some(pending),
+            buffered: BytesMut::new(),
+        })
+    }
+
+    /// Renew a stale entry the upstream confirmed with `304 Not Modified`.
+    /// Headers of the 304 replace the stored ones, and freshness is computed
+    /// anew from them. Returns the response to serve.
+    pub(crate) fn refresh(
+        &self,
+        key: &CacheKey,
+        stale: &CachedResponse,
+        not_modified: &HeaderMap,
+        config: &ResponseCacheConfig,
+    ) -> Arc<CachedResponse> {
+        let mut headers = stale.headers.clone();
+        for name in not_modified.keys() {
+            if name == header::CONTENT_LENGTH {
+                continue;
+            }
+            headers.remove(name);
+            for value in not_modified.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        let fresh_for = freshness(&headers, config);
+        let response = CachedResponse {
+            route_id: stale.route_id,
+            upstream_id: stale.upstream_id,
+            etag: headers.get(header::ETAG).cloned(),
+            headers,
+            body: stale.body.clone(),
+            stored: Instant::now(),
+            fresh_for: fresh_for.unwrap_or_default(),
+        };
+        if fresh_for.is_none() {
+            self.entries().remove(key);
+            return Arc::new(response);
+        }
+        self.insert(key.clone(), response)
+    }
+
+    fn insert(&self, key: CacheKey, response: CachedResponse) -> Arc<CachedResponse> {
+        let size = key.size()
+            + response.body.len()
+            + response
+                .headers
+                .iter()
+                .map(|(k, v)| k.as_str().len() + v.len())
+                .sum::<usize>();
+        let response = Arc::new(response);
+        let mut entries = self.entries();
+        entries.remove(&key);
+        if response.body.len() > self.max_entry_bytes || size > self.capacity_bytes {
+            return response;
+        }
+        while entries.size + size > self.capacity_bytes {
+            let Some((_, oldest)) = entries.recency.pop_first() else {
+                break;
+            };
+            entries.remove(&oldest);
+        }
+        let tick = entries.next_tick();
+        entries.recency.insert(tick, key.clone());
+        entries.size += size;
+        entries.slots.insert(
+            key,
+            Slot {
+                response: Arc::clone(&response),
+                size,
+                tick,
+            },
+        );
+        response
+    }
+
+    /// Bytes currently held.
+    #[cfg(test)]
+    fn size(&self) -> usize {
+        self.entries().size
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries().slots.len()
+    }
+}
+
+impl ConfigChangeListener for ResponseCache {
+    fn upstream_changed(&self, _id: Uuid, alias: &str) {
+        self.entries().retain(|key, _| key.alias != alias);
+    }
+
+    fn route_changed(&self, route_id: Uuid) {
+        self.entries()
+            .retain(|_, response| response.route_id != route_id);
+    }
+}
+
+/// How long a response stays fresh, or `None` if it must not be stored.
+///
+/// `no-store`, `private`, `Set-Cookie` and a `Vary` on headers the route
+/// does not key on make a response uncacheable. `s-maxage` takes precedence
+/// over `max-age`; `no-cache` allows storing but requires revalidation on
+/// every use. Without either, the route's `ttl` applies.
+fn freshness(headers: &HeaderMap, config: &ResponseCacheConfig) -> Option<Duration> {
+    if headers.contains_key(header::SET_COOKIE) {
+        return None;
+    }
+    for vary in headers.get_all(header::VARY) {
+        let vary = vary.to_str().ok()?;
+        for name in vary.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if !config.vary.iter().any(|v| v.eq_ignore_ascii_case(name)) {
+                return None;
+            }
+        }
+    }
+
+    let mut max_age = None;
+    let mut s_maxage = None;
+    let mut no_cache = false;
+    for value in headers.get_all(header::CACHE_CONTROL) {
+        let value = value.to_str().ok()?;
+        for directive in value.split(',').map(str::trim) {
+            let (name, arg) = match directive.split_once('=') {
+                Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" | "private" => return None,
+                "no-cache" => no_cache = true,
+                "max-age" => max_age = arg.and_then(|a| a.parse::<u64>().ok()),
+                "s-maxage" => s_maxage = arg.and_then(|a| a.parse::<u64>().ok()),
+                _ => {}
+            }
+        }
+    }
+    if no_cache {
+        return Some(Duration::ZERO);
+    }
+    Some(s_maxage.or(max_age).map_or(config.ttl, Duration::from_secs))
+}
+
+/// Body stream that keeps a copy of what it forwards, for [`ResponseCache::record`].
+struct Recording {
+    inner: BodyStream,
+    cache: Arc<ResponseCache>,
+    pending: Option<PendingEntry>,
+    buffered: BytesMut,
+}
+
+impl Stream for Recording {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let item = ready!(this.inner.poll_next_unpin(cx));
+        match item {
+            Some(Ok(ref chunk)) if this.pending.is_some() => {
+                if this.buffered.len() + chunk.len() > this.cache.max_entry_bytes {
+                    this.pending = None;
+                    this.buffered = BytesMut::new();
+                } else {
+                    this.buffered.extend_from_slice(chunk);
+                }
+            }
+            Some(Ok(_)) => {}
+            Some(Err(_)) => {
+                this.pending = None;
+                this.buffered = BytesMut::new();
+            }
+            None => {
+                if let Some(pending) = this.pending.take() {
+                    let body = std::mem::take(&mut this.buffered).freeze();
+                    this.cache.complete(pending, body);
+                }
+            }
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn config() -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            ttl: Duration::from_secs(60),
+            vary: vec!["accept".into()],
+        }
+    }
+
+    fn key(path: &str) -> CacheKey {
+        CacheKey::new(
+            Uuid::nil(),
+            "api.example.com",
+            path,
+            &[],
+            &config(),
+            &HeaderMap::new(),
+        )
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(k, v)| {
+                (
+                    http::HeaderName::from_static(k),
+                    HeaderValue::from_static(v),
+                )
+            })
+            .collect()
+    }
+
+    fn store(cache: &ResponseCache, key: CacheKey, route_id: Uuid, resp: &HeaderMap, body: &str) {
+        let pending = cache
+            .admit(key, route_id, Uuid::nil(), &config(), StatusCode::OK, resp)
+            .expect("cacheable");
+        cache.complete(pending, Bytes::from(body.to_string()));
+    }
+
+    #[test]
+    fn freshness_follows_cache_control() {
+        let cfg = config();
+        let fresh = |pairs: &[(&'static str, &'static str)]| freshness(&headers(pairs), &cfg);
+        assert_eq!(fresh(&[]), Some(Duration::from_secs(60)));
+        assert_eq!(
+            fresh(&[("cache-control", "public, max-age=10")]),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            fresh(&[("cache-control", "max-age=10, s-maxage=30")]),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            fresh(&[("cache-control", "no-cache")]),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(fresh(&[("cache-control", "no-store")]), None);
+        assert_eq!(fresh(&[("cache-control", "private, max-age=60")]), None);
+        assert_eq!(fresh(&[("set-cookie", "session=1")]), None);
+        assert_eq!(fresh(&[("vary", "Accept")]), Some(Duration::from_secs(60)));
+        assert_eq!(fresh(&[("vary", "accept, authorization")]), None);
+        assert_eq!(fresh(&[("vary", "*")]), None);
+    }
+
+    #[test]
+    fn key_separates_vary_values_and_ignores_query_order() {
+        let cfg = config();
+        let json = headers(&[("accept", "application/json")]);
+        let xml = headers(&[("accept", "application/xml")]);
+        let query = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect()
+        };
+        let a = CacheKey::new(
+            Uuid::nil(),
+            "a",
+            "/p",
+            &query(&[("x", "1"), ("y", "2")]),
+            &cfg,
+            &json,
+        );
+        let b = CacheKey::new(
+            Uuid::nil(),
+            "a",
+            "/p",
+            &query(&[("y", "2"), ("x", "1")]),
+            &cfg,
+            &json,
+        );
+        let c = CacheKey::new(
+            Uuid::nil(),
+            "a",
+            "/p",
+            &query(&[("x", "1"), ("y", "2")]),
+            &cfg,
+            &xml,
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn uncacheable_responses_are_not_admitted() {
+        let cache = ResponseCache::default();
+        let admit = |status, resp: &HeaderMap| {
+            cache
+                .admit(key("/p"), Uuid::nil(), Uuid::nil(), &config(), status, resp)
+                .is_some()
+        };
+        assert!(admit(StatusCode::OK, &HeaderMap::new()));
+        assert!(!admit(StatusCode::NOT_FOUND, &HeaderMap::new()));
+        assert!(!admit(
+            StatusCode::OK,
+            &headers(&[("cache-control", "no-store")])
+        ));
+        // Nothing to reuse: always stale and no validator.
+        assert!(!admit(
+            StatusCode::OK,
+            &headers(&[("cache-control", "no-cache")])
+        ));
+        assert!(admit(
+            StatusCode::OK,
+            &headers(&[("cache-control", "no-cache"), ("etag", "\"v1\"")])
+        ));
+        assert!(!admit(
+            StatusCode::OK,
+            &headers(&[("content-length", "2000000")])
+        ));
+    }
+
+    #[test]
+    fn lookup_distinguishes_fresh_stale_and_moved_entries() {
+        let cache = ResponseCache::default();
+        let route = Uuid::new_v4();
+        store(&cache, key("/fresh"), route, &HeaderMap::new(), "a");
+        store(
+            &cache,
+            key("/stale"),
+            route,
+            &headers(&[("cache-control", "max-age=0"), ("etag", "\"v1\"")]),
+            "b",
+        );
+
+        assert!(matches!(
+            cache.lookup(&key("/fresh"), route, Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+        assert!(matches!(
+            cache.lookup(&key("/stale"), route, Uuid::nil()),
+            Lookup::Stale(_)
+        ));
+        assert!(matches!(
+            cache.lookup(&key("/fresh"), Uuid::new_v4(), Uuid::nil()),
+            Lookup::Miss
+        ));
+        assert!(matches!(
+            cache.lookup(&key("/fresh"), route, Uuid::nil()),
+            Lookup::Miss
+        ));
+    }
+
+    #[test]
+    fn least_recently_used_entries_are_evicted() {
+        let probe = ResponseCache::default();
+        store(
+            &probe,
+            key("/a"),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            "0123456789",
+        );
+        let entry_size = probe.size();
+
+        let cache = ResponseCache::new(entry_size * 2, entry_size);
+        store(
+            &cache,
+            key("/a"),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            "0123456789",
+        );
+        store(
+            &cache,
+            key("/b"),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            "0123456789",
+        );
+        // Touch /a so /b is the oldest.
+        assert!(matches!(
+            cache.lookup(&key("/a"), Uuid::nil(), Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+        store(
+            &cache,
+            key("/c"),
+            Uuid::nil(),
+            &HeaderMap::new(),
+            "0123456789",
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.size() <= entry_size * 2);
+        assert!(matches!(
+            cache.lookup(&key("/b"), Uuid::nil(), Uuid::nil()),
+            Lookup::Miss
+        ));
+        assert!(matches!(
+            cache.lookup(&key("/a"), Uuid::nil(), Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+        assert!(matches!(
+            cache.lookup(&key("/c"), Uuid::nil(), Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+    }
+
+    #[test]
+    fn config_changes_invalidate_entries() {
+        let cache = ResponseCache::default();
+        let (kept, changed) = (Uuid::new_v4(), Uuid::new_v4());
+        store(&cache, key("/a"), kept, &HeaderMap::new(), "a");
+        store(&cache, key("/b"), changed, &HeaderMap::new(), "b");
+
+        cache.route_changed(changed);
+        assert_eq!(cache.len(), 1);
+        assert!(matches!(
+            cache.lookup(&key("/a"), kept, Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+
+        cache.upstream_changed(Uuid::new_v4(), "api.example.com");
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test]
+    fn refresh_renews_a_stale_entry() {
+        let cache = ResponseCache::default();
+        let resp = headers(&[("cache-control", "no-cache"), ("etag", "\"v1\"")]);
+        store(&cache, key("/p"), Uuid::nil(), &resp, "body");
+        let Lookup::Stale(stale) = cache.lookup(&key("/p"), Uuid::nil(), Uuid::nil()) else {
+            panic!("expected a stale entry");
+        };
+
+        let renewed = cache.refresh(
+            &key("/p"),
+            &stale,
+            &headers(&[("cache-control", "max-age=60"), ("etag", "\"v1\"")]),
+            &config(),
+        );
+        assert_eq!(renewed.body(), "body");
+        assert!(matches!(
+            cache.lookup(&key("/p"), Uuid::nil(), Uuid::nil()),
+            Lookup::Fresh(_)
+        ));
+    }
+
+    #[test]
+    fn conditional_requests_get_not_modified() {
+        let cache = ResponseCache::default();
+        store(
+            &cache,
+            key("/p"),
+            Uuid::nil(),
+            &headers(&[("etag", "\"v1\"")]),
+            "body",
+        );
+        let Lookup::Fresh(entry) = cache.lookup(&key("/p"), Uuid::nil(), Uuid::nil()) else {
+            panic!("expected a fresh entry");
+        };
+
+        let resp = entry.to_response(&headers(&[("if-none-match", "\"v0\", W/\"v1\"")]));
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        let resp = entry.to_response(&headers(&[("if-none-match", "\"v0\"")]));
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().contains_key(header::AGE));
+    }
+
+    #[tokio::test]
+    async fn recorded_streams_are_stored_when_complete() {
+        let cache = Arc::new(ResponseCache::new(1024, 8));
+        let chunks = |parts: &[&'static str]| -> BodyStream {
+            Box::pin(stream::iter(
+                parts
+                    .iter()
+                    .map(|p| Ok::<_, BoxError>(Bytes::from_static(p.as_bytes())))
+                    .collect::<Vec<_>>(),
+            ))
+        };
+        let admit = |path| {
+            cache
+                .admit(
+                    key(path),
+                    Uuid::nil(),
+                    Uuid::nil(),
+                    &config(),
+                    StatusCode::OK,
+                    &HeaderMap::new(),
+                )
+                .unwrap()
+        };
+
+        let body = Body::Stream(cache.record(admit("/small"), chunks(&["ab", "cd"])));
+        assert_eq!(body.into_bytes().await.unwrap(), "abcd");
+        let Lookup::Fresh(entry) = cache.lookup(&key("/small"), Uuid::nil(), Uuid::nil()) else {
+            panic!("expected the recorded body to be stored");
+        };
+        assert_eq!(entry.body(), "abcd");
+
+        let body = Body::Stream(cache.record(admit("/large"), chunks(&["abcdef", "ghij"])));
+        assert_eq!(body.into_bytes().await.unwrap(), "abcdefghij");
+        assert!(matches!(
+            cache.lookup(&key("/large"), Uuid::nil(), Uuid::nil()),
+            Lookup::Miss
+        ));
+    }
+}
@@ -1,35 +1,86 @@
-use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::AtomicU64;
+use std::time::{Duration, Instant};
 
+use crate::domain::builtin_guards::BuiltinGuards;
+use crate::domain::circuit_breaker::{CircuitBreakerRegistry, CircuitPermit};
 use crate::domain::credential::CredentialResolver;
 use crate::domain::error::DomainError;
-use crate::domain::model::{PassthroughMode, PathSuffixMode};
-use crate::domain::plugin::AuthContext;
+use crate::domain::grpc_transcoding::TranscoderCache;
+use crate::domain::hierarchy::EffectiveUpstream;
+use crate::domain::load_balancer::{self, EndpointLease, LoadBalancer};
+use crate::domain::model::{
+    CircuitBreakerStatus, DegradeConfig, Endpoint, FailureConditions, FallbackResponse, GrpcMatch,
+    PassthroughMode, PathSuffixMode, PluginPhase, ResponseCacheConfig, Route, Upstream,
+};
+use crate::domain::plugin::{
+    AuthContext, AuthPlugin, PluginError, PluginExchange, PluginHeaders, PluginRequest,
+    PluginResponse, PluginRuntime,
+};
+use bytes::Bytes;
 use futures_util::StreamExt;
 use http::{HeaderMap, HeaderName, HeaderValue};
+use modkit_http::HttpClientConfig;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ErrorSource;
-use oagw_sdk::body::{Body, BodyStream, BoxError};
+use oagw_sdk::body::{Body, BodyStream, BoxError, Trailers};
 
 use crate::domain::services::{ControlPlaneService, DataPlaneService};
 
-use crate::domain::rate_limit::RateLimiter;
-use crate::infra::plugin::AuthPluginRegistry;
+use crate::domain::rate_limit::{RateLimitDecision, RateLimiter};
+use crate::domain::usage::UsageSink;
+use crate::infra::plugin::{AuthPluginRegistry, OAuth2TokenCache, StarlarkRuntime};
 
+use super::builtin_guards::{self, CorsResponseHeaders};
+use super::grpc;
 use super::headers;
+use super::health_check;
+use super::plugin_chain::{self, PluginChain};
+use super::request_body;
 use super::request_builder;
+use super::response_cache::{CacheKey, CacheStatus, CachedResponse, Lookup, ResponseCache};
+use super::usage_meter::{self, UsageMeter, UsageSinks};
+use super::websocket;
 
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const WEBSOCKET_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Value of [`headers::DEGRADED_HEADER`] when the rate limiter degraded the request.
+const DEGRADED_REASON: &str = "rate_limit";
+
+/// A request the route's response cache applies to.
+struct Cacheable<'a> {
+    config: &'a ResponseCacheConfig,
+    key: CacheKey,
+    /// Entry to revalidate with the upstream.
+    stale: Option<Arc<CachedResponse>>,
+}
 
 /// Data Plane service implementation: proxy orchestration and plugin execution.
 pub struct DataPlaneServiceImpl {
     cp: Arc<dyn ControlPlaneService>,
+    credential_resolver: Arc<dyn CredentialResolver>,
     http_client: reqwest::Client,
+    /// HTTP/2-only client for gRPC upstreams, including plaintext (h2c) ones.
+    grpc_client: reqwest::Client,
+    transcoders: TranscoderCache,
     auth_registry: AuthPluginRegistry,
-    rate_limiter: RateLimiter,
+    /// Client used by the `OAuth2` plugins to call token endpoints.
+    token_http_config: HttpClientConfig,
+    /// Tokens obtained by the `OAuth2` plugins.
+    oauth2_tokens: Arc<OAuth2TokenCache>,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breakers: Arc<CircuitBreakerRegistry>,
+    load_balancer: Arc<LoadBalancer>,
+    /// Sandbox running custom guard and transform plugins.
+    plugin_runtime: Arc<dyn PluginRuntime>,
+    /// Upstream responses stored for routes with `response_cache`.
+    response_cache: Arc<ResponseCache>,
+    /// Receivers of a usage record per exchange; none disables metering.
+    usage_sinks: UsageSinks,
     request_timeout: Duration,
+    websocket_idle_timeout: Duration,
 }
 
 impl DataPlaneServiceImpl {
@@ -47,16 +98,36 @@ impl DataPlaneServiceImpl {
             // No overall timeout — SSE streams run indefinitely.
             // Request-header timeout is applied via tokio::time::timeout below.
             .build()?;
+        let grpc_client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .http2_prior_knowledge()
+            .build()?;
 
-        let auth_registry = AuthPluginRegistry::with_builtins(credential_resolver);
-        let rate_limiter = RateLimiter::new();
-
+        let token_http_config = HttpClientConfig::token_endpoint();
+        let oauth2_tokens = Arc::new(OAuth2TokenCache::new());
+        let auth_registry = AuthPluginRegistry::with_builtins(
+            Arc::clone(&credential_resolver),
+            token_http_config.clone(),
+            Arc::clone(&oauth2_tokens),
+        );
         Ok(Self {
             cp,
+            credential_resolver,
             http_client,
+            grpc_client,
+            transcoders: TranscoderCache::new(),
             auth_registry,
-            rate_limiter,
+            token_http_config,
+            oauth2_tokens,
+            rate_limiter: Arc::new(RateLimiter::new()),
+            circuit_breakers: Arc::new(CircuitBreakerRegistry::new()),
+            load_balancer: Arc::new(LoadBalancer::new()),
+            plugin_runtime: Arc::new(StarlarkRuntime::new()),
+            response_cache: Arc::new(ResponseCache::default()),
+            usage_sinks: Arc::new([]),
             request_timeout: REQUEST_TIMEOUT,
+            websocket_idle_timeout: WEBSOCKET_IDLE_TIMEOUT,
         })
     }
 
@@ -66,15 +137,128 @@ impl DataPlaneServiceImpl {
         self.request_timeout = timeout;
         self
     }
-}
 
-#[async_trait::async_trait]
-impl DataPlaneService for DataPlaneServiceImpl {
-    async fn proxy_request(
+    /// Override how long a relayed WebSocket connection may stay silent
+    /// before the gateway closes it.
+    #[must_use]
+    pub fn with_websocket_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.websocket_idle_timeout = timeout;
+        self
+    }
+
+    /// Override the runtime executing custom plugins, e.g. to share the one
+    /// the control plane compiles plugins with.
+    #[must_use]
+    pub fn with_plugin_runtime(mut self, runtime: Arc<dyn PluginRuntime>) -> Self {
+        self.plugin_runtime = runtime;
+        self
+    }
+
+    /// Override the response cache, e.g. to share one the control plane
+    /// invalidates on configuration changes.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
+
+    /// Override the rate limiter, e.g. to share one the control plane
+    /// resets limits of on configuration changes.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Override the circuit breakers, e.g. to share ones the control plane
+    /// resets on configuration changes.
+    #[must_use]
+    pub fn with_circuit_breakers(mut self, circuit_breakers: Arc<CircuitBreakerRegistry>) -> Self {
+        self.circuit_breakers = circuit_breakers;
+        self
+    }
+
+    /// Override the load balancer, e.g. to share one the control plane
+    /// evicts endpoint pools from on configuration changes.
+    #[must_use]
+    pub fn with_load_balancer(mut self, load_balancer: Arc<LoadBalancer>) -> Self {
+        self.load_balancer = load_balancer;
+        self
+    }
+
+    /// Add a sink for the usage records of proxied exchanges.
+    #[must_use]
+    pub fn with_usage_sink(mut self, sink: Arc<dyn UsageSink>) -> Self {
+        self.usage_sinks = self
+            .usage_sinks
+            .iter()
+            .cloned()
+            .chain(std::iter::once(sink))
+            .collect();
+        self
+    }
+
+    /// Override the `OAuth2` token cache, e.g. to share one the control plane
+    /// evicts tokens from on configuration changes.
+    #[must_use]
+    pub fn with_oauth2_tokens(mut self, tokens: Arc<OAuth2TokenCache>) -> Self {
+        self.oauth2_tokens = tokens;
+        self.rebuild_auth_registry();
+        self
+    }
+
+    /// Override the HTTP client configuration used to call `OAuth2` token
+    /// endpoints (TLS-only by default).
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub fn with_token_http_config(mut self, config: HttpClientConfig) -> Self {
+        self.token_http_config = config;
+        self.rebuild_auth_registry();
+        self
+    }
+
+    fn rebuild_auth_registry(&mut self) {
+        self.auth_registry = AuthPluginRegistry::with_builtins(
+            Arc::clone(&self.credential_resolver),
+            self.token_http_config.clone(),
+            Arc::clone(&self.oauth2_tokens),
+        );
+    }
+
+    /// Choose the endpoint for this call: the one pinned by
+    /// `X-OAGW-Target-Host`, or the load balancer's pick. Pinned calls bypass
+    /// balancing state, so they return no lease.
+    fn pick_endpoint(
+        &self,
+        upstream: &Upstream,
+        pinned: Option<usize>,
+        instance_uri: &str,
+    ) -> Result<(Endpoint, Option<EndpointLease>), DomainError> {
+        if let Some(index) = pinned {
+            return Ok((upstream.server.endpoints[index].clone(), None));
+        }
+        let pool = self.load_balancer.pool(upstream);
+        health_check::ensure_prober(&self.http_client, &pool);
+        let lease = pool.select().ok_or_else(|| DomainError::DownstreamError {
+            detail: "upstream has no endpoints".into(),
+            instance: instance_uri.to_string(),
+        })?;
+        Ok((lease.endpoint().clone(), Some(lease)))
+    }
+
+    /// The proxy pipeline. `chain` receives the custom plugin chain once the
+    /// route is resolved, so the caller can run `on_error` hooks; `cors`
+    /// receives the CORS headers for the response of a cross-origin request,
+    /// and `meter` the usage of the exchange once it has a route.
+    async fn proxy(
         &self,
         ctx: SecurityContext,
         req: http::Request<Body>,
+        chain: &mut Option<PluginChain>,
+        cors: &mut Option<CorsResponseHeaders>,
+        meter: &mut Option<UsageMeter>,
     ) -> Result<http::Response<Body>, DomainError> {
+        let started = Instant::now();
         let instance_uri = req.uri().to_string();
 
         // Normalize and parse alias and path_suffix from URI.
@@ -99,27 +283,202 @@ impl DataPlaneService for DataPlaneServiceImpl {
             })
             .unwrap_or_default();
 
-        let (parts, body) = req.into_parts();
+        let (mut parts, body) = req.into_parts();
+        let request_bytes = Arc::new(AtomicU64::new(0));
+        let body = usage_meter::count_request_body(body, &request_bytes);
         let method = parts.method;
         let req_headers = parts.headers;
 
-        // Convert Body to Bytes for the outbound HTTP request.
-        let body_bytes = body
-            .into_bytes()
-            .await
-            .map_err(|e| DomainError::Validation {
-                detail: format!("failed to read request body: {e}"),
-                instance: instance_uri.clone(),
-            })?;
+        // A WebSocket handshake goes through the same pipeline as any other
+        // request; the client side of the upgrade is completed after the
+        // upstream accepts it.
+        let client_upgrade = if websocket::is_upgrade_request(&req_headers) {
+            let on_upgrade = parts
+                .extensions
+                .remove::<hyper::upgrade::OnUpgrade>()
+                .ok_or_else(|| DomainError::Validation {
+                    detail: "WebSocket upgrade requires an HTTP/1.1 client connection".into(),
+                    instance: instance_uri.clone(),
+                })?;
+            Some(on_upgrade)
+        } else {
+            None
+        };
+
+        let grpc_native = grpc::is_grpc_request(&req_headers);
 
         // 1. Resolve upstream by alias.
-        let upstream = self.cp.resolve_upstream(&ctx, &alias).await?;
+        let mut effective = self.cp.resolve_effective_upstream(&ctx, &alias).await?;
+
+        // 1a. CORS preflights are answered locally, before auth and rate
+        // limiting.
+        if let Some(announced) = builtin_guards::preflight_method(&method, &req_headers)
+            && let Some(resp) = self
+                .answer_preflight(
+                    &effective,
+                    announced,
+                    &path_suffix,
+                    &req_headers,
+                    &instance_uri,
+                )
+                .await?
+        {
+            return Ok(resp);
+        }
 
         // 2. Resolve route.
-        let route = self
+        let mut route = self
             .cp
-            .resolve_route(&ctx, upstream.id, method.as_ref(), &path_suffix)
+            .match_route(&effective, method.as_ref(), &path_suffix)
             .await?;
+        if !self.usage_sinks.is_empty() {
+            *meter = Some(UsageMeter::start(
+                &ctx,
+                effective.upstream.id,
+                &route,
+                started,
+                request_bytes,
+            ));
+        }
+
+        // Check rate limits (upstream then route) before anything else is
+        // derived from the route: a degraded request may be redirected to
+        // its fallback upstream, which routes it on its own. Runs before
+        // auth. An inherited limit is counted against the binding that
+        // defines it.
+        let mut degrade: Option<DegradeConfig> = None;
+        for (key, rl) in [
+            (
+                RateLimiter::upstream_key(effective.rate_limit_owner),
+                effective.upstream.rate_limit.as_ref(),
+            ),
+            (RateLimiter::route_key(route.id), route.rate_limit.as_ref()),
+        ] {
+            let Some(rl) = rl else { continue };
+            let decision = self.rate_limiter.acquire(&key, rl, &instance_uri).await?;
+            if decision == RateLimitDecision::Degraded && degrade.is_none() {
+                degrade = Some(rl.degrade.clone().unwrap_or_default());
+            }
+        }
+        if let Some(ref d) = degrade {
+            if let Some(meter) = meter.as_mut() {
+                meter.degrade();
+            }
+            if let Some(ref fallback) = d.fallback_response {
+                return degraded_fallback_response(fallback, &instance_uri);
+            }
+            if let Some(fallback_id) = d.fallback_upstream_id {
+                let fallback = self.cp.get_upstream(&ctx, fallback_id).await?;
+                effective = self
+                    .cp
+                    .resolve_effective_upstream(&ctx, &fallback.alias)
+                    .await?;
+                route = self
+                    .cp
+                    .match_route(&effective, method.as_ref(), &path_suffix)
+                    .await?;
+                if let Some(meter) = meter.as_mut() {
+                    meter.reroute(effective.upstream.id, &route);
+                }
+            }
+        }
+
+        // Builtin guards: refuse origins the CORS guard does not allow, and
+        // bound the whole exchange by the timeout guard's request deadline.
+        let guards = resolve_builtin_guards(&effective.upstream, Some(&route), &instance_uri)?;
+        if let Some(ref config) = guards.cors {
+            *cors = CorsResponseHeaders::for_request(config, &req_headers, &instance_uri)?;
+        }
+        let timeouts = guards.timeout.unwrap_or_default();
+        let deadline = timeouts
+            .request_timeout
+            .map(|t| tokio::time::Instant::from_std(started) + t);
+
+        // path_suffix is the full path from the proxy URL; strip the route
+        // prefix so the outbound path is route_path + remaining_suffix.
+        let grpc_path = route.match_rules.grpc.as_ref().map(GrpcMatch::path);
+        let route_path = match (&grpc_path, &route.match_rules.http) {
+            (Some(path), _) => path.as_str(),
+            (None, Some(http_match)) => http_match.path.as_str(),
+            (None, None) => "/",
+        };
+        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
+        let mut outbound_path = request_builder::join_path(route_path, remaining_suffix);
+        let mut outbound_query = query_params.clone();
+
+        // 2'. Resolve custom plugins. They see the client's request addressed
+        // to the outbound path, without hop-by-hop or internal headers.
+        let mut visible_headers = req_headers.clone();
+        headers::strip_hop_by_hop(&mut visible_headers);
+        headers::strip_internal_headers(&mut visible_headers);
+        *chain = PluginChain::resolve(
+            self.cp.as_ref(),
+            &effective,
+            &route,
+            PluginExchange {
+                tenant_id: ctx.subject_tenant_id(),
+                route_id: route.id,
+                started,
+                request: PluginRequest {
+                    method: method.to_string(),
+                    path: outbound_path.clone(),
+                    query: query_params.clone(),
+                    headers: PluginHeaders::new(plugin_chain::header_pairs(&visible_headers)),
+                    body: Bytes::new(),
+                },
+                response: None,
+                error: None,
+            },
+            &instance_uri,
+        )
+        .await?;
+
+        // Plugins see the buffered request body. Without them the body is
+        // streamed to the upstream as it arrives.
+        let (body, client_body) = match chain.as_mut() {
+            Some(plugins) => {
+                let bytes = request_body::buffer(body, &instance_uri).await?;
+                plugins.exchange_mut().request.body = bytes.clone();
+                (Body::Bytes(bytes.clone()), Some(bytes))
+            }
+            None => (body, None),
+        };
+
+        // 2a. gRPC routes take native calls, or HTTP/JSON calls when the route
+        // transcodes them; the JSON body becomes a framed protobuf message.
+        let transcoder = if grpc_native {
+            None
+        } else {
+            self.transcoders
+                .get(&route)
+                .map_err(|e| DomainError::ProtocolError {
+                    detail: format!("route grpc_transcoding is unusable: {e}"),
+                    instance: instance_uri.clone(),
+                })?
+        };
+        if route.match_rules.grpc.is_some() && !grpc_native && transcoder.is_none() {
+            return Err(DomainError::Validation {
+                detail: format!(
+                    "route only accepts gRPC calls (content-type: {})",
+                    grpc::GRPC_CONTENT_TYPE
+                ),
+                instance: instance_uri,
+            });
+        }
+        let mut body = match transcoder {
+            Some(ref t) => {
+                let json = request_body::buffer(body, &instance_uri).await?;
+                t.encode_request(&json)
+                    .and_then(|message| grpc::encode_frame(&message))
+                    .map(|frame| Body::Bytes(Bytes::from(frame)))
+                    .map_err(|e| DomainError::Validation {
+                        detail: format!("request body does not match the gRPC request type: {e}"),
+                        instance: instance_uri.clone(),
+                    })?
+            }
+            None => body,
+        };
+        let grpc_call = grpc_native || transcoder.is_some();
 
         // 2b. Validate query parameters against route's allowlist.
         if let Some(ref http_match) = route.match_rules.http
@@ -155,6 +514,19 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         }
 
+        let upstream = effective.upstream;
+
+        // 2e. Honour X-OAGW-Target-Host before the header is stripped below.
+        let target_host = req_headers
+            .get(headers::TARGET_HOST_HEADER)
+            .map(|v| {
+                v.to_str().map_err(|_| DomainError::InvalidTargetHost {
+                    instance: instance_uri.clone(),
+                })
+            })
+            .transpose()?;
+        let pinned = load_balancer::resolve_target_host(&upstream, target_host, &instance_uri)?;
+
         // 3. Prepare outbound headers (passthrough + strip).
         let mode = upstream
             .headers
@@ -170,115 +542,249 @@ impl DataPlaneService for DataPlaneServiceImpl {
         headers::strip_hop_by_hop(&mut outbound_headers);
         headers::strip_internal_headers(&mut outbound_headers);
 
-        // 4. Execute auth plugin.
-        if let Some(ref auth) = upstream.auth {
-            let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
-                DomainError::AuthenticationFailed {
-                    detail: e.to_string(),
-                    instance: instance_uri.clone(),
-                }
-            })?;
-            let auth_headers: HashMap<String, String> = outbound_headers
-                .iter()
-                .filter_map(|(k, v)| {
-                    v.to_str()
-                        .ok()
-                        .map(|s| (k.as_str().to_string(), s.to_string()))
-                })
-                .collect();
-            let mut auth_ctx = AuthContext {
-                headers: auth_headers,
-                config: auth.config.clone().unwrap_or_default(),
-            };
-            plugin
-                .authenticate(&mut auth_ctx)
-                .await
-                .map_err(|e| match e {
-                    crate::domain::plugin::PluginError::SecretNotFound(ref s) => {
-                        DomainError::SecretNotFound {
-                            detail: s.clone(),
-                            instance: instance_uri.clone(),
-                        }
-                    }
-                    crate::domain::plugin::PluginError::Rejected(ref msg) => {
-                        DomainError::Validation {
-                            detail: msg.clone(),
-                            instance: instance_uri.clone(),
-                        }
-                    }
-                    crate::domain::plugin::PluginError::AuthFailed(_)
-                    | crate::domain::plugin::PluginError::Internal(_) => {
-                        DomainError::AuthenticationFailed {
-                            detail: e.to_string(),
-                            instance: instance_uri.clone(),
-                        }
+        // 4. Execute auth plugin. The pre-auth context is kept so the request
+        // can be re-authenticated if the upstream rejects the credentials.
+        let auth = match upstream.auth {
+            Some(ref auth) => {
+                let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
+                    DomainError::AuthenticationFailed {
+                        detail: e.to_string(),
+                        instance: instance_uri.clone(),
                     }
                 })?;
-            outbound_headers = HeaderMap::new();
-            for (k, v) in &auth_ctx.headers {
-                if let (Ok(name), Ok(val)) = (
-                    HeaderName::from_bytes(k.as_bytes()),
-                    HeaderValue::from_str(v),
-                ) {
-                    outbound_headers.insert(name, val);
+                let auth_ctx = AuthContext {
+                    upstream_id: upstream.id,
+                    tenant_id: ctx.subject_tenant_id(),
+                    security_context: ctx.clone(),
+                    headers: outbound_headers
+                        .iter()
+                        .filter_map(|(k, v)| {
+                            v.to_str()
+                                .ok()
+                                .map(|s| (k.as_str().to_string(), s.to_string()))
+                        })
+                        .collect(),
+                    config: auth.config.clone().unwrap_or_default(),
+                };
+                outbound_headers =
+                    run_auth_plugin(plugin.as_ref(), &auth_ctx, &instance_uri).await?;
+                Some((plugin, auth_ctx))
+            }
+            None => None,
+        };
+
+        // Small bodies stay replayable so a request rejected with 401 can be
+        // resent with refreshed credentials; large uploads keep streaming.
+        if auth.is_some()
+            && matches!(body, Body::Stream(_))
+            && request_body::declared_length(&req_headers)
+                .is_some_and(|len| len <= request_body::REPLAYABLE_LIMIT)
+        {
+            body = Body::Bytes(request_body::buffer(body, &instance_uri).await?);
+        }
+
+        // 4a. Run on_request hooks after auth; their header edits are
+        // replayed on top of the authenticated headers, also on re-auth.
+        let mut header_edits = Vec::new();
+        let mut body_rewritten = false;
+        if let Some(plugins) = chain.as_mut()
+            && plugins.has(PluginPhase::OnRequest)
+        {
+            if let Some(resp) = plugins
+                .run(
+                    self.plugin_runtime.as_ref(),
+                    PluginPhase::OnRequest,
+                    &instance_uri,
+                )
+                .await?
+            {
+                return Ok(resp);
+            }
+            let request = &plugins.exchange().request;
+            header_edits = request.headers.edits().to_vec();
+            outbound_path.clone_from(&request.path);
+            outbound_query.clone_from(&request.query);
+            if client_body.as_ref() != Some(&request.body) {
+                if grpc_call {
+                    return Err(DomainError::PluginFailed {
+                        detail: "plugins cannot rewrite the body of a gRPC call".into(),
+                        instance: instance_uri,
+                    });
                 }
+                body = Body::Bytes(request.body.clone());
+                body_rewritten = true;
             }
         }
+        let apply_plugin_edits = |outbound: &mut HeaderMap| {
+            plugin_chain::apply_header_edits(outbound, &header_edits);
+            if body_rewritten {
+                outbound.remove(http::header::CONTENT_LENGTH);
+            }
+        };
+        apply_plugin_edits(&mut outbound_headers);
+
+        // 4b. Answer plain GETs on caching routes from the response cache
+        // while the entry is fresh. A stale entry with an ETag is revalidated
+        // by the upstream call below.
+        let cacheable = match route.response_cache {
+            Some(ref config)
+                if method == http::Method::GET && !grpc_call && client_upgrade.is_none() =>
+            {
+                let key = CacheKey::new(
+                    ctx.subject_tenant_id(),
+                    &alias,
+                    &outbound_path,
+                    &outbound_query,
+                    config,
+                    &req_headers,
+                );
+                let stale = match self.response_cache.lookup(&key, route.id, upstream.id) {
+                    Lookup::Fresh(entry) => {
+                        return self
+                            .serve_cached(
+                                chain,
+                                &entry,
+                                CacheStatus::Hit,
+                                &req_headers,
+                                degrade.is_some(),
+                                &instance_uri,
+                            )
+                            .await;
+                    }
+                    Lookup::Stale(entry) => Some(entry),
+                    Lookup::Miss => None,
+                };
+                Some(Cacheable { config, key, stale })
+            }
+            _ => None,
+        };
+
+        // A streamed body keeps the length the client announced; without one
+        // it is sent with chunked framing.
+        let streamed_length = match body {
+            Body::Stream(_) => req_headers.get(http::header::CONTENT_LENGTH).cloned(),
+            _ => None,
+        };
 
         // 5. Apply header rules + set Host.
-        if let Some(ref hc) = upstream.headers
-            && let Some(ref rules) = hc.request
+        let (endpoint, lease) = self.pick_endpoint(&upstream, pinned, &instance_uri)?;
+        let finish_headers = |outbound: &mut HeaderMap| {
+            if let Some(ref hc) = upstream.headers
+                && let Some(ref rules) = hc.request
+            {
+                headers::apply_header_rules(outbound, rules);
+            }
+            headers::set_host_header(outbound, &endpoint.host, endpoint.port);
+            if let Some(ref length) = streamed_length {
+                outbound.insert(http::header::CONTENT_LENGTH, length.clone());
+            }
+            if degrade.is_some() {
+                outbound.insert(
+                    headers::DEGRADED_HEADER,
+                    HeaderValue::from_static(DEGRADED_REASON),
+                );
+            }
+            if client_upgrade.is_some() {
+                websocket::apply_handshake_headers(&req_headers, outbound);
+            }
+            if grpc_call {
+                grpc::apply_request_headers(&req_headers, outbound, !grpc_native);
+            }
+            // The gateway, not the client, decides what to revalidate.
+            if let Some(ref cacheable) = cacheable {
+                outbound.remove(http::header::IF_NONE_MATCH);
+                outbound.remove(http::header::IF_MODIFIED_SINCE);
+                if let Some(etag) = cacheable.stale.as_ref().and_then(|e| e.etag()) {
+                    outbound.insert(http::header::IF_NONE_MATCH, etag.clone());
+                }
+            }
+        };
+        finish_headers(&mut outbound_headers);
+
+        // 5b. Fail fast if the circuit for this endpoint is open.
+        let circuit = match upstream.circuit_breaker {
+            Some(ref cb) if cb.enabled => {
+                let key = CircuitBreakerRegistry::key(
+                    upstream.id,
+                    cb.scope,
+                    &endpoint.host,
+                    endpoint.port,
+                );
+                let target = format!("{}:{}", endpoint.host, endpoint.port);
+                let permit = self
+                    .circuit_breakers
+                    .try_acquire(&key, cb, &target, &instance_uri)?;
+                Some((permit, &cb.failure_conditions))
+            }
+            _ => None,
+        };
+
+        // 6. Build URL from the outbound path, as plugins left it.
+        let url =
+            request_builder::build_upstream_url(&endpoint, &outbound_path, "", &outbound_query);
+
+        // 7. Forward request with timeout on response headers: the route's
+        // first-byte timeout, or the gateway's, cut short by the request
+        // deadline. A native call's grpc-timeout can only shorten it further.
+        let client = if grpc_call {
+            &self.grpc_client
+        } else {
+            &self.http_client
+        };
+        let mut timeout = timeouts.first_byte_timeout.unwrap_or(self.request_timeout);
+        if let Some(deadline) = deadline {
+            timeout = timeout.min(deadline.saturating_duration_since(tokio::time::Instant::now()));
+        }
+        let timeout = match grpc::parse_timeout(&req_headers) {
+            Some(grpc_deadline) if grpc_native => grpc_deadline.min(timeout),
+            _ => timeout,
+        };
+        let send = |outbound_headers: HeaderMap, body: reqwest::Body| {
+            let mut outbound = client
+                .request(method.clone(), websocket::handshake_url(&url))
+                .headers(outbound_headers)
+                .body(body);
+            if client_upgrade.is_some() {
+                outbound = outbound.version(http::Version::HTTP_11);
+            }
+            tokio::time::timeout(timeout, outbound.send())
+        };
+        let replay = request_body::replayable(&body);
+        let mut result = send(outbound_headers, request_body::to_reqwest(body)).await;
+
+        // 7a. The upstream rejected the injected credentials: if the auth
+        // plugin can refresh them, authenticate again and resend once. A
+        // streamed body has been consumed, so its 401 goes to the client.
+        let rejected = matches!(
+            &result,
+            Ok(Ok(resp)) if resp.status() == http::StatusCode::UNAUTHORIZED
+        );
+        if rejected
+            && let Some(replay) = replay
+            && let Some((ref plugin, ref auth_ctx)) = auth
+            && plugin.on_unauthorized(auth_ctx).await
         {
-            headers::apply_header_rules(&mut outbound_headers, rules);
+            let mut outbound_headers =
+                run_auth_plugin(plugin.as_ref(), auth_ctx, &instance_uri).await?;
+            apply_plugin_edits(&mut outbound_headers);
+            finish_headers(&mut outbound_headers);
+            result = send(outbound_headers, reqwest::Body::from(replay)).await;
         }
-        let endpoint =
-            upstream
-                .server
-                .endpoints
-                .first()
-                .ok_or_else(|| DomainError::DownstreamError {
-                    detail: "upstream has no endpoints".into(),
-                    instance: instance_uri.clone(),
-                })?;
-        headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
 
-        // 6. Check rate limit (upstream then route).
-        if let Some(ref rl) = upstream.rate_limit {
-            let key = format!("upstream:{}", upstream.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
+        // A send that failed on the client's body (too large, aborted) says
+        // nothing about the upstream's health.
+        if let Ok(Err(ref e)) = result
+            && let Some(body_err) = request_body::client_fault(e)
+        {
+            return Err(body_err.to_domain(&instance_uri));
         }
-        if let Some(ref rl) = route.rate_limit {
-            let key = format!("route:{}", route.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
+        if let Some((permit, conditions)) = circuit {
+            record_circuit_outcome(permit, conditions, &result);
         }
-
-        // 7. Build URL.
-        // path_suffix is the full path from the proxy URL; strip the route prefix
-        // so we get: endpoint + route_path + remaining_suffix.
-        let route_path = route
-            .match_rules
-            .http
-            .as_ref()
-            .map_or("/", |h| h.path.as_str());
-        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
-        let url = request_builder::build_upstream_url(
-            endpoint,
-            route_path,
-            remaining_suffix,
-            &query_params,
-        )?;
-
-        // 8. Forward request with timeout on response headers.
-        let send_future = self
-            .http_client
-            .request(method, &url)
-            .headers(outbound_headers)
-            .body(body_bytes)
-            .send();
-
-        let timeout = self.request_timeout;
-        let response = tokio::time::timeout(timeout, send_future)
-            .await
+        if let Some(ref lease) = lease {
+            record_endpoint_outcome(lease, &result);
+        }
+        let response = result
             .map_err(|_| DomainError::RequestTimeout {
                 detail: format!("request to {url} timed out after {timeout:?}"),
                 instance: instance_uri.clone(),
@@ -297,16 +803,142 @@ impl DataPlaneService for DataPlaneServiceImpl {
                 }
             })?;
 
-        // 9. Build streaming response.
+        // 8. Build streaming response.
         let status = response.status();
         let mut resp_headers = response.headers().clone();
         headers::sanitize_response_headers(&mut resp_headers);
 
-        let body_stream: BodyStream = Box::pin(
-            response
-                .bytes_stream()
-                .map(|r| r.map_err(|e| Box::new(e) as BoxError)),
-        );
+        // 8a. A 304 renews the entry being revalidated. Other responses are
+        // stored, if cacheable, once their body has been received.
+        let cached_route = cacheable.is_some();
+        let mut pending = None;
+        if let Some(cacheable) = cacheable {
+            if status == http::StatusCode::NOT_MODIFIED
+                && let Some(stale) = cacheable.stale
+            {
+                drop(lease);
+                let entry = self.response_cache.refresh(
+                    &cacheable.key,
+                    &stale,
+                    &resp_headers,
+                    cacheable.config,
+                );
+                return self
+                    .serve_cached(
+                        chain,
+                        &entry,
+                        CacheStatus::Revalidated,
+                        &req_headers,
+                        degrade.is_some(),
+                        &instance_uri,
+                    )
+                    .await;
+            }
+            pending = self.response_cache.admit(
+                cacheable.key,
+                route.id,
+                upstream.id,
+                cacheable.config,
+                status,
+                &resp_headers,
+            );
+        }
+
+        // 8b. Upstream accepted the upgrade: answer the client with 101 and
+        // relay frames in the background. An upstream that refuses the
+        // upgrade is answered like a regular response below.
+        if let Some(client_upgrade) = client_upgrade
+            && status == http::StatusCode::SWITCHING_PROTOCOLS
+        {
+            websocket::spawn_relay(client_upgrade, response, self.websocket_idle_timeout, lease);
+            let mut resp = http::Response::new(Body::Empty);
+            *resp.status_mut() = status;
+            *resp.headers_mut() = resp_headers;
+            resp.extensions_mut().insert(ErrorSource::Upstream);
+            return Ok(resp);
+        }
+
+        // 8c. gRPC responses keep their trailers; transcoded calls are
+        // converted back to JSON. Anything but 200 is not a gRPC response and
+        // is passed through below.
+        if grpc_call && status == http::StatusCode::OK {
+            let response = http::Response::from(response);
+            let mut resp = if let Some(transcoder) = transcoder {
+                grpc::transcode_response(response, transcoder, lease, deadline, &instance_uri)
+                    .await?
+            } else {
+                let trailers = Trailers::new();
+                let body = grpc::passthrough_body(response, trailers.clone(), lease);
+                let mut resp = http::Response::new(Body::Stream(body));
+                *resp.headers_mut() = resp_headers;
+                resp.extensions_mut().insert(trailers);
+                resp.extensions_mut().insert(ErrorSource::Upstream);
+                resp
+            };
+            if degrade.is_some() {
+                resp.headers_mut().insert(
+                    headers::DEGRADED_HEADER,
+                    HeaderValue::from_static(DEGRADED_REASON),
+                );
+            }
+            return Ok(resp);
+        }
+
+        // 8d. on_response hooks work on the buffered upstream response.
+        if let Some(plugins) = chain.as_mut()
+            && plugins.has(PluginPhase::OnResponse)
+        {
+            let read = response.bytes();
+            let read = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, read).await.map_err(|_| {
+                    DomainError::RequestTimeout {
+                        detail: format!("request to {url} exceeded the route's request timeout"),
+                        instance: instance_uri.clone(),
+                    }
+                })?,
+                None => read.await,
+            };
+            let upstream_body = read.map_err(|e| DomainError::DownstreamError {
+                detail: format!("failed to read upstream response: {e}"),
+                instance: instance_uri.clone(),
+            })?;
+            drop(lease);
+            if let Some(pending) = pending {
+                self.response_cache.complete(pending, upstream_body.clone());
+            }
+            let mut resp = self
+                .run_on_response(
+                    plugins,
+                    status,
+                    resp_headers,
+                    upstream_body,
+                    degrade.is_some(),
+                    &instance_uri,
+                )
+                .await?;
+            if cached_route {
+                resp.headers_mut().insert(
+                    headers::CACHE_STATUS_HEADER,
+                    CacheStatus::Miss.header_value(),
+                );
+            }
+            return Ok(resp);
+        }
+
+        // The lease travels with the body so the endpoint counts as in flight
+        // until the response has been fully streamed.
+        let body_stream: BodyStream = Box::pin(response.bytes_stream().map(move |r| {
+            let _ = &lease;
+            r.map_err(|e| Box::new(e) as BoxError)
+        }));
+        let body_stream = match deadline {
+            Some(deadline) => builtin_guards::with_deadline(body_stream, deadline),
+            None => body_stream,
+        };
+        let body_stream = match pending {
+            Some(pending) => self.response_cache.record(pending, body_stream),
+            None => body_stream,
+        };
 
         let mut resp = http::Response::builder()
             .status(status)
@@ -317,10 +949,292 @@ impl DataPlaneService for DataPlaneServiceImpl {
             })?;
 
         *resp.headers_mut() = resp_headers;
+        if degrade.is_some() {
+            resp.headers_mut().insert(
+                headers::DEGRADED_HEADER,
+                HeaderValue::from_static(DEGRADED_REASON),
+            );
+        }
+        if cached_route {
+            resp.headers_mut().insert(
+                headers::CACHE_STATUS_HEADER,
+                CacheStatus::Miss.header_value(),
+            );
+        }
         resp.extensions_mut().insert(ErrorSource::Upstream);
 
         Ok(resp)
     }
+
+    /// Answer a CORS preflight when the CORS guard covers the route of the
+    /// method it announces, or the upstream if no route takes that method.
+    /// Other preflights are proxied like any `OPTIONS` request.
+    async fn answer_preflight(
+        &self,
+        upstream: &EffectiveUpstream,
+        announced: &str,
+        path_suffix: &str,
+        req_headers: &HeaderMap,
+        instance_uri: &str,
+    ) -> Result<Option<http::Response<Body>>, DomainError> {
+        let route = match self.cp.match_route(upstream, announced, path_suffix).await {
+            Ok(route) => Some(route),
+            Err(DomainError::NotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+        let guards = resolve_builtin_guards(&upstream.upstream, route.as_ref(), instance_uri)?;
+        match guards.cors {
+            Some(ref config) => {
+                builtin_guards::preflight_response(config, req_headers, instance_uri).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Run on_response hooks on a buffered upstream response and build the
+    /// response for the client from what they leave.
+    async fn run_on_response(
+        &self,
+        plugins: &mut PluginChain,
+        status: http::StatusCode,
+        mut resp_headers: HeaderMap,
+        upstream_body: Bytes,
+        degraded: bool,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        plugins.exchange_mut().response = Some(PluginResponse {
+            status: status.as_u16(),
+            headers: PluginHeaders::new(plugin_chain::header_pairs(&resp_headers)),
+            body: upstream_body.clone(),
+        });
+        if let Some(resp) = plugins
+            .run(
+                self.plugin_runtime.as_ref(),
+                PluginPhase::OnResponse,
+                instance_uri,
+            )
+            .await?
+        {
+            return Ok(resp);
+        }
+        let Some(ref out) = plugins.exchange().response else {
+            return Err(DomainError::internal("plugin response state is missing"));
+        };
+        plugin_chain::apply_header_edits(&mut resp_headers, out.headers.edits());
+        headers::sanitize_response_headers(&mut resp_headers);
+        if out.body != upstream_body {
+            resp_headers.remove(http::header::CONTENT_LENGTH);
+        }
+        let mut resp = http::Response::builder()
+            .status(out.status)
+            .body(Body::from(out.body.clone()))
+            .map_err(|e| DomainError::PluginFailed {
+                detail: format!("plugin response is invalid: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+        *resp.headers_mut() = resp_headers;
+        if degraded {
+            resp.headers_mut().insert(
+                headers::DEGRADED_HEADER,
+                HeaderValue::from_static(DEGRADED_REASON),
+            );
+        }
+        resp.extensions_mut().insert(ErrorSource::Upstream);
+        Ok(resp)
+    }
+
+    /// Answer from a cached response, as if the upstream had sent it:
+    /// on_response hooks run on it, and a matching `If-None-Match` from the
+    /// client gets `304 Not Modified`.
+    async fn serve_cached(
+        &self,
+        chain: &mut Option<PluginChain>,
+        entry: &CachedResponse,
+        status: CacheStatus,
+        req_headers: &HeaderMap,
+        degraded: bool,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let mut resp = match chain.as_mut() {
+            Some(plugins) if plugins.has(PluginPhase::OnResponse) => {
+                self.run_on_response(
+                    plugins,
+                    http::StatusCode::OK,
+                    entry.headers_with_age(),
+                    entry.body().clone(),
+                    degraded,
+                    instance_uri,
+                )
+                .await?
+            }
+            _ => {
+                let mut resp = entry.to_response(req_headers);
+                if degraded {
+                    resp.headers_mut().insert(
+                        headers::DEGRADED_HEADER,
+                        HeaderValue::from_static(DEGRADED_REASON),
+                    );
+                }
+                resp.extensions_mut().insert(ErrorSource::Upstream);
+                resp
+            }
+        };
+        resp.headers_mut()
+            .insert(headers::CACHE_STATUS_HEADER, status.header_value());
+        Ok(resp)
+    }
+}
+
+#[async_trait::async_trait]
+impl DataPlaneService for DataPlaneServiceImpl {
+    async fn proxy_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let instance_uri = req.uri().to_string();
+        let mut chain = None;
+        let mut cors = None;
+        let mut meter = None;
+        let result = match (
+            self.proxy(ctx, req, &mut chain, &mut cors, &mut meter)
+                .await,
+            chain,
+        ) {
+            (Err(err), Some(chain))
+                if plugin_chain::recoverable(&err) && chain.has(PluginPhase::OnError) =>
+            {
+                chain
+                    .recover(self.plugin_runtime.as_ref(), err, &instance_uri)
+                    .await
+            }
+            (result, _) => result,
+        };
+        let result = match (result, cors) {
+            (Ok(mut resp), Some(cors)) => {
+                cors.apply(resp.headers_mut());
+                Ok(resp)
+            }
+            (result, _) => result,
+        };
+        // Only responses are metered: requests the gateway refused never
+        // reached the upstream.
+        match (result, meter) {
+            (Ok(resp), Some(meter)) => Ok(meter.attach(resp, Arc::clone(&self.usage_sinks))),
+            (result, _) => result,
+        }
+    }
+
+    fn circuit_breaker_status(&self, upstream: &Upstream) -> Vec<CircuitBreakerStatus> {
+        self.circuit_breakers.status(upstream)
+    }
+}
+
+/// Builtin guards attached to `upstream` and `route`.
+fn resolve_builtin_guards(
+    upstream: &Upstream,
+    route: Option<&Route>,
+    instance_uri: &str,
+) -> Result<BuiltinGuards, DomainError> {
+    BuiltinGuards::resolve([
+        upstream.plugins.as_ref(),
+        route.and_then(|r| r.plugins.as_ref()),
+    ])
+    .map_err(|detail| DomainError::PluginFailed {
+        detail,
+        instance: instance_uri.to_string(),
+    })
+}
+
+/// Run `plugin` on a copy of `auth_ctx` and return the resulting outbound
+/// headers.
+async fn run_auth_plugin(
+    plugin: &dyn AuthPlugin,
+    auth_ctx: &AuthContext,
+    instance_uri: &str,
+) -> Result<HeaderMap, DomainError> {
+    let mut auth_ctx = auth_ctx.clone();
+    plugin
+        .authenticate(&mut auth_ctx)
+        .await
+        .map_err(|e| match e {
+            PluginError::SecretNotFound(ref s) => DomainError::SecretNotFound {
+                detail: s.clone(),
+                instance: instance_uri.to_string(),
+            },
+            PluginError::Rejected(ref msg) => DomainError::Validation {
+                detail: msg.clone(),
+                instance: instance_uri.to_string(),
+            },
+            PluginError::AuthFailed(_) | PluginError::Internal(_) | PluginError::Script(_) => {
+                DomainError::AuthenticationFailed {
+                    detail: e.to_string(),
+                    instance: instance_uri.to_string(),
+                }
+            }
+        })?;
+    let mut outbound_headers = HeaderMap::new();
+    for (k, v) in &auth_ctx.headers {
+        if let (Ok(name), Ok(val)) = (
+            HeaderName::from_bytes(k.as_bytes()),
+            HeaderValue::from_str(v),
+        ) {
+            outbound_headers.insert(name, val);
+        }
+    }
+    Ok(outbound_headers)
+}
+
+/// Feed the upstream call result into outlier detection: 5xx responses and
+/// connect errors count against the endpoint, other outcomes are ignored.
+fn record_endpoint_outcome(
+    lease: &EndpointLease,
+    result: &Result<Result<reqwest::Response, reqwest::Error>, tokio::time::error::Elapsed>,
+) {
+    match result {
+        Ok(Ok(resp)) if resp.status().is_server_error() => lease.failure(),
+        Ok(Ok(_)) => lease.success(),
+        Ok(Err(e)) if e.is_connect() => lease.failure(),
+        Ok(Err(_)) | Err(_) => {}
+    }
+}
+
+/// Feed the upstream call result into the circuit breaker. Errors that match
+/// none of the failure conditions leave the circuit untouched.
+fn record_circuit_outcome(
+    permit: CircuitPermit,
+    conditions: &FailureConditions,
+    result: &Result<Result<reqwest::Response, reqwest::Error>, tokio::time::error::Elapsed>,
+) {
+    let failed = match result {
+        Ok(Ok(resp)) => conditions.status_codes.contains(&resp.status().as_u16()),
+        Err(_) => conditions.timeout,
+        Ok(Err(e)) if e.is_timeout() => conditions.timeout,
+        Ok(Err(e)) if e.is_connect() => conditions.connection_error,
+        Ok(Err(_)) => return,
+    };
+    if failed {
+        permit.failure();
+    } else if matches!(result, Ok(Ok(_))) {
+        permit.success();
+    }
+}
+
+/// Build the static response configured for the `degrade` strategy.
+fn degraded_fallback_response(
+    fallback: &FallbackResponse,
+    instance_uri: &str,
+) -> Result<http::Response<Body>, DomainError> {
+    let mut resp = http::Response::builder()
+        .status(fallback.status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .header(headers::DEGRADED_HEADER, DEGRADED_REASON)
+        .body(Body::from(fallback.body.clone()))
+        .map_err(|e| DomainError::Internal {
+            message: format!("invalid degrade fallback response for {instance_uri}: {e}"),
+        })?;
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
 }
 
 /// Normalize a URL path: collapse consecutive slashes and resolve `.`/`..` segments.
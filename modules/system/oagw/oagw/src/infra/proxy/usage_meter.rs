@@ -0,0 +1,572 @@
+//! Data-plane metering of proxied exchanges.
+//!
+//! A [`UsageMeter`] is started once the route of a request is resolved and
+//! attached to the response the client receives. The usage record is emitted
+//! when the response body has been relayed, or when the client drops it.
+//! Routes with `usage_extraction` also have the token counts their upstream
+//! reports read from JSON bodies and server-sent events on the way through.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll, ready};
+use std::time::Instant;
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use http::{HeaderMap, header};
+use modkit_security::SecurityContext;
+use oagw_sdk::body::{Body, BodyStream, BoxError};
+use serde_json::Value;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::headers;
+use crate::domain::model::{Route, UsageExtractionConfig};
+use crate::domain::usage::{UsageRecord, UsageSink};
+
+/// JSON bodies larger than this are relayed without extracting token counts.
+const MAX_JSON_BODY_BYTES: usize = 1024 * 1024;
+
+/// Server-sent event lines and event data larger than this are skipped.
+const MAX_EVENT_BYTES: usize = 256 * 1024;
+
+/// Configured usage sinks, shared by the responses being metered.
+pub(crate) type UsageSinks = Arc<[Arc<dyn UsageSink>]>;
+
+/// Count the bytes of a client request body as the upstream reads it.
+pub(crate) fn count_request_body(body: Body, counter: &Arc<AtomicU64>) -> Body {
+    match body {
+        Body::Empty => Body::Empty,
+        Body::Bytes(bytes) => {
+            counter.store(bytes.len() as u64, Ordering::Relaxed);
+            Body::Bytes(bytes)
+        }
+        Body::Stream(stream) => {
+            let counter = Arc::clone(counter);
+            Body::Stream(Box::pin(stream.inspect(move |chunk| {
+                if let Ok(chunk) = chunk {
+                    counter.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+                }
+            })))
+        }
+    }
+}
+
+/// Usage of one exchange, up to its response.
+pub(crate) struct UsageMeter {
+    tenant_id: Uuid,
+    subject_id: Uuid,
+    upstream_id: Uuid,
+    route_id: Uuid,
+    extraction: Option<UsageExtractionConfig>,
+    degraded: bool,
+    started: Instant,
+    request_bytes: Arc<AtomicU64>,
+}
+
+impl UsageMeter {
+    pub(crate) fn start(
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        route: &Route,
+        started: Instant,
+        request_bytes: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            tenant_id: ctx.subject_tenant_id(),
+            subject_id: ctx.subject_id(),
+            upstream_id,
+            route_id: route.id,
+            extraction: route.usage_extraction.clone(),
+            degraded: false,
+            started,
+            request_bytes,
+        }
+    }
+
+    /// A rate limit degraded the request.
+    pub(crate) fn degrade(&mut self) {
+        self.degraded = true;
+    }
+
+    /// The request was redirected to `route` of another upstream.
+    pub(crate) fn reroute(&mut self, upstream_id: Uuid, route: &Route) {
+        self.upstream_id = upstream_id;
+        self.route_id = route.id;
+        self.extraction = route.usage_extraction.clone();
+    }
+
+    /// Meter `resp`; the record goes to `sinks` once its body is relayed.
+    pub(crate) fn attach(
+        self,
+        resp: http::Response<Body>,
+        sinks: UsageSinks,
+    ) -> http::Response<Body> {
+        let (parts, body) = resp.into_parts();
+        let extractor = self
+            .extraction
+            .as_ref()
+            .and_then(|config| TokenExtractor::for_response(config, &parts.headers));
+        let mut pending = Pending {
+            status: parts.status.as_u16(),
+            cache_hit: parts
+                .headers
+                .get(headers::CACHE_STATUS_HEADER)
+                .is_some_and(|v| v == "hit"),
+            response_bytes: 0,
+            extractor,
+            meter: self,
+            sinks,
+        };
+        let body = match body {
+            Body::Empty => {
+                pending.finish();
+                Body::Empty
+            }
+            Body::Bytes(bytes) => {
+                pending.observe(&bytes);
+                pending.finish();
+                Body::Bytes(bytes)
+            }
+            Body::Stream(inner) => Body::Stream(Box::pin(Metered {
+                inner,
+                pending: Some(pending),
+            })),
+        };
+        http::Response::from_parts(parts, body)
+    }
+}
+
+/// A response whose usage has not been emitted yet.
+struct Pending {
+    meter: UsageMeter,
+    status: u16,
+    cache_hit: bool,
+    response_bytes: u64,
+    extractor: Option<TokenExtractor>,
+    sinks: UsageSinks,
+}
+
+impl Pending {
+    fn observe(&mut self, chunk: &Bytes) {
+        self.response_bytes += chunk.len() as u64;
+        if let Some(ref mut extractor) = self.extractor {
+            extractor.feed(chunk);
+        }
+    }
+
+    fn finish(self) {
+        let (input_tokens, output_tokens) =
+            self.extractor.map_or((None, None), TokenExtractor::finish);
+        let record = UsageRecord {
+            tenant_id: self.meter.tenant_id,
+            subject_id: self.meter.subject_id,
+            upstream_id: self.meter.upstream_id,
+            route_id: self.meter.route_id,
+            status: self.status,
+            latency: self.meter.started.elapsed(),
+            request_bytes: self.meter.request_bytes.load(Ordering::Relaxed),
+            response_bytes: self.response_bytes,
+            input_tokens,
+            output_tokens,
+            cache_hit: self.cache_hit,
+            degraded: self.meter.degraded,
+            completed_at: OffsetDateTime::now_utc(),
+        };
+        for sink in self.sinks.iter() {
+            sink.record(&record);
+        }
+    }
+}
+
+/// Response body stream that meters what it relays.
+struct Metered {
+    inner: BodyStream,
+    pending: Option<Pending>,
+}
+
+impl Stream for Metered {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.inner.poll_next_unpin(cx));
+        match item {
+            Some(Ok(ref chunk)) => {
+                if let Some(ref mut pending) = self.pending {
+                    pending.observe(chunk);
+                }
+            }
+            Some(Err(_)) | None => {
+                if let Some(pending) = self.pending.take() {
+                    pending.finish();
+                }
+            }
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl Drop for Metered {
+    fn drop(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            pending.finish();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    EventStream,
+}
+
+/// Reads the token counts an upstream reports from a response body seen in
+/// chunks.
+struct TokenExtractor {
+    config: UsageExtractionConfig,
+    format: Format,
+    /// The JSON body, or the unterminated line of an event stream.
+    buffer: Vec<u8>,
+    /// `data` of the event being received.
+    data: Vec<u8>,
+    /// Input beyond the size limits is skipped: the rest of a JSON body, or
+    /// the rest of an oversized event.
+    skipping: bool,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+}
+
+impl TokenExtractor {
+    /// An extractor for JSON and event-stream responses sent without a
+    /// content encoding; other bodies carry no counts it can read.
+    fn for_response(config: &UsageExtractionConfig, headers: &HeaderMap) -> Option<Self> {
+        if headers
+            .get(header::CONTENT_ENCODING)
+            .is_some_and(|v| v != "identity")
+        {
+            return None;
+        }
+        let content_type = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let format = if mime == "text/event-stream" {
+            Format::EventStream
+        } else if mime == "application/json" || mime.ends_with("+json") {
+            Format::Json
+        } else {
+            return None;
+        };
+        Some(Self {
+            config: config.clone(),
+            format,
+            buffer: Vec::new(),
+            data: Vec::new(),
+            skipping: false,
+            input_tokens: None,
+            output_tokens: None,
+        })
+    }
+
+    fn feed(&mut self, chunk: &[u8]) {
+        match self.format {
+            Format::Json => {
+                if self.skipping {
+                    return;
+                }
+                if self.buffer.len() + chunk.len() > MAX_JSON_BODY_BYTES {
+                    self.skipping = true;
+                    self.buffer = Vec::new();
+                } else {
+                    self.buffer.extend_from_slice(chunk);
+                }
+            }
+            Format::EventStream => {
+                let mut rest = chunk;
+                while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+                    let (line, tail) = rest.split_at(pos);
+                    rest = &tail[1..];
+                    if self.buffer.len() + line.len() > MAX_EVENT_BYTES {
+                        self.skipping = true;
+                        self.buffer.clear();
+                        continue;
+                    }
+                    let mut full = std::mem::take(&mut self.buffer);
+                    full.extend_from_slice(line);
+                    self.event_line(&full);
+                }
+                if self.buffer.len() + rest.len() > MAX_EVENT_BYTES {
+                    self.skipping = true;
+                    self.buffer.clear();
+                } else {
+                    self.buffer.extend_from_slice(rest);
+                }
+            }
+        }
+    }
+
+    /// Handle one line of an event stream: `data` lines accumulate, a blank
+    /// line dispatches the event.
+    fn event_line(&mut self, line: &[u8]) {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            if !self.skipping
+                && !self.data.is_empty()
+                && let Ok(value) = serde_json::from_slice::<Value>(&self.data)
+            {
+                self.take_counts(&value);
+            }
+            self.data.clear();
+            self.skipping = false;
+            return;
+        }
+        let Some(value) = line.strip_prefix(b"data:") else {
+            return;
+        };
+        let value = value.strip_prefix(b" ").unwrap_or(value);
+        if self.data.len() + value.len() + 1 > MAX_EVENT_BYTES {
+            self.skipping = true;
+            self.data.clear();
+        }
+        if self.skipping {
+            return;
+        }
+        if !self.data.is_empty() {
+            self.data.push(b'\n');
+        }
+        self.data.extend_from_slice(value);
+    }
+
+    fn take_counts(&mut self, value: &Value) {
+        let lookup = |pointer: Option<&str>| {
+            pointer
+                .and_then(|p| value.pointer(p))
+                .and_then(Value::as_u64)
+        };
+        if let Some(n) = lookup(self.config.input_tokens.as_deref()) {
+            self.input_tokens = Some(n);
+        }
+        if let Some(n) = lookup(self.config.output_tokens.as_deref()) {
+            self.output_tokens = Some(n);
+        }
+    }
+
+    fn finish(mut self) -> (Option<u64>, Option<u64>) {
+        match self.format {
+            Format::Json => {
+                if !self.skipping
+                    && let Ok(value) = serde_json::from_slice::<Value>(&self.buffer)
+                {
+                    self.take_counts(&value);
+                }
+            }
+            Format::EventStream => {
+                // A stream may end without terminating its last event.
+                let line = std::mem::take(&mut self.buffer);
+                if !line.is_empty() {
+                    self.event_line(&line);
+                }
+                self.event_line(b"");
+            }
+        }
+        (self.input_tokens, self.output_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures_util::stream;
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<UsageRecord>>);
+
+    impl UsageSink for Collect {
+        fn record(&self, record: &UsageRecord) {
+            self.0.lock().unwrap().push(record.clone());
+        }
+    }
+
+    impl Collect {
+        fn records(&self) -> Vec<UsageRecord> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    fn config() -> UsageExtractionConfig {
+        UsageExtractionConfig {
+            input_tokens: Some("/usage/input_tokens".into()),
+            output_tokens: Some("/usage/output_tokens".into()),
+        }
+    }
+
+    fn headers(content_type: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+        headers
+    }
+
+    fn extract(content_type: &'static str, chunks: &[&str]) -> (Option<u64>, Option<u64>) {
+        let mut extractor = TokenExtractor::for_response(&config(), &headers(content_type))
+            .expect("content type should be supported");
+        for chunk in chunks {
+            extractor.feed(chunk.as_bytes());
+        }
+        extractor.finish()
+    }
+
+    fn meter(extraction: Option<UsageExtractionConfig>, request_bytes: u64) -> UsageMeter {
+        UsageMeter {
+            tenant_id: Uuid::new_v4(),
+            subject_id: Uuid::new_v4(),
+            upstream_id: Uuid::new_v4(),
+            route_id: Uuid::new_v4(),
+            extraction,
+            degraded: false,
+            started: Instant::now(),
+            request_bytes: Arc::new(AtomicU64::new(request_bytes)),
+        }
+    }
+
+    fn streamed(parts: &[&'static str]) -> Body {
+        Body::Stream(Box::pin(stream::iter(
+            parts
+                .iter()
+                .map(|p| Ok::<_, BoxError>(Bytes::from_static(p.as_bytes())))
+                .collect::<Vec<_>>(),
+        )))
+    }
+
+    fn response(content_type: &'static str, body: Body) -> http::Response<Body> {
+        let mut resp = http::Response::new(body);
+        *resp.headers_mut() = headers(content_type);
+        resp
+    }
+
+    #[test]
+    fn json_counts_are_read_across_chunks() {
+        assert_eq!(
+            extract(
+                "application/json; charset=utf-8",
+                &[
+                    r#"{"id":"x","usage":{"input_"#,
+                    r#"tokens":12,"output_tokens":34}}"#
+                ],
+            ),
+            (Some(12), Some(34))
+        );
+        assert_eq!(extract("application/json", &["not json"]), (None, None));
+    }
+
+    #[test]
+    fn event_stream_keeps_last_reported_counts() {
+        let (input, output) = extract(
+            "text/event-stream",
+            &[
+                "event: message_start\r\ndata: {\"usage\":{\"input_tokens\":7,\"output_tokens\":1}}\r\n\r\n",
+                "data: {\"delta\":\"hi\"}\n\ndata: {\"usage\":",
+                "{\"output_tokens\":9}}\n\ndata: [DONE]\n\n",
+            ],
+        );
+        assert_eq!((input, output), (Some(7), Some(9)));
+
+        // The last event may end with the stream.
+        assert_eq!(
+            extract(
+                "text/event-stream",
+                &["data: {\"usage\":{\"output_tokens\":3}}"]
+            ),
+            (None, Some(3))
+        );
+    }
+
+    #[test]
+    fn oversized_events_are_skipped() {
+        let big = format!("data: \"{}\"\n", "x".repeat(MAX_EVENT_BYTES));
+        let (_, output) = extract(
+            "text/event-stream",
+            &[
+                big.as_str(),
+                "data: {\"usage\":{\"output_tokens\":1}}\n\n",
+                "data: {\"usage\":{\"output_tokens\":2}}\n\n",
+            ],
+        );
+        assert_eq!(output, Some(2));
+    }
+
+    #[test]
+    fn encoded_and_opaque_bodies_are_not_parsed() {
+        let mut gzip = headers("application/json");
+        gzip.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert!(TokenExtractor::for_response(&config(), &gzip).is_none());
+        assert!(
+            TokenExtractor::for_response(&config(), &headers("application/octet-stream")).is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn streamed_response_is_recorded_when_relayed() {
+        let sink = Arc::new(Collect::default());
+        let sinks: UsageSinks = Arc::new([sink.clone() as Arc<dyn UsageSink>]);
+        let resp = meter(Some(config()), 42).attach(
+            response(
+                "application/json",
+                streamed(&[r#"{"usage":{"input_tokens":5,"#, r#""output_tokens":6}}"#]),
+            ),
+            sinks,
+        );
+        assert!(sink.records().is_empty());
+
+        let body = resp.into_body().into_bytes().await.unwrap();
+        let records = sink.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, 200);
+        assert_eq!(records[0].request_bytes, 42);
+        assert_eq!(records[0].response_bytes, body.len() as u64);
+        assert_eq!(records[0].input_tokens, Some(5));
+        assert_eq!(records[0].output_tokens, Some(6));
+        assert!(!records[0].cache_hit);
+    }
+
+    #[test]
+    fn dropped_stream_and_buffered_bodies_are_recorded() {
+        let sink = Arc::new(Collect::default());
+        let sinks: UsageSinks = Arc::new([sink.clone() as Arc<dyn UsageSink>]);
+
+        let resp =
+            meter(None, 0).attach(response("text/plain", streamed(&["a", "b"])), sinks.clone());
+        drop(resp);
+
+        let mut cached = response("text/plain", Body::from("abc"));
+        cached.headers_mut().insert(
+            headers::CACHE_STATUS_HEADER,
+            HeaderValue::from_static("hit"),
+        );
+        let _ = meter(None, 0).attach(cached, sinks);
+
+        let records = sink.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].response_bytes, 0);
+        assert_eq!(records[1].response_bytes, 3);
+        assert!(records[1].cache_hit);
+    }
+
+    #[tokio::test]
+    async fn request_body_is_counted_as_read() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let body = count_request_body(streamed(&["abc", "de"]), &counter);
+        body.into_bytes().await.unwrap();
+        assert_eq!(counter.load(Ordering::Relaxed), 5);
+
+        count_request_body(Body::from("xyz"), &counter);
+        assert_eq!(counter.load(Ordering::Relaxed), 3);
+    }
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use futures_util::future::{self, Either};
+use futures_util::{Sink, SinkExt, StreamExt};
+use http::{HeaderMap, HeaderValue, header};
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, Role};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+/// Handshake headers copied verbatim from the client to the upstream.
+/// `Sec-WebSocket-Extensions` is deliberately absent: the relay re-frames
+/// every message, so extensions such as permessage-deflate cannot be
+/// negotiated end to end.
+const FORWARDED_HANDSHAKE_HEADERS: &[&str] = &[
+    "sec-websocket-key",
+    "sec-websocket-version",
+    "sec-websocket-protocol",
+];
+
+/// Reason sent in the close frame when a connection is closed for inactivity.
+const IDLE_CLOSE_REASON: &str = "idle timeout";
+
+/// Whether the inbound request asks to upgrade to WebSocket.
+pub(crate) fn is_upgrade_request(headers: &HeaderMap) -> bool {
+    let upgrade = headers
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let connection = headers
+        .get(header::CONNECTION)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|token| token.trim().eq_ignore_ascii_case("upgrade"))
+        });
+    upgrade && connection
+}
+
+/// Restore the handshake headers on the outbound request. They are removed
+/// earlier as hop-by-hop or by the passthrough filter, but the upstream needs
+/// them to accept the upgrade.
+pub(crate) fn apply_handshake_headers(inbound: &HeaderMap, outbound: &mut HeaderMap) {
+    outbound.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+    outbound.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+    for name in FORWARDED_HANDSHAKE_HEADERS {
+        if let Some(value) = inbound.get(*name) {
+            outbound.insert(*name, value.clone());
+        }
+    }
+    outbound.remove(header::SEC_WEBSOCKET_EXTENSIONS);
+}
+
+/// The HTTP client only speaks `http`/`https`; the upgrade itself happens on
+/// top of the HTTP/1.1 exchange.
+pub(crate) fn handshake_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{rest}")
+    } else if let Some(rest) = url.strip_prefix("ws://") {
+        format!("http://{rest}")
+    } else {
+        url.to_string()
+    }
+}
+
+/// Relay frames between the client and upstream connections once both
+/// upgrades complete. `guard` is held for the lifetime of the connection.
+pub(crate) fn spawn_relay<G: Send + 'static>(
+    client: OnUpgrade,
+    upstream: reqwest::Response,
+    idle_timeout: Duration,
+    guard: G,
+) {
+    tokio::spawn(async move {
+        let _guard = guard;
+        let client = async move { client.await.map_err(|e| e.to_string()) };
+        let upstream = async move { upstream.upgrade().await.map_err(|e| e.to_string()) };
+        let (client, upstream) = match future::try_join(client, upstream).await {
+            Ok(pair) => pair,
+            Err(e) => {
+                tracing::warn!(error = %e, "websocket upgrade failed");
+                return;
+            }
+        };
+        let client = WebSocketStream::from_raw_socket(TokioIo::new(client), Role::Server, None);
+        let upstream = WebSocketStream::from_raw_socket(upstream, Role::Client, None);
+        let (client, upstream) = future::join(client, upstream).await;
+        relay(client, upstream, idle_timeout).await;
+    });
+}
+
+async fn relay<C, U>(client: WebSocketStream<C>, upstream: WebSocketStream<U>, idle: Duration)
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+    U: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    loop {
+        let next = future::select(client_rx.next(), upstream_rx.next());
+        match tokio::time::timeout(idle, next).await {
+            Err(_) => {
+                let frame = CloseFrame {
+                    code: CloseCode::Away,
+                    reason: IDLE_CLOSE_REASON.into(),
+                };
+                let _ = client_tx.send(Message::Close(Some(frame.clone()))).await;
+                let _ = upstream_tx.send(Message::Close(Some(frame))).await;
+                return;
+            }
+            Ok(Either::Left((msg, _))) => {
+                if !forward(msg, &mut upstream_tx).await {
+                    let _ = client_tx.close().await;
+                    return;
+                }
+            }
+            Ok(Either::Right((msg, _))) => {
+                if !forward(msg, &mut client_tx).await {
+                    let _ = upstream_tx.close().await;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Forward one message to the other side. Returns `false` once either side
+/// has closed or failed, after passing any close frame along.
+///
+/// Ping/pong frames are answered by each connection on its own and are not
+/// relayed, though they still count as activity for the idle timeout.
+async fn forward<S>(msg: Option<Result<Message, WsError>>, tx: &mut S) -> bool
+where
+    S: Sink<Message, Error = WsError> + Unpin,
+{
+    match msg {
+        Some(Ok(Message::Close(frame))) => {
+            let _ = tx.send(Message::Close(frame)).await;
+            false
+        }
+        Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => true,
+        Some(Ok(msg)) => tx.send(msg).await.is_ok(),
+        Some(Err(_)) | None => {
+            let _ = tx.close().await;
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_upgrade_request() {
+        let mut h = HeaderMap::new();
+        h.insert(header::UPGRADE, HeaderValue::from_static("WebSocket"));
+        h.insert(
+            header::CONNECTION,
+            HeaderValue::from_static("keep-alive, Upgrade"),
+        );
+        assert!(is_upgrade_request(&h));
+
+        h.remove(header::CONNECTION);
+        assert!(!is_upgrade_request(&h));
+    }
+
+    #[test]
+    fn handshake_headers_restored_without_extensions() {
+        let mut inbound = HeaderMap::new();
+        inbound.insert("sec-websocket-key", HeaderValue::from_static("abc=="));
+        inbound.insert("sec-websocket-version", HeaderValue::from_static("13"));
+        inbound.insert(
+            "sec-websocket-extensions",
+            HeaderValue::from_static("permessage-deflate"),
+        );
+        let mut outbound = HeaderMap::new();
+        apply_handshake_headers(&inbound, &mut outbound);
+
+        assert_eq!(outbound.get(header::UPGRADE).unwrap(), "websocket");
+        assert_eq!(outbound.get(header::CONNECTION).unwrap(), "upgrade");
+        assert_eq!(outbound.get("sec-websocket-key").unwrap(), "abc==");
+        assert_eq!(outbound.get("sec-websocket-version").unwrap(), "13");
+        assert!(outbound.get("sec-websocket-extensions").is_none());
+    }
+
+    #[test]
+    fn handshake_url_maps_ws_schemes() {
+        assert_eq!(
+            handshake_url("wss://api.example.com/v1"),
+            "https://api.example.com/v1"
+        );
+        assert_eq!(
+            handshake_url("ws://127.0.0.1:80/ws"),
+            "http://127.0.0.1:80/ws"
+        );
+        assert_eq!(handshake_url("http://h/x"), "http://h/x");
+    }
+}
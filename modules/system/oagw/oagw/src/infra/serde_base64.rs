@@ -0,0 +1,17 @@
+//! `#[serde(with = "crate::infra::serde_base64")]` for binary fields that
+//! travel as standard base64 strings in JSON.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(bytes))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    STANDARD
+        .decode(encoded.as_bytes())
+        .map_err(|e| serde::de::Error::custom(format!("invalid base64: {e}")))
+}
@@ -1,11 +1,19 @@
-use crate::domain::credential::{CredentialError, CredentialResolver, SecretValue};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::domain::credential::{CredentialError, CredentialResolver, SecretValue, SecretVersion};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
 
 /// In-memory credential resolver for development and testing.
+///
+/// Credentials are global: every tenant resolves the same values.
 #[domain_model]
 pub struct InMemoryCredentialResolver {
-    store: DashMap<String, String>,
+    /// Value and version of each credential.
+    store: DashMap<String, (String, u64)>,
+    /// Version given to the next credential set.
+    next_version: AtomicU64,
 }
 
 impl InMemoryCredentialResolver {
@@ -13,6 +21,7 @@ impl InMemoryCredentialResolver {
     pub fn new() -> Self {
         Self {
             store: DashMap::new(),
+            next_version: AtomicU64::new(0),
         }
     }
 
@@ -22,14 +31,15 @@ impl InMemoryCredentialResolver {
     pub fn with_credentials(creds: Vec<(String, String)>) -> Self {
         let resolver = Self::new();
         for (key, value) in creds {
-            resolver.store.insert(key, value);
+            resolver.set(key, value);
         }
         resolver
     }
 
     /// Add or update a credential.
     pub fn set(&self, secret_ref: String, value: String) {
-        self.store.insert(secret_ref, value);
+        let version = self.next_version.fetch_add(1, Ordering::Relaxed);
+        self.store.insert(secret_ref, (value, version));
     }
 }
 
@@ -41,10 +51,25 @@ impl Default for InMemoryCredentialResolver {
 
 #[async_trait::async_trait]
 impl CredentialResolver for InMemoryCredentialResolver {
-    async fn resolve(&self, secret_ref: &str) -> Result<SecretValue, CredentialError> {
+    async fn resolve(
+        &self,
+        _ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretValue, CredentialError> {
+        self.store
+            .get(secret_ref)
+            .map(|v| SecretValue::new(v.0.clone()))
+            .ok_or_else(|| CredentialError::NotFound(secret_ref.to_string()))
+    }
+
+    async fn version(
+        &self,
+        _ctx: &SecurityContext,
+        secret_ref: &str,
+    ) -> Result<SecretVersion, CredentialError> {
         self.store
             .get(secret_ref)
-            .map(|v| SecretValue::new(v.value().clone()))
+            .map(|v| SecretVersion::new(format!("seeded@{}", v.1)))
             .ok_or_else(|| CredentialError::NotFound(secret_ref.to_string()))
     }
 }
@@ -60,14 +85,19 @@ mod tests {
             "sk-abc123".into(),
         )]);
 
-        let secret = resolver.resolve("cred://openai-key").await.unwrap();
+        let secret = resolver
+            .resolve(&SecurityContext::anonymous(), "cred://openai-key")
+            .await
+            .unwrap();
         assert_eq!(secret.as_str(), "sk-abc123");
     }
 
     #[tokio::test]
     async fn resolve_missing_key_returns_not_found() {
         let resolver = InMemoryCredentialResolver::new();
-        let result = resolver.resolve("cred://nonexistent").await;
+        let result = resolver
+            .resolve(&SecurityContext::anonymous(), "cred://nonexistent")
+            .await;
         assert!(matches!(result, Err(CredentialError::NotFound(_))));
     }
 
@@ -75,7 +105,10 @@ mod tests {
     async fn set_and_resolve() {
         let resolver = InMemoryCredentialResolver::new();
         resolver.set("cred://key".into(), "secret-value".into());
-        let secret = resolver.resolve("cred://key").await.unwrap();
+        let secret = resolver
+            .resolve(&SecurityContext::anonymous(), "cred://key")
+            .await
+            .unwrap();
         assert_eq!(secret.as_str(), "secret-value");
     }
 
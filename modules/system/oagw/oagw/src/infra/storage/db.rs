@@ -0,0 +1,37 @@
+//! Database error conversion helpers.
+
+use std::fmt::Display;
+
+use modkit_db::DbError;
+use modkit_db::secure::ScopeError;
+use sea_orm::SqlErr;
+
+use crate::domain::repo::RepositoryError;
+
+/// Convert any displayable error into a `RepositoryError::Internal`.
+pub(super) fn db_err(e: impl Display) -> RepositoryError {
+    RepositoryError::Internal(format!("database error: {e}"))
+}
+
+/// Whether a scoped query failed because of a unique constraint violation.
+pub(super) fn is_unique_violation(e: &ScopeError) -> bool {
+    matches!(
+        e,
+        ScopeError::Db(db) if matches!(db.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+    )
+}
+
+/// Fail a transaction closure with a repository error, rolling it back.
+/// [`tx_err`] recovers the error once the transaction has ended.
+pub(super) fn abort(e: RepositoryError) -> DbError {
+    DbError::Other(anyhow::Error::new(e))
+}
+
+/// Convert the error of a transaction into a `RepositoryError`, keeping
+/// errors raised with [`abort`] as they were.
+pub(super) fn tx_err(e: DbError) -> RepositoryError {
+    match e {
+        DbError::Other(e) => e.downcast().unwrap_or_else(db_err),
+        e => db_err(e),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod plugin;
+pub mod plugin_ref;
+pub mod route;
+pub mod upstream;
@@ -0,0 +1,23 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// A custom plugin referenced by an upstream (`route_id` unset) or a route.
+/// `referrer_id` is the id of whichever holds the reference.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_plugin_ref")]
+#[secure(tenant_col = "tenant_id", no_resource, no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub plugin_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub referrer_id: Uuid,
+    pub tenant_id: Uuid,
+    pub upstream_id: Uuid,
+    pub route_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
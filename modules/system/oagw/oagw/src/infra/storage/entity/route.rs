@@ -0,0 +1,45 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_route")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub upstream_id: Uuid,
+    #[sea_orm(column_name = "match")]
+    pub match_rules: Json,
+    pub plugins: Option<Json>,
+    pub rate_limit: Option<Json>,
+    pub grpc_transcoding: Option<Json>,
+    pub response_cache: Option<Json>,
+    pub usage_extraction: Option<Json>,
+    pub tags: Json,
+    pub priority: i32,
+    pub enabled: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::upstream::Entity",
+        from = "Column::UpstreamId",
+        to = "super::upstream::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Upstream,
+}
+
+impl Related<super::upstream::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Upstream.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
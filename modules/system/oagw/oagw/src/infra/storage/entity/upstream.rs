@@ -0,0 +1,40 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_upstream")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub alias: String,
+    pub server: Json,
+    pub protocol: String,
+    pub auth: Option<Json>,
+    pub headers: Option<Json>,
+    pub plugins: Option<Json>,
+    pub rate_limit: Option<Json>,
+    pub circuit_breaker: Option<Json>,
+    pub load_balancing: Option<Json>,
+    pub tags: Json,
+    pub enabled: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::route::Entity")]
+    Route,
+}
+
+impl Related<super::route::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Route.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,1518 @@
+//! Conversions between `SeaORM` models and domain types.
+//!
+//! Structured columns (`server`, `auth`, `headers`, `plugins`, `rate_limit`,
+//! `circuit_breaker`, `load_balancing`, `match`, `grpc_transcoding`,
+//! `response_cache`, `usage_extraction`, `tags`, `phases`, `config_schema`)
+//! are stored as JSON. The serde types below describe that stored shape; they
+//! are kept separate from both the REST DTOs and the GTS provisioning payloads
+//! so the persisted format only changes deliberately.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::gts_helpers::custom_plugin_ids;
+use crate::domain::model as domain;
+use crate::domain::repo::RepositoryError;
+
+use super::entity::{plugin, route, upstream};
+
+// ---------------------------------------------------------------------------
+// Stored JSON shapes
+// ---------------------------------------------------------------------------
+
+fn default_cost() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum Scheme {
+    Http,
+    #[default]
+    Https,
+    Wss,
+    Wt,
+    Grpc,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Endpoint {
+    #[serde(default)]
+    scheme: Scheme,
+    host: String,
+    port: u16,
+    #[serde(default = "default_weight")]
+    weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize)]
+struct Server {
+    endpoints: Vec<Endpoint>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum SharingMode {
+    #[default]
+    Private,
+    Inherit,
+    Enforce,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AuthConfig {
+    #[serde(rename = "type")]
+    plugin_type: String,
+    #[serde(default)]
+    sharing: SharingMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    config: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum PassthroughMode {
+    #[default]
+    None,
+    Allowlist,
+    All,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct RequestHeaderRules {
+    #[serde(default)]
+    set: HashMap<String, String>,
+    #[serde(default)]
+    add: HashMap<String, String>,
+    #[serde(default)]
+    remove: Vec<String>,
+    #[serde(default)]
+    passthrough: PassthroughMode,
+    #[serde(default)]
+    passthrough_allowlist: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ResponseHeaderRules {
+    #[serde(default)]
+    set: HashMap<String, String>,
+    #[serde(default)]
+    add: HashMap<String, String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct HeadersConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<RequestHeaderRules>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response: Option<ResponseHeaderRules>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct PluginsConfig {
+    #[serde(default)]
+    sharing: SharingMode,
+    #[serde(default)]
+    items: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    config: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum RateLimitAlgorithm {
+    #[default]
+    TokenBucket,
+    SlidingWindow,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum Window {
+    #[default]
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SustainedRate {
+    rate: u32,
+    #[serde(default)]
+    window: Window,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BurstConfig {
+    capacity: u32,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum RateLimitScope {
+    Global,
+    #[default]
+    Tenant,
+    User,
+    Ip,
+    Route,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum RateLimitStrategy {
+    #[default]
+    Reject,
+    Queue,
+    Degrade,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RateLimitConfig {
+    #[serde(default)]
+    sharing: SharingMode,
+    #[serde(default)]
+    algorithm: RateLimitAlgorithm,
+    sustained: SustainedRate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    burst: Option<BurstConfig>,
+    #[serde(default)]
+    scope: RateLimitScope,
+    #[serde(default)]
+    strategy: RateLimitStrategy,
+    #[serde(default = "default_cost")]
+    cost: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    queue: Option<QueueConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    degrade: Option<DegradeConfig>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct QueueConfig {
+    max_depth: u32,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    timeout: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DegradeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fallback_upstream_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fallback_response: Option<FallbackResponse>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FallbackResponse {
+    status: u16,
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CircuitBreakerConfig {
+    enabled: bool,
+    failure_threshold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure_rate_threshold: Option<u8>,
+    minimum_requests: u32,
+    window_seconds: u32,
+    success_threshold: u32,
+    timeout_seconds: u32,
+    half_open_max_requests: u32,
+    failure_conditions: FailureConditions,
+    #[serde(default)]
+    scope: CircuitBreakerScope,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FailureConditions {
+    status_codes: Vec<u16>,
+    timeout: bool,
+    connection_error: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum CircuitBreakerScope {
+    Global,
+    #[default]
+    PerEndpoint,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LoadBalancingConfig {
+    #[serde(default)]
+    strategy: LoadBalancingStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    outlier_detection: Option<OutlierDetectionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health_check: Option<HealthCheckConfig>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    Weighted,
+    LeastInFlight,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OutlierDetectionConfig {
+    consecutive_failures: u32,
+    base_ejection_seconds: u32,
+    max_ejection_percent: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HealthCheckConfig {
+    path: String,
+    interval_seconds: u32,
+    timeout_seconds: u32,
+    healthy_threshold: u32,
+    unhealthy_threshold: u32,
+    expected_statuses: Vec<u16>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum PathSuffixMode {
+    Disabled,
+    #[default]
+    Append,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HttpMatch {
+    methods: Vec<HttpMethod>,
+    path: String,
+    #[serde(default)]
+    query_allowlist: Vec<String>,
+    #[serde(default)]
+    path_suffix_mode: PathSuffixMode,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GrpcMatch {
+    service: String,
+    method: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MatchRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http: Option<HttpMatch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grpc: Option<GrpcMatch>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GrpcTranscodingConfig {
+    #[serde(with = "crate::infra::serde_base64")]
+    descriptor_set: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResponseCacheConfig {
+    #[serde(with = "modkit_utils::humantime_serde")]
+    ttl: Duration,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    vary: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UsageExtractionConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input_tokens: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_tokens: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PluginType {
+    Auth,
+    Guard,
+    Transform,
+}
+
+// Variants mirror the hook names (`on_request`, `on_response`, `on_error`).
+#[allow(clippy::enum_variant_names)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PluginPhase {
+    OnRequest,
+    OnResponse,
+    OnError,
+}
+
+// ---------------------------------------------------------------------------
+// Stored shape <-> domain
+// ---------------------------------------------------------------------------
+
+impl From<Scheme> for domain::Scheme {
+    fn from(v: Scheme) -> Self {
+        match v {
+            Scheme::Http => Self::Http,
+            Scheme::Https => Self::Https,
+            Scheme::Wss => Self::Wss,
+            Scheme::Wt => Self::Wt,
+            Scheme::Grpc => Self::Grpc,
+        }
+    }
+}
+
+impl From<domain::Scheme> for Scheme {
+    fn from(v: domain::Scheme) -> Self {
+        match v {
+            domain::Scheme::Http => Self::Http,
+            domain::Scheme::Https => Self::Https,
+            domain::Scheme::Wss => Self::Wss,
+            domain::Scheme::Wt => Self::Wt,
+            domain::Scheme::Grpc => Self::Grpc,
+        }
+    }
+}
+
+impl From<Endpoint> for domain::Endpoint {
+    fn from(v: Endpoint) -> Self {
+        Self {
+            scheme: v.scheme.into(),
+            host: v.host,
+            port: v.port,
+            weight: v.weight,
+        }
+    }
+}
+
+impl From<domain::Endpoint> for Endpoint {
+    fn from(v: domain::Endpoint) -> Self {
+        Self {
+            scheme: v.scheme.into(),
+            host: v.host,
+            port: v.port,
+            weight: v.weight,
+        }
+    }
+}
+
+impl From<Server> for domain::Server {
+    fn from(v: Server) -> Self {
+        Self {
+            endpoints: v.endpoints.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<domain::Server> for Server {
+    fn from(v: domain::Server) -> Self {
+        Self {
+            endpoints: v.endpoints.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SharingMode> for domain::SharingMode {
+    fn from(v: SharingMode) -> Self {
+        match v {
+            SharingMode::Private => Self::Private,
+            SharingMode::Inherit => Self::Inherit,
+            SharingMode::Enforce => Self::Enforce,
+        }
+    }
+}
+
+impl From<domain::SharingMode> for SharingMode {
+    fn from(v: domain::SharingMode) -> Self {
+        match v {
+            domain::SharingMode::Private => Self::Private,
+            domain::SharingMode::Inherit => Self::Inherit,
+            domain::SharingMode::Enforce => Self::Enforce,
+        }
+    }
+}
+
+impl From<AuthConfig> for domain::AuthConfig {
+    fn from(v: AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type,
+            sharing: v.sharing.into(),
+            config: v.config,
+        }
+    }
+}
+
+impl From<domain::AuthConfig> for AuthConfig {
+    fn from(v: domain::AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type,
+            sharing: v.sharing.into(),
+            config: v.config,
+        }
+    }
+}
+
+impl From<PassthroughMode> for domain::PassthroughMode {
+    fn from(v: PassthroughMode) -> Self {
+        match v {
+            PassthroughMode::None => Self::None,
+            PassthroughMode::Allowlist => Self::Allowlist,
+            PassthroughMode::All => Self::All,
+        }
+    }
+}
+
+impl From<domain::PassthroughMode> for PassthroughMode {
+    fn from(v: domain::PassthroughMode) -> Self {
+        match v {
+            domain::PassthroughMode::None => Self::None,
+            domain::PassthroughMode::Allowlist => Self::Allowlist,
+            domain::PassthroughMode::All => Self::All,
+        }
+    }
+}
+
+impl From<RequestHeaderRules> for domain::RequestHeaderRules {
+    fn from(v: RequestHeaderRules) -> Self {
+        Self {
+            set: v.set,
+            add: v.add,
+            remove: v.remove,
+            passthrough: v.passthrough.into(),
+            passthrough_allowlist: v.passthrough_allowlist,
+        }
+    }
+}
+
+impl From<domain::RequestHeaderRules> for RequestHeaderRules {
+    fn from(v: domain::RequestHeaderRules) -> Self {
+        Self {
+            set: v.set,
+            add: v.add,
+            remove: v.remove,
+            passthrough: v.passthrough.into(),
+            passthrough_allowlist: v.passthrough_allowlist,
+        }
+    }
+}
+
+impl From<ResponseHeaderRules> for domain::ResponseHeaderRules {
+    fn from(v: ResponseHeaderRules) -> Self {
+        Self {
+            set: v.set,
+            add: v.add,
+            remove: v.remove,
+        }
+    }
+}
+
+impl From<domain::ResponseHeaderRules> for ResponseHeaderRules {
+    fn from(v: domain::ResponseHeaderRules) -> Self {
+        Self {
+            set: v.set,
+            add: v.add,
+            remove: v.remove,
+        }
+    }
+}
+
+impl From<HeadersConfig> for domain::HeadersConfig {
+    fn from(v: HeadersConfig) -> Self {
+        Self {
+            request: v.request.map(Into::into),
+            response: v.response.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::HeadersConfig> for HeadersConfig {
+    fn from(v: domain::HeadersConfig) -> Self {
+        Self {
+            request: v.request.map(Into::into),
+            response: v.response.map(Into::into),
+        }
+    }
+}
+
+impl From<PluginsConfig> for domain::PluginsConfig {
+    fn from(v: PluginsConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            items: v.items,
+            config: v.config,
+        }
+    }
+}
+
+impl From<domain::PluginsConfig> for PluginsConfig {
+    fn from(v: domain::PluginsConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            items: v.items,
+            config: v.config,
+        }
+    }
+}
+
+impl From<RateLimitAlgorithm> for domain::RateLimitAlgorithm {
+    fn from(v: RateLimitAlgorithm) -> Self {
+        match v {
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket,
+            RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow,
+        }
+    }
+}
+
+impl From<domain::RateLimitAlgorithm> for RateLimitAlgorithm {
+    fn from(v: domain::RateLimitAlgorithm) -> Self {
+        match v {
+            domain::RateLimitAlgorithm::TokenBucket => Self::TokenBucket,
+            domain::RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow,
+        }
+    }
+}
+
+impl From<Window> for domain::Window {
+    fn from(v: Window) -> Self {
+        match v {
+            Window::Second => Self::Second,
+            Window::Minute => Self::Minute,
+            Window::Hour => Self::Hour,
+            Window::Day => Self::Day,
+        }
+    }
+}
+
+impl From<domain::Window> for Window {
+    fn from(v: domain::Window) -> Self {
+        match v {
+            domain::Window::Second => Self::Second,
+            domain::Window::Minute => Self::Minute,
+            domain::Window::Hour => Self::Hour,
+            domain::Window::Day => Self::Day,
+        }
+    }
+}
+
+impl From<SustainedRate> for domain::SustainedRate {
+    fn from(v: SustainedRate) -> Self {
+        Self {
+            rate: v.rate,
+            window: v.window.into(),
+        }
+    }
+}
+
+impl From<domain::SustainedRate> for SustainedRate {
+    fn from(v: domain::SustainedRate) -> Self {
+        Self {
+            rate: v.rate,
+            window: v.window.into(),
+        }
+    }
+}
+
+impl From<BurstConfig> for domain::BurstConfig {
+    fn from(v: BurstConfig) -> Self {
+        Self {
+            capacity: v.capacity,
+        }
+    }
+}
+
+impl From<domain::BurstConfig> for BurstConfig {
+    fn from(v: domain::BurstConfig) -> Self {
+        Self {
+            capacity: v.capacity,
+        }
+    }
+}
+
+impl From<RateLimitScope> for domain::RateLimitScope {
+    fn from(v: RateLimitScope) -> Self {
+        match v {
+            RateLimitScope::Global => Self::Global,
+            RateLimitScope::Tenant => Self::Tenant,
+            RateLimitScope::User => Self::User,
+            RateLimitScope::Ip => Self::Ip,
+            RateLimitScope::Route => Self::Route,
+        }
+    }
+}
+
+impl From<domain::RateLimitScope> for RateLimitScope {
+    fn from(v: domain::RateLimitScope) -> Self {
+        match v {
+            domain::RateLimitScope::Global => Self::Global,
+            domain::RateLimitScope::Tenant => Self::Tenant,
+            domain::RateLimitScope::User => Self::User,
+            domain::RateLimitScope::Ip => Self::Ip,
+            domain::RateLimitScope::Route => Self::Route,
+        }
+    }
+}
+
+impl From<RateLimitStrategy> for domain::RateLimitStrategy {
+    fn from(v: RateLimitStrategy) -> Self {
+        match v {
+            RateLimitStrategy::Reject => Self::Reject,
+            RateLimitStrategy::Queue => Self::Queue,
+            RateLimitStrategy::Degrade => Self::Degrade,
+        }
+    }
+}
+
+impl From<domain::RateLimitStrategy> for RateLimitStrategy {
+    fn from(v: domain::RateLimitStrategy) -> Self {
+        match v {
+            domain::RateLimitStrategy::Reject => Self::Reject,
+            domain::RateLimitStrategy::Queue => Self::Queue,
+            domain::RateLimitStrategy::Degrade => Self::Degrade,
+        }
+    }
+}
+
+impl From<QueueConfig> for domain::QueueConfig {
+    fn from(v: QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<domain::QueueConfig> for QueueConfig {
+    fn from(v: domain::QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            timeout: v.timeout,
+        }
+    }
+}
+
+impl From<DegradeConfig> for domain::DegradeConfig {
+    fn from(v: DegradeConfig) -> Self {
+        Self {
+            fallback_upstream_id: v.fallback_upstream_id,
+            fallback_response: v.fallback_response.map(|r| domain::FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }
+    }
+}
+
+impl From<domain::DegradeConfig> for DegradeConfig {
+    fn from(v: domain::DegradeConfig) -> Self {
+        Self {
+            fallback_upstream_id: v.fallback_upstream_id,
+            fallback_response: v.fallback_response.map(|r| FallbackResponse {
+                status: r.status,
+                body: r.body,
+            }),
+        }
+    }
+}
+
+impl From<RateLimitConfig> for domain::RateLimitConfig {
+    fn from(v: RateLimitConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            algorithm: v.algorithm.into(),
+            sustained: v.sustained.into(),
+            burst: v.burst.map(Into::into),
+            scope: v.scope.into(),
+            strategy: v.strategy.into(),
+            cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::RateLimitConfig> for RateLimitConfig {
+    fn from(v: domain::RateLimitConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            algorithm: v.algorithm.into(),
+            sustained: v.sustained.into(),
+            burst: v.burst.map(Into::into),
+            scope: v.scope.into(),
+            strategy: v.strategy.into(),
+            cost: v.cost,
+            queue: v.queue.map(Into::into),
+            degrade: v.degrade.map(Into::into),
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate_threshold: v.failure_rate_threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate_threshold: v.failure_rate_threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                domain::CircuitBreakerScope::Global => CircuitBreakerScope::Global,
+                domain::CircuitBreakerScope::PerEndpoint => CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: match v.strategy {
+                LoadBalancingStrategy::RoundRobin => domain::LoadBalancingStrategy::RoundRobin,
+                LoadBalancingStrategy::Weighted => domain::LoadBalancingStrategy::Weighted,
+                LoadBalancingStrategy::LeastInFlight => {
+                    domain::LoadBalancingStrategy::LeastInFlight
+                }
+            },
+            outlier_detection: v.outlier_detection.map(|o| domain::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_seconds: o.base_ejection_seconds,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+            health_check: v.health_check.map(|h| domain::HealthCheckConfig {
+                path: h.path,
+                interval_seconds: h.interval_seconds,
+                timeout_seconds: h.timeout_seconds,
+                healthy_threshold: h.healthy_threshold,
+                unhealthy_threshold: h.unhealthy_threshold,
+                expected_statuses: h.expected_statuses,
+            }),
+        }
+    }
+}
+
+impl From<domain::LoadBalancingConfig> for LoadBalancingConfig {
+    fn from(v: domain::LoadBalancingConfig) -> Self {
+        Self {
+            strategy: match v.strategy {
+                domain::LoadBalancingStrategy::RoundRobin => LoadBalancingStrategy::RoundRobin,
+                domain::LoadBalancingStrategy::Weighted => LoadBalancingStrategy::Weighted,
+                domain::LoadBalancingStrategy::LeastInFlight => {
+                    LoadBalancingStrategy::LeastInFlight
+                }
+            },
+            outlier_detection: v.outlier_detection.map(|o| OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_seconds: o.base_ejection_seconds,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+            health_check: v.health_check.map(|h| HealthCheckConfig {
+                path: h.path,
+                interval_seconds: h.interval_seconds,
+                timeout_seconds: h.timeout_seconds,
+                healthy_threshold: h.healthy_threshold,
+                unhealthy_threshold: h.unhealthy_threshold,
+                expected_statuses: h.expected_statuses,
+            }),
+        }
+    }
+}
+
+impl From<HttpMethod> for domain::HttpMethod {
+    fn from(v: HttpMethod) -> Self {
+        match v {
+            HttpMethod::Get => Self::Get,
+            HttpMethod::Post => Self::Post,
+            HttpMethod::Put => Self::Put,
+            HttpMethod::Delete => Self::Delete,
+            HttpMethod::Patch => Self::Patch,
+        }
+    }
+}
+
+impl From<domain::HttpMethod> for HttpMethod {
+    fn from(v: domain::HttpMethod) -> Self {
+        match v {
+            domain::HttpMethod::Get => Self::Get,
+            domain::HttpMethod::Post => Self::Post,
+            domain::HttpMethod::Put => Self::Put,
+            domain::HttpMethod::Delete => Self::Delete,
+            domain::HttpMethod::Patch => Self::Patch,
+        }
+    }
+}
+
+impl From<PathSuffixMode> for domain::PathSuffixMode {
+    fn from(v: PathSuffixMode) -> Self {
+        match v {
+            PathSuffixMode::Disabled => Self::Disabled,
+            PathSuffixMode::Append => Self::Append,
+        }
+    }
+}
+
+impl From<domain::PathSuffixMode> for PathSuffixMode {
+    fn from(v: domain::PathSuffixMode) -> Self {
+        match v {
+            domain::PathSuffixMode::Disabled => Self::Disabled,
+            domain::PathSuffixMode::Append => Self::Append,
+        }
+    }
+}
+
+impl From<HttpMatch> for domain::HttpMatch {
+    fn from(v: HttpMatch) -> Self {
+        Self {
+            methods: v.methods.into_iter().map(Into::into).collect(),
+            path: v.path,
+            query_allowlist: v.query_allowlist,
+            path_suffix_mode: v.path_suffix_mode.into(),
+        }
+    }
+}
+
+impl From<domain::HttpMatch> for HttpMatch {
+    fn from(v: domain::HttpMatch) -> Self {
+        Self {
+            methods: v.methods.into_iter().map(Into::into).collect(),
+            path: v.path,
+            query_allowlist: v.query_allowlist,
+            path_suffix_mode: v.path_suffix_mode.into(),
+        }
+    }
+}
+
+impl From<GrpcMatch> for domain::GrpcMatch {
+    fn from(v: GrpcMatch) -> Self {
+        Self {
+            service: v.service,
+            method: v.method,
+        }
+    }
+}
+
+impl From<domain::GrpcMatch> for GrpcMatch {
+    fn from(v: domain::GrpcMatch) -> Self {
+        Self {
+            service: v.service,
+            method: v.method,
+        }
+    }
+}
+
+impl From<MatchRules> for domain::MatchRules {
+    fn from(v: MatchRules) -> Self {
+        Self {
+            http: v.http.map(Into::into),
+            grpc: v.grpc.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::MatchRules> for MatchRules {
+    fn from(v: domain::MatchRules) -> Self {
+        Self {
+            http: v.http.map(Into::into),
+            grpc: v.grpc.map(Into::into),
+        }
+    }
+}
+
+impl From<GrpcTranscodingConfig> for domain::GrpcTranscodingConfig {
+    fn from(v: GrpcTranscodingConfig) -> Self {
+        Self {
+            descriptor_set: v.descriptor_set,
+        }
+    }
+}
+
+impl From<domain::GrpcTranscodingConfig> for GrpcTranscodingConfig {
+    fn from(v: domain::GrpcTranscodingConfig) -> Self {
+        Self {
+            descriptor_set: v.descriptor_set,
+        }
+    }
+}
+
+impl From<ResponseCacheConfig> for domain::ResponseCacheConfig {
+    fn from(v: ResponseCacheConfig) -> Self {
+        Self {
+            ttl: v.ttl,
+            vary: v.vary,
+        }
+    }
+}
+
+impl From<domain::ResponseCacheConfig> for ResponseCacheConfig {
+    fn from(v: domain::ResponseCacheConfig) -> Self {
+        Self {
+            ttl: v.ttl,
+            vary: v.vary,
+        }
+    }
+}
+
+impl From<UsageExtractionConfig> for domain::UsageExtractionConfig {
+    fn from(v: UsageExtractionConfig) -> Self {
+        Self {
+            input_tokens: v.input_tokens,
+            output_tokens: v.output_tokens,
+        }
+    }
+}
+
+impl From<domain::UsageExtractionConfig> for UsageExtractionConfig {
+    fn from(v: domain::UsageExtractionConfig) -> Self {
+        Self {
+            input_tokens: v.input_tokens,
+            output_tokens: v.output_tokens,
+        }
+    }
+}
+
+impl From<PluginType> for domain::PluginType {
+    fn from(v: PluginType) -> Self {
+        match v {
+            PluginType::Auth => Self::Auth,
+            PluginType::Guard => Self::Guard,
+            PluginType::Transform => Self::Transform,
+        }
+    }
+}
+
+impl From<PluginPhase> for domain::PluginPhase {
+    fn from(v: PluginPhase) -> Self {
+        match v {
+            PluginPhase::OnRequest => Self::OnRequest,
+            PluginPhase::OnResponse => Self::OnResponse,
+            PluginPhase::OnError => Self::OnError,
+        }
+    }
+}
+
+impl From<domain::PluginPhase> for PluginPhase {
+    fn from(v: domain::PluginPhase) -> Self {
+        match v {
+            domain::PluginPhase::OnRequest => Self::OnRequest,
+            domain::PluginPhase::OnResponse => Self::OnResponse,
+            domain::PluginPhase::OnError => Self::OnError,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSON column helpers
+// ---------------------------------------------------------------------------
+
+fn to_json<S: Serialize>(column: &str, value: S) -> Result<serde_json::Value, RepositoryError> {
+    serde_json::to_value(value)
+        .map_err(|e| RepositoryError::Internal(format!("failed to encode '{column}': {e}")))
+}
+
+fn to_json_opt<S: Serialize>(
+    column: &str,
+    value: Option<S>,
+) -> Result<Option<serde_json::Value>, RepositoryError> {
+    value.map(|v| to_json(column, v)).transpose()
+}
+
+fn from_json<D: DeserializeOwned>(
+    column: &str,
+    value: serde_json::Value,
+) -> Result<D, RepositoryError> {
+    serde_json::from_value(value)
+        .map_err(|e| RepositoryError::Internal(format!("failed to decode '{column}': {e}")))
+}
+
+fn from_json_opt<D: DeserializeOwned>(
+    column: &str,
+    value: Option<serde_json::Value>,
+) -> Result<Option<D>, RepositoryError> {
+    value.map(|v| from_json(column, v)).transpose()
+}
+
+// ---------------------------------------------------------------------------
+// Upstream
+// ---------------------------------------------------------------------------
+
+/// Build a fully-populated active model for an upstream row.
+///
+/// `created_at` is only set when `created_at` is `Some`, so updates keep the
+/// original creation timestamp.
+pub(super) fn upstream_to_active_model(
+    u: domain::Upstream,
+    created_at: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+) -> Result<upstream::ActiveModel, RepositoryError> {
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    Ok(upstream::ActiveModel {
+        id: Set(u.id),
+        tenant_id: Set(u.tenant_id),
+        alias: Set(u.alias),
+        server: Set(to_json("server", Server::from(u.server))?),
+        protocol: Set(u.protocol),
+        auth: Set(to_json_opt("auth", u.auth.map(AuthConfig::from))?),
+        headers: Set(to_json_opt("headers", u.headers.map(HeadersConfig::from))?),
+        plugins: Set(to_json_opt("plugins", u.plugins.map(PluginsConfig::from))?),
+        rate_limit: Set(to_json_opt(
+            "rate_limit",
+            u.rate_limit.map(RateLimitConfig::from),
+        )?),
+        circuit_breaker: Set(to_json_opt(
+            "circuit_breaker",
+            u.circuit_breaker.map(CircuitBreakerConfig::from),
+        )?),
+        load_balancing: Set(to_json_opt(
+            "load_balancing",
+            u.load_balancing.map(LoadBalancingConfig::from),
+        )?),
+        tags: Set(to_json("tags", u.tags)?),
+        enabled: Set(u.enabled),
+        created_at: created_at.map_or(NotSet, Set),
+        updated_at: Set(now),
+    })
+}
+
+/// Decode an upstream row into the domain type.
+pub(super) fn upstream_from_model(m: upstream::Model) -> Result<domain::Upstream, RepositoryError> {
+    Ok(domain::Upstream {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        alias: m.alias,
+        server: from_json::<Server>("server", m.server)?.into(),
+        protocol: m.protocol,
+        enabled: m.enabled,
+        auth: from_json_opt::<AuthConfig>("auth", m.auth)?.map(Into::into),
+        headers: from_json_opt::<HeadersConfig>("headers", m.headers)?.map(Into::into),
+        plugins: from_json_opt::<PluginsConfig>("plugins", m.plugins)?.map(Into::into),
+        rate_limit: from_json_opt::<RateLimitConfig>("rate_limit", m.rate_limit)?.map(Into::into),
+        circuit_breaker: from_json_opt::<CircuitBreakerConfig>(
+            "circuit_breaker",
+            m.circuit_breaker,
+        )?
+        .map(Into::into),
+        load_balancing: from_json_opt::<LoadBalancingConfig>("load_balancing", m.load_balancing)?
+            .map(Into::into),
+        tags: from_json("tags", m.tags)?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Route
+// ---------------------------------------------------------------------------
+
+/// Build a fully-populated active model for a route row.
+///
+/// `created_at` is only set when `created_at` is `Some`, so updates keep the
+/// original creation timestamp.
+pub(super) fn route_to_active_model(
+    r: domain::Route,
+    created_at: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+) -> Result<route::ActiveModel, RepositoryError> {
+    use sea_orm::ActiveValue::{NotSet, Set};
+
+    Ok(route::ActiveModel {
+        id: Set(r.id),
+        tenant_id: Set(r.tenant_id),
+        upstream_id: Set(r.upstream_id),
+        match_rules: Set(to_json("match", MatchRules::from(r.match_rules))?),
+        plugins: Set(to_json_opt("plugins", r.plugins.map(PluginsConfig::from))?),
+        rate_limit: Set(to_json_opt(
+            "rate_limit",
+            r.rate_limit.map(RateLimitConfig::from),
+        )?),
+        grpc_transcoding: Set(to_json_opt(
+            "grpc_transcoding",
+            r.grpc_transcoding.map(GrpcTranscodingConfig::from),
+        )?),
+        response_cache: Set(to_json_opt(
+            "response_cache",
+            r.response_cache.map(ResponseCacheConfig::from),
+        )?),
+        usage_extraction: Set(to_json_opt(
+            "usage_extraction",
+            r.usage_extraction.map(UsageExtractionConfig::from),
+        )?),
+        tags: Set(to_json("tags", r.tags)?),
+        priority: Set(r.priority),
+        enabled: Set(r.enabled),
+        created_at: created_at.map_or(NotSet, Set),
+        updated_at: Set(now),
+    })
+}
+
+/// Decode a route row into the domain type.
+pub(super) fn route_from_model(m: route::Model) -> Result<domain::Route, RepositoryError> {
+    Ok(domain::Route {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        upstream_id: m.upstream_id,
+        match_rules: from_json::<MatchRules>("match", m.match_rules)?.into(),
+        plugins: from_json_opt::<PluginsConfig>("plugins", m.plugins)?.map(Into::into),
+        rate_limit: from_json_opt::<RateLimitConfig>("rate_limit", m.rate_limit)?.map(Into::into),
+        grpc_transcoding: from_json_opt::<GrpcTranscodingConfig>(
+            "grpc_transcoding",
+            m.grpc_transcoding,
+        )?
+        .map(Into::into),
+        response_cache: from_json_opt::<ResponseCacheConfig>("response_cache", m.response_cache)?
+            .map(Into::into),
+        usage_extraction: from_json_opt::<UsageExtractionConfig>(
+            "usage_extraction",
+            m.usage_extraction,
+        )?
+        .map(Into::into),
+        tags: from_json("tags", m.tags)?,
+        priority: m.priority,
+        enabled: m.enabled,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Plugin
+// ---------------------------------------------------------------------------
+
+/// Build the active model for a new custom plugin row.
+pub(super) fn plugin_to_active_model(
+    p: domain::CustomPlugin,
+    now: OffsetDateTime,
+) -> Result<plugin::ActiveModel, RepositoryError> {
+    use sea_orm::ActiveValue::Set;
+
+    let phases: Vec<PluginPhase> = p.phases.into_iter().map(Into::into).collect();
+    Ok(plugin::ActiveModel {
+        id: Set(p.id),
+        tenant_id: Set(p.tenant_id),
+        name: Set(p.name),
+        description: Set(p.description),
+        plugin_type: Set(p.plugin_type.as_str().to_string()),
+        phases: Set(to_json("phases", phases)?),
+        config_schema: Set(p.config_schema),
+        source_code: Set(p.source_code),
+        created_at: Set(now),
+    })
+}
+
+/// Custom plugins referenced by a stored `plugins` column.
+pub(super) fn plugin_ids_from_json(
+    plugins: Option<serde_json::Value>,
+) -> Result<Vec<Uuid>, RepositoryError> {
+    let plugins = from_json_opt::<PluginsConfig>("plugins", plugins)?.map(Into::into);
+    Ok(custom_plugin_ids(plugins.as_ref()))
+}
+
+/// Decode a custom plugin row into the domain type.
+pub(super) fn plugin_from_model(m: plugin::Model) -> Result<domain::CustomPlugin, RepositoryError> {
+    Ok(domain::CustomPlugin {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        name: m.name,
+        description: m.description,
+        plugin_type: from_json::<PluginType>(
+            "plugin_type",
+            serde_json::Value::String(m.plugin_type),
+        )?
+        .into(),
+        phases: from_json::<Vec<PluginPhase>>("phases", m.phases)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        config_schema: m.config_schema,
+        source_code: m.source_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn full_upstream() -> domain::Upstream {
+        domain::Upstream {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            alias: "api.openai.com".into(),
+            server: domain::Server {
+                endpoints: vec![domain::Endpoint {
+                    scheme: domain::Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: Some(domain::AuthConfig {
+                plugin_type: "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.apikey.v1".into(),
+                sharing: domain::SharingMode::Inherit,
+                config: Some(HashMap::from([(
+                    "secret_ref".to_string(),
+                    "cred://openai-key".to_string(),
+                )])),
+            }),
+            headers: Some(domain::HeadersConfig {
+                request: Some(domain::RequestHeaderRules {
+                    passthrough: domain::PassthroughMode::Allowlist,
+                    passthrough_allowlist: vec!["x-request-id".into()],
+                    ..Default::default()
+                }),
+                response: None,
+            }),
+            plugins: Some(domain::PluginsConfig {
+                sharing: domain::SharingMode::Enforce,
+                items: vec!["gts.x.core.oagw.guard_plugin.v1~x.core.oagw.cors.v1".into()],
+                config: HashMap::from([(
+                    "gts.x.core.oagw.guard_plugin.v1~x.core.oagw.cors.v1".into(),
+                    serde_json::json!({"max_age": 600}),
+                )]),
+            }),
+            rate_limit: Some(domain::RateLimitConfig {
+                sharing: domain::SharingMode::Private,
+                algorithm: domain::RateLimitAlgorithm::SlidingWindow,
+                sustained: domain::SustainedRate {
+                    rate: 100,
+                    window: domain::Window::Minute,
+                },
+                burst: Some(domain::BurstConfig { capacity: 10 }),
+                scope: domain::RateLimitScope::User,
+                strategy: domain::RateLimitStrategy::Queue,
+                cost: 2,
+                queue: Some(domain::QueueConfig {
+                    max_depth: 10,
+                    timeout: Duration::from_secs(2),
+                }),
+                degrade: None,
+            }),
+            circuit_breaker: Some(domain::CircuitBreakerConfig {
+                failure_rate_threshold: Some(50),
+                scope: domain::CircuitBreakerScope::Global,
+                ..Default::default()
+            }),
+            load_balancing: Some(domain::LoadBalancingConfig {
+                strategy: domain::LoadBalancingStrategy::LeastInFlight,
+                outlier_detection: Some(domain::OutlierDetectionConfig::default()),
+                health_check: Some(domain::HealthCheckConfig::default()),
+            }),
+            tags: vec!["ai".into(), "llm".into()],
+        }
+    }
+
+    fn into_model(am: upstream::ActiveModel, now: OffsetDateTime) -> upstream::Model {
+        upstream::Model {
+            id: am.id.unwrap(),
+            tenant_id: am.tenant_id.unwrap(),
+            alias: am.alias.unwrap(),
+            server: am.server.unwrap(),
+            protocol: am.protocol.unwrap(),
+            auth: am.auth.unwrap(),
+            headers: am.headers.unwrap(),
+            plugins: am.plugins.unwrap(),
+            rate_limit: am.rate_limit.unwrap(),
+            circuit_breaker: am.circuit_breaker.unwrap(),
+            load_balancing: am.load_balancing.unwrap(),
+            tags: am.tags.unwrap(),
+            enabled: am.enabled.unwrap(),
+            created_at: now,
+            updated_at: am.updated_at.unwrap(),
+        }
+    }
+
+    #[test]
+    fn upstream_round_trips_through_model() {
+        let now = OffsetDateTime::now_utc();
+        let original = full_upstream();
+        let am = upstream_to_active_model(original.clone(), Some(now), now).unwrap();
+        let decoded = upstream_from_model(into_model(am, now)).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn stored_json_uses_api_field_names() {
+        let now = OffsetDateTime::now_utc();
+        let am = upstream_to_active_model(full_upstream(), None, now).unwrap();
+        assert!(am.created_at.is_not_set());
+
+        let auth = am.auth.unwrap().unwrap();
+        assert_eq!(
+            auth["type"],
+            "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.apikey.v1"
+        );
+        assert_eq!(auth["sharing"], "inherit");
+
+        let rl = am.rate_limit.unwrap().unwrap();
+        assert_eq!(rl["algorithm"], "sliding_window");
+        assert_eq!(rl["sustained"]["window"], "minute");
+
+        let cb = am.circuit_breaker.unwrap().unwrap();
+        assert_eq!(cb["scope"], "global");
+        assert_eq!(cb["failure_conditions"]["status_codes"][0], 500);
+
+        let lb = am.load_balancing.unwrap().unwrap();
+        assert_eq!(lb["strategy"], "least_in_flight");
+        assert_eq!(lb["health_check"]["path"], "/health");
+    }
+
+    #[test]
+    fn route_round_trips_through_model() {
+        let now = OffsetDateTime::now_utc();
+        let original = domain::Route {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            upstream_id: Uuid::new_v4(),
+            match_rules: domain::MatchRules {
+                http: Some(domain::HttpMatch {
+                    methods: vec![domain::HttpMethod::Get, domain::HttpMethod::Post],
+                    path: "/v1/chat/completions".into(),
+                    query_allowlist: vec!["stream".into()],
+                    path_suffix_mode: domain::PathSuffixMode::Disabled,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            grpc_transcoding: Some(domain::GrpcTranscodingConfig {
+                descriptor_set: vec![0x0a, 0x00, 0xff],
+            }),
+            response_cache: Some(domain::ResponseCacheConfig {
+                ttl: Duration::from_secs(300),
+                vary: vec!["accept".into()],
+            }),
+            usage_extraction: Some(domain::UsageExtractionConfig {
+                input_tokens: Some("/usage/prompt_tokens".into()),
+                output_tokens: None,
+            }),
+            tags: vec![],
+            priority: 7,
+            enabled: false,
+        };
+
+        let am = route_to_active_model(original.clone(), Some(now), now).unwrap();
+        let match_json = am.match_rules.clone().unwrap();
+        assert_eq!(match_json["http"]["methods"][0], "GET");
+        assert!(match_json.get("grpc").is_none());
+        let transcoding = am.grpc_transcoding.clone().unwrap().unwrap();
+        assert_eq!(transcoding["descriptor_set"], "CgD/");
+        let cache = am.response_cache.clone().unwrap().unwrap();
+        assert_eq!(cache["ttl"], "5m");
+        let usage = am.usage_extraction.clone().unwrap().unwrap();
+        assert_eq!(usage["input_tokens"], "/usage/prompt_tokens");
+        assert!(usage.get("output_tokens").is_none());
+
+        let model = route::Model {
+            id: am.id.unwrap(),
+            tenant_id: am.tenant_id.unwrap(),
+            upstream_id: am.upstream_id.unwrap(),
+            match_rules: am.match_rules.unwrap(),
+            plugins: am.plugins.unwrap(),
+            rate_limit: am.rate_limit.unwrap(),
+            grpc_transcoding: am.grpc_transcoding.unwrap(),
+            response_cache: am.response_cache.unwrap(),
+            usage_extraction: am.usage_extraction.unwrap(),
+            tags: am.tags.unwrap(),
+            priority: am.priority.unwrap(),
+            enabled: am.enabled.unwrap(),
+            created_at: now,
+            updated_at: am.updated_at.unwrap(),
+        };
+        assert_eq!(route_from_model(model).unwrap(), original);
+    }
+
+    #[test]
+    fn plugin_round_trips_through_model() {
+        use sea_orm::ActiveValue::Set;
+
+        let now = OffsetDateTime::now_utc();
+        let original = domain::CustomPlugin {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "redact-pii".into(),
+            description: Some("Masks emails".into()),
+            plugin_type: domain::PluginType::Transform,
+            phases: vec![domain::PluginPhase::OnResponse],
+            config_schema: serde_json::json!({"type": "object"}),
+            source_code: "def on_response(ctx):\n    return ctx.next()\n".into(),
+        };
+        let am = plugin_to_active_model(original.clone(), now).unwrap();
+        assert_eq!(am.plugin_type, Set("transform".to_string()));
+        assert_eq!(am.phases, Set(serde_json::json!(["on_response"])));
+
+        let model = plugin::Model {
+            id: am.id.unwrap(),
+            tenant_id: am.tenant_id.unwrap(),
+            name: am.name.unwrap(),
+            description: am.description.unwrap(),
+            plugin_type: am.plugin_type.unwrap(),
+            phases: am.phases.unwrap(),
+            config_schema: am.config_schema.unwrap(),
+            source_code: am.source_code.unwrap(),
+            created_at: am.created_at.unwrap(),
+        };
+        assert_eq!(plugin_from_model(model).unwrap(), original);
+    }
+
+    #[test]
+    fn corrupt_json_is_reported_as_internal() {
+        let now = OffsetDateTime::now_utc();
+        let am = upstream_to_active_model(full_upstream(), Some(now), now).unwrap();
+        let mut model = into_model(am, now);
+        model.server = serde_json::json!({"endpoints": "nope"});
+        let err = upstream_from_model(model).unwrap_err();
+        assert!(matches!(err, RepositoryError::Internal(msg) if msg.contains("server")));
+    }
+}
@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => {
+                r#"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id UUID PRIMARY KEY NOT NULL,
+    tenant_id UUID NOT NULL,
+    alias VARCHAR(255) NOT NULL,
+    server JSONB NOT NULL,
+    protocol VARCHAR(255) NOT NULL,
+    auth JSONB,
+    headers JSONB,
+    plugins JSONB,
+    rate_limit JSONB,
+    tags JSONB NOT NULL DEFAULT '[]',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT uq_oagw_upstream_tenant_alias UNIQUE (tenant_id, alias)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_upstream_tenant ON oagw_upstream(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_upstream_enabled ON oagw_upstream(tenant_id, enabled) WHERE enabled = TRUE;
+
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id UUID PRIMARY KEY NOT NULL,
+    tenant_id UUID NOT NULL,
+    upstream_id UUID NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    "match" JSONB NOT NULL,
+    plugins JSONB,
+    rate_limit JSONB,
+    tags JSONB NOT NULL DEFAULT '[]',
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_route_tenant ON oagw_route(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_route_upstream ON oagw_route(upstream_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_route_priority ON oagw_route(upstream_id, priority DESC);
+                "#
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id VARCHAR(36) NOT NULL,
+    alias VARCHAR(255) NOT NULL,
+    server JSON NOT NULL,
+    protocol VARCHAR(255) NOT NULL,
+    auth JSON,
+    headers JSON,
+    plugins JSON,
+    rate_limit JSON,
+    tags JSON NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE KEY uq_oagw_upstream_tenant_alias (tenant_id, alias),
+    INDEX idx_oagw_upstream_tenant (tenant_id),
+    INDEX idx_oagw_upstream_enabled (tenant_id, enabled)
+);
+
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id VARCHAR(36) NOT NULL,
+    upstream_id VARCHAR(36) NOT NULL,
+    `match` JSON NOT NULL,
+    plugins JSON,
+    rate_limit JSON,
+    tags JSON NOT NULL,
+    priority INT NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    INDEX idx_oagw_route_tenant (tenant_id),
+    INDEX idx_oagw_route_upstream (upstream_id),
+    INDEX idx_oagw_route_priority (upstream_id, priority DESC),
+    CONSTRAINT fk_oagw_route_upstream FOREIGN KEY (upstream_id)
+        REFERENCES oagw_upstream(id) ON DELETE CASCADE
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r#"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id TEXT PRIMARY KEY NOT NULL,
+    tenant_id TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    server TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    auth TEXT,
+    headers TEXT,
+    plugins TEXT,
+    rate_limit TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, alias)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_upstream_tenant ON oagw_upstream(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_upstream_enabled ON oagw_upstream(tenant_id, enabled);
+
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id TEXT PRIMARY KEY NOT NULL,
+    tenant_id TEXT NOT NULL,
+    upstream_id TEXT NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    "match" TEXT NOT NULL,
+    plugins TEXT,
+    rate_limit TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_route_tenant ON oagw_route(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_route_upstream ON oagw_route(upstream_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_route_priority ON oagw_route(upstream_id, priority DESC);
+                "#
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared("DROP TABLE IF EXISTS oagw_route;")
+            .await?;
+        conn.execute_unprepared("DROP TABLE IF EXISTS oagw_upstream;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                "ALTER TABLE oagw_upstream ADD COLUMN IF NOT EXISTS circuit_breaker JSONB;"
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                "ALTER TABLE oagw_upstream ADD COLUMN circuit_breaker JSON;"
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                "ALTER TABLE oagw_upstream ADD COLUMN circuit_breaker TEXT;"
+            }
+        };
+
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE oagw_upstream DROP COLUMN circuit_breaker;")
+            .await?;
+        Ok(())
+    }
+}
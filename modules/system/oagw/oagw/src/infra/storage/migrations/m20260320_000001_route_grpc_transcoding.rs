@@ -0,0 +1,33 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                "ALTER TABLE oagw_route ADD COLUMN IF NOT EXISTS grpc_transcoding JSONB;"
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                "ALTER TABLE oagw_route ADD COLUMN grpc_transcoding JSON;"
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                "ALTER TABLE oagw_route ADD COLUMN grpc_transcoding TEXT;"
+            }
+        };
+
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE oagw_route DROP COLUMN grpc_transcoding;")
+            .await?;
+        Ok(())
+    }
+}
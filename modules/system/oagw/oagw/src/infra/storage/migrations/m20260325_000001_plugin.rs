@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin (
+    id UUID PRIMARY KEY NOT NULL,
+    tenant_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    plugin_type VARCHAR(32) NOT NULL,
+    phases JSONB NOT NULL DEFAULT '[]',
+    config_schema JSONB NOT NULL DEFAULT '{}',
+    source_code TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT uq_oagw_plugin_tenant_name UNIQUE (tenant_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_tenant ON oagw_plugin(tenant_id);
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id VARCHAR(36) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    plugin_type VARCHAR(32) NOT NULL,
+    phases JSON NOT NULL,
+    config_schema JSON NOT NULL,
+    source_code MEDIUMTEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE KEY uq_oagw_plugin_tenant_name (tenant_id, name),
+    INDEX idx_oagw_plugin_tenant (tenant_id)
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin (
+    id TEXT PRIMARY KEY NOT NULL,
+    tenant_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    plugin_type TEXT NOT NULL,
+    phases TEXT NOT NULL DEFAULT '[]',
+    config_schema TEXT NOT NULL DEFAULT '{}',
+    source_code TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (tenant_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_tenant ON oagw_plugin(tenant_id);
+                "
+            }
+        };
+
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS oagw_plugin;")
+            .await?;
+        Ok(())
+    }
+}
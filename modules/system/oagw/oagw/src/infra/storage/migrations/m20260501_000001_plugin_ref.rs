@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, EntityTrait, QuerySelect, Set};
+use uuid::Uuid;
+
+use crate::infra::storage::entity::{plugin_ref, route, upstream};
+use crate::infra::storage::mapper::plugin_ids_from_json;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin_ref (
+    plugin_id UUID NOT NULL,
+    referrer_id UUID NOT NULL,
+    tenant_id UUID NOT NULL,
+    upstream_id UUID NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    route_id UUID REFERENCES oagw_route(id) ON DELETE CASCADE,
+    PRIMARY KEY (plugin_id, referrer_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_ref_upstream ON oagw_plugin_ref(upstream_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_ref_route ON oagw_plugin_ref(route_id);
+                "
+            }
+            sea_orm::DatabaseBackend::MySql => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin_ref (
+    plugin_id VARCHAR(36) NOT NULL,
+    referrer_id VARCHAR(36) NOT NULL,
+    tenant_id VARCHAR(36) NOT NULL,
+    upstream_id VARCHAR(36) NOT NULL,
+    route_id VARCHAR(36),
+    PRIMARY KEY (plugin_id, referrer_id),
+    INDEX idx_oagw_plugin_ref_upstream (upstream_id),
+    INDEX idx_oagw_plugin_ref_route (route_id),
+    CONSTRAINT fk_oagw_plugin_ref_upstream FOREIGN KEY (upstream_id)
+        REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    CONSTRAINT fk_oagw_plugin_ref_route FOREIGN KEY (route_id)
+        REFERENCES oagw_route(id) ON DELETE CASCADE
+);
+                "
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                r"
+CREATE TABLE IF NOT EXISTS oagw_plugin_ref (
+    plugin_id TEXT NOT NULL,
+    referrer_id TEXT NOT NULL,
+    tenant_id TEXT NOT NULL,
+    upstream_id TEXT NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    route_id TEXT REFERENCES oagw_route(id) ON DELETE CASCADE,
+    PRIMARY KEY (plugin_id, referrer_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_ref_upstream ON oagw_plugin_ref(upstream_id);
+CREATE INDEX IF NOT EXISTS idx_oagw_plugin_ref_route ON oagw_plugin_ref(route_id);
+                "
+            }
+        };
+
+        let conn = manager.get_connection();
+        conn.execute_unprepared(sql).await?;
+        backfill(conn).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS oagw_plugin_ref;")
+            .await?;
+        Ok(())
+    }
+}
+
+/// Record the references held by existing upstreams and routes. Only the
+/// columns that exist as of this migration are read.
+async fn backfill(conn: &impl ConnectionTrait) -> Result<(), DbErr> {
+    let upstreams: Vec<(Uuid, Uuid, Option<serde_json::Value>)> = upstream::Entity::find()
+        .select_only()
+        .columns([
+            upstream::Column::Id,
+            upstream::Column::TenantId,
+            upstream::Column::Plugins,
+        ])
+        .into_tuple()
+        .all(conn)
+        .await?;
+    let routes: Vec<(Uuid, Uuid, Uuid, Option<serde_json::Value>)> = route::Entity::find()
+        .select_only()
+        .columns([
+            route::Column::Id,
+            route::Column::TenantId,
+            route::Column::UpstreamId,
+            route::Column::Plugins,
+        ])
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    let referrers = upstreams
+        .into_iter()
+        .map(|(id, tenant_id, plugins)| (tenant_id, id, None, plugins))
+        .chain(
+            routes
+                .into_iter()
+                .map(|(id, tenant_id, upstream_id, plugins)| {
+                    (tenant_id, upstream_id, Some(id), plugins)
+                }),
+        );
+    for (tenant_id, upstream_id, route_id, plugins) in referrers {
+        let plugin_ids =
+            plugin_ids_from_json(plugins).map_err(|e| DbErr::Migration(e.to_string()))?;
+        for plugin_id in plugin_ids {
+            plugin_ref::Entity::insert(plugin_ref::ActiveModel {
+                plugin_id: Set(plugin_id),
+                referrer_id: Set(route_id.unwrap_or(upstream_id)),
+                tenant_id: Set(tenant_id),
+                upstream_id: Set(upstream_id),
+                route_id: Set(route_id),
+            })
+            .exec_without_returning(conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
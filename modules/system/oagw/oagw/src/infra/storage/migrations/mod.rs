@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+mod m20260301_000001_initial;
+mod m20260310_000001_upstream_circuit_breaker;
+mod m20260315_000001_upstream_load_balancing;
+mod m20260320_000001_route_grpc_transcoding;
+mod m20260325_000001_plugin;
+mod m20260401_000001_route_response_cache;
+mod m20260415_000001_route_usage_extraction;
+mod m20260501_000001_plugin_ref;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20260301_000001_initial::Migration),
+            Box::new(m20260310_000001_upstream_circuit_breaker::Migration),
+            Box::new(m20260315_000001_upstream_load_balancing::Migration),
+            Box::new(m20260320_000001_route_grpc_transcoding::Migration),
+            Box::new(m20260325_000001_plugin::Migration),
+            Box::new(m20260401_000001_route_response_cache::Migration),
+            Box::new(m20260415_000001_route_usage_extraction::Migration),
+            Box::new(m20260501_000001_plugin_ref::Migration),
+        ]
+    }
+}
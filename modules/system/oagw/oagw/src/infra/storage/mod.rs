@@ -1,7 +1,19 @@
 pub(crate) mod credential_repo;
+mod db;
+pub(crate) mod entity;
+mod mapper;
+pub(crate) mod migrations;
+pub(crate) mod plugin_repo;
+mod plugin_sea_repo;
 pub(crate) mod route_repo;
+mod route_sea_repo;
 pub(crate) mod upstream_repo;
+mod upstream_sea_repo;
 
 pub(crate) use credential_repo::InMemoryCredentialResolver;
+pub(crate) use plugin_repo::{InMemoryPluginRefs, InMemoryPluginRepo};
+pub(crate) use plugin_sea_repo::SeaOrmPluginRepo;
 pub(crate) use route_repo::InMemoryRouteRepo;
+pub(crate) use route_sea_repo::SeaOrmRouteRepo;
 pub(crate) use upstream_repo::InMemoryUpstreamRepo;
+pub(crate) use upstream_sea_repo::SeaOrmUpstreamRepo;
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::domain::model::{CustomPlugin, ListQuery};
+use crate::domain::repo::{PluginRepository, RepositoryError};
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Kind of resource holding a plugin reference.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum ReferrerKind {
+    Upstream,
+    Route,
+}
+
+struct Referrer {
+    tenant_id: Uuid,
+    kind: ReferrerKind,
+    plugin_ids: Vec<Uuid>,
+}
+
+/// Custom plugin references held by upstreams and routes, shared by the
+/// in-memory repositories so that a plugin in use cannot be deleted.
+///
+/// Repositories write their store and the references under the same lock.
+#[domain_model]
+#[derive(Default)]
+pub struct InMemoryPluginRefs {
+    /// Referrer id -> the plugins it references.
+    referrers: Mutex<HashMap<Uuid, Referrer>>,
+}
+
+/// Locked view of [`InMemoryPluginRefs`].
+pub(super) struct PluginRefsGuard<'a>(MutexGuard<'a, HashMap<Uuid, Referrer>>);
+
+impl InMemoryPluginRefs {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn lock(&self) -> PluginRefsGuard<'_> {
+        PluginRefsGuard(
+            self.referrers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+}
+
+impl PluginRefsGuard<'_> {
+    /// Replace the references held by `referrer_id`.
+    pub(super) fn set(
+        &mut self,
+        tenant_id: Uuid,
+        kind: ReferrerKind,
+        referrer_id: Uuid,
+        plugin_ids: Vec<Uuid>,
+    ) {
+        if plugin_ids.is_empty() {
+            self.0.remove(&referrer_id);
+        } else {
+            self.0.insert(
+                referrer_id,
+                Referrer {
+                    tenant_id,
+                    kind,
+                    plugin_ids,
+                },
+            );
+        }
+    }
+
+    /// Drop the references held by `referrer_id`.
+    pub(super) fn remove(&mut self, referrer_id: Uuid) {
+        self.0.remove(&referrer_id);
+    }
+
+    /// Upstreams and routes of the tenant referencing `plugin_id`, sorted.
+    fn referrers_of(&self, tenant_id: Uuid, plugin_id: Uuid) -> (Vec<Uuid>, Vec<Uuid>) {
+        let mut upstreams = Vec::new();
+        let mut routes = Vec::new();
+        for (id, referrer) in self.0.iter() {
+            if referrer.tenant_id == tenant_id && referrer.plugin_ids.contains(&plugin_id) {
+                match referrer.kind {
+                    ReferrerKind::Upstream => upstreams.push(*id),
+                    ReferrerKind::Route => routes.push(*id),
+                }
+            }
+        }
+        upstreams.sort_unstable();
+        routes.sort_unstable();
+        (upstreams, routes)
+    }
+}
+
+/// In-memory custom plugin repository backed by `DashMap`.
+#[domain_model]
+pub struct InMemoryPluginRepo {
+    /// Primary store: id -> CustomPlugin.
+    store: DashMap<Uuid, CustomPlugin>,
+    /// Name index: (tenant_id, name) -> plugin_id.
+    name_index: DashMap<(Uuid, String), Uuid>,
+    /// References held by upstreams and routes.
+    plugin_refs: Arc<InMemoryPluginRefs>,
+}
+
+impl InMemoryPluginRepo {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            store: DashMap::new(),
+            name_index: DashMap::new(),
+            plugin_refs: Arc::default(),
+        }
+    }
+
+    /// Check deletes against the references recorded by the upstream and
+    /// route repositories sharing `plugin_refs`.
+    #[must_use]
+    pub fn with_plugin_refs(mut self, plugin_refs: Arc<InMemoryPluginRefs>) -> Self {
+        self.plugin_refs = plugin_refs;
+        self
+    }
+}
+
+impl Default for InMemoryPluginRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginRepository for InMemoryPluginRepo {
+    async fn create(&self, plugin: CustomPlugin) -> Result<CustomPlugin, RepositoryError> {
+        match self
+            .name_index
+            .entry((plugin.tenant_id, plugin.name.clone()))
+        {
+            dashmap::mapref::entry::Entry::Occupied(_) => {
+                return Err(RepositoryError::Conflict(format!(
+                    "plugin '{}' already exists for tenant",
+                    plugin.name
+                )));
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                entry.insert(plugin.id);
+            }
+        }
+
+        self.store.insert(plugin.id, plugin.clone());
+        Ok(plugin)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<CustomPlugin, RepositoryError> {
+        self.store
+            .get(&id)
+            .filter(|p| p.tenant_id == tenant_id)
+            .map(|p| p.clone())
+            .ok_or(RepositoryError::NotFound {
+                entity: "plugin",
+                id,
+            })
+    }
+
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<CustomPlugin>, RepositoryError> {
+        let mut all: Vec<CustomPlugin> = self
+            .store
+            .iter()
+            .filter(|e| e.value().tenant_id == tenant_id)
+            .map(|e| e.value().clone())
+            .collect();
+
+        all.sort_by_key(|p| p.id);
+
+        let skip = query.skip as usize;
+        let top = query.top as usize;
+        Ok(all.into_iter().skip(skip).take(top).collect())
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        // Hold the references while deleting so that none can be added
+        // between the check and the delete.
+        let refs = self.plugin_refs.lock();
+        let (upstreams, routes) = refs.referrers_of(tenant_id, id);
+        if self
+            .store
+            .get(&id)
+            .is_some_and(|p| p.tenant_id == tenant_id)
+            && (!upstreams.is_empty() || !routes.is_empty())
+        {
+            return Err(RepositoryError::PluginInUse {
+                id,
+                upstreams,
+                routes,
+            });
+        }
+
+        let (_, plugin) = self
+            .store
+            .remove_if(&id, |_, p| p.tenant_id == tenant_id)
+            .ok_or(RepositoryError::NotFound {
+                entity: "plugin",
+                id,
+            })?;
+
+        self.name_index.remove(&(tenant_id, plugin.name));
+        drop(refs);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{PluginPhase, PluginType};
+
+    use super::*;
+
+    fn make_plugin(tenant_id: Uuid, name: &str) -> CustomPlugin {
+        CustomPlugin {
+            id: Uuid::new_v4(),
+            tenant_id,
+            name: name.into(),
+            description: None,
+            plugin_type: PluginType::Guard,
+            phases: vec![PluginPhase::OnRequest],
+            config_schema: serde_json::json!({}),
+            source_code: "def on_request(ctx):\n    return ctx.next()\n".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn name_is_unique_per_tenant() {
+        let repo = InMemoryPluginRepo::new();
+        let t1 = Uuid::new_v4();
+        let t2 = Uuid::new_v4();
+
+        repo.create(make_plugin(t1, "require-header"))
+            .await
+            .unwrap();
+        let err = repo.create(make_plugin(t1, "require-header")).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+
+        repo.create(make_plugin(t2, "require-header"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_frees_name_and_is_tenant_scoped() {
+        let repo = InMemoryPluginRepo::new();
+        let owner = Uuid::new_v4();
+        let p = make_plugin(owner, "require-header");
+        repo.create(p.clone()).await.unwrap();
+
+        assert!(matches!(
+            repo.delete(Uuid::new_v4(), p.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert_eq!(repo.get_by_id(owner, p.id).await.unwrap(), p);
+
+        repo.delete(owner, p.id).await.unwrap();
+        assert!(matches!(
+            repo.get_by_id(owner, p.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        repo.create(make_plugin(owner, "require-header"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_is_refused_while_referenced() {
+        let refs = Arc::new(InMemoryPluginRefs::new());
+        let repo = InMemoryPluginRepo::new().with_plugin_refs(refs.clone());
+        let tenant = Uuid::new_v4();
+        let p = make_plugin(tenant, "require-header");
+        repo.create(p.clone()).await.unwrap();
+
+        let (upstream, route) = (Uuid::new_v4(), Uuid::new_v4());
+        refs.lock()
+            .set(tenant, ReferrerKind::Upstream, upstream, vec![p.id]);
+        refs.lock()
+            .set(tenant, ReferrerKind::Route, route, vec![p.id]);
+        // References from another tenant are ignored.
+        refs.lock().set(
+            Uuid::new_v4(),
+            ReferrerKind::Route,
+            Uuid::new_v4(),
+            vec![p.id],
+        );
+
+        match repo.delete(tenant, p.id).await {
+            Err(RepositoryError::PluginInUse {
+                upstreams, routes, ..
+            }) => {
+                assert_eq!(upstreams, vec![upstream]);
+                assert_eq!(routes, vec![route]);
+            }
+            other => panic!("expected PluginInUse, got {other:?}"),
+        }
+
+        refs.lock().remove(upstream);
+        refs.lock().set(tenant, ReferrerKind::Route, route, vec![]);
+        repo.delete(tenant, p.id).await.unwrap();
+    }
+}
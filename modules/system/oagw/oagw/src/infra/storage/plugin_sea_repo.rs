@@ -0,0 +1,430 @@
+use std::sync::Arc;
+
+use modkit_db::secure::{DBRunner, ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::{ActiveValue::Set, ColumnTrait, Condition, EntityTrait, Order, QueryFilter};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::model::{CustomPlugin, ListQuery};
+use crate::domain::repo::{PluginRepository, RepositoryError};
+
+use super::db::{abort, db_err, is_unique_violation, tx_err};
+use super::entity::plugin::{Column, Entity as PluginEntity};
+use super::entity::plugin_ref::{
+    ActiveModel as PluginRefActiveModel, Column as RefColumn, Entity as PluginRefEntity,
+};
+use super::mapper::{plugin_from_model, plugin_to_active_model};
+
+/// Replace the plugin references held by an upstream (`route_id` unset) or a
+/// route. Called in the transaction that writes the referrer.
+pub(super) async fn replace_plugin_refs(
+    runner: &impl DBRunner,
+    tenant_id: Uuid,
+    upstream_id: Uuid,
+    route_id: Option<Uuid>,
+    plugin_ids: Vec<Uuid>,
+) -> Result<(), ScopeError> {
+    let scope = AccessScope::for_tenant(tenant_id);
+    let referrer = match route_id {
+        Some(route_id) => Condition::all().add(RefColumn::RouteId.eq(route_id)),
+        None => Condition::all()
+            .add(RefColumn::UpstreamId.eq(upstream_id))
+            .add(RefColumn::RouteId.is_null()),
+    };
+    PluginRefEntity::delete_many()
+        .filter(referrer)
+        .secure()
+        .scope_with(&scope)
+        .exec(runner)
+        .await?;
+    for plugin_id in plugin_ids {
+        let am = PluginRefActiveModel {
+            plugin_id: Set(plugin_id),
+            referrer_id: Set(route_id.unwrap_or(upstream_id)),
+            tenant_id: Set(tenant_id),
+            upstream_id: Set(upstream_id),
+            route_id: Set(route_id),
+        };
+        secure_insert::<PluginRefEntity>(am, &scope, runner).await?;
+    }
+    Ok(())
+}
+
+/// `SeaORM`-backed custom plugin repository.
+///
+/// Name uniqueness per tenant is enforced by the `(tenant_id, name)` unique
+/// constraint. Rows are never updated; plugins are replaced by creating a new
+/// one and repointing references. The upstream and route repositories keep
+/// `oagw_plugin_ref` in step with their writes, so a delete only has to look
+/// there.
+pub struct SeaOrmPluginRepo {
+    db: Arc<DBProvider<DbError>>,
+}
+
+impl SeaOrmPluginRepo {
+    #[must_use]
+    pub fn new(db: Arc<DBProvider<DbError>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginRepository for SeaOrmPluginRepo {
+    async fn create(&self, plugin: CustomPlugin) -> Result<CustomPlugin, RepositoryError> {
+        let scope = AccessScope::for_tenant(plugin.tenant_id);
+        let name = plugin.name.clone();
+        let am = plugin_to_active_model(plugin, OffsetDateTime::now_utc())?;
+
+        let conn = self.db.conn().map_err(db_err)?;
+        let model = secure_insert::<PluginEntity>(am, &scope, &conn)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    RepositoryError::Conflict(format!("plugin '{name}' already exists for tenant"))
+                } else {
+                    db_err(e)
+                }
+            })?;
+        plugin_from_model(model)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<CustomPlugin, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let model = PluginEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .and_id(id)
+            .map_err(db_err)?
+            .one(&conn)
+            .await
+            .map_err(db_err)?
+            .ok_or(RepositoryError::NotFound {
+                entity: "plugin",
+                id,
+            })?;
+        plugin_from_model(model)
+    }
+
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<CustomPlugin>, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        PluginEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(plugin_from_model)
+            .collect()
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        // Delete first and roll back if references remain, so the check and
+        // the delete see the same state.
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = AccessScope::for_tenant(tenant_id);
+                    let result = PluginEntity::delete_many()
+                        .filter(Condition::all().add(Column::Id.eq(id)))
+                        .secure()
+                        .scope_with(&scope)
+                        .exec(tx)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    if result.rows_affected == 0 {
+                        return Err(abort(RepositoryError::NotFound {
+                            entity: "plugin",
+                            id,
+                        }));
+                    }
+
+                    let refs = PluginRefEntity::find()
+                        .secure()
+                        .scope_with(&scope)
+                        .filter(Condition::all().add(RefColumn::PluginId.eq(id)))
+                        .order_by(RefColumn::ReferrerId, Order::Asc)
+                        .all(tx)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    if refs.is_empty() {
+                        return Ok(());
+                    }
+                    let (routes, upstreams): (Vec<_>, Vec<_>) =
+                        refs.into_iter().partition(|r| r.route_id.is_some());
+                    Err(abort(RepositoryError::PluginInUse {
+                        id,
+                        upstreams: upstreams.into_iter().map(|r| r.referrer_id).collect(),
+                        routes: routes.into_iter().map(|r| r.referrer_id).collect(),
+                    }))
+                })
+            })
+            .await
+            .map_err(tx_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    use crate::domain::gts_helpers::format_plugin_gts;
+    use crate::domain::model::{
+        Endpoint, HttpMatch, HttpMethod, MatchRules, PathSuffixMode, PluginPhase, PluginType,
+        PluginsConfig, Route, Scheme, Server, SharingMode, Upstream,
+    };
+    use crate::domain::repo::{RouteRepository, UpstreamRepository};
+    use crate::domain::test_support::sqlite_test_db;
+    use crate::infra::storage::mapper::upstream_to_active_model;
+    use crate::infra::storage::migrations::Migrator;
+    use crate::infra::storage::{SeaOrmRouteRepo, SeaOrmUpstreamRepo};
+
+    use super::super::entity::upstream::Entity as UpstreamEntity;
+    use super::*;
+
+    fn uses(plugin: &CustomPlugin) -> Option<PluginsConfig> {
+        Some(PluginsConfig {
+            sharing: SharingMode::Private,
+            items: vec![format_plugin_gts(plugin.plugin_type, plugin.id)],
+            config: Default::default(),
+        })
+    }
+
+    fn make_upstream(tenant_id: Uuid, plugins: Option<PluginsConfig>) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: format!("svc-{}", Uuid::new_v4().simple()),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
+            tags: vec![],
+        }
+    }
+
+    fn make_route(tenant_id: Uuid, upstream_id: Uuid, plugins: Option<PluginsConfig>) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            tenant_id,
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods: vec![HttpMethod::Get],
+                    path: "/v1".into(),
+                    query_allowlist: vec![],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins,
+            rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
+            tags: vec![],
+            priority: 0,
+            enabled: true,
+        }
+    }
+
+    fn in_use(result: Result<(), RepositoryError>) -> (Vec<Uuid>, Vec<Uuid>) {
+        match result {
+            Err(RepositoryError::PluginInUse {
+                upstreams, routes, ..
+            }) => (upstreams, routes),
+            other => panic!("expected PluginInUse, got {other:?}"),
+        }
+    }
+
+    fn make_plugin(tenant_id: Uuid, name: &str) -> CustomPlugin {
+        CustomPlugin {
+            id: Uuid::new_v4(),
+            tenant_id,
+            name: name.into(),
+            description: Some("Requires X-Customer-Id".into()),
+            plugin_type: PluginType::Guard,
+            phases: vec![PluginPhase::OnRequest],
+            config_schema: serde_json::json!({
+                "type": "object",
+                "properties": {"header": {"type": "string", "default": "x-customer-id"}}
+            }),
+            source_code: "def on_request(ctx):\n    return ctx.next()\n".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip() {
+        let repo = SeaOrmPluginRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+        let p = make_plugin(tenant, "require-header");
+
+        assert_eq!(repo.create(p.clone()).await.unwrap(), p);
+        assert_eq!(repo.get_by_id(tenant, p.id).await.unwrap(), p);
+        assert!(matches!(
+            repo.get_by_id(Uuid::new_v4(), p.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn name_uniqueness_is_enforced_by_database() {
+        let repo = SeaOrmPluginRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+
+        repo.create(make_plugin(tenant, "require-header"))
+            .await
+            .unwrap();
+        let err = repo.create(make_plugin(tenant, "require-header")).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+
+        repo.create(make_plugin(Uuid::new_v4(), "require-header"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn list_and_delete_are_tenant_scoped() {
+        let repo = SeaOrmPluginRepo::new(sqlite_test_db().await);
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let p = make_plugin(owner, "a");
+        repo.create(p.clone()).await.unwrap();
+        repo.create(make_plugin(owner, "b")).await.unwrap();
+        repo.create(make_plugin(other, "c")).await.unwrap();
+
+        let all = repo.list(owner, &ListQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        assert!(matches!(
+            repo.delete(other, p.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        repo.delete(owner, p.id).await.unwrap();
+        assert_eq!(
+            repo.list(owner, &ListQuery::default()).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_is_refused_while_referenced() {
+        let db = sqlite_test_db().await;
+        let plugins = SeaOrmPluginRepo::new(db.clone());
+        let upstreams = SeaOrmUpstreamRepo::new(db.clone());
+        let routes = SeaOrmRouteRepo::new(db);
+        let tenant = Uuid::new_v4();
+        let p = plugins
+            .create(make_plugin(tenant, "require-header"))
+            .await
+            .unwrap();
+
+        let mut u = upstreams
+            .create(make_upstream(tenant, uses(&p)))
+            .await
+            .unwrap();
+        let r = routes
+            .create(make_route(tenant, u.id, uses(&p)))
+            .await
+            .unwrap();
+        assert_eq!(
+            in_use(plugins.delete(tenant, p.id).await),
+            (vec![u.id], vec![r.id])
+        );
+        assert_eq!(plugins.get_by_id(tenant, p.id).await.unwrap(), p);
+
+        routes.delete(tenant, r.id).await.unwrap();
+        assert_eq!(
+            in_use(plugins.delete(tenant, p.id).await),
+            (vec![u.id], vec![])
+        );
+
+        u.plugins = None;
+        upstreams.update(u).await.unwrap();
+        plugins.delete(tenant, p.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn deleting_upstream_drops_its_references() {
+        let db = sqlite_test_db().await;
+        let plugins = SeaOrmPluginRepo::new(db.clone());
+        let upstreams = SeaOrmUpstreamRepo::new(db.clone());
+        let routes = SeaOrmRouteRepo::new(db);
+        let tenant = Uuid::new_v4();
+        let p = plugins
+            .create(make_plugin(tenant, "require-header"))
+            .await
+            .unwrap();
+        let u = upstreams
+            .create(make_upstream(tenant, uses(&p)))
+            .await
+            .unwrap();
+        routes
+            .create(make_route(tenant, u.id, uses(&p)))
+            .await
+            .unwrap();
+
+        // Another tenant's reference to the same id does not count.
+        let other = Uuid::new_v4();
+        upstreams
+            .create(make_upstream(other, uses(&p)))
+            .await
+            .unwrap();
+
+        upstreams.delete(tenant, u.id).await.unwrap();
+        plugins.delete(tenant, p.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn references_of_existing_rows_are_backfilled() {
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+        let db = connect_db("sqlite::memory:", opts).await.unwrap();
+        let mut migrations = Migrator::migrations();
+        let last = migrations.pop().unwrap();
+        run_migrations_for_testing(&db, migrations).await.unwrap();
+
+        let tenant = Uuid::new_v4();
+        let p = make_plugin(tenant, "require-header");
+        let u = make_upstream(tenant, uses(&p));
+        let now = OffsetDateTime::now_utc();
+        let am = upstream_to_active_model(u.clone(), Some(now), now).unwrap();
+        secure_insert::<UpstreamEntity>(am, &AccessScope::for_tenant(tenant), &db.conn().unwrap())
+            .await
+            .unwrap();
+        run_migrations_for_testing(&db, vec![last]).await.unwrap();
+
+        let plugins = SeaOrmPluginRepo::new(Arc::new(DBProvider::new(db)));
+        plugins.create(p.clone()).await.unwrap();
+        assert_eq!(
+            in_use(plugins.delete(tenant, p.id).await),
+            (vec![u.id], vec![])
+        );
+    }
+}
@@ -1,9 +1,14 @@
+use std::sync::Arc;
+
+use crate::domain::gts_helpers::custom_plugin_ids;
 use crate::domain::model::{HttpMethod, ListQuery, Route};
 use crate::domain::repo::{RepositoryError, RouteRepository};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
 use uuid::Uuid;
 
+use super::plugin_repo::{InMemoryPluginRefs, ReferrerKind};
+
 /// In-memory route repository backed by `DashMap`.
 #[domain_model]
 pub struct InMemoryRouteRepo {
@@ -11,6 +16,8 @@ pub struct InMemoryRouteRepo {
     store: DashMap<Uuid, Route>,
     /// Upstream index: upstream_id -> vec of route_ids.
     upstream_index: DashMap<Uuid, Vec<Uuid>>,
+    /// Custom plugins referenced by each route.
+    plugin_refs: Arc<InMemoryPluginRefs>,
 }
 
 impl InMemoryRouteRepo {
@@ -19,8 +26,17 @@ impl InMemoryRouteRepo {
         Self {
             store: DashMap::new(),
             upstream_index: DashMap::new(),
+            plugin_refs: Arc::default(),
         }
     }
+
+    /// Record plugin references in `plugin_refs`, shared with the plugin
+    /// repository.
+    #[must_use]
+    pub fn with_plugin_refs(mut self, plugin_refs: Arc<InMemoryPluginRefs>) -> Self {
+        self.plugin_refs = plugin_refs;
+        self
+    }
 }
 
 impl Default for InMemoryRouteRepo {
@@ -35,7 +51,15 @@ impl RouteRepository for InMemoryRouteRepo {
         let route_id = route.id;
         let upstream_id = route.upstream_id;
 
+        let mut refs = self.plugin_refs.lock();
         self.store.insert(route_id, route.clone());
+        refs.set(
+            route.tenant_id,
+            ReferrerKind::Route,
+            route_id,
+            custom_plugin_ids(route.plugins.as_ref()),
+        );
+        drop(refs);
 
         // Update upstream index.
         self.upstream_index
@@ -119,23 +143,25 @@ impl RouteRepository for InMemoryRouteRepo {
             if !route.enabled {
                 continue;
             }
-            // Must have HTTP match rules.
-            let Some(http_match) = &route.match_rules.http else {
-                continue;
-            };
-            // Method must match (unknown methods never match).
+            // Unknown methods never match.
             let Some(req_method) = &request_method else {
                 continue;
             };
-            if !http_match.methods.contains(req_method) {
-                continue;
-            }
-            // Path must be a prefix match.
-            if !path.starts_with(&http_match.path) {
+            let path_len = if let Some(http_match) = &route.match_rules.http {
+                // Method must match and path must be a prefix match.
+                if !http_match.methods.contains(req_method) || !path.starts_with(&http_match.path) {
+                    continue;
+                }
+                http_match.path.len()
+            } else if let Some(grpc_match) = &route.match_rules.grpc {
+                // gRPC calls are POSTs to exactly `/{service}/{method}`.
+                if *req_method != HttpMethod::Post || path != grpc_match.path() {
+                    continue;
+                }
+                path.len()
+            } else {
                 continue;
-            }
-
-            let path_len = http_match.path.len();
+            };
             let priority = route.priority;
 
             // Select by longest path prefix, then highest priority.
@@ -159,7 +185,14 @@ impl RouteRepository for InMemoryRouteRepo {
                 id: route.id,
             });
         }
+        let mut refs = self.plugin_refs.lock();
         self.store.insert(route.id, route.clone());
+        refs.set(
+            route.tenant_id,
+            ReferrerKind::Route,
+            route.id,
+            custom_plugin_ids(route.plugins.as_ref()),
+        );
         Ok(route)
     }
 
@@ -177,6 +210,7 @@ impl RouteRepository for InMemoryRouteRepo {
         drop(entry);
 
         self.store.remove(&id);
+        self.plugin_refs.lock().remove(id);
         if let Some(mut ids) = self.upstream_index.get_mut(&upstream_id) {
             ids.retain(|rid| *rid != id);
         }
@@ -200,6 +234,7 @@ impl RouteRepository for InMemoryRouteRepo {
         for id in route_ids {
             if let Some((_, route)) = self.store.remove(&id) {
                 if route.tenant_id == tenant_id {
+                    self.plugin_refs.lock().remove(id);
                     deleted += 1;
                 } else {
                     // Put it back — wrong tenant.
@@ -218,7 +253,7 @@ impl RouteRepository for InMemoryRouteRepo {
     }
 }
 
-fn parse_method(s: &str) -> Option<HttpMethod> {
+pub(super) fn parse_method(s: &str) -> Option<HttpMethod> {
     match s.to_uppercase().as_str() {
         "GET" => Some(HttpMethod::Get),
         "POST" => Some(HttpMethod::Post),
@@ -231,7 +266,7 @@ fn parse_method(s: &str) -> Option<HttpMethod> {
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{HttpMatch, MatchRules, PathSuffixMode};
+    use crate::domain::model::{GrpcMatch, HttpMatch, MatchRules, PathSuffixMode};
 
     use super::*;
 
@@ -257,6 +292,9 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
             tags: vec![],
             priority,
             enabled: true,
@@ -363,6 +401,38 @@ mod tests {
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
 
+    #[tokio::test]
+    async fn find_matching_grpc_route_by_exact_path() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let mut grpc = make_route(tenant, upstream, vec![], "", 0);
+        grpc.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "example.v1.UserService".into(),
+                method: "GetUser".into(),
+            }),
+        };
+        repo.create(grpc.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/example.v1.UserService/GetUser")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, grpc.id);
+
+        for (method, path) in [
+            ("GET", "/example.v1.UserService/GetUser"),
+            ("POST", "/example.v1.UserService/GetUserX"),
+            ("POST", "/example.v1.UserService"),
+        ] {
+            let result = repo.find_matching(tenant, upstream, method, path).await;
+            assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+        }
+    }
+
     #[tokio::test]
     async fn list_by_upstream_returns_correct_set() {
         let repo = InMemoryRouteRepo::new();
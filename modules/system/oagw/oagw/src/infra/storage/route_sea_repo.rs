@@ -0,0 +1,480 @@
+use std::sync::Arc;
+
+use modkit_db::secure::{
+    ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, QueryFilter};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::gts_helpers::custom_plugin_ids;
+use crate::domain::model::{HttpMethod, ListQuery, Route};
+use crate::domain::repo::{RepositoryError, RouteRepository};
+
+use super::db::{abort, db_err, tx_err};
+use super::entity::route::{Column, Entity as RouteEntity};
+use super::mapper::{route_from_model, route_to_active_model};
+use super::plugin_sea_repo::replace_plugin_refs;
+use super::route_repo::parse_method;
+
+/// `SeaORM`-backed route repository.
+///
+/// Routes reference their upstream with `ON DELETE CASCADE`; matching loads
+/// the enabled routes of one upstream and ranks them in memory with the same
+/// rules as the in-memory repository. Writes update the route's
+/// `oagw_plugin_ref` rows in the same transaction.
+pub struct SeaOrmRouteRepo {
+    db: Arc<DBProvider<DbError>>,
+}
+
+impl SeaOrmRouteRepo {
+    #[must_use]
+    pub fn new(db: Arc<DBProvider<DbError>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl RouteRepository for SeaOrmRouteRepo {
+    async fn create(&self, route: Route) -> Result<Route, RepositoryError> {
+        let id = route.id;
+        let tenant_id = route.tenant_id;
+        let upstream_id = route.upstream_id;
+        let plugin_ids = custom_plugin_ids(route.plugins.as_ref());
+        let now = OffsetDateTime::now_utc();
+        let am = route_to_active_model(route, Some(now), now)?;
+
+        let model = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = AccessScope::for_tenant(tenant_id);
+                    let model = secure_insert::<RouteEntity>(am, &scope, tx)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    replace_plugin_refs(tx, tenant_id, upstream_id, Some(id), plugin_ids)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    Ok(model)
+                })
+            })
+            .await
+            .map_err(tx_err)?;
+        route_from_model(model)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let model = RouteEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .and_id(id)
+            .map_err(db_err)?
+            .one(&conn)
+            .await
+            .map_err(db_err)?
+            .ok_or(RepositoryError::NotFound {
+                entity: "route",
+                id,
+            })?;
+        route_from_model(model)
+    }
+
+    async fn list_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        RouteEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Column::UpstreamId.eq(upstream_id)))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(route_from_model)
+            .collect()
+    }
+
+    async fn find_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        method: &str,
+        path: &str,
+    ) -> Result<Route, RepositoryError> {
+        let not_found = RepositoryError::NotFound {
+            entity: "route",
+            id: Uuid::nil(),
+        };
+        // Unknown methods never match.
+        let Some(request_method) = parse_method(method) else {
+            return Err(not_found);
+        };
+
+        let conn = self.db.conn().map_err(db_err)?;
+        let candidates = RouteEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(
+                Condition::all()
+                    .add(Column::UpstreamId.eq(upstream_id))
+                    .add(Column::Enabled.eq(true)),
+            )
+            .all(&conn)
+            .await
+            .map_err(db_err)?;
+
+        let mut best: Option<Route> = None;
+        let mut best_path_len = 0;
+        let mut best_priority = i32::MIN;
+
+        for model in candidates {
+            let route = route_from_model(model)?;
+            let path_len = if let Some(http_match) = &route.match_rules.http {
+                if !http_match.methods.contains(&request_method)
+                    || !path.starts_with(&http_match.path)
+                {
+                    continue;
+                }
+                http_match.path.len()
+            } else if let Some(grpc_match) = &route.match_rules.grpc {
+                if request_method != HttpMethod::Post || path != grpc_match.path() {
+                    continue;
+                }
+                path.len()
+            } else {
+                continue;
+            };
+            let priority = route.priority;
+
+            // Select by longest path prefix, then highest priority.
+            if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
+                best_path_len = path_len;
+                best_priority = priority;
+                best = Some(route);
+            }
+        }
+
+        best.ok_or(not_found)
+    }
+
+    async fn update(&self, route: Route) -> Result<Route, RepositoryError> {
+        let id = route.id;
+        let tenant_id = route.tenant_id;
+        let upstream_id = route.upstream_id;
+        let plugin_ids = custom_plugin_ids(route.plugins.as_ref());
+        let am = route_to_active_model(route, None, OffsetDateTime::now_utc())?;
+
+        let model = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = AccessScope::for_tenant(tenant_id);
+                    let model = secure_update_with_scope::<RouteEntity>(am, &scope, id, tx)
+                        .await
+                        .map_err(|e| {
+                            abort(match e {
+                                ScopeError::Denied(_) => RepositoryError::NotFound {
+                                    entity: "route",
+                                    id,
+                                },
+                                e => db_err(e),
+                            })
+                        })?;
+                    replace_plugin_refs(tx, tenant_id, upstream_id, Some(id), plugin_ids)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    Ok(model)
+                })
+            })
+            .await
+            .map_err(tx_err)?;
+        route_from_model(model)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = RouteEntity::delete_many()
+            .filter(Condition::all().add(Column::Id.eq(id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(db_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(RepositoryError::NotFound {
+                entity: "route",
+                id,
+            });
+        }
+        Ok(())
+    }
+
+    async fn delete_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<u64, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = RouteEntity::delete_many()
+            .filter(Condition::all().add(Column::UpstreamId.eq(upstream_id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(db_err)?;
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{
+        Endpoint, GrpcMatch, HttpMatch, MatchRules, PathSuffixMode, Scheme, Server, Upstream,
+    };
+    use crate::domain::repo::UpstreamRepository;
+    use crate::domain::test_support::sqlite_test_db;
+    use crate::infra::storage::SeaOrmUpstreamRepo;
+
+    use super::*;
+
+    /// Insert a parent upstream row so the route foreign key is satisfied.
+    async fn seed_upstream(db: &Arc<DBProvider<DbError>>, tenant_id: Uuid) -> Uuid {
+        let upstream = Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: format!("svc-{}", Uuid::new_v4().simple()),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
+            tags: vec![],
+        };
+        SeaOrmUpstreamRepo::new(db.clone())
+            .create(upstream)
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn make_route(
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        methods: Vec<HttpMethod>,
+        path: &str,
+        priority: i32,
+    ) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            tenant_id,
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods,
+                    path: path.into(),
+                    query_allowlist: vec![],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
+            tags: vec![],
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn find_matching_longest_prefix_then_priority() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        repo.create(make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1",
+            50,
+        ))
+        .await
+        .unwrap();
+        repo.create(make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1/chat",
+            0,
+        ))
+        .await
+        .unwrap();
+        let high = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 10);
+        repo.create(high.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "post", "/v1/chat/completions")
+            .await
+            .unwrap();
+        assert_eq!(matched, high);
+    }
+
+    #[tokio::test]
+    async fn find_matching_skips_disabled_and_foreign_routes() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        let mut disabled = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 0);
+        disabled.enabled = false;
+        repo.create(disabled).await.unwrap();
+
+        let result = repo
+            .find_matching(tenant, upstream, "GET", "/v1/models")
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+
+        let result = repo
+            .find_matching(Uuid::new_v4(), upstream, "GET", "/v1/models")
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn find_matching_grpc_route_and_persists_transcoding() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        let mut grpc = make_route(tenant, upstream, vec![], "", 0);
+        grpc.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "example.v1.UserService".into(),
+                method: "GetUser".into(),
+            }),
+        };
+        grpc.grpc_transcoding = Some(crate::domain::model::GrpcTranscodingConfig {
+            descriptor_set: vec![1, 2, 3],
+        });
+        repo.create(grpc.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/example.v1.UserService/GetUser")
+            .await
+            .unwrap();
+        assert_eq!(matched, grpc);
+
+        let result = repo
+            .find_matching(
+                tenant,
+                upstream,
+                "POST",
+                "/example.v1.UserService/GetUser/x",
+            )
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn update_and_delete_are_tenant_scoped() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        let mut route = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 0);
+        repo.create(route.clone()).await.unwrap();
+
+        route.priority = 5;
+        assert_eq!(repo.update(route.clone()).await.unwrap().priority, 5);
+
+        let foreign = Route {
+            tenant_id: Uuid::new_v4(),
+            ..route.clone()
+        };
+        assert!(matches!(
+            repo.update(foreign.clone()).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.delete(foreign.tenant_id, route.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+
+        repo.delete(tenant, route.id).await.unwrap();
+        assert!(repo.get_by_id(tenant, route.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn deleting_upstream_cascades_to_routes() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        let route = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 0);
+        repo.create(route.clone()).await.unwrap();
+
+        SeaOrmUpstreamRepo::new(db.clone())
+            .delete(tenant, upstream)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            repo.get_by_id(tenant, route.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn delete_by_upstream_counts_rows() {
+        let db = sqlite_test_db().await;
+        let repo = SeaOrmRouteRepo::new(db.clone());
+        let tenant = Uuid::new_v4();
+        let upstream = seed_upstream(&db, tenant).await;
+
+        for path in ["/a", "/b", "/c"] {
+            repo.create(make_route(tenant, upstream, vec![HttpMethod::Get], path, 0))
+                .await
+                .unwrap();
+        }
+
+        let listed = repo
+            .list_by_upstream(tenant, upstream, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 3);
+
+        assert_eq!(repo.delete_by_upstream(tenant, upstream).await.unwrap(), 3);
+        assert_eq!(repo.delete_by_upstream(tenant, upstream).await.unwrap(), 0);
+    }
+}
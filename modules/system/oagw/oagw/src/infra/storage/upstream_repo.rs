@@ -1,9 +1,14 @@
+use std::sync::Arc;
+
+use crate::domain::gts_helpers::custom_plugin_ids;
 use crate::domain::model::{ListQuery, Upstream};
 use crate::domain::repo::{RepositoryError, UpstreamRepository};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
 use uuid::Uuid;
 
+use super::plugin_repo::{InMemoryPluginRefs, ReferrerKind};
+
 /// In-memory upstream repository backed by `DashMap`.
 #[domain_model]
 pub struct InMemoryUpstreamRepo {
@@ -11,6 +16,8 @@ pub struct InMemoryUpstreamRepo {
     store: DashMap<Uuid, Upstream>,
     /// Alias index: (tenant_id, alias) -> upstream_id.
     alias_index: DashMap<(Uuid, String), Uuid>,
+    /// Custom plugins referenced by each upstream.
+    plugin_refs: Arc<InMemoryPluginRefs>,
 }
 
 impl InMemoryUpstreamRepo {
@@ -19,8 +26,17 @@ impl InMemoryUpstreamRepo {
         Self {
             store: DashMap::new(),
             alias_index: DashMap::new(),
+            plugin_refs: Arc::default(),
         }
     }
+
+    /// Record plugin references in `plugin_refs`, shared with the plugin
+    /// repository.
+    #[must_use]
+    pub fn with_plugin_refs(mut self, plugin_refs: Arc<InMemoryPluginRefs>) -> Self {
+        self.plugin_refs = plugin_refs;
+        self
+    }
 }
 
 impl Default for InMemoryUpstreamRepo {
@@ -47,7 +63,14 @@ impl UpstreamRepository for InMemoryUpstreamRepo {
             }
         }
 
+        let mut refs = self.plugin_refs.lock();
         self.store.insert(upstream.id, upstream.clone());
+        refs.set(
+            upstream.tenant_id,
+            ReferrerKind::Upstream,
+            upstream.id,
+            custom_plugin_ids(upstream.plugins.as_ref()),
+        );
         Ok(upstream)
     }
 
@@ -123,14 +146,22 @@ impl UpstreamRepository for InMemoryUpstreamRepo {
                     )));
                 }
                 dashmap::mapref::entry::Entry::Vacant(entry) => {
-                    // Remove old alias, then insert new — while holding the entry lock.
-                    self.alias_index.remove(&(tenant_id, old.alias.clone()));
                     entry.insert(id);
                 }
             }
+            // Release the old alias only after the entry lock is dropped:
+            // both keys may live in the same shard.
+            self.alias_index.remove(&(tenant_id, old.alias.clone()));
         }
 
+        let mut refs = self.plugin_refs.lock();
         self.store.insert(id, upstream.clone());
+        refs.set(
+            tenant_id,
+            ReferrerKind::Upstream,
+            id,
+            custom_plugin_ids(upstream.plugins.as_ref()),
+        );
         Ok(upstream)
     }
 
@@ -151,6 +182,7 @@ impl UpstreamRepository for InMemoryUpstreamRepo {
         }
 
         self.alias_index.remove(&(tenant_id, upstream.alias));
+        self.plugin_refs.lock().remove(id);
         Ok(())
     }
 }
@@ -171,6 +203,7 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
             },
             protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
@@ -179,6 +212,8 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
             tags: vec![],
         }
     }
@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use modkit_db::secure::{
+    ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, QueryFilter};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::gts_helpers::custom_plugin_ids;
+use crate::domain::model::{ListQuery, Upstream};
+use crate::domain::repo::{RepositoryError, UpstreamRepository};
+
+use super::db::{abort, db_err, is_unique_violation, tx_err};
+use super::entity::upstream::{Column, Entity as UpstreamEntity};
+use super::mapper::{upstream_from_model, upstream_to_active_model};
+use super::plugin_sea_repo::replace_plugin_refs;
+
+/// `SeaORM`-backed upstream repository.
+///
+/// Every query is tenant-scoped through `AccessScope`; alias uniqueness per
+/// tenant is enforced by the `(tenant_id, alias)` unique constraint. Writes
+/// update the upstream's `oagw_plugin_ref` rows in the same transaction; the
+/// rows are removed with the upstream by `ON DELETE CASCADE`.
+pub struct SeaOrmUpstreamRepo {
+    db: Arc<DBProvider<DbError>>,
+}
+
+impl SeaOrmUpstreamRepo {
+    #[must_use]
+    pub fn new(db: Arc<DBProvider<DbError>>) -> Self {
+        Self { db }
+    }
+}
+
+fn alias_conflict(alias: &str) -> RepositoryError {
+    RepositoryError::Conflict(format!("alias '{alias}' already exists for tenant"))
+}
+
+#[async_trait::async_trait]
+impl UpstreamRepository for SeaOrmUpstreamRepo {
+    async fn create(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let id = upstream.id;
+        let tenant_id = upstream.tenant_id;
+        let alias = upstream.alias.clone();
+        let plugin_ids = custom_plugin_ids(upstream.plugins.as_ref());
+        let now = OffsetDateTime::now_utc();
+        let am = upstream_to_active_model(upstream, Some(now), now)?;
+
+        let model = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = AccessScope::for_tenant(tenant_id);
+                    let model = secure_insert::<UpstreamEntity>(am, &scope, tx)
+                        .await
+                        .map_err(|e| {
+                            if is_unique_violation(&e) {
+                                abort(alias_conflict(&alias))
+                            } else {
+                                abort(db_err(e))
+                            }
+                        })?;
+                    replace_plugin_refs(tx, tenant_id, id, None, plugin_ids)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    Ok(model)
+                })
+            })
+            .await
+            .map_err(tx_err)?;
+        upstream_from_model(model)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let model = UpstreamEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .and_id(id)
+            .map_err(db_err)?
+            .one(&conn)
+            .await
+            .map_err(db_err)?
+            .ok_or(RepositoryError::NotFound {
+                entity: "upstream",
+                id,
+            })?;
+        upstream_from_model(model)
+    }
+
+    async fn get_by_alias(
+        &self,
+        tenant_id: Uuid,
+        alias: &str,
+    ) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let model = UpstreamEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Column::Alias.eq(alias)))
+            .one(&conn)
+            .await
+            .map_err(db_err)?
+            .ok_or(RepositoryError::NotFound {
+                entity: "upstream",
+                id: Uuid::nil(),
+            })?;
+        upstream_from_model(model)
+    }
+
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Upstream>, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        UpstreamEntity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(upstream_from_model)
+            .collect()
+    }
+
+    async fn update(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let id = upstream.id;
+        let tenant_id = upstream.tenant_id;
+        let alias = upstream.alias.clone();
+        let plugin_ids = custom_plugin_ids(upstream.plugins.as_ref());
+        let am = upstream_to_active_model(upstream, None, OffsetDateTime::now_utc())?;
+
+        let model = self
+            .db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = AccessScope::for_tenant(tenant_id);
+                    let model = secure_update_with_scope::<UpstreamEntity>(am, &scope, id, tx)
+                        .await
+                        .map_err(|e| {
+                            abort(match e {
+                                ScopeError::Denied(_) => RepositoryError::NotFound {
+                                    entity: "upstream",
+                                    id,
+                                },
+                                e if is_unique_violation(&e) => alias_conflict(&alias),
+                                e => db_err(e),
+                            })
+                        })?;
+                    replace_plugin_refs(tx, tenant_id, id, None, plugin_ids)
+                        .await
+                        .map_err(|e| abort(db_err(e)))?;
+                    Ok(model)
+                })
+            })
+            .await
+            .map_err(tx_err)?;
+        upstream_from_model(model)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = UpstreamEntity::delete_many()
+            .filter(Condition::all().add(Column::Id.eq(id)))
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await
+            .map_err(db_err)?;
+
+        if result.rows_affected == 0 {
+            return Err(RepositoryError::NotFound {
+                entity: "upstream",
+                id,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{Endpoint, Scheme, Server};
+    use crate::domain::test_support::sqlite_test_db;
+
+    use super::*;
+
+    fn make_upstream(tenant_id: Uuid, alias: &str) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: alias.into(),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
+            tags: vec!["ai".into()],
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip() {
+        let repo = SeaOrmUpstreamRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+        let u = make_upstream(tenant, "openai");
+
+        let created = repo.create(u.clone()).await.unwrap();
+        assert_eq!(created, u);
+
+        assert_eq!(repo.get_by_id(tenant, u.id).await.unwrap(), u);
+        assert_eq!(repo.get_by_alias(tenant, "openai").await.unwrap(), u);
+    }
+
+    #[tokio::test]
+    async fn alias_uniqueness_is_enforced_by_database() {
+        let repo = SeaOrmUpstreamRepo::new(sqlite_test_db().await);
+        let t1 = Uuid::new_v4();
+        let t2 = Uuid::new_v4();
+
+        repo.create(make_upstream(t1, "openai")).await.unwrap();
+        let err = repo.create(make_upstream(t1, "openai")).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+
+        // Same alias under another tenant is fine.
+        repo.create(make_upstream(t2, "openai")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_to_duplicate_alias_returns_conflict() {
+        let repo = SeaOrmUpstreamRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+
+        repo.create(make_upstream(tenant, "openai")).await.unwrap();
+        let mut u2 = make_upstream(tenant, "anthropic");
+        repo.create(u2.clone()).await.unwrap();
+
+        u2.alias = "openai".into();
+        let err = repo.update(u2.clone()).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+
+        let fetched = repo.get_by_id(tenant, u2.id).await.unwrap();
+        assert_eq!(fetched.alias, "anthropic");
+    }
+
+    #[tokio::test]
+    async fn cross_tenant_access_is_not_found() {
+        let repo = SeaOrmUpstreamRepo::new(sqlite_test_db().await);
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let u = make_upstream(owner, "openai");
+        repo.create(u.clone()).await.unwrap();
+
+        assert!(matches!(
+            repo.get_by_id(other, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.update(Upstream {
+                tenant_id: other,
+                ..u.clone()
+            })
+            .await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.delete(other, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+
+        // Still present for the owner.
+        repo.delete(owner, u.id).await.unwrap();
+        assert!(repo.get_by_id(owner, u.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_is_tenant_scoped_and_paginated() {
+        let repo = SeaOrmUpstreamRepo::new(sqlite_test_db().await);
+        let tenant = Uuid::new_v4();
+        for i in 0..5 {
+            repo.create(make_upstream(tenant, &format!("svc-{i}")))
+                .await
+                .unwrap();
+        }
+        repo.create(make_upstream(Uuid::new_v4(), "foreign"))
+            .await
+            .unwrap();
+
+        let all = repo.list(tenant, &ListQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 5);
+        assert!(all.windows(2).all(|w| w[0].id < w[1].id));
+
+        let page = repo
+            .list(tenant, &ListQuery { top: 2, skip: 3 })
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].id, all[3].id);
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use modkit::client_hub::ClientHub;
+use modkit_security::SecurityContext;
+use tenant_resolver_sdk::{GetAncestorsOptions, TenantResolverClient, TenantResolverError};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::hierarchy::TenantHierarchy;
+
+/// Tenant ancestry backed by the tenant-resolver module.
+///
+/// The client is looked up in the hub on every call: deployments without a
+/// tenant resolver treat every tenant as a root, so only its own upstreams
+/// are visible.
+pub struct TenantResolverHierarchy {
+    hub: Arc<ClientHub>,
+}
+
+impl TenantResolverHierarchy {
+    #[must_use]
+    pub fn new(hub: Arc<ClientHub>) -> Self {
+        Self { hub }
+    }
+}
+
+#[async_trait::async_trait]
+impl TenantHierarchy for TenantResolverHierarchy {
+    async fn ancestors(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let Ok(resolver) = self.hub.get::<dyn TenantResolverClient>() else {
+            return Ok(Vec::new());
+        };
+        match resolver
+            .get_ancestors(ctx, tenant_id, &GetAncestorsOptions::default())
+            .await
+        {
+            Ok(resp) => Ok(resp.ancestors.into_iter().map(|t| t.id).collect()),
+            Err(TenantResolverError::TenantNotFound { .. }) => Ok(Vec::new()),
+            Err(e) => Err(DomainError::internal(format!(
+                "failed to resolve ancestors of tenant {tenant_id}: {e}"
+            ))),
+        }
+    }
+}
+
+/// In-memory tenant tree for tests.
+#[cfg(any(test, feature = "test-utils"))]
+#[modkit_macros::domain_model]
+pub struct InMemoryTenantHierarchy {
+    parents: dashmap::DashMap<Uuid, Uuid>,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl InMemoryTenantHierarchy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            parents: dashmap::DashMap::new(),
+        }
+    }
+
+    /// Make `parent` the direct parent of `child`.
+    pub fn set_parent(&self, child: Uuid, parent: Uuid) {
+        self.parents.insert(child, parent);
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl Default for InMemoryTenantHierarchy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+#[async_trait::async_trait]
+impl TenantHierarchy for InMemoryTenantHierarchy {
+    async fn ancestors(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let mut ancestors = Vec::new();
+        let mut current = tenant_id;
+        while let Some(parent) = self.parents.get(&current).map(|p| *p) {
+            if parent == tenant_id || ancestors.contains(&parent) {
+                return Err(DomainError::internal(format!(
+                    "cycle in tenant hierarchy at {parent}"
+                )));
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        Ok(ancestors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn in_memory_ancestors_are_ordered_parent_to_root() {
+        let (root, partner, customer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let tree = InMemoryTenantHierarchy::new();
+        tree.set_parent(partner, root);
+        tree.set_parent(customer, partner);
+        let ctx = SecurityContext::anonymous();
+
+        assert_eq!(
+            tree.ancestors(&ctx, customer).await.unwrap(),
+            vec![partner, root]
+        );
+        assert!(tree.ancestors(&ctx, root).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resolver_hierarchy_without_client_has_no_ancestors() {
+        let hierarchy = TenantResolverHierarchy::new(Arc::new(ClientHub::new()));
+        let ancestors = hierarchy
+            .ancestors(&SecurityContext::anonymous(), Uuid::new_v4())
+            .await
+            .unwrap();
+        assert!(ancestors.is_empty());
+    }
+}
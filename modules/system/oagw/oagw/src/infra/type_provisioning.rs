@@ -34,6 +34,10 @@ fn default_port() -> u16 {
     443
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 fn default_cost() -> u32 {
     1
 }
@@ -56,6 +60,8 @@ struct Endpoint {
     host: String,
     #[serde(default = "default_port")]
     port: u16,
+    #[serde(default = "default_weight")]
+    weight: u32,
 }
 
 #[derive(Deserialize)]
@@ -129,6 +135,8 @@ struct PluginsConfig {
     sharing: SharingMode,
     #[serde(default)]
     items: Vec<String>,
+    #[serde(default)]
+    config: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Deserialize, Default)]
@@ -196,6 +204,165 @@ struct RateLimitConfig {
     strategy: RateLimitStrategy,
     #[serde(default = "default_cost")]
     cost: u32,
+    #[serde(default)]
+    queue: Option<QueueConfig>,
+    #[serde(default)]
+    degrade: Option<DegradeConfig>,
+}
+
+#[derive(Deserialize)]
+struct QueueConfig {
+    max_depth: u32,
+    #[serde(with = "modkit_utils::humantime_serde")]
+    timeout: std::time::Duration,
+}
+
+#[derive(Deserialize)]
+struct DegradeConfig {
+    #[serde(default)]
+    fallback_upstream_id: Option<Uuid>,
+    #[serde(default)]
+    fallback_response: Option<FallbackResponse>,
+}
+
+#[derive(Deserialize)]
+struct FallbackResponse {
+    status: u16,
+    #[serde(default)]
+    body: String,
+}
+
+/// Circuit breaker settings; omitted fields fall back to the domain defaults.
+#[derive(Deserialize)]
+#[serde(default)]
+struct CircuitBreakerConfig {
+    enabled: bool,
+    failure_threshold: u32,
+    failure_rate_threshold: Option<u8>,
+    minimum_requests: u32,
+    window_seconds: u32,
+    success_threshold: u32,
+    timeout_seconds: u32,
+    half_open_max_requests: u32,
+    failure_conditions: FailureConditions,
+    scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        let d = domain::CircuitBreakerConfig::default();
+        Self {
+            enabled: d.enabled,
+            failure_threshold: d.failure_threshold,
+            failure_rate_threshold: d.failure_rate_threshold,
+            minimum_requests: d.minimum_requests,
+            window_seconds: d.window_seconds,
+            success_threshold: d.success_threshold,
+            timeout_seconds: d.timeout_seconds,
+            half_open_max_requests: d.half_open_max_requests,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct FailureConditions {
+    status_codes: Vec<u16>,
+    timeout: bool,
+    connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        let d = domain::FailureConditions::default();
+        Self {
+            status_codes: d.status_codes,
+            timeout: d.timeout,
+            connection_error: d.connection_error,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum CircuitBreakerScope {
+    Global,
+    #[default]
+    PerEndpoint,
+}
+
+/// Load balancing settings; omitted fields fall back to the domain defaults.
+#[derive(Deserialize)]
+#[serde(default)]
+struct LoadBalancingConfig {
+    strategy: LoadBalancingStrategy,
+    outlier_detection: Option<OutlierDetectionConfig>,
+    health_check: Option<HealthCheckConfig>,
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: LoadBalancingStrategy::default(),
+            outlier_detection: Some(OutlierDetectionConfig::default()),
+            health_check: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum LoadBalancingStrategy {
+    #[default]
+    RoundRobin,
+    Weighted,
+    LeastInFlight,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct OutlierDetectionConfig {
+    consecutive_failures: u32,
+    base_ejection_seconds: u32,
+    max_ejection_percent: u8,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        let d = domain::OutlierDetectionConfig::default();
+        Self {
+            consecutive_failures: d.consecutive_failures,
+            base_ejection_seconds: d.base_ejection_seconds,
+            max_ejection_percent: d.max_ejection_percent,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct HealthCheckConfig {
+    path: String,
+    interval_seconds: u32,
+    timeout_seconds: u32,
+    healthy_threshold: u32,
+    unhealthy_threshold: u32,
+    expected_statuses: Vec<u16>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        let d = domain::HealthCheckConfig::default();
+        Self {
+            path: d.path,
+            interval_seconds: d.interval_seconds,
+            timeout_seconds: d.timeout_seconds,
+            healthy_threshold: d.healthy_threshold,
+            unhealthy_threshold: d.unhealthy_threshold,
+            expected_statuses: d.expected_statuses,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -240,6 +407,28 @@ struct MatchRules {
     grpc: Option<GrpcMatch>,
 }
 
+#[derive(Deserialize)]
+struct GrpcTranscodingConfig {
+    #[serde(with = "crate::infra::serde_base64")]
+    descriptor_set: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct ResponseCacheConfig {
+    #[serde(with = "modkit_utils::humantime_serde")]
+    ttl: std::time::Duration,
+    #[serde(default)]
+    vary: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct UsageExtractionConfig {
+    #[serde(default)]
+    input_tokens: Option<String>,
+    #[serde(default)]
+    output_tokens: Option<String>,
+}
+
 /// Intermediate serde struct for deserializing upstream GTS entity content.
 #[derive(Deserialize)]
 struct UpstreamPayload {
@@ -257,6 +446,10 @@ struct UpstreamPayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
+    load_balancing: Option<LoadBalancingConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default = "default_true")]
     enabled: bool,
@@ -274,6 +467,12 @@ struct RoutePayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    grpc_transcoding: Option<GrpcTranscodingConfig>,
+    #[serde(default)]
+    response_cache: Option<ResponseCacheConfig>,
+    #[serde(default)]
+    usage_extraction: Option<UsageExtractionConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default)]
     priority: i32,
@@ -303,6 +502,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -445,6 +645,68 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             scope: v.scope.into(),
             strategy: v.strategy.into(),
             cost: v.cost,
+            queue: v.queue.map(|q| domain::QueueConfig {
+                max_depth: q.max_depth,
+                timeout: q.timeout,
+            }),
+            degrade: v.degrade.map(|d| domain::DegradeConfig {
+                fallback_upstream_id: d.fallback_upstream_id,
+                fallback_response: d.fallback_response.map(|r| domain::FallbackResponse {
+                    status: r.status,
+                    body: r.body,
+                }),
+            }),
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate_threshold: v.failure_rate_threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<LoadBalancingConfig> for domain::LoadBalancingConfig {
+    fn from(v: LoadBalancingConfig) -> Self {
+        Self {
+            strategy: match v.strategy {
+                LoadBalancingStrategy::RoundRobin => domain::LoadBalancingStrategy::RoundRobin,
+                LoadBalancingStrategy::Weighted => domain::LoadBalancingStrategy::Weighted,
+                LoadBalancingStrategy::LeastInFlight => {
+                    domain::LoadBalancingStrategy::LeastInFlight
+                }
+            },
+            outlier_detection: v.outlier_detection.map(|o| domain::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                base_ejection_seconds: o.base_ejection_seconds,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+            health_check: v.health_check.map(|h| domain::HealthCheckConfig {
+                path: h.path,
+                interval_seconds: h.interval_seconds,
+                timeout_seconds: h.timeout_seconds,
+                healthy_threshold: h.healthy_threshold,
+                unhealthy_threshold: h.unhealthy_threshold,
+                expected_statuses: h.expected_statuses,
+            }),
         }
     }
 }
@@ -454,6 +716,7 @@ impl From<PluginsConfig> for domain::PluginsConfig {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -508,6 +771,32 @@ impl From<MatchRules> for domain::MatchRules {
     }
 }
 
+impl From<GrpcTranscodingConfig> for domain::GrpcTranscodingConfig {
+    fn from(v: GrpcTranscodingConfig) -> Self {
+        Self {
+            descriptor_set: v.descriptor_set,
+        }
+    }
+}
+
+impl From<ResponseCacheConfig> for domain::ResponseCacheConfig {
+    fn from(v: ResponseCacheConfig) -> Self {
+        Self {
+            ttl: v.ttl,
+            vary: v.vary,
+        }
+    }
+}
+
+impl From<UsageExtractionConfig> for domain::UsageExtractionConfig {
+    fn from(v: UsageExtractionConfig) -> Self {
+        Self {
+            input_tokens: v.input_tokens,
+            output_tokens: v.output_tokens,
+        }
+    }
+}
+
 impl From<UpstreamPayload> for ProvisionedUpstream {
     fn from(p: UpstreamPayload) -> Self {
         Self {
@@ -520,6 +809,8 @@ impl From<UpstreamPayload> for ProvisionedUpstream {
                 headers: p.headers.map(Into::into),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                circuit_breaker: p.circuit_breaker.map(Into::into),
+                load_balancing: p.load_balancing.map(Into::into),
                 tags: p.tags,
                 enabled: p.enabled,
             },
@@ -536,6 +827,9 @@ impl From<RoutePayload> for ProvisionedRoute {
                 match_rules: p.match_rules.into(),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                grpc_transcoding: p.grpc_transcoding.map(Into::into),
+                response_cache: p.response_cache.map(Into::into),
+                usage_extraction: p.usage_extraction.map(Into::into),
                 tags: p.tags,
                 priority: p.priority,
                 enabled: p.enabled,
@@ -838,7 +1132,7 @@ mod tests {
             "server": {
                 "endpoints": [
                     {"scheme": "https", "host": "api.openai.com", "port": 443},
-                    {"scheme": "http", "host": "fallback.local", "port": 8080}
+                    {"scheme": "http", "host": "fallback.local", "port": 8080, "weight": 3}
                 ]
             },
             "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -854,6 +1148,14 @@ mod tests {
                     "passthrough": "all"
                 }
             },
+            "circuit_breaker": {
+                "failure_threshold": 10,
+                "scope": "global"
+            },
+            "load_balancing": {
+                "strategy": "weighted",
+                "health_check": {"path": "/healthz"}
+            },
             "enabled": true,
             "tags": ["prod", "llm"]
         });
@@ -868,6 +1170,8 @@ mod tests {
         assert_eq!(req.server.endpoints[0].host, "api.openai.com");
         assert_eq!(req.server.endpoints[0].port, 443);
         assert_eq!(req.server.endpoints[1].scheme, domain::Scheme::Http);
+        assert_eq!(req.server.endpoints[0].weight, 1);
+        assert_eq!(req.server.endpoints[1].weight, 3);
         assert_eq!(
             req.protocol,
             "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1"
@@ -887,6 +1191,22 @@ mod tests {
         let rr = headers.request.as_ref().unwrap();
         assert_eq!(rr.set.get("x-custom").unwrap(), "value");
         assert_eq!(rr.passthrough, domain::PassthroughMode::All);
+
+        let cb = req.circuit_breaker.as_ref().unwrap();
+        assert_eq!(cb.failure_threshold, 10);
+        assert_eq!(cb.scope, domain::CircuitBreakerScope::Global);
+
+        let lb = req.load_balancing.as_ref().unwrap();
+        assert_eq!(lb.strategy, domain::LoadBalancingStrategy::Weighted);
+        assert_eq!(
+            lb.outlier_detection,
+            Some(domain::OutlierDetectionConfig::default())
+        );
+        let hc = lb.health_check.as_ref().unwrap();
+        assert_eq!(hc.path, "/healthz");
+        assert_eq!(hc.interval_seconds, 10);
+        assert_eq!(cb.timeout_seconds, 30);
+        assert_eq!(cb.failure_conditions.status_codes, vec![500, 502, 503, 504]);
     }
 
     #[test]
@@ -952,6 +1272,27 @@ mod tests {
         assert_eq!(rl.cost, 2);
     }
 
+    #[test]
+    fn deserialize_grpc_route_with_transcoding() {
+        let json = serde_json::json!({
+            "tenant_id": Uuid::new_v4(),
+            "upstream_id": Uuid::new_v4(),
+            "match": {
+                "grpc": {"service": "example.v1.UserService", "method": "GetUser"}
+            },
+            "grpc_transcoding": {"descriptor_set": "AQID"}
+        });
+
+        let payload: RoutePayload = serde_json::from_value(json).unwrap();
+        let provisioned: ProvisionedRoute = payload.into();
+        let req = &provisioned.request;
+        assert_eq!(req.match_rules.grpc.as_ref().unwrap().method, "GetUser");
+        assert_eq!(
+            req.grpc_transcoding.as_ref().unwrap().descriptor_set,
+            vec![1, 2, 3]
+        );
+    }
+
     #[test]
     fn deserialize_missing_field_returns_error() {
         // Missing required "server" field.
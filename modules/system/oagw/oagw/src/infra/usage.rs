@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::domain::usage::{UsageQuery, UsageRecord, UsageReport, UsageSink, UsageTotals};
+
+/// Default time usage stays queryable.
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+const BUCKET_SECS: i64 = 60;
+
+/// Aggregation bucket: one subject on one route during one minute. The
+/// minute comes first so expired buckets form a prefix of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct BucketKey {
+    minute: i64,
+    tenant_id: Uuid,
+    upstream_id: Uuid,
+    route_id: Uuid,
+    subject_id: Uuid,
+}
+
+impl BucketKey {
+    /// Smallest key of `minute`.
+    fn start_of(minute: i64) -> Self {
+        Self {
+            minute,
+            tenant_id: Uuid::nil(),
+            upstream_id: Uuid::nil(),
+            route_id: Uuid::nil(),
+            subject_id: Uuid::nil(),
+        }
+    }
+}
+
+/// In-process usage aggregate: per-minute totals for each tenant, route and
+/// subject, kept for a bounded retention and lost on restart. Deployments
+/// that bill from usage should add a durable sink alongside it.
+pub struct InMemoryUsageAggregator {
+    retention_minutes: i64,
+    buckets: Mutex<BTreeMap<BucketKey, UsageTotals>>,
+}
+
+impl InMemoryUsageAggregator {
+    #[must_use]
+    pub fn new(retention: Duration) -> Self {
+        let minutes = retention.as_secs() / BUCKET_SECS.unsigned_abs();
+        Self {
+            retention_minutes: i64::try_from(minutes).unwrap_or(i64::MAX).max(1),
+            buckets: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn buckets(&self) -> MutexGuard<'_, BTreeMap<BucketKey, UsageTotals>> {
+        self.buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Default for InMemoryUsageAggregator {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETENTION)
+    }
+}
+
+impl UsageSink for InMemoryUsageAggregator {
+    fn record(&self, record: &UsageRecord) {
+        let minute = record.completed_at.unix_timestamp().div_euclid(BUCKET_SECS);
+        let key = BucketKey {
+            minute,
+            tenant_id: record.tenant_id,
+            upstream_id: record.upstream_id,
+            route_id: record.route_id,
+            subject_id: record.subject_id,
+        };
+        let mut buckets = self.buckets();
+        buckets
+            .entry(key)
+            .or_insert_with(|| UsageTotals {
+                upstream_id: record.upstream_id,
+                route_id: record.route_id,
+                subject_id: Some(record.subject_id),
+                ..UsageTotals::default()
+            })
+            .add(record);
+
+        let horizon = minute.saturating_sub(self.retention_minutes);
+        if buckets
+            .first_key_value()
+            .is_some_and(|(k, _)| k.minute <= horizon)
+        {
+            *buckets = buckets.split_off(&BucketKey::start_of(horizon + 1));
+        }
+    }
+}
+
+impl UsageReport for InMemoryUsageAggregator {
+    /// Minutes overlapping `[from, to)` are included in full.
+    fn totals(&self, tenant_id: Uuid, query: &UsageQuery) -> Vec<UsageTotals> {
+        let from = query
+            .from
+            .map_or(i64::MIN, |t| t.unix_timestamp().div_euclid(BUCKET_SECS));
+        let to = query.to.map_or(i64::MAX, |t| {
+            let secs = t.unix_timestamp();
+            let minute = secs.div_euclid(BUCKET_SECS);
+            if secs.rem_euclid(BUCKET_SECS) == 0 && t.nanosecond() == 0 {
+                minute
+            } else {
+                minute.saturating_add(1)
+            }
+        });
+        if from >= to {
+            return Vec::new();
+        }
+
+        let mut grouped: BTreeMap<(Uuid, Uuid, Option<Uuid>), UsageTotals> = BTreeMap::new();
+        let buckets = self.buckets();
+        for (key, totals) in buckets.range(BucketKey::start_of(from)..BucketKey::start_of(to)) {
+            if key.tenant_id != tenant_id
+                || query.upstream_id.is_some_and(|id| id != key.upstream_id)
+                || query.route_id.is_some_and(|id| id != key.route_id)
+                || query.subject_id.is_some_and(|id| id != key.subject_id)
+            {
+                continue;
+            }
+            let subject_id = query.group_by_subject.then_some(key.subject_id);
+            grouped
+                .entry((key.upstream_id, key.route_id, subject_id))
+                .or_insert_with(|| UsageTotals {
+                    upstream_id: key.upstream_id,
+                    route_id: key.route_id,
+                    subject_id,
+                    ..UsageTotals::default()
+                })
+                .merge(totals);
+        }
+        grouped.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::*;
+
+    fn record(tenant_id: Uuid, subject_id: Uuid, route_id: Uuid, at: i64) -> UsageRecord {
+        UsageRecord {
+            tenant_id,
+            subject_id,
+            upstream_id: Uuid::nil(),
+            route_id,
+            status: 200,
+            latency: Duration::from_millis(10),
+            request_bytes: 100,
+            response_bytes: 1000,
+            input_tokens: Some(5),
+            output_tokens: None,
+            cache_hit: false,
+            degraded: false,
+            completed_at: OffsetDateTime::from_unix_timestamp(at).unwrap(),
+        }
+    }
+
+    fn at(secs: i64) -> Option<OffsetDateTime> {
+        Some(OffsetDateTime::from_unix_timestamp(secs).unwrap())
+    }
+
+    #[test]
+    fn totals_are_tenant_scoped_and_grouped() {
+        let agg = InMemoryUsageAggregator::default();
+        let tenant = Uuid::new_v4();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let route = Uuid::new_v4();
+        agg.record(&record(tenant, alice, route, 60_000));
+        agg.record(&record(tenant, bob, route, 60_030));
+        agg.record(&UsageRecord {
+            status: 502,
+            ..record(tenant, alice, route, 60_090)
+        });
+        agg.record(&record(Uuid::new_v4(), alice, route, 60_000));
+
+        let totals = agg.totals(tenant, &UsageQuery::default());
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].route_id, route);
+        assert_eq!(totals[0].subject_id, None);
+        assert_eq!(totals[0].requests, 3);
+        assert_eq!(totals[0].errors, 1);
+        assert_eq!(totals[0].response_bytes, 3000);
+        assert_eq!(totals[0].input_tokens, 15);
+        assert_eq!(totals[0].total_latency, Duration::from_millis(30));
+
+        let per_subject = agg.totals(
+            tenant,
+            &UsageQuery {
+                group_by_subject: true,
+                ..UsageQuery::default()
+            },
+        );
+        assert_eq!(per_subject.len(), 2);
+        let alice_totals = per_subject
+            .iter()
+            .find(|t| t.subject_id == Some(alice))
+            .unwrap();
+        assert_eq!(alice_totals.requests, 2);
+
+        let only_bob = agg.totals(
+            tenant,
+            &UsageQuery {
+                subject_id: Some(bob),
+                ..UsageQuery::default()
+            },
+        );
+        assert_eq!(only_bob[0].requests, 1);
+    }
+
+    #[test]
+    fn window_selects_whole_minutes() {
+        let agg = InMemoryUsageAggregator::default();
+        let tenant = Uuid::new_v4();
+        let route = Uuid::new_v4();
+        agg.record(&record(tenant, Uuid::nil(), route, 60_000));
+        agg.record(&record(tenant, Uuid::nil(), route, 60_060));
+        agg.record(&record(tenant, Uuid::nil(), route, 60_120));
+
+        let window = |from, to| {
+            agg.totals(
+                tenant,
+                &UsageQuery {
+                    from: at(from),
+                    to: at(to),
+                    ..UsageQuery::default()
+                },
+            )
+            .first()
+            .map_or(0, |t| t.requests)
+        };
+        assert_eq!(window(60_060, 60_120), 1);
+        assert_eq!(window(60_030, 60_061), 2);
+        assert_eq!(window(60_000, 60_180), 3);
+        assert_eq!(window(60_180, 60_000), 0);
+    }
+
+    #[test]
+    fn buckets_past_retention_are_dropped() {
+        let agg = InMemoryUsageAggregator::new(Duration::from_secs(600));
+        let tenant = Uuid::new_v4();
+        let route = Uuid::new_v4();
+        agg.record(&record(tenant, Uuid::nil(), route, 60_000));
+        agg.record(&record(tenant, Uuid::nil(), route, 60_300));
+        agg.record(&record(tenant, Uuid::nil(), route, 60_660));
+
+        let totals = agg.totals(tenant, &UsageQuery::default());
+        assert_eq!(totals[0].requests, 2);
+    }
+}
@@ -2,9 +2,18 @@ use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use crate::config::OagwConfig;
+use crate::domain::circuit_breaker::CircuitBreakerRegistry;
 use crate::domain::credential::CredentialResolver;
+use crate::domain::error::DomainError;
+use crate::domain::load_balancer::LoadBalancer;
+use crate::domain::model::{
+    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, UpdateRouteRequest,
+    UpdateUpstreamRequest, Upstream,
+};
+use crate::domain::rate_limit::RateLimiter;
 use crate::domain::type_catalog::oagw_gts_entities;
 use crate::domain::type_provisioning::TypeProvisioningService;
+use crate::domain::usage::UsageReport;
 use crate::infra::type_provisioning::TypeProvisioningServiceImpl;
 use async_trait::async_trait;
 use modkit::api::OpenApiRegistry;
@@ -14,19 +23,31 @@ use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
 use tracing::info;
 use types_registry_sdk::{RegisterResult, RegisterSummary, TypesRegistryClient};
+use uuid::Uuid;
 
 use crate::api::rest::routes;
+use crate::domain::plugin::PluginRuntime;
+use crate::domain::repo::{PluginRepository, RouteRepository, UpstreamRepository};
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, ServiceGatewayClientV1Facade,
 };
+use crate::infra::credential_store::CredentialStoreResolver;
+use crate::infra::plugin::{OAuth2TokenCache, SandboxLimits, StarlarkRuntime};
 use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryCredentialResolver, InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::proxy::response_cache::ResponseCache;
+use crate::infra::storage::{
+    InMemoryCredentialResolver, InMemoryPluginRefs, InMemoryPluginRepo, InMemoryRouteRepo,
+    InMemoryUpstreamRepo, SeaOrmPluginRepo, SeaOrmRouteRepo, SeaOrmUpstreamRepo,
+};
+use crate::infra::tenant_hierarchy::TenantResolverHierarchy;
+use crate::infra::usage::InMemoryUsageAggregator;
 
 /// Shared application state injected into all handlers.
 #[derive(Clone)]
 pub struct AppState {
     pub(crate) cp: Arc<dyn ControlPlaneService>,
     pub(crate) dp: Arc<dyn DataPlaneService>,
+    pub(crate) usage: Arc<dyn UsageReport>,
     pub(crate) config: crate::config::RuntimeConfig,
 }
 
@@ -34,7 +55,7 @@ pub struct AppState {
 #[modkit::module(
     name = "oagw",
     deps = ["types-registry"],
-    capabilities = [system, rest]
+    capabilities = [system, rest, db]
 )]
 pub struct OutboundApiGatewayModule {
     state: arc_swap::ArcSwapOption<AppState>,
@@ -52,6 +73,14 @@ impl Default for OutboundApiGatewayModule {
     }
 }
 
+impl modkit::contracts::DatabaseCapability for OutboundApiGatewayModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        info!("Providing OAGW database migrations");
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
 #[async_trait]
 impl Module for OutboundApiGatewayModule {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
@@ -61,25 +90,82 @@ impl Module for OutboundApiGatewayModule {
         info!("OAGW config: proxy_timeout_secs={}", cfg.proxy_timeout_secs);
 
         // -- Control Plane init --
-        let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
-        let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+        let (upstream_repo, route_repo, plugin_repo): (
+            Arc<dyn UpstreamRepository>,
+            Arc<dyn RouteRepository>,
+            Arc<dyn PluginRepository>,
+        ) = if let Some(db) = ctx.db() {
+            let db = Arc::new(db);
+            (
+                Arc::new(SeaOrmUpstreamRepo::new(db.clone())),
+                Arc::new(SeaOrmRouteRepo::new(db.clone())),
+                Arc::new(SeaOrmPluginRepo::new(db)),
+            )
+        } else {
+            tracing::warn!(
+                "No database configured for OAGW; upstreams, routes and plugins are kept in memory only"
+            );
+            let plugin_refs = Arc::new(InMemoryPluginRefs::new());
+            (
+                Arc::new(InMemoryUpstreamRepo::new().with_plugin_refs(plugin_refs.clone())),
+                Arc::new(InMemoryRouteRepo::new().with_plugin_refs(plugin_refs.clone())),
+                Arc::new(InMemoryPluginRepo::new().with_plugin_refs(plugin_refs)),
+            )
+        };
+        let plugin_runtime: Arc<dyn PluginRuntime> =
+            Arc::new(StarlarkRuntime::with_limits(SandboxLimits {
+                max_duration: Duration::from_millis(cfg.plugin_timeout_ms),
+                ..SandboxLimits::default()
+            }));
+        let response_cache = Arc::new(ResponseCache::new(
+            cfg.response_cache_capacity_bytes,
+            cfg.response_cache_max_entry_bytes,
+        ));
+        let circuit_breakers = Arc::new(CircuitBreakerRegistry::new());
+        let load_balancer = Arc::new(LoadBalancer::new());
+        let rate_limiter = Arc::new(RateLimiter::new());
+        let oauth2_tokens = Arc::new(OAuth2TokenCache::new());
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(
+            ControlPlaneServiceImpl::new(
+                upstream_repo,
+                route_repo,
+                plugin_repo,
+                plugin_runtime.clone(),
+            )
+            .with_tenant_hierarchy(Arc::new(TenantResolverHierarchy::new(ctx.client_hub())))
+            .with_config_listener(response_cache.clone())
+            .with_config_listener(circuit_breakers.clone())
+            .with_config_listener(load_balancer.clone())
+            .with_config_listener(rate_limiter.clone())
+            .with_config_listener(oauth2_tokens.clone()),
+        );
 
-        let cred_resolver = InMemoryCredentialResolver::new();
+        let seeded = InMemoryCredentialResolver::new();
         for (secret_ref, value) in &cfg.credentials {
             info!("Seeding credential: {secret_ref}");
-            cred_resolver.set(secret_ref.clone(), value.clone());
+            seeded.set(secret_ref.clone(), value.clone());
         }
-        let cred_resolver: Arc<dyn CredentialResolver> = Arc::new(cred_resolver);
+        let cred_resolver: Arc<dyn CredentialResolver> =
+            Arc::new(CredentialStoreResolver::new(ctx.client_hub(), seeded));
 
         ctx.client_hub()
             .register::<dyn CredentialResolver>(cred_resolver.clone());
 
         // -- Data Plane init --
+        let usage = Arc::new(InMemoryUsageAggregator::new(Duration::from_secs(
+            cfg.usage_retention_secs,
+        )));
         let dp: Arc<dyn DataPlaneService> = Arc::new(
             DataPlaneServiceImpl::new(cp.clone(), cred_resolver)?
-                .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs)),
+                .with_plugin_runtime(plugin_runtime)
+                .with_response_cache(response_cache)
+                .with_circuit_breakers(circuit_breakers)
+                .with_load_balancer(load_balancer)
+                .with_rate_limiter(rate_limiter)
+                .with_oauth2_tokens(oauth2_tokens)
+                .with_usage_sink(usage.clone())
+                .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs))
+                .with_websocket_idle_timeout(Duration::from_secs(cfg.websocket_idle_timeout_secs)),
         );
 
         // -- Facade (for external SDK consumers) --
@@ -123,6 +209,7 @@ impl Module for OutboundApiGatewayModule {
         let app_state = AppState {
             cp,
             dp,
+            usage,
             config: (&cfg).into(),
         };
 
@@ -144,7 +231,7 @@ impl SystemCapability for OutboundApiGatewayModule {
         let provisioning: Arc<dyn TypeProvisioningService> =
             Arc::new(TypeProvisioningServiceImpl::new(registry));
 
-        // -- Materialize provisioned upstreams and routes into in-memory repos --
+        // -- Materialize provisioned upstreams and routes into the repos --
         let app_state = self
             .state
             .load()
@@ -155,38 +242,30 @@ impl SystemCapability for OutboundApiGatewayModule {
 
         let upstreams = provisioning.list_upstreams().await?;
         for u in &upstreams {
-            let ctx = SecurityContext::builder()
-                .subject_tenant_id(u.tenant_id)
-                .build()?;
-            let created = app_state
-                .cp
-                .create_upstream(&ctx, u.request.clone())
+            let ctx = provisioning_ctx(u.tenant_id)?;
+            let provisioned = provision_upstream(app_state.cp.as_ref(), &ctx, &u.request)
                 .await
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to provision upstream (tenant={}): {e}", u.tenant_id)
                 })?;
             info!(
-                id = %created.id,
+                id = %provisioned.id,
                 tenant_id = %u.tenant_id,
-                alias = %created.alias,
+                alias = %provisioned.alias,
                 "Provisioned upstream from types-registry"
             );
         }
 
         let routes = provisioning.list_routes().await?;
         for r in &routes {
-            let ctx = SecurityContext::builder()
-                .subject_tenant_id(r.tenant_id)
-                .build()?;
-            let created = app_state
-                .cp
-                .create_route(&ctx, r.request.clone())
+            let ctx = provisioning_ctx(r.tenant_id)?;
+            let provisioned = provision_route(app_state.cp.as_ref(), &ctx, &r.request)
                 .await
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to provision route (tenant={}): {e}", r.tenant_id)
                 })?;
             info!(
-                id = %created.id,
+                id = %provisioned.id,
                 tenant_id = %r.tenant_id,
                 "Provisioned route from types-registry"
             );
@@ -206,6 +285,113 @@ impl SystemCapability for OutboundApiGatewayModule {
     }
 }
 
+/// Context provisioned entries are written under: the module itself, acting
+/// for `tenant_id`.
+fn provisioning_ctx(tenant_id: Uuid) -> anyhow::Result<SecurityContext> {
+    Ok(SecurityContext::builder()
+        .subject_id(Uuid::nil())
+        .subject_type("system")
+        .subject_tenant_id(tenant_id)
+        .build()?)
+}
+
+/// Create the provisioned upstream, or update the caller's own upstream
+/// already bound to its alias, e.g. by a previous run on persistent storage.
+/// Fields the definition leaves out keep their stored values.
+async fn provision_upstream(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    req: &CreateUpstreamRequest,
+) -> Result<Upstream, DomainError> {
+    match cp.create_upstream(ctx, req.clone()).await {
+        Err(DomainError::Conflict { detail }) => {
+            let alias = req.alias.clone().unwrap_or_else(|| {
+                req.server
+                    .endpoints
+                    .first()
+                    .map(Endpoint::alias_contribution)
+                    .unwrap_or_default()
+            });
+            // Only the caller's own binding is replaced; ancestors' bindings
+            // of the alias are not looked at.
+            let Some(existing) = find_own_upstream(cp, ctx, &alias).await? else {
+                return Err(DomainError::conflict(detail));
+            };
+            let update = UpdateUpstreamRequest {
+                server: Some(req.server.clone()),
+                protocol: Some(req.protocol.clone()),
+                alias: Some(alias),
+                auth: req.auth.clone(),
+                headers: req.headers.clone(),
+                plugins: req.plugins.clone(),
+                rate_limit: req.rate_limit.clone(),
+                circuit_breaker: req.circuit_breaker.clone(),
+                load_balancing: req.load_balancing.clone(),
+                tags: Some(req.tags.clone()),
+                enabled: Some(req.enabled),
+            };
+            cp.update_upstream(ctx, existing.id, update).await
+        }
+        created => created,
+    }
+}
+
+/// The caller's own upstream bound to `alias`.
+async fn find_own_upstream(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    alias: &str,
+) -> Result<Option<Upstream>, DomainError> {
+    let mut query = ListQuery::default();
+    loop {
+        let page = cp.list_upstreams(ctx, &query).await?;
+        let full = page.len() == query.top as usize;
+        if let Some(found) = page.into_iter().find(|u| u.alias == alias) {
+            return Ok(Some(found));
+        }
+        if !full {
+            return Ok(None);
+        }
+        query.skip += query.top;
+    }
+}
+
+/// Create the provisioned route, or update the route of its upstream with
+/// the same match rules. Fields the definition leaves out keep their stored
+/// values.
+async fn provision_route(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    req: &CreateRouteRequest,
+) -> Result<Route, DomainError> {
+    let mut query = ListQuery::default();
+    loop {
+        let page = cp.list_routes(ctx, req.upstream_id, &query).await?;
+        let full = page.len() == query.top as usize;
+        if let Some(existing) = page
+            .into_iter()
+            .find(|route| route.match_rules == req.match_rules)
+        {
+            let update = UpdateRouteRequest {
+                match_rules: None,
+                plugins: req.plugins.clone(),
+                rate_limit: req.rate_limit.clone(),
+                grpc_transcoding: req.grpc_transcoding.clone(),
+                response_cache: req.response_cache.clone(),
+                usage_extraction: req.usage_extraction.clone(),
+                tags: Some(req.tags.clone()),
+                priority: Some(req.priority),
+                enabled: Some(req.enabled),
+            };
+            return cp.update_route(ctx, existing.id, update).await;
+        }
+        if !full {
+            return cp.create_route(ctx, req.clone()).await;
+        }
+        query.skip += query.top;
+    }
+}
+
 impl RestApiCapability for OutboundApiGatewayModule {
     fn register_rest(
         &self,
@@ -227,3 +413,90 @@ impl RestApiCapability for OutboundApiGatewayModule {
         Ok(router)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{HttpMatch, HttpMethod, MatchRules, PathSuffixMode, Scheme, Server};
+
+    fn make_cp() -> ControlPlaneServiceImpl {
+        ControlPlaneServiceImpl::new(
+            Arc::new(InMemoryUpstreamRepo::new()),
+            Arc::new(InMemoryRouteRepo::new()),
+            Arc::new(InMemoryPluginRepo::new()),
+            Arc::new(StarlarkRuntime::new()),
+        )
+    }
+
+    fn upstream_request(tags: &[&str]) -> CreateUpstreamRequest {
+        CreateUpstreamRequest {
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                    weight: 1,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            alias: None,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            load_balancing: None,
+            tags: tags.iter().map(|t| (*t).to_owned()).collect(),
+            enabled: true,
+        }
+    }
+
+    fn route_request(upstream_id: Uuid, priority: i32) -> CreateRouteRequest {
+        CreateRouteRequest {
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods: vec![HttpMethod::Post],
+                    path: "/v1/chat/completions".into(),
+                    query_allowlist: vec![],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            grpc_transcoding: None,
+            response_cache: None,
+            usage_extraction: None,
+            tags: vec![],
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn provisioning_again_updates_existing_entries() {
+        let cp = make_cp();
+        let ctx = provisioning_ctx(Uuid::new_v4()).unwrap();
+        // The tenant already bound the alias the definition derives.
+        let existing = cp
+            .create_upstream(&ctx, upstream_request(&["manual"]))
+            .await
+            .unwrap();
+
+        let upstream = provision_upstream(&cp, &ctx, &upstream_request(&["provisioned"]))
+            .await
+            .unwrap();
+        assert_eq!(upstream.id, existing.id);
+        assert_eq!(upstream.tags, vec!["provisioned".to_owned()]);
+
+        let route = provision_route(&cp, &ctx, &route_request(upstream.id, 1))
+            .await
+            .unwrap();
+        let again = provision_route(&cp, &ctx, &route_request(upstream.id, 2))
+            .await
+            .unwrap();
+        assert_eq!(again.id, route.id);
+        assert_eq!(again.priority, 2);
+    }
+}
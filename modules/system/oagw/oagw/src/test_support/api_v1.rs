@@ -49,6 +49,14 @@ impl<'a> ApiV1<'a> {
         )
     }
 
+    pub fn get_upstream_circuit_breaker(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(
+            self.harness,
+            Method::GET,
+            format!("/oagw/v1/upstreams/{id}/circuit-breaker"),
+        )
+    }
+
     // -- Route CRUD --
 
     pub fn post_route(&self) -> RequestCase<'a> {
@@ -79,6 +87,47 @@ impl<'a> ApiV1<'a> {
         )
     }
 
+    // -- Custom plugins --
+
+    pub fn post_plugin(&self) -> RequestCase<'a> {
+        RequestCase::new(self.harness, Method::POST, "/oagw/v1/plugins")
+    }
+
+    pub fn list_plugins(&self) -> RequestCase<'a> {
+        RequestCase::new(self.harness, Method::GET, "/oagw/v1/plugins")
+    }
+
+    pub fn get_plugin(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(self.harness, Method::GET, format!("/oagw/v1/plugins/{id}"))
+    }
+
+    pub fn get_plugin_source(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(
+            self.harness,
+            Method::GET,
+            format!("/oagw/v1/plugins/{id}/source"),
+        )
+    }
+
+    /// Plugins are immutable; the gateway answers 405.
+    pub fn put_plugin(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(self.harness, Method::PUT, format!("/oagw/v1/plugins/{id}"))
+    }
+
+    pub fn delete_plugin(&self, id: &str) -> RequestCase<'a> {
+        RequestCase::new(
+            self.harness,
+            Method::DELETE,
+            format!("/oagw/v1/plugins/{id}"),
+        )
+    }
+
+    // -- Usage --
+
+    pub fn get_usage(&self) -> RequestCase<'a> {
+        RequestCase::new(self.harness, Method::GET, "/oagw/v1/usage")
+    }
+
     // -- Proxy --
 
     pub fn proxy(&self, method: Method, alias: &str, path: &str) -> RequestCase<'a> {
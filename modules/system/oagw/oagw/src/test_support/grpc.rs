@@ -0,0 +1,123 @@
+//! `example.v1.UserService` fixtures for gRPC proxy tests.
+//!
+//! ```proto
+//! service UserService {
+//!   rpc GetUser(GetUserRequest) returns (User);
+//!   rpc ListUsers(ListUsersRequest) returns (stream User);
+//! }
+//! ```
+
+use bytes::Bytes;
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    MethodDescriptorProto, ServiceDescriptorProto,
+};
+
+pub const USER_SERVICE: &str = "example.v1.UserService";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct GetUserRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct User {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ListUsersRequest {
+    #[prost(int32, tag = "1")]
+    pub page_size: i32,
+}
+
+/// Serialized `FileDescriptorSet` describing [`USER_SERVICE`], as stored in
+/// a route's `grpc_transcoding.descriptor_set`.
+#[must_use]
+pub fn user_service_descriptor_set() -> Vec<u8> {
+    let field = |name: &str, json_name: &str, number: i32, ty: Type| FieldDescriptorProto {
+        name: Some(name.into()),
+        json_name: Some(json_name.into()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(ty as i32),
+        ..Default::default()
+    };
+    let message = |name: &str, fields: Vec<FieldDescriptorProto>| DescriptorProto {
+        name: Some(name.into()),
+        field: fields,
+        ..Default::default()
+    };
+    let method =
+        |name: &str, input: &str, output: &str, server_streaming: bool| MethodDescriptorProto {
+            name: Some(name.into()),
+            input_type: Some(format!(".example.v1.{input}")),
+            output_type: Some(format!(".example.v1.{output}")),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        };
+
+    let file = FileDescriptorProto {
+        name: Some("example/v1/user.proto".into()),
+        package: Some("example.v1".into()),
+        syntax: Some("proto3".into()),
+        message_type: vec![
+            message("GetUserRequest", vec![field("id", "id", 1, Type::String)]),
+            message(
+                "User",
+                vec![
+                    field("id", "id", 1, Type::String),
+                    field("name", "name", 2, Type::String),
+                ],
+            ),
+            message(
+                "ListUsersRequest",
+                vec![field("page_size", "pageSize", 1, Type::Int32)],
+            ),
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("UserService".into()),
+            method: vec![
+                method("GetUser", "GetUserRequest", "User", false),
+                method("ListUsers", "ListUsersRequest", "User", true),
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    FileDescriptorSet { file: vec![file] }.encode_to_vec()
+}
+
+/// Encode `message` as one length-prefixed gRPC frame.
+#[must_use]
+pub fn frame<M: Message>(message: &M) -> Bytes {
+    let encoded = message.encode_to_vec();
+    let mut framed = Vec::with_capacity(encoded.len() + 5);
+    framed.push(0);
+    framed.extend_from_slice(&u32::try_from(encoded.len()).unwrap().to_be_bytes());
+    framed.extend_from_slice(&encoded);
+    Bytes::from(framed)
+}
+
+/// Decode every length-prefixed frame in `body` as `M`.
+///
+/// # Panics
+///
+/// Panics if the body is not a sequence of uncompressed `M` frames.
+#[must_use]
+pub fn unframe<M: Message + Default>(mut body: &[u8]) -> Vec<M> {
+    let mut messages = Vec::new();
+    while !body.is_empty() {
+        assert_eq!(body[0], 0, "compressed frame");
+        let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+        messages.push(M::decode(&body[5..5 + len]).unwrap());
+        body = &body[5 + len..];
+    }
+    messages
+}
@@ -1,14 +1,17 @@
 //! Top-level test harness that wires all components together.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use modkit::client_hub::ClientHub;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use tokio::net::TcpListener;
 use uuid::Uuid;
 
 use crate::api::rest::routes::test_router;
+use crate::infra::tenant_hierarchy::InMemoryTenantHierarchy;
 
 use super::api_v1::ApiV1;
 use super::mock::shared_mock;
@@ -19,6 +22,7 @@ pub struct AppHarness {
     facade: Arc<dyn ServiceGatewayClientV1>,
     ctx: SecurityContext,
     router: axum::Router,
+    parent: Option<Box<AppHarness>>,
 }
 
 impl AppHarness {
@@ -43,9 +47,38 @@ impl AppHarness {
         &self.ctx
     }
 
+    /// Harness acting as the parent tenant of this one, sharing its gateway.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless built with [`AppHarnessBuilder::with_parent_tenant`].
+    pub fn parent(&self) -> &AppHarness {
+        self.parent
+            .as_deref()
+            .expect("harness was built without a parent tenant")
+    }
+
     pub(crate) fn router(&self) -> &axum::Router {
         &self.router
     }
+
+    /// Serve the router on `127.0.0.1:0` and return the bound address.
+    ///
+    /// Requests sent through [`ApiV1`] bypass the network, so connection
+    /// upgrades (WebSocket) can only be exercised against a real listener.
+    pub async fn serve(&self) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind harness listener");
+        let addr = listener.local_addr().expect("failed to get local addr");
+        let router = self.router.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, router)
+                .await
+                .expect("harness server error");
+        });
+        addr
+    }
 }
 
 /// Builder for [`AppHarness`].
@@ -53,6 +86,9 @@ impl AppHarness {
 pub struct AppHarnessBuilder {
     credentials: Vec<(String, String)>,
     request_timeout: Option<Duration>,
+    websocket_idle_timeout: Option<Duration>,
+    max_body_size_bytes: Option<usize>,
+    parent_tenant: bool,
 }
 
 impl AppHarnessBuilder {
@@ -66,6 +102,23 @@ impl AppHarnessBuilder {
         self
     }
 
+    pub fn with_websocket_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.websocket_idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size_bytes = Some(bytes);
+        self
+    }
+
+    /// Make the harness tenant a child of another tenant, reachable through
+    /// [`AppHarness::parent`].
+    pub fn with_parent_tenant(mut self) -> Self {
+        self.parent_tenant = true;
+        self
+    }
+
     pub async fn build(self) -> AppHarness {
         let hub = ClientHub::new();
 
@@ -74,25 +127,53 @@ impl AppHarnessBuilder {
             cp_builder = cp_builder.with_credentials(self.credentials);
         }
 
+        let tenant_id = Uuid::new_v4();
+        let mut parent_ctx = None;
+        if self.parent_tenant {
+            let parent_id = Uuid::new_v4();
+            let tree = InMemoryTenantHierarchy::new();
+            tree.set_parent(tenant_id, parent_id);
+            cp_builder = cp_builder.with_tenant_hierarchy(Arc::new(tree));
+            parent_ctx = Some(tenant_context(parent_id));
+        }
+
         let mut dp_builder = TestDpBuilder::new();
         if let Some(timeout) = self.request_timeout {
             dp_builder = dp_builder.with_request_timeout(timeout);
         }
+        if let Some(timeout) = self.websocket_idle_timeout {
+            dp_builder = dp_builder.with_websocket_idle_timeout(timeout);
+        }
 
-        let app_state = build_test_app_state(&hub, cp_builder, dp_builder);
-
-        let ctx = SecurityContext::builder()
-            .subject_tenant_id(Uuid::new_v4())
-            .subject_id(Uuid::new_v4())
-            .build()
-            .expect("test security context");
+        let mut app_state = build_test_app_state(&hub, cp_builder, dp_builder).await;
+        if let Some(bytes) = self.max_body_size_bytes {
+            app_state.state.config.max_body_size_bytes = bytes;
+        }
 
+        let parent = parent_ctx.map(|ctx| {
+            Box::new(AppHarness {
+                facade: app_state.facade.clone(),
+                router: test_router(app_state.state.clone(), ctx.clone()),
+                ctx,
+                parent: None,
+            })
+        });
+        let ctx = tenant_context(tenant_id);
         let router = test_router(app_state.state, ctx.clone());
 
         AppHarness {
             facade: app_state.facade,
             ctx,
             router,
+            parent,
         }
     }
 }
+
+fn tenant_context(tenant_id: Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_tenant_id(tenant_id)
+        .subject_id(Uuid::new_v4())
+        .build()
+        .expect("test security context")
+}
@@ -1,7 +1,7 @@
 //! Mock upstream server for integration tests.
 //!
 //! Simulates upstream services: OpenAI-compatible HTTP JSON, SSE streaming,
-//! error conditions, WebSocket, WebTransport stub.
+//! error conditions, WebSocket, gRPC (h2c), WebTransport stub.
 //!
 //! # Usage
 //! ```ignore
@@ -28,6 +28,8 @@ use tokio::net::TcpListener;
 use tokio::sync::Mutex;
 use tokio::sync::oneshot;
 
+use super::grpc;
+
 // ---------------------------------------------------------------------------
 // Dynamic mock response types
 // ---------------------------------------------------------------------------
@@ -140,6 +142,8 @@ pub(crate) struct SharedState {
     recorded: Mutex<VecDeque<RecordedRequest>>,
     max_recorded: usize,
     dynamic_routes: DashMap<RouteKey, MockResponse>,
+    /// Number of `OAuth2` tokens issued per test prefix.
+    issued_tokens: DashMap<String, u32>,
 }
 
 impl SharedState {
@@ -148,6 +152,7 @@ impl SharedState {
             recorded: Mutex::new(VecDeque::new()),
             max_recorded,
             dynamic_routes: DashMap::new(),
+            issued_tokens: DashMap::new(),
         }
     }
 
@@ -265,8 +270,18 @@ impl MockUpstream {
             .route("/error/500", get(error_500))
             // Response header test
             .route("/response-headers", get(response_with_bad_headers))
-            // WebSocket (future use)
+            // WebSocket
             .route("/ws/echo", get(ws_echo))
+            // Per-test WebSocket echo, reachable via `MockGuard::path("/ws/echo")`
+            .route("/{prefix}/ws/echo", get(ws_echo))
+            // Per-test OAuth2 token endpoint and a resource protected by it
+            .route("/{prefix}/oauth/token", post(oauth_token))
+            .route("/{prefix}/oauth/resource", get(oauth_resource))
+            // gRPC `example.v1.UserService` over h2c. gRPC paths cannot carry
+            // a per-test prefix, so these handlers echo request metadata back
+            // as `x-echo-*` response headers instead of relying on recording.
+            .route("/example.v1.UserService/GetUser", post(grpc_get_user))
+            .route("/example.v1.UserService/ListUsers", post(grpc_list_users))
             // WebTransport stub (future use)
             .route("/wt/stub", get(wt_stub))
             // Dynamic route fallback - catches all unmatched paths
@@ -347,6 +362,11 @@ impl MockGuard {
         format!("{}{}", self.test_prefix, path)
     }
 
+    /// Absolute URL of a prefixed path on the shared mock server.
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", shared_mock().addr, self.path(path))
+    }
+
     /// Register a mock response for this test.
     pub fn mock(&mut self, method: &str, path: &str, response: MockResponse) -> &mut Self {
         let full_path = self.path(path);
@@ -565,7 +585,7 @@ async fn error_500(
 }
 
 // ---------------------------------------------------------------------------
-// WebSocket handlers (future use — OAGW WS proxy not in this phase)
+// WebSocket handlers
 // ---------------------------------------------------------------------------
 
 async fn ws_echo(
@@ -581,22 +601,103 @@ async fn ws_echo(
 async fn handle_ws_echo(mut socket: WebSocket) {
     while let Some(Ok(msg)) = socket.recv().await {
         match msg {
-            Message::Text(text) => {
-                if socket.send(Message::Text(text)).await.is_err() {
-                    break;
-                }
-            }
-            Message::Binary(data) => {
-                if socket.send(Message::Binary(data)).await.is_err() {
-                    break;
-                }
-            }
+            Message::Text(_) | Message::Binary(_) => {}
             Message::Close(_) => break,
-            _ => {}
+            _ => continue,
+        }
+        if socket.send(msg).await.is_err() {
+            break;
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// gRPC handlers
+// ---------------------------------------------------------------------------
+
+/// Build a gRPC response: `frames` followed by trailers carrying `status`.
+/// Request metadata is echoed as `x-echo-*` headers.
+fn grpc_response(
+    request: &HeaderMap,
+    frames: Vec<Bytes>,
+    status: u32,
+    message: &str,
+) -> axum::response::Response {
+    use http_body::Frame;
+
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", status.into());
+    if let Ok(v) = message.parse() {
+        trailers.insert("grpc-message", v);
+    }
+    let body = futures_util::stream::iter(
+        frames
+            .into_iter()
+            .map(Frame::data)
+            .chain(std::iter::once(Frame::trailers(trailers)))
+            .map(Ok::<_, std::convert::Infallible>),
+    );
+
+    let mut builder = axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/grpc");
+    for (name, value) in request {
+        builder = builder.header(format!("x-echo-{name}"), value);
+    }
+    builder
+        .body(axum::body::Body::new(http_body_util::StreamBody::new(body)))
+        .unwrap()
+}
+
+/// Trailers-only error response: the status travels in the headers.
+fn grpc_trailers_only(status: u32, message: &str) -> axum::response::Response {
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/grpc")
+        .header("grpc-status", status)
+        .header("grpc-message", message)
+        .body(axum::body::Body::empty())
+        .unwrap()
+}
+
+/// `GetUser`: ids of the form `status-<code>` fail with that gRPC status;
+/// any other id returns `User { id, name: "user <id>" }`.
+async fn grpc_get_user(headers: HeaderMap, body: Bytes) -> axum::response::Response {
+    let Some(request) = grpc::unframe::<grpc::GetUserRequest>(&body).pop() else {
+        return grpc_trailers_only(3, "missing request message");
+    };
+    if let Some(code) = request.id.strip_prefix("status-") {
+        let code = code.parse().unwrap_or(2);
+        return grpc_trailers_only(code, &format!("user {} failed", request.id));
+    }
+    let user = grpc::User {
+        name: format!("user {}", request.id),
+        id: request.id,
+    };
+    grpc_response(&headers, vec![grpc::frame(&user)], 0, "")
+}
+
+/// `ListUsers`: streams `|page_size|` users. A negative page size ends the
+/// stream with `UNAVAILABLE` after the users were sent.
+async fn grpc_list_users(headers: HeaderMap, body: Bytes) -> axum::response::Response {
+    let page_size = grpc::unframe::<grpc::ListUsersRequest>(&body)
+        .pop()
+        .map_or(0, |r| r.page_size);
+    let frames = (0..page_size.unsigned_abs())
+        .map(|i| {
+            grpc::frame(&grpc::User {
+                id: format!("u{i}"),
+                name: format!("user u{i}"),
+            })
+        })
+        .collect();
+    if page_size < 0 {
+        grpc_response(&headers, frames, 14, "stream aborted")
+    } else {
+        grpc_response(&headers, frames, 0, "")
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Response header test handler
 // ---------------------------------------------------------------------------
@@ -624,6 +725,61 @@ async fn response_with_bad_headers(
         .expect("response builder should not fail")
 }
 
+// ---------------------------------------------------------------------------
+// OAuth2 handlers
+// ---------------------------------------------------------------------------
+
+/// Client credentials token endpoint. Tokens are numbered per test prefix:
+/// `tok-1`, `tok-2`, ...
+async fn oauth_token(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    Path(prefix): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    state
+        .record("POST", &uri.to_string(), &headers, &body)
+        .await;
+
+    let issued = {
+        let mut count = state.issued_tokens.entry(prefix).or_insert(0);
+        *count += 1;
+        *count
+    };
+    let resp = json!({
+        "access_token": format!("tok-{issued}"),
+        "token_type": "Bearer",
+        "expires_in": 3600,
+    });
+    (StatusCode::OK, axum::Json(resp))
+}
+
+/// Resource that treats `tok-1` as revoked: it answers `401` to that token
+/// and echoes the `authorization` header of any other request.
+async fn oauth_resource(
+    State(state): State<Arc<SharedState>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    state.record("GET", &uri.to_string(), &headers, &[]).await;
+
+    let authorization = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if authorization == "Bearer tok-1" {
+        return (
+            StatusCode::UNAUTHORIZED,
+            axum::Json(json!({"error": "invalid_token"})),
+        );
+    }
+    (
+        StatusCode::OK,
+        axum::Json(json!({"authorization": authorization})),
+    )
+}
+
 // ---------------------------------------------------------------------------
 // WebTransport stub (future use)
 // ---------------------------------------------------------------------------
@@ -2,6 +2,7 @@
 
 pub mod api_v1;
 pub mod body;
+pub mod grpc;
 pub mod harness;
 mod mock;
 pub mod request;
@@ -15,6 +16,6 @@ pub use response::TestResponse;
 
 pub use crate::domain::gts_helpers::{format_route_gts, format_upstream_gts, parse_resource_gts};
 pub use crate::domain::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, TestAppState, TestCpBuilder, TestCredentialResolver, TestDpBuilder,
-    build_test_app_state, build_test_gateway,
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID, TestAppState,
+    TestCpBuilder, TestCredentialResolver, TestDpBuilder, build_test_app_state, build_test_gateway,
 };
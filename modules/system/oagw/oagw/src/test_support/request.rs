@@ -43,6 +43,18 @@ impl<'a> RequestCase<'a> {
         self
     }
 
+    /// Send `chunks` as a streamed body of unknown length, announced with
+    /// `Transfer-Encoding: chunked` like a streaming upload would be.
+    pub fn with_chunked_body(mut self, chunks: Vec<bytes::Bytes>) -> Self {
+        let stream = futures::stream::iter(chunks.into_iter().map(Ok::<_, std::io::Error>));
+        self.body = Some(Body::from_stream(stream));
+        self.headers.insert(
+            http::header::TRANSFER_ENCODING,
+            HeaderValue::from_static("chunked"),
+        );
+        self
+    }
+
     /// Add a request header.
     pub fn with_header(
         mut self,
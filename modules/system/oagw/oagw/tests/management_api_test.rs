@@ -55,6 +55,7 @@ async fn get_upstream_by_gts_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -113,6 +114,7 @@ async fn update_upstream_preserves_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -152,6 +154,7 @@ async fn delete_upstream_returns_204() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -182,6 +185,7 @@ async fn create_route_success() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -235,6 +239,7 @@ async fn list_upstreams_with_pagination() {
                             scheme: oagw_sdk::Scheme::Https,
                             host: format!("host{i}.example.com"),
                             port: 443,
+                            weight: 1,
                         }],
                     },
                     "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -520,3 +525,147 @@ async fn list_routes_filters_by_upstream() {
         assert_eq!(route["upstream_id"].as_str().unwrap(), uuid_a);
     }
 }
+
+// ---------------------------------------------------------------------------
+// Custom plugins (scenarios/plugins/plugin-management)
+// ---------------------------------------------------------------------------
+
+const REQUIRE_HEADER_SOURCE: &str = r#"
+def on_request(ctx):
+    if ctx.request.headers.get(ctx.config["header"]) == None:
+        return ctx.reject(400, "MISSING_HEADER", "missing " + ctx.config["header"])
+    return ctx.next()
+"#;
+
+async fn create_guard_plugin(h: &AppHarness) -> String {
+    let resp = h
+        .api_v1()
+        .post_plugin()
+        .with_body(serde_json::json!({
+            "name": "require-header",
+            "plugin_type": "guard",
+            "config_schema": {
+                "type": "object",
+                "properties": {"header": {"type": "string", "default": "x-customer-id"}}
+            },
+            "source_code": REQUIRE_HEADER_SOURCE
+        }))
+        .expect_status(201)
+        .await;
+    resp.json()["id"].as_str().unwrap().to_string()
+}
+
+// 4.1: create returns 201 with a GTS id; metadata and source are readable.
+#[tokio::test]
+async fn create_plugin_and_read_source() {
+    let h = AppHarness::builder().build().await;
+    let id = create_guard_plugin(&h).await;
+    assert!(id.starts_with("gts.x.core.oagw.guard_plugin.v1~"));
+
+    let json = h.api_v1().get_plugin(&id).expect_status(200).await.json();
+    assert_eq!(json["name"], "require-header");
+    assert_eq!(json["plugin_type"], "guard");
+    assert_eq!(json["phases"], serde_json::json!(["on_request"]));
+    assert!(json.get("source_code").is_none());
+
+    let resp = h.api_v1().get_plugin_source(&id).expect_status(200).await;
+    resp.assert_header("content-type", "text/plain; charset=utf-8");
+    assert_eq!(resp.text(), REQUIRE_HEADER_SOURCE);
+
+    let list = h.api_v1().list_plugins().expect_status(200).await.json();
+    assert_eq!(list.as_array().unwrap().len(), 1);
+}
+
+// 4.1: source that does not compile is rejected at creation.
+#[tokio::test]
+async fn create_plugin_with_invalid_source_returns_400() {
+    let h = AppHarness::builder().build().await;
+    h.api_v1()
+        .post_plugin()
+        .with_body(serde_json::json!({
+            "name": "broken",
+            "plugin_type": "transform",
+            "source_code": "def on_request(ctx):\n    return ctx.next(\n"
+        }))
+        .expect_status(400)
+        .await;
+}
+
+// 4.2: plugins are immutable.
+#[tokio::test]
+async fn update_plugin_is_not_allowed() {
+    let h = AppHarness::builder().build().await;
+    let id = create_guard_plugin(&h).await;
+    h.api_v1()
+        .put_plugin(&id)
+        .with_body(serde_json::json!({"name": "renamed"}))
+        .expect_status(405)
+        .await;
+}
+
+// 4.3: a referenced plugin cannot be deleted until the reference is removed.
+#[tokio::test]
+async fn delete_referenced_plugin_returns_409() {
+    let h = AppHarness::builder().build().await;
+    let id = create_guard_plugin(&h).await;
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "api.openai.com", "port": 443, "scheme": "https"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "plugin-ref",
+            "plugins": {"items": [id]},
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let upstream_id = resp.json()["id"].as_str().unwrap().to_string();
+
+    let resp = h.api_v1().delete_plugin(&id).expect_status(409).await;
+    let json = resp.json();
+    assert_eq!(
+        json["type"],
+        "gts.x.core.errors.err.v1~x.oagw.plugin.in_use.v1"
+    );
+    assert!(json["detail"].as_str().unwrap().contains(&upstream_id));
+
+    h.api_v1()
+        .delete_upstream(&upstream_id)
+        .expect_status(204)
+        .await;
+    h.api_v1().delete_plugin(&id).expect_status(204).await;
+    h.api_v1().get_plugin(&id).expect_status(404).await;
+}
+
+// 4.4: a guard plugin cannot be attached as the upstream's auth plugin.
+#[tokio::test]
+async fn guard_plugin_as_auth_returns_400() {
+    let h = AppHarness::builder().build().await;
+    let id = create_guard_plugin(&h).await;
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(serde_json::json!({
+            "server": {
+                "endpoints": [{"host": "api.openai.com", "port": 443, "scheme": "https"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "auth": {"type": id, "sharing": "private"},
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(400)
+        .await;
+    assert!(
+        resp.json()["detail"]
+            .as_str()
+            .unwrap()
+            .contains("plugin type mismatch")
+    );
+}
@@ -1,13 +1,15 @@
 use http::{Method, StatusCode};
 use oagw::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, AppHarness, MockBody, MockGuard, MockResponse, parse_resource_gts,
+    APIKEY_AUTH_PLUGIN_ID, AppHarness, CORS_GUARD_PLUGIN_ID, MockBody, MockGuard, MockResponse,
+    RecordedRequest, TIMEOUT_GUARD_PLUGIN_ID, grpc, parse_resource_gts,
 };
 use oagw_sdk::Body;
 use oagw_sdk::api::ErrorSource;
 use oagw_sdk::{
     BurstConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint, HttpMatch, HttpMethod,
     MatchRules, PathSuffixMode, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
-    RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate, Window,
+    RateLimitStrategy, ResponseCacheConfig, Scheme, Server, SharingMode, SustainedRate,
+    UpdateRouteRequest, UsageExtractionConfig, Window,
 };
 use serde_json::json;
 
@@ -129,6 +131,7 @@ async fn proxy_injects_auth_header() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -230,6 +233,7 @@ async fn proxy_sse_streaming() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -346,6 +350,7 @@ async fn proxy_disabled_upstream_returns_503() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: 9999,
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -388,6 +393,7 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -404,6 +410,8 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                 scope: RateLimitScope::Tenant,
                 strategy: RateLimitStrategy::Reject,
                 cost: 1,
+                queue: None,
+                degrade: None,
             })
             .build(),
         )
@@ -488,6 +496,7 @@ async fn proxy_upstream_timeout_returns_504() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -548,6 +557,7 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -602,6 +612,7 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -662,6 +673,7 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -742,6 +754,7 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -808,6 +821,7 @@ async fn proxy_response_headers_sanitized() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -900,6 +914,7 @@ async fn proxy_path_suffix_disabled_rejects_extra_path() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -988,6 +1003,7 @@ async fn proxy_with_mock_guard_custom_response() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
                 },
                 "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
@@ -1040,3 +1056,2504 @@ async fn proxy_with_mock_guard_custom_response() {
     assert_eq!(recorded.len(), 1);
     assert!(recorded[0].uri.contains("/custom/endpoint"));
 }
+
+// ---------------------------------------------------------------------------
+// Rate-limit algorithms and strategies (scenarios/rate-limiting)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream with the given `rate_limit` JSON and a GET route to a
+/// guarded mock path. Returns the proxy path (without leading slash).
+async fn setup_rate_limited(
+    h: &AppHarness,
+    guard: &mut MockGuard,
+    alias: &str,
+    rate_limit: serde_json::Value,
+) -> String {
+    guard.mock(
+        "GET",
+        "/limited",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": [],
+            "rate_limit": rate_limit
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/limited");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    path[1..].to_string()
+}
+
+// 18.2: sliding window rejects over-limit requests with 429 + Retry-After.
+#[tokio::test]
+async fn proxy_sliding_window_rejects_with_retry_after() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "sliding",
+        json!({
+            "algorithm": "sliding_window",
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "reject"
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("sliding", &path)
+        .expect_status(200)
+        .await;
+    let resp = h
+        .api_v1()
+        .proxy_get("sliding", &path)
+        .expect_status(429)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway")
+        .assert_header("content-type", "application/problem+json");
+    assert!(resp.headers().contains_key("retry-after"));
+}
+
+// 18.5 B: queued request that cannot get capacity in time fails with 503.
+#[tokio::test]
+async fn proxy_queue_timeout_returns_503() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "queued",
+        json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "queue",
+            "queue": {"max_depth": 10, "timeout": "100ms"}
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("queued", &path)
+        .expect_status(200)
+        .await;
+    let resp = h
+        .api_v1()
+        .proxy_get("queued", &path)
+        .expect_status(503)
+        .await;
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.queue.timeout.v1"
+    );
+    assert!(resp.headers().contains_key("retry-after"));
+}
+
+// 18.5 B: queued request is forwarded once capacity frees up.
+#[tokio::test]
+async fn proxy_queue_waits_then_succeeds() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "queued-ok",
+        json!({
+            "sustained": {"rate": 10, "window": "second"},
+            "burst": {"capacity": 1},
+            "strategy": "queue",
+            "queue": {"max_depth": 10, "timeout": "2s"}
+        }),
+    )
+    .await;
+
+    for _ in 0..3 {
+        h.api_v1()
+            .proxy_get("queued-ok", &path)
+            .expect_status(200)
+            .await;
+    }
+    assert_eq!(guard.recorded_requests().await.len(), 3);
+}
+
+// 18.5 C: degrade without fallback forwards with a marker header.
+#[tokio::test]
+async fn proxy_degrade_forwards_with_marker_header() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "degraded",
+        json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "degrade"
+        }),
+    )
+    .await;
+
+    let first = h
+        .api_v1()
+        .proxy_get("degraded", &path)
+        .expect_status(200)
+        .await;
+    assert!(!first.headers().contains_key("x-oagw-degraded"));
+
+    let second = h
+        .api_v1()
+        .proxy_get("degraded", &path)
+        .expect_status(200)
+        .await;
+    second.assert_header("x-oagw-degraded", "rate_limit");
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 2);
+    assert!(
+        recorded[1]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "x-oagw-degraded" && v == "rate_limit")
+    );
+}
+
+// 18.5 C: degrade with a static fallback response does not reach the upstream.
+#[tokio::test]
+async fn proxy_degrade_serves_fallback_response() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "degraded-static",
+        json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "degrade",
+            "degrade": {
+                "fallback_response": {
+                    "status": 503,
+                    "body": "{\"error\": \"Service temporarily degraded\"}"
+                }
+            }
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("degraded-static", &path)
+        .expect_status(200)
+        .await;
+    let resp = h
+        .api_v1()
+        .proxy_get("degraded-static", &path)
+        .expect_status(503)
+        .await;
+    resp.assert_header("x-oagw-degraded", "rate_limit")
+        .assert_header("x-oagw-error-source", "gateway");
+    assert_eq!(resp.json()["error"], "Service temporarily degraded");
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+
+    let usage = h.api_v1().get_usage().expect_status(200).await.json();
+    assert_eq!(usage[0]["requests"], 2);
+    assert_eq!(usage[0]["degraded"], 1);
+}
+
+// 18.5 C: degrade with a fallback upstream routes the request on that upstream.
+#[tokio::test]
+async fn proxy_degrade_routes_on_fallback_upstream() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "degraded-spare",
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, fallback_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+    let path = setup_rate_limited(
+        &h,
+        &mut guard,
+        "degraded-primary",
+        json!({
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "degrade",
+            "degrade": {"fallback_upstream_id": fallback_uuid}
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("degraded-primary", &path)
+        .expect_status(200)
+        .await;
+    // The fallback upstream has no route for the request yet.
+    h.api_v1()
+        .proxy_get("degraded-primary", &path)
+        .expect_status(404)
+        .await;
+
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": fallback_uuid,
+            "match": {"http": {"methods": ["GET"], "path": format!("/{path}")}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+    h.api_v1()
+        .proxy_get("degraded-primary", &path)
+        .expect_status(200)
+        .await
+        .assert_header("x-oagw-degraded", "rate_limit");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Circuit breaker (docs/adr-circuit-breaker.md)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream with the given `circuit_breaker` JSON and a GET route to
+/// a guarded mock path answering `status`. Returns the upstream GTS id and the
+/// proxy path (without leading slash).
+async fn setup_circuit_breaker(
+    h: &AppHarness,
+    guard: &mut MockGuard,
+    alias: &str,
+    status: u16,
+    circuit_breaker: serde_json::Value,
+) -> (String, String) {
+    guard.mock(
+        "GET",
+        "/flaky",
+        MockResponse {
+            status,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"status": status})),
+        },
+    );
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": [],
+            "circuit_breaker": circuit_breaker
+        }))
+        .expect_status(201)
+        .await;
+    let upstream_id = resp.json()["id"].as_str().unwrap().to_string();
+    let (_, upstream_uuid) = parse_resource_gts(&upstream_id).unwrap();
+
+    let path = guard.path("/flaky");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    (upstream_id, path[1..].to_string())
+}
+
+#[tokio::test]
+async fn proxy_circuit_opens_after_consecutive_failures() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let (upstream_id, path) = setup_circuit_breaker(
+        &h,
+        &mut guard,
+        "cb-open",
+        500,
+        json!({"failure_threshold": 2, "timeout_seconds": 30}),
+    )
+    .await;
+
+    // Upstream failures pass through until the threshold is reached.
+    for _ in 0..2 {
+        h.api_v1()
+            .proxy_get("cb-open", &path)
+            .expect_status(500)
+            .await
+            .assert_header("x-oagw-error-source", "upstream");
+    }
+
+    let resp = h
+        .api_v1()
+        .proxy_get("cb-open", &path)
+        .expect_status(503)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway")
+        .assert_header("x-circuit-state", "OPEN")
+        .assert_header("content-type", "application/problem+json");
+    assert!(resp.headers().contains_key("retry-after"));
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.circuit_breaker.open.v1"
+    );
+    // The short-circuited call never reached the upstream.
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    let status = h
+        .api_v1()
+        .get_upstream_circuit_breaker(&upstream_id)
+        .expect_status(200)
+        .await;
+    let circuits = status.json();
+    let circuits = circuits.as_array().unwrap();
+    assert_eq!(circuits.len(), 1);
+    assert_eq!(
+        circuits[0]["endpoint"],
+        format!("127.0.0.1:{}", h.mock_port())
+    );
+    assert_eq!(circuits[0]["state"], "open");
+    assert!(circuits[0]["retry_after_secs"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn proxy_circuit_ignores_statuses_outside_failure_conditions() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let (upstream_id, path) = setup_circuit_breaker(
+        &h,
+        &mut guard,
+        "cb-404",
+        404,
+        json!({"failure_threshold": 1}),
+    )
+    .await;
+
+    for _ in 0..3 {
+        h.api_v1()
+            .proxy_get("cb-404", &path)
+            .expect_status(404)
+            .await;
+    }
+
+    let status = h
+        .api_v1()
+        .get_upstream_circuit_breaker(&upstream_id)
+        .expect_status(200)
+        .await;
+    assert_eq!(status.json()[0]["state"], "closed");
+    assert_eq!(status.json()[0]["window_requests"], 3);
+}
+
+#[tokio::test]
+async fn upstream_circuit_breaker_config_round_trips() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let (upstream_id, _) = setup_circuit_breaker(
+        &h,
+        &mut guard,
+        "cb-config",
+        200,
+        json!({"failure_rate_threshold": 50, "scope": "global"}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .get_upstream(&upstream_id)
+        .expect_status(200)
+        .await;
+    let cb = &resp.json()["circuit_breaker"];
+    assert_eq!(cb["enabled"], true);
+    assert_eq!(cb["failure_threshold"], 5);
+    assert_eq!(cb["failure_rate_threshold"], 50);
+    assert_eq!(cb["scope"], "global");
+    assert_eq!(
+        cb["failure_conditions"]["status_codes"],
+        json!([500, 502, 503, 504])
+    );
+
+    let status = h
+        .api_v1()
+        .get_upstream_circuit_breaker(&upstream_id)
+        .expect_status(200)
+        .await;
+    let circuits = status.json();
+    assert_eq!(circuits.as_array().unwrap().len(), 1);
+    assert!(circuits[0].get("endpoint").is_none());
+    assert_eq!(circuits[0]["state"], "closed");
+}
+
+// ---------------------------------------------------------------------------
+// Load balancing and X-OAGW-Target-Host (scenarios/proxy-api/custom-header-routing)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream whose two endpoints (`127.0.0.1` and `localhost`) both
+/// point at the shared mock server, plus a route to a guarded `/lb` path.
+/// Returns the proxy path (without leading slash).
+async fn setup_two_endpoints(h: &AppHarness, guard: &mut MockGuard, alias: &str) -> String {
+    guard.mock(
+        "GET",
+        "/lb",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [
+                    {"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"},
+                    {"host": "localhost", "port": h.mock_port(), "scheme": "http"}
+                ]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/lb");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    path[1..].to_string()
+}
+
+fn recorded_hosts(recorded: &[RecordedRequest]) -> Vec<String> {
+    recorded
+        .iter()
+        .filter_map(|r| {
+            r.headers
+                .iter()
+                .find(|(k, _)| k == "host")
+                .map(|(_, v)| v.clone())
+        })
+        .collect()
+}
+
+// positive-2.1: explicit alias without the header is round-robined.
+#[tokio::test]
+async fn proxy_round_robins_across_endpoints() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_two_endpoints(&h, &mut guard, "lb-rr").await;
+
+    for _ in 0..4 {
+        h.api_v1()
+            .proxy_get("lb-rr", &path)
+            .expect_status(200)
+            .await;
+    }
+
+    let hosts = recorded_hosts(&guard.recorded_requests().await);
+    assert_eq!(hosts.len(), 4);
+    for endpoint in ["127.0.0.1", "localhost"] {
+        let expected = format!("{endpoint}:{}", h.mock_port());
+        assert_eq!(hosts.iter().filter(|host| **host == expected).count(), 2);
+    }
+}
+
+// positive-3.1: the header pins the endpoint (case-insensitively) and is not forwarded.
+#[tokio::test]
+async fn proxy_target_host_header_pins_endpoint() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_two_endpoints(&h, &mut guard, "lb-pin").await;
+
+    for _ in 0..3 {
+        h.api_v1()
+            .proxy_get("lb-pin", &path)
+            .with_header(
+                http::HeaderName::from_static("x-oagw-target-host"),
+                http::HeaderValue::from_static("LocalHost"),
+            )
+            .expect_status(200)
+            .await;
+    }
+
+    let recorded = guard.recorded_requests().await;
+    let hosts = recorded_hosts(&recorded);
+    assert_eq!(hosts, vec![format!("localhost:{}", h.mock_port()); 3]);
+    assert!(
+        recorded
+            .iter()
+            .all(|r| r.headers.iter().all(|(k, _)| k != "x-oagw-target-host"))
+    );
+}
+
+// negative-1.2 / negative-2.1: malformed or unknown target hosts are rejected.
+#[tokio::test]
+async fn proxy_rejects_invalid_and_unknown_target_host() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_two_endpoints(&h, &mut guard, "lb-bad").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("lb-bad", &path)
+        .with_header(
+            http::HeaderName::from_static("x-oagw-target-host"),
+            http::HeaderValue::from_static("localhost:8080"),
+        )
+        .expect_status(400)
+        .await;
+    assert!(
+        resp.json()["type"]
+            .as_str()
+            .unwrap()
+            .contains("invalid_target_host")
+    );
+
+    let resp = h
+        .api_v1()
+        .proxy_get("lb-bad", &path)
+        .with_header(
+            http::HeaderName::from_static("x-oagw-target-host"),
+            http::HeaderValue::from_static("10.0.1.1"),
+        )
+        .expect_status(400)
+        .await;
+    assert!(
+        resp.json()["type"]
+            .as_str()
+            .unwrap()
+            .contains("unknown_target_host")
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// WebSocket (scenarios/protocols/websocket)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream pointing at the mock WebSocket echo plus a GET route to
+/// it. `extra` is merged into the upstream body (auth, rate limits).
+/// Returns the `ws://` URL to connect to through the gateway.
+async fn setup_websocket(
+    h: &AppHarness,
+    guard: &MockGuard,
+    alias: &str,
+    extra: serde_json::Value,
+) -> String {
+    let mut body = json!({
+        "server": {
+            "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+        },
+        "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+        "alias": alias,
+        "enabled": true,
+        "tags": []
+    });
+    if let (Some(body), Some(extra)) = (body.as_object_mut(), extra.as_object()) {
+        body.extend(extra.clone());
+    }
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(body)
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/ws/echo");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    let addr = h.serve().await;
+    format!("ws://{addr}/oagw/v1/proxy/{alias}{path}")
+}
+
+// 14.1 + 14.2: upgrade is proxied, auth is injected into the handshake and
+// frames are relayed in both directions.
+#[tokio::test]
+async fn proxy_websocket_relays_frames_with_auth_on_handshake() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://openai-key".into(), "sk-test123".into())])
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    let url = setup_websocket(
+        &h,
+        &guard,
+        "ws-echo",
+        json!({
+            "auth": {
+                "type": APIKEY_AUTH_PLUGIN_ID,
+                "sharing": "private",
+                "config": {
+                    "header": "authorization",
+                    "prefix": "Bearer ",
+                    "secret_ref": "cred://openai-key"
+                }
+            }
+        }),
+    )
+    .await;
+
+    let (mut ws, resp) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect("websocket handshake through proxy");
+    assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+    assert_eq!(resp.headers()["x-oagw-error-source"], "upstream");
+
+    ws.send(Message::text("hello")).await.unwrap();
+    let echoed = ws.next().await.unwrap().unwrap();
+    assert_eq!(echoed, Message::text("hello"));
+    ws.send(Message::binary(vec![1u8, 2, 3])).await.unwrap();
+    let echoed = ws.next().await.unwrap().unwrap();
+    assert_eq!(echoed, Message::binary(vec![1u8, 2, 3]));
+    ws.close(None).await.unwrap();
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    let auth = recorded[0]
+        .headers
+        .iter()
+        .find(|(k, _)| k == "authorization")
+        .map(|(_, v)| v.as_str());
+    assert_eq!(auth, Some("Bearer sk-test123"));
+}
+
+// 14.3: the rate limit counts connection establishment, not frames.
+#[tokio::test]
+async fn proxy_websocket_handshake_is_rate_limited() {
+    use tokio_tungstenite::tungstenite::Error as WsError;
+
+    let h = AppHarness::builder().build().await;
+    let guard = MockGuard::new();
+    let url = setup_websocket(
+        &h,
+        &guard,
+        "ws-limited",
+        json!({
+            "rate_limit": {
+                "algorithm": "sliding_window",
+                "sustained": {"rate": 1, "window": "minute"},
+                "strategy": "reject"
+            }
+        }),
+    )
+    .await;
+
+    let (_first, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect("first connection within limit");
+    let err = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect_err("second connection must be rejected");
+    let WsError::Http(resp) = err else {
+        panic!("expected HTTP rejection, got {err:?}");
+    };
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()["x-oagw-error-source"], "gateway");
+    assert!(resp.headers().contains_key("retry-after"));
+}
+
+// 14.4: silent connections are closed with 1001 (going away).
+#[tokio::test]
+async fn proxy_websocket_idle_timeout_closes_connection() {
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+    let h = AppHarness::builder()
+        .with_websocket_idle_timeout(std::time::Duration::from_millis(200))
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    let url = setup_websocket(&h, &guard, "ws-idle", json!({})).await;
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect("websocket handshake through proxy");
+    let msg = tokio::time::timeout(std::time::Duration::from_secs(5), ws.next())
+        .await
+        .expect("gateway should close the idle connection")
+        .unwrap()
+        .unwrap();
+    let Message::Close(Some(frame)) = msg else {
+        panic!("expected close frame, got {msg:?}");
+    };
+    assert_eq!(frame.code, CloseCode::Away);
+    assert_eq!(frame.reason.as_str(), "idle timeout");
+}
+
+// ---------------------------------------------------------------------------
+// gRPC
+// ---------------------------------------------------------------------------
+
+const GRPC_PROTOCOL: &str = "gts.x.core.oagw.protocol.v1~x.core.oagw.grpc.v1";
+
+/// Create a gRPC upstream at the mock's h2c listener and a route for
+/// `example.v1.UserService/{method}`. `extra_route` is merged into the route
+/// body (transcoding, rate limits).
+async fn setup_grpc(h: &AppHarness, alias: &str, method: &str, extra_route: serde_json::Value) {
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": GRPC_PROTOCOL,
+            "alias": alias,
+            "enabled": true,
+            "tags": [],
+            "headers": {
+                "request": {"passthrough": "allowlist", "passthrough_allowlist": ["x-tenant-meta"]}
+            },
+            "auth": {
+                "type": APIKEY_AUTH_PLUGIN_ID,
+                "sharing": "private",
+                "config": {
+                    "header": "authorization",
+                    "prefix": "Bearer ",
+                    "secret_ref": "cred://grpc-key"
+                }
+            }
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let mut body = json!({
+        "upstream_id": upstream_uuid,
+        "match": {"grpc": {"service": grpc::USER_SERVICE, "method": method}},
+        "enabled": true,
+        "tags": [],
+        "priority": 0
+    });
+    if let (Some(body), Some(extra)) = (body.as_object_mut(), extra_route.as_object()) {
+        body.extend(extra.clone());
+    }
+    h.api_v1()
+        .post_route()
+        .with_body(body)
+        .expect_status(201)
+        .await;
+}
+
+fn grpc_transcoding() -> serde_json::Value {
+    use base64::Engine as _;
+    let descriptor_set =
+        base64::engine::general_purpose::STANDARD.encode(grpc::user_service_descriptor_set());
+    json!({"grpc_transcoding": {"descriptor_set": descriptor_set}})
+}
+
+async fn grpc_harness() -> AppHarness {
+    AppHarness::builder()
+        .with_credentials(vec![("cred://grpc-key".into(), "grpc-secret".into())])
+        .build()
+        .await
+}
+
+/// Native gRPC call through a served harness over h2c. Returns the response
+/// head, the body and the trailers.
+async fn grpc_call(
+    addr: std::net::SocketAddr,
+    alias: &str,
+    method: &str,
+    message: bytes::Bytes,
+) -> (http::response::Parts, bytes::Bytes, Option<http::HeaderMap>) {
+    use http_body_util::BodyExt;
+
+    let client = reqwest::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap();
+    let resp = client
+        .post(format!(
+            "http://{addr}/oagw/v1/proxy/{alias}/{}/{method}",
+            grpc::USER_SERVICE
+        ))
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .header("x-tenant-meta", "m-1")
+        .body(message)
+        .send()
+        .await
+        .unwrap();
+    let (parts, body) = http::Response::from(resp).into_parts();
+    let collected = body.collect().await.unwrap();
+    let trailers = collected.trailers().cloned();
+    (parts, collected.to_bytes(), trailers)
+}
+
+// 15.1: native unary call keeps metadata, gets credentials injected and
+// returns the upstream trailers.
+#[tokio::test]
+async fn proxy_grpc_unary_forwards_metadata_and_trailers() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-unary", "GetUser", json!({})).await;
+    let addr = h.serve().await;
+
+    let request = grpc::frame(&grpc::GetUserRequest { id: "42".into() });
+    let (parts, body, trailers) = grpc_call(addr, "grpc-unary", "GetUser", request).await;
+
+    assert_eq!(parts.status, StatusCode::OK);
+    assert_eq!(parts.headers["content-type"], "application/grpc");
+    assert_eq!(parts.headers["x-echo-authorization"], "Bearer grpc-secret");
+    assert_eq!(parts.headers["x-echo-x-tenant-meta"], "m-1");
+    assert_eq!(parts.headers["x-echo-te"], "trailers");
+    let users = grpc::unframe::<grpc::User>(&body);
+    assert_eq!(
+        users,
+        vec![grpc::User {
+            id: "42".into(),
+            name: "user 42".into()
+        }]
+    );
+    assert_eq!(trailers.expect("trailers")["grpc-status"], "0");
+}
+
+// 15.2: server-streaming call delivers every message and completes with
+// trailers.
+#[tokio::test]
+async fn proxy_grpc_server_streaming_completes_with_trailers() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-stream", "ListUsers", json!({})).await;
+    let addr = h.serve().await;
+
+    let request = grpc::frame(&grpc::ListUsersRequest { page_size: 3 });
+    let (parts, body, trailers) = grpc_call(addr, "grpc-stream", "ListUsers", request).await;
+
+    assert_eq!(parts.status, StatusCode::OK);
+    let ids: Vec<String> = grpc::unframe::<grpc::User>(&body)
+        .into_iter()
+        .map(|u| u.id)
+        .collect();
+    assert_eq!(ids, vec!["u0", "u1", "u2"]);
+    assert_eq!(trailers.expect("trailers")["grpc-status"], "0");
+}
+
+// 15.3: HTTP/JSON clients reach a unary method through transcoding.
+#[tokio::test]
+async fn proxy_grpc_transcodes_unary_json() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-json", "GetUser", grpc_transcoding()).await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-json", "example.v1.UserService/GetUser")
+        .with_body(json!({"id": "7"}))
+        .expect_status(200)
+        .await;
+    resp.assert_header("content-type", "application/json");
+    assert_eq!(resp.json(), json!({"id": "7", "name": "user 7"}));
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-json", "example.v1.UserService/GetUser")
+        .with_body(json!({"id": 7}))
+        .expect_status(400)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+}
+
+// 15.3: server-streaming responses become one JSON line per message.
+#[tokio::test]
+async fn proxy_grpc_transcodes_server_stream_to_ndjson() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-ndjson", "ListUsers", grpc_transcoding()).await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-ndjson", "example.v1.UserService/ListUsers")
+        .with_body(json!({"page_size": 2}))
+        .expect_status(200)
+        .await;
+    resp.assert_header("content-type", "application/x-ndjson");
+    let lines: Vec<serde_json::Value> = resp
+        .text()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            json!({"id": "u0", "name": "user u0"}),
+            json!({"id": "u1", "name": "user u1"})
+        ]
+    );
+
+    // A stream that fails after sending messages ends with an error line.
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-ndjson", "example.v1.UserService/ListUsers")
+        .with_body(json!({"page_size": -1}))
+        .expect_status(200)
+        .await;
+    let text = resp.text();
+    let last: serde_json::Value = serde_json::from_str(text.lines().last().unwrap()).unwrap();
+    assert_eq!(last["error"]["code"], 14);
+    assert_eq!(last["error"]["message"], "stream aborted");
+}
+
+// 15.4 A: gateway errors reach native clients as gRPC statuses.
+#[tokio::test]
+async fn proxy_grpc_gateway_error_uses_grpc_status() {
+    let h = grpc_harness().await;
+    setup_grpc(
+        &h,
+        "grpc-limited",
+        "GetUser",
+        json!({
+            "rate_limit": {
+                "algorithm": "sliding_window",
+                "sustained": {"rate": 1, "window": "minute"},
+                "strategy": "reject"
+            }
+        }),
+    )
+    .await;
+    let addr = h.serve().await;
+
+    let request = grpc::frame(&grpc::GetUserRequest { id: "1".into() });
+    let (first, _, _) = grpc_call(addr, "grpc-limited", "GetUser", request.clone()).await;
+    assert_eq!(first.headers["x-oagw-error-source"], "upstream");
+
+    let (parts, body, _) = grpc_call(addr, "grpc-limited", "GetUser", request).await;
+    assert_eq!(parts.status, StatusCode::OK);
+    assert_eq!(parts.headers["content-type"], "application/grpc");
+    assert_eq!(parts.headers["grpc-status"], "8");
+    assert_eq!(parts.headers["x-oagw-error-source"], "gateway");
+    assert!(parts.headers.contains_key("retry-after"));
+    assert!(body.is_empty());
+}
+
+// 15.4 B: upstream gRPC errors are attributed to the upstream; transcoded
+// calls map RESOURCE_EXHAUSTED to 429.
+#[tokio::test]
+async fn proxy_grpc_upstream_error_attributed_to_upstream() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-errors", "GetUser", json!({})).await;
+    let addr = h.serve().await;
+
+    let request = grpc::frame(&grpc::GetUserRequest {
+        id: "status-14".into(),
+    });
+    let (parts, _, _) = grpc_call(addr, "grpc-errors", "GetUser", request).await;
+    assert_eq!(parts.status, StatusCode::OK);
+    assert_eq!(parts.headers["grpc-status"], "14");
+    assert_eq!(parts.headers["x-oagw-error-source"], "upstream");
+
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-errors-json", "GetUser", grpc_transcoding()).await;
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-errors-json", "example.v1.UserService/GetUser")
+        .with_body(json!({"id": "status-8"}))
+        .expect_status(429)
+        .await;
+    resp.assert_header("x-oagw-error-source", "upstream");
+    assert_eq!(resp.json()["code"], 8);
+    assert_eq!(resp.json()["message"], "user status-8 failed");
+}
+
+// A gRPC route without transcoding only accepts native gRPC calls.
+#[tokio::test]
+async fn proxy_grpc_route_rejects_plain_http_without_transcoding() {
+    let h = grpc_harness().await;
+    setup_grpc(&h, "grpc-native-only", "GetUser", json!({})).await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("grpc-native-only", "example.v1.UserService/GetUser")
+        .with_body(json!({"id": "1"}))
+        .expect_status(400)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+}
+
+// ---------------------------------------------------------------------------
+// Basic, bearer and OAuth2 auth plugins (scenarios/proxy-api/authentication)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream with the given `auth` block and a route to the mock's
+/// OAuth2-protected resource. Returns the proxy path (without leading slash).
+async fn setup_auth_upstream(
+    h: &AppHarness,
+    guard: &MockGuard,
+    alias: &str,
+    auth: serde_json::Value,
+) -> String {
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "auth": auth,
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/oauth/resource");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    path[1..].to_string()
+}
+
+fn count_requests(recorded: &[RecordedRequest], suffix: &str) -> usize {
+    recorded.iter().filter(|r| r.uri.ends_with(suffix)).count()
+}
+
+// positive-9.3: Basic credentials are built from two secrets.
+#[tokio::test]
+async fn proxy_basic_auth_injects_credentials() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![
+            ("cred://legacy/user".into(), "Aladdin".into()),
+            ("cred://legacy/pass".into(), "open sesame".into()),
+        ])
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    let path = setup_auth_upstream(
+        &h,
+        &guard,
+        "auth-basic",
+        json!({
+            "type": "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.basic.v1",
+            "sharing": "private",
+            "config": {
+                "username_ref": "cred://legacy/user",
+                "password_ref": "cred://legacy/pass"
+            }
+        }),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("auth-basic", &path)
+        .expect_status(200)
+        .await;
+    assert_eq!(
+        resp.json()["authorization"],
+        "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+    );
+}
+
+// positive-9.5: the token is fetched once, cached, and refreshed when the
+// upstream rejects it; the rejected request is retried exactly once.
+#[tokio::test]
+async fn proxy_oauth2_refreshes_token_and_retries_once_on_401() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![
+            ("cred://vendor/client_id".into(), "gateway".into()),
+            ("cred://vendor/client_secret".into(), "s3cret".into()),
+        ])
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    let path = setup_auth_upstream(
+        &h,
+        &guard,
+        "auth-oauth2",
+        json!({
+            "type": "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.oauth2_client_cred.v1",
+            "sharing": "private",
+            "config": {
+                "token_url": guard.url("/oauth/token"),
+                "client_id_ref": "cred://vendor/client_id",
+                "client_secret_ref": "cred://vendor/client_secret",
+                "scope": "read"
+            }
+        }),
+    )
+    .await;
+
+    // tok-1 is rejected by the resource; the gateway refreshes and retries.
+    let resp = h
+        .api_v1()
+        .proxy_get("auth-oauth2", &path)
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json()["authorization"], "Bearer tok-2");
+
+    // The refreshed token is reused.
+    let resp = h
+        .api_v1()
+        .proxy_get("auth-oauth2", &path)
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json()["authorization"], "Bearer tok-2");
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(count_requests(&recorded, "/oauth/token"), 2);
+    assert_eq!(count_requests(&recorded, "/oauth/resource"), 3);
+}
+
+// positive-9.4: a static bearer token cannot be refreshed, so a 401 is
+// passed through without a retry.
+#[tokio::test]
+async fn proxy_bearer_auth_does_not_retry_on_401() {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://api/token".into(), "tok-1".into())])
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    let path = setup_auth_upstream(
+        &h,
+        &guard,
+        "auth-bearer",
+        json!({
+            "type": "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.bearer.v1",
+            "sharing": "private",
+            "config": {"secret_ref": "cred://api/token"}
+        }),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("auth-bearer", &path)
+        .expect_status(401)
+        .await;
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(count_requests(&recorded, "/oauth/resource"), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Custom Starlark plugins (scenarios/plugins)
+// ---------------------------------------------------------------------------
+
+async fn create_plugin(
+    h: &AppHarness,
+    name: &str,
+    plugin_type: &str,
+    config_schema: serde_json::Value,
+    source: &str,
+) -> String {
+    let resp = h
+        .api_v1()
+        .post_plugin()
+        .with_body(json!({
+            "name": name,
+            "plugin_type": plugin_type,
+            "config_schema": config_schema,
+            "source_code": source
+        }))
+        .expect_status(201)
+        .await;
+    resp.json()["id"].as_str().unwrap().to_string()
+}
+
+/// Create an upstream and a GET route to the guarded mock path `/plugins`
+/// with the given plugin layers. `route` is merged into the route body.
+/// Returns the proxy path (without leading slash).
+async fn setup_plugin_route(
+    h: &AppHarness,
+    guard: &mut MockGuard,
+    alias: &str,
+    upstream_plugins: serde_json::Value,
+    route: serde_json::Value,
+) -> String {
+    guard.mock(
+        "GET",
+        "/plugins",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"name": "Ann", "email": "ann@example.com"})),
+        },
+    );
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "plugins": upstream_plugins,
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/plugins");
+    let mut body = json!({
+        "upstream_id": upstream_uuid,
+        "match": {"http": {"methods": ["GET"], "path": path, "query_allowlist": ["page"]}},
+        "enabled": true,
+        "tags": [],
+        "priority": 0
+    });
+    if let (Some(body), Some(extra)) = (body.as_object_mut(), route.as_object()) {
+        body.extend(extra.clone());
+    }
+    h.api_v1()
+        .post_route()
+        .with_body(body)
+        .expect_status(201)
+        .await;
+
+    path[1..].to_string()
+}
+
+fn recorded_header(recorded: &RecordedRequest, name: &str) -> Option<String> {
+    recorded
+        .headers
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.clone())
+}
+
+// 10.4: a guard rejects with its own status and error code.
+#[tokio::test]
+async fn proxy_guard_plugin_rejects_with_its_status_and_code() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let plugin = create_plugin(
+        &h,
+        "require-header",
+        "guard",
+        json!({"properties": {"header": {"type": "string", "default": "x-customer-id"}}}),
+        r#"
+def on_request(ctx):
+    name = ctx.config["header"]
+    if ctx.request.headers.get(name) == None:
+        return ctx.reject(400, "MISSING_HEADER", "missing " + name)
+    return ctx.next()
+"#,
+    )
+    .await;
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-guard",
+        json!({"items": [plugin]}),
+        json!({}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-guard", &path)
+        .expect_status(400)
+        .await;
+    let problem = resp.json();
+    assert_eq!(problem["code"], "MISSING_HEADER");
+    assert_eq!(problem["detail"], "missing x-customer-id");
+    assert!(guard.recorded_requests().await.is_empty());
+
+    h.api_v1()
+        .proxy_get("plugin-guard", &path)
+        .with_header(
+            http::HeaderName::from_static("x-customer-id"),
+            http::HeaderValue::from_static("c-1"),
+        )
+        .expect_status(200)
+        .await;
+}
+
+// 11.6: upstream plugins run before route plugins, guards before transforms
+// within a layer, otherwise in the order listed.
+#[tokio::test]
+async fn proxy_plugins_run_upstream_layer_then_route_layer() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let source = r#"
+def on_request(ctx):
+    order = ctx.request.headers.get("x-chain-order")
+    name = ctx.config["name"]
+    ctx.request.headers.set("x-chain-order", name if order == None else order + "," + name)
+    return ctx.next()
+"#;
+    let mut ids = Vec::new();
+    for (name, plugin_type) in [
+        ("U1", "guard"),
+        ("U2", "transform"),
+        ("R1", "guard"),
+        ("R2", "transform"),
+    ] {
+        let schema = json!({"properties": {"name": {"type": "string", "default": name}}});
+        ids.push(create_plugin(&h, name, plugin_type, schema, source).await);
+    }
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-order",
+        json!({"items": [ids[1], ids[0]]}),
+        json!({"plugins": {"items": [ids[3], ids[2]]}}),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("plugin-order", &path)
+        .expect_status(200)
+        .await;
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(
+        recorded_header(&recorded[0], "x-chain-order").as_deref(),
+        Some("U1,U2,R1,R2")
+    );
+}
+
+// 11.1, 11.2: transforms rewrite the path and add query parameters; the
+// route's query allowlist is checked against the client's query only.
+#[tokio::test]
+async fn proxy_transform_rewrites_path_and_query() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v2/plugins",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"rewritten": true})),
+        },
+    );
+    let plugin = create_plugin(
+        &h,
+        "rewrite",
+        "transform",
+        json!({}),
+        r#"
+def on_request(ctx):
+    ctx.request.set_path(ctx.config["path_prefix"] + "/plugins")
+    ctx.request.add_query("api-version", "2")
+    ctx.request.headers.remove("x-debug")
+    return ctx.next()
+"#,
+    )
+    .await;
+    let path_prefix = guard.path("/v2");
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-rewrite",
+        json!({}),
+        json!({"plugins": {
+            "items": [plugin],
+            "config": {(plugin.clone()): {"path_prefix": path_prefix}}
+        }}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-rewrite", &path)
+        .with_query("page", "3")
+        .with_header(
+            http::HeaderName::from_static("x-debug"),
+            http::HeaderValue::from_static("1"),
+        )
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json()["rewritten"], true);
+    let recorded = guard.recorded_requests().await;
+    let (path_part, query) = recorded[0].uri.split_once('?').unwrap();
+    assert_eq!(path_part, guard.path("/v2/plugins"));
+    assert!(query.contains("page=3"));
+    assert!(query.contains("api-version=2"));
+    assert_eq!(recorded_header(&recorded[0], "x-debug"), None);
+
+    h.api_v1()
+        .proxy_get("plugin-rewrite", &path)
+        .with_query("api-version", "2")
+        .expect_status(400)
+        .await;
+}
+
+// 11.4: on_response redacts fields of a JSON body.
+#[tokio::test]
+async fn proxy_on_response_redacts_json_body() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let plugin = create_plugin(
+        &h,
+        "redact",
+        "transform",
+        json!({"properties": {"fields": {"type": "array", "default": ["email"]}}}),
+        r#"
+def on_response(ctx):
+    data = ctx.response.json()
+    for field in ctx.config["fields"]:
+        if field in data:
+            data[field] = "[REDACTED]"
+    ctx.response.set_json(data)
+    return ctx.next()
+"#,
+    )
+    .await;
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-redact",
+        json!({"items": [plugin]}),
+        json!({}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-redact", &path)
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json(), json!({"name": "Ann", "email": "[REDACTED]"}));
+}
+
+// 11.5, 11.7: on_error answers a gateway error; respond short-circuits the
+// upstream call.
+#[tokio::test]
+async fn proxy_plugins_respond_on_request_and_on_error() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let plugin = create_plugin(
+        &h,
+        "answer",
+        "transform",
+        json!({}),
+        r#"
+def on_request(ctx):
+    if ctx.request.headers.get("x-cached") != None:
+        return ctx.respond(200, {"cached": True})
+    return ctx.next()
+
+def on_error(ctx):
+    if not ctx.error.upstream and ctx.error.status == 400:
+        return ctx.respond(400, {"error": "bad_request", "message": ctx.error.message})
+    return ctx.next()
+"#,
+    )
+    .await;
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-respond",
+        json!({}),
+        json!({"plugins": {"items": [plugin]}}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-respond", &path)
+        .with_header(
+            http::HeaderName::from_static("x-cached"),
+            http::HeaderValue::from_static("1"),
+        )
+        .expect_status(200)
+        .await;
+    resp.assert_header("content-type", "application/json");
+    assert_eq!(resp.json(), json!({"cached": true}));
+    assert!(guard.recorded_requests().await.is_empty());
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-respond", &path)
+        .with_query("debug", "1")
+        .expect_status(400)
+        .await;
+    let json = resp.json();
+    assert_eq!(json["error"], "bad_request");
+    assert!(
+        json["message"]
+            .as_str()
+            .unwrap()
+            .contains("query_allowlist")
+    );
+}
+
+// 4.5: a custom plugin that no longer exists fails the call with 503;
+// builtin plugins need no stored definition.
+#[tokio::test]
+async fn proxy_missing_custom_plugin_returns_503() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let missing = format!(
+        "gts.x.core.oagw.transform_plugin.v1~{}",
+        uuid::Uuid::new_v4().simple()
+    );
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-missing",
+        json!({"items": ["gts.x.core.oagw.guard_plugin.v1~x.core.oagw.cors.v1"]}),
+        json!({"plugins": {"items": [missing]}}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-missing", &path)
+        .expect_status(503)
+        .await;
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.plugin.not_found.v1"
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// negative-11.8: a plugin exceeding its step budget fails the call with 503.
+#[tokio::test]
+async fn proxy_plugin_exceeding_sandbox_limits_returns_503() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let plugin = create_plugin(
+        &h,
+        "spin",
+        "guard",
+        json!({}),
+        "def on_request(ctx):\n    for i in range(100000000):\n        pass\n    return ctx.next()\n",
+    )
+    .await;
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "plugin-spin",
+        json!({"items": [plugin]}),
+        json!({}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("plugin-spin", &path)
+        .expect_status(503)
+        .await;
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.plugin.failed.v1"
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Builtin CORS and timeout guards (scenarios/plugins/guards)
+// ---------------------------------------------------------------------------
+
+fn cors_plugins(config: serde_json::Value) -> serde_json::Value {
+    json!({"items": [CORS_GUARD_PLUGIN_ID], "config": {CORS_GUARD_PLUGIN_ID: config}})
+}
+
+// positive-10.2: a preflight is answered by the gateway from the CORS guard
+// config, without calling the upstream.
+#[tokio::test]
+async fn proxy_cors_guard_answers_preflight_locally() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "cors-preflight",
+        cors_plugins(json!({
+            "allowed_origins": ["https://app.example.com"],
+            "allowed_methods": ["GET", "POST"],
+            "allowed_headers": ["Content-Type", "Authorization"],
+            "max_age": 3600,
+            "allow_credentials": true
+        })),
+        json!({}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy(Method::OPTIONS, "cors-preflight", &path)
+        .with_header(
+            http::header::ORIGIN,
+            http::HeaderValue::from_static("https://app.example.com"),
+        )
+        .with_header(
+            http::header::ACCESS_CONTROL_REQUEST_METHOD,
+            http::HeaderValue::from_static("GET"),
+        )
+        .with_header(
+            http::header::ACCESS_CONTROL_REQUEST_HEADERS,
+            http::HeaderValue::from_static("Content-Type, Authorization"),
+        )
+        .expect_status(204)
+        .await;
+    resp.assert_header("access-control-allow-origin", "https://app.example.com")
+        .assert_header("access-control-allow-methods", "GET, POST")
+        .assert_header(
+            "access-control-allow-headers",
+            "Content-Type, Authorization",
+        )
+        .assert_header("access-control-max-age", "3600")
+        .assert_header("access-control-allow-credentials", "true")
+        .assert_header("vary", "Origin");
+
+    let resp = h
+        .api_v1()
+        .proxy(Method::OPTIONS, "cors-preflight", &path)
+        .with_header(
+            http::header::ORIGIN,
+            http::HeaderValue::from_static("https://app.example.com"),
+        )
+        .with_header(
+            http::header::ACCESS_CONTROL_REQUEST_METHOD,
+            http::HeaderValue::from_static("DELETE"),
+        )
+        .expect_status(403)
+        .await;
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.cors.method_not_allowed.v1"
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// Cross-origin requests from allowed origins get the CORS response headers;
+// other origins are refused before reaching the upstream.
+#[tokio::test]
+async fn proxy_cors_guard_checks_origin_of_actual_requests() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_plugin_route(
+        &h,
+        &mut guard,
+        "cors-actual",
+        cors_plugins(json!({
+            "allowed_origins": ["https://app.example.com"],
+            "expose_headers": ["X-Request-ID"]
+        })),
+        json!({}),
+    )
+    .await;
+
+    let resp = h
+        .api_v1()
+        .proxy_get("cors-actual", &path)
+        .with_header(
+            http::header::ORIGIN,
+            http::HeaderValue::from_static("https://evil.com"),
+        )
+        .expect_status(403)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.cors.origin_not_allowed.v1"
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+
+    let resp = h
+        .api_v1()
+        .proxy_get("cors-actual", &path)
+        .with_header(
+            http::header::ORIGIN,
+            http::HeaderValue::from_static("https://app.example.com"),
+        )
+        .expect_status(200)
+        .await;
+    resp.assert_header("access-control-allow-origin", "https://app.example.com")
+        .assert_header("access-control-expose-headers", "X-Request-ID")
+        .assert_header("vary", "Origin");
+    assert!(
+        !resp
+            .headers()
+            .contains_key("access-control-allow-credentials")
+    );
+
+    // Requests without Origin are not cross-origin and pass untouched.
+    let resp = h
+        .api_v1()
+        .proxy_get("cors-actual", &path)
+        .expect_status(200)
+        .await;
+    assert!(!resp.headers().contains_key("access-control-allow-origin"));
+}
+
+// negative-10.3: credentials cannot be combined with the wildcard origin.
+#[tokio::test]
+async fn cors_guard_rejects_credentials_with_wildcard_origin() {
+    let h = AppHarness::builder().build().await;
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "cors-wildcard",
+            "plugins": cors_plugins(json!({"allowed_origins": ["*"], "allow_credentials": true})),
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(400)
+        .await;
+    let detail = resp.json()["detail"].as_str().unwrap().to_string();
+    assert!(
+        detail.contains("allow_credentials cannot be used with the wildcard origin"),
+        "{detail}"
+    );
+}
+
+// negative-10.1: the timeout guard fails a call whose upstream does not
+// answer within the route's first-byte timeout.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn proxy_timeout_guard_returns_504() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let _gate = guard.mock_gated(
+        "GET",
+        "/slow",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "guard-timeout",
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+    let path = guard.path("/slow");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": path}},
+            "plugins": {
+                "items": [TIMEOUT_GUARD_PLUGIN_ID],
+                "config": {TIMEOUT_GUARD_PLUGIN_ID: {"first_byte_timeout": "100ms"}}
+            },
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    let started = std::time::Instant::now();
+    let resp = h
+        .api_v1()
+        .proxy_get("guard-timeout", &path[1..])
+        .expect_status(504)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.timeout.request.v1"
+    );
+    // Well before the gateway-wide request timeout.
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+}
+
+// ---------------------------------------------------------------------------
+// Streaming request bodies (scenarios/proxy-api/body-validation)
+// ---------------------------------------------------------------------------
+
+/// Create an upstream and a POST route to the guarded mock path `/upload`.
+/// Returns the proxy path (without leading slash).
+async fn setup_upload_route(h: &AppHarness, guard: &mut MockGuard, alias: &str) -> String {
+    guard.mock(
+        "POST",
+        "/upload",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"stored": true})),
+        },
+    );
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": alias,
+            "enabled": true,
+            "tags": []
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let path = guard.path("/upload");
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["POST"], "path": path}},
+            "enabled": true,
+            "tags": [],
+            "priority": 0
+        }))
+        .expect_status(201)
+        .await;
+
+    path[1..].to_string()
+}
+
+fn upload_chunks(count: usize, size: usize) -> Vec<bytes::Bytes> {
+    (0..count)
+        .map(|i| bytes::Bytes::from(vec![b'a' + i as u8; size]))
+        .collect()
+}
+
+// 8.3: a chunked upload is forwarded as a chunked stream.
+#[tokio::test]
+async fn proxy_streams_chunked_request_body() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_upload_route(&h, &mut guard, "stream-chunked").await;
+
+    let chunks = upload_chunks(4, 1024);
+    let resp = h
+        .api_v1()
+        .proxy_post("stream-chunked", &path)
+        .with_header(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/octet-stream"),
+        )
+        .with_chunked_body(chunks.clone())
+        .expect_status(200)
+        .await;
+    assert_eq!(resp.json()["stored"], true);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].body, chunks.concat());
+    assert!(
+        recorded[0]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "transfer-encoding" && v == "chunked")
+    );
+    assert!(
+        !recorded[0]
+            .headers
+            .iter()
+            .any(|(k, _)| k == "content-length")
+    );
+}
+
+// 8.3: a streamed body of known length keeps its Content-Length upstream.
+#[tokio::test]
+async fn proxy_streamed_body_keeps_content_length() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_upload_route(&h, &mut guard, "stream-sized").await;
+
+    h.api_v1()
+        .proxy_post("stream-sized", &path)
+        .with_body("0123456789")
+        .expect_status(200)
+        .await;
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded[0].body, b"0123456789");
+    assert!(
+        recorded[0]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "content-length" && v == "10")
+    );
+}
+
+// 8.3: max_body_size_bytes is enforced while the body streams, also when
+// no Content-Length announces the size up front.
+#[tokio::test]
+async fn proxy_chunked_body_over_limit_returns_413() {
+    let h = AppHarness::builder().with_max_body_size(2048).build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_upload_route(&h, &mut guard, "stream-limit").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("stream-limit", &path)
+        .with_chunked_body(upload_chunks(4, 1024))
+        .expect_status(413)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    assert_eq!(
+        resp.json()["type"],
+        "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1"
+    );
+
+    h.api_v1()
+        .proxy_post("stream-limit", &path)
+        .with_chunked_body(upload_chunks(2, 1024))
+        .expect_status(200)
+        .await;
+}
+
+// 8.3: a body known to exceed max_body_size_bytes is refused before the
+// upstream is contacted.
+#[tokio::test]
+async fn proxy_sized_body_over_limit_returns_413_without_upstream_call() {
+    let h = AppHarness::builder().with_max_body_size(8).build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_upload_route(&h, &mut guard, "stream-sized-limit").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("stream-sized-limit", &path)
+        .with_body("0123456789")
+        .expect_status(413)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// negative-8.2: only chunked transfer encoding is accepted.
+#[tokio::test]
+async fn proxy_unsupported_transfer_encoding_returns_400() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_upload_route(&h, &mut guard, "stream-te").await;
+
+    let resp = h
+        .api_v1()
+        .proxy_post("stream-te", &path)
+        .with_body(json!({"a": 1}))
+        .with_header(
+            http::header::TRANSFER_ENCODING,
+            http::HeaderValue::from_static("gzip"),
+        )
+        .expect_status(400)
+        .await;
+    resp.assert_header("x-oagw-error-source", "gateway");
+    let detail = resp.json()["detail"].as_str().unwrap().to_string();
+    assert!(detail.contains("unsupported transfer encoding"), "{detail}");
+    assert!(guard.recorded_requests().await.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Response caching
+// ---------------------------------------------------------------------------
+
+/// Upstream on the shared mock with one cached GET route on `/v1/models`.
+/// Returns the route id.
+async fn setup_cached_route(h: &AppHarness, guard: &MockGuard, alias: &str) -> uuid::Uuid {
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias(alias)
+            .build(),
+        )
+        .await
+        .unwrap();
+    let route = h
+        .facade()
+        .create_route(
+            ctx,
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/models"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .response_cache(ResponseCacheConfig {
+                ttl: std::time::Duration::from_secs(60),
+                vary: vec![],
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+    route.id
+}
+
+fn models_response(cache_headers: &[(&str, &str)]) -> MockResponse {
+    MockResponse {
+        status: 200,
+        headers: cache_headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        body: MockBody::Json(json!({"data": [{"id": "gpt-4"}]})),
+    }
+}
+
+/// GET the cached route; returns status, `x-oagw-cache` and body.
+async fn get_models(
+    h: &AppHarness,
+    guard: &MockGuard,
+    alias: &str,
+) -> (StatusCode, String, bytes::Bytes) {
+    let req = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!("/{alias}{}", guard.path("/v1/models")))
+        .body(Body::Empty)
+        .unwrap();
+    let resp = h
+        .facade()
+        .proxy_request(h.security_context().clone(), req)
+        .await
+        .unwrap();
+    let status = resp.status();
+    let cache = resp
+        .headers()
+        .get("x-oagw-cache")
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let body = resp.into_body().into_bytes().await.unwrap();
+    (status, cache, body)
+}
+
+// A fresh cached response is served without calling the upstream.
+#[tokio::test]
+async fn proxy_serves_fresh_cached_response() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/models",
+        models_response(&[("cache-control", "max-age=60")]),
+    );
+    setup_cached_route(&h, &guard, "cache-hit").await;
+
+    let (status, cache, first) = get_models(&h, &guard, "cache-hit").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cache, "miss");
+    let (status, cache, second) = get_models(&h, &guard, "cache-hit").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cache, "hit");
+    assert_eq!(first, second);
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+// A stale entry is revalidated with If-None-Match and renewed by a 304.
+#[tokio::test]
+async fn proxy_revalidates_stale_cached_response() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    let validators = [("cache-control", "no-cache"), ("etag", "\"v1\"")];
+    guard.mock("GET", "/v1/models", models_response(&validators));
+    setup_cached_route(&h, &guard, "cache-revalidate").await;
+
+    let (_, cache, first) = get_models(&h, &guard, "cache-revalidate").await;
+    assert_eq!(cache, "miss");
+
+    guard.mock(
+        "GET",
+        "/v1/models",
+        MockResponse {
+            status: 304,
+            headers: validators
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            body: MockBody::Text(String::new()),
+        },
+    );
+    let (status, cache, second) = get_models(&h, &guard, "cache-revalidate").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cache, "revalidated");
+    assert_eq!(first, second);
+
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(recorded.len(), 2);
+    assert!(
+        recorded[1]
+            .headers
+            .iter()
+            .any(|(k, v)| k == "if-none-match" && v == "\"v1\"")
+    );
+}
+
+// Cache-Control: no-store keeps a response out of the cache.
+#[tokio::test]
+async fn proxy_does_not_cache_no_store_responses() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/models",
+        models_response(&[("cache-control", "no-store")]),
+    );
+    setup_cached_route(&h, &guard, "cache-no-store").await;
+
+    for _ in 0..2 {
+        let (_, cache, _) = get_models(&h, &guard, "cache-no-store").await;
+        assert_eq!(cache, "miss");
+    }
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// Updating the route drops the responses cached for it.
+#[tokio::test]
+async fn proxy_route_update_invalidates_cached_responses() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/v1/models",
+        models_response(&[("cache-control", "max-age=60")]),
+    );
+    let route_id = setup_cached_route(&h, &guard, "cache-invalidate").await;
+
+    get_models(&h, &guard, "cache-invalidate").await;
+    h.facade()
+        .update_route(
+            h.security_context().clone(),
+            route_id,
+            UpdateRouteRequest::builder().priority(1).build(),
+        )
+        .await
+        .unwrap();
+
+    let (_, cache, _) = get_models(&h, &guard, "cache-invalidate").await;
+    assert_eq!(cache, "miss");
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Usage metering
+// ---------------------------------------------------------------------------
+
+// Proxied requests are metered per route, including upstream-reported tokens.
+#[tokio::test]
+async fn proxy_usage_is_reported_per_route() {
+    let h = AppHarness::builder().build().await;
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/v1/chat/completions",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({
+                "id": "chatcmpl-1",
+                "usage": {"prompt_tokens": 12, "completion_tokens": 30},
+            })),
+        },
+    );
+    let ctx = h.security_context().clone();
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("usage-metered")
+            .build(),
+        )
+        .await
+        .unwrap();
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path: guard.path("/v1/chat/completions"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .usage_extraction(UsageExtractionConfig {
+                input_tokens: Some("/usage/prompt_tokens".into()),
+                output_tokens: Some("/usage/completion_tokens".into()),
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let req = http::Request::builder()
+            .method(Method::POST)
+            .uri(format!(
+                "/usage-metered{}",
+                guard.path("/v1/chat/completions")
+            ))
+            .body(Body::from(r#"{"model":"gpt-4"}"#))
+            .unwrap();
+        let resp = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        resp.into_body().into_bytes().await.unwrap();
+    }
+
+    let resp = h
+        .api_v1()
+        .get_usage()
+        .with_query("group_by", "subject")
+        .expect_status(200)
+        .await;
+    let usage = resp.json();
+    let usage = usage.as_array().unwrap();
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0]["subject_id"], json!(ctx.subject_id()));
+    assert_eq!(usage[0]["requests"], 2);
+    assert_eq!(usage[0]["errors"], 0);
+    assert_eq!(usage[0]["request_bytes"], 34);
+    assert_eq!(usage[0]["input_tokens"], 24);
+    assert_eq!(usage[0]["output_tokens"], 60);
+}
+
+// ---------------------------------------------------------------------------
+// Tenant hierarchy (scenarios/hierarchy)
+// ---------------------------------------------------------------------------
+
+/// Bind `alias` for the harness tenant to the mock server, with `extra`
+/// merged into the upstream body.
+async fn bind_alias(h: &AppHarness, alias: &str, extra: serde_json::Value) {
+    let mut body = json!({
+        "server": {
+            "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+        },
+        "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+        "alias": alias,
+        "enabled": true,
+        "tags": []
+    });
+    if let (Some(body), Some(extra)) = (body.as_object_mut(), extra.as_object()) {
+        body.extend(extra.clone());
+    }
+    h.api_v1()
+        .post_upstream()
+        .with_body(body)
+        .expect_status(201)
+        .await;
+}
+
+fn apikey_auth(secret_ref: &str, sharing: &str) -> serde_json::Value {
+    json!({
+        "type": APIKEY_AUTH_PLUGIN_ID,
+        "sharing": sharing,
+        "config": {"header": "authorization", "prefix": "Bearer ", "secret_ref": secret_ref}
+    })
+}
+
+// 4.6, 9.8: plugins listed by an ancestor's upstream and route run for its
+// descendants, although they belong to the ancestor.
+#[tokio::test]
+async fn proxy_runs_plugins_owned_by_ancestor() {
+    let h = AppHarness::builder().with_parent_tenant().build().await;
+    let mut guard = MockGuard::new();
+    let source = r#"
+def on_request(ctx):
+    order = ctx.request.headers.get("x-chain-order")
+    name = ctx.config["name"]
+    ctx.request.headers.set("x-chain-order", name if order == None else order + "," + name)
+    return ctx.next()
+"#;
+    let mut ids = Vec::new();
+    for name in ["partner-upstream", "partner-route"] {
+        let schema = json!({"properties": {"name": {"type": "string", "default": name}}});
+        ids.push(create_plugin(h.parent(), name, "transform", schema, source).await);
+    }
+    let path = setup_plugin_route(
+        h.parent(),
+        &mut guard,
+        "inherited-plugins",
+        json!({"items": [ids[0]], "sharing": "inherit"}),
+        json!({"plugins": {"items": [ids[1]]}}),
+    )
+    .await;
+
+    h.api_v1()
+        .proxy_get("inherited-plugins", &path)
+        .expect_status(200)
+        .await;
+    let recorded = guard.recorded_requests().await;
+    assert_eq!(
+        recorded_header(&recorded[0], "x-chain-order").as_deref(),
+        Some("partner-upstream,partner-route")
+    );
+}
+
+// 18.6: an enforced ancestor limit is one budget shared with descendants,
+// even when they bind the alias to their own upstream.
+#[tokio::test]
+async fn proxy_enforced_rate_limit_is_shared_with_descendants() {
+    let h = AppHarness::builder().with_parent_tenant().build().await;
+    let mut guard = MockGuard::new();
+    let path = setup_rate_limited(
+        h.parent(),
+        &mut guard,
+        "enforced-limit",
+        json!({
+            "sharing": "enforce",
+            "algorithm": "sliding_window",
+            "sustained": {"rate": 1, "window": "minute"},
+            "strategy": "reject"
+        }),
+    )
+    .await;
+    bind_alias(&h, "enforced-limit", json!({})).await;
+
+    h.parent()
+        .api_v1()
+        .proxy_get("enforced-limit", &path)
+        .expect_status(200)
+        .await;
+    h.api_v1()
+        .proxy_get("enforced-limit", &path)
+        .expect_status(429)
+        .await;
+}
+
+// 6.1, 6.2: a descendant overrides inherited credentials with its own, but
+// not enforced ones.
+#[tokio::test]
+async fn proxy_descendant_auth_overrides_only_inherited_auth() {
+    let h = AppHarness::builder()
+        .with_parent_tenant()
+        .with_credentials(vec![
+            ("cred://partner-key".into(), "partner".into()),
+            ("cred://customer-key".into(), "customer".into()),
+        ])
+        .build()
+        .await;
+    let guard = MockGuard::new();
+    for (alias, sharing, expected) in [
+        ("auth-inherited", "inherit", "Bearer customer"),
+        ("auth-enforced", "enforce", "Bearer partner"),
+    ] {
+        let path = setup_auth_upstream(
+            h.parent(),
+            &guard,
+            alias,
+            apikey_auth("cred://partner-key", sharing),
+        )
+        .await;
+        bind_alias(
+            &h,
+            alias,
+            json!({"auth": apikey_auth("cred://customer-key", "private")}),
+        )
+        .await;
+
+        let resp = h.api_v1().proxy_get(alias, &path).expect_status(200).await;
+        assert_eq!(resp.json()["authorization"], expected, "{alias}");
+    }
+}